@@ -12,6 +12,11 @@ pub const AD_METADATA_SYMBOL: &str = "ADP";
 // METADATA_URI    : max 200 bytes
 pub const AD_METADATA_URI: &str = "<TEST_FIELD>";
 
+// Metadata of the collection NFT grouping the positions of a pool
+pub const ADC_METADATA_NAME: &str = "Ai Dex Positions";
+pub const ADC_METADATA_SYMBOL: &str = "ADPC";
+pub const ADC_METADATA_URI: &str = "<TEST_FIELD>";
+
 // pub const ADB_METADATA_NAME_PREFIX: &str = "Ai Dex Position TradeBatch";
 pub const ADB_METADATA_SYMBOL: &str = "ADPB";
 pub const ADB_METADATA_URI: &str =
@@ -3,3 +3,10 @@ pub const TRANSFER_MEMO_COLLECT_FEES: &str = "Ai Dex CollectFees";
 pub const TRANSFER_MEMO_COLLECT_REWARD: &str = "Ai Dex CollectReward";
 pub const TRANSFER_MEMO_DECREASE_LIQUIDITY: &str = "Ai Dex Withdraw";
 pub const TRANSFER_MEMO_SWAP: &str = "Ai Dex Trade";
+pub const TRANSFER_MEMO_CANCEL_LIMIT_ORDER: &str = "Ai Dex CancelLimitOrder";
+pub const TRANSFER_MEMO_CLOSE_DCA_SCHEDULE: &str = "Ai Dex CloseDcaSchedule";
+pub const TRANSFER_MEMO_CLOSE_TWAP_ORDER: &str = "Ai Dex CloseTwapOrder";
+pub const TRANSFER_MEMO_COLLECT_REFERRAL_FEES: &str = "Ai Dex CollectReferralFees";
+pub const TRANSFER_MEMO_CLAWBACK_REWARD: &str = "Ai Dex ClawbackReward";
+pub const TRANSFER_MEMO_SKIM_EXCESS: &str = "Ai Dex SkimExcess";
+pub const TRANSFER_MEMO_WITHDRAW_VAULT: &str = "Ai Dex WithdrawVault";
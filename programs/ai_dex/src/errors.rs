@@ -115,6 +115,198 @@ pub enum ErrorCode {
     DuplicateAccountTypesError, // 0x17a5 (6053)
     #[msg("Only full-range positions are supported in this pool.")]
     FullRangeOnlyPoolError, // 0x17a6 (6054)
+    #[msg("Pool fee rates are frozen.")]
+    FeeRatesFrozenError, // 0x17a7 (6055)
+    #[msg("TWAP window must be greater than zero.")]
+    InvalidTwapWindowError, // 0x17a8 (6056)
+    #[msg("Oracle does not have observations old enough for the requested window.")]
+    OracleObservationTooOldError, // 0x17a9 (6057)
+    #[msg("Invalid multi-hop route length.")]
+    InvalidRouteLengthError, // 0x17aa (6058)
+    #[msg("Invalid account in multi-hop route.")]
+    InvalidRouteAccountError, // 0x17ab (6059)
+    #[msg("Duplicate pool in multi-hop route.")]
+    DuplicateRoutePoolError, // 0x17ac (6060)
+    #[msg("Transaction deadline has passed.")]
+    TransactionExpiredError, // 0x17ad (6061)
+    #[msg("Swap was only partially filled.")]
+    PartialFillError, // 0x17ae (6062)
+    #[msg("Invalid number of tick arrays to initialize.")]
+    InvalidTickArrayBatchLengthError, // 0x17af (6063)
+    #[msg("Tick array account does not match its start tick index.")]
+    InvalidTickArrayAccountError, // 0x17b0 (6064)
+    #[msg("Dynamic tick array must be resized to initialize or uninitialize a tick.")]
+    DynamicTickArrayResizeError, // 0x17b1 (6065)
+    #[msg("Too many supplemental tick arrays provided.")]
+    TooManySupplementalTickArraysError, // 0x17b2 (6066)
+    #[msg("Reward accounts do not match the initialized rewards of the pool.")]
+    InvalidRewardAccountsError, // 0x17b3 (6067)
+    #[msg("Swap amount exceeds the zap input amount.")]
+    ZapSwapAmountExceedsInputError, // 0x17b4 (6068)
+    #[msg("Liquidity deposited is below the minimum.")]
+    LiquidityBelowMinimumError, // 0x17b5 (6069)
+    #[msg("Position must have no liquidity to change its tick range.")]
+    NonZeroLiquidityRangeChangeError, // 0x17b6 (6070)
+    #[msg("Positions must be distinct and share the same pool and tick range to be merged.")]
+    IncompatiblePositionsMergeError, // 0x17b7 (6071)
+    #[msg("Position lock must end in the future and cannot be shortened.")]
+    InvalidPositionLockError, // 0x17b8 (6072)
+    #[msg("Position liquidity is locked.")]
+    PositionLockedError, // 0x17b9 (6073)
+    #[msg("Position operator can only withdraw to accounts of the position owner.")]
+    InvalidOperatorDestinationError, // 0x17ba (6074)
+    #[msg("Invalid agent strategy constraints.")]
+    InvalidAgentStrategyError, // 0x17bb (6075)
+    #[msg("Agent rebalanced the position too recently.")]
+    AgentRebalanceTooFrequentError, // 0x17bc (6076)
+    #[msg("Range is wider than the agent strategy allows.")]
+    AgentTickWidthExceededError, // 0x17bd (6077)
+    #[msg("Pool price is too far from its TWAP for the agent strategy.")]
+    AgentSlippageExceededError, // 0x17be (6078)
+    #[msg("Range order position must be single-sided.")]
+    InvalidRangeOrderError, // 0x17bf (6079)
+    #[msg("Range order bounty exceeds the maximum.")]
+    InvalidRangeOrderBountyError, // 0x17c0 (6080)
+    #[msg("Price has not fully crossed the range order.")]
+    RangeOrderNotFilledError, // 0x17c1 (6081)
+    #[msg("Fill exceeds the open amount of the limit order.")]
+    LimitOrderFillExceedsAmountError, // 0x17c2 (6082)
+    #[msg("Invalid trigger order price conditions or bounty.")]
+    InvalidTriggerOrderError, // 0x17c3 (6083)
+    #[msg("Pool price has not crossed a trigger of the order.")]
+    TriggerOrderNotTriggeredError, // 0x17c4 (6084)
+    #[msg("DCA schedule amount per interval and interval must be greater than zero.")]
+    InvalidDcaScheduleError, // 0x17c5 (6085)
+    #[msg("DCA schedule interval has not elapsed since the last execution.")]
+    DcaIntervalNotElapsedError, // 0x17c6 (6086)
+    #[msg("TWAP order must have at least one tranche of at least one token and one second.")]
+    InvalidTwapOrderError, // 0x17c7 (6087)
+    #[msg("Next tranche of the TWAP order is not due yet.")]
+    TwapTrancheNotDueError, // 0x17c8 (6088)
+    #[msg("Position accounts are missing, too many, or do not belong to the pool.")]
+    InvalidPositionAccountsError, // 0x17c9 (6089)
+    #[msg("Invalid trade batch position indexes, tick ranges or accounts.")]
+    InvalidTradeBatchPositionsError, // 0x17ca (6090)
+    #[msg("Trade batch extension is required for this trade batch index or to delete the position trade batch.")]
+    TradeBatchExtensionRequiredError, // 0x17cb (6091)
+    #[msg("Position NFT name and symbol must not be empty, and name, symbol and URI must fit the Metaplex limits.")]
+    InvalidNftMetadataError, // 0x17cc (6092)
+    #[msg("Position collection mint, metadata and master edition must be provided together.")]
+    InvalidPositionCollectionAccountsError, // 0x17cd (6093)
+    #[msg("Native SOL handling requires only the system program and a pool with a wSOL token.")]
+    InvalidNativeSolAccountsError, // 0x17ce (6094)
+    #[msg("Token mint is paused, its transfers are disabled.")]
+    TokenMintPausedError, // 0x17cf (6095)
+    #[msg("Transfer hook program of the token mint is not allowlisted by its token wrapper.")]
+    TransferHookProgramNotAllowedError, // 0x17d0 (6096)
+    #[msg("Pool is paused, swaps and liquidity increases are disabled.")]
+    PoolPausedError, // 0x17d1 (6097)
+    #[msg("Protocol is paused by the emergency authority.")]
+    ProtocolPausedError, // 0x17d2 (6098)
+    #[msg("Swap moves the price further than the circuit breaker of the pool allows.")]
+    CircuitBreakerTrippedError, // 0x17d3 (6099)
+    #[msg("Circuit breaker window must be non-zero when the maximum tick move is set.")]
+    InvalidCircuitBreakerError, // 0x17d4 (6100)
+    #[msg("Swap exceeds the maximum price impact of the pool.")]
+    PriceImpactExceededError, // 0x17d5 (6101)
+    #[msg("Swap takes more out of a vault within the slot than the pool allows.")]
+    SlotVolumeExceededError, // 0x17d6 (6102)
+    #[msg("Swap reverses a swap of the same authority within the slot.")]
+    SandwichSwapError, // 0x17d7 (6103)
+    #[msg("Pool is not open for swaps yet.")]
+    PoolNotOpenError, // 0x17d8 (6104)
+    #[msg("Pool is already open, its open time cannot be changed.")]
+    PoolAlreadyOpenError, // 0x17d9 (6105)
+    #[msg("Pool allowlist has no free slot left.")]
+    PoolAllowlistFullError, // 0x17da (6106)
+    #[msg("Authority is not on the allowlist of the pool and holds no gating token.")]
+    AuthorityNotAllowlistedError, // 0x17db (6107)
+    #[msg("Pool creation fee must have a destination when it is non-zero.")]
+    InvalidPoolCreationFeeError, // 0x17dc (6108)
+    #[msg("Pool creation fee accounts are missing or do not match the config.")]
+    PoolCreationFeeAccountsError, // 0x17dd (6109)
+    #[msg("Fee distribution recipients are invalid or their weights do not sum up to 10000 bps.")]
+    InvalidFeeDistributionError, // 0x17de (6110)
+    #[msg("Fee recipient token accounts are missing or do not match the fee distribution.")]
+    FeeRecipientAccountsError, // 0x17df (6111)
+    #[msg("Referral fee rate exceeds the maximum referral fee rate.")]
+    ReferralFeeRateExceededError, // 0x17e0 (6112)
+    #[msg("Swap cannot be referred by its own token authority.")]
+    SelfReferralError, // 0x17e1 (6113)
+    #[msg("Fee discount tiers are invalid.")]
+    InvalidFeeDiscountTiersError, // 0x17e2 (6114)
+    #[msg("Volume fee tiers are invalid.")]
+    InvalidVolumeFeeTiersError, // 0x17e3 (6115)
+    #[msg("Adaptive fee bounds are invalid.")]
+    InvalidAdaptiveFeeError, // 0x17e4 (6116)
+    #[msg("Adaptive fee requires the oracle of the pool to be initialized.")]
+    AdaptiveFeeOracleError, // 0x17e5 (6117)
+    #[msg("Launch fee schedule is invalid.")]
+    InvalidLaunchFeeError, // 0x17e6 (6118)
+    #[msg("Signer is neither the fee authority nor the fee manager of the pool.")]
+    FeeManagerError, // 0x17e7 (6119)
+    #[msg("Invalid number of fee tiers to initialize.")]
+    InvalidFeeTierBatchLengthError, // 0x17e8 (6120)
+    #[msg("Fee tier account does not match its tick spacing.")]
+    InvalidFeeTierAccountError, // 0x17e9 (6121)
+    #[msg("Emissions end timestamp must be in the future.")]
+    InvalidEmissionsEndTimestampError, // 0x17ea (6122)
+    #[msg("Emission schedule segments are invalid.")]
+    InvalidEmissionScheduleError, // 0x17eb (6123)
+    #[msg("Reward boost must be zero or between 1x and the maximum reward boost.")]
+    InvalidRewardBoostError, // 0x17ec (6124)
+    #[msg("Reward emissions have no end or have not ended yet.")]
+    RewardEmissionsNotEndedError, // 0x17ed (6125)
+    #[msg("Vault reserves of the pool are not tracked.")]
+    VaultReservesNotTrackedError, // 0x17ee (6126)
+    #[msg("Pool is not in emergency mode.")]
+    EmergencyModeDisabledError, // 0x17ef (6127)
+    #[msg("LP tokens are only supported on full range only pools.")]
+    NotFullRangeOnlyPoolError, // 0x17f0 (6128)
+    #[msg("Amount of LP tokens must be greater than zero.")]
+    ZeroLpTokenAmountError, // 0x17f1 (6129)
+    #[msg("Vault already holds the maximum number of positions.")]
+    VaultPositionLimitError, // 0x17f2 (6130)
+    #[msg("Signer is neither the strategy authority nor the agent of the vault.")]
+    VaultRebalanceAuthorityError, // 0x17f3 (6131)
+    #[msg("Accounts do not match the positions of the vault.")]
+    InvalidVaultPositionAccountsError, // 0x17f4 (6132)
+    #[msg("Amount of vault shares must be greater than zero.")]
+    ZeroVaultShareAmountError, // 0x17f5 (6133)
+    #[msg("A flash swap of the pool awaits repayment.")]
+    FlashSwapInProgressError, // 0x17f6 (6134)
+    #[msg("No flash swap of the pool awaits repayment.")]
+    NoFlashSwapInProgressError, // 0x17f7 (6135)
+    #[msg("Flash swap is not repaid by a later instruction of the transaction.")]
+    FlashSwapRepaymentMissingError, // 0x17f8 (6136)
+    #[msg("Swap hook program of the pool is missing.")]
+    SwapHookProgramMissingError, // 0x17f9 (6137)
+    #[msg("Swap permit is not signed by the token authority.")]
+    InvalidSwapPermitSignatureError, // 0x17fa (6138)
+    #[msg("Swap permit nonce is not the current nonce of the token authority.")]
+    InvalidSwapPermitNonceError, // 0x17fb (6139)
+    #[msg("Session does not match the token authority or its token accounts.")]
+    InvalidSessionError, // 0x17fc (6140)
+    #[msg("Session has expired.")]
+    SessionExpiredError, // 0x17fd (6141)
+    #[msg("Session does not allow swaps on this pool.")]
+    SessionPoolNotAllowedError, // 0x17fe (6142)
+    #[msg("Swap exceeds the remaining notional of the session.")]
+    SessionNotionalLimitExceededError, // 0x17ff (6143)
+    #[msg("Session must allow between one and the maximum number of pools.")]
+    InvalidSessionPoolsError, // 0x1800 (6144)
+    #[msg("Reference oracle is missing or does not price the token mints of the pool.")]
+    InvalidReferenceOracleError, // 0x1801 (6145)
+    #[msg("Initial price deviates too far from the reference oracle price.")]
+    InitialPriceDeviationError, // 0x1802 (6146)
+    #[msg("TWAP price limit requires the oracle of the pool to be initialized.")]
+    TwapPriceLimitOracleError, // 0x1803 (6147)
+    #[msg("Tick is not initialized.")]
+    TickNotInitializedError, // 0x1804 (6148)
+    #[msg("Outstanding emissions of the reward are not tracked.")]
+    RewardEmissionsNotTrackedError, // 0x1805 (6149)
+    #[msg("Swap hook of the pool is not called by this instruction.")]
+    SwapHookNotSupportedError, // 0x1806 (6150)
 }
 
 impl From<TryFromIntError> for ErrorCode {
@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::instructions::rebalance_position::*;
+use crate::state::*;
+use crate::util::{
+    check_deadline, parse_remaining_accounts, to_timestamp_u64, AccountsType,
+    RemainingAccountsInfo,
+};
+
+#[event]
+pub struct AgentRebalanceEvent {
+    pub agent: Pubkey,
+    pub agent_strategy: Pubkey,
+    pub position: Pubkey,
+    pub old_tick_lower_index: i32,
+    pub old_tick_upper_index: i32,
+    pub new_tick_lower_index: i32,
+    pub new_tick_upper_index: i32,
+    pub tick_current_index: i32,
+    pub twap_tick_index: i32,
+    pub timestamp: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AgentRebalancePosition<'info> {
+    /// The `position_authority` of the rebalance is the agent of the strategy.
+    pub rebalance: RebalancePosition<'info>,
+
+    #[account(mut,
+        seeds = [
+            b"agent_strategy",
+            rebalance.position.key().as_ref(),
+        ],
+        bump,
+        constraint = agent_strategy.agent == rebalance.position_authority.key() @ ErrorCode::InvalidDelegateError,
+    )]
+    pub agent_strategy: Account<'info, AgentStrategy>,
+
+    #[account(seeds = [b"oracle", rebalance.ai_dex_pool.key().as_ref()], bump)]
+    pub oracle: AccountLoader<'info, Oracle>,
+}
+
+/// Moves all liquidity of a position into a new tick range on behalf of its holder.
+///
+/// The rebalance is signed by the agent of the position's strategy and must stay within its
+/// constraints. The tokens that do not fit the new range can only be sent to accounts of the
+/// position owner.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the rebalance.
+/// * `new_tick_lower_index` - The new lower tick index of the position.
+/// * `new_tick_upper_index` - The new upper tick index of the position.
+/// * `liquidity_min` - The minimum liquidity to deposit into the new range.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+/// * `deadline_timestamp` - Optional unix timestamp after which the transaction is rejected.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the rebalance is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidOperatorDestinationError` - If the tokens are not sent to accounts of the position owner.
+/// * `ErrorCode::AgentRebalanceTooFrequentError` - If the agent rebalanced too recently.
+/// * `ErrorCode::AgentTickWidthExceededError` - If the new range is too wide.
+/// * `ErrorCode::AgentSlippageExceededError` - If the pool price is too far from its TWAP.
+/// * `ErrorCode::TransactionExpiredError` - If the deadline has passed.
+pub fn agent_rebalance_position_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, AgentRebalancePosition<'info>>,
+    new_tick_lower_index: i32,
+    new_tick_upper_index: i32,
+    liquidity_min: u128,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+    deadline_timestamp: Option<i64>,
+) -> Result<()> {
+    let position_owner = ctx.accounts.rebalance.position_token_account.owner;
+    if ctx.accounts.rebalance.token_owner_account_a.owner != position_owner
+        || ctx.accounts.rebalance.token_owner_account_b.owner != position_owner
+    {
+        return Err(ErrorCode::InvalidOperatorDestinationError.into());
+    }
+
+    let clock = Clock::get()?;
+    check_deadline(deadline_timestamp, clock.unix_timestamp)?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let tick_current_index = ctx.accounts.rebalance.ai_dex_pool.load()?.tick_current_index;
+    let twap_tick_index = ctx.accounts.oracle.load()?.twap_tick(
+        timestamp,
+        AGENT_TWAP_WINDOW_SECONDS,
+        tick_current_index,
+    )?;
+    ctx.accounts.agent_strategy.check_rebalance(
+        new_tick_lower_index,
+        new_tick_upper_index,
+        tick_current_index,
+        twap_tick_index,
+        timestamp,
+    )?;
+
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+    )?;
+
+    let old_tick_lower_index = ctx.accounts.rebalance.position.tick_lower_index;
+    let old_tick_upper_index = ctx.accounts.rebalance.position.tick_upper_index;
+
+    let rebalanced_event = rebalance_liquidity(
+        &mut ctx.accounts.rebalance,
+        &remaining_accounts,
+        new_tick_lower_index,
+        new_tick_upper_index,
+        liquidity_min,
+        timestamp,
+    )?;
+
+    ctx.accounts.agent_strategy.record_rebalance(timestamp);
+
+    emit_cpi!(rebalanced_event);
+    emit_cpi!(AgentRebalanceEvent {
+        agent: ctx.accounts.rebalance.position_authority.key(),
+        agent_strategy: ctx.accounts.agent_strategy.key(),
+        position: ctx.accounts.rebalance.position.key(),
+        old_tick_lower_index,
+        old_tick_upper_index,
+        new_tick_lower_index,
+        new_tick_upper_index,
+        tick_current_index,
+        twap_tick_index,
+        timestamp,
+    });
+
+    Ok(())
+}
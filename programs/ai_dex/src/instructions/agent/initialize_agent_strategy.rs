@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::state::*;
+use crate::util::verify_position_authority;
+
+#[event]
+pub struct AgentStrategyInitializedEvent {
+    pub position_authority: Pubkey,
+    pub position: Pubkey,
+    pub agent_strategy: Pubkey,
+    pub agent: Pubkey,
+    pub max_tick_width: u32,
+    pub min_rebalance_interval: u64,
+    pub max_slippage_bps: u16,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeAgentStrategy<'info> {
+    #[account(mut)]
+    pub position_authority: Signer<'info>,
+
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: the account that will be the agent can be arbitrary
+    pub agent: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = position_authority,
+        seeds = [
+            b"agent_strategy",
+            position.key().as_ref(),
+        ],
+        bump,
+        space = AgentStrategy::LEN
+    )]
+    pub agent_strategy: Account<'info, AgentStrategy>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Initializes the agent strategy of a position.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the operation.
+/// * `max_tick_width` - The maximum width of the ranges the agent can move the position to, in ticks.
+/// * `min_rebalance_interval` - The minimum number of seconds between two rebalances of the agent.
+/// * `max_slippage_bps` - The maximum distance between the pool price and its TWAP when the agent rebalances.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the operation is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidAgentStrategyError` - If the constraints are invalid.
+/// * Any error from verifying the position authority.
+pub fn initialize_agent_strategy_handler(
+    ctx: Context<InitializeAgentStrategy>,
+    max_tick_width: u32,
+    min_rebalance_interval: u64,
+    max_slippage_bps: u16,
+) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    ctx.accounts.agent_strategy.initialize(
+        ctx.accounts.position.key(),
+        ctx.accounts.agent.key(),
+        max_tick_width,
+        min_rebalance_interval,
+        max_slippage_bps,
+    )?;
+
+    emit_cpi!(AgentStrategyInitializedEvent {
+        position_authority: ctx.accounts.position_authority.key(),
+        position: ctx.accounts.position.key(),
+        agent_strategy: ctx.accounts.agent_strategy.key(),
+        agent: ctx.accounts.agent.key(),
+        max_tick_width,
+        min_rebalance_interval,
+        max_slippage_bps,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,7 @@
+pub mod agent_rebalance_position;
+pub mod initialize_agent_strategy;
+pub mod set_agent_strategy;
+
+pub use agent_rebalance_position::*;
+pub use initialize_agent_strategy::*;
+pub use set_agent_strategy::*;
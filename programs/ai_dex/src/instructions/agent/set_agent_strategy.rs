@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::state::*;
+use crate::util::verify_position_authority;
+
+#[event]
+pub struct AgentStrategyUpdatedEvent {
+    pub position_authority: Pubkey,
+    pub position: Pubkey,
+    pub agent_strategy: Pubkey,
+    pub old_agent: Pubkey,
+    pub new_agent: Pubkey,
+    pub max_tick_width: u32,
+    pub min_rebalance_interval: u64,
+    pub max_slippage_bps: u16,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetAgentStrategy<'info> {
+    pub position_authority: Signer<'info>,
+
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: the account that will be the new agent can be arbitrary
+    pub new_agent: UncheckedAccount<'info>,
+
+    #[account(mut, has_one = position)]
+    pub agent_strategy: Account<'info, AgentStrategy>,
+}
+
+/// Updates the agent and the constraints of the agent strategy of a position.
+///
+/// Passing the default pubkey as the new agent revokes the agent.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the operation.
+/// * `max_tick_width` - The maximum width of the ranges the agent can move the position to, in ticks.
+/// * `min_rebalance_interval` - The minimum number of seconds between two rebalances of the agent.
+/// * `max_slippage_bps` - The maximum distance between the pool price and its TWAP when the agent rebalances.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the operation is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidAgentStrategyError` - If the constraints are invalid.
+/// * Any error from verifying the position authority.
+pub fn set_agent_strategy_handler(
+    ctx: Context<SetAgentStrategy>,
+    max_tick_width: u32,
+    min_rebalance_interval: u64,
+    max_slippage_bps: u16,
+) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    let old_agent = ctx.accounts.agent_strategy.agent;
+    ctx.accounts.agent_strategy.update(
+        ctx.accounts.new_agent.key(),
+        max_tick_width,
+        min_rebalance_interval,
+        max_slippage_bps,
+    )?;
+
+    emit_cpi!(AgentStrategyUpdatedEvent {
+        position_authority: ctx.accounts.position_authority.key(),
+        position: ctx.accounts.position.key(),
+        agent_strategy: ctx.accounts.agent_strategy.key(),
+        old_agent,
+        new_agent: ctx.accounts.new_agent.key(),
+        max_tick_width,
+        min_rebalance_interval,
+        max_slippage_bps,
+    });
+
+    Ok(())
+}
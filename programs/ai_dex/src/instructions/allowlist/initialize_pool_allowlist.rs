@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[event]
+pub struct PoolAllowlistInitializedEvent {
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub allowlist_authority: Pubkey,
+    pub pool_allowlist: Pubkey,
+    pub kind: PoolAllowlistKind,
+    pub funder: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(kind: PoolAllowlistKind)]
+pub struct InitializePoolAllowlist<'info> {
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    #[account(has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::Allowlist)
+    )]
+    pub allowlist_authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = funder,
+        seeds = [kind.seed(), ai_dex_pool.key().as_ref()],
+        bump,
+        space = PoolAllowlist::LEN
+    )]
+    pub pool_allowlist: Box<Account<'info, PoolAllowlist>>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The extension of the config, if any, holding the delegated allowlist authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+}
+
+/// Initializes an empty allowlist of an AI DEX pool.
+///
+/// The allowlist does not restrict anything until it is enabled with `set_pool_allowlist_enabled`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the initialization.
+/// * `kind` - The operations of the pool the allowlist restricts.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the allowlist is successfully initialized, otherwise returns an error.
+pub fn initialize_pool_allowlist_handler(
+    ctx: Context<InitializePoolAllowlist>,
+    kind: PoolAllowlistKind,
+) -> Result<()> {
+    ctx.accounts.pool_allowlist.initialize(ctx.accounts.ai_dex_pool.key());
+
+    emit_cpi!(PoolAllowlistInitializedEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        allowlist_authority: ctx.accounts.allowlist_authority.key(),
+        pool_allowlist: ctx.accounts.pool_allowlist.key(),
+        kind,
+        funder: ctx.accounts.funder.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,9 @@
+pub mod initialize_pool_allowlist;
+pub mod set_pool_allowlist_authority;
+pub mod set_pool_allowlist_enabled;
+pub mod set_pool_allowlist_gating_mint;
+
+pub use initialize_pool_allowlist::*;
+pub use set_pool_allowlist_authority::*;
+pub use set_pool_allowlist_enabled::*;
+pub use set_pool_allowlist_gating_mint::*;
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[event]
+pub struct PoolAllowlistAuthoritySetEvent {
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub allowlist_authority: Pubkey,
+    pub pool_allowlist: Pubkey,
+    pub kind: PoolAllowlistKind,
+    pub authority: Pubkey,
+    pub allowed: bool,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(kind: PoolAllowlistKind)]
+pub struct SetPoolAllowlistAuthority<'info> {
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    #[account(has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::Allowlist)
+    )]
+    pub allowlist_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [kind.seed(), ai_dex_pool.key().as_ref()],
+        bump,
+    )]
+    pub pool_allowlist: Box<Account<'info, PoolAllowlist>>,
+
+    /// The extension of the config, if any, holding the delegated allowlist authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+}
+
+/// Adds an authority to or removes it from an allowlist of an AI DEX pool.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the update.
+/// * `kind` - The operations of the pool the allowlist restricts.
+/// * `authority` - The authority to add or remove.
+/// * `allowed` - Whether the authority is added to or removed from the allowlist.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the allowlist is successfully updated, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::PoolAllowlistFullError` - If the allowlist has no free slot left for the authority.
+/// * `ErrorCode::AuthorityNotAllowlistedError` - If the removed authority is not on the allowlist.
+pub fn set_pool_allowlist_authority_handler(
+    ctx: Context<SetPoolAllowlistAuthority>,
+    kind: PoolAllowlistKind,
+    authority: Pubkey,
+    allowed: bool,
+) -> Result<()> {
+    if allowed {
+        ctx.accounts.pool_allowlist.add_authority(authority)?;
+    } else {
+        ctx.accounts.pool_allowlist.remove_authority(authority)?;
+    }
+
+    emit_cpi!(PoolAllowlistAuthoritySetEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        allowlist_authority: ctx.accounts.allowlist_authority.key(),
+        pool_allowlist: ctx.accounts.pool_allowlist.key(),
+        kind,
+        authority,
+        allowed,
+    });
+
+    Ok(())
+}
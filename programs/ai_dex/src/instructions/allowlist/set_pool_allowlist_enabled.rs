@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[event]
+pub struct PoolAllowlistEnabledSetEvent {
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub allowlist_authority: Pubkey,
+    pub pool_allowlist: Pubkey,
+    pub kind: PoolAllowlistKind,
+    pub enabled: bool,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(kind: PoolAllowlistKind)]
+pub struct SetPoolAllowlistEnabled<'info> {
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::Allowlist)
+    )]
+    pub allowlist_authority: Signer<'info>,
+
+    #[account(
+        seeds = [kind.seed(), ai_dex_pool.key().as_ref()],
+        bump,
+    )]
+    pub pool_allowlist: Box<Account<'info, PoolAllowlist>>,
+
+    /// The extension of the config, if any, holding the delegated allowlist authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+}
+
+/// Enables or disables an allowlist of an AI DEX pool.
+///
+/// The allowlist must be initialized first, so an enabled allowlist always exists.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the update.
+/// * `kind` - The operations of the pool the allowlist restricts.
+/// * `enabled` - Whether the allowlist is enabled.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the allowlist is successfully enabled or disabled, otherwise returns an error.
+pub fn set_pool_allowlist_enabled_handler(
+    ctx: Context<SetPoolAllowlistEnabled>,
+    kind: PoolAllowlistKind,
+    enabled: bool,
+) -> Result<()> {
+    ctx.accounts
+        .ai_dex_pool
+        .load_mut()?
+        .set_allowlist_enabled(kind, enabled);
+
+    emit_cpi!(PoolAllowlistEnabledSetEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        allowlist_authority: ctx.accounts.allowlist_authority.key(),
+        pool_allowlist: ctx.accounts.pool_allowlist.key(),
+        kind,
+        enabled,
+    });
+
+    Ok(())
+}
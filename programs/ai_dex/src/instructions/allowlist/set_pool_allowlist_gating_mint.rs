@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[event]
+pub struct PoolAllowlistGatingMintSetEvent {
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub allowlist_authority: Pubkey,
+    pub pool_allowlist: Pubkey,
+    pub kind: PoolAllowlistKind,
+    pub old_gating_mint: Pubkey,
+    pub new_gating_mint: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(kind: PoolAllowlistKind)]
+pub struct SetPoolAllowlistGatingMint<'info> {
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    #[account(has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::Allowlist)
+    )]
+    pub allowlist_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [kind.seed(), ai_dex_pool.key().as_ref()],
+        bump,
+    )]
+    pub pool_allowlist: Box<Account<'info, PoolAllowlist>>,
+
+    /// The extension of the config, if any, holding the delegated allowlist authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+}
+
+/// Sets the gating mint of an allowlist of an AI DEX pool.
+///
+/// Holders of a token of the gating mint, typically an NFT, are allowed without being listed.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the update.
+/// * `kind` - The operations of the pool the allowlist restricts.
+/// * `gating_mint` - The new gating mint, or the default pubkey for none.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the gating mint is successfully set, otherwise returns an error.
+pub fn set_pool_allowlist_gating_mint_handler(
+    ctx: Context<SetPoolAllowlistGatingMint>,
+    kind: PoolAllowlistKind,
+    gating_mint: Pubkey,
+) -> Result<()> {
+    let old_gating_mint = ctx.accounts.pool_allowlist.gating_mint;
+    ctx.accounts.pool_allowlist.set_gating_mint(gating_mint);
+
+    emit_cpi!(PoolAllowlistGatingMintSetEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        allowlist_authority: ctx.accounts.allowlist_authority.key(),
+        pool_allowlist: ctx.accounts.pool_allowlist.key(),
+        kind,
+        old_gating_mint,
+        new_gating_mint: gating_mint,
+    });
+
+    Ok(())
+}
@@ -1,14 +1,14 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::errors::ErrorCode;
 use crate::state::*;
-use crate::util::{burn_and_close_user_position_token, verify_position_authority};
+use crate::util::{burn_and_close_user_position_token, to_timestamp_u64, verify_position_authority};
 
 #[event]
 pub struct PositionClosedEvent {
     pub position_authority: Pubkey,
-    pub receiver: Pubkey,
+    pub rent_receiver: Pubkey,
     pub position_mint: Pubkey,
     pub position_token_account: PositionTokenAccountData,
     pub position: Pubkey,
@@ -21,16 +21,17 @@ pub struct PositionTokenAccountData {
     pub mint: Pubkey,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct ClosePosition<'info> {
     pub position_authority: Signer<'info>,
 
-    /// CHECK: safe, for receiving rent only
+    /// CHECK: safe, for receiving reclaimed rent only; may be any account (e.g. a treasury)
     #[account(mut)]
-    pub receiver: UncheckedAccount<'info>,
+    pub rent_receiver: UncheckedAccount<'info>,
 
     #[account(mut,
-        close = receiver,
+        close = rent_receiver,
         seeds = [
             b"position".as_ref(),
             position_mint.key().as_ref()
@@ -40,15 +41,15 @@ pub struct ClosePosition<'info> {
     pub position: Account<'info, Position>,
 
     #[account(mut, address = position.position_mint)]
-    pub position_mint: Account<'info, Mint>,
+    pub position_mint: InterfaceAccount<'info, Mint>,
 
     #[account(mut,
         constraint = position_token_account.amount == 1,
         constraint = position_token_account.mint == position.position_mint)]
-    pub position_token_account: Box<Account<'info, TokenAccount>>,
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    #[account(address = position_mint.to_account_info().owner.clone())]
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn close_position_handler(ctx: Context<ClosePosition>) -> Result<()> {
@@ -57,21 +58,26 @@ pub fn close_position_handler(ctx: Context<ClosePosition>) -> Result<()> {
         &ctx.accounts.position_authority,
     )?;
 
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    if ctx.accounts.position.is_locked(timestamp) {
+        return Err(ErrorCode::PositionLockedError.into());
+    }
+
     if !Position::is_position_empty(&ctx.accounts.position) {
         return Err(ErrorCode::NonEmptyPositionCloseError.into());
     }
 
     burn_and_close_user_position_token(
         &ctx.accounts.position_authority,
-        &ctx.accounts.receiver,
+        &ctx.accounts.rent_receiver,
         &ctx.accounts.position_mint,
         &ctx.accounts.position_token_account,
         &ctx.accounts.token_program,
     )?;
 
-    emit!(PositionClosedEvent {
+    emit_cpi!(PositionClosedEvent {
         position_authority: ctx.accounts.position_authority.key(),
-        receiver: ctx.accounts.receiver.key(),
+        rent_receiver: ctx.accounts.rent_receiver.key(),
         position_mint: ctx.accounts.position_mint.key(),
         position_token_account: PositionTokenAccountData {
             key: ctx.accounts.position_token_account.key(),
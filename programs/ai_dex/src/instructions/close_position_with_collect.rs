@@ -0,0 +1,361 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{
+    Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface,
+};
+use std::convert::TryFrom;
+
+use crate::constants::transfer_memo;
+use crate::errors::ErrorCode;
+use crate::math::convert_to_liquidity_delta;
+use crate::orchestrator::liquidity_orchestrator::{
+    calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
+};
+use crate::state::*;
+use crate::util::{
+    burn_and_close_user_position_token, calculate_transfer_fee_excluded_amount, check_deadline,
+    parse_remaining_accounts, remaining_accounts_slice, to_timestamp_u64, transfer_from_vault_to_owner,
+    verify_position_authority, AccountsType, RemainingAccountsInfo, TickArrayAccount,
+};
+
+use super::calculate_collect_reward;
+
+// Accounts passed in the `RewardAccounts` slice for each initialized reward:
+// reward owner account, reward mint, reward vault and reward token program.
+const ACCOUNTS_PER_REWARD: usize = 4;
+
+#[event]
+pub struct PositionClosedWithCollectEvent {
+    pub position_authority: Pubkey,
+    pub rent_receiver: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub position: Pubkey,
+    pub position_mint: Pubkey,
+    pub liquidity_amount: u128,
+    pub delta_a: u64,
+    pub delta_b: u64,
+    pub fee_owed_a: u64,
+    pub fee_owed_b: u64,
+    pub reward_amounts: [u64; NUM_REWARDS],
+    pub timestamp: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClosePositionWithCollect<'info> {
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    /// Pays the rent if a tick of a dynamic tick array changes while removing the liquidity.
+    #[account(mut)]
+    pub position_authority: Signer<'info>,
+
+    /// CHECK: safe, for receiving reclaimed rent only; may be any account (e.g. a treasury)
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+
+    #[account(mut,
+        has_one = ai_dex_pool,
+        close = rent_receiver,
+        seeds = [
+            b"position".as_ref(),
+            position_mint.key().as_ref()
+        ],
+        bump,
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(mut, address = position.position_mint)]
+    pub position_mint: Box<InterfaceAccount<'info, MintInterface>>,
+
+    #[account(mut,
+        constraint = position_token_account.amount == 1,
+        constraint = position_token_account.mint == position.position_mint)]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
+    pub token_mint_a: Box<InterfaceAccount<'info, MintInterface>>,
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
+    pub token_mint_b: Box<InterfaceAccount<'info, MintInterface>>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == ai_dex_pool.load()?.token_mint_a)]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+    #[account(mut, constraint = token_owner_account_b.mint == ai_dex_pool.load()?.token_mint_b)]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    /// CHECK: A fixed or dynamic tick array of the pool, checked in the handler
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: A fixed or dynamic tick array of the pool, checked in the handler
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(address = position_mint.to_account_info().owner.clone())]
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(address = token_mint_a.to_account_info().owner.clone())]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = token_mint_b.to_account_info().owner.clone())]
+    pub token_program_b: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+    pub system_program: Program<'info, System>,
+
+    /// The config of the pool, checked against the protocol-wide emergency pause
+    #[account(
+        address = ai_dex_pool.load()?.ai_dex_config,
+        constraint = !ai_dex_config.paused @ ErrorCode::ProtocolPausedError
+    )]
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+}
+
+/// Removes all liquidity of a position, collects its fees and rewards, and closes it.
+///
+/// The accounts of every initialized reward are passed in the `RewardAccounts` slice of the
+/// remaining accounts, in reward index order: the reward owner account, the reward mint, the
+/// reward vault and the reward token program.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required to close the position.
+/// * `token_min_a` - The minimum amount of token A to receive for the removed liquidity.
+/// * `token_min_b` - The minimum amount of token B to receive for the removed liquidity.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+/// * `deadline_timestamp` - Optional unix timestamp after which the transaction is rejected.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the position is closed, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::TransactionExpiredError` - If the deadline has passed.
+/// * `ErrorCode::PositionLockedError` - If the position is locked.
+/// * `ErrorCode::TokenAmountBelowMinimumError` - If the removed liquidity is worth less than the minimum amounts.
+/// * `ErrorCode::InvalidRewardAccountsError` - If the reward accounts do not match the initialized rewards.
+/// * `ErrorCode::NonEmptyPositionCloseError` - If a reward vault cannot pay out the full amount owed.
+pub fn close_position_with_collect_handler<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ClosePositionWithCollect<'info>>,
+    token_min_a: u64,
+    token_min_b: u64,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+    deadline_timestamp: Option<i64>,
+) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    let clock = Clock::get()?;
+    check_deadline(deadline_timestamp, clock.unix_timestamp)?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    if ctx.accounts.position.is_locked(timestamp) {
+        return Err(ErrorCode::PositionLockedError.into());
+    }
+
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::TransferHookReward,
+            AccountsType::RewardAccounts,
+        ],
+    )?;
+
+    // Remove the remaining liquidity, which also brings the fees and rewards owed up to date
+    let liquidity_amount = ctx.accounts.position.liquidity;
+    let (delta_a, delta_b) = if liquidity_amount > 0 {
+        let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, false)?;
+
+        let ai_dex_pool_key = ctx.accounts.ai_dex_pool.key();
+        let tick_array_lower = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_lower, &ai_dex_pool_key)?;
+        let tick_array_upper = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_upper, &ai_dex_pool_key)?;
+
+        let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
+        let update = calculate_modify_liquidity(
+            &ai_dex,
+            &ctx.accounts.position,
+            &tick_array_lower,
+            &tick_array_upper,
+            liquidity_delta,
+            timestamp,
+        )?;
+        sync_modify_liquidity_values(
+            &mut ai_dex,
+            &mut ctx.accounts.position,
+            &tick_array_lower,
+            &tick_array_upper,
+            update,
+            timestamp,
+            &ctx.accounts.position_authority,
+            &ctx.accounts.system_program,
+        )?;
+
+        let deltas = calculate_liquidity_token_deltas(
+            ai_dex.tick_current_index,
+            ai_dex.sqrt_price,
+            &ctx.accounts.position,
+            liquidity_delta,
+        )?;
+        drop(ai_dex);
+        deltas
+    } else {
+        (0, 0)
+    };
+
+    let transfer_fee_excluded_delta_a = calculate_transfer_fee_excluded_amount(
+        &ctx.accounts.token_mint_a,
+        delta_a,
+    )?;
+    let transfer_fee_excluded_delta_b = calculate_transfer_fee_excluded_amount(
+        &ctx.accounts.token_mint_b,
+        delta_b,
+    )?;
+    if transfer_fee_excluded_delta_a.amount < token_min_a {
+        return Err(ErrorCode::TokenAmountBelowMinimumError.into());
+    }
+    if transfer_fee_excluded_delta_b.amount < token_min_b {
+        return Err(ErrorCode::TokenAmountBelowMinimumError.into());
+    }
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_owner_account_a,
+        &ctx.accounts.token_program_a,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_a,
+        delta_a,
+        transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
+    )?;
+    transfer_from_vault_to_owner(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_owner_account_b,
+        &ctx.accounts.token_program_b,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_b,
+        delta_b,
+        transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
+    )?;
+
+    // Collect the fees
+    let fee_owed_a = ctx.accounts.position.fee_owed_a;
+    let fee_owed_b = ctx.accounts.position.fee_owed_b;
+    ctx.accounts.position.reset_fees_owed();
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_owner_account_a,
+        &ctx.accounts.token_program_a,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_a,
+        fee_owed_a,
+        transfer_memo::TRANSFER_MEMO_COLLECT_FEES.as_bytes(),
+    )?;
+    transfer_from_vault_to_owner(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_owner_account_b,
+        &ctx.accounts.token_program_b,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_b,
+        fee_owed_b,
+        transfer_memo::TRANSFER_MEMO_COLLECT_FEES.as_bytes(),
+    )?;
+
+    // Collect every initialized reward
+    let reward_infos = ctx.accounts.ai_dex_pool.load()?.reward_infos;
+    let initialized_rewards = reward_infos.iter().filter(|reward| reward.initialized()).count();
+    let reward_accounts = remaining_accounts_slice(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        AccountsType::RewardAccounts,
+    );
+    if reward_accounts.len() != initialized_rewards * ACCOUNTS_PER_REWARD {
+        return Err(ErrorCode::InvalidRewardAccountsError.into());
+    }
+
+    let mut reward_amounts = [0u64; NUM_REWARDS];
+    let initialized_reward_infos = reward_infos
+        .iter()
+        .enumerate()
+        .filter(|(_, reward)| reward.initialized());
+    for ((index, reward_info), accounts) in
+        initialized_reward_infos.zip(reward_accounts.chunks(ACCOUNTS_PER_REWARD))
+    {
+        let reward_owner_account = InterfaceAccount::<TokenAccountInterface>::try_from(&accounts[0])?;
+        let reward_mint = InterfaceAccount::<MintInterface>::try_from(&accounts[1])?;
+        let reward_vault = InterfaceAccount::<TokenAccountInterface>::try_from(&accounts[2])?;
+        let reward_token_program = Interface::<TokenInterface>::try_from(&accounts[3])?;
+
+        if reward_mint.key() != reward_info.mint
+            || reward_vault.key() != reward_info.vault
+            || reward_owner_account.mint != reward_info.mint
+            || reward_token_program.key() != *reward_mint.to_account_info().owner
+        {
+            return Err(ErrorCode::InvalidRewardAccountsError.into());
+        }
+
+        let (transfer_amount, updated_amount_owed) = calculate_collect_reward(
+            ctx.accounts.position.reward_infos[index],
+            reward_vault.amount,
+        );
+        ctx.accounts.position.update_reward_owed(index, updated_amount_owed);
+        ctx.accounts.ai_dex_pool.load_mut()?.record_reward_collected(index, transfer_amount);
+        reward_amounts[index] = transfer_amount;
+
+        transfer_from_vault_to_owner(
+            &ctx.accounts.ai_dex_pool,
+            &reward_mint,
+            &reward_vault,
+            &reward_owner_account,
+            &reward_token_program,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_reward,
+            transfer_amount,
+            transfer_memo::TRANSFER_MEMO_COLLECT_REWARD.as_bytes(),
+        )?;
+    }
+
+    // A reward vault short of tokens leaves an amount owed, so the position cannot be closed
+    if !Position::is_position_empty(&ctx.accounts.position) {
+        return Err(ErrorCode::NonEmptyPositionCloseError.into());
+    }
+
+    burn_and_close_user_position_token(
+        &ctx.accounts.position_authority,
+        &ctx.accounts.rent_receiver,
+        &ctx.accounts.position_mint,
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.token_program,
+    )?;
+
+    emit_cpi!(PositionClosedWithCollectEvent {
+        position_authority: ctx.accounts.position_authority.key(),
+        rent_receiver: ctx.accounts.rent_receiver.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        position: ctx.accounts.position.key(),
+        position_mint: ctx.accounts.position_mint.key(),
+        liquidity_amount,
+        delta_a,
+        delta_b,
+        fee_owed_a,
+        fee_owed_b,
+        reward_amounts,
+        timestamp,
+    });
+
+    Ok(())
+}
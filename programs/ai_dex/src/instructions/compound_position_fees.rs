@@ -0,0 +1,182 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::errors::ErrorCode;
+use crate::math::{convert_to_liquidity_delta, get_liquidity_from_amounts, sqrt_price_from_tick_index};
+use crate::orchestrator::liquidity_orchestrator::{
+    calculate_fee_and_reward_growths, calculate_liquidity_token_deltas,
+    calculate_modify_liquidity, sync_modify_liquidity_values,
+};
+use crate::state::*;
+use crate::util::{check_deadline, to_timestamp_u64, verify_position_authority, TickArrayAccount};
+
+#[event]
+pub struct CompoundEvent {
+    pub position_authority: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub position: Pubkey,
+    pub fee_owed_a: u64,
+    pub fee_owed_b: u64,
+    pub liquidity_amount: u128,
+    pub compounded_a: u64,
+    pub compounded_b: u64,
+    pub timestamp: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CompoundPositionFees<'info> {
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    /// Pays the rent when a tick of a dynamic tick array gets initialized.
+    #[account(mut)]
+    pub position_authority: Signer<'info>,
+
+    #[account(mut, has_one = ai_dex_pool)]
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: A fixed or dynamic tick array of the pool, checked in the handler
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: A fixed or dynamic tick array of the pool, checked in the handler
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The config of the pool, checked against the protocol-wide emergency pause
+    #[account(
+        address = ai_dex_pool.load()?.ai_dex_config,
+        constraint = !ai_dex_config.paused @ ErrorCode::ProtocolPausedError
+    )]
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+}
+
+/// Reinvests the fees owed to a position as liquidity of the same position.
+///
+/// The fees of the position are updated, and the largest liquidity the fees owed can fund at the
+/// current price is added to the position. The fee tokens are already held by the vaults, so no
+/// tokens are transferred; the part of the fees that does not fit stays owed to the position.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the compounding.
+/// * `liquidity_min` - The minimum liquidity to add to the position.
+/// * `deadline_timestamp` - Optional unix timestamp after which the transaction is rejected.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the fees are compounded, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::LiquidityBelowMinimumError` - If the liquidity added is below `liquidity_min`.
+/// * `ErrorCode::TransactionExpiredError` - If the deadline has passed.
+pub fn compound_position_fees_handler(
+    ctx: Context<CompoundPositionFees>,
+    liquidity_min: u128,
+    deadline_timestamp: Option<i64>,
+) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    let clock = Clock::get()?;
+    check_deadline(deadline_timestamp, clock.unix_timestamp)?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let ai_dex_pool_key = ctx.accounts.ai_dex_pool.key();
+    let tick_array_lower = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_lower, &ai_dex_pool_key)?;
+    let tick_array_upper = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_upper, &ai_dex_pool_key)?;
+
+    let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
+
+    // Bring the fees owed up to date
+    if ctx.accounts.position.liquidity > 0 {
+        let (position_update, reward_infos) = calculate_fee_and_reward_growths(
+            &ai_dex,
+            &ctx.accounts.position,
+            &tick_array_lower,
+            &tick_array_upper,
+            timestamp,
+        )?;
+        ai_dex.update_rewards(reward_infos, timestamp);
+        ctx.accounts.position.update(&position_update);
+    }
+
+    let fee_owed_a = ctx.accounts.position.fee_owed_a;
+    let fee_owed_b = ctx.accounts.position.fee_owed_b;
+
+    let liquidity_amount = get_liquidity_from_amounts(
+        ai_dex.sqrt_price,
+        sqrt_price_from_tick_index(ctx.accounts.position.tick_lower_index),
+        sqrt_price_from_tick_index(ctx.accounts.position.tick_upper_index),
+        fee_owed_a,
+        fee_owed_b,
+    )?;
+    if liquidity_amount < liquidity_min {
+        return Err(ErrorCode::LiquidityBelowMinimumError.into());
+    }
+
+    let (compounded_a, compounded_b) = if liquidity_amount > 0 {
+        let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, true)?;
+
+        let update = calculate_modify_liquidity(
+            &ai_dex,
+            &ctx.accounts.position,
+            &tick_array_lower,
+            &tick_array_upper,
+            liquidity_delta,
+            timestamp,
+        )?;
+        sync_modify_liquidity_values(
+            &mut ai_dex,
+            &mut ctx.accounts.position,
+            &tick_array_lower,
+            &tick_array_upper,
+            update,
+            timestamp,
+            &ctx.accounts.position_authority,
+            &ctx.accounts.system_program,
+        )?;
+
+        calculate_liquidity_token_deltas(
+            ai_dex.tick_current_index,
+            ai_dex.sqrt_price,
+            &ctx.accounts.position,
+            liquidity_delta,
+        )?
+    } else {
+        (0, 0)
+    };
+    drop(ai_dex);
+
+    // The compounded fees now back the liquidity, only the rest stays owed
+    ctx.accounts.position.fee_owed_a = fee_owed_a
+        .checked_sub(compounded_a)
+        .ok_or(ErrorCode::TokenLimitExceededError)?;
+    ctx.accounts.position.fee_owed_b = fee_owed_b
+        .checked_sub(compounded_b)
+        .ok_or(ErrorCode::TokenLimitExceededError)?;
+
+    emit_cpi!(CompoundEvent {
+        position_authority: ctx.accounts.position_authority.key(),
+        ai_dex_pool: ai_dex_pool_key,
+        position: ctx.accounts.position.key(),
+        fee_owed_a,
+        fee_owed_b,
+        liquidity_amount,
+        compounded_a,
+        compounded_b,
+        timestamp,
+    });
+
+    Ok(())
+}
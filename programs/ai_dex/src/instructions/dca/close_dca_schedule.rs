@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::transfer_memo;
+use crate::state::*;
+use crate::util::{
+    parse_remaining_accounts, transfer_from_vault_to_owner, AccountsType, RemainingAccountsInfo,
+};
+
+#[event]
+pub struct DcaScheduleClosedEvent {
+    pub owner: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub dca_schedule: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseDcaSchedule<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mut, has_one = ai_dex_pool, has_one = owner, close = owner)]
+    pub dca_schedule: Box<Account<'info, DcaSchedule>>,
+
+    #[account(address = ai_dex_pool.load()?.input_token_mint(dca_schedule.a_to_b))]
+    pub token_mint_input: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = ai_dex_pool.load()?.output_token_mint(dca_schedule.a_to_b))]
+    pub token_mint_output: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, constraint = token_owner_account_input.mint == token_mint_input.key())]
+    pub token_owner_account_input: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, constraint = token_owner_account_output.mint == token_mint_output.key())]
+    pub token_owner_account_output: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, address = ai_dex_pool.load()?.input_token_vault(dca_schedule.a_to_b))]
+    pub token_vault_input: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = ai_dex_pool.load()?.output_token_vault(dca_schedule.a_to_b))]
+    pub token_vault_output: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = token_mint_input.to_account_info().owner.clone())]
+    pub token_program_input: Interface<'info, TokenInterface>,
+    #[account(address = token_mint_output.to_account_info().owner.clone())]
+    pub token_program_output: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+}
+
+/// Closes a DCA schedule.
+///
+/// The input token not swapped yet and the output token received from executions are sent back
+/// to the owner, and the schedule account is closed.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required to close the schedule.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the schedule is closed, otherwise returns an error.
+pub fn close_dca_schedule_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CloseDcaSchedule<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::TransferHookInput, AccountsType::TransferHookOutput],
+    )?;
+
+    let amount_in = ctx.accounts.dca_schedule.amount_in;
+    let amount_out = ctx.accounts.dca_schedule.amount_out;
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.token_mint_input,
+        &ctx.accounts.token_vault_input,
+        &ctx.accounts.token_owner_account_input,
+        &ctx.accounts.token_program_input,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_input,
+        amount_in,
+        transfer_memo::TRANSFER_MEMO_CLOSE_DCA_SCHEDULE.as_bytes(),
+    )?;
+    transfer_from_vault_to_owner(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.token_mint_output,
+        &ctx.accounts.token_vault_output,
+        &ctx.accounts.token_owner_account_output,
+        &ctx.accounts.token_program_output,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_output,
+        amount_out,
+        transfer_memo::TRANSFER_MEMO_CLOSE_DCA_SCHEDULE.as_bytes(),
+    )?;
+
+    emit_cpi!(DcaScheduleClosedEvent {
+        owner: ctx.accounts.owner.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        dca_schedule: ctx.accounts.dca_schedule.key(),
+        amount_in,
+        amount_out,
+    });
+
+    Ok(())
+}
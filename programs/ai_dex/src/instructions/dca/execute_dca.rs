@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::state::*;
+use crate::util::{
+    parse_remaining_accounts, swap_within_vaults, to_timestamp_u64, AccountsType,
+    RemainingAccountsInfo,
+};
+
+#[event]
+pub struct DcaExecutedEvent {
+    pub ai_dex_pool: Pubkey,
+    pub dca_schedule: Pubkey,
+    pub a_to_b: bool,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub amount_in_remaining: u64,
+    pub timestamp: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteDca<'info> {
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut, has_one = ai_dex_pool)]
+    pub dca_schedule: Box<Account<'info, DcaSchedule>>,
+
+    /// CHECK: The first tick array of the swap, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_0: UncheckedAccount<'info>,
+    /// CHECK: The second tick array of the swap, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_1: UncheckedAccount<'info>,
+    /// CHECK: The third tick array of the swap, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_2: UncheckedAccount<'info>,
+
+    /// CHECK: The oracle account of the pool. An observation is recorded only if it has been initialized.
+    #[account(mut, seeds = [b"oracle", ai_dex_pool.key().as_ref()], bump)]
+    pub oracle: UncheckedAccount<'info>,
+
+    /// CHECK: The tick array bitmap of the pool. Used only if it has been initialized.
+    #[account(seeds = [b"tick_array_bitmap", ai_dex_pool.key().as_ref()], bump)]
+    pub tick_array_bitmap: UncheckedAccount<'info>,
+
+    /// The token vaults of the pool, whose balances bound the volume swapped out within a slot
+    #[account(address = ai_dex_pool.load()?.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = ai_dex_pool.load()?.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+}
+
+/// Executes the next swap of a DCA schedule.
+///
+/// The instruction is permissionless. Once the interval of the schedule has elapsed since the last
+/// execution, the next `amount_per_interval` of the deposit is swapped through the pool. The input
+/// and the output never leave the vaults of the pool; the output is credited to the schedule.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the execution.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the execution is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::DcaIntervalNotElapsedError` - If the interval has not elapsed since the last execution.
+/// * `ErrorCode::NoTradableAmountError` - If the deposit has been fully swapped.
+/// * `ErrorCode::PartialFillError` - If the price guard or the tick arrays stop the swap early.
+/// * `ErrorCode::SlotVolumeExceededError` - If the volume swapped out within the slot exceeds the cap of the pool.
+/// * `ErrorCode::SandwichSwapError` - If the owner swapped in the opposite direction within the slot.
+/// * `ErrorCode::SwapHookNotSupportedError` - If the pool has a swap hook, which order fills do not call.
+pub fn execute_dca_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ExecuteDca<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let amount = ctx.accounts.dca_schedule.next_execution_amount(timestamp)?;
+
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::SupplementalTickArrays],
+    )?;
+
+    let a_to_b = ctx.accounts.dca_schedule.a_to_b;
+    let ai_dex_pool_key = ctx.accounts.ai_dex_pool.key();
+    let (amount_in, amount_out) = swap_within_vaults(
+        &mut *ctx.accounts.ai_dex_pool.load_mut()?,
+        &ai_dex_pool_key,
+        &ctx.accounts.dca_schedule.owner,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.tick_array_0,
+        &ctx.accounts.tick_array_1,
+        &ctx.accounts.tick_array_2,
+        &remaining_accounts.supplemental_tick_arrays,
+        &ctx.accounts.tick_array_bitmap,
+        &ctx.accounts.oracle,
+        amount,
+        ctx.accounts.dca_schedule.sqrt_price_limit,
+        a_to_b,
+        timestamp,
+    )?;
+    ctx.accounts.dca_schedule.record_execution(amount_in, amount_out, timestamp)?;
+
+    emit_cpi!(DcaExecutedEvent {
+        ai_dex_pool: ai_dex_pool_key,
+        dca_schedule: ctx.accounts.dca_schedule.key(),
+        a_to_b,
+        amount_in,
+        amount_out,
+        amount_in_remaining: ctx.accounts.dca_schedule.amount_in,
+        timestamp,
+    });
+
+    Ok(())
+}
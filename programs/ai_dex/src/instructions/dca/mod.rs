@@ -0,0 +1,7 @@
+pub mod close_dca_schedule;
+pub mod execute_dca;
+pub mod open_dca_schedule;
+
+pub use close_dca_schedule::*;
+pub use execute_dca::*;
+pub use open_dca_schedule::*;
@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::*;
+use crate::util::{
+    calculate_transfer_fee_excluded_amount, parse_remaining_accounts, transfer_from_owner_to_vault,
+    verify_pool_allowlist, AccountsType, RemainingAccountsInfo,
+};
+
+#[event]
+pub struct DcaScheduleOpenedEvent {
+    pub owner: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub dca_schedule: Pubkey,
+    pub a_to_b: bool,
+    pub amount: u64,
+    pub amount_in: u64,
+    pub amount_per_interval: u64,
+    pub interval: u64,
+    pub sqrt_price_limit: u128,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(a_to_b: bool)]
+pub struct OpenDcaSchedule<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [
+            b"dca_schedule",
+            ai_dex_pool.key().as_ref(),
+            owner.key().as_ref(),
+            &[a_to_b as u8],
+        ],
+        bump,
+        space = DcaSchedule::LEN
+    )]
+    pub dca_schedule: Box<Account<'info, DcaSchedule>>,
+
+    #[account(address = ai_dex_pool.load()?.input_token_mint(a_to_b))]
+    pub token_mint_input: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, constraint = token_owner_account_input.mint == token_mint_input.key())]
+    pub token_owner_account_input: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = ai_dex_pool.load()?.input_token_vault(a_to_b))]
+    pub token_vault_input: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = token_mint_input.to_account_info().owner.clone())]
+    pub token_program_input: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+    pub system_program: Program<'info, System>,
+
+    /// The swap allowlist of the pool, required while it is enabled
+    #[account(seeds = [b"swap_allowlist", ai_dex_pool.key().as_ref()], bump)]
+    pub swap_allowlist: Option<Box<Account<'info, PoolAllowlist>>>,
+
+    /// The token account of the owner holding a gating token of the swap allowlist, if any
+    pub swap_gating_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+}
+
+/// Opens a DCA schedule swapping a deposit through a pool in recurring executions.
+///
+/// The deposit is transferred into the vault of the pool. Every `interval` seconds, any keeper can
+/// swap `amount_per_interval` of it with `execute_dca`, until the deposit is fully swapped or the
+/// owner closes the schedule.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required to open the schedule.
+/// * `a_to_b` - If true the schedule sells token A for token B, otherwise token B for token A.
+/// * `amount` - The amount of input token transferred from the owner.
+/// * `amount_per_interval` - The amount of input token swapped per execution.
+/// * `interval` - The minimum number of seconds between two executions.
+/// * `sqrt_price_limit` - The square root price an execution cannot move the price past. `0` means no limit.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the schedule is opened, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::NoTradableAmountError` - If no input token reaches the vault.
+/// * `ErrorCode::InvalidDcaScheduleError` - If the amount per interval or the interval is zero.
+pub fn open_dca_schedule_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, OpenDcaSchedule<'info>>,
+    a_to_b: bool,
+    amount: u64,
+    amount_per_interval: u64,
+    interval: u64,
+    sqrt_price_limit: u128,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    verify_pool_allowlist(
+        &*ctx.accounts.ai_dex_pool.load()?,
+        PoolAllowlistKind::Swap,
+        ctx.accounts.swap_allowlist.as_deref(),
+        ctx.accounts.owner.key,
+        ctx.accounts.swap_gating_token_account.as_deref(),
+    )?;
+
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::TransferHookInput],
+    )?;
+
+    // The schedule only swaps what the vault receives
+    let amount_in = calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_input, amount)?.amount;
+    ctx.accounts.dca_schedule.initialize(
+        ctx.accounts.ai_dex_pool.key(),
+        ctx.accounts.owner.key(),
+        a_to_b,
+        amount_in,
+        amount_per_interval,
+        interval,
+        sqrt_price_limit,
+    )?;
+
+    transfer_from_owner_to_vault(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.owner,
+        &ctx.accounts.token_mint_input,
+        &ctx.accounts.token_owner_account_input,
+        &ctx.accounts.token_vault_input,
+        &ctx.accounts.token_program_input,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_input,
+        amount,
+    )?;
+
+    emit_cpi!(DcaScheduleOpenedEvent {
+        owner: ctx.accounts.owner.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        dca_schedule: ctx.accounts.dca_schedule.key(),
+        a_to_b,
+        amount,
+        amount_in,
+        amount_per_interval,
+        interval,
+        sqrt_price_limit,
+    });
+
+    Ok(())
+}
@@ -6,7 +6,8 @@ use crate::orchestrator::liquidity_orchestrator::{
 };
 use crate::math::convert_to_liquidity_delta;
 use crate::util::{calculate_transfer_fee_excluded_amount, parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
-use crate::util::{to_timestamp_u64, transfer_from_vault_to_owner, verify_position_authority};
+use crate::util::{check_deadline, to_timestamp_u64, transfer_from_vault_to_owner, verify_position_authority_or_operator, TickArrayAccount};
+use crate::util::{parse_native_sol_system_program, unwrap_pool_native_sol};
 use crate::constants::transfer_memo;
 
 use super::ModifyLiquidity;
@@ -43,7 +44,10 @@ pub struct DecreaseLiquidityEvent {
 /// * `liquidity_amount` - The amount of liquidity to be decreased.
 /// * `token_min_a` - The minimum amount of token A to be transferred.
 /// * `token_min_b` - The minimum amount of token B to be transferred.
-/// * `remaining_accounts_info` - Optional information about remaining accounts.
+/// * `remaining_accounts_info` - Optional information about remaining accounts. A `NativeSol` slice
+///   holding the system program delivers the wSOL side as SOL by closing the wSOL token account of
+///   the position authority, which must own it.
+/// * `deadline_timestamp` - Optional unix timestamp after which the transaction is rejected.
 ///
 /// # Returns
 ///
@@ -54,6 +58,8 @@ pub struct DecreaseLiquidityEvent {
 /// This function will return an error if:
 /// * The position authority verification fails.
 /// * The liquidity amount is zero.
+/// * The deadline has passed.
+/// * The position is locked.
 /// * Parsing the remaining accounts fails.
 /// * Calculating the liquidity delta fails.
 /// * Calculating the modify liquidity values fails.
@@ -62,17 +68,24 @@ pub struct DecreaseLiquidityEvent {
 /// * Calculating the transfer fee excluded amounts fails.
 /// * The transfer fee excluded amounts are below the minimum thresholds.
 /// * Transferring from the vault to the owner's accounts fails.
+/// * Native SOL delivery is requested for a pool without wSOL, or the wSOL account cannot be closed.
 pub fn decrease_liquidity_handler<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, ModifyLiquidity<'info>>,
     liquidity_amount: u128,
     token_min_a: u64,
     token_min_b: u64,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
+    deadline_timestamp: Option<i64>,
 ) -> Result<()> {
     // Verify position authority
-    verify_position_authority(
+    verify_position_authority_or_operator(
         &ctx.accounts.position_token_account,
+        &ctx.accounts.position,
         &ctx.accounts.position_authority,
+        &[
+            ctx.accounts.token_owner_account_a.owner,
+            ctx.accounts.token_owner_account_b.owner,
+        ],
     )?;
 
     // Check for zero liquidity amount
@@ -81,7 +94,13 @@ pub fn decrease_liquidity_handler<'a, 'b, 'c, 'info>(
     }
 
     // Get the current clock timestamp
-    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let clock = Clock::get()?;
+    check_deadline(deadline_timestamp, clock.unix_timestamp)?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    if ctx.accounts.position.is_locked(timestamp) {
+        return Err(ErrorCode::PositionLockedError.into());
+    }
 
     // Process remaining accounts
     let remaining_accounts = parse_remaining_accounts(
@@ -90,37 +109,52 @@ pub fn decrease_liquidity_handler<'a, 'b, 'c, 'info>(
         &[
             AccountsType::TransferHookA,
             AccountsType::TransferHookB,
+            AccountsType::NativeSol,
         ],
     )?;
+    let native_sol_system_program = parse_native_sol_system_program(
+        &remaining_accounts.native_sol,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_mint_b,
+    )?;
 
     // Calculate liquidity delta
     let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, false)?;
 
+    let ai_dex_pool_key = ctx.accounts.ai_dex_pool.key();
+    let tick_array_lower = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_lower, &ai_dex_pool_key)?;
+    let tick_array_upper = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_upper, &ai_dex_pool_key)?;
+
     // Calculate and sync modify liquidity values
+    let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
     let update = calculate_modify_liquidity(
-        &ctx.accounts.ai_dex_pool,
+        &ai_dex,
         &ctx.accounts.position,
-        &ctx.accounts.tick_array_lower,
-        &ctx.accounts.tick_array_upper,
+        &tick_array_lower,
+        &tick_array_upper,
         liquidity_delta,
         timestamp,
     )?;
     sync_modify_liquidity_values(
-        &mut ctx.accounts.ai_dex_pool,
+        &mut ai_dex,
         &mut ctx.accounts.position,
-        &ctx.accounts.tick_array_lower,
-        &ctx.accounts.tick_array_upper,
+        &tick_array_lower,
+        &tick_array_upper,
         update,
         timestamp,
+        &ctx.accounts.position_authority,
+        &ctx.accounts.system_program,
     )?;
 
     // Calculate liquidity token deltas
     let (delta_a, delta_b) = calculate_liquidity_token_deltas(
-        ctx.accounts.ai_dex_pool.tick_current_index,
-        ctx.accounts.ai_dex_pool.sqrt_price,
+        ai_dex.tick_current_index,
+        ai_dex.sqrt_price,
         &ctx.accounts.position,
         liquidity_delta,
     )?;
+    ctx.accounts.position.record_withdrawal(delta_a, delta_b);
+    drop(ai_dex);
 
     // Calculate transfer fee excluded amounts
     let transfer_fee_excluded_delta_a = calculate_transfer_fee_excluded_amount(
@@ -166,7 +200,20 @@ pub fn decrease_liquidity_handler<'a, 'b, 'c, 'info>(
         transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
     )?;
 
-    emit!(DecreaseLiquidityEvent {
+    // Deliver the wSOL side as SOL by closing the transient wSOL account
+    if native_sol_system_program.is_some() {
+        unwrap_pool_native_sol(
+            &ctx.accounts.position_authority,
+            &ctx.accounts.token_mint_a,
+            &ctx.accounts.token_owner_account_a,
+            &ctx.accounts.token_program_a,
+            &ctx.accounts.token_mint_b,
+            &ctx.accounts.token_owner_account_b,
+            &ctx.accounts.token_program_b,
+        )?;
+    }
+
+    emit_cpi!(DecreaseLiquidityEvent {
         liquidity_amount,
         token_min_a,
         token_min_b,
@@ -0,0 +1,183 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::memo::Memo;
+
+use crate::errors::ErrorCode;
+use crate::orchestrator::ai_dex_orchestrator::{next_ai_dex_liquidity, next_ai_dex_reward_infos};
+use crate::orchestrator::liquidity_orchestrator::calculate_liquidity_token_deltas;
+use crate::math::convert_to_liquidity_delta;
+use crate::state::*;
+use crate::util::{parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
+use crate::util::{to_timestamp_u64, transfer_from_vault_to_owner, verify_position_authority_or_operator};
+use crate::constants::transfer_memo;
+
+#[event]
+pub struct EmergencyWithdrawEvent {
+    pub ai_dex_pool: Pubkey,
+    pub position: Pubkey,
+    pub position_authority: Pubkey,
+    pub liquidity_amount: u128,
+    pub delta_a: u64,
+    pub delta_b: u64,
+    pub fee_owed_a: u64,
+    pub fee_owed_b: u64,
+    pub timestamp: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct EmergencyWithdraw<'info> {
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(address = token_mint_a.to_account_info().owner.clone())]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = token_mint_b.to_account_info().owner.clone())]
+    pub token_program_b: Interface<'info, TokenInterface>,
+
+    pub memo_program: Program<'info, Memo>,
+
+    pub position_authority: Signer<'info>,
+
+    #[account(mut, has_one = ai_dex_pool)]
+    pub position: Account<'info, Position>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == ai_dex_pool.load()?.token_mint_a)]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, constraint = token_owner_account_b.mint == ai_dex_pool.load()?.token_mint_b)]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, constraint = token_vault_a.key() == ai_dex_pool.load()?.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, constraint = token_vault_b.key() == ai_dex_pool.load()?.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+}
+
+/// Withdraws the whole liquidity of a position of a pool in emergency mode, without the tick arrays.
+///
+/// The principal is computed from the range of the position and the current price of the pool,
+/// and is paid along with the fees the position had accrued at its last update. The fees and
+/// rewards accrued since then cannot be computed without the ticks and are forfeited, while the
+/// rewards already owed remain collectable with `collect_reward`. The ticks are left untouched.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the withdrawal.
+/// * `remaining_accounts_info` - Optional information about the transfer hook accounts.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the withdrawal is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::EmergencyModeDisabledError` - If the pool is not in emergency mode.
+/// * `ErrorCode::PositionLockedError` - If the position is locked.
+/// * `ErrorCode::ZeroLiquidityError` - If the position has no liquidity.
+pub fn emergency_withdraw_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, EmergencyWithdraw<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    verify_position_authority_or_operator(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position,
+        &ctx.accounts.position_authority,
+        &[
+            ctx.accounts.token_owner_account_a.owner,
+            ctx.accounts.token_owner_account_b.owner,
+        ],
+    )?;
+
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    if ctx.accounts.position.is_locked(timestamp) {
+        return Err(ErrorCode::PositionLockedError.into());
+    }
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+        ],
+    )?;
+
+    let position = &mut ctx.accounts.position;
+    let liquidity_amount = position.liquidity;
+    let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, false)?;
+
+    let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
+    if !ai_dex.emergency_mode {
+        return Err(ErrorCode::EmergencyModeDisabledError.into());
+    }
+
+    let (delta_a, delta_b) = calculate_liquidity_token_deltas(
+        ai_dex.tick_current_index,
+        ai_dex.sqrt_price,
+        position,
+        liquidity_delta,
+    )?;
+
+    // The rewards of the remaining liquidity are settled before it shrinks
+    let reward_infos = next_ai_dex_reward_infos(&ai_dex, timestamp)?;
+    let liquidity = next_ai_dex_liquidity(
+        &ai_dex,
+        position.tick_upper_index,
+        position.tick_lower_index,
+        liquidity_delta,
+    )?;
+    ai_dex.update_rewards_and_liquidity(reward_infos, liquidity, timestamp);
+    drop(ai_dex);
+
+    let (fee_owed_a, fee_owed_b) = (position.fee_owed_a, position.fee_owed_b);
+    position.reset_liquidity();
+    position.reset_fees_owed();
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_owner_account_a,
+        &ctx.accounts.token_program_a,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_a,
+        delta_a.checked_add(fee_owed_a).ok_or(ErrorCode::AmountCalculationOverflowError)?,
+        transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
+    )?;
+    transfer_from_vault_to_owner(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_owner_account_b,
+        &ctx.accounts.token_program_b,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_b,
+        delta_b.checked_add(fee_owed_b).ok_or(ErrorCode::AmountCalculationOverflowError)?,
+        transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
+    )?;
+
+    emit_cpi!(EmergencyWithdrawEvent {
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        position: ctx.accounts.position.key(),
+        position_authority: ctx.accounts.position_authority.key(),
+        liquidity_amount,
+        delta_a,
+        delta_b,
+        fee_owed_a,
+        fee_owed_b,
+        timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AiDexConfig, AiDexPool};
+
+#[event]
+pub struct PoolEmergencyModeEnabledEvent {
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub emergency_authority: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct EnablePoolEmergencyMode<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(address = ai_dex_config.emergency_authority)]
+    pub emergency_authority: Signer<'info>,
+}
+
+/// Puts an AI DEX pool in emergency mode, for instance when one of its tick arrays becomes unusable.
+///
+/// In emergency mode, the positions can withdraw their principal with `emergency_withdraw`, which
+/// does not need the tick arrays. Swaps and liquidity increases are disallowed for good, since the
+/// ticks no longer track the liquidity withdrawn that way. Only the emergency authority has
+/// permission to invoke this instruction.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for enabling the emergency mode.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the emergency mode is successfully enabled,
+/// or an `Err` if an error occurs.
+pub fn enable_pool_emergency_mode_handler(ctx: Context<EnablePoolEmergencyMode>) -> Result<()> {
+    ctx.accounts.ai_dex_pool.load_mut()?.enable_emergency_mode();
+
+    emit_cpi!(PoolEmergencyModeEnabledEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        emergency_authority: ctx.accounts.emergency_authority.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::memo::Memo;
+
+use crate::util::{parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
+use crate::{
+    constants::transfer_memo,
+    orchestrator::ai_dex_orchestrator::next_ai_dex_reward_infos,
+    state::*,
+    util::{to_timestamp_u64, transfer_from_vault_to_owner},
+};
+
+#[event]
+pub struct RewardClawedBackEvent {
+    pub ai_dex_pool: Pubkey,
+    pub reward_authority: Pubkey,
+    pub reward_mint: Pubkey,
+    pub reward_vault: Pubkey,
+    pub destination_token_account: Pubkey,
+    pub reward_index: u8,
+    pub transfer_amount: u64,
+    pub emissions_outstanding: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct ClawbackReward<'info> {
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(address = ai_dex_pool.load()?.reward_infos[reward_index as usize].authority)]
+    pub reward_authority: Signer<'info>,
+
+    #[account(address = ai_dex_pool.load()?.reward_infos[reward_index as usize].mint)]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, address = ai_dex_pool.load()?.reward_infos[reward_index as usize].vault)]
+    pub reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, constraint = destination_token_account.mint == reward_mint.key())]
+    pub destination_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = reward_mint.to_account_info().owner.clone())]
+    pub reward_token_program: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+}
+
+/// Withdraws the reward tokens of a vault in excess of the rewards the positions may still collect.
+///
+/// Only possible once the emissions of the reward have ended, so the budget left over by a
+/// campaign is not stranded in the vault. The rewards of the pool are updated first, so every
+/// token emitted up to the end of the emissions stays in the vault for the positions.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the clawback.
+/// * `reward_index` - The index of the reward to claw back.
+/// * `remaining_accounts_info` - Optional information about the transfer hook accounts of the reward.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the surplus is successfully withdrawn,
+/// or an `Err` if an error occurs.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidRewardIndexError` - If the reward index is invalid.
+/// * `ErrorCode::RewardEmissionsNotTrackedError` - If the reward was initialized before the pool was migrated.
+/// * `ErrorCode::RewardEmissionsNotEndedError` - If the emissions of the reward have no end or have not ended yet.
+pub fn clawback_reward_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ClawbackReward<'info>>,
+    reward_index: u8,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookReward,
+        ],
+    )?;
+
+    let index = reward_index as usize;
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let (transfer_amount, emissions_outstanding) = {
+        let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
+        let reward_infos = next_ai_dex_reward_infos(&ai_dex, timestamp)?;
+        ai_dex.update_rewards(reward_infos, timestamp);
+
+        let transfer_amount = ai_dex.reward_surplus(index, ctx.accounts.reward_vault.amount, timestamp)?;
+        (transfer_amount, ai_dex.reward_emissions[index].emissions_outstanding)
+    };
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.reward_mint,
+        &ctx.accounts.reward_vault,
+        &ctx.accounts.destination_token_account,
+        &ctx.accounts.reward_token_program,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_reward,
+        transfer_amount,
+        transfer_memo::TRANSFER_MEMO_CLAWBACK_REWARD.as_bytes(),
+    )?;
+
+    emit_cpi!(RewardClawedBackEvent {
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        reward_authority: ctx.accounts.reward_authority.key(),
+        reward_mint: ctx.accounts.reward_mint.key(),
+        reward_vault: ctx.accounts.reward_vault.key(),
+        destination_token_account: ctx.accounts.destination_token_account.key(),
+        reward_index,
+        transfer_amount,
+        emissions_outstanding,
+    });
+
+    Ok(())
+}
@@ -1,13 +1,13 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use anchor_spl::memo::Memo;
 
 use crate::util::{parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
+use crate::util::{parse_native_sol_system_program, unwrap_pool_native_sol};
 use crate::{
     constants::transfer_memo,
     state::*,
-    util::{transfer_from_vault_to_owner, verify_position_authority},
+    util::{transfer_from_vault_to_owner, verify_position_authority_or_operator},
 };
 
 #[event]
@@ -26,9 +26,10 @@ pub struct FeesCollectedEvent {
     pub fee_owed_b: u64,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CollectFees<'info> {
-    pub ai_dex_pool: Box<Account<'info, AiDexPool>>,
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
 
     pub position_authority: Signer<'info>,
 
@@ -38,21 +39,21 @@ pub struct CollectFees<'info> {
         constraint = position_token_account.mint == position.position_mint,
         constraint = position_token_account.amount == 1
     )]
-    pub position_token_account: Box<Account<'info, token::TokenAccount>>,
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    #[account(address = ai_dex_pool.token_mint_a)]
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
     pub token_mint_a: InterfaceAccount<'info, Mint>,
-    #[account(address = ai_dex_pool.token_mint_b)]
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
     pub token_mint_b: InterfaceAccount<'info, Mint>,
 
-    #[account(mut, constraint = token_owner_account_a.mint == ai_dex_pool.token_mint_a)]
+    #[account(mut, constraint = token_owner_account_a.mint == ai_dex_pool.load()?.token_mint_a)]
     pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
-    #[account(mut, address = ai_dex_pool.token_vault_a)]
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_a)]
     pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    #[account(mut, constraint = token_owner_account_b.mint == ai_dex_pool.token_mint_b)]
+    #[account(mut, constraint = token_owner_account_b.mint == ai_dex_pool.load()?.token_mint_b)]
     pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
-    #[account(mut, address = ai_dex_pool.token_vault_b)]
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_b)]
     pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(address = token_mint_a.to_account_info().owner.clone())]
@@ -71,7 +72,9 @@ pub struct CollectFees<'info> {
 /// # Arguments
 ///
 /// * `ctx` - The context containing all the accounts required for the fee collection.
-/// * `remaining_accounts_info` - Optional information about remaining accounts.
+/// * `remaining_accounts_info` - Optional information about remaining accounts. A `NativeSol` slice
+///   holding the system program delivers the wSOL fees as SOL by closing the wSOL token account of
+///   the position authority, which must own it and be writable.
 ///
 /// # Returns
 ///
@@ -83,13 +86,19 @@ pub struct CollectFees<'info> {
 /// * The position authority verification fails.
 /// * Parsing the remaining accounts fails.
 /// * Transferring fees from the vault to the owner fails.
+/// * Native SOL delivery is requested for a pool without wSOL, or the wSOL account cannot be closed.
 pub fn collect_fees_handler<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, CollectFees<'info>>,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
 ) -> Result<()> {
-    verify_position_authority(
+    verify_position_authority_or_operator(
         &ctx.accounts.position_token_account,
+        &ctx.accounts.position,
         &ctx.accounts.position_authority,
+        &[
+            ctx.accounts.token_owner_account_a.owner,
+            ctx.accounts.token_owner_account_b.owner,
+        ],
     )?;
 
     // Process remaining accounts
@@ -99,8 +108,14 @@ pub fn collect_fees_handler<'a, 'b, 'c, 'info>(
         &[
             AccountsType::TransferHookA,
             AccountsType::TransferHookB,
+            AccountsType::NativeSol,
         ],
     )?;
+    let native_sol_system_program = parse_native_sol_system_program(
+        &remaining_accounts.native_sol,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_mint_b,
+    )?;
 
     let position = &mut ctx.accounts.position;
 
@@ -134,7 +149,20 @@ pub fn collect_fees_handler<'a, 'b, 'c, 'info>(
         transfer_memo::TRANSFER_MEMO_COLLECT_FEES.as_bytes(),
     )?;
 
-    emit!(FeesCollectedEvent {
+    // Deliver the wSOL fees as SOL by closing the transient wSOL account
+    if native_sol_system_program.is_some() {
+        unwrap_pool_native_sol(
+            &ctx.accounts.position_authority,
+            &ctx.accounts.token_mint_a,
+            &ctx.accounts.token_owner_account_a,
+            &ctx.accounts.token_program_a,
+            &ctx.accounts.token_mint_b,
+            &ctx.accounts.token_owner_account_b,
+            &ctx.accounts.token_program_b,
+        )?;
+    }
+
+    emit_cpi!(FeesCollectedEvent {
         ai_dex: ctx.accounts.ai_dex_pool.key(),
         position_authority: ctx.accounts.position_authority.key(),
         position: ctx.accounts.position.key(),
@@ -0,0 +1,184 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::memo::Memo;
+
+use crate::errors::ErrorCode;
+use crate::util::{
+    parse_remaining_accounts, remaining_accounts_slice, AccountsType, RemainingAccountsInfo,
+};
+use crate::{
+    constants::transfer_memo,
+    state::*,
+    util::{transfer_from_vault_to_owner, verify_position_authority_or_operator},
+};
+
+// Accounts passed in the `PositionAccounts` slice for each position:
+// position and position token account.
+const ACCOUNTS_PER_POSITION: usize = 2;
+
+/// Maximum number of positions whose fees can be collected in a single instruction.
+pub const MAX_POSITIONS_PER_COLLECT: usize = 8;
+
+#[event]
+pub struct FeesCollectedMultiEvent {
+    pub ai_dex: Pubkey,
+    pub position_authority: Pubkey,
+    pub positions: Vec<Pubkey>,
+    pub token_owner_account_a: Pubkey,
+    pub token_owner_account_b: Pubkey,
+    pub fee_owed_a: u64,
+    pub fee_owed_b: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CollectFeesMulti<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    pub position_authority: Signer<'info>,
+
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == ai_dex_pool.load()?.token_mint_a)]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, constraint = token_owner_account_b.mint == ai_dex_pool.load()?.token_mint_b)]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = token_mint_a.to_account_info().owner.clone())]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = token_mint_b.to_account_info().owner.clone())]
+    pub token_program_b: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+}
+
+/// Collects the fees owed to several positions of the same pool at once.
+///
+/// The positions and their position token accounts are passed in the `PositionAccounts` slice of
+/// the remaining accounts. The authority is verified for every position as in `collect_fees`, and
+/// the fees of all the positions are transferred to the owner in a single transfer per token.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the fee collection.
+/// * `remaining_accounts_info` - Information about remaining accounts, including the positions.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the fee collection is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidPositionAccountsError` - If no position or more than `MAX_POSITIONS_PER_COLLECT`
+///   positions are passed, or a position does not belong to the pool.
+/// * `ErrorCode::InvalidPositionTokenAmountError` - If a position token account does not hold its position.
+/// * Any error from verifying the authority of a position.
+pub fn collect_fees_multi_handler<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CollectFeesMulti<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::PositionAccounts,
+        ],
+    )?;
+
+    let position_accounts = remaining_accounts_slice(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        AccountsType::PositionAccounts,
+    );
+    let num_positions = position_accounts.len() / ACCOUNTS_PER_POSITION;
+    if num_positions == 0
+        || num_positions > MAX_POSITIONS_PER_COLLECT
+        || position_accounts.len() % ACCOUNTS_PER_POSITION != 0
+    {
+        return Err(ErrorCode::InvalidPositionAccountsError.into());
+    }
+
+    let ai_dex_pool_key = ctx.accounts.ai_dex_pool.key();
+    let destination_owners = [
+        ctx.accounts.token_owner_account_a.owner,
+        ctx.accounts.token_owner_account_b.owner,
+    ];
+
+    let mut positions = Vec::with_capacity(num_positions);
+    let mut fee_owed_a: u64 = 0;
+    let mut fee_owed_b: u64 = 0;
+    for accounts in position_accounts.chunks(ACCOUNTS_PER_POSITION) {
+        let mut position = Account::<Position>::try_from(&accounts[0])?;
+        let position_token_account = InterfaceAccount::<TokenAccount>::try_from(&accounts[1])?;
+
+        if position.ai_dex_pool != ai_dex_pool_key {
+            return Err(ErrorCode::InvalidPositionAccountsError.into());
+        }
+        if position_token_account.mint != position.position_mint || position_token_account.amount != 1 {
+            return Err(ErrorCode::InvalidPositionTokenAmountError.into());
+        }
+        verify_position_authority_or_operator(
+            &position_token_account,
+            &position,
+            &ctx.accounts.position_authority,
+            &destination_owners,
+        )?;
+
+        fee_owed_a = fee_owed_a
+            .checked_add(position.fee_owed_a)
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        fee_owed_b = fee_owed_b
+            .checked_add(position.fee_owed_b)
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+
+        // Written back right away so a position passed twice is only collected once
+        position.reset_fees_owed();
+        position.exit(&crate::ID)?;
+        positions.push(position.key());
+    }
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_owner_account_a,
+        &ctx.accounts.token_program_a,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_a,
+        fee_owed_a,
+        transfer_memo::TRANSFER_MEMO_COLLECT_FEES.as_bytes(),
+    )?;
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_owner_account_b,
+        &ctx.accounts.token_program_b,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_b,
+        fee_owed_b,
+        transfer_memo::TRANSFER_MEMO_COLLECT_FEES.as_bytes(),
+    )?;
+
+    emit_cpi!(FeesCollectedMultiEvent {
+        ai_dex: ai_dex_pool_key,
+        position_authority: ctx.accounts.position_authority.key(),
+        positions,
+        token_owner_account_a: ctx.accounts.token_owner_account_a.key(),
+        token_owner_account_b: ctx.accounts.token_owner_account_b.key(),
+        fee_owed_a,
+        fee_owed_b,
+    });
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use crate::errors::ErrorCode;
 use crate::util::{parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
 use crate::{constants::transfer_memo, state::*, util::transfer_from_vault_to_owner};
 use anchor_lang::prelude::*;
@@ -13,6 +14,7 @@ pub struct CollectProtocolFeesEvent {
     pub token_mint_b: Pubkey,
     pub token_vault_b: Pubkey,
     pub token_destination_b: Pubkey,
+    pub fee_distribution: Option<Pubkey>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -22,31 +24,34 @@ pub struct AIDexData {
     pub protocol_fee_owed_b: u64,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CollectProtocolFees<'info> {
     pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
 
     #[account(mut, has_one = ai_dex_config)]
-    pub ai_dex_pool: Box<Account<'info, AiDexPool>>,
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
 
-    #[account(address = ai_dex_config.config_authority)]
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::CollectProtocolFees)
+    )]
     pub config_authority: Signer<'info>,
 
-    #[account(address = ai_dex_pool.token_mint_a)]
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
     pub token_mint_a: InterfaceAccount<'info, Mint>,
-    #[account(address = ai_dex_pool.token_mint_b)]
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
     pub token_mint_b: InterfaceAccount<'info, Mint>,
 
-    #[account(mut, address = ai_dex_pool.token_vault_a)]
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_a)]
     pub token_vault_a: InterfaceAccount<'info, TokenAccount>,
 
-    #[account(mut, address = ai_dex_pool.token_vault_b)]
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_b)]
     pub token_vault_b: InterfaceAccount<'info, TokenAccount>,
 
-    #[account(mut, constraint = token_destination_a.mint == ai_dex_pool.token_mint_a)]
+    #[account(mut, constraint = token_destination_a.mint == ai_dex_pool.load()?.token_mint_a)]
     pub token_destination_a: InterfaceAccount<'info, TokenAccount>,
 
-    #[account(mut, constraint = token_destination_b.mint == ai_dex_pool.token_mint_b)]
+    #[account(mut, constraint = token_destination_b.mint == ai_dex_pool.load()?.token_mint_b)]
     pub token_destination_b: InterfaceAccount<'info, TokenAccount>,
 
     #[account(address = token_mint_a.to_account_info().owner.clone())]
@@ -55,6 +60,19 @@ pub struct CollectProtocolFees<'info> {
     pub token_program_b: Interface<'info, TokenInterface>,
     pub memo_program: Program<'info, Memo>,
 
+    /// The extension of the config, if any, holding the delegated collect protocol fees authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+
+    /// The fee distribution of the config, if any, splitting the fees across its recipients
+    #[account(
+        seeds = [b"fee_distribution", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub fee_distribution: Option<Box<Account<'info, FeeDistribution>>>,
 }
 
 /// Handles the collection of protocol fees.
@@ -62,6 +80,10 @@ pub struct CollectProtocolFees<'info> {
 /// This function processes any remaining accounts and transfers the owed protocol fees
 /// from the vault to the destination accounts.
 ///
+/// If a fee distribution is passed, the fees are split across its recipients, whose token
+/// accounts for token A and token B are passed in pairs as the `FeeRecipients` remaining
+/// accounts, in the order of the distribution. The rounding dust goes to the destination accounts.
+///
 /// # Arguments
 ///
 /// * `ctx` - The context containing all the accounts required for the protocol fee collection.
@@ -75,12 +97,17 @@ pub struct CollectProtocolFees<'info> {
 ///
 /// This function will return an error if:
 /// * Parsing the remaining accounts fails.
+/// * The token accounts of the fee recipients do not match the fee distribution.
 /// * Transferring protocol fees from the vault to the destination accounts fails.
-pub fn collect_protocol_fees_handler<'a, 'b, 'c, 'info>(
+pub fn collect_protocol_fees_handler<'a, 'b, 'c: 'info, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, CollectProtocolFees<'info>>,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
 ) -> Result<()> {
     let ai_dex = &ctx.accounts.ai_dex_pool;
+    let (protocol_fee_owed_a, protocol_fee_owed_b) = {
+        let ai_dex_state = ai_dex.load()?;
+        (ai_dex_state.protocol_fee_owed_a, ai_dex_state.protocol_fee_owed_b)
+    };
 
     // Process remaining accounts
     let remaining_accounts = parse_remaining_accounts(
@@ -89,9 +116,39 @@ pub fn collect_protocol_fees_handler<'a, 'b, 'c, 'info>(
         &[
             AccountsType::TransferHookA,
             AccountsType::TransferHookB,
+            AccountsType::FeeRecipients,
         ],
     )?;
-    // Transfer the owed protocol fees from the vault to the destination account for token A.
+
+    let recipients: Vec<FeeRecipient> = match &ctx.accounts.fee_distribution {
+        Some(fee_distribution) => fee_distribution.active_recipients().copied().collect(),
+        None => Vec::new(),
+    };
+    let recipient_accounts = load_fee_recipient_accounts(
+        &recipients,
+        ctx.remaining_accounts,
+        &remaining_accounts.fee_recipients,
+        &ctx.accounts.token_mint_a.key(),
+        &ctx.accounts.token_mint_b.key(),
+    )?;
+
+    // Transfer the owed protocol fees from the vault to the recipients and the destination account for token A.
+    let mut dust_a = protocol_fee_owed_a;
+    for (recipient, (recipient_account_a, _)) in recipients.iter().zip(recipient_accounts.iter()) {
+        let share = FeeDistribution::share(recipient, protocol_fee_owed_a);
+        transfer_from_vault_to_owner(
+            ai_dex,
+            &ctx.accounts.token_mint_a,
+            &ctx.accounts.token_vault_a,
+            recipient_account_a,
+            &ctx.accounts.token_program_a,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_a,
+            share,
+            transfer_memo::TRANSFER_MEMO_COLLECT_PROTOCOL_FEES.as_bytes(),
+        )?;
+        dust_a -= share;
+    }
     transfer_from_vault_to_owner(
         ai_dex,
         &ctx.accounts.token_mint_a,
@@ -100,10 +157,26 @@ pub fn collect_protocol_fees_handler<'a, 'b, 'c, 'info>(
         &ctx.accounts.token_program_a,
         &ctx.accounts.memo_program,
         &remaining_accounts.transfer_hook_a,
-        ai_dex.protocol_fee_owed_a,
+        dust_a,
         transfer_memo::TRANSFER_MEMO_COLLECT_PROTOCOL_FEES.as_bytes(),
     )?;
-    // Transfer the owed protocol fees from the vault to the destination account for token B.
+    // Transfer the owed protocol fees from the vault to the recipients and the destination account for token B.
+    let mut dust_b = protocol_fee_owed_b;
+    for (recipient, (_, recipient_account_b)) in recipients.iter().zip(recipient_accounts.iter()) {
+        let share = FeeDistribution::share(recipient, protocol_fee_owed_b);
+        transfer_from_vault_to_owner(
+            ai_dex,
+            &ctx.accounts.token_mint_b,
+            &ctx.accounts.token_vault_b,
+            recipient_account_b,
+            &ctx.accounts.token_program_b,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_b,
+            share,
+            transfer_memo::TRANSFER_MEMO_COLLECT_PROTOCOL_FEES.as_bytes(),
+        )?;
+        dust_b -= share;
+    }
     transfer_from_vault_to_owner(
         ai_dex,
         &ctx.accounts.token_mint_b,
@@ -112,15 +185,15 @@ pub fn collect_protocol_fees_handler<'a, 'b, 'c, 'info>(
         &ctx.accounts.token_program_b,
         &ctx.accounts.memo_program,
         &remaining_accounts.transfer_hook_b,
-        ai_dex.protocol_fee_owed_b,
+        dust_b,
         transfer_memo::TRANSFER_MEMO_COLLECT_PROTOCOL_FEES.as_bytes(),
     )?;
 
-    emit!(CollectProtocolFeesEvent {
+    emit_cpi!(CollectProtocolFeesEvent {
         ai_dex: AIDexData {
             key: ctx.accounts.ai_dex_pool.key(),
-            protocol_fee_owed_a: ctx.accounts.ai_dex_pool.protocol_fee_owed_a,
-            protocol_fee_owed_b: ctx.accounts.ai_dex_pool.protocol_fee_owed_b,
+            protocol_fee_owed_a,
+            protocol_fee_owed_b,
         },
         token_mint_a: ctx.accounts.token_mint_a.key(),
         token_vault_a: ctx.accounts.token_vault_a.key(),
@@ -128,7 +201,54 @@ pub fn collect_protocol_fees_handler<'a, 'b, 'c, 'info>(
         token_mint_b: ctx.accounts.token_mint_b.key(),
         token_vault_b: ctx.accounts.token_vault_b.key(),
         token_destination_b: ctx.accounts.token_destination_b.key(),
+        fee_distribution: ctx.accounts.fee_distribution.as_ref().map(|fee_distribution| fee_distribution.key()),
     });    
 
-    Ok(ctx.accounts.ai_dex_pool.reset_protocol_fees_owed())
+    Ok(ctx.accounts.ai_dex_pool.load_mut()?.reset_protocol_fees_owed())
+}
+
+/// Loads the token accounts of the fee recipients, passed in pairs for token A and token B.
+///
+/// The accounts are borrowed from the remaining accounts of the instruction, which outlive the
+/// parsed slices.
+///
+/// # Errors
+///
+/// * `FeeRecipientAccountsError` - If the number of accounts does not match the recipients, or
+///   an account is not owned by its recipient or does not hold the expected mint.
+fn load_fee_recipient_accounts<'info>(
+    recipients: &[FeeRecipient],
+    remaining_accounts: &'info [AccountInfo<'info>],
+    fee_recipient_accounts: &Option<Vec<AccountInfo<'info>>>,
+    token_mint_a: &Pubkey,
+    token_mint_b: &Pubkey,
+) -> Result<Vec<(InterfaceAccount<'info, TokenAccount>, InterfaceAccount<'info, TokenAccount>)>> {
+    let accounts = fee_recipient_accounts.as_deref().unwrap_or(&[]);
+    if accounts.len() != recipients.len() * 2 {
+        return Err(ErrorCode::FeeRecipientAccountsError.into());
+    }
+
+    let find = |account: &AccountInfo<'info>| -> Result<&'info AccountInfo<'info>> {
+        remaining_accounts
+            .iter()
+            .find(|remaining_account| remaining_account.key == account.key)
+            .ok_or(ErrorCode::FeeRecipientAccountsError.into())
+    };
+
+    let mut recipient_accounts = Vec::with_capacity(recipients.len());
+    for (recipient, pair) in recipients.iter().zip(accounts.chunks_exact(2)) {
+        let account_a = InterfaceAccount::<TokenAccount>::try_from(find(&pair[0])?)?;
+        let account_b = InterfaceAccount::<TokenAccount>::try_from(find(&pair[1])?)?;
+        if !pair[0].is_writable
+            || !pair[1].is_writable
+            || account_a.owner != recipient.owner
+            || account_b.owner != recipient.owner
+            || account_a.mint != *token_mint_a
+            || account_b.mint != *token_mint_b
+        {
+            return Err(ErrorCode::FeeRecipientAccountsError.into());
+        }
+        recipient_accounts.push((account_a, account_b));
+    }
+    Ok(recipient_accounts)
 }
@@ -0,0 +1,126 @@
+use crate::util::{parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
+use crate::{constants::transfer_memo, state::*, util::transfer_from_vault_to_owner};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::memo::Memo;
+
+#[event]
+pub struct ReferralFeesCollectedEvent {
+    pub ai_dex_pool: Pubkey,
+    pub referrer: Pubkey,
+    pub referral_fees: Pubkey,
+    pub token_mint_a: Pubkey,
+    pub token_destination_a: Pubkey,
+    pub fee_owed_a: u64,
+    pub token_mint_b: Pubkey,
+    pub token_destination_b: Pubkey,
+    pub fee_owed_b: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CollectReferralFees<'info> {
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    pub referrer: Signer<'info>,
+
+    #[account(mut, has_one = ai_dex_pool, has_one = referrer)]
+    pub referral_fees: Box<Account<'info, ReferralFees>>,
+
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_a)]
+    pub token_vault_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_b)]
+    pub token_vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, constraint = token_destination_a.mint == ai_dex_pool.load()?.token_mint_a)]
+    pub token_destination_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, constraint = token_destination_b.mint == ai_dex_pool.load()?.token_mint_b)]
+    pub token_destination_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = token_mint_a.to_account_info().owner.clone())]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = token_mint_b.to_account_info().owner.clone())]
+    pub token_program_b: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+}
+
+/// Collects the referral fees accrued by a referrer on an AI DEX pool.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the referral fee collection.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the referral fee collection is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * Parsing the remaining accounts fails.
+/// * Transferring the referral fees from the vaults to the destination accounts fails.
+pub fn collect_referral_fees_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CollectReferralFees<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let ai_dex = &ctx.accounts.ai_dex_pool;
+    let fee_owed_a = ctx.accounts.referral_fees.fee_owed_a;
+    let fee_owed_b = ctx.accounts.referral_fees.fee_owed_b;
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+        ],
+    )?;
+
+    transfer_from_vault_to_owner(
+        ai_dex,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_destination_a,
+        &ctx.accounts.token_program_a,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_a,
+        fee_owed_a,
+        transfer_memo::TRANSFER_MEMO_COLLECT_REFERRAL_FEES.as_bytes(),
+    )?;
+    transfer_from_vault_to_owner(
+        ai_dex,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_destination_b,
+        &ctx.accounts.token_program_b,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_b,
+        fee_owed_b,
+        transfer_memo::TRANSFER_MEMO_COLLECT_REFERRAL_FEES.as_bytes(),
+    )?;
+
+    ai_dex.load_mut()?.collect_referral_fees(fee_owed_a, fee_owed_b)?;
+    ctx.accounts.referral_fees.reset_fees_owed();
+
+    emit_cpi!(ReferralFeesCollectedEvent {
+        ai_dex_pool: ai_dex.key(),
+        referrer: ctx.accounts.referrer.key(),
+        referral_fees: ctx.accounts.referral_fees.key(),
+        token_mint_a: ctx.accounts.token_mint_a.key(),
+        token_destination_a: ctx.accounts.token_destination_a.key(),
+        fee_owed_a,
+        token_mint_b: ctx.accounts.token_mint_b.key(),
+        token_destination_b: ctx.accounts.token_destination_b.key(),
+        fee_owed_b,
+    });
+
+    Ok(())
+}
@@ -1,5 +1,4 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use anchor_spl::memo::Memo;
 
@@ -22,10 +21,12 @@ pub struct RewardCollectedEvent {
     pub updated_amount_owed: u64,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(reward_index: u8)]
 pub struct CollectReward<'info> {
-    pub ai_dex_pool: Box<Account<'info, AiDexPool>>,
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
 
     pub position_authority: Signer<'info>,
 
@@ -35,17 +36,17 @@ pub struct CollectReward<'info> {
         constraint = position_token_account.mint == position.position_mint,
         constraint = position_token_account.amount == 1
     )]
-    pub position_token_account: Box<Account<'info, token::TokenAccount>>,
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(mut,
-        constraint = reward_owner_account.mint == ai_dex_pool.reward_infos[reward_index as usize].mint
+        constraint = reward_owner_account.mint == ai_dex_pool.load()?.reward_infos[reward_index as usize].mint
     )]
     pub reward_owner_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    #[account(address = ai_dex_pool.reward_infos[reward_index as usize].mint)]
+    #[account(address = ai_dex_pool.load()?.reward_infos[reward_index as usize].mint)]
     pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
 
-    #[account(mut, address = ai_dex_pool.reward_infos[reward_index as usize].vault)]
+    #[account(mut, address = ai_dex_pool.load()?.reward_infos[reward_index as usize].vault)]
     pub reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(address = reward_mint.to_account_info().owner.clone())]
@@ -95,6 +96,7 @@ pub fn collect_reward_handler<'a, 'b, 'c, 'info>(
     );
 
     position.update_reward_owed(index, updated_amount_owed);
+    ctx.accounts.ai_dex_pool.load_mut()?.record_reward_collected(index, transfer_amount);
 
     transfer_from_vault_to_owner(
         &ctx.accounts.ai_dex_pool,
@@ -108,7 +110,7 @@ pub fn collect_reward_handler<'a, 'b, 'c, 'info>(
         transfer_memo::TRANSFER_MEMO_COLLECT_REWARD.as_bytes(),
     )?;
 
-    emit!(RewardCollectedEvent {
+    emit_cpi!(RewardCollectedEvent {
         position_key: ctx.accounts.position.key(),
         position_authority: ctx.accounts.position_authority.key(),
         reward_mint: ctx.accounts.reward_mint.key(),
@@ -122,7 +124,7 @@ pub fn collect_reward_handler<'a, 'b, 'c, 'info>(
     Ok(())
 }
 
-fn calculate_collect_reward(position_reward: PositionRewardInfo, vault_amount: u64) -> (u64, u64) {
+pub(crate) fn calculate_collect_reward(position_reward: PositionRewardInfo, vault_amount: u64) -> (u64, u64) {
     let amount_owed = position_reward.amount_owed;
     let (transfer_amount, updated_amount_owed) = if amount_owed > vault_amount {
         (vault_amount, amount_owed - vault_amount)
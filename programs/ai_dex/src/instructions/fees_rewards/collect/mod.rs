@@ -1,7 +1,21 @@
+pub mod clawback_reward;
 pub mod collect_fees;
+pub mod collect_fees_multi;
 pub mod collect_protocol_fees;
+pub mod collect_referral_fees;
 pub mod collect_reward;
+pub mod quote_collect;
+pub mod skim_excess;
+pub mod sweep_protocol_fees;
+pub mod withdraw_treasury;
 
+pub use clawback_reward::*;
 pub use collect_fees::*;
+pub use collect_fees_multi::*;
 pub use collect_protocol_fees::*;
-pub use collect_reward::*;
\ No newline at end of file
+pub use collect_referral_fees::*;
+pub use collect_reward::*;
+pub use quote_collect::*;
+pub use skim_excess::*;
+pub use sweep_protocol_fees::*;
+pub use withdraw_treasury::*;
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    orchestrator::liquidity_orchestrator::calculate_fee_and_reward_growths, state::*,
+    util::{to_timestamp_u64, TickArrayAccount},
+};
+
+/// The fees and rewards a position can collect, as quoted by `quote_collect`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CollectQuote {
+    pub fee_owed_a: u64,
+    pub fee_owed_b: u64,
+    pub reward_owed: [u64; NUM_REWARDS],
+}
+
+#[derive(Accounts)]
+pub struct QuoteCollect<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(has_one = ai_dex_pool)]
+    pub position: Account<'info, Position>,
+
+    /// CHECK: A fixed or dynamic tick array of the pool, checked in the handler
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: A fixed or dynamic tick array of the pool, checked in the handler
+    pub tick_array_upper: UncheckedAccount<'info>,
+}
+
+/// Quotes the fees and rewards a position can collect now.
+///
+/// The fees and rewards of the position are updated to the current timestamp as
+/// `update_fees_and_rewards` would, but no state is changed. The amounts are those owed by the
+/// pool, before the transfer fees of their mints. The quote is returned to the caller through the
+/// instruction return data.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the pool, the position and its tick arrays.
+///
+/// # Returns
+///
+/// * `Result<CollectQuote>` - The fees and rewards owed to the position.
+///
+/// # Errors
+///
+/// * Any error from loading the tick arrays of the position.
+pub fn quote_collect_handler(ctx: Context<QuoteCollect>) -> Result<CollectQuote> {
+    let position = &ctx.accounts.position;
+    let mut quote = CollectQuote {
+        fee_owed_a: position.fee_owed_a,
+        fee_owed_b: position.fee_owed_b,
+        reward_owed: position.reward_infos.map(|reward_info| reward_info.amount_owed),
+    };
+    // Without liquidity, the position accrues nothing beyond what it is owed
+    if position.liquidity == 0 {
+        return Ok(quote);
+    }
+
+    let ai_dex_pool_key = ctx.accounts.ai_dex_pool.key();
+    let tick_array_lower = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_lower, &ai_dex_pool_key)?;
+    let tick_array_upper = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_upper, &ai_dex_pool_key)?;
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let (position_update, _) = calculate_fee_and_reward_growths(
+        &*ctx.accounts.ai_dex_pool.load()?,
+        position,
+        &tick_array_lower,
+        &tick_array_upper,
+        timestamp,
+    )?;
+    quote.fee_owed_a = position_update.fee_owed_a;
+    quote.fee_owed_b = position_update.fee_owed_b;
+    quote.reward_owed = position_update.reward_infos.map(|reward_info| reward_info.amount_owed);
+
+    Ok(quote)
+}
@@ -0,0 +1,132 @@
+use crate::util::{parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
+use crate::{constants::transfer_memo, state::*, util::transfer_from_vault_to_owner};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::memo::Memo;
+
+#[event]
+pub struct ExcessSkimmedEvent {
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub treasury: Pubkey,
+    pub token_mint_a: Pubkey,
+    pub treasury_token_account_a: Pubkey,
+    pub excess_a: u64,
+    pub token_mint_b: Pubkey,
+    pub treasury_token_account_b: Pubkey,
+    pub excess_b: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SkimExcess<'info> {
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    /// CHECK: The treasury of the config, a PDA owning the treasury token accounts
+    #[account(seeds = [b"treasury", ai_dex_config.key().as_ref()], bump)]
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_a)]
+    pub token_vault_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_b)]
+    pub token_vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::mint = token_mint_a, token::authority = treasury)]
+    pub treasury_token_account_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = token_mint_b, token::authority = treasury)]
+    pub treasury_token_account_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = token_mint_a.to_account_info().owner.clone())]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = token_mint_b.to_account_info().owner.clone())]
+    pub token_program_b: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+}
+
+/// Skims the tokens sent to the vaults of a pool outside of the program into the treasury of its config.
+///
+/// Every transfer of the program into or out of the vaults is accounted for in the reserves of the
+/// pool, which cover the liquidity, the fees and the balances of the orders. Anything beyond the
+/// reserves was sent to the vaults directly and is otherwise stuck. The instruction is
+/// permissionless, since the excess can only reach token accounts owned by the treasury PDA.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the skim.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the skim is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * The vault reserves of the pool are not tracked, for pools initialized before the reserves were.
+/// * Parsing the remaining accounts fails.
+/// * Transferring the excess from the vaults to the treasury token accounts fails.
+pub fn skim_excess_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, SkimExcess<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let ai_dex = &ctx.accounts.ai_dex_pool;
+    let (excess_a, excess_b) = ai_dex.load_mut()?.take_vault_surplus(
+        ctx.accounts.token_vault_a.amount,
+        ctx.accounts.token_vault_b.amount,
+    )?;
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+        ],
+    )?;
+
+    transfer_from_vault_to_owner(
+        ai_dex,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.treasury_token_account_a,
+        &ctx.accounts.token_program_a,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_a,
+        excess_a,
+        transfer_memo::TRANSFER_MEMO_SKIM_EXCESS.as_bytes(),
+    )?;
+    transfer_from_vault_to_owner(
+        ai_dex,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.treasury_token_account_b,
+        &ctx.accounts.token_program_b,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_b,
+        excess_b,
+        transfer_memo::TRANSFER_MEMO_SKIM_EXCESS.as_bytes(),
+    )?;
+
+    emit_cpi!(ExcessSkimmedEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        treasury: ctx.accounts.treasury.key(),
+        token_mint_a: ctx.accounts.token_mint_a.key(),
+        treasury_token_account_a: ctx.accounts.treasury_token_account_a.key(),
+        excess_a,
+        token_mint_b: ctx.accounts.token_mint_b.key(),
+        treasury_token_account_b: ctx.accounts.treasury_token_account_b.key(),
+        excess_b,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use crate::util::{parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
+use crate::{constants::transfer_memo, state::*, util::transfer_from_vault_to_owner};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::memo::Memo;
+
+#[event]
+pub struct ProtocolFeesSweptEvent {
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub treasury: Pubkey,
+    pub token_mint_a: Pubkey,
+    pub treasury_token_account_a: Pubkey,
+    pub protocol_fee_owed_a: u64,
+    pub token_mint_b: Pubkey,
+    pub treasury_token_account_b: Pubkey,
+    pub protocol_fee_owed_b: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SweepProtocolFees<'info> {
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    /// CHECK: The treasury of the config, a PDA owning the treasury token accounts
+    #[account(seeds = [b"treasury", ai_dex_config.key().as_ref()], bump)]
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_a)]
+    pub token_vault_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_b)]
+    pub token_vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::mint = token_mint_a, token::authority = treasury)]
+    pub treasury_token_account_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = token_mint_b, token::authority = treasury)]
+    pub treasury_token_account_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = token_mint_a.to_account_info().owner.clone())]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = token_mint_b.to_account_info().owner.clone())]
+    pub token_program_b: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+}
+
+/// Sweeps the protocol fees owed by a pool into the treasury of its config.
+///
+/// The instruction is permissionless: the fees can only reach token accounts owned by the treasury
+/// PDA of the config, so routine collections no longer need the config authority to sign. Funds
+/// leave the treasury through `withdraw_treasury`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the sweep.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the sweep is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * Parsing the remaining accounts fails.
+/// * Transferring protocol fees from the vaults to the treasury token accounts fails.
+pub fn sweep_protocol_fees_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, SweepProtocolFees<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let ai_dex = &ctx.accounts.ai_dex_pool;
+    let (protocol_fee_owed_a, protocol_fee_owed_b) = {
+        let ai_dex_state = ai_dex.load()?;
+        (ai_dex_state.protocol_fee_owed_a, ai_dex_state.protocol_fee_owed_b)
+    };
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+        ],
+    )?;
+
+    transfer_from_vault_to_owner(
+        ai_dex,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.treasury_token_account_a,
+        &ctx.accounts.token_program_a,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_a,
+        protocol_fee_owed_a,
+        transfer_memo::TRANSFER_MEMO_COLLECT_PROTOCOL_FEES.as_bytes(),
+    )?;
+    transfer_from_vault_to_owner(
+        ai_dex,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.treasury_token_account_b,
+        &ctx.accounts.token_program_b,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_b,
+        protocol_fee_owed_b,
+        transfer_memo::TRANSFER_MEMO_COLLECT_PROTOCOL_FEES.as_bytes(),
+    )?;
+
+    ctx.accounts.ai_dex_pool.load_mut()?.reset_protocol_fees_owed();
+
+    emit_cpi!(ProtocolFeesSweptEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        treasury: ctx.accounts.treasury.key(),
+        token_mint_a: ctx.accounts.token_mint_a.key(),
+        treasury_token_account_a: ctx.accounts.treasury_token_account_a.key(),
+        protocol_fee_owed_a,
+        token_mint_b: ctx.accounts.token_mint_b.key(),
+        treasury_token_account_b: ctx.accounts.treasury_token_account_b.key(),
+        protocol_fee_owed_b,
+    });
+
+    Ok(())
+}
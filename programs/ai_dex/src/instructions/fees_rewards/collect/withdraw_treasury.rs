@@ -0,0 +1,93 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+#[event]
+pub struct TreasuryWithdrawnEvent {
+    pub ai_dex_config: Pubkey,
+    pub config_authority: Pubkey,
+    pub treasury: Pubkey,
+    pub token_mint: Pubkey,
+    pub treasury_token_account: Pubkey,
+    pub token_destination: Pubkey,
+    pub amount: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::CollectProtocolFees)
+    )]
+    pub config_authority: Signer<'info>,
+
+    /// CHECK: The treasury of the config, a PDA owning the treasury token accounts
+    #[account(seeds = [b"treasury", ai_dex_config.key().as_ref()], bump)]
+    pub treasury: UncheckedAccount<'info>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, token::mint = token_mint, token::authority = treasury)]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::mint = token_mint)]
+    pub token_destination: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = token_mint.to_account_info().owner.clone())]
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The extension of the config, if any, holding the delegated collect protocol fees authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+}
+
+/// Withdraws tokens from a treasury token account of an AI DEX config.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the withdrawal.
+/// * `amount` - The amount of tokens to withdraw.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the withdrawal is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// This function will return an error if the treasury token account does not hold the amount.
+pub fn withdraw_treasury_handler(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+    let ai_dex_config_key = ctx.accounts.ai_dex_config.key();
+    let treasury_seeds: &[&[u8]] = &[b"treasury", ai_dex_config_key.as_ref(), &[ctx.bumps.treasury]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.treasury_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.token_destination.to_account_info(),
+                authority: ctx.accounts.treasury.to_account_info(),
+            },
+            &[treasury_seeds],
+        ),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    emit_cpi!(TreasuryWithdrawnEvent {
+        ai_dex_config: ai_dex_config_key,
+        config_authority: ctx.accounts.config_authority.key(),
+        treasury: ctx.accounts.treasury.key(),
+        token_mint: ctx.accounts.token_mint.key(),
+        treasury_token_account: ctx.accounts.treasury_token_account.key(),
+        token_destination: ctx.accounts.token_destination.key(),
+        amount,
+    });
+
+    Ok(())
+}
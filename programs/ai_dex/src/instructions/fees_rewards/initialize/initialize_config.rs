@@ -10,6 +10,7 @@ pub struct ConfigInitializedEvent {
     pub default_protocol_fee_rate: u16,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct InitializeConfig<'info> {
     #[account(init, payer = funder, space = AiDexConfig::LEN)]
@@ -51,7 +52,7 @@ pub fn initialize_config_handler(
         default_protocol_fee_rate,
     )?;
 
-    emit!(ConfigInitializedEvent {
+    emit_cpi!(ConfigInitializedEvent {
         config_key: config.key(),
         funder: ctx.accounts.funder.key(),
         config_authority,
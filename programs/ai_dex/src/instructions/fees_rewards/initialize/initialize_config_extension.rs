@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[event]
+pub struct ConfigExtensionInitializedEvent {
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_config_extension: Pubkey,
+    pub funder: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeConfigExtension<'info> {
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    #[account(
+        init,
+        payer = funder,
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+        space = AiDexConfigExtension::LEN
+    )]
+    pub ai_dex_config_extension: Account<'info, AiDexConfigExtension>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Initializes the extension of a config, holding the settings added after the config layout was fixed.
+///
+/// The position NFT metadata starts with the default name, symbol and URI.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for initializing the config extension.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the config extension is successfully initialized,
+/// or an `Err` if an error occurs.
+pub fn initialize_config_extension_handler(ctx: Context<InitializeConfigExtension>) -> Result<()> {
+    ctx.accounts
+        .ai_dex_config_extension
+        .initialize(ctx.accounts.ai_dex_config.key())?;
+
+    emit_cpi!(ConfigExtensionInitializedEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_config_extension: ctx.accounts.ai_dex_config_extension.key(),
+        funder: ctx.accounts.funder.key(),
+    });
+
+    Ok(())
+}
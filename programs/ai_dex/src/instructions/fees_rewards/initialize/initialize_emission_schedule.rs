@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[event]
+pub struct EmissionScheduleInitializedEvent {
+    pub ai_dex_pool: Pubkey,
+    pub reward_index: u8,
+    pub reward_authority: Pubkey,
+    pub emission_schedule: Pubkey,
+    pub segments: Vec<EmissionSegment>,
+    pub funder: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct InitializeEmissionSchedule<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(address = ai_dex_pool.load()?.reward_infos[reward_index as usize].authority)]
+    pub reward_authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = funder,
+        seeds = [b"emission_schedule", ai_dex_pool.key().as_ref(), reward_index.to_le_bytes().as_ref()],
+        bump,
+        space = EmissionSchedule::LEN
+    )]
+    pub emission_schedule: Box<Account<'info, EmissionSchedule>>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Initializes the emission schedule of a reward of an AI DEX pool.
+///
+/// Once it exists, anyone may call `advance_emission_schedule` to apply the emission rate of the
+/// active segment, so campaigns with step-downs need no authority transaction at each boundary.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the initialization.
+/// * `reward_index` - The index of the reward.
+/// * `segments` - The emission segments, sorted by time.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the schedule is successfully initialized, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `InvalidRewardIndexError` - If the reward index is out of bounds.
+/// * `InvalidEmissionScheduleError` - If the segments are invalid.
+pub fn initialize_emission_schedule_handler(
+    ctx: Context<InitializeEmissionSchedule>,
+    reward_index: u8,
+    segments: Vec<EmissionSegment>,
+) -> Result<()> {
+    ctx.accounts
+        .emission_schedule
+        .initialize(ctx.accounts.ai_dex_pool.key(), reward_index, &segments)?;
+
+    emit_cpi!(EmissionScheduleInitializedEvent {
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        reward_index,
+        reward_authority: ctx.accounts.reward_authority.key(),
+        emission_schedule: ctx.accounts.emission_schedule.key(),
+        segments,
+        funder: ctx.accounts.funder.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[event]
+pub struct FeeDiscountRegistryInitializedEvent {
+    pub ai_dex_config: Pubkey,
+    pub config_authority: Pubkey,
+    pub fee_discount_registry: Pubkey,
+    pub tiers: Vec<FeeDiscountTier>,
+    pub funder: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeFeeDiscountRegistry<'info> {
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::Fee)
+    )]
+    pub config_authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = funder,
+        seeds = [b"fee_discount_registry", ai_dex_config.key().as_ref()],
+        bump,
+        space = FeeDiscountRegistry::LEN
+    )]
+    pub fee_discount_registry: Box<Account<'info, FeeDiscountRegistry>>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The extension of the config, if any, holding the delegated fee authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+}
+
+/// Initializes the fee discount registry of an AI DEX config.
+///
+/// Once it exists, a swap passing it along with a token account of the token authority gets
+/// the fee discount of the best tier the token account qualifies for.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the initialization.
+/// * `tiers` - The fee discount tiers.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the registry is successfully initialized, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `InvalidFeeDiscountTiersError` - If the tiers are invalid.
+pub fn initialize_fee_discount_registry_handler(
+    ctx: Context<InitializeFeeDiscountRegistry>,
+    tiers: Vec<FeeDiscountTier>,
+) -> Result<()> {
+    ctx.accounts
+        .fee_discount_registry
+        .initialize(ctx.accounts.ai_dex_config.key(), &tiers)?;
+
+    emit_cpi!(FeeDiscountRegistryInitializedEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        fee_discount_registry: ctx.accounts.fee_discount_registry.key(),
+        tiers,
+        funder: ctx.accounts.funder.key(),
+    });
+
+    Ok(())
+}
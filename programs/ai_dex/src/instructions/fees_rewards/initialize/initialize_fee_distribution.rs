@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[event]
+pub struct FeeDistributionInitializedEvent {
+    pub ai_dex_config: Pubkey,
+    pub config_authority: Pubkey,
+    pub fee_distribution: Pubkey,
+    pub recipients: Vec<FeeRecipient>,
+    pub funder: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeFeeDistribution<'info> {
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::CollectProtocolFees)
+    )]
+    pub config_authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = funder,
+        seeds = [b"fee_distribution", ai_dex_config.key().as_ref()],
+        bump,
+        space = FeeDistribution::LEN
+    )]
+    pub fee_distribution: Box<Account<'info, FeeDistribution>>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The extension of the config, if any, holding the delegated collect protocol fees authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+}
+
+/// Initializes the fee distribution of an AI DEX config.
+///
+/// Once it exists, `collect_protocol_fees` splits the collected protocol fees across its
+/// recipients according to their weights.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the initialization.
+/// * `recipients` - The recipients of the protocol fees and their weights in basis points.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the fee distribution is successfully initialized, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `InvalidFeeDistributionError` - If the recipients are invalid or their weights do not sum up to 10000 bps.
+pub fn initialize_fee_distribution_handler(
+    ctx: Context<InitializeFeeDistribution>,
+    recipients: Vec<FeeRecipient>,
+) -> Result<()> {
+    ctx.accounts
+        .fee_distribution
+        .initialize(ctx.accounts.ai_dex_config.key(), &recipients)?;
+
+    emit_cpi!(FeeDistributionInitializedEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        fee_distribution: ctx.accounts.fee_distribution.key(),
+        recipients,
+        funder: ctx.accounts.funder.key(),
+    });
+
+    Ok(())
+}
@@ -11,6 +11,7 @@ pub struct FeeTierInitializedEvent {
     pub default_fee_rate: u16,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(tick_spacing: u16)]
 pub struct InitializeFeeTier<'info> {
@@ -30,10 +31,19 @@ pub struct InitializeFeeTier<'info> {
     #[account(mut)]
     pub funder: Signer<'info>,
 
-    #[account(address = config.config_authority)]
+    #[account(
+        address = config_role_authority(&config, ai_dex_config_extension.as_deref(), ConfigRole::Fee)
+    )]
     pub config_authority: Signer<'info>,
 
     pub system_program: Program<'info, System>,
+
+    /// The extension of the config, if any, holding the delegated fee authority
+    #[account(
+        seeds = [b"config_extension", config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
 }
 
 /// Initializes a fee tier with specified tick spacing and default fee rate.
@@ -61,7 +71,7 @@ pub fn initialize_fee_tier_handler(
         .fee_tier
         .initialize(&ctx.accounts.config, tick_spacing, default_fee_rate)?;
 
-    emit!(FeeTierInitializedEvent {
+    emit_cpi!(FeeTierInitializedEvent {
         config_key: ctx.accounts.config.key(),
         fee_tier_key: ctx.accounts.fee_tier.key(),
         funder: ctx.accounts.funder.key(),
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::ErrorCode,
+    state::*,
+    util::create_program_account,
+};
+
+/// The maximum number of fee tiers that can be initialized in a single instruction.
+pub const MAX_FEE_TIERS_PER_INITIALIZE: usize = 8;
+
+#[event]
+pub struct FeeTiersInitializedEvent {
+    pub config_key: Pubkey,
+    pub funder: Pubkey,
+    pub config_authority: Pubkey,
+    pub fee_tier_keys: Vec<Pubkey>,
+    pub fee_tiers: Vec<FeeTierParams>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeFeeTiers<'info> {
+    pub config: Box<Account<'info, AiDexConfig>>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+        address = config_role_authority(&config, ai_dex_config_extension.as_deref(), ConfigRole::Fee)
+    )]
+    pub config_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The extension of the config, if any, holding the delegated fee authority
+    #[account(
+        seeds = [b"config_extension", config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+
+    // Remaining accounts, one for each fee tier in the same order:
+    // - fee_tier (mut), the uninitialized fee tier PDA
+}
+
+/// Initializes several fee tiers in a single instruction, typically during deployment.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the accounts required for initialization.
+/// * `fee_tiers` - The tick spacing and default fee rate of each fee tier to initialize.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if all fee tiers are initialized, otherwise returns an error.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - No fee tier is given, or more than `MAX_FEE_TIERS_PER_INITIALIZE`.
+/// - A remaining account is not the fee tier PDA of its tick spacing.
+/// - A fee tier already exists, or its default fee rate exceeds the maximum fee rate.
+pub fn initialize_fee_tiers_handler<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, InitializeFeeTiers<'info>>,
+    fee_tiers: Vec<FeeTierParams>,
+) -> Result<()> {
+    if fee_tiers.is_empty() || fee_tiers.len() > MAX_FEE_TIERS_PER_INITIALIZE {
+        return Err(ErrorCode::InvalidFeeTierBatchLengthError.into());
+    }
+    if ctx.remaining_accounts.len() != fee_tiers.len() {
+        return Err(ErrorCode::InvalidFeeTierAccountError.into());
+    }
+
+    let config_key = ctx.accounts.config.key();
+    let rent = Rent::get()?;
+
+    let mut fee_tier_keys = Vec::with_capacity(fee_tiers.len());
+    for (params, fee_tier_info) in fee_tiers.iter().zip(ctx.remaining_accounts.iter()) {
+        let tick_spacing_seed = params.tick_spacing.to_le_bytes();
+        let (fee_tier_key, bump) = Pubkey::find_program_address(
+            &[b"fee_tier", config_key.as_ref(), tick_spacing_seed.as_ref()],
+            &crate::ID,
+        );
+        if fee_tier_info.key() != fee_tier_key {
+            return Err(ErrorCode::InvalidFeeTierAccountError.into());
+        }
+
+        create_program_account(
+            &ctx.accounts.funder,
+            fee_tier_info,
+            &ctx.accounts.system_program,
+            &[b"fee_tier", config_key.as_ref(), tick_spacing_seed.as_ref(), &[bump]],
+            FeeTier::LEN,
+            &rent,
+        )?;
+
+        let mut fee_tier = Account::<FeeTier>::try_from_unchecked(fee_tier_info)?;
+        fee_tier.initialize(&ctx.accounts.config, params.tick_spacing, params.default_fee_rate)?;
+        // Write the account discriminator, as `init` would do for a single fee tier
+        fee_tier.exit(&crate::ID)?;
+
+        fee_tier_keys.push(fee_tier_key);
+    }
+
+    emit_cpi!(FeeTiersInitializedEvent {
+        config_key,
+        funder: ctx.accounts.funder.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        fee_tier_keys,
+        fee_tiers,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[event]
+pub struct ReferralFeesInitializedEvent {
+    pub ai_dex_pool: Pubkey,
+    pub referrer: Pubkey,
+    pub referral_fees: Pubkey,
+    pub funder: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeReferralFees<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    /// CHECK: The referrer collecting the fees, any account can be a referrer
+    pub referrer: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = funder,
+        seeds = [b"referral_fees", ai_dex_pool.key().as_ref(), referrer.key().as_ref()],
+        bump,
+        space = ReferralFees::LEN
+    )]
+    pub referral_fees: Box<Account<'info, ReferralFees>>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Initializes the account accruing the referral fees of a referrer on an AI DEX pool.
+///
+/// Anyone can initialize it, typically the frontend or bot referring the swaps.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the initialization.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the referral fees are successfully initialized, otherwise returns an error.
+pub fn initialize_referral_fees_handler(ctx: Context<InitializeReferralFees>) -> Result<()> {
+    ctx.accounts
+        .referral_fees
+        .initialize(ctx.accounts.ai_dex_pool.key(), ctx.accounts.referrer.key());
+
+    emit_cpi!(ReferralFeesInitializedEvent {
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        referrer: ctx.accounts.referrer.key(),
+        referral_fees: ctx.accounts.referral_fees.key(),
+        funder: ctx.accounts.funder.key(),
+    });
+
+    Ok(())
+}
@@ -4,7 +4,7 @@ use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::{
     errors::ErrorCode,
     state::AiDexPool,
-    util::{is_token_wrapper_initialized, is_supported_token_mint}
+    util::{load_token_wrapper, is_supported_token_mint}
 };
 
 #[event]
@@ -19,17 +19,18 @@ pub struct RewardInitializedEvent {
     pub is_token_wrapper_initialized: bool,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(reward_index: u8)]
 pub struct InitializeReward<'info> {
-    #[account(address = ai_dex_pool.reward_infos[reward_index as usize].authority)]
+    #[account(address = ai_dex_pool.load()?.reward_infos[reward_index as usize].authority)]
     pub reward_authority: Signer<'info>,
 
     #[account(mut)]
     pub funder: Signer<'info>,
 
     #[account(mut)]
-    pub ai_dex_pool: Box<Account<'info, AiDexPool>>,
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
 
     pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
 
@@ -37,7 +38,7 @@ pub struct InitializeReward<'info> {
     #[account(
         seeds = [
             b"token_wrapper",
-            ai_dex_pool.ai_dex_config.as_ref(),
+            ai_dex_pool.load()?.ai_dex_config.as_ref(),
             reward_mint.key().as_ref()],
             bump,
     )]
@@ -73,16 +74,17 @@ pub struct InitializeReward<'info> {
 ///
 /// * `ErrorCode::UnsupportedTokenMintError` - If the token mint is not supported.
 pub fn initialize_reward_handler(ctx: Context<InitializeReward>, reward_index: u8) -> Result<()> {
-    let ai_dex = &mut ctx.accounts.ai_dex_pool;
+    let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
 
     // Don't allow initializing a reward with an unsupported token mint
-    let is_token_wrapper_initialized = is_token_wrapper_initialized(
+    let reward_token_wrapper = load_token_wrapper(
         ai_dex.ai_dex_config,
         ctx.accounts.reward_mint.key(),
         &ctx.accounts.reward_token_wrapper,
     )?;
+    let is_token_wrapper_initialized = reward_token_wrapper.is_some();
   
-    if !is_supported_token_mint(&ctx.accounts.reward_mint, is_token_wrapper_initialized).unwrap() {
+    if !is_supported_token_mint(&ctx.accounts.reward_mint, reward_token_wrapper.as_ref()).unwrap() {
         return Err(ErrorCode::UnsupportedTokenMintError.into());
     }  
 
@@ -92,7 +94,7 @@ pub fn initialize_reward_handler(ctx: Context<InitializeReward>, reward_index: u
         ctx.accounts.reward_vault.key(),
     )?;
 
-    emit!(RewardInitializedEvent {
+    emit_cpi!(RewardInitializedEvent {
         reward_index,
         ai_dex: ctx.accounts.ai_dex_pool.key(),
         reward_authority: ctx.accounts.reward_authority.key(),
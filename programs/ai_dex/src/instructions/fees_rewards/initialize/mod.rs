@@ -1,7 +1,19 @@
 pub mod initialize_config;
+pub mod initialize_config_extension;
+pub mod initialize_emission_schedule;
+pub mod initialize_fee_discount_registry;
+pub mod initialize_fee_distribution;
 pub mod initialize_fee_tier;
+pub mod initialize_fee_tiers;
+pub mod initialize_referral_fees;
 pub mod initialize_reward;
 
 pub use initialize_config::*;
+pub use initialize_config_extension::*;
+pub use initialize_emission_schedule::*;
+pub use initialize_fee_discount_registry::*;
+pub use initialize_fee_distribution::*;
 pub use initialize_fee_tier::*;
+pub use initialize_fee_tiers::*;
+pub use initialize_referral_fees::*;
 pub use initialize_reward::*;
\ No newline at end of file
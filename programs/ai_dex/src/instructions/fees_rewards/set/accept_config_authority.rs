@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::state::AiDexConfig;
+
+#[event]
+pub struct ConfigAuthorityAcceptedEvent {
+    pub ai_dex_config: Pubkey,
+    pub old_config_authority: Pubkey,
+    pub new_config_authority: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptConfigAuthority<'info> {
+    #[account(mut)]
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(address = ai_dex_config.pending_config_authority)]
+    pub pending_config_authority: Signer<'info>,
+}
+
+/// Accepts the config authority proposed with `propose_config_authority`.
+///
+/// Only the pending config authority has permission to invoke this instruction.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for accepting the config authority.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the config authority is successfully accepted,
+/// or an `Err` if an error occurs.
+pub fn accept_config_authority_handler(
+    ctx: Context<AcceptConfigAuthority>
+) -> Result<()> {
+    let old_config_authority = ctx.accounts.ai_dex_config.config_authority;
+    ctx.accounts.ai_dex_config.accept_config_authority();
+
+    emit_cpi!(ConfigAuthorityAcceptedEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        old_config_authority,
+        new_config_authority: ctx.accounts.pending_config_authority.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AiDexConfigExtension, ConfigRole};
+
+#[event]
+pub struct FeeAuthorityAcceptedEvent {
+    pub ai_dex_config: Pubkey,
+    pub old_fee_authority: Pubkey,
+    pub new_fee_authority: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptFeeAuthority<'info> {
+    #[account(mut)]
+    pub ai_dex_config_extension: Account<'info, AiDexConfigExtension>,
+
+    #[account(address = ai_dex_config_extension.pending_fee_authority)]
+    pub pending_fee_authority: Signer<'info>,
+}
+
+/// Accepts the fee authority proposed with `propose_fee_authority`.
+///
+/// Only the pending fee authority has permission to invoke this instruction.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for accepting the fee authority.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the fee authority is successfully accepted,
+/// or an `Err` if an error occurs.
+pub fn accept_fee_authority_handler(
+    ctx: Context<AcceptFeeAuthority>
+) -> Result<()> {
+    let old_fee_authority = ctx.accounts.ai_dex_config_extension.authority(ConfigRole::Fee);
+    ctx.accounts.ai_dex_config_extension.accept_fee_authority();
+
+    emit_cpi!(FeeAuthorityAcceptedEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config_extension.ai_dex_config,
+        old_fee_authority,
+        new_fee_authority: ctx.accounts.pending_fee_authority.key(),
+    });
+
+    Ok(())
+}
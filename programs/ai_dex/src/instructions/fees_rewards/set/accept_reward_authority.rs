@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::state::AiDexPool;
+
+use super::set_reward_authority::RewardAuthorityUpdatedEvent;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct AcceptRewardAuthority<'info> {
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(address = ai_dex_pool.load()?.pending_reward_authorities[reward_index as usize])]
+    pub pending_reward_authority: Signer<'info>,
+}
+
+/// Accepts the reward authority proposed with `propose_reward_authority` for a specific reward index.
+///
+/// Only the pending reward authority has permission to invoke this instruction.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for accepting the reward authority.
+/// * `reward_index` - The index of the reward for which the authority is accepted.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the reward authority is successfully accepted,
+/// or an `Err` if an error occurs.
+pub fn accept_reward_authority_handler(
+    ctx: Context<AcceptRewardAuthority>,
+    reward_index: u8
+) -> Result<()> {
+    let previous_reward_authority = {
+        let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
+        let previous_reward_authority = ai_dex.reward_infos[reward_index as usize].authority;
+        ai_dex.accept_reward_authority(reward_index as usize)?;
+        previous_reward_authority
+    };
+
+    emit_cpi!(RewardAuthorityUpdatedEvent {
+        ai_dex_key: ctx.accounts.ai_dex_pool.key(),
+        reward_index,
+        previous_reward_authority,
+        new_reward_authority: ctx.accounts.pending_reward_authority.key(),
+    });
+
+    Ok(())
+}
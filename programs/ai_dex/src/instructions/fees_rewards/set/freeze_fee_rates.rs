@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AiDexPool, AiDexConfig, AiDexConfigExtension, ConfigRole, config_role_authority};
+
+#[event]
+pub struct FeeRatesFrozenEvent {
+    pub ai_dex_config: Pubkey,
+    pub ai_dex: Pubkey,
+    pub config_authority: Pubkey,
+    pub fee_rate: u16,
+    pub protocol_fee_rate: u16,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FreezeFeeRates<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::Fee)
+    )]
+    pub config_authority: Signer<'info>,
+
+    /// The extension of the config, if any, holding the delegated fee authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+}
+
+/// Freezes the current fee rate and protocol fee rate of an AI DEX pool.
+///
+/// Once frozen, neither `set_fee_rate` nor `set_protocol_fee_rate` can modify the pool,
+/// regardless of later changes to the config-level defaults. The freeze is irreversible.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for freezing the fee rates.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the fee rates are successfully frozen,
+/// or an `Err` if an error occurs.
+///
+/// # Errors
+///
+/// * `FeeRatesFrozenError` - If the fee rates of the pool are already frozen.
+pub fn freeze_fee_rates_handler(ctx: Context<FreezeFeeRates>) -> Result<()> {
+    let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
+    ai_dex.freeze_fee_rates()?;
+
+    emit_cpi!(FeeRatesFrozenEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex: ctx.accounts.ai_dex_pool.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        fee_rate: ai_dex.fee_rate,
+        protocol_fee_rate: ai_dex.protocol_fee_rate,
+    });
+
+    Ok(())
+}
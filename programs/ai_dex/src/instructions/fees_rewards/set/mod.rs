@@ -1,17 +1,59 @@
+pub mod accept_config_authority;
+pub mod accept_fee_authority;
+pub mod accept_reward_authority;
+pub mod freeze_fee_rates;
+pub mod propose_config_authority;
+pub mod propose_fee_authority;
+pub mod propose_reward_authority;
+pub mod set_adaptive_fee;
+pub mod set_config_extension_authority;
 pub mod set_default_fee_rate;
 pub mod set_default_protocol_fee_rate;
+pub mod set_emergency_authority;
+pub mod set_emission_schedule;
 pub mod set_fee_authority;
+pub mod set_fee_discount_tiers;
+pub mod set_fee_distribution;
+pub mod set_fee_manager;
 pub mod set_fee_rate;
+pub mod set_fee_tier_full_range_only;
+pub mod set_launch_fee;
+pub mod set_locked_reward_boost;
+pub mod set_nft_metadata_defaults;
+pub mod set_pool_creation_fee;
 pub mod set_protocol_fee_rate;
+pub mod set_referral_fee_rate;
 pub mod set_reward_authority;
 pub mod set_reward_authority_by_config_authority;
 pub mod set_reward_emissions;
+pub mod set_volume_fee_tiers;
 
+pub use accept_config_authority::*;
+pub use accept_fee_authority::*;
+pub use accept_reward_authority::*;
+pub use freeze_fee_rates::*;
+pub use propose_config_authority::*;
+pub use propose_fee_authority::*;
+pub use propose_reward_authority::*;
+pub use set_adaptive_fee::*;
+pub use set_config_extension_authority::*;
 pub use set_default_fee_rate::*;
 pub use set_default_protocol_fee_rate::*;
+pub use set_emergency_authority::*;
+pub use set_emission_schedule::*;
 pub use set_fee_authority::*;
+pub use set_fee_discount_tiers::*;
+pub use set_fee_distribution::*;
+pub use set_fee_manager::*;
 pub use set_fee_rate::*;
+pub use set_fee_tier_full_range_only::*;
+pub use set_launch_fee::*;
+pub use set_locked_reward_boost::*;
+pub use set_nft_metadata_defaults::*;
+pub use set_pool_creation_fee::*;
 pub use set_protocol_fee_rate::*;
+pub use set_referral_fee_rate::*;
 pub use set_reward_authority::*;
 pub use set_reward_authority_by_config_authority::*;
-pub use set_reward_emissions::*;
\ No newline at end of file
+pub use set_reward_emissions::*;
+pub use set_volume_fee_tiers::*;
\ No newline at end of file
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::state::AiDexConfig;
+
+#[event]
+pub struct ConfigAuthorityProposedEvent {
+    pub ai_dex_config: Pubkey,
+    pub config_authority: Pubkey,
+    pub pending_config_authority: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProposeConfigAuthority<'info> {
+    #[account(mut)]
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+
+    /// CHECK: the account that will be new authority can be arbitrary
+    pub pending_config_authority: UncheckedAccount<'info>,
+}
+
+/// Proposes a new config authority for the AI DEX configuration.
+///
+/// The current config authority stays in place until the proposed authority signs
+/// `accept_config_authority`, so a mistyped key cannot take over the configuration.
+/// A new proposal replaces the pending one.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for proposing the new config authority.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the config authority is successfully proposed,
+/// or an `Err` if an error occurs.
+pub fn propose_config_authority_handler(
+    ctx: Context<ProposeConfigAuthority>
+) -> Result<()> {
+    ctx
+        .accounts
+        .ai_dex_config
+        .propose_config_authority(ctx.accounts.pending_config_authority.key());
+
+    emit_cpi!(ConfigAuthorityProposedEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        pending_config_authority: ctx.accounts.pending_config_authority.key(),
+    });
+
+    Ok(())
+}
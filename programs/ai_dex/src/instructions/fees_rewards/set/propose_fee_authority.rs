@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AiDexConfig, AiDexConfigExtension};
+
+#[event]
+pub struct FeeAuthorityProposedEvent {
+    pub ai_dex_config: Pubkey,
+    pub config_authority: Pubkey,
+    pub pending_fee_authority: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProposeFeeAuthority<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_config_extension: Account<'info, AiDexConfigExtension>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+
+    /// CHECK: the account that will be new authority can be arbitrary
+    pub pending_fee_authority: UncheckedAccount<'info>,
+}
+
+/// Proposes a new fee authority for the AI DEX configuration.
+///
+/// The current fee authority stays in place until the proposed authority signs
+/// `accept_fee_authority`. Only the config authority has permission to invoke this instruction.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for proposing the new fee authority.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the fee authority is successfully proposed,
+/// or an `Err` if an error occurs.
+pub fn propose_fee_authority_handler(
+    ctx: Context<ProposeFeeAuthority>
+) -> Result<()> {
+    ctx
+        .accounts
+        .ai_dex_config_extension
+        .propose_fee_authority(ctx.accounts.pending_fee_authority.key());
+
+    emit_cpi!(FeeAuthorityProposedEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        pending_fee_authority: ctx.accounts.pending_fee_authority.key(),
+    });
+
+    Ok(())
+}
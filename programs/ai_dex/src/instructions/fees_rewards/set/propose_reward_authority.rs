@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::state::AiDexPool;
+
+#[event]
+pub struct RewardAuthorityProposedEvent {
+    pub ai_dex_key: Pubkey,
+    pub reward_index: u8,
+    pub reward_authority: Pubkey,
+    pub pending_reward_authority: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct ProposeRewardAuthority<'info> {
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(address = ai_dex_pool.load()?.reward_infos[reward_index as usize].authority)]
+    pub reward_authority: Signer<'info>,
+
+    /// CHECK: the account that will be new authority can be arbitrary
+    pub pending_reward_authority: UncheckedAccount<'info>,
+}
+
+/// Proposes a new reward authority for a specific reward index in the AI DEX.
+///
+/// The current reward authority stays in place until the proposed authority signs
+/// `accept_reward_authority`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for proposing the new reward authority.
+/// * `reward_index` - The index of the reward for which the authority is to be proposed.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the reward authority is successfully proposed,
+/// or an `Err` if an error occurs.
+pub fn propose_reward_authority_handler(
+    ctx: Context<ProposeRewardAuthority>,
+    reward_index: u8
+) -> Result<()> {
+    ctx.accounts.ai_dex_pool.load_mut()?.propose_reward_authority(
+        reward_index as usize,
+        ctx.accounts.pending_reward_authority.key(),
+    )?;
+
+    emit_cpi!(RewardAuthorityProposedEvent {
+        ai_dex_key: ctx.accounts.ai_dex_pool.key(),
+        reward_index,
+        reward_authority: ctx.accounts.reward_authority.key(),
+        pending_reward_authority: ctx.accounts.pending_reward_authority.key(),
+    });
+
+    Ok(())
+}
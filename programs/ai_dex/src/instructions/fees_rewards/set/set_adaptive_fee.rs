@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{AiDexPool, AiDexConfig, AiDexConfigExtension, ConfigRole, config_role_authority};
+
+#[event]
+pub struct AdaptiveFeeSetEvent {
+    pub ai_dex_pool: Pubkey,
+    pub ai_dex_config: Pubkey,
+    pub config_authority: Pubkey,
+    pub window_seconds: u32,
+    pub fee_rate_per_tick: u16,
+    pub min_fee_rate: u16,
+    pub max_fee_rate: u16,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetAdaptiveFee<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::Fee)
+    )]
+    pub config_authority: Signer<'info>,
+
+    /// CHECK: The oracle account of the pool. It must be initialized to enable the adaptive fee.
+    #[account(seeds = [b"oracle", ai_dex_pool.key().as_ref()], bump)]
+    pub oracle: UncheckedAccount<'info>,
+
+    /// The extension of the config, if any, holding the delegated fee authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+}
+
+/// Sets the adaptive fee of an AI DEX pool.
+///
+/// While enabled, each swap pays the base fee rate plus `fee_rate_per_tick` for every tick between
+/// the current tick and the average tick of the oracle over the window, bounded by the minimum and
+/// maximum fee rates.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the adaptive fee.
+/// * `window_seconds` - The oracle window over which the volatility is measured, zero to disable the adaptive fee.
+/// * `fee_rate_per_tick` - The fee rate added per tick of volatility.
+/// * `min_fee_rate` - The lower bound of the adaptive fee rate.
+/// * `max_fee_rate` - The upper bound of the adaptive fee rate.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the adaptive fee is successfully set,
+/// or an `Err` if the fee rates are frozen, the bounds are invalid or the oracle is not initialized.
+pub fn set_adaptive_fee_handler(
+    ctx: Context<SetAdaptiveFee>,
+    window_seconds: u32,
+    fee_rate_per_tick: u16,
+    min_fee_rate: u16,
+    max_fee_rate: u16,
+) -> Result<()> {
+    let oracle = &ctx.accounts.oracle;
+    if window_seconds != 0 && (oracle.owner != &crate::ID || oracle.data_is_empty()) {
+        return Err(ErrorCode::AdaptiveFeeOracleError.into());
+    }
+
+    ctx.accounts
+        .ai_dex_pool
+        .load_mut()?
+        .set_adaptive_fee(window_seconds, fee_rate_per_tick, min_fee_rate, max_fee_rate)?;
+
+    emit_cpi!(AdaptiveFeeSetEvent {
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        window_seconds,
+        fee_rate_per_tick,
+        min_fee_rate,
+        max_fee_rate,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AiDexConfig, AiDexConfigExtension, ConfigRole};
+
+#[event]
+pub struct ConfigExtensionAuthorityUpdatedEvent {
+    pub ai_dex_config: Pubkey,
+    pub config_authority: Pubkey,
+    pub role: ConfigRole,
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetConfigExtensionAuthority<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_config_extension: Account<'info, AiDexConfigExtension>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+
+    /// CHECK: the account that will be new authority can be arbitrary
+    pub new_authority: UncheckedAccount<'info>,
+}
+
+/// Delegates a role of the AI DEX configuration to a new authority.
+///
+/// Each role is held by its own key, so the keys used for day-to-day operations do not control
+/// the whole configuration. Setting the default pubkey gives the role back to the config authority.
+/// Only the config authority has permission to invoke this instruction.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the new authority.
+/// * `role` - The role delegated to the new authority.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the authority is successfully updated,
+/// or an `Err` if an error occurs.
+pub fn set_config_extension_authority_handler(
+    ctx: Context<SetConfigExtensionAuthority>,
+    role: ConfigRole,
+) -> Result<()> {
+    let old_authority = ctx.accounts.ai_dex_config_extension.authority(role);
+    ctx
+        .accounts
+        .ai_dex_config_extension
+        .update_authority(role, ctx.accounts.new_authority.key());
+
+    emit_cpi!(ConfigExtensionAuthorityUpdatedEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        role,
+        old_authority,
+        new_authority: ctx.accounts.new_authority.key(),
+    });
+
+    Ok(())
+}
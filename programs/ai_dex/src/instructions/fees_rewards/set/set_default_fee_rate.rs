@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::state::{FeeTier, AiDexConfig};
+use crate::state::{FeeTier, AiDexConfig, AiDexConfigExtension, ConfigRole, config_role_authority};
 
 #[event]
 pub struct DefaultFeeRateSetEvent {
@@ -11,6 +11,7 @@ pub struct DefaultFeeRateSetEvent {
     pub new_default_fee_rate: u16,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct SetDefaultFeeRate<'info> {
     pub ai_dex_config: Account<'info, AiDexConfig>,
@@ -18,8 +19,17 @@ pub struct SetDefaultFeeRate<'info> {
     #[account(mut, has_one = ai_dex_config)]
     pub fee_tier: Account<'info, FeeTier>,
 
-    #[account(address = ai_dex_config.config_authority)]
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::Fee)
+    )]
     pub config_authority: Signer<'info>,
+
+    /// The extension of the config, if any, holding the delegated fee authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
 }
 
 /// Sets the default fee rate for a fee tier.
@@ -42,7 +52,7 @@ pub fn set_default_fee_rate_handler(ctx: Context<SetDefaultFeeRate>, default_fee
         .fee_tier
         .update_default_fee_rate(default_fee_rate)?;
 
-    emit!(DefaultFeeRateSetEvent {
+    emit_cpi!(DefaultFeeRateSetEvent {
         ai_dex_config_key: ctx.accounts.ai_dex_config.key(),
         fee_tier_key: ctx.accounts.fee_tier.key(),
         config_authority: ctx.accounts.config_authority.key(),
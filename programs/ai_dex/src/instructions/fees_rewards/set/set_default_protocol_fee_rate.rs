@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::state::AiDexConfig;
+use crate::state::{AiDexConfig, AiDexConfigExtension, ConfigRole, config_role_authority};
 
 #[event]
 pub struct DefaultProtocolFeeRateSetEvent {
@@ -9,13 +9,23 @@ pub struct DefaultProtocolFeeRateSetEvent {
     pub new_default_protocol_fee_rate: u16,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct SetDefaultProtocolFeeRate<'info> {
     #[account(mut)]
     pub ai_dex_config: Account<'info, AiDexConfig>,
 
-    #[account(address = ai_dex_config.config_authority)]
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::Fee)
+    )]
     pub config_authority: Signer<'info>,
+
+    /// The extension of the config, if any, holding the delegated fee authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
 }
 
 /// Sets the default protocol fee rate for the AI DEX configuration.
@@ -40,7 +50,7 @@ pub fn set_default_protocol_fee_rate_handler(
         .ai_dex_config
         .update_default_protocol_fee_rate(default_protocol_fee_rate)?;
 
-    emit!(DefaultProtocolFeeRateSetEvent {
+    emit_cpi!(DefaultProtocolFeeRateSetEvent {
         ai_dex_config: ctx.accounts.ai_dex_config.key(),
         config_authority: ctx.accounts.config_authority.key(),
         new_default_protocol_fee_rate: default_protocol_fee_rate,
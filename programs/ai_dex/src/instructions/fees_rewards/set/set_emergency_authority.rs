@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::state::AiDexConfig;
+
+#[event]
+pub struct EmergencyAuthorityUpdatedEvent {
+    pub ai_dex_config: Pubkey,
+    pub config_authority: Pubkey,
+    pub old_emergency_authority: Pubkey,
+    pub new_emergency_authority: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetEmergencyAuthority<'info> {
+    #[account(mut)]
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+
+    /// CHECK: the account that will be new authority can be arbitrary
+    pub new_emergency_authority: UncheckedAccount<'info>,
+}
+
+/// Sets the emergency authority of the AI DEX configuration.
+///
+/// The emergency authority is the only one able to pause and unpause the whole protocol with
+/// `set_protocol_pause`. Only the config authority has permission to invoke this instruction.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the new emergency authority.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the emergency authority is successfully updated,
+/// or an `Err` if an error occurs.
+pub fn set_emergency_authority_handler(
+    ctx: Context<SetEmergencyAuthority>
+) -> Result<()> {
+    let old_emergency_authority = ctx.accounts.ai_dex_config.emergency_authority;
+    ctx
+        .accounts
+        .ai_dex_config
+        .update_emergency_authority(ctx.accounts.new_emergency_authority.key());
+
+    emit_cpi!(EmergencyAuthorityUpdatedEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        old_emergency_authority,
+        new_emergency_authority: ctx.accounts.new_emergency_authority.key(),
+    });
+
+    Ok(())
+}
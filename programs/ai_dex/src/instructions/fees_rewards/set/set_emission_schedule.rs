@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AiDexPool, EmissionSchedule, EmissionSegment};
+
+#[event]
+pub struct EmissionScheduleSetEvent {
+    pub ai_dex_pool: Pubkey,
+    pub reward_index: u8,
+    pub reward_authority: Pubkey,
+    pub emission_schedule: Pubkey,
+    pub segments: Vec<EmissionSegment>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetEmissionSchedule<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(address = ai_dex_pool.load()?.reward_infos[emission_schedule.reward_index as usize].authority)]
+    pub reward_authority: Signer<'info>,
+
+    #[account(mut, has_one = ai_dex_pool)]
+    pub emission_schedule: Box<Account<'info, EmissionSchedule>>,
+}
+
+/// Replaces the segments of the emission schedule of a reward of an AI DEX pool.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the update.
+/// * `segments` - The emission segments, sorted by time.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the schedule is successfully set, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `InvalidEmissionScheduleError` - If the segments are invalid.
+pub fn set_emission_schedule_handler(
+    ctx: Context<SetEmissionSchedule>,
+    segments: Vec<EmissionSegment>,
+) -> Result<()> {
+    ctx.accounts.emission_schedule.set_segments(&segments)?;
+
+    emit_cpi!(EmissionScheduleSetEvent {
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        reward_index: ctx.accounts.emission_schedule.reward_index,
+        reward_authority: ctx.accounts.reward_authority.key(),
+        emission_schedule: ctx.accounts.emission_schedule.key(),
+        segments,
+    });
+
+    Ok(())
+}
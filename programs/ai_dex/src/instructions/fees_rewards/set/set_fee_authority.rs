@@ -9,6 +9,7 @@ pub struct FeeAuthorityUpdatedEvent {
     pub new_fee_authority: Pubkey,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct SetFeeAuthority<'info> {
     #[account(mut)]
@@ -42,7 +43,7 @@ pub fn set_fee_authority_handler(
         .ai_dex_config
         .update_config_authority(ctx.accounts.new_config_authority.key());
 
-    emit!(FeeAuthorityUpdatedEvent {
+    emit_cpi!(FeeAuthorityUpdatedEvent {
         ai_dex_config: ctx.accounts.ai_dex_config.key(),
         old_fee_authority: ctx.accounts.config_authority.key(),
         new_fee_authority: ctx.accounts.new_config_authority.key(),
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[event]
+pub struct FeeDiscountTiersSetEvent {
+    pub ai_dex_config: Pubkey,
+    pub config_authority: Pubkey,
+    pub fee_discount_registry: Pubkey,
+    pub tiers: Vec<FeeDiscountTier>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetFeeDiscountTiers<'info> {
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::Fee)
+    )]
+    pub config_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_discount_registry", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub fee_discount_registry: Box<Account<'info, FeeDiscountRegistry>>,
+
+    /// The extension of the config, if any, holding the delegated fee authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+}
+
+/// Replaces the tiers of the fee discount registry of an AI DEX config.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the update.
+/// * `tiers` - The fee discount tiers, none to disable the discounts.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the tiers are successfully set, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `InvalidFeeDiscountTiersError` - If the tiers are invalid.
+pub fn set_fee_discount_tiers_handler(
+    ctx: Context<SetFeeDiscountTiers>,
+    tiers: Vec<FeeDiscountTier>,
+) -> Result<()> {
+    ctx.accounts.fee_discount_registry.set_tiers(&tiers)?;
+
+    emit_cpi!(FeeDiscountTiersSetEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        fee_discount_registry: ctx.accounts.fee_discount_registry.key(),
+        tiers,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[event]
+pub struct FeeDistributionSetEvent {
+    pub ai_dex_config: Pubkey,
+    pub config_authority: Pubkey,
+    pub fee_distribution: Pubkey,
+    pub recipients: Vec<FeeRecipient>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetFeeDistribution<'info> {
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::CollectProtocolFees)
+    )]
+    pub config_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_distribution", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub fee_distribution: Box<Account<'info, FeeDistribution>>,
+
+    /// The extension of the config, if any, holding the delegated collect protocol fees authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+}
+
+/// Replaces the recipients of the fee distribution of an AI DEX config.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the update.
+/// * `recipients` - The recipients of the protocol fees and their weights in basis points.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the recipients are successfully set, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `InvalidFeeDistributionError` - If the recipients are invalid or their weights do not sum up to 10000 bps.
+pub fn set_fee_distribution_handler(
+    ctx: Context<SetFeeDistribution>,
+    recipients: Vec<FeeRecipient>,
+) -> Result<()> {
+    ctx.accounts.fee_distribution.set_recipients(&recipients)?;
+
+    emit_cpi!(FeeDistributionSetEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        fee_distribution: ctx.accounts.fee_distribution.key(),
+        recipients,
+    });
+
+    Ok(())
+}
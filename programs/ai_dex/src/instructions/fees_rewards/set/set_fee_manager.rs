@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AiDexPool, AiDexConfig, AiDexConfigExtension, ConfigRole, config_role_authority};
+
+#[event]
+pub struct FeeManagerSetEvent {
+    pub ai_dex_pool: Pubkey,
+    pub ai_dex_config: Pubkey,
+    pub config_authority: Pubkey,
+    pub old_fee_manager: Pubkey,
+    pub new_fee_manager: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetFeeManager<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::Fee)
+    )]
+    pub config_authority: Signer<'info>,
+
+    /// The extension of the config, if any, holding the delegated fee authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+}
+
+/// Sets the fee manager of an AI DEX pool.
+///
+/// The fee manager may set the fee rate of this pool only, so market-specific operators can tune
+/// fees without protocol-wide powers.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the fee manager.
+/// * `fee_manager` - The new fee manager, or the default pubkey to remove it.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the fee manager is successfully set,
+/// or an `Err` if an error occurs.
+pub fn set_fee_manager_handler(ctx: Context<SetFeeManager>, fee_manager: Pubkey) -> Result<()> {
+    let mut ai_dex_pool = ctx.accounts.ai_dex_pool.load_mut()?;
+    let old_fee_manager = ai_dex_pool.fee_manager;
+    ai_dex_pool.set_fee_manager(fee_manager);
+
+    emit_cpi!(FeeManagerSetEvent {
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        old_fee_manager,
+        new_fee_manager: fee_manager,
+    });
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 
-use crate::state::{AiDexPool, AiDexConfig};
+use crate::errors::ErrorCode;
+use crate::state::{AiDexPool, AiDexConfig, AiDexConfigExtension, ConfigRole, config_role_authority};
 
 #[event]
 pub struct FeeRateSetEvent {
@@ -11,20 +12,33 @@ pub struct FeeRateSetEvent {
     pub new_fee_rate: u16,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct SetFeeRate<'info> {
     pub ai_dex_config: Account<'info, AiDexConfig>,
 
     #[account(mut, has_one = ai_dex_config)]
-    pub ai_dex_pool: Account<'info, AiDexPool>,
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
 
-    #[account(address = ai_dex_config.config_authority)]
+    /// The fee authority of the config, or the fee manager of the pool
+    #[account(
+        constraint = config_authority.key() == config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::Fee)
+            || ai_dex_pool.load()?.is_fee_manager(config_authority.key) @ ErrorCode::FeeManagerError
+    )]
     pub config_authority: Signer<'info>,
+
+    /// The extension of the config, if any, holding the delegated fee authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
 }
 
 /// Sets a new fee rate for the AI DEX.
 ///
-/// This function updates the fee rate in the AI DEX configuration.
+/// This function updates the fee rate in the AI DEX configuration. Besides the fee authority of the
+/// config, the fee manager of the pool, if any, may set its fee rate.
 ///
 /// # Arguments
 ///
@@ -40,11 +54,12 @@ pub fn set_fee_rate_handler(
     fee_rate: u16
 ) -> Result<()> {
     // Log the current fee rate before updating
-    let old_fee_rate = ctx.accounts.ai_dex_pool.fee_rate;
+    let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
+    let old_fee_rate = ai_dex.fee_rate;
     
-    ctx.accounts.ai_dex_pool.update_fee_rate(fee_rate)?;
+    ai_dex.update_fee_rate(fee_rate)?;
 
-    emit!(FeeRateSetEvent {
+    emit_cpi!(FeeRateSetEvent {
         ai_dex_key: ctx.accounts.ai_dex_pool.key(),
         ai_dex_config_key: ctx.accounts.ai_dex_config.key(),
         config_authority: ctx.accounts.config_authority.key(),
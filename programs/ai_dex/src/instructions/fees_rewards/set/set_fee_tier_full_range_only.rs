@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{FeeTier, AiDexConfig, AiDexConfigExtension, ConfigRole, config_role_authority};
+
+#[event]
+pub struct FeeTierFullRangeOnlySetEvent {
+    pub ai_dex_config_key: Pubkey,
+    pub fee_tier_key: Pubkey,
+    pub config_authority: Pubkey,
+    pub tick_spacing: u16,
+    pub full_range_only: bool,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetFeeTierFullRangeOnly<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub fee_tier: Account<'info, FeeTier>,
+
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::Fee)
+    )]
+    pub config_authority: Signer<'info>,
+
+    /// The extension of the config, if any, holding the delegated fee authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+}
+
+/// Sets whether the pools initialized from a fee tier only allow full range positions.
+///
+/// Full range only pools hold uniform liquidity over the whole price range, which suits long-tail
+/// tokens. The flag is copied to the pools when they are initialized, so the pools already
+/// initialized from the fee tier are unaffected.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the flag.
+/// * `full_range_only` - Whether positions can only be opened on the full range of ticks.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the flag is successfully set,
+/// or an `Err` if an error occurs.
+pub fn set_fee_tier_full_range_only_handler(
+    ctx: Context<SetFeeTierFullRangeOnly>,
+    full_range_only: bool,
+) -> Result<()> {
+    ctx.accounts.fee_tier.set_full_range_only(full_range_only);
+
+    emit_cpi!(FeeTierFullRangeOnlySetEvent {
+        ai_dex_config_key: ctx.accounts.ai_dex_config.key(),
+        fee_tier_key: ctx.accounts.fee_tier.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        tick_spacing: ctx.accounts.fee_tier.tick_spacing,
+        full_range_only,
+    });
+
+    Ok(())
+}
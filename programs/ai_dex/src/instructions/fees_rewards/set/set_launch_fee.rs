@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AiDexPool, AiDexConfig, AiDexConfigExtension, ConfigRole, config_role_authority};
+use crate::util::to_timestamp_u64;
+
+#[event]
+pub struct LaunchFeeSetEvent {
+    pub ai_dex_pool: Pubkey,
+    pub ai_dex_config: Pubkey,
+    pub config_authority: Pubkey,
+    pub start_fee_rate: u16,
+    pub end_fee_rate: u16,
+    pub duration_seconds: u32,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetLaunchFee<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::Fee)
+    )]
+    pub config_authority: Signer<'info>,
+
+    /// The extension of the config, if any, holding the delegated fee authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+}
+
+/// Sets the launch fee schedule of an AI DEX pool that has not opened yet.
+///
+/// From the open time, the launch fee rate decays linearly from the start fee rate to the end fee
+/// rate over the duration of the schedule. Swaps pay the launch fee rate while it is higher than
+/// the normal fee rate of the pool, which deters sniping right after the launch.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the launch fee.
+/// * `start_fee_rate` - The fee rate at the open time.
+/// * `end_fee_rate` - The fee rate at the end of the schedule.
+/// * `duration_seconds` - The duration of the schedule after the open time, zero to disable it.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the launch fee is successfully set,
+/// or an `Err` if an error occurs.
+///
+/// # Errors
+///
+/// * `ErrorCode::PoolAlreadyOpenError` - If the pool is already open.
+/// * `ErrorCode::FeeRatesFrozenError` - If the fee rates of the pool are frozen.
+/// * `ErrorCode::InvalidLaunchFeeError` - If the start fee rate exceeds the maximum fee rate or is lower than the end fee rate.
+pub fn set_launch_fee_handler(
+    ctx: Context<SetLaunchFee>,
+    start_fee_rate: u16,
+    end_fee_rate: u16,
+    duration_seconds: u32,
+) -> Result<()> {
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    ctx.accounts
+        .ai_dex_pool
+        .load_mut()?
+        .set_launch_fee(start_fee_rate, end_fee_rate, duration_seconds, timestamp)?;
+
+    emit_cpi!(LaunchFeeSetEvent {
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        start_fee_rate,
+        end_fee_rate,
+        duration_seconds,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AiDexPool, AiDexConfig, AiDexConfigExtension, ConfigRole, config_role_authority};
+
+#[event]
+pub struct LockedRewardBoostSetEvent {
+    pub ai_dex_pool: Pubkey,
+    pub ai_dex_config: Pubkey,
+    pub reward_emissions_super_authority: Pubkey,
+    pub old_locked_reward_boost_bps: u16,
+    pub new_locked_reward_boost_bps: u16,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetLockedRewardBoost<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::RewardEmissionsSuper)
+    )]
+    pub reward_emissions_super_authority: Signer<'info>,
+
+    /// The extension of the config, if any, holding the delegated reward emissions super authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+}
+
+/// Sets the multiplier of the rewards of the locked positions of an AI DEX pool.
+///
+/// Positions pick up the new boost when their boost is refreshed with `refresh_position_reward_boost`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the boost.
+/// * `locked_reward_boost_bps` - The reward boost in basis points, zero to disable it.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the boost is successfully set,
+/// or an `Err` if an error occurs.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidRewardBoostError` - If the boost is below 1x or above the maximum reward boost.
+pub fn set_locked_reward_boost_handler(
+    ctx: Context<SetLockedRewardBoost>,
+    locked_reward_boost_bps: u16,
+) -> Result<()> {
+    let mut ai_dex_pool = ctx.accounts.ai_dex_pool.load_mut()?;
+    let old_locked_reward_boost_bps = ai_dex_pool.locked_reward_boost_bps;
+    ai_dex_pool.set_locked_reward_boost(locked_reward_boost_bps)?;
+
+    emit_cpi!(LockedRewardBoostSetEvent {
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        reward_emissions_super_authority: ctx.accounts.reward_emissions_super_authority.key(),
+        old_locked_reward_boost_bps,
+        new_locked_reward_boost_bps: locked_reward_boost_bps,
+    });
+
+    Ok(())
+}
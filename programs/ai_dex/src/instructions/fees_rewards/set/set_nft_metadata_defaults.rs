@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AiDexConfig, AiDexConfigExtension};
+
+#[event]
+pub struct NftMetadataDefaultsSetEvent {
+    pub ai_dex_config: Pubkey,
+    pub config_authority: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetNftMetadataDefaults<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_config_extension: Account<'info, AiDexConfigExtension>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+}
+
+/// Sets the name, symbol and URI of the position NFTs minted with metadata in the pools of a config.
+///
+/// Positions opened before the change keep their metadata.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the NFT metadata.
+/// * `name` - The name of the position NFTs, up to 32 bytes.
+/// * `symbol` - The symbol of the position NFTs, up to 10 bytes.
+/// * `uri` - The URI of the position NFTs metadata, up to 200 bytes.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the NFT metadata is successfully updated,
+/// or an `Err` if an error occurs.
+pub fn set_nft_metadata_defaults_handler(
+    ctx: Context<SetNftMetadataDefaults>,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    ctx.accounts
+        .ai_dex_config_extension
+        .update_position_nft_metadata(name.clone(), symbol.clone(), uri.clone())?;
+
+    emit_cpi!(NftMetadataDefaultsSetEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        name,
+        symbol,
+        uri,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AiDexConfig, AiDexConfigExtension, ConfigRole, config_role_authority};
+
+#[event]
+pub struct PoolCreationFeeSetEvent {
+    pub ai_dex_config: Pubkey,
+    pub config_authority: Pubkey,
+    pub pool_creation_fee: u64,
+    pub pool_creation_fee_mint: Pubkey,
+    pub pool_creation_fee_destination: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPoolCreationFee<'info> {
+    #[account(mut)]
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::Fee)
+    )]
+    pub config_authority: Signer<'info>,
+
+    /// The extension of the config, if any, holding the delegated fee authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+}
+
+/// Sets the fee collected from the funder of every new pool of the AI DEX configuration.
+///
+/// The fee discourages spam pools and funds the protocol treasury. It is paid in lamports if
+/// `pool_creation_fee_mint` is the default pubkey, in tokens of that mint otherwise.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the pool creation fee.
+/// * `pool_creation_fee` - The fee amount, zero to disable the fee.
+/// * `pool_creation_fee_mint` - The mint of the fee, or the default pubkey for a fee in lamports.
+/// * `pool_creation_fee_destination` - The wallet or token account receiving the fee.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the pool creation fee is successfully updated,
+/// or an `Err` if an error occurs.
+pub fn set_pool_creation_fee_handler(
+    ctx: Context<SetPoolCreationFee>,
+    pool_creation_fee: u64,
+    pool_creation_fee_mint: Pubkey,
+    pool_creation_fee_destination: Pubkey,
+) -> Result<()> {
+    ctx.accounts.ai_dex_config.update_pool_creation_fee(
+        pool_creation_fee,
+        pool_creation_fee_mint,
+        pool_creation_fee_destination,
+    )?;
+
+    emit_cpi!(PoolCreationFeeSetEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        pool_creation_fee,
+        pool_creation_fee_mint,
+        pool_creation_fee_destination,
+    });
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::state::{AiDexPool, AiDexConfig};
+use crate::state::{AiDexPool, AiDexConfig, AiDexConfigExtension, ConfigRole, config_role_authority};
 
 #[event]
 pub struct ProtocolFeeRateSetEvent {
@@ -10,15 +10,25 @@ pub struct ProtocolFeeRateSetEvent {
     pub new_protocol_fee_rate: u16,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct SetProtocolFeeRate<'info> {
     pub ai_dex_config: Account<'info, AiDexConfig>,
 
     #[account(mut, has_one = ai_dex_config)]
-    pub ai_dex_pool: Account<'info, AiDexPool>,
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
 
-    #[account(address = ai_dex_config.config_authority)]
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::Fee)
+    )]
     pub config_authority: Signer<'info>,
+
+    /// The extension of the config, if any, holding the delegated fee authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
 }
 
 /// Sets a new protocol fee rate for the AI DEX.
@@ -41,9 +51,10 @@ pub fn set_protocol_fee_rate_handler(
     ctx
         .accounts
         .ai_dex_pool
+        .load_mut()?
         .update_protocol_fee_rate(protocol_fee_rate)?;
 
-    emit!(ProtocolFeeRateSetEvent {
+    emit_cpi!(ProtocolFeeRateSetEvent {
         ai_dex_config: ctx.accounts.ai_dex_config.key(),
         ai_dex: ctx.accounts.ai_dex_pool.key(),
         config_authority: ctx.accounts.config_authority.key(),
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AiDexConfig, AiDexConfigExtension, ConfigRole, config_role_authority};
+
+#[event]
+pub struct ReferralFeeRateSetEvent {
+    pub ai_dex_config: Pubkey,
+    pub config_authority: Pubkey,
+    pub old_referral_fee_rate: u16,
+    pub new_referral_fee_rate: u16,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetReferralFeeRate<'info> {
+    #[account(mut)]
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::Fee)
+    )]
+    pub config_authority: Signer<'info>,
+
+    /// The extension of the config, if any, holding the delegated fee authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+}
+
+/// Sets the referral fee rate of the AI DEX configuration.
+///
+/// The referral fee rate is the share of the swap fee, in basis points, carved out for the
+/// referrer of a swap before the protocol fee is taken.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the referral fee rate.
+/// * `referral_fee_rate` - The new referral fee rate.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the referral fee rate is successfully updated,
+/// or an `Err` if the rate exceeds the maximum referral fee rate.
+pub fn set_referral_fee_rate_handler(
+    ctx: Context<SetReferralFeeRate>,
+    referral_fee_rate: u16,
+) -> Result<()> {
+    let old_referral_fee_rate = ctx.accounts.ai_dex_config.referral_fee_rate;
+    ctx.accounts.ai_dex_config.update_referral_fee_rate(referral_fee_rate)?;
+
+    emit_cpi!(ReferralFeeRateSetEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        old_referral_fee_rate,
+        new_referral_fee_rate: referral_fee_rate,
+    });
+
+    Ok(())
+}
@@ -10,13 +10,14 @@ pub struct RewardAuthorityUpdatedEvent {
     pub new_reward_authority: Pubkey,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(reward_index: u8)]
 pub struct SetRewardAuthority<'info> {
     #[account(mut)]
-    pub ai_dex_pool: Account<'info, AiDexPool>,
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
 
-    #[account(address = ai_dex_pool.reward_infos[reward_index as usize].authority)]
+    #[account(address = ai_dex_pool.load()?.reward_infos[reward_index as usize].authority)]
     pub reward_authority: Signer<'info>,
 
     /// CHECK: the account that will be new authority can be arbitrary
@@ -40,12 +41,12 @@ pub fn set_reward_authority_handler(
     ctx: Context<SetRewardAuthority>,
     reward_index: u8
 ) -> Result<()> {
-    ctx.accounts.ai_dex_pool.update_reward_authority(
+    ctx.accounts.ai_dex_pool.load_mut()?.update_reward_authority(
         reward_index as usize,
         ctx.accounts.new_reward_authority.key(),
     )?;
 
-    emit!(RewardAuthorityUpdatedEvent {
+    emit_cpi!(RewardAuthorityUpdatedEvent {
         ai_dex_key: ctx.accounts.ai_dex_pool.key(),
         reward_index,
         previous_reward_authority: ctx.accounts.reward_authority.key(),
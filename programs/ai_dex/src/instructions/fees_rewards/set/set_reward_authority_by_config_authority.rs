@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::state::{AiDexPool, AiDexConfig, NUM_REWARDS};
+use crate::state::{AiDexPool, AiDexConfig, NUM_REWARDS, AiDexConfigExtension, ConfigRole, config_role_authority};
 use crate::errors::ErrorCode::InvalidRewardIndexError;
 
 #[event]
@@ -12,19 +12,29 @@ pub struct RewardAuthoritySetEvent {
     pub config_authority: Pubkey,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(reward_index: u8)]
 pub struct SetRewardAuthorityByConfigAuthority<'info> {
     pub ai_dex_config: Account<'info, AiDexConfig>,
 
     #[account(mut, has_one = ai_dex_config)]
-    pub ai_dex_pool: Account<'info, AiDexPool>,
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
 
-    #[account(address = ai_dex_config.config_authority)]
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::RewardEmissionsSuper)
+    )]
     pub config_authority: Signer<'info>,
 
     /// CHECK: the account that will be new authority can be arbitrary
     pub new_reward_authority: UncheckedAccount<'info>,
+
+    /// The extension of the config, if any, holding the delegated reward emissions super authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
 }
 
 /// Sets the AiDex reward authority at the provided `reward_index`.
@@ -49,14 +59,15 @@ pub fn set_reward_authority_by_config_authority_handler(
         return Err(InvalidRewardIndexError.into());
     }
 
-    let old_reward_authority = ctx.accounts.ai_dex_pool.reward_infos[reward_index as usize].authority;
+    let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
+    let old_reward_authority = ai_dex.reward_infos[reward_index as usize].authority;
     
-    ctx.accounts.ai_dex_pool.update_reward_authority(
+    ai_dex.update_reward_authority(
         reward_index as usize,
         ctx.accounts.new_reward_authority.key(),
     )?;
 
-    emit!(RewardAuthoritySetEvent {
+    emit_cpi!(RewardAuthoritySetEvent {
         ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
         reward_index,
         old_reward_authority,
@@ -7,7 +7,7 @@ use crate::math::checked_mul_shift_right;
 use crate::state::AiDexPool;
 use crate::util::to_timestamp_u64;
 
-const DAY_IN_SECONDS: u128 = 60 * 60 * 24;
+pub const DAY_IN_SECONDS: u128 = 60 * 60 * 24;
 
 #[event]
 pub struct RewardEmissionsSetEvent {
@@ -17,6 +17,7 @@ pub struct RewardEmissionsSetEvent {
     pub reward_vault: RewardVaultData,
     pub emissions_per_second_x64: u128,
     pub emissions_per_day: u64,
+    pub emissions_end_timestamp: u64,
     pub timestamp: u64,
 }
 
@@ -26,16 +27,17 @@ pub struct RewardVaultData {
     pub amount: u64,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(reward_index: u8)]
 pub struct SetRewardEmissions<'info> {
     #[account(mut)]
-    pub ai_dex_pool: Account<'info, AiDexPool>,
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
 
-    #[account(address = ai_dex_pool.reward_infos[reward_index as usize].authority)]
+    #[account(address = ai_dex_pool.load()?.reward_infos[reward_index as usize].authority)]
     pub reward_authority: Signer<'info>,
 
-    #[account(address = ai_dex_pool.reward_infos[reward_index as usize].vault)]
+    #[account(address = ai_dex_pool.load()?.reward_infos[reward_index as usize].vault)]
     pub reward_vault: InterfaceAccount<'info, TokenAccount>,
 }
 
@@ -46,6 +48,8 @@ pub struct SetRewardEmissions<'info> {
 /// * `ctx` - The context containing all the accounts and programs required for the operation.
 /// * `reward_index` - The index of the reward to set emissions for.
 /// * `emissions_per_second_x64` - The emissions rate per second, scaled by 2^64.
+/// * `emissions_end_timestamp` - The timestamp after which no more rewards are emitted, zero for no end.
+///   Campaigns funded for a fixed period set it, so the vault is never emitted beyond its balance.
 ///
 /// # Returns
 ///
@@ -53,31 +57,42 @@ pub struct SetRewardEmissions<'info> {
 ///
 /// # Errors
 ///
-/// * `ErrorCode::InsufficientRewardVaultAmountError` - If the reward vault does not have enough tokens to cover the emissions for a day.
+/// * `ErrorCode::InsufficientRewardVaultAmountError` - If the reward vault does not have enough tokens to cover the emissions for a day,
+///   or until the end of the emissions if sooner.
+/// * `ErrorCode::InvalidEmissionsEndTimestampError` - If the end timestamp is not in the future.
 pub fn set_reward_emissions_handler(
     ctx: Context<SetRewardEmissions>,
     reward_index: u8,
     emissions_per_second_x64: u128,
+    emissions_end_timestamp: u64,
 ) -> Result<()> {
-    let ai_dex = &ctx.accounts.ai_dex_pool;
+    let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
     let reward_vault = &ctx.accounts.reward_vault;
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
 
     let emissions_per_day = checked_mul_shift_right(DAY_IN_SECONDS, emissions_per_second_x64)?;
-    if reward_vault.amount < emissions_per_day {
+    let required_emissions = match emissions_end_timestamp {
+        0 => emissions_per_day,
+        _ => {
+            let emission_seconds = emissions_end_timestamp.saturating_sub(timestamp) as u128;
+            checked_mul_shift_right(emission_seconds.min(DAY_IN_SECONDS), emissions_per_second_x64)?
+        }
+    };
+    if reward_vault.amount < required_emissions {
         return Err(ErrorCode::InsufficientRewardVaultAmountError.into());
     }
 
-    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
-    let next_reward_infos = next_ai_dex_reward_infos(ai_dex, timestamp)?;
+    let next_reward_infos = next_ai_dex_reward_infos(&ai_dex, timestamp)?;
 
-    ctx.accounts.ai_dex_pool.update_emissions(
+    ai_dex.update_emissions(
         reward_index as usize,
         next_reward_infos,
         timestamp,
         emissions_per_second_x64,
+        emissions_end_timestamp,
     )?;
 
-    emit!(RewardEmissionsSetEvent {
+    emit_cpi!(RewardEmissionsSetEvent {
         ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
         reward_index,
         reward_authority: ctx.accounts.reward_authority.key(),
@@ -87,6 +102,7 @@ pub fn set_reward_emissions_handler(
         },
         emissions_per_second_x64,
         emissions_per_day,
+        emissions_end_timestamp,
         timestamp,
     });
     
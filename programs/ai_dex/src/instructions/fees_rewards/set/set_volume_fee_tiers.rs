@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AiDexPool, AiDexConfig, AiDexConfigExtension, ConfigRole, VolumeFeeTier, config_role_authority};
+
+#[event]
+pub struct VolumeFeeTiersSetEvent {
+    pub ai_dex_pool: Pubkey,
+    pub ai_dex_config: Pubkey,
+    pub config_authority: Pubkey,
+    pub window_seconds: u32,
+    pub tiers: Vec<VolumeFeeTier>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetVolumeFeeTiers<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::Fee)
+    )]
+    pub config_authority: Signer<'info>,
+
+    /// The extension of the config, if any, holding the delegated fee authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+}
+
+/// Sets the volume fee tiers of an AI DEX pool.
+///
+/// The volume of the pool, in token A, is tracked over consecutive windows. Each swap pays the
+/// fee rate of the highest tier reached by the volume of the last complete window, or the base
+/// fee rate below the first tier.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the tiers.
+/// * `window_seconds` - The duration of the volume windows, zero to disable the tiers.
+/// * `tiers` - The fee tiers, sorted by strictly increasing minimum volume.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the tiers are successfully set,
+/// or an `Err` if the fee rates are frozen or the tiers are invalid.
+pub fn set_volume_fee_tiers_handler(
+    ctx: Context<SetVolumeFeeTiers>,
+    window_seconds: u32,
+    tiers: Vec<VolumeFeeTier>,
+) -> Result<()> {
+    ctx.accounts
+        .ai_dex_pool
+        .load_mut()?
+        .set_volume_fee_tiers(window_seconds, &tiers)?;
+
+    emit_cpi!(VolumeFeeTiersSetEvent {
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        window_seconds,
+        tiers,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{
+    errors::ErrorCode,
+    instructions::DAY_IN_SECONDS,
+    math::checked_mul_shift_right,
+    orchestrator::ai_dex_orchestrator::next_ai_dex_reward_infos,
+    state::*,
+    util::to_timestamp_u64,
+};
+
+#[event]
+pub struct EmissionScheduleAdvancedEvent {
+    pub ai_dex_pool: Pubkey,
+    pub reward_index: u8,
+    pub emission_schedule: Pubkey,
+    pub emissions_per_second_x64: u128,
+    pub emissions_end_timestamp: u64,
+    pub timestamp: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AdvanceEmissionSchedule<'info> {
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(has_one = ai_dex_pool)]
+    pub emission_schedule: Box<Account<'info, EmissionSchedule>>,
+
+    #[account(address = ai_dex_pool.load()?.reward_infos[emission_schedule.reward_index as usize].vault)]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+}
+
+/// Applies the emission rate of the active segment of an emission schedule to its reward.
+///
+/// The emissions end with the active segment, so a late crank never over-emits. Between segments,
+/// the emissions stop until the crank runs within the next one. The instruction is permissionless.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the crank.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the schedule is successfully advanced, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::InsufficientRewardVaultAmountError` - If the reward vault does not have enough tokens to cover
+///   the emissions for a day, or until the end of the segment if sooner.
+pub fn advance_emission_schedule_handler(ctx: Context<AdvanceEmissionSchedule>) -> Result<()> {
+    let emission_schedule = &ctx.accounts.emission_schedule;
+    let reward_index = emission_schedule.reward_index;
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let (emissions_per_second_x64, emissions_end_timestamp) = match emission_schedule.active_segment(timestamp) {
+        Some(segment) => (segment.emissions_per_second_x64, segment.end_timestamp),
+        None => (0, 0),
+    };
+
+    let emission_seconds = (emissions_end_timestamp.saturating_sub(timestamp) as u128).min(DAY_IN_SECONDS);
+    if ctx.accounts.reward_vault.amount < checked_mul_shift_right(emission_seconds, emissions_per_second_x64)? {
+        return Err(ErrorCode::InsufficientRewardVaultAmountError.into());
+    }
+
+    let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
+    let next_reward_infos = next_ai_dex_reward_infos(&ai_dex, timestamp)?;
+    ai_dex.update_emissions(
+        reward_index as usize,
+        next_reward_infos,
+        timestamp,
+        emissions_per_second_x64,
+        emissions_end_timestamp,
+    )?;
+
+    emit_cpi!(EmissionScheduleAdvancedEvent {
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        reward_index,
+        emission_schedule: emission_schedule.key(),
+        emissions_per_second_x64,
+        emissions_end_timestamp,
+        timestamp,
+    });
+
+    Ok(())
+}
@@ -1,2 +1,11 @@
+pub mod advance_emission_schedule;
+pub mod refresh_pool_rewards;
+pub mod refresh_position_reward_boost;
 pub mod update_fees_and_rewards;
-pub use update_fees_and_rewards::*;
\ No newline at end of file
+pub mod update_fees_and_rewards_multi;
+
+pub use advance_emission_schedule::*;
+pub use refresh_pool_rewards::*;
+pub use refresh_position_reward_boost::*;
+pub use update_fees_and_rewards::*;
+pub use update_fees_and_rewards_multi::*;
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::ErrorCode,
+    orchestrator::ai_dex_orchestrator::next_ai_dex_reward_infos,
+    state::*,
+    util::to_timestamp_u64,
+};
+
+#[event]
+pub struct PoolRewardsRefreshedEvent {
+    pub ai_dex_pool: Pubkey,
+    pub reward_infos: Vec<AiDexRewardInfo>,
+    pub timestamp: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RefreshPoolRewards {}
+
+/// Advances the reward accumulators of many pools in a single instruction.
+///
+/// Every remaining account must be a writable `AiDexPool`. For each pool the global reward
+/// growths are advanced to the current timestamp and `reward_last_updated_timestamp` is updated.
+/// The instruction is permissionless, since it only moves accounting forward in time.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction. The pools are passed as remaining accounts.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if all pools are successfully refreshed,
+/// or an `Err` if an error occurs.
+///
+/// # Errors
+///
+/// * `InsufficientRemainingAccountsError` - If no pool is provided.
+/// * `AccountNotMutable` - If a provided pool is not writable.
+/// * Any deserialization error if a remaining account is not an `AiDexPool` owned by this program.
+pub fn refresh_pool_rewards_handler<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, RefreshPoolRewards>,
+) -> Result<()> {
+    if ctx.remaining_accounts.is_empty() {
+        return Err(ErrorCode::InsufficientRemainingAccountsError.into());
+    }
+
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    for account_info in ctx.remaining_accounts.iter() {
+        if !account_info.is_writable {
+            return Err(anchor_lang::error::ErrorCode::AccountNotMutable.into());
+        }
+
+        let ai_dex_loader = AccountLoader::<AiDexPool>::try_from(account_info)?;
+        let mut ai_dex = ai_dex_loader.load_mut()?;
+        let reward_infos = next_ai_dex_reward_infos(&ai_dex, timestamp)?;
+        ai_dex.update_rewards(reward_infos, timestamp);
+
+        emit_cpi!(PoolRewardsRefreshedEvent {
+            ai_dex_pool: account_info.key(),
+            reward_infos: reward_infos.to_vec(),
+            timestamp,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    orchestrator::liquidity_orchestrator::calculate_fee_and_reward_growths, state::*,
+    util::{to_timestamp_u64, TickArrayAccount},
+};
+
+#[event]
+pub struct PositionRewardBoostRefreshedEvent {
+    pub ai_dex_pool: Pubkey,
+    pub position: Pubkey,
+    pub old_reward_boost_bps: u16,
+    pub new_reward_boost_bps: u16,
+    pub timestamp: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RefreshPositionRewardBoost<'info> {
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut, has_one = ai_dex_pool)]
+    pub position: Account<'info, Position>,
+
+    /// CHECK: A fixed or dynamic tick array of the pool, checked in the handler
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: A fixed or dynamic tick array of the pool, checked in the handler
+    pub tick_array_upper: UncheckedAccount<'info>,
+}
+
+/// Refreshes the reward boost of a position from its lock and the boost of its pool.
+///
+/// The fees and rewards of the position are settled first, with its previous boost, so a new
+/// boost only applies to the rewards earned from now on. A locked position gets the boost of the
+/// pool, an unlocked one loses its boost. The instruction is permissionless.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the refresh.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the boost is successfully refreshed,
+/// or an `Err` if an error occurs.
+pub fn refresh_position_reward_boost_handler(ctx: Context<RefreshPositionRewardBoost>) -> Result<()> {
+    let ai_dex_pool_key = ctx.accounts.ai_dex_pool.key();
+    let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
+    let position = &mut ctx.accounts.position;
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    // A position without liquidity has no rewards to settle
+    if position.liquidity > 0 {
+        let tick_array_lower = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_lower, &ai_dex_pool_key)?;
+        let tick_array_upper = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_upper, &ai_dex_pool_key)?;
+        let (position_update, reward_infos) = calculate_fee_and_reward_growths(
+            &ai_dex,
+            position,
+            &tick_array_lower,
+            &tick_array_upper,
+            timestamp,
+        )?;
+        ai_dex.update_rewards(reward_infos, timestamp);
+        position.update(&position_update);
+    }
+
+    let old_reward_boost_bps = position.reward_boost_bps;
+    let new_reward_boost_bps = if position.is_locked(timestamp) {
+        ai_dex.locked_reward_boost_bps
+    } else {
+        0
+    };
+    position.set_reward_boost(new_reward_boost_bps);
+
+    emit_cpi!(PositionRewardBoostRefreshedEvent {
+        ai_dex_pool: ai_dex_pool_key,
+        position: position.key(),
+        old_reward_boost_bps,
+        new_reward_boost_bps,
+        timestamp,
+    });
+
+    Ok(())
+}
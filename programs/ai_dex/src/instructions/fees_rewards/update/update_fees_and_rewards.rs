@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    orchestrator::liquidity_orchestrator::calculate_fee_and_reward_growths, state::*, util::to_timestamp_u64,
+    orchestrator::liquidity_orchestrator::calculate_fee_and_reward_growths, state::*,
+    util::{to_timestamp_u64, TickArrayAccount},
 };
 
 #[event]
@@ -26,18 +27,19 @@ pub struct RewardInfo {
     pub amount: u64,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct UpdateFeesAndRewards<'info> {
     #[account(mut)]
-    pub ai_dex_pool: Account<'info, AiDexPool>,
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
 
     #[account(mut, has_one = ai_dex_pool)]
     pub position: Account<'info, Position>,
 
-    #[account(has_one = ai_dex_pool)]
-    pub tick_array_lower: AccountLoader<'info, TickArray>,
-    #[account(has_one = ai_dex_pool)]
-    pub tick_array_upper: AccountLoader<'info, TickArray>,
+    /// CHECK: A fixed or dynamic tick array of the pool, checked in the handler
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: A fixed or dynamic tick array of the pool, checked in the handler
+    pub tick_array_upper: UncheckedAccount<'info>,
 }
 
 /// Updates the fees and rewards for a given position.
@@ -55,22 +57,26 @@ pub struct UpdateFeesAndRewards<'info> {
 /// This function returns a `Result` which is `Ok` if the fees and rewards are successfully updated,
 /// or an `Err` if an error occurs.
 pub fn update_fees_and_rewards_handler(ctx: Context<UpdateFeesAndRewards>) -> Result<()> {
-    let ai_dex = &mut ctx.accounts.ai_dex_pool;
+    let ai_dex_pool_key = ctx.accounts.ai_dex_pool.key();
+    let tick_array_lower = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_lower, &ai_dex_pool_key)?;
+    let tick_array_upper = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_upper, &ai_dex_pool_key)?;
+
+    let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
     let position = &mut ctx.accounts.position;
     let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
 
     let (position_update, reward_infos) = calculate_fee_and_reward_growths(
-        ai_dex,
+        &ai_dex,
         position,
-        &ctx.accounts.tick_array_lower,
-        &ctx.accounts.tick_array_upper,
+        &tick_array_lower,
+        &tick_array_upper,
         timestamp,
     )?;
 
     ai_dex.update_rewards(reward_infos, timestamp);
     position.update(&position_update);
 
-    emit!(FeesAndRewardsUpdatedEvent {
+    emit_cpi!(FeesAndRewardsUpdatedEvent {
         ai_dex: ctx.accounts.ai_dex_pool.key(),
         position: PositionData {
             key: ctx.accounts.position.key(),
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::ErrorCode,
+    orchestrator::liquidity_orchestrator::calculate_fee_and_reward_growths,
+    state::*,
+    util::{to_timestamp_u64, TickArrayAccount},
+};
+
+use super::{FeesAndRewardsUpdatedEvent, PositionData};
+
+// Remaining accounts passed for each position: position, tick array lower and tick array upper.
+const ACCOUNTS_PER_POSITION: usize = 3;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateFeesAndRewardsMulti<'info> {
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+}
+
+/// Updates the fees and rewards of many positions of a pool in a single instruction.
+///
+/// The remaining accounts are triples of a writable position of the pool followed by its lower and
+/// upper tick arrays. Each position is updated as in `update_fees_and_rewards`. The instruction is
+/// permissionless, since it only moves accounting forward in time.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the instruction. The positions and tick arrays are passed as remaining accounts.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if all positions are successfully updated,
+/// or an `Err` if an error occurs.
+///
+/// # Errors
+///
+/// * `InsufficientRemainingAccountsError` - If no position is provided.
+/// * `InvalidPositionAccountsError` - If the remaining accounts are not triples, or a position does not belong to the pool.
+/// * `AccountNotMutable` - If a provided position is not writable.
+pub fn update_fees_and_rewards_multi_handler<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, UpdateFeesAndRewardsMulti<'info>>,
+) -> Result<()> {
+    if ctx.remaining_accounts.is_empty() {
+        return Err(ErrorCode::InsufficientRemainingAccountsError.into());
+    }
+    if ctx.remaining_accounts.len() % ACCOUNTS_PER_POSITION != 0 {
+        return Err(ErrorCode::InvalidPositionAccountsError.into());
+    }
+
+    let ai_dex_pool_key = ctx.accounts.ai_dex_pool.key();
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
+
+    for accounts in ctx.remaining_accounts.chunks(ACCOUNTS_PER_POSITION) {
+        if !accounts[0].is_writable {
+            return Err(anchor_lang::error::ErrorCode::AccountNotMutable.into());
+        }
+
+        let mut position = Account::<Position>::try_from(&accounts[0])?;
+        if position.ai_dex_pool != ai_dex_pool_key {
+            return Err(ErrorCode::InvalidPositionAccountsError.into());
+        }
+        let tick_array_lower = TickArrayAccount::try_from_with_pool(&accounts[1], &ai_dex_pool_key)?;
+        let tick_array_upper = TickArrayAccount::try_from_with_pool(&accounts[2], &ai_dex_pool_key)?;
+
+        let (position_update, reward_infos) = calculate_fee_and_reward_growths(
+            &ai_dex,
+            &position,
+            &tick_array_lower,
+            &tick_array_upper,
+            timestamp,
+        )?;
+
+        ai_dex.update_rewards(reward_infos, timestamp);
+        position.update(&position_update);
+        position.exit(&crate::ID)?;
+
+        emit_cpi!(FeesAndRewardsUpdatedEvent {
+            ai_dex: ai_dex_pool_key,
+            position: PositionData {
+                key: position.key(),
+                update_info: format!("{:?}", position_update),
+            },
+            tick_array_lower: accounts[1].key(),
+            tick_array_upper: accounts[2].key(),
+            reward_infos: reward_infos.to_vec(),
+            timestamp,
+        });
+    }
+
+    Ok(())
+}
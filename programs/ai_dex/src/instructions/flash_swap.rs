@@ -0,0 +1,327 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+
+use crate::util::{calculate_transfer_fee_excluded_amount, parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
+use crate::{
+    constants::transfer_memo,
+    errors::ErrorCode,
+    state::{AiDexConfig, AiDexPool, PoolAllowlist, PoolAllowlistKind},
+    swap_with_transfer_fee_extension,
+    util::{
+        apply_tick_array_bitmap, check_deadline, load_supplemental_tick_arrays, oracle_volatility_ticks,
+        record_oracle_observation, record_pool_stats, to_timestamp_u64, transfer_from_vault_to_owner,
+        update_ai_dex_after_swap, verify_pool_allowlist, SwapTickSequence, TickArrayAccount,
+    },
+};
+
+// Index of the pool among the accounts of `repay_flash_swap`
+const REPAY_FLASH_SWAP_AI_DEX_POOL_INDEX: usize = 1;
+
+#[event]
+pub struct FlashSwapExecutedEvent {
+    pub token_authority: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub amount: u64,
+    pub other_amount_threshold: u64,
+    pub sqrt_price_limit: u128,
+    pub amount_specified_is_input: bool,
+    pub a_to_b: bool,
+    pub fee_rate: u16,
+    pub input_amount_owed: u64,
+    pub output_amount: u64,
+    pub lp_fee: u64,
+    pub protocol_fee: u64,
+    pub output_transfer_fee: u64,
+    pub pre_sqrt_price: u128,
+    pub post_sqrt_price: u128,
+    pub post_tick_index: i32,
+    pub timestamp: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FlashSwap<'info> {
+    /// The token program for token mint A
+    #[account(address = token_mint_a.to_account_info().owner.clone())]
+    pub token_program_a: Interface<'info, TokenInterface>,
+
+    /// The token program for token mint B
+    #[account(address = token_mint_b.to_account_info().owner.clone())]
+    pub token_program_b: Interface<'info, TokenInterface>,
+
+    /// The memo program
+    pub memo_program: Program<'info, Memo>,
+
+    /// The authority receiving the output of the swap
+    pub token_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == ai_dex_pool.load()?.token_mint_a)]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, constraint = token_owner_account_b.mint == ai_dex_pool.load()?.token_mint_b)]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: The first tick array, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_0: UncheckedAccount<'info>,
+    /// CHECK: The second tick array, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_1: UncheckedAccount<'info>,
+    /// CHECK: The third tick array, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_2: UncheckedAccount<'info>,
+
+    /// CHECK: The oracle account of the pool. An observation is recorded only if it has been initialized.
+    #[account(mut, seeds = [b"oracle", ai_dex_pool.key().as_ref()], bump)]
+    pub oracle: UncheckedAccount<'info>,
+
+    /// CHECK: The statistics account of the pool. The swap is recorded only if it has been initialized.
+    #[account(mut, seeds = [b"pool_stats", ai_dex_pool.key().as_ref()], bump)]
+    pub pool_stats: UncheckedAccount<'info>,
+
+    /// CHECK: The tick array bitmap of the pool. Used only if it has been initialized.
+    #[account(seeds = [b"tick_array_bitmap", ai_dex_pool.key().as_ref()], bump)]
+    pub tick_array_bitmap: UncheckedAccount<'info>,
+
+    /// CHECK: The instructions sysvar, searched for the repayment of the flash swap
+    #[account(address = solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    /// The config of the pool, checked against the protocol-wide emergency pause
+    #[account(
+        address = ai_dex_pool.load()?.ai_dex_config,
+        constraint = !ai_dex_config.paused @ ErrorCode::ProtocolPausedError
+    )]
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    /// The swap allowlist of the pool, required while it is enabled
+    #[account(seeds = [b"swap_allowlist", ai_dex_pool.key().as_ref()], bump)]
+    pub swap_allowlist: Option<Box<Account<'info, PoolAllowlist>>>,
+
+    /// The token account of the token authority holding a gating token of the swap allowlist, if any
+    pub swap_gating_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+}
+
+/// Swaps tokens, sending the output before the input is paid.
+///
+/// The pool is updated and the output sent as in `swap`, and the input amount is recorded as owed
+/// by the pool. A later top-level `repay_flash_swap` instruction of the same transaction on the
+/// same pool must pay it, which is checked through the instructions sysvar, so the output can be
+/// used in between, to arbitrage or to liquidate without capital. A single flash swap of a pool
+/// can await repayment at a time. Referral fees, fee discounts and native SOL are not supported.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the flash swap.
+/// * `amount` - The amount to be swapped.
+/// * `other_amount_threshold` - The minimum output or maximum input, depending on `amount_specified_is_input`.
+/// * `sqrt_price_limit` - The square root price limit for the swap. `0` means no limit in the swap direction.
+/// * `amount_specified_is_input` - Whether the specified amount is the input amount.
+/// * `a_to_b` - The direction of the swap.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+/// * `deadline_timestamp` - Optional unix timestamp after which the transaction is rejected.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the flash swap is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::FlashSwapInProgressError` - If another flash swap of the pool awaits repayment.
+/// * `ErrorCode::SwapHookNotSupportedError` - If the pool has a swap hook, which flash swaps do not call.
+/// * `ErrorCode::FlashSwapRepaymentMissingError` - If no later instruction of the transaction repays the flash swap.
+/// * `ErrorCode::AmountOutBelowMinimumError` - If the output is below `other_amount_threshold`.
+/// * `ErrorCode::AmountInAboveMaximumError` - If the input is above `other_amount_threshold`.
+pub fn flash_swap_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, FlashSwap<'info>>,
+    amount: u64,
+    other_amount_threshold: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+    deadline_timestamp: Option<i64>,
+) -> Result<()> {
+    let ai_dex = &ctx.accounts.ai_dex_pool;
+    let clock = Clock::get()?;
+    check_deadline(deadline_timestamp, clock.unix_timestamp)?;
+    verify_pool_allowlist(
+        &*ai_dex.load()?,
+        PoolAllowlistKind::Swap,
+        ctx.accounts.swap_allowlist.as_deref(),
+        ctx.accounts.token_authority.key,
+        ctx.accounts.swap_gating_token_account.as_deref(),
+    )?;
+    verify_flash_swap_repayment(&ctx.accounts.instructions_sysvar, &ai_dex.key())?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::SupplementalTickArrays,
+        ],
+    )?;
+
+    let tick_array_0 = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_0, &ai_dex.key())?;
+    let tick_array_1 = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_1, &ai_dex.key())?;
+    let tick_array_2 = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_2, &ai_dex.key())?;
+    let supplemental_tick_arrays = load_supplemental_tick_arrays(
+        &remaining_accounts.supplemental_tick_arrays,
+        &ai_dex.key(),
+    )?;
+
+    let mut swap_tick_sequence = SwapTickSequence::new_with_tick_arrays(
+        tick_array_0.load_mut()?,
+        tick_array_1.load_mut().ok(),
+        tick_array_2.load_mut().ok(),
+    );
+    for tick_array in supplemental_tick_arrays.iter() {
+        swap_tick_sequence.push_tick_array(tick_array.load_mut()?);
+    }
+    apply_tick_array_bitmap(
+        &ctx.accounts.tick_array_bitmap,
+        &mut swap_tick_sequence,
+        ai_dex.load()?.tick_spacing,
+        a_to_b,
+    )?;
+
+    let volatility_ticks = oracle_volatility_ticks(&ctx.accounts.oracle, &*ai_dex.load()?, timestamp)?;
+    let pre_sqrt_price = ai_dex.load()?.sqrt_price;
+    let swap_update = swap_with_transfer_fee_extension(
+        &*ai_dex.load()?,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_mint_b,
+        &mut swap_tick_sequence,
+        amount,
+        sqrt_price_limit,
+        amount_specified_is_input,
+        a_to_b,
+        timestamp,
+        0,
+        0,
+        volatility_ticks,
+    )?;
+
+    let (output_mint, input_amount_owed, vault_output_amount) = if a_to_b {
+        (&ctx.accounts.token_mint_b, swap_update.amount_a, swap_update.amount_b)
+    } else {
+        (&ctx.accounts.token_mint_a, swap_update.amount_b, swap_update.amount_a)
+    };
+    let output = calculate_transfer_fee_excluded_amount(output_mint, vault_output_amount)?;
+
+    if amount_specified_is_input {
+        if output.amount < other_amount_threshold {
+            return Err(ErrorCode::AmountOutBelowMinimumError.into());
+        }
+    } else if input_amount_owed > other_amount_threshold {
+        return Err(ErrorCode::AmountInAboveMaximumError.into());
+    }
+
+    record_oracle_observation(&ctx.accounts.oracle, &*ai_dex.load()?, timestamp)?;
+    record_pool_stats(&ctx.accounts.pool_stats, &swap_update, a_to_b, timestamp)?;
+
+    update_ai_dex_after_swap(
+        ai_dex,
+        ctx.accounts.token_authority.key,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_vault_b,
+        &swap_update,
+        a_to_b,
+        timestamp,
+    )?;
+    ai_dex.load_mut()?.start_flash_swap(input_amount_owed, a_to_b)?;
+
+    if a_to_b {
+        transfer_from_vault_to_owner(
+            ai_dex,
+            &ctx.accounts.token_mint_b,
+            &ctx.accounts.token_vault_b,
+            &ctx.accounts.token_owner_account_b,
+            &ctx.accounts.token_program_b,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_b,
+            swap_update.amount_b,
+            transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
+        )?;
+    } else {
+        transfer_from_vault_to_owner(
+            ai_dex,
+            &ctx.accounts.token_mint_a,
+            &ctx.accounts.token_vault_a,
+            &ctx.accounts.token_owner_account_a,
+            &ctx.accounts.token_program_a,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_a,
+            swap_update.amount_a,
+            transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
+        )?;
+    }
+
+    emit_cpi!(FlashSwapExecutedEvent {
+        token_authority: ctx.accounts.token_authority.key(),
+        ai_dex_pool: ai_dex.key(),
+        amount,
+        other_amount_threshold,
+        sqrt_price_limit,
+        amount_specified_is_input,
+        a_to_b,
+        fee_rate: swap_update.fee_rate,
+        input_amount_owed,
+        output_amount: output.amount,
+        lp_fee: swap_update.lp_fee(),
+        protocol_fee: swap_update.next_protocol_fee,
+        output_transfer_fee: output.transfer_fee,
+        pre_sqrt_price,
+        post_sqrt_price: swap_update.next_sqrt_price,
+        post_tick_index: swap_update.next_tick_index,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// Checks that a later top-level instruction of the transaction repays the flash swap of the pool.
+///
+/// # Arguments
+///
+/// * `instructions_sysvar` - The instructions sysvar.
+/// * `ai_dex_pool` - The pool of the flash swap.
+///
+/// # Errors
+///
+/// * `ErrorCode::FlashSwapRepaymentMissingError` - If no later `repay_flash_swap` instruction targets the pool.
+fn verify_flash_swap_repayment(instructions_sysvar: &AccountInfo, ai_dex_pool: &Pubkey) -> Result<()> {
+    let mut index = usize::from(load_current_index_checked(instructions_sysvar)?) + 1;
+    while let Ok(instruction) = load_instruction_at_checked(index, instructions_sysvar) {
+        if instruction.program_id == crate::ID
+            && instruction.data.starts_with(&crate::instruction::RepayFlashSwap::DISCRIMINATOR)
+            && instruction
+                .accounts
+                .get(REPAY_FLASH_SWAP_AI_DEX_POOL_INDEX)
+                .map_or(false, |account| account.pubkey == *ai_dex_pool)
+        {
+            return Ok(());
+        }
+        index += 1;
+    }
+
+    Err(ErrorCode::FlashSwapRepaymentMissingError.into())
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::ErrorCode,
+    orchestrator::{
+        ai_dex_orchestrator::next_seconds_per_liquidity_global,
+        tick_orchestrator::next_seconds_per_liquidity_inside,
+    },
+    state::*,
+    util::{to_timestamp_u64, TickArrayAccount},
+};
+
+#[derive(Accounts)]
+pub struct GetSecondsPerLiquidityInside<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    /// CHECK: The fixed or dynamic tick array holding the lower tick, checked in the handler
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: The fixed or dynamic tick array holding the upper tick, checked in the handler
+    pub tick_array_upper: UncheckedAccount<'info>,
+}
+
+/// Computes the seconds per liquidity inside a tick range of a pool, up to the current timestamp.
+///
+/// The value is a Q64.64 snapshot only comparable with other snapshots of the same range taken
+/// while both ticks stayed initialized. The difference between two snapshots, multiplied by the
+/// liquidity of a position of the range, is the number of seconds the position was in range, so
+/// incentive programs can reward in-range time for any range. It is returned to the caller through
+/// the instruction return data, so other programs can read it after a CPI.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the pool and the tick arrays of the range.
+/// * `tick_lower_index` - The lower tick index of the range.
+/// * `tick_upper_index` - The upper tick index of the range.
+///
+/// # Returns
+///
+/// * `Result<u128>` - The seconds per liquidity inside the range, as a Q64.64.
+///
+/// # Errors
+///
+/// * `InvalidTickIndexError` - If the lower tick index is not below the upper tick index.
+/// * `TickNotInitializedError` - If a tick of the range is not initialized.
+pub fn get_seconds_per_liquidity_inside_handler(
+    ctx: Context<GetSecondsPerLiquidityInside>,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> Result<u128> {
+    if tick_lower_index >= tick_upper_index {
+        return Err(ErrorCode::InvalidTickIndexError.into());
+    }
+
+    let ai_dex_pool_key = ctx.accounts.ai_dex_pool.key();
+    let tick_array_lower = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_lower, &ai_dex_pool_key)?;
+    let tick_array_upper = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_upper, &ai_dex_pool_key)?;
+
+    let ai_dex = ctx.accounts.ai_dex_pool.load()?;
+    let tick_lower = tick_array_lower.get_tick(tick_lower_index, ai_dex.tick_spacing)?;
+    let tick_upper = tick_array_upper.get_tick(tick_upper_index, ai_dex.tick_spacing)?;
+    if !tick_lower.initialized || !tick_upper.initialized {
+        return Err(ErrorCode::TickNotInitializedError.into());
+    }
+
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let seconds_per_liquidity_global_x64 = next_seconds_per_liquidity_global(&ai_dex, timestamp)?;
+
+    Ok(next_seconds_per_liquidity_inside(
+        ai_dex.tick_current_index,
+        &tick_lower,
+        tick_lower_index,
+        &tick_upper,
+        tick_upper_index,
+        seconds_per_liquidity_global_x64,
+    ))
+}
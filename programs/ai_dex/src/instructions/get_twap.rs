@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{state::*, util::to_timestamp_u64};
+
+#[derive(Accounts)]
+pub struct GetTwap<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        seeds = [b"oracle", ai_dex_pool.key().as_ref()],
+        bump,
+        has_one = ai_dex_pool
+    )]
+    pub oracle: AccountLoader<'info, Oracle>,
+}
+
+/// Computes the time-weighted average tick of a pool over the given window.
+///
+/// The result is the geometric-mean price over `[now - window_seconds, now]`, expressed as a tick
+/// index. It is returned to the caller through the instruction return data, so other programs can
+/// read it after a CPI.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the pool and its oracle.
+/// * `window_seconds` - The length of the averaging window in seconds.
+///
+/// # Returns
+///
+/// * `Result<i32>` - The time-weighted average tick index.
+///
+/// # Errors
+///
+/// * `InvalidTwapWindowError` - If `window_seconds` is zero.
+/// * `OracleObservationTooOldError` - If the oracle does not hold observations covering the whole window.
+pub fn get_twap_handler(ctx: Context<GetTwap>, window_seconds: u32) -> Result<i32> {
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let oracle = ctx.accounts.oracle.load()?;
+
+    oracle.twap_tick(
+        timestamp,
+        window_seconds,
+        ctx.accounts.ai_dex_pool.load()?.tick_current_index,
+    )
+}
@@ -1,5 +1,4 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use anchor_spl::memo::Memo;
 
@@ -10,7 +9,9 @@ use crate::orchestrator::liquidity_orchestrator::{
 use crate::math::convert_to_liquidity_delta;
 use crate::state::*;
 use crate::util::{calculate_transfer_fee_included_amount, parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
-use crate::util::{to_timestamp_u64, transfer_from_owner_to_vault, verify_position_authority};
+use crate::util::{check_deadline, to_timestamp_u64, transfer_from_owner_to_vault, verify_position_authority_or_operator, TickArrayAccount};
+use crate::util::verify_pool_allowlist;
+use crate::util::{is_native_mint, parse_native_sol_system_program, unwrap_pool_native_sol, wrap_native_sol};
 
 #[event]
 pub struct IncreaseLiquidityEvent {
@@ -32,10 +33,11 @@ pub struct IncreaseLiquidityEvent {
     pub timestamp: u64,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct ModifyLiquidity<'info> {
     #[account(mut)]
-    pub ai_dex_pool: Account<'info, AiDexPool>,
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
 
     #[account(address = token_mint_a.to_account_info().owner.clone())]
     pub token_program_a: Interface<'info, TokenInterface>,
@@ -44,6 +46,8 @@ pub struct ModifyLiquidity<'info> {
 
     pub memo_program: Program<'info, Memo>,
 
+    /// Pays the rent when a tick of a dynamic tick array gets initialized.
+    #[account(mut)]
     pub position_authority: Signer<'info>,
 
     #[account(mut, has_one = ai_dex_pool)]
@@ -52,28 +56,45 @@ pub struct ModifyLiquidity<'info> {
         constraint = position_token_account.mint == position.position_mint,
         constraint = position_token_account.amount == 1
     )]
-    pub position_token_account: Box<Account<'info, token::TokenAccount>>,
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    #[account(address = ai_dex_pool.token_mint_a)]
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
     pub token_mint_a: InterfaceAccount<'info, Mint>,
-    #[account(address = ai_dex_pool.token_mint_b)]
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
     pub token_mint_b: InterfaceAccount<'info, Mint>,
 
-    #[account(mut, constraint = token_owner_account_a.mint == ai_dex_pool.token_mint_a)]
+    #[account(mut, constraint = token_owner_account_a.mint == ai_dex_pool.load()?.token_mint_a)]
     pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
-    #[account(mut, constraint = token_owner_account_b.mint == ai_dex_pool.token_mint_b)]
+    #[account(mut, constraint = token_owner_account_b.mint == ai_dex_pool.load()?.token_mint_b)]
     pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    #[account(mut, constraint = token_vault_a.key() == ai_dex_pool.token_vault_a)]
+    #[account(mut, constraint = token_vault_a.key() == ai_dex_pool.load()?.token_vault_a)]
     pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
-    #[account(mut, constraint = token_vault_b.key() == ai_dex_pool.token_vault_b)]
+    #[account(mut, constraint = token_vault_b.key() == ai_dex_pool.load()?.token_vault_b)]
     pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    #[account(mut, has_one = ai_dex_pool)]
-    pub tick_array_lower: AccountLoader<'info, TickArray>,
-    #[account(mut, has_one = ai_dex_pool)]
-    pub tick_array_upper: AccountLoader<'info, TickArray>,
+    /// CHECK: A fixed or dynamic tick array of the pool, checked in the handler
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: A fixed or dynamic tick array of the pool, checked in the handler
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
 
+    /// The config of the pool, checked against the protocol-wide emergency pause
+    #[account(
+        address = ai_dex_pool.load()?.ai_dex_config,
+        constraint = !ai_dex_config.paused @ ErrorCode::ProtocolPausedError
+    )]
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    /// The liquidity allowlist of the pool, required while it is enabled
+    #[account(seeds = [b"lp_allowlist", ai_dex_pool.key().as_ref()], bump)]
+    pub lp_allowlist: Option<Box<Account<'info, PoolAllowlist>>>,
+
+    /// The token account of the position authority holding a gating token of the liquidity allowlist, if any
+    pub lp_gating_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 }
 
 /// Handles the increase of liquidity in the protocol.
@@ -85,6 +106,7 @@ pub struct ModifyLiquidity<'info> {
 /// * `token_max_a` - The maximum amount of token A that can be transferred.
 /// * `token_max_b` - The maximum amount of token B that can be transferred.
 /// * `remaining_accounts_info` - Optional information about remaining accounts.
+/// * `deadline_timestamp` - Optional unix timestamp after which the transaction is rejected.
 ///
 /// # Returns
 ///
@@ -94,56 +116,86 @@ pub struct ModifyLiquidity<'info> {
 ///
 /// * `ErrorCode::ZeroLiquidityError` - If the liquidity amount is zero.
 /// * `ErrorCode::TokenLimitExceededError` - If the transfer amount exceeds the specified token limits.
+/// * `ErrorCode::TransactionExpiredError` - If the deadline has passed.
+/// * `ErrorCode::InvalidNativeSolAccountsError` - If native SOL handling is requested for a pool without wSOL.
+/// * `ErrorCode::AuthorityNotAllowlistedError` - If the liquidity allowlist of the pool is enabled and the position authority is not allowed.
 pub fn increase_liquidity_handler<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, ModifyLiquidity<'info>>,
     liquidity_amount: u128,
     token_max_a: u64,
     token_max_b: u64,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
+    deadline_timestamp: Option<i64>,
 ) -> Result<()> {
-    verify_position_authority(
+    verify_pool_allowlist(
+        &*ctx.accounts.ai_dex_pool.load()?,
+        PoolAllowlistKind::Liquidity,
+        ctx.accounts.lp_allowlist.as_deref(),
+        ctx.accounts.position_authority.key,
+        ctx.accounts.lp_gating_token_account.as_deref(),
+    )?;
+
+    verify_position_authority_or_operator(
         &ctx.accounts.position_token_account,
+        &ctx.accounts.position,
         &ctx.accounts.position_authority,
+        &[],
     )?;
 
     if liquidity_amount == 0 {
         return Err(ErrorCode::ZeroLiquidityError.into());
     }
 
-    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let clock = Clock::get()?;
+    check_deadline(deadline_timestamp, clock.unix_timestamp)?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
 
     let remaining_accounts = parse_remaining_accounts(
         &ctx.remaining_accounts,
         &remaining_accounts_info,
-        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+        &[AccountsType::TransferHookA, AccountsType::TransferHookB, AccountsType::NativeSol],
+    )?;
+    let native_sol_system_program = parse_native_sol_system_program(
+        &remaining_accounts.native_sol,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_mint_b,
     )?;
 
     let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, true)?;
 
+    let ai_dex_pool_key = ctx.accounts.ai_dex_pool.key();
+    let tick_array_lower = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_lower, &ai_dex_pool_key)?;
+    let tick_array_upper = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_upper, &ai_dex_pool_key)?;
+
+    let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
     let update = calculate_modify_liquidity(
-        &ctx.accounts.ai_dex_pool,
+        &ai_dex,
         &ctx.accounts.position,
-        &ctx.accounts.tick_array_lower,
-        &ctx.accounts.tick_array_upper,
+        &tick_array_lower,
+        &tick_array_upper,
         liquidity_delta,
         timestamp,
     )?;
 
     sync_modify_liquidity_values(
-        &mut ctx.accounts.ai_dex_pool,
+        &mut ai_dex,
         &mut ctx.accounts.position,
-        &ctx.accounts.tick_array_lower,
-        &ctx.accounts.tick_array_upper,
+        &tick_array_lower,
+        &tick_array_upper,
         update,
         timestamp,
+        &ctx.accounts.position_authority,
+        &ctx.accounts.system_program,
     )?;
 
     let (delta_a, delta_b) = calculate_liquidity_token_deltas(
-        ctx.accounts.ai_dex_pool.tick_current_index,
-        ctx.accounts.ai_dex_pool.sqrt_price,
+        ai_dex.tick_current_index,
+        ai_dex.sqrt_price,
         &ctx.accounts.position,
         liquidity_delta,
     )?;
+    ctx.accounts.position.record_deposit(delta_a, delta_b, ai_dex.sqrt_price, timestamp);
+    drop(ai_dex);
 
     let transfer_fee_included_delta_a = calculate_transfer_fee_included_amount(
         &ctx.accounts.token_mint_a,
@@ -162,7 +214,29 @@ pub fn increase_liquidity_handler<'a, 'b, 'c, 'info>(
         return Err(ErrorCode::TokenLimitExceededError.into());
     }
 
+    // Pay the wSOL deposit in lamports
+    if let Some(system_program) = &native_sol_system_program {
+        if is_native_mint(&ctx.accounts.token_mint_a.key()) {
+            wrap_native_sol(
+                &ctx.accounts.position_authority,
+                &ctx.accounts.token_owner_account_a,
+                &ctx.accounts.token_program_a,
+                system_program,
+                transfer_fee_included_delta_a.amount,
+            )?;
+        } else {
+            wrap_native_sol(
+                &ctx.accounts.position_authority,
+                &ctx.accounts.token_owner_account_b,
+                &ctx.accounts.token_program_b,
+                system_program,
+                transfer_fee_included_delta_b.amount,
+            )?;
+        }
+    }
+
     transfer_from_owner_to_vault(
+        &ctx.accounts.ai_dex_pool,
         &ctx.accounts.position_authority,
         &ctx.accounts.token_mint_a,
         &ctx.accounts.token_owner_account_a,
@@ -174,6 +248,7 @@ pub fn increase_liquidity_handler<'a, 'b, 'c, 'info>(
     )?;
 
     transfer_from_owner_to_vault(
+        &ctx.accounts.ai_dex_pool,
         &ctx.accounts.position_authority,
         &ctx.accounts.token_mint_b,
         &ctx.accounts.token_owner_account_b,
@@ -184,7 +259,20 @@ pub fn increase_liquidity_handler<'a, 'b, 'c, 'info>(
         transfer_fee_included_delta_b.amount,
     )?;
 
-    emit!(IncreaseLiquidityEvent {
+    // Return what is left in the wSOL account as SOL
+    if native_sol_system_program.is_some() {
+        unwrap_pool_native_sol(
+            &ctx.accounts.position_authority,
+            &ctx.accounts.token_mint_a,
+            &ctx.accounts.token_owner_account_a,
+            &ctx.accounts.token_program_a,
+            &ctx.accounts.token_mint_b,
+            &ctx.accounts.token_owner_account_b,
+            &ctx.accounts.token_program_b,
+        )?;
+    }
+
+    emit_cpi!(IncreaseLiquidityEvent {
         liquidity_amount,
         token_max_a,
         token_max_b,
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+use crate::{state::*, util::mark_tick_array_initialized};
+
+#[event]
+pub struct DynamicTickArrayInitializedEvent {
+    pub ai_dex_pool: Pubkey,
+    pub funder: Pubkey,
+    pub tick_array: Pubkey,
+    pub start_tick_index: i32,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(start_tick_index: i32)]
+pub struct InitializeDynamicTickArray<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+        init,
+        payer = funder,
+        seeds = [b"tick_array", ai_dex_pool.key().as_ref(), start_tick_index.to_string().as_bytes()],
+        bump,
+        space = DynamicTickArray::MIN_LEN
+    )]
+    pub tick_array: Account<'info, DynamicTickArray>,
+
+    /// CHECK: only updated if the pool's tick array bitmap has been initialized
+    #[account(
+        mut,
+        seeds = [b"tick_array_bitmap", ai_dex_pool.key().as_ref()],
+        bump
+    )]
+    pub tick_array_bitmap: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Initializes a dynamic tick array with the given starting tick index.
+///
+/// The dynamic tick array uses the same address as a fixed tick array with the same start tick index,
+/// and starts without any stored tick.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the accounts required for initialization.
+/// * `start_tick_index` - The starting index for the tick array.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the initialization is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The start tick index is not a valid start tick index for the pool.
+/// - The pool's tick array bitmap cannot be updated.
+pub fn initialize_dynamic_tick_array_handler(
+    ctx: Context<InitializeDynamicTickArray>,
+    start_tick_index: i32,
+) -> Result<()> {
+    ctx.accounts
+        .tick_array
+        .initialize(&ctx.accounts.ai_dex_pool, start_tick_index)?;
+
+    // Track the new tick array in the pool's bitmap, if the pool has one
+    mark_tick_array_initialized(
+        &ctx.accounts.tick_array_bitmap,
+        start_tick_index,
+        ctx.accounts.ai_dex_pool.load()?.tick_spacing,
+    )?;
+
+    emit_cpi!(DynamicTickArrayInitializedEvent {
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        funder: ctx.accounts.funder.key(),
+        tick_array: ctx.accounts.tick_array.key(),
+        start_tick_index,
+    });
+
+    Ok(())
+}
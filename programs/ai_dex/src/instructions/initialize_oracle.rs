@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::{state::*, util::to_timestamp_u64};
+
+#[event]
+pub struct OracleInitializedEvent {
+    pub ai_dex_pool: Pubkey,
+    pub funder: Pubkey,
+    pub oracle: Pubkey,
+    pub timestamp: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeOracle<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+        init,
+        payer = funder,
+        seeds = [b"oracle", ai_dex_pool.key().as_ref()],
+        bump,
+        space = Oracle::LEN
+    )]
+    pub oracle: AccountLoader<'info, Oracle>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Initializes the price oracle of a pool.
+///
+/// Once initialized, every swap on the pool records an observation of
+/// (timestamp, tick_cumulative, liquidity) into the oracle ring buffer.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the accounts required for initialization.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the initialization is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The oracle account cannot be loaded for initialization.
+pub fn initialize_oracle_handler(ctx: Context<InitializeOracle>) -> Result<()> {
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let mut oracle = ctx.accounts.oracle.load_init()?;
+    oracle.initialize(
+        ctx.accounts.ai_dex_pool.key(),
+        timestamp,
+        ctx.accounts.ai_dex_pool.load()?.liquidity,
+    );
+
+    emit_cpi!(OracleInitializedEvent {
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        funder: ctx.accounts.funder.key(),
+        oracle: ctx.accounts.oracle.key(),
+        timestamp,
+    });
+
+    Ok(())
+}
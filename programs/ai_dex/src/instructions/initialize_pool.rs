@@ -1,12 +1,20 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
 use crate::{
   errors::ErrorCode,
+  math::tick_index_from_sqrt_price,
   state::*,
-  util::{is_token_wrapper_initialized, is_supported_token_mint}
+  util::{load_token_wrapper, is_supported_token_mint, to_timestamp_u64}
 };
 
+// Window of the time-weighted average price of the reference oracle the initial price is checked against
+const INITIAL_PRICE_ORACLE_WINDOW_SECONDS: u32 = 30 * 60;
+
+// Maximum distance between the initial price and the reference price, in ticks of about 0.01% each
+const MAX_INITIAL_PRICE_DEVIATION_TICKS: i32 = 500;
+
 #[event]
 pub struct PoolInitializedEvent {
     pub ai_dex_pool: Pubkey,
@@ -24,8 +32,14 @@ pub struct PoolInitializedEvent {
     pub fee_tier: Pubkey,
     pub token_program_a: Pubkey,
     pub token_program_b: Pubkey,
+    pub tick_array_bitmap: Pubkey,
+    pub open_time: u64,
+    pub pool_creation_fee: u64,
+    pub pool_creation_fee_mint: Pubkey,
+    pub full_range_only: bool,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(tick_spacing: u16)]
 pub struct InitializePool<'info> {
@@ -71,7 +85,16 @@ pub struct InitializePool<'info> {
         payer = funder,
         space = AiDexPool::LEN
     )]
-    pub ai_dex_pool: Box<Account<'info, AiDexPool>>,
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        init,
+        seeds = [b"tick_array_bitmap", ai_dex_pool.key().as_ref()],
+        bump,
+        payer = funder,
+        space = TickArrayBitmap::LEN
+    )]
+    pub tick_array_bitmap: AccountLoader<'info, TickArrayBitmap>,
 
     #[account(
         init,
@@ -100,15 +123,43 @@ pub struct InitializePool<'info> {
     pub token_program_b: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: The destination of the pool creation fee set in the config, a wallet for a fee in
+    /// lamports or a token account for a fee in tokens. Required while the config charges a fee.
+    #[account(mut, address = ai_dex_config.pool_creation_fee_destination)]
+    pub pool_creation_fee_destination: Option<UncheckedAccount<'info>>,
+
+    /// The mint of a pool creation fee in tokens
+    #[account(address = ai_dex_config.pool_creation_fee_mint)]
+    pub pool_creation_fee_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// The token account of the funder paying a pool creation fee in tokens
+    #[account(mut, token::authority = funder)]
+    pub pool_creation_fee_funder_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The token program of the mint of a pool creation fee in tokens
+    pub pool_creation_fee_token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// An existing pool of the same token mints, whose oracle prices the initial price, if any
+    pub reference_pool: Option<AccountLoader<'info, AiDexPool>>,
+
+    /// The oracle of the reference pool, required along with it
+    pub reference_oracle: Option<AccountLoader<'info, Oracle>>,
 }
 
 /// Initializes a new pool in the protocol.
 ///
+/// Pools initialized from a full range only fee tier only allow full range positions. If a
+/// reference pool of the same token mints and its oracle are passed, the initial price must be
+/// within `MAX_INITIAL_PRICE_DEVIATION_TICKS` of the time-weighted average price of the reference
+/// pool over the last `INITIAL_PRICE_ORACLE_WINDOW_SECONDS`.
+///
 /// # Arguments
 ///
 /// * `ctx` - The context containing all the accounts and programs required for the operation.
 /// * `tick_spacing` - The spacing between ticks in the pool.
 /// * `initial_sqrt_price` - The initial square root price of the pool.
+/// * `open_time` - The timestamp from which swaps are allowed, so that liquidity can be seeded before launch.
 ///
 /// # Returns
 ///
@@ -117,43 +168,54 @@ pub struct InitializePool<'info> {
 /// # Errors
 ///
 /// * `ErrorCode::UnsupportedTokenMintError` - If the token mint is not supported.
+/// * `ErrorCode::PoolCreationFeeAccountsError` - If the config charges a pool creation fee and the accounts to pay it are missing.
+/// * `ErrorCode::InvalidReferenceOracleError` - If the reference oracle is missing or does not price the token mints of the pool.
+/// * `ErrorCode::InitialPriceDeviationError` - If the initial price deviates too far from the reference price.
 pub fn initialize_pool_handler(
     ctx: Context<InitializePool>,
     tick_spacing: u16,
     initial_sqrt_price: u128,
+    open_time: u64,
 ) -> Result<()> {
     let token_mint_a = ctx.accounts.token_mint_a.key();
     let token_mint_b = ctx.accounts.token_mint_b.key();
 
-    let ai_dex = &mut ctx.accounts.ai_dex_pool;
+    let mut ai_dex = ctx.accounts.ai_dex_pool.load_init()?;
     let ai_dex_config = &ctx.accounts.ai_dex_config;
 
     let default_fee_rate = ctx.accounts.fee_tier.default_fee_rate;
+    let full_range_only = ctx.accounts.fee_tier.full_range_only;
 
     // ignore the bump passed and use one Anchor derived
     let bump = ctx.bumps.ai_dex_pool;
 
     // Don't allow creating a pool with unsupported token mints
-    let is_token_wrapper_initialized_a = is_token_wrapper_initialized(
+    let token_wrapper_a = load_token_wrapper(
       ai_dex_config.key(),
       token_mint_a,
       &ctx.accounts.token_wrapper_a
     )?;
 
-    if !is_supported_token_mint(&ctx.accounts.token_mint_a, is_token_wrapper_initialized_a).unwrap() {
+    if !is_supported_token_mint(&ctx.accounts.token_mint_a, token_wrapper_a.as_ref()).unwrap() {
       return Err(ErrorCode::UnsupportedTokenMintError.into());
     }
 
-    let is_token_wrapper_initialized_b = is_token_wrapper_initialized(
+    let token_wrapper_b = load_token_wrapper(
       ai_dex_config.key(),
       token_mint_b,
       &ctx.accounts.token_wrapper_b
     )?;
 
-    if !is_supported_token_mint(&ctx.accounts.token_mint_b, is_token_wrapper_initialized_b).unwrap() {
+    if !is_supported_token_mint(&ctx.accounts.token_mint_b, token_wrapper_b.as_ref()).unwrap() {
       return Err(ErrorCode::UnsupportedTokenMintError.into());
     }
 
+    // Reject initial prices far off the market price, which would be arbitraged away at once
+    verify_initial_price(&ctx.accounts, initial_sqrt_price)?;
+
+    // Collect the pool creation fee from the funder
+    collect_pool_creation_fee(&ctx.accounts)?;
+
     // Initialize the pool
     let result = ai_dex.initialize(
         ai_dex_config,
@@ -165,13 +227,21 @@ pub fn initialize_pool_handler(
         ctx.accounts.token_vault_a.key(),
         token_mint_b,
         ctx.accounts.token_vault_b.key(),
+        open_time,
+        full_range_only,
     );
 
     // Check for initialization errors
     match result {
         Ok(_) => {
-            emit!(PoolInitializedEvent {
-                ai_dex_pool: ai_dex.key(),
+            // Start tracking the pool's tick arrays from an empty bitmap
+            ctx.accounts
+                .tick_array_bitmap
+                .load_init()?
+                .initialize(ctx.accounts.ai_dex_pool.key());
+
+            emit_cpi!(PoolInitializedEvent {
+                ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
                 ai_dex_config: ai_dex_config.key(),
                 token_mint_a: token_mint_a,
                 token_mint_b: token_mint_b,
@@ -186,6 +256,11 @@ pub fn initialize_pool_handler(
                 fee_tier: ctx.accounts.fee_tier.key(),
                 token_program_a: ctx.accounts.token_program_a.key(),
                 token_program_b: ctx.accounts.token_program_b.key(),
+                tick_array_bitmap: ctx.accounts.tick_array_bitmap.key(),
+                open_time,
+                pool_creation_fee: ai_dex_config.pool_creation_fee,
+                pool_creation_fee_mint: ai_dex_config.pool_creation_fee_mint,
+                full_range_only: ai_dex.is_full_range_only(),
             });            
             Ok(())
         },
@@ -196,3 +271,91 @@ pub fn initialize_pool_handler(
         }
     }
 }
+
+/// Checks the initial price against the oracle of the reference pool, if one is passed.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidReferenceOracleError` - If the reference oracle is missing or does not price the token mints of the pool.
+/// * `ErrorCode::InitialPriceDeviationError` - If the initial price deviates too far from the reference price.
+/// * `ErrorCode::OracleObservationTooOldError` - If the reference oracle does not cover the whole window.
+fn verify_initial_price(accounts: &InitializePool, initial_sqrt_price: u128) -> Result<()> {
+    let (reference_pool_loader, reference_oracle) = match (&accounts.reference_pool, &accounts.reference_oracle) {
+        (None, None) => return Ok(()),
+        (Some(reference_pool), Some(reference_oracle)) => (reference_pool, reference_oracle.load()?),
+        _ => return Err(ErrorCode::InvalidReferenceOracleError.into()),
+    };
+    let reference_pool = reference_pool_loader.load()?;
+
+    if reference_oracle.ai_dex_pool != reference_pool_loader.key()
+        || reference_pool.token_mint_a != accounts.token_mint_a.key()
+        || reference_pool.token_mint_b != accounts.token_mint_b.key()
+    {
+        return Err(ErrorCode::InvalidReferenceOracleError.into());
+    }
+
+    let reference_tick = reference_oracle.twap_tick(
+        to_timestamp_u64(Clock::get()?.unix_timestamp)?,
+        INITIAL_PRICE_ORACLE_WINDOW_SECONDS,
+        reference_pool.tick_current_index,
+    )?;
+    let initial_tick = tick_index_from_sqrt_price(&initial_sqrt_price);
+    if (i64::from(initial_tick) - i64::from(reference_tick)).abs() > i64::from(MAX_INITIAL_PRICE_DEVIATION_TICKS) {
+        return Err(ErrorCode::InitialPriceDeviationError.into());
+    }
+
+    Ok(())
+}
+
+/// Transfers the pool creation fee of the config, if any, from the funder to its destination.
+///
+/// # Errors
+///
+/// * `ErrorCode::PoolCreationFeeAccountsError` - If an account required to pay the fee is missing.
+fn collect_pool_creation_fee(accounts: &InitializePool) -> Result<()> {
+    let ai_dex_config = &accounts.ai_dex_config;
+    if ai_dex_config.pool_creation_fee == 0 {
+        return Ok(());
+    }
+
+    let destination = accounts
+        .pool_creation_fee_destination
+        .as_ref()
+        .ok_or(ErrorCode::PoolCreationFeeAccountsError)?;
+
+    if ai_dex_config.pool_creation_fee_mint == Pubkey::default() {
+        return system_program::transfer(
+            CpiContext::new(
+                accounts.system_program.to_account_info(),
+                Transfer {
+                    from: accounts.funder.to_account_info(),
+                    to: destination.to_account_info(),
+                },
+            ),
+            ai_dex_config.pool_creation_fee,
+        );
+    }
+
+    let (mint, funder_account, token_program) = match (
+        &accounts.pool_creation_fee_mint,
+        &accounts.pool_creation_fee_funder_account,
+        &accounts.pool_creation_fee_token_program,
+    ) {
+        (Some(mint), Some(funder_account), Some(token_program)) => (mint, funder_account, token_program),
+        _ => return Err(ErrorCode::PoolCreationFeeAccountsError.into()),
+    };
+
+    token_interface::transfer_checked(
+        CpiContext::new(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: funder_account.to_account_info(),
+                mint: mint.to_account_info(),
+                to: destination.to_account_info(),
+                authority: accounts.funder.to_account_info(),
+            },
+        ),
+        ai_dex_config.pool_creation_fee,
+        mint.decimals,
+    )
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[event]
+pub struct PoolStatsInitializedEvent {
+    pub ai_dex_pool: Pubkey,
+    pub funder: Pubkey,
+    pub pool_stats: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializePoolStats<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+        init,
+        payer = funder,
+        seeds = [b"pool_stats", ai_dex_pool.key().as_ref()],
+        bump,
+        space = PoolStats::LEN
+    )]
+    pub pool_stats: Account<'info, PoolStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Initializes the cumulative statistics account of a pool.
+///
+/// Once initialized, every swap through `swap` or `two_hop_swap` adds its volume, fees and
+/// protocol fees to the statistics. The swaps made before are not accounted for.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the accounts required for initialization.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the initialization is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The statistics account of the pool already exists.
+pub fn initialize_pool_stats_handler(ctx: Context<InitializePoolStats>) -> Result<()> {
+    ctx.accounts.pool_stats.initialize(ctx.accounts.ai_dex_pool.key());
+
+    emit_cpi!(PoolStatsInitializedEvent {
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        funder: ctx.accounts.funder.key(),
+        pool_stats: ctx.accounts.pool_stats.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::metadata::Metadata;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::{state::*, util::mint_position_collection_token};
+
+#[event]
+pub struct PositionCollectionInitializedEvent {
+    pub ai_dex_pool: Pubkey,
+    pub funder: Pubkey,
+    pub position_collection_mint: Pubkey,
+    pub position_collection_metadata: Pubkey,
+    pub position_collection_master_edition: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializePositionCollection<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+        init,
+        payer = funder,
+        seeds = [b"position_collection", ai_dex_pool.key().as_ref()],
+        bump,
+        mint::authority = ai_dex_pool,
+        mint::decimals = 0,
+    )]
+    pub position_collection_mint: Box<Account<'info, Mint>>,
+
+    #[account(init,
+        payer = funder,
+        associated_token::mint = position_collection_mint,
+        associated_token::authority = ai_dex_pool,
+    )]
+    pub position_collection_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: checked via the Metadata CPI call
+    #[account(mut)]
+    pub position_collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: checked via the Metadata CPI call
+    #[account(mut)]
+    pub position_collection_master_edition: UncheckedAccount<'info>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub metadata_program: Program<'info, Metadata>,
+}
+
+/// Initializes the collection NFT grouping the position NFTs of a pool.
+///
+/// It is meant to be sent together with the pool initialization. Once the collection exists,
+/// the positions opened with metadata can pass it to be minted as verified members of the
+/// collection, so wallets and marketplaces can group the positions of the pool.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the accounts required for initialization.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the initialization is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The collection token, its metadata or its master edition cannot be created.
+pub fn initialize_position_collection_handler(ctx: Context<InitializePositionCollection>) -> Result<()> {
+    mint_position_collection_token(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.position_collection_mint,
+        &ctx.accounts.position_collection_token_account,
+        &ctx.accounts.position_collection_metadata,
+        &ctx.accounts.position_collection_master_edition,
+        &ctx.accounts.funder,
+        &ctx.accounts.metadata_program,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+        &ctx.accounts.rent,
+    )?;
+
+    emit_cpi!(PositionCollectionInitializedEvent {
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        funder: ctx.accounts.funder.key(),
+        position_collection_mint: ctx.accounts.position_collection_mint.key(),
+        position_collection_metadata: ctx.accounts.position_collection_metadata.key(),
+        position_collection_master_edition: ctx.accounts.position_collection_master_edition.key(),
+    });
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::state::*;
+use crate::{state::*, util::mark_tick_array_initialized};
 
 #[event]
 pub struct TickArrayInitializedEvent {
@@ -10,10 +10,11 @@ pub struct TickArrayInitializedEvent {
     pub start_tick_index: i32,  // Assuming tick indices are 32-bit integers
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(start_tick_index: i32)]
 pub struct InitializeTickArray<'info> {
-    pub ai_dex_pool: Account<'info, AiDexPool>,
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
 
     #[account(mut)]
     pub funder: Signer<'info>,
@@ -27,6 +28,14 @@ pub struct InitializeTickArray<'info> {
     )]
     pub tick_array: AccountLoader<'info, TickArray>,
 
+    /// CHECK: only updated if the pool's tick array bitmap has been initialized
+    #[account(
+        mut,
+        seeds = [b"tick_array_bitmap", ai_dex_pool.key().as_ref()],
+        bump
+    )]
+    pub tick_array_bitmap: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -46,6 +55,7 @@ pub struct InitializeTickArray<'info> {
 /// This function will return an error if:
 /// - The tick array cannot be loaded for initialization.
 /// - The tick array initialization fails.
+/// - The pool's tick array bitmap cannot be updated.
 pub fn initialize_tick_array_handler(ctx: Context<InitializeTickArray>, start_tick_index: i32) -> Result<()> {
     // Attempt to load and initialize the tick array
     let mut tick_array = match ctx.accounts.tick_array.load_init() {
@@ -59,9 +69,16 @@ pub fn initialize_tick_array_handler(ctx: Context<InitializeTickArray>, start_ti
     // Attempt to initialize the tick array
     match tick_array.initialize(&ctx.accounts.ai_dex_pool, start_tick_index) {
         Ok(_) => {
+            // Track the new tick array in the pool's bitmap, if the pool has one
+            mark_tick_array_initialized(
+                &ctx.accounts.tick_array_bitmap,
+                start_tick_index,
+                ctx.accounts.ai_dex_pool.load()?.tick_spacing,
+            )?;
+
             // Emit a log event after successful initialization
             // Structured JSON logging
-            emit!(TickArrayInitializedEvent {
+            emit_cpi!(TickArrayInitializedEvent {
                 ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
                 funder: ctx.accounts.funder.key(),
                 tick_array: ctx.accounts.tick_array.key(),
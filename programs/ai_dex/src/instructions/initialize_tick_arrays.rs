@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::ErrorCode,
+    state::*,
+    util::{create_program_account, mark_tick_array_initialized},
+};
+
+/// The maximum number of tick arrays that can be initialized in a single instruction.
+pub const MAX_TICK_ARRAYS_PER_INITIALIZE: usize = 8;
+
+#[event]
+pub struct TickArraysInitializedEvent {
+    pub ai_dex_pool: Pubkey,
+    pub funder: Pubkey,
+    pub tick_arrays: Vec<Pubkey>,
+    pub start_tick_indexes: Vec<i32>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeTickArrays<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// CHECK: only updated if the pool's tick array bitmap has been initialized
+    #[account(
+        mut,
+        seeds = [b"tick_array_bitmap", ai_dex_pool.key().as_ref()],
+        bump
+    )]
+    pub tick_array_bitmap: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // Remaining accounts, one for each start tick index in the same order:
+    // - tick_array (mut), the uninitialized tick array PDA
+}
+
+/// Initializes several tick arrays of a pool in a single instruction.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the accounts required for initialization.
+/// * `start_tick_indexes` - The starting index of each tick array to initialize.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if all tick arrays are initialized, otherwise returns an error.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - No start tick index is given, or more than `MAX_TICK_ARRAYS_PER_INITIALIZE`.
+/// - A remaining account is not the tick array PDA of its start tick index.
+/// - A tick array already exists or its initialization fails.
+/// - The pool's tick array bitmap cannot be updated.
+pub fn initialize_tick_arrays_handler<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, InitializeTickArrays<'info>>,
+    start_tick_indexes: Vec<i32>,
+) -> Result<()> {
+    if start_tick_indexes.is_empty() || start_tick_indexes.len() > MAX_TICK_ARRAYS_PER_INITIALIZE {
+        return Err(ErrorCode::InvalidTickArrayBatchLengthError.into());
+    }
+    if ctx.remaining_accounts.len() != start_tick_indexes.len() {
+        return Err(ErrorCode::InvalidTickArrayAccountError.into());
+    }
+
+    let ai_dex_pool_key = ctx.accounts.ai_dex_pool.key();
+    let tick_spacing = ctx.accounts.ai_dex_pool.load()?.tick_spacing;
+    let rent = Rent::get()?;
+
+    let mut tick_arrays = Vec::with_capacity(start_tick_indexes.len());
+    for (start_tick_index, tick_array_info) in start_tick_indexes.iter().zip(ctx.remaining_accounts.iter()) {
+        let start_tick_index_seed = start_tick_index.to_string();
+        let (tick_array_key, bump) = Pubkey::find_program_address(
+            &[b"tick_array", ai_dex_pool_key.as_ref(), start_tick_index_seed.as_bytes()],
+            &crate::ID,
+        );
+        if tick_array_info.key() != tick_array_key {
+            return Err(ErrorCode::InvalidTickArrayAccountError.into());
+        }
+
+        create_program_account(
+            &ctx.accounts.funder,
+            tick_array_info,
+            &ctx.accounts.system_program,
+            &[b"tick_array", ai_dex_pool_key.as_ref(), start_tick_index_seed.as_bytes(), &[bump]],
+            TickArray::LEN,
+            &rent,
+        )?;
+
+        let tick_array = AccountLoader::<TickArray>::try_from_unchecked(&crate::ID, tick_array_info)?;
+        tick_array
+            .load_init()?
+            .initialize(&ctx.accounts.ai_dex_pool, *start_tick_index)?;
+        // Write the account discriminator, as `init` would do for a single tick array
+        tick_array.exit(&crate::ID)?;
+
+        mark_tick_array_initialized(&ctx.accounts.tick_array_bitmap, *start_tick_index, tick_spacing)?;
+        tick_arrays.push(tick_array_key);
+    }
+
+    emit_cpi!(TickArraysInitializedEvent {
+        ai_dex_pool: ai_dex_pool_key,
+        funder: ctx.accounts.funder.key(),
+        tick_arrays,
+        start_tick_indexes,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::transfer_memo;
+use crate::errors::ErrorCode;
+use crate::math::{get_limit_order_fill_amount, sqrt_price_from_tick_index};
+use crate::state::*;
+use crate::util::{
+    calculate_transfer_fee_included_amount, parse_remaining_accounts, transfer_from_owner_to_vault,
+    transfer_from_vault_to_owner, AccountsType, RemainingAccountsInfo,
+};
+
+#[event]
+pub struct LimitOrderFilledEvent {
+    pub taker: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub limit_order: Pubkey,
+    pub tick_index: i32,
+    pub a_to_b: bool,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub transfer_fee_included_amount_out: u64,
+    pub amount_in_remaining: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FillLimitOrder<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    pub taker: Signer<'info>,
+
+    #[account(mut, has_one = ai_dex_pool)]
+    pub limit_order: Box<Account<'info, LimitOrder>>,
+
+    #[account(address = ai_dex_pool.load()?.input_token_mint(limit_order.a_to_b))]
+    pub token_mint_input: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = ai_dex_pool.load()?.output_token_mint(limit_order.a_to_b))]
+    pub token_mint_output: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The account of the taker receiving the input token of the order.
+    #[account(mut, constraint = token_taker_account_input.mint == token_mint_input.key())]
+    pub token_taker_account_input: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// The account of the taker paying the output token of the order.
+    #[account(mut, constraint = token_taker_account_output.mint == token_mint_output.key())]
+    pub token_taker_account_output: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, address = ai_dex_pool.load()?.input_token_vault(limit_order.a_to_b))]
+    pub token_vault_input: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = ai_dex_pool.load()?.output_token_vault(limit_order.a_to_b))]
+    pub token_vault_output: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = token_mint_input.to_account_info().owner.clone())]
+    pub token_program_input: Interface<'info, TokenInterface>,
+    #[account(address = token_mint_output.to_account_info().owner.clone())]
+    pub token_program_output: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+}
+
+/// Fills a limit order at the exact price of its tick.
+///
+/// The taker buys `amount` of the input token of the order and pays for it in the output token at
+/// the price of the order's tick. The payment is held by the vault of the pool for the order owner.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required to fill the order.
+/// * `amount` - The amount of input token of the order to buy.
+/// * `other_amount_threshold` - The maximum amount of output token the taker pays, transfer fee included.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the order is filled, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::NoTradableAmountError` - If the amount is zero.
+/// * `ErrorCode::LimitOrderFillExceedsAmountError` - If the amount exceeds the open amount of the order.
+/// * `ErrorCode::AmountInAboveMaximumError` - If the taker would pay more than `other_amount_threshold`.
+pub fn fill_limit_order_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, FillLimitOrder<'info>>,
+    amount: u64,
+    other_amount_threshold: u64,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    if amount == 0 {
+        return Err(ErrorCode::NoTradableAmountError.into());
+    }
+
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::TransferHookInput, AccountsType::TransferHookOutput],
+    )?;
+
+    let tick_index = ctx.accounts.limit_order.tick_index;
+    let a_to_b = ctx.accounts.limit_order.a_to_b;
+    let amount_out = get_limit_order_fill_amount(
+        sqrt_price_from_tick_index(tick_index),
+        amount,
+        a_to_b,
+    )?;
+    ctx.accounts.limit_order.fill(amount, amount_out)?;
+
+    // The vault must receive the full payment of the order owner
+    let transfer_fee_included_amount_out = calculate_transfer_fee_included_amount(
+        &ctx.accounts.token_mint_output,
+        amount_out,
+    )?.amount;
+    if transfer_fee_included_amount_out > other_amount_threshold {
+        return Err(ErrorCode::AmountInAboveMaximumError.into());
+    }
+
+    transfer_from_owner_to_vault(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.taker,
+        &ctx.accounts.token_mint_output,
+        &ctx.accounts.token_taker_account_output,
+        &ctx.accounts.token_vault_output,
+        &ctx.accounts.token_program_output,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_output,
+        transfer_fee_included_amount_out,
+    )?;
+    transfer_from_vault_to_owner(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.token_mint_input,
+        &ctx.accounts.token_vault_input,
+        &ctx.accounts.token_taker_account_input,
+        &ctx.accounts.token_program_input,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_input,
+        amount,
+        transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
+    )?;
+
+    emit_cpi!(LimitOrderFilledEvent {
+        taker: ctx.accounts.taker.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        limit_order: ctx.accounts.limit_order.key(),
+        tick_index,
+        a_to_b,
+        amount_in: amount,
+        amount_out,
+        transfer_fee_included_amount_out,
+        amount_in_remaining: ctx.accounts.limit_order.amount_in,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,7 @@
+pub mod cancel_limit_order;
+pub mod fill_limit_order;
+pub mod place_limit_order;
+
+pub use cancel_limit_order::*;
+pub use fill_limit_order::*;
+pub use place_limit_order::*;
@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::*;
+use crate::util::{
+    calculate_transfer_fee_excluded_amount, parse_remaining_accounts, transfer_from_owner_to_vault,
+    AccountsType, RemainingAccountsInfo,
+};
+
+#[event]
+pub struct LimitOrderPlacedEvent {
+    pub owner: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub limit_order: Pubkey,
+    pub tick_index: i32,
+    pub a_to_b: bool,
+    pub amount: u64,
+    pub amount_in: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(tick_index: i32, a_to_b: bool)]
+pub struct PlaceLimitOrder<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [
+            b"limit_order",
+            ai_dex_pool.key().as_ref(),
+            owner.key().as_ref(),
+            tick_index.to_le_bytes().as_ref(),
+            &[a_to_b as u8],
+        ],
+        bump,
+        space = LimitOrder::LEN
+    )]
+    pub limit_order: Box<Account<'info, LimitOrder>>,
+
+    #[account(address = ai_dex_pool.load()?.input_token_mint(a_to_b))]
+    pub token_mint_input: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, constraint = token_owner_account_input.mint == token_mint_input.key())]
+    pub token_owner_account_input: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = ai_dex_pool.load()?.input_token_vault(a_to_b))]
+    pub token_vault_input: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = token_mint_input.to_account_info().owner.clone())]
+    pub token_program_input: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Places a limit order at a tick of a pool.
+///
+/// The input token is transferred into the vault of the pool, where it rests until a taker buys it
+/// at the exact price of the tick with `fill_limit_order`, or the owner cancels the order.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required to place the order.
+/// * `tick_index` - The tick whose price the order sells at.
+/// * `a_to_b` - If true the order sells token A for token B, otherwise token B for token A.
+/// * `amount` - The amount of input token transferred from the owner.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the order is placed, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidTickIndexError` - If the tick index is out of bounds.
+/// * `ErrorCode::NoTradableAmountError` - If no input token reaches the vault.
+pub fn place_limit_order_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, PlaceLimitOrder<'info>>,
+    tick_index: i32,
+    a_to_b: bool,
+    amount: u64,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::TransferHookInput],
+    )?;
+
+    // The order only sells what the vault receives
+    let amount_in = calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_input, amount)?.amount;
+    ctx.accounts.limit_order.initialize(
+        ctx.accounts.ai_dex_pool.key(),
+        ctx.accounts.owner.key(),
+        tick_index,
+        a_to_b,
+        amount_in,
+    )?;
+
+    transfer_from_owner_to_vault(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.owner,
+        &ctx.accounts.token_mint_input,
+        &ctx.accounts.token_owner_account_input,
+        &ctx.accounts.token_vault_input,
+        &ctx.accounts.token_program_input,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_input,
+        amount,
+    )?;
+
+    emit_cpi!(LimitOrderPlacedEvent {
+        owner: ctx.accounts.owner.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        limit_order: ctx.accounts.limit_order.key(),
+        tick_index,
+        a_to_b,
+        amount,
+        amount_in,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::state::*;
+use crate::util::{to_timestamp_u64, verify_position_authority};
+
+#[event]
+pub struct PositionLockedEvent {
+    pub position_authority: Pubkey,
+    pub position: Pubkey,
+    pub position_mint: Pubkey,
+    pub locked_until: u64,
+    pub timestamp: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct LockPosition<'info> {
+    pub position_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub position: Account<'info, Position>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+}
+
+/// Locks the liquidity of a position until the given timestamp.
+///
+/// While locked, the liquidity of the position cannot be decreased and the position cannot be
+/// closed. Liquidity can still be added, and fees and rewards can still be collected. An existing
+/// lock can only be extended.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required to lock the position.
+/// * `locked_until` - The unix timestamp until which the liquidity is locked.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the position is locked, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidPositionLockError` - If `locked_until` is not in the future or shortens the current lock.
+/// * Any error from verifying the position authority.
+pub fn lock_position_handler(ctx: Context<LockPosition>, locked_until: u64) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    ctx.accounts.position.lock(locked_until, timestamp)?;
+
+    emit_cpi!(PositionLockedEvent {
+        position_authority: ctx.accounts.position_authority.key(),
+        position: ctx.accounts.position.key(),
+        position_mint: ctx.accounts.position.position_mint,
+        locked_until,
+        timestamp,
+    });
+
+    Ok(())
+}
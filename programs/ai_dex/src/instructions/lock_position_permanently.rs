@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::util::verify_position_authority;
+
+use super::LockPosition;
+
+#[event]
+pub struct PositionPermanentlyLockedEvent {
+    pub position_authority: Pubkey,
+    pub position: Pubkey,
+    pub position_mint: Pubkey,
+    pub liquidity: u128,
+}
+
+/// Locks the liquidity of a position forever.
+///
+/// The lock is irreversible: the liquidity of the position can never be decreased and the
+/// position can never be closed. The position NFT stays with its holder, who can still collect
+/// the fees and rewards of the locked liquidity.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required to lock the position.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the position is locked, otherwise returns an error.
+///
+/// # Errors
+///
+/// * Any error from verifying the position authority.
+pub fn lock_position_permanently_handler(ctx: Context<LockPosition>) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    ctx.accounts.position.lock_permanently();
+
+    emit_cpi!(PositionPermanentlyLockedEvent {
+        position_authority: ctx.accounts.position_authority.key(),
+        position: ctx.accounts.position.key(),
+        position_mint: ctx.accounts.position.position_mint,
+        liquidity: ctx.accounts.position.liquidity,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,353 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface};
+use std::convert::TryFrom;
+
+use crate::errors::ErrorCode;
+use crate::math::convert_to_liquidity_delta;
+use crate::orchestrator::liquidity_orchestrator::{
+    calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
+};
+use crate::state::*;
+use crate::util::{calculate_transfer_fee_included_amount, parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
+use crate::util::{
+    mint_lp_token, remaining_accounts_slice, to_timestamp_u64, transfer_from_owner_to_vault, verify_pool_allowlist,
+    TickArrayAccount,
+};
+
+// Accounts passed in the `RewardAccounts` slice for each initialized reward:
+// reward owner account, reward mint, reward vault and reward token program.
+const ACCOUNTS_PER_REWARD: usize = 4;
+
+#[event]
+pub struct LpTokenDepositedEvent {
+    pub token_authority: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub lp_token: Pubkey,
+    pub lp_amount: u64,
+    pub liquidity_amount: u128,
+    pub delta_a: u64,
+    pub delta_b: u64,
+    pub fee_share_a: u64,
+    pub fee_share_b: u64,
+    pub reward_shares: [u64; NUM_REWARDS],
+    pub transfer_fee_included_amount_a: u64,
+    pub transfer_fee_included_amount_b: u64,
+    pub timestamp: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ModifyLpToken<'info> {
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(has_one = ai_dex_pool, has_one = lp_mint, has_one = position)]
+    pub lp_token: Box<Account<'info, LpToken>>,
+
+    #[account(mut)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut, token::mint = lp_mint)]
+    pub lp_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub position: Box<Account<'info, Position>>,
+
+    /// Pays the rent when a tick of a dynamic tick array gets initialized.
+    #[account(mut)]
+    pub token_authority: Signer<'info>,
+
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, MintInterface>,
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, MintInterface>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == ai_dex_pool.load()?.token_mint_a)]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+    #[account(mut, constraint = token_owner_account_b.mint == ai_dex_pool.load()?.token_mint_b)]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    #[account(mut, constraint = token_vault_a.key() == ai_dex_pool.load()?.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+    #[account(mut, constraint = token_vault_b.key() == ai_dex_pool.load()?.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    /// CHECK: A fixed or dynamic tick array of the pool, checked in the handler
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: A fixed or dynamic tick array of the pool, checked in the handler
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    #[account(address = token_mint_a.to_account_info().owner.clone())]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = token_mint_b.to_account_info().owner.clone())]
+    pub token_program_b: Interface<'info, TokenInterface>,
+
+    pub memo_program: Program<'info, Memo>,
+    pub system_program: Program<'info, System>,
+
+    /// The config of the pool, checked against the protocol-wide emergency pause
+    #[account(
+        address = ai_dex_pool.load()?.ai_dex_config,
+        constraint = !ai_dex_config.paused @ ErrorCode::ProtocolPausedError
+    )]
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    /// The liquidity allowlist of the pool, required while it is enabled
+    #[account(seeds = [b"lp_allowlist", ai_dex_pool.key().as_ref()], bump)]
+    pub lp_allowlist: Option<Box<Account<'info, PoolAllowlist>>>,
+
+    /// The token account of the token authority holding a gating token of the liquidity allowlist, if any
+    pub lp_gating_token_account: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+}
+
+/// Deposits tokens into the full range position of an LP token, minting LP tokens in exchange.
+///
+/// The depositor adds the liquidity backing `lp_amount` LP tokens, and pays in its share of the
+/// fees and rewards the position has earned but not yet collected, so that the LP tokens already
+/// minted keep their value. When no LP tokens are minted, the fees and rewards left in the position
+/// are swept first, so that they do not go to the depositor.
+///
+/// The accounts of every initialized reward are passed in the `RewardAccounts` slice of the
+/// remaining accounts, in reward index order: the reward owner account, the reward mint, the
+/// reward vault and the reward token program.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts and programs required for the operation.
+/// * `lp_amount` - The amount of LP tokens to mint.
+/// * `token_max_a` - The maximum amount of token A that can be transferred.
+/// * `token_max_b` - The maximum amount of token B that can be transferred.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the operation is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::ZeroLpTokenAmountError` - If the amount of LP tokens is zero.
+/// * `ErrorCode::TokenLimitExceededError` - If the transfer amount exceeds the specified token limits.
+/// * `ErrorCode::AuthorityNotAllowlistedError` - If the liquidity allowlist of the pool is enabled and the depositor is not allowed.
+/// * `ErrorCode::InvalidRewardAccountsError` - If the reward accounts do not match the initialized rewards.
+pub fn deposit_lp_token_handler<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ModifyLpToken<'info>>,
+    lp_amount: u64,
+    token_max_a: u64,
+    token_max_b: u64,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    verify_pool_allowlist(
+        &*ctx.accounts.ai_dex_pool.load()?,
+        PoolAllowlistKind::Liquidity,
+        ctx.accounts.lp_allowlist.as_deref(),
+        ctx.accounts.token_authority.key,
+        ctx.accounts.lp_gating_token_account.as_deref(),
+    )?;
+
+    if lp_amount == 0 {
+        return Err(ErrorCode::ZeroLpTokenAmountError.into());
+    }
+
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::TransferHookReward,
+            AccountsType::RewardAccounts,
+        ],
+    )?;
+
+    let lp_supply = ctx.accounts.lp_mint.supply;
+    let liquidity_amount = get_lp_token_liquidity(lp_amount, lp_supply, ctx.accounts.position.liquidity, true)?;
+    if liquidity_amount == 0 {
+        return Err(ErrorCode::ZeroLiquidityError.into());
+    }
+    let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, true)?;
+
+    let ai_dex_pool_key = ctx.accounts.ai_dex_pool.key();
+    let tick_array_lower = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_lower, &ai_dex_pool_key)?;
+    let tick_array_upper = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_upper, &ai_dex_pool_key)?;
+
+    let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
+    let update = calculate_modify_liquidity(
+        &ai_dex,
+        &ctx.accounts.position,
+        &tick_array_lower,
+        &tick_array_upper,
+        liquidity_delta,
+        timestamp,
+    )?;
+
+    sync_modify_liquidity_values(
+        &mut ai_dex,
+        &mut ctx.accounts.position,
+        &tick_array_lower,
+        &tick_array_upper,
+        update,
+        timestamp,
+        &ctx.accounts.token_authority,
+        &ctx.accounts.system_program,
+    )?;
+
+    // Without LP tokens, nobody is entitled to the amounts left in the position
+    if lp_supply == 0 {
+        sweep_lp_token_amounts_owed(&mut ai_dex, &mut ctx.accounts.position)?;
+    }
+
+    let (delta_a, delta_b) = calculate_liquidity_token_deltas(
+        ai_dex.tick_current_index,
+        ai_dex.sqrt_price,
+        &ctx.accounts.position,
+        liquidity_delta,
+    )?;
+    ctx.accounts.position.record_deposit(delta_a, delta_b, ai_dex.sqrt_price, timestamp);
+    drop(ai_dex);
+
+    // The fees owed have been updated with the liquidity, so the share paid in is up to date
+    let fee_share_a = get_lp_token_amount_owed(lp_amount, lp_supply, ctx.accounts.position.fee_owed_a, true)?;
+    let fee_share_b = get_lp_token_amount_owed(lp_amount, lp_supply, ctx.accounts.position.fee_owed_b, true)?;
+    ctx.accounts.position.add_fees_owed(fee_share_a, fee_share_b)?;
+
+    let amount_a = delta_a
+        .checked_add(fee_share_a)
+        .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+    let amount_b = delta_b
+        .checked_add(fee_share_b)
+        .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+
+    let transfer_fee_included_amount_a = calculate_transfer_fee_included_amount(
+        &ctx.accounts.token_mint_a,
+        amount_a,
+    )?;
+    let transfer_fee_included_amount_b = calculate_transfer_fee_included_amount(
+        &ctx.accounts.token_mint_b,
+        amount_b,
+    )?;
+
+    // token_max_a and token_max_b should be applied to the transfer fee included amount
+    if transfer_fee_included_amount_a.amount > token_max_a {
+        return Err(ErrorCode::TokenLimitExceededError.into());
+    }
+    if transfer_fee_included_amount_b.amount > token_max_b {
+        return Err(ErrorCode::TokenLimitExceededError.into());
+    }
+
+    transfer_from_owner_to_vault(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.token_authority,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_owner_account_a,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_program_a,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_a,
+        transfer_fee_included_amount_a.amount,
+    )?;
+
+    transfer_from_owner_to_vault(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.token_authority,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.token_owner_account_b,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_program_b,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_b,
+        transfer_fee_included_amount_b.amount,
+    )?;
+
+    // Pay in the share of every initialized reward
+    let reward_infos = ctx.accounts.ai_dex_pool.load()?.reward_infos;
+    let initialized_rewards = reward_infos.iter().filter(|reward| reward.initialized()).count();
+    let reward_accounts = remaining_accounts_slice(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        AccountsType::RewardAccounts,
+    );
+    if reward_accounts.len() != initialized_rewards * ACCOUNTS_PER_REWARD {
+        return Err(ErrorCode::InvalidRewardAccountsError.into());
+    }
+
+    let mut reward_shares = [0u64; NUM_REWARDS];
+    let initialized_reward_infos = reward_infos
+        .iter()
+        .enumerate()
+        .filter(|(_, reward)| reward.initialized());
+    for ((index, reward_info), accounts) in
+        initialized_reward_infos.zip(reward_accounts.chunks(ACCOUNTS_PER_REWARD))
+    {
+        let reward_owner_account = InterfaceAccount::<TokenAccountInterface>::try_from(&accounts[0])?;
+        let reward_mint = InterfaceAccount::<MintInterface>::try_from(&accounts[1])?;
+        let reward_vault = InterfaceAccount::<TokenAccountInterface>::try_from(&accounts[2])?;
+        let reward_token_program = Interface::<TokenInterface>::try_from(&accounts[3])?;
+
+        if reward_mint.key() != reward_info.mint
+            || reward_vault.key() != reward_info.vault
+            || reward_owner_account.mint != reward_info.mint
+            || reward_token_program.key() != *reward_mint.to_account_info().owner
+        {
+            return Err(ErrorCode::InvalidRewardAccountsError.into());
+        }
+
+        let reward_owed = ctx.accounts.position.reward_infos[index].amount_owed;
+        let reward_share = get_lp_token_amount_owed(lp_amount, lp_supply, reward_owed, true)?;
+        let updated_reward_owed = reward_owed
+            .checked_add(reward_share)
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        ctx.accounts.position.update_reward_owed(index, updated_reward_owed);
+        ctx.accounts.ai_dex_pool.load_mut()?.record_reward_deposited(index, reward_share);
+        reward_shares[index] = reward_share;
+
+        let transfer_fee_included_reward_share = calculate_transfer_fee_included_amount(
+            &reward_mint,
+            reward_share,
+        )?;
+        transfer_from_owner_to_vault(
+            &ctx.accounts.ai_dex_pool,
+            &ctx.accounts.token_authority,
+            &reward_mint,
+            &reward_owner_account,
+            &reward_vault,
+            &reward_token_program,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_reward,
+            transfer_fee_included_reward_share.amount,
+        )?;
+    }
+
+    mint_lp_token(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.lp_mint,
+        &ctx.accounts.lp_token_account,
+        &ctx.accounts.token_program,
+        lp_amount,
+    )?;
+
+    emit_cpi!(LpTokenDepositedEvent {
+        token_authority: ctx.accounts.token_authority.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        lp_token: ctx.accounts.lp_token.key(),
+        lp_amount,
+        liquidity_amount,
+        delta_a,
+        delta_b,
+        fee_share_a,
+        fee_share_b,
+        reward_shares,
+        transfer_fee_included_amount_a: transfer_fee_included_amount_a.amount,
+        transfer_fee_included_amount_b: transfer_fee_included_amount_b.amount,
+        timestamp,
+    });
+
+    Ok(())
+}
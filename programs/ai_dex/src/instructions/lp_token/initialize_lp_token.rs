@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token};
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+#[event]
+pub struct LpTokenInitializedEvent {
+    pub funder: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub lp_token: Pubkey,
+    pub lp_mint: Pubkey,
+    pub position: Pubkey,
+    pub tick_lower_index: i32,
+    pub tick_upper_index: i32,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeLpToken<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        init,
+        payer = funder,
+        seeds = [b"lp_token", ai_dex_pool.key().as_ref()],
+        bump,
+        space = LpToken::LEN
+    )]
+    pub lp_token: Box<Account<'info, LpToken>>,
+
+    #[account(
+        init,
+        payer = funder,
+        seeds = [b"lp_mint", ai_dex_pool.key().as_ref()],
+        bump,
+        mint::authority = ai_dex_pool,
+        mint::decimals = LP_TOKEN_DECIMALS,
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    /// The full range position held on behalf of the LP token holders, whose position mint is the LP token account
+    #[account(
+        init,
+        payer = funder,
+        space = Position::LEN,
+        seeds = [b"position".as_ref(), lp_token.key().as_ref()],
+        bump,
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Initializes the fungible LP token of a full range only pool.
+///
+/// The LP token is minted by `deposit_lp_token` and burned by `withdraw_lp_token`, which add
+/// liquidity to and remove liquidity from a full range position of the pool held by the protocol.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the accounts required for the initialization.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the LP token is initialized, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::NotFullRangeOnlyPoolError` - If the pool is not a full range only pool.
+pub fn initialize_lp_token_handler(ctx: Context<InitializeLpToken>) -> Result<()> {
+    let (tick_lower_index, tick_upper_index) = {
+        let ai_dex = ctx.accounts.ai_dex_pool.load()?;
+        if !ai_dex.is_full_range_only() {
+            return Err(ErrorCode::NotFullRangeOnlyPoolError.into());
+        }
+        ai_dex.full_range_indexes()
+    };
+
+    ctx.accounts.position.open_position(
+        &ctx.accounts.ai_dex_pool,
+        ctx.accounts.lp_token.key(),
+        tick_lower_index,
+        tick_upper_index,
+    )?;
+    ctx.accounts.lp_token.initialize(
+        ctx.accounts.ai_dex_pool.key(),
+        ctx.accounts.lp_mint.key(),
+        ctx.accounts.position.key(),
+    );
+
+    emit_cpi!(LpTokenInitializedEvent {
+        funder: ctx.accounts.funder.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        lp_token: ctx.accounts.lp_token.key(),
+        lp_mint: ctx.accounts.lp_mint.key(),
+        position: ctx.accounts.position.key(),
+        tick_lower_index,
+        tick_upper_index,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,7 @@
+pub mod deposit_lp_token;
+pub mod initialize_lp_token;
+pub mod withdraw_lp_token;
+
+pub use deposit_lp_token::*;
+pub use initialize_lp_token::*;
+pub use withdraw_lp_token::*;
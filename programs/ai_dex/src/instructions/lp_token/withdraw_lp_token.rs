@@ -0,0 +1,263 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface};
+use std::convert::TryFrom;
+
+use crate::constants::transfer_memo;
+use crate::errors::ErrorCode;
+use crate::math::convert_to_liquidity_delta;
+use crate::orchestrator::liquidity_orchestrator::{
+    calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
+};
+use crate::state::*;
+use crate::util::{calculate_transfer_fee_excluded_amount, parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
+use crate::util::{
+    burn_lp_token, remaining_accounts_slice, to_timestamp_u64, transfer_from_vault_to_owner, TickArrayAccount,
+};
+
+use super::ModifyLpToken;
+
+// Accounts passed in the `RewardAccounts` slice for each initialized reward:
+// reward owner account, reward mint, reward vault and reward token program.
+const ACCOUNTS_PER_REWARD: usize = 4;
+
+#[event]
+pub struct LpTokenWithdrawnEvent {
+    pub token_authority: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub lp_token: Pubkey,
+    pub lp_amount: u64,
+    pub liquidity_amount: u128,
+    pub delta_a: u64,
+    pub delta_b: u64,
+    pub fee_share_a: u64,
+    pub fee_share_b: u64,
+    pub reward_shares: [u64; NUM_REWARDS],
+    pub transfer_fee_excluded_amount_a: u64,
+    pub transfer_fee_excluded_amount_b: u64,
+    pub timestamp: u64,
+}
+
+/// Burns LP tokens, withdrawing their share of the full range position of the LP token.
+///
+/// The holder receives the liquidity backing `lp_amount` LP tokens, along with its share of the
+/// fees and rewards the position has earned but not yet collected. The share of a reward is capped
+/// by the balance of the reward vault.
+///
+/// The accounts of every initialized reward are passed in the `RewardAccounts` slice of the
+/// remaining accounts, in reward index order: the reward owner account, the reward mint, the
+/// reward vault and the reward token program.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts and programs required for the operation.
+/// * `lp_amount` - The amount of LP tokens to burn.
+/// * `token_min_a` - The minimum amount of token A to receive.
+/// * `token_min_b` - The minimum amount of token B to receive.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the operation is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::ZeroLpTokenAmountError` - If the amount of LP tokens is zero.
+/// * `ErrorCode::ZeroLiquidityError` - If the LP tokens are not backed by any liquidity.
+/// * `ErrorCode::TokenAmountBelowMinimumError` - If the amount received is below the specified minimum.
+/// * `ErrorCode::InvalidRewardAccountsError` - If the reward accounts do not match the initialized rewards.
+pub fn withdraw_lp_token_handler<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ModifyLpToken<'info>>,
+    lp_amount: u64,
+    token_min_a: u64,
+    token_min_b: u64,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    if lp_amount == 0 {
+        return Err(ErrorCode::ZeroLpTokenAmountError.into());
+    }
+
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::TransferHookReward,
+            AccountsType::RewardAccounts,
+        ],
+    )?;
+
+    let lp_supply = ctx.accounts.lp_mint.supply;
+    let liquidity_amount = get_lp_token_liquidity(lp_amount, lp_supply, ctx.accounts.position.liquidity, false)?;
+    if liquidity_amount == 0 {
+        return Err(ErrorCode::ZeroLiquidityError.into());
+    }
+    let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, false)?;
+
+    let ai_dex_pool_key = ctx.accounts.ai_dex_pool.key();
+    let tick_array_lower = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_lower, &ai_dex_pool_key)?;
+    let tick_array_upper = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_upper, &ai_dex_pool_key)?;
+
+    let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
+    let update = calculate_modify_liquidity(
+        &ai_dex,
+        &ctx.accounts.position,
+        &tick_array_lower,
+        &tick_array_upper,
+        liquidity_delta,
+        timestamp,
+    )?;
+
+    sync_modify_liquidity_values(
+        &mut ai_dex,
+        &mut ctx.accounts.position,
+        &tick_array_lower,
+        &tick_array_upper,
+        update,
+        timestamp,
+        &ctx.accounts.token_authority,
+        &ctx.accounts.system_program,
+    )?;
+
+    let (delta_a, delta_b) = calculate_liquidity_token_deltas(
+        ai_dex.tick_current_index,
+        ai_dex.sqrt_price,
+        &ctx.accounts.position,
+        liquidity_delta,
+    )?;
+    ctx.accounts.position.record_withdrawal(delta_a, delta_b);
+    drop(ai_dex);
+
+    // The fees owed have been updated with the liquidity, so the share paid out is up to date
+    let fee_share_a = get_lp_token_amount_owed(lp_amount, lp_supply, ctx.accounts.position.fee_owed_a, false)?;
+    let fee_share_b = get_lp_token_amount_owed(lp_amount, lp_supply, ctx.accounts.position.fee_owed_b, false)?;
+    ctx.accounts.position.remove_fees_owed(fee_share_a, fee_share_b)?;
+
+    burn_lp_token(
+        &ctx.accounts.token_authority,
+        &ctx.accounts.lp_mint,
+        &ctx.accounts.lp_token_account,
+        &ctx.accounts.token_program,
+        lp_amount,
+    )?;
+
+    let amount_a = delta_a
+        .checked_add(fee_share_a)
+        .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+    let amount_b = delta_b
+        .checked_add(fee_share_b)
+        .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+
+    let transfer_fee_excluded_amount_a = calculate_transfer_fee_excluded_amount(
+        &ctx.accounts.token_mint_a,
+        amount_a,
+    )?;
+    let transfer_fee_excluded_amount_b = calculate_transfer_fee_excluded_amount(
+        &ctx.accounts.token_mint_b,
+        amount_b,
+    )?;
+
+    if transfer_fee_excluded_amount_a.amount < token_min_a {
+        return Err(ErrorCode::TokenAmountBelowMinimumError.into());
+    }
+    if transfer_fee_excluded_amount_b.amount < token_min_b {
+        return Err(ErrorCode::TokenAmountBelowMinimumError.into());
+    }
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_owner_account_a,
+        &ctx.accounts.token_program_a,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_a,
+        amount_a,
+        transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
+    )?;
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_owner_account_b,
+        &ctx.accounts.token_program_b,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_b,
+        amount_b,
+        transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
+    )?;
+
+    // Pay out the share of every initialized reward
+    let reward_infos = ctx.accounts.ai_dex_pool.load()?.reward_infos;
+    let initialized_rewards = reward_infos.iter().filter(|reward| reward.initialized()).count();
+    let reward_accounts = remaining_accounts_slice(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        AccountsType::RewardAccounts,
+    );
+    if reward_accounts.len() != initialized_rewards * ACCOUNTS_PER_REWARD {
+        return Err(ErrorCode::InvalidRewardAccountsError.into());
+    }
+
+    let mut reward_shares = [0u64; NUM_REWARDS];
+    let initialized_reward_infos = reward_infos
+        .iter()
+        .enumerate()
+        .filter(|(_, reward)| reward.initialized());
+    for ((index, reward_info), accounts) in
+        initialized_reward_infos.zip(reward_accounts.chunks(ACCOUNTS_PER_REWARD))
+    {
+        let reward_owner_account = InterfaceAccount::<TokenAccountInterface>::try_from(&accounts[0])?;
+        let reward_mint = InterfaceAccount::<MintInterface>::try_from(&accounts[1])?;
+        let reward_vault = InterfaceAccount::<TokenAccountInterface>::try_from(&accounts[2])?;
+        let reward_token_program = Interface::<TokenInterface>::try_from(&accounts[3])?;
+
+        if reward_mint.key() != reward_info.mint
+            || reward_vault.key() != reward_info.vault
+            || reward_owner_account.mint != reward_info.mint
+            || reward_token_program.key() != *reward_mint.to_account_info().owner
+        {
+            return Err(ErrorCode::InvalidRewardAccountsError.into());
+        }
+
+        let reward_owed = ctx.accounts.position.reward_infos[index].amount_owed;
+        let reward_share = get_lp_token_amount_owed(lp_amount, lp_supply, reward_owed, false)?
+            .min(reward_vault.amount);
+        ctx.accounts.position.update_reward_owed(index, reward_owed - reward_share);
+        ctx.accounts.ai_dex_pool.load_mut()?.record_reward_collected(index, reward_share);
+        reward_shares[index] = reward_share;
+
+        transfer_from_vault_to_owner(
+            &ctx.accounts.ai_dex_pool,
+            &reward_mint,
+            &reward_vault,
+            &reward_owner_account,
+            &reward_token_program,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_reward,
+            reward_share,
+            transfer_memo::TRANSFER_MEMO_COLLECT_REWARD.as_bytes(),
+        )?;
+    }
+
+    emit_cpi!(LpTokenWithdrawnEvent {
+        token_authority: ctx.accounts.token_authority.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        lp_token: ctx.accounts.lp_token.key(),
+        lp_amount,
+        liquidity_amount,
+        delta_a,
+        delta_b,
+        fee_share_a,
+        fee_share_b,
+        reward_shares,
+        transfer_fee_excluded_amount_a: transfer_fee_excluded_amount_a.amount,
+        transfer_fee_excluded_amount_b: transfer_fee_excluded_amount_b.amount,
+        timestamp,
+    });
+
+    Ok(())
+}
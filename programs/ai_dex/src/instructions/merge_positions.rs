@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::ErrorCode;
+use crate::orchestrator::liquidity_orchestrator::calculate_fee_and_reward_growths;
+use crate::state::*;
+use crate::util::{
+    burn_and_close_user_position_token, to_timestamp_u64, verify_position_authority,
+    TickArrayAccount,
+};
+
+#[event]
+pub struct PositionsMergedEvent {
+    pub position_authority: Pubkey,
+    pub rent_receiver: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub position: Pubkey,
+    pub source_position: Pubkey,
+    pub source_position_mint: Pubkey,
+    pub merged_liquidity: u128,
+    pub liquidity: u128,
+    pub timestamp: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MergePositions<'info> {
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    pub position_authority: Signer<'info>,
+
+    /// CHECK: safe, for receiving reclaimed rent only; may be any account (e.g. a treasury)
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+
+    /// The position that receives the liquidity and amounts owed of the source position
+    #[account(mut, has_one = ai_dex_pool)]
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The position that is merged into `position` and closed
+    #[account(mut,
+        has_one = ai_dex_pool,
+        close = rent_receiver,
+        seeds = [
+            b"position".as_ref(),
+            source_position_mint.key().as_ref()
+        ],
+        bump,
+        constraint = source_position.key() != position.key() @ ErrorCode::IncompatiblePositionsMergeError,
+    )]
+    pub source_position: Box<Account<'info, Position>>,
+
+    #[account(mut, address = source_position.position_mint)]
+    pub source_position_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut,
+        constraint = source_position_token_account.amount == 1,
+        constraint = source_position_token_account.mint == source_position.position_mint)]
+    pub source_position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: A fixed or dynamic tick array of the pool, checked in the handler
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: A fixed or dynamic tick array of the pool, checked in the handler
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(address = source_position_mint.to_account_info().owner.clone())]
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The config of the pool, checked against the protocol-wide emergency pause
+    #[account(
+        address = ai_dex_pool.load()?.ai_dex_config,
+        constraint = !ai_dex_config.paused @ ErrorCode::ProtocolPausedError
+    )]
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+}
+
+/// Merges a position into another one with the same tick range and closes it.
+///
+/// Both positions must be held by the position authority. Their fees and rewards are updated
+/// first, then the liquidity and the amounts owed of the source position are added to the
+/// destination position, and the source position and its NFT are closed. The liquidity of the
+/// pool and of the ticks is unchanged.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the merge.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the positions are merged, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::IncompatiblePositionsMergeError` - If the positions are the same or have different tick ranges.
+/// * Any error from verifying the position authority of either position.
+pub fn merge_positions_handler(ctx: Context<MergePositions>) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+    verify_position_authority(
+        &ctx.accounts.source_position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let ai_dex_pool_key = ctx.accounts.ai_dex_pool.key();
+    let tick_array_lower = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_lower, &ai_dex_pool_key)?;
+    let tick_array_upper = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_upper, &ai_dex_pool_key)?;
+
+    // Bring the fees and rewards of both positions up to date
+    let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
+    for position in [&mut ctx.accounts.position, &mut ctx.accounts.source_position] {
+        if position.liquidity == 0 {
+            continue;
+        }
+        let (position_update, reward_infos) = calculate_fee_and_reward_growths(
+            &ai_dex,
+            position,
+            &tick_array_lower,
+            &tick_array_upper,
+            timestamp,
+        )?;
+        ai_dex.update_rewards(reward_infos, timestamp);
+        position.update(&position_update);
+    }
+    drop(ai_dex);
+
+    let merged_liquidity = ctx.accounts.source_position.liquidity;
+    ctx.accounts.position.merge(&ctx.accounts.source_position)?;
+
+    burn_and_close_user_position_token(
+        &ctx.accounts.position_authority,
+        &ctx.accounts.rent_receiver,
+        &ctx.accounts.source_position_mint,
+        &ctx.accounts.source_position_token_account,
+        &ctx.accounts.token_program,
+    )?;
+
+    emit_cpi!(PositionsMergedEvent {
+        position_authority: ctx.accounts.position_authority.key(),
+        rent_receiver: ctx.accounts.rent_receiver.key(),
+        ai_dex_pool: ai_dex_pool_key,
+        position: ctx.accounts.position.key(),
+        source_position: ctx.accounts.source_position.key(),
+        source_position_mint: ctx.accounts.source_position_mint.key(),
+        merged_liquidity,
+        liquidity: ctx.accounts.position.liquidity,
+        timestamp,
+    });
+
+    Ok(())
+}
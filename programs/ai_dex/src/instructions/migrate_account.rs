@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use std::convert::TryInto;
+
+use crate::state::{AiDexConfig, FeeTier, Position, PositionTradeBatch};
+use crate::util::migrate_program_account;
+
+#[event]
+pub struct AccountMigratedEvent {
+    pub account: Pubkey,
+    pub funder: Pubkey,
+    pub old_version: u8,
+    pub new_version: u8,
+    pub old_len: u64,
+    pub new_len: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// CHECK: A position, position trade batch, fee tier or config of any past layout, which may
+    /// be too short to deserialize. Its owner is checked here and its discriminator in the handler
+    #[account(mut, owner = crate::ID)]
+    pub account: UncheckedAccount<'info>,
+
+    /// Pays the rent of the bytes added to the account.
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Upgrades a versioned account to its current layout.
+///
+/// The account is grown to the current size of its type, the added bytes being zeroed, and its
+/// version is raised. Positions, position trade batches, fee tiers and configs are supported,
+/// pools being migrated by `migrate_pool`. The instruction is permissionless and migrating an
+/// account twice does nothing.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the migration.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the account is successfully migrated,
+/// or an `Err` if an error occurs.
+///
+/// # Errors
+///
+/// * `AccountDiscriminatorMismatch` - If the account is not of a supported type.
+pub fn migrate_account_handler(ctx: Context<MigrateAccount>) -> Result<()> {
+    let account = ctx.accounts.account.to_account_info();
+    let discriminator: [u8; 8] = account
+        .try_borrow_data()?
+        .get(..8)
+        .and_then(|discriminator| discriminator.try_into().ok())
+        .ok_or(anchor_lang::error::ErrorCode::AccountDiscriminatorNotFound)?;
+
+    let funder = &ctx.accounts.funder;
+    let system_program = &ctx.accounts.system_program;
+    let old_len = account.data_len() as u64;
+    let (old_version, new_version) = if discriminator == Position::DISCRIMINATOR {
+        migrate_program_account::<Position>(funder, &account, system_program)?
+    } else if discriminator == PositionTradeBatch::DISCRIMINATOR {
+        migrate_program_account::<PositionTradeBatch>(funder, &account, system_program)?
+    } else if discriminator == FeeTier::DISCRIMINATOR {
+        migrate_program_account::<FeeTier>(funder, &account, system_program)?
+    } else if discriminator == AiDexConfig::DISCRIMINATOR {
+        migrate_program_account::<AiDexConfig>(funder, &account, system_program)?
+    } else {
+        return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+    };
+
+    emit_cpi!(AccountMigratedEvent {
+        account: account.key(),
+        funder: funder.key(),
+        old_version,
+        new_version,
+        old_len,
+        new_len: account.data_len() as u64,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+use crate::state::AiDexPool;
+use crate::util::realloc_program_account;
+
+#[event]
+pub struct PoolMigratedEvent {
+    pub ai_dex_pool: Pubkey,
+    pub funder: Pubkey,
+    pub old_version: u8,
+    pub new_version: u8,
+    pub old_len: u64,
+    pub new_len: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigratePool<'info> {
+    /// An AI DEX pool of any past layout, which may be too short to load until it is grown.
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    /// Pays the rent of the bytes added to the pool.
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Upgrades an AI DEX pool to the current layout.
+///
+/// The account is grown to the current size of a pool, the added bytes being zeroed, and its
+/// version is raised. No bytes are moved: fields are only ever appended to the layout of a pool,
+/// so the fields of past versions keep their offsets and the appended ones read as zero. The
+/// features they configure stay disabled until set. The instruction is permissionless and migrating
+/// a pool twice does nothing.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the migration.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the pool is successfully migrated,
+/// or an `Err` if an error occurs.
+///
+/// # Errors
+///
+/// * `AccountDiscriminatorMismatch` - If the account is not an AI DEX pool.
+pub fn migrate_pool_handler(ctx: Context<MigratePool>) -> Result<()> {
+    let ai_dex_pool = ctx.accounts.ai_dex_pool.to_account_info();
+    let old_len = ai_dex_pool.data_len() as u64;
+    realloc_program_account(
+        &ctx.accounts.funder,
+        &ai_dex_pool,
+        &ctx.accounts.system_program,
+        AiDexPool::LEN,
+    )?;
+
+    let old_version = ctx.accounts.ai_dex_pool.load_mut()?.migrate();
+    let new_version = ctx.accounts.ai_dex_pool.load()?.version;
+
+    emit_cpi!(PoolMigratedEvent {
+        ai_dex_pool: ai_dex_pool.key(),
+        funder: ctx.accounts.funder.key(),
+        old_version,
+        new_version,
+        old_len,
+        new_len: ai_dex_pool.data_len() as u64,
+    });
+
+    Ok(())
+}
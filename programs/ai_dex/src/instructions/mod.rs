@@ -1,22 +1,92 @@
 pub mod close_position;
+pub mod close_position_with_collect;
+pub mod compound_position_fees;
 pub mod decrease_liquidity;
+pub mod emergency_withdraw;
+pub mod enable_pool_emergency_mode;
+pub mod flash_swap;
+pub mod get_seconds_per_liquidity_inside;
+pub mod get_twap;
 pub mod increase_liquidity;
+pub mod initialize_dynamic_tick_array;
+pub mod initialize_oracle;
+pub mod initialize_pool_stats;
 pub mod initialize_pool;
+pub mod initialize_position_collection;
 pub mod initialize_tick_array;
+pub mod initialize_tick_arrays;
+pub mod lock_position;
+pub mod lock_position_permanently;
+pub mod merge_positions;
+pub mod migrate_account;
+pub mod migrate_pool;
+pub mod multi_hop_swap;
 pub mod open_position;
+pub mod open_position_token22;
+pub mod open_position_with_liquidity;
 pub mod open_position_with_metadata;
+pub mod quote_swap;
+pub mod rebalance_position;
+pub mod repay_flash_swap;
+pub mod set_anti_sandwich;
+pub mod set_circuit_breaker;
+pub mod set_max_price_impact;
+pub mod set_max_slot_volume;
+pub mod set_pool_open_time;
+pub mod set_pool_pause;
+pub mod set_position_label;
+pub mod set_position_operator;
+pub mod set_protocol_pause;
 pub mod swap;
+pub mod sync_native_vault;
 pub mod two_hop_swap;
+pub mod zap_in;
+pub mod zap_out;
 
 pub use close_position::*;
+pub use close_position_with_collect::*;
+pub use compound_position_fees::*;
 pub use decrease_liquidity::*;
+pub use emergency_withdraw::*;
+pub use enable_pool_emergency_mode::*;
+pub use flash_swap::*;
+pub use get_seconds_per_liquidity_inside::*;
+pub use get_twap::*;
 pub use increase_liquidity::*;
+pub use initialize_dynamic_tick_array::*;
+pub use initialize_oracle::*;
+pub use initialize_pool_stats::*;
 pub use initialize_pool::*;
+pub use initialize_position_collection::*;
 pub use initialize_tick_array::*;
+pub use initialize_tick_arrays::*;
+pub use lock_position::*;
+pub use lock_position_permanently::*;
+pub use merge_positions::*;
+pub use migrate_account::*;
+pub use migrate_pool::*;
+pub use multi_hop_swap::*;
 pub use open_position::*;
+pub use open_position_token22::*;
+pub use open_position_with_liquidity::*;
 pub use open_position_with_metadata::*;
+pub use quote_swap::*;
+pub use rebalance_position::*;
+pub use repay_flash_swap::*;
+pub use set_anti_sandwich::*;
+pub use set_circuit_breaker::*;
+pub use set_max_price_impact::*;
+pub use set_max_slot_volume::*;
+pub use set_pool_open_time::*;
+pub use set_pool_pause::*;
+pub use set_position_label::*;
+pub use set_position_operator::*;
+pub use set_protocol_pause::*;
 pub use swap::*;
+pub use sync_native_vault::*;
 pub use two_hop_swap::*;
+pub use zap_in::*;
+pub use zap_out::*;
 
 pub mod trade_batch;
 pub use trade_batch::*;
@@ -26,3 +96,39 @@ pub use fees_rewards::*;
 
 pub mod wrapper;
 pub use wrapper::*;
+
+pub mod allowlist;
+pub use allowlist::*;
+
+pub mod agent;
+pub use agent::*;
+
+pub mod limit_order;
+pub use limit_order::*;
+
+pub mod range_order;
+pub use range_order::*;
+
+pub mod trigger_order;
+pub use trigger_order::*;
+
+pub mod dca;
+pub use dca::*;
+
+pub mod twap_order;
+pub use twap_order::*;
+
+pub mod lp_token;
+pub use lp_token::*;
+
+pub mod vault;
+pub use vault::*;
+
+pub mod swap_hook;
+pub use swap_hook::*;
+
+pub mod swap_permit;
+pub use swap_permit::*;
+
+pub mod session;
+pub use session::*;
@@ -0,0 +1,494 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::memo::Memo;
+use std::convert::TryFrom;
+
+use crate::orchestrator::swap_orchestrator::PostSwapUpdate;
+use crate::swap_with_transfer_fee_extension;
+use crate::util::{
+    calculate_transfer_fee_excluded_amount, oracle_volatility_ticks, record_oracle_observation,
+    token_account_amount, transfer_from_owner_to_vault, transfer_from_vault_to_owner,
+    verify_pool_allowlist,
+};
+use crate::{
+    errors::ErrorCode,
+    state::{AiDexConfig, AiDexPool, PoolAllowlistKind},
+    util::{check_deadline, to_timestamp_u64, SwapTickSequence, TickArrayAccount},
+    constants::transfer_memo,
+};
+
+/// The maximum number of pools in a multi-hop route.
+pub const MAX_MULTI_HOP_ROUTE_LENGTH: usize = 4;
+
+/// The number of remaining accounts supplied for each hop of the route.
+pub const MULTI_HOP_ACCOUNTS_PER_HOP: usize = 11;
+
+/// Describes a single hop of a multi-hop route.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct MultiHopSwapHop {
+    pub a_to_b: bool,
+    pub sqrt_price_limit: u128,
+}
+
+#[event]
+pub struct MultiHopSwapEvent {
+    pub token_authority: Pubkey,
+    pub ai_dex_pools: Vec<Pubkey>,
+    pub route: Vec<MultiHopSwapHop>,
+    pub amount: u64,
+    pub other_amount_threshold: u64,
+    pub amount_specified_is_input: bool,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub token_mint_input: Pubkey,
+    pub token_mint_output: Pubkey,
+    pub token_owner_account_input: Pubkey,
+    pub token_owner_account_output: Pubkey,
+    pub timestamp: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MultiHopSwap<'info> {
+    /// The authority that signs the transaction.
+    pub token_authority: Signer<'info>,
+
+    /// The token account of the owner for the input token.
+    #[account(mut)]
+    pub token_owner_account_input: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token account of the owner for the output token.
+    #[account(mut)]
+    pub token_owner_account_output: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The memo program.
+    pub memo_program: Program<'info, Memo>,
+
+    /// The config of the pools of the route, checked against the protocol-wide emergency pause
+    #[account(constraint = !ai_dex_config.paused @ ErrorCode::ProtocolPausedError)]
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    // Remaining accounts, MULTI_HOP_ACCOUNTS_PER_HOP for each hop in route order:
+    // - ai_dex_pool (mut)
+    // - token_mint_a
+    // - token_mint_b
+    // - token_vault_a (mut)
+    // - token_vault_b (mut)
+    // - token_program_a
+    // - token_program_b
+    // - tick_array_0 (mut), fixed or dynamic
+    // - tick_array_1 (mut), fixed or dynamic
+    // - tick_array_2 (mut), fixed or dynamic
+    // - oracle (mut)
+}
+
+/// The accounts of a single hop, validated against the pool they belong to.
+struct MultiHopSwapHopAccounts<'info> {
+    ai_dex_pool: AccountLoader<'info, AiDexPool>,
+    token_mint_a: Box<InterfaceAccount<'info, Mint>>,
+    token_mint_b: Box<InterfaceAccount<'info, Mint>>,
+    token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    token_program_a: Interface<'info, TokenInterface>,
+    token_program_b: Interface<'info, TokenInterface>,
+    tick_arrays: Vec<TickArrayAccount<'info>>,
+    oracle: UncheckedAccount<'info>,
+}
+
+impl<'info> MultiHopSwapHopAccounts<'info> {
+    fn try_from_accounts(accounts: &'info [AccountInfo<'info>]) -> Result<Self> {
+        let ai_dex_pool = AccountLoader::<AiDexPool>::try_from(&accounts[0])?;
+        if !accounts[0].is_writable {
+            return Err(ErrorCode::InvalidRouteAccountError.into());
+        }
+
+        let token_mint_a = Box::new(InterfaceAccount::<Mint>::try_from(&accounts[1])?);
+        let token_mint_b = Box::new(InterfaceAccount::<Mint>::try_from(&accounts[2])?);
+        let token_vault_a = Box::new(InterfaceAccount::<TokenAccount>::try_from(&accounts[3])?);
+        let token_vault_b = Box::new(InterfaceAccount::<TokenAccount>::try_from(&accounts[4])?);
+        let token_program_a = Interface::<TokenInterface>::try_from(&accounts[5])?;
+        let token_program_b = Interface::<TokenInterface>::try_from(&accounts[6])?;
+
+        let ai_dex = ai_dex_pool.load()?;
+        if token_mint_a.key() != ai_dex.token_mint_a
+            || token_mint_b.key() != ai_dex.token_mint_b
+            || token_vault_a.key() != ai_dex.token_vault_a
+            || token_vault_b.key() != ai_dex.token_vault_b
+            || token_program_a.key() != *token_mint_a.to_account_info().owner
+            || token_program_b.key() != *token_mint_b.to_account_info().owner
+        {
+            return Err(ErrorCode::InvalidRouteAccountError.into());
+        }
+        drop(ai_dex);
+
+        let mut tick_arrays = Vec::with_capacity(3);
+        for tick_array_info in &accounts[7..10] {
+            let tick_array = TickArrayAccount::try_from(tick_array_info)?;
+            if tick_array.ai_dex_pool()? != ai_dex_pool.key() {
+                return Err(ErrorCode::InvalidRouteAccountError.into());
+            }
+            tick_arrays.push(tick_array);
+        }
+
+        let (oracle_key, _) = Pubkey::find_program_address(
+            &[b"oracle", ai_dex_pool.key().as_ref()],
+            &crate::ID,
+        );
+        if accounts[10].key() != oracle_key {
+            return Err(ErrorCode::InvalidRouteAccountError.into());
+        }
+        let oracle = UncheckedAccount::try_from(&accounts[10]);
+
+        Ok(Self {
+            ai_dex_pool,
+            token_mint_a,
+            token_mint_b,
+            token_vault_a,
+            token_vault_b,
+            token_program_a,
+            token_program_b,
+            tick_arrays,
+            oracle,
+        })
+    }
+
+    fn input_token_mint(&self, a_to_b: bool) -> &InterfaceAccount<'info, Mint> {
+        if a_to_b { &self.token_mint_a } else { &self.token_mint_b }
+    }
+
+    fn output_token_mint(&self, a_to_b: bool) -> &InterfaceAccount<'info, Mint> {
+        if a_to_b { &self.token_mint_b } else { &self.token_mint_a }
+    }
+
+    fn input_token_vault(&self, a_to_b: bool) -> &InterfaceAccount<'info, TokenAccount> {
+        if a_to_b { &self.token_vault_a } else { &self.token_vault_b }
+    }
+
+    fn output_token_vault(&self, a_to_b: bool) -> &InterfaceAccount<'info, TokenAccount> {
+        if a_to_b { &self.token_vault_b } else { &self.token_vault_a }
+    }
+
+    fn input_token_program(&self, a_to_b: bool) -> &Interface<'info, TokenInterface> {
+        if a_to_b { &self.token_program_a } else { &self.token_program_b }
+    }
+
+    fn output_token_program(&self, a_to_b: bool) -> &Interface<'info, TokenInterface> {
+        if a_to_b { &self.token_program_b } else { &self.token_program_a }
+    }
+}
+
+/// Returns the (input, output) amounts of a swap update given its direction.
+fn swap_update_amounts(swap_update: &PostSwapUpdate, a_to_b: bool) -> (u64, u64) {
+    if a_to_b {
+        (swap_update.amount_a, swap_update.amount_b)
+    } else {
+        (swap_update.amount_b, swap_update.amount_a)
+    }
+}
+
+/// Checks a pool of the route: it must belong to the config checked for the emergency pause, and
+/// must not have a swap hook, which multi-hop swaps do not call.
+fn verify_route_pool(ai_dex: &AiDexPool, ai_dex_config: &Pubkey) -> Result<()> {
+    if ai_dex.ai_dex_config != *ai_dex_config {
+        return Err(ErrorCode::InvalidRouteAccountError.into());
+    }
+    ai_dex.check_swap_hook_not_set()
+}
+
+/// Handles a swap routed through up to `MAX_MULTI_HOP_ROUTE_LENGTH` pools.
+///
+/// The output token of each hop is the input token of the next one, and intermediate tokens are
+/// transferred directly from vault to vault. Supports both exact-in and exact-out routes; for exact-out,
+/// the amounts are calculated from the last hop to the first, while the transfers always occur from the
+/// first hop to the last. Mints with a transfer hook are not supported in multi-hop routes.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the fixed accounts; the accounts of each hop are passed as remaining accounts.
+/// * `amount` - The amount to be swapped.
+/// * `other_amount_threshold` - The minimum or maximum amount threshold for the swap.
+/// * `amount_specified_is_input` - A boolean indicating if the specified amount is the input amount.
+/// * `route` - The direction and square root price limit of each hop.
+/// * `deadline_timestamp` - Optional unix timestamp after which the transaction is rejected.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the swap is successful, or an `Err` if an error occurs.
+///
+/// # Errors
+///
+/// This function can return errors in the following cases:
+/// * Transaction expired error if the deadline has passed.
+/// * Invalid route length error if the route is empty or longer than `MAX_MULTI_HOP_ROUTE_LENGTH`.
+/// * Invalid route account error if the remaining accounts do not match the route, or a pool does not
+///   belong to the config.
+/// * Duplicate route pool error if the same pool is used twice.
+/// * Swap hook not supported error if a pool of the route has a swap hook.
+/// * Authority not allowlisted error if a pool of the route restricts swapping to its allowlist.
+/// * Invalid intermediary mint error if consecutive hops do not share a token.
+/// * Amount mismatch error if the output of a hop does not match the input of the next hop.
+/// * Amount out below minimum error if the output amount is less than the specified threshold.
+/// * Amount in above maximum error if the input amount is more than the specified threshold.
+pub fn multi_hop_swap_handler<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, MultiHopSwap<'info>>,
+    amount: u64,
+    other_amount_threshold: u64,
+    amount_specified_is_input: bool,
+    route: Vec<MultiHopSwapHop>,
+    deadline_timestamp: Option<i64>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    check_deadline(deadline_timestamp, clock.unix_timestamp)?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    if route.is_empty() || route.len() > MAX_MULTI_HOP_ROUTE_LENGTH {
+        return Err(ErrorCode::InvalidRouteLengthError.into());
+    }
+    if ctx.remaining_accounts.len() != route.len() * MULTI_HOP_ACCOUNTS_PER_HOP {
+        return Err(ErrorCode::InvalidRouteAccountError.into());
+    }
+
+    let mut hops = Vec::with_capacity(route.len());
+    for hop_accounts in ctx.remaining_accounts.chunks(MULTI_HOP_ACCOUNTS_PER_HOP) {
+        let hop = MultiHopSwapHopAccounts::try_from_accounts(hop_accounts)?;
+        verify_route_pool(&*hop.ai_dex_pool.load()?, &ctx.accounts.ai_dex_config.key())?;
+        hops.push(hop);
+    }
+
+    for (i, hop) in hops.iter().enumerate() {
+        // Don't allow swaps on the same ai_dex twice
+        if hops[..i].iter().any(|prev| prev.ai_dex_pool.key() == hop.ai_dex_pool.key()) {
+            return Err(ErrorCode::DuplicateRoutePoolError.into());
+        }
+        if i > 0 {
+            let prev_output_mint = hops[i - 1].output_token_mint(route[i - 1].a_to_b).key();
+            if prev_output_mint != hop.input_token_mint(route[i].a_to_b).key() {
+                return Err(ErrorCode::InvalidIntermediaryMintError.into());
+            }
+        }
+    }
+
+    let first = &hops[0];
+    let last = &hops[hops.len() - 1];
+    let token_mint_input = first.input_token_mint(route[0].a_to_b);
+    let token_mint_output = last.output_token_mint(route[route.len() - 1].a_to_b);
+    let token_mint_input_key = token_mint_input.key();
+    let token_mint_output_key = token_mint_output.key();
+    if ctx.accounts.token_owner_account_input.mint != token_mint_input_key
+        || ctx.accounts.token_owner_account_output.mint != token_mint_output_key
+    {
+        return Err(ErrorCode::InvalidRouteAccountError.into());
+    }
+
+    // Pools restricting swaps to an allowlist are only reachable through `swap` and `two_hop_swap`,
+    // which take the allowlist accounts
+    for hop in hops.iter() {
+        verify_pool_allowlist(
+            &*hop.ai_dex_pool.load()?,
+            PoolAllowlistKind::Swap,
+            None,
+            ctx.accounts.token_authority.key,
+            None,
+        )?;
+    }
+
+    let mut swap_tick_sequences = Vec::with_capacity(hops.len());
+    for hop in hops.iter() {
+        swap_tick_sequences.push(SwapTickSequence::new_with_tick_arrays(
+            hop.tick_arrays[0].load_mut()?,
+            hop.tick_arrays[1].load_mut().ok(),
+            hop.tick_arrays[2].load_mut().ok(),
+        ));
+    }
+
+    let mut swap_updates: Vec<Option<PostSwapUpdate>> = hops.iter().map(|_| None).collect();
+    if amount_specified_is_input {
+        // Exact-in: calculations occur from the first hop to the last hop,
+        // the output of each hop is the input of the next one.
+        let mut hop_amount = amount;
+        for (i, hop) in hops.iter().enumerate() {
+            let volatility_ticks = oracle_volatility_ticks(&hop.oracle, &*hop.ai_dex_pool.load()?, timestamp)?;
+            let swap_update = swap_with_transfer_fee_extension(
+                &*hop.ai_dex_pool.load()?,
+                &hop.token_mint_a,
+                &hop.token_mint_b,
+                &mut swap_tick_sequences[i],
+                hop_amount,
+                route[i].sqrt_price_limit,
+                true,
+                route[i].a_to_b,
+                timestamp,
+                0,
+                0,
+                volatility_ticks,
+            )?;
+            // We use vault to vault transfer, so transfer fee will be collected once.
+            hop_amount = swap_update_amounts(&swap_update, route[i].a_to_b).1;
+            swap_updates[i] = Some(swap_update);
+        }
+    } else {
+        // Exact-out: calculations occur from the last hop to the first hop,
+        // but the actual swaps occur from the first hop to the last hop.
+        let mut hop_amount = amount;
+        for (i, hop) in hops.iter().enumerate().rev() {
+            let volatility_ticks = oracle_volatility_ticks(&hop.oracle, &*hop.ai_dex_pool.load()?, timestamp)?;
+            let swap_update = swap_with_transfer_fee_extension(
+                &*hop.ai_dex_pool.load()?,
+                &hop.token_mint_a,
+                &hop.token_mint_b,
+                &mut swap_tick_sequences[i],
+                hop_amount,
+                route[i].sqrt_price_limit,
+                false,
+                route[i].a_to_b,
+                timestamp,
+                0,
+                0,
+                volatility_ticks,
+            )?;
+            hop_amount = calculate_transfer_fee_excluded_amount(
+                hop.input_token_mint(route[i].a_to_b),
+                swap_update_amounts(&swap_update, route[i].a_to_b).0,
+            )?.amount;
+            swap_updates[i] = Some(swap_update);
+        }
+    }
+    drop(swap_tick_sequences);
+    let swap_updates: Vec<PostSwapUpdate> = swap_updates.into_iter().flatten().collect();
+
+    // All output token of a hop should be consumed by the next hop
+    for i in 1..hops.len() {
+        let prev_output = swap_update_amounts(&swap_updates[i - 1], route[i - 1].a_to_b).1;
+        let input = swap_update_amounts(&swap_updates[i], route[i].a_to_b).0;
+        if prev_output != input {
+            return Err(ErrorCode::AmountMismatchError.into());
+        }
+    }
+
+    let amount_in = swap_update_amounts(&swap_updates[0], route[0].a_to_b).0;
+    let amount_out = swap_update_amounts(&swap_updates[hops.len() - 1], route[route.len() - 1].a_to_b).1;
+
+    if amount_specified_is_input {
+        let output_amount = calculate_transfer_fee_excluded_amount(token_mint_output, amount_out)?.amount;
+        if output_amount < other_amount_threshold {
+            return Err(ErrorCode::AmountOutBelowMinimumError.into());
+        }
+    } else if amount_in > other_amount_threshold {
+        return Err(ErrorCode::AmountInAboveMaximumError.into());
+    }
+
+    transfer_from_owner_to_vault(
+        &first.ai_dex_pool,
+        &ctx.accounts.token_authority,
+        token_mint_input,
+        &ctx.accounts.token_owner_account_input,
+        first.input_token_vault(route[0].a_to_b),
+        first.input_token_program(route[0].a_to_b),
+        &ctx.accounts.memo_program,
+        &None,
+        amount_in,
+    )?;
+
+    for i in 0..hops.len() {
+        let a_to_b = route[i].a_to_b;
+        let swap_update = &swap_updates[i];
+        let (_, output_amount) = swap_update_amounts(swap_update, a_to_b);
+
+        record_oracle_observation(&hops[i].oracle, &*hops[i].ai_dex_pool.load()?, timestamp)?;
+
+        let mut ai_dex_pool = hops[i].ai_dex_pool.load_mut()?;
+        ai_dex_pool.record_slot_volume(clock.slot, output_amount, hops[i].output_token_vault(a_to_b).amount, a_to_b)?;
+        ai_dex_pool.record_swap_authority(clock.slot, ctx.accounts.token_authority.key(), a_to_b)?;
+        ai_dex_pool.record_window_volume(swap_update.amount_a, timestamp);
+        ai_dex_pool.update_after_swap(
+            swap_update.next_liquidity,
+            swap_update.next_tick_index,
+            swap_update.next_sqrt_price,
+            swap_update.next_fee_growth_global,
+            swap_update.next_reward_infos,
+            swap_update.next_protocol_fee,
+            a_to_b,
+            timestamp,
+        );
+        drop(ai_dex_pool);
+
+        // Transfer from pool to the next pool, or to the owner for the last hop
+        let destination: &InterfaceAccount<'info, TokenAccount> = if i + 1 < hops.len() {
+            hops[i + 1].input_token_vault(route[i + 1].a_to_b)
+        } else {
+            &ctx.accounts.token_owner_account_output
+        };
+
+        let hop = &hops[i];
+        let destination_amount = token_account_amount(destination)?;
+        transfer_from_vault_to_owner(
+            &hop.ai_dex_pool,
+            hop.output_token_mint(a_to_b),
+            hop.output_token_vault(a_to_b),
+            destination,
+            hop.output_token_program(a_to_b),
+            &ctx.accounts.memo_program,
+            &None,
+            output_amount,
+            transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
+        )?;
+
+        // The next pool accounts for what its vault received
+        if i + 1 < hops.len() {
+            hops[i + 1].ai_dex_pool.load_mut()?.track_vault_balance(
+                &destination.key(),
+                destination_amount,
+                token_account_amount(destination)?,
+            );
+        }
+    }
+
+    emit_cpi!(MultiHopSwapEvent {
+        token_authority: ctx.accounts.token_authority.key(),
+        ai_dex_pools: hops.iter().map(|hop| hop.ai_dex_pool.key()).collect(),
+        route,
+        amount,
+        other_amount_threshold,
+        amount_specified_is_input,
+        amount_in,
+        amount_out,
+        token_mint_input: token_mint_input_key,
+        token_mint_output: token_mint_output_key,
+        token_owner_account_input: ctx.accounts.token_owner_account_input.key(),
+        token_owner_account_output: ctx.accounts.token_owner_account_output.key(),
+        timestamp,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_multi_hop_swap_rejects_pool_of_other_config() {
+        let ai_dex_config = Pubkey::new_unique();
+        let ai_dex = AiDexPool {
+            ai_dex_config,
+            ..Default::default()
+        };
+
+        assert!(verify_route_pool(&ai_dex, &ai_dex_config).is_ok());
+        let result = verify_route_pool(&ai_dex, &Pubkey::new_unique());
+        assert_eq!(result.unwrap_err(), ErrorCode::InvalidRouteAccountError.into());
+    }
+
+    #[test]
+    fn test_multi_hop_swap_rejects_pool_with_swap_hook() {
+        let ai_dex_config = Pubkey::new_unique();
+        let mut ai_dex = AiDexPool {
+            ai_dex_config,
+            ..Default::default()
+        };
+        ai_dex.set_swap_hook_program(Pubkey::new_unique());
+
+        let result = verify_route_pool(&ai_dex, &ai_dex_config);
+        assert_eq!(result.unwrap_err(), ErrorCode::SwapHookNotSupportedError.into());
+    }
+}
@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::TokenAccount as TokenAccountInterface;
 
-use crate::{state::*, util::mint_position_token_and_remove_authority};
+use crate::errors::ErrorCode;
+use crate::{state::*, util::{mint_position_token_and_remove_authority, verify_pool_allowlist}};
 use crate::state;
 
 #[event]
@@ -18,6 +20,7 @@ pub struct PositionOpenedEvent {
     pub token_program: Pubkey,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct OpenPosition<'info> {
     #[account(mut)]
@@ -51,13 +54,27 @@ pub struct OpenPosition<'info> {
     )]
     pub position_token_account: Box<Account<'info, TokenAccount>>,
 
-    pub ai_dex_pool: Box<Account<'info, AiDexPool>>,
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
 
     #[account(address = token::ID)]
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
     pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The config of the pool, checked against the protocol-wide emergency pause
+    #[account(
+        address = ai_dex_pool.load()?.ai_dex_config,
+        constraint = !ai_dex_config.paused @ ErrorCode::ProtocolPausedError
+    )]
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    /// The liquidity allowlist of the pool, required while it is enabled
+    #[account(seeds = [b"lp_allowlist", ai_dex_pool.key().as_ref()], bump)]
+    pub lp_allowlist: Option<Box<Account<'info, PoolAllowlist>>>,
+
+    /// The token account of the owner holding a gating token of the liquidity allowlist, if any
+    pub lp_gating_token_account: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
 }
 
 /// Opens a position in the AI DEX.
@@ -84,6 +101,14 @@ pub fn open_position_handler(
     tick_lower_index: i32,
     tick_upper_index: i32,
 ) -> Result<()> {
+    verify_pool_allowlist(
+        &*ctx.accounts.ai_dex_pool.load()?,
+        PoolAllowlistKind::Liquidity,
+        ctx.accounts.lp_allowlist.as_deref(),
+        ctx.accounts.owner.key,
+        ctx.accounts.lp_gating_token_account.as_deref(),
+    )?;
+
     let ai_dex = &ctx.accounts.ai_dex_pool;
     let position_mint = &ctx.accounts.position_mint;
     let position = &mut ctx.accounts.position;
@@ -104,7 +129,7 @@ pub fn open_position_handler(
         &ctx.accounts.token_program,
     )?;
 
-    emit!(PositionOpenedEvent {
+    emit_cpi!(PositionOpenedEvent {
         funder: ctx.accounts.funder.key(),
         ai_dex_pool: ai_dex.key(),
         position: position.key(),
@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::TokenAccount as TokenAccountInterface;
+
+use crate::errors::ErrorCode;
+use crate::constants::nft::ai_dex_nft_update_auth::ID as AD_NFT_UPDATE_AUTH;
+use crate::{
+    state::*,
+    util::{mint_position_token_2022_with_metadata_and_remove_authority, verify_pool_allowlist},
+};
+
+#[event]
+pub struct PositionToken22OpenedEvent {
+    pub funder: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub position: Pubkey,
+    pub position_mint: Pubkey,
+    pub position_token_account: Pubkey,
+    pub owner: Pubkey,
+    pub tick_lower_index: i32,
+    pub tick_upper_index: i32,
+    pub token_program: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct OpenPositionToken22<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// CHECK: safe, the account that will be the owner of the position can be arbitrary
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = funder,
+        space = Position::LEN,
+        seeds = [b"position".as_ref(), position_mint.key().as_ref()],
+        bump,
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    /// The Token-2022 position mint, created in the handler with its metadata extensions
+    #[account(mut)]
+    pub position_mint: Signer<'info>,
+
+    /// CHECK: the associated token account of the owner, created and checked via the associated token program CPI
+    #[account(mut)]
+    pub position_token_account: UncheckedAccount<'info>,
+
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// CHECK: checked via account constraints
+    #[account(address = AD_NFT_UPDATE_AUTH)]
+    pub metadata_update_auth: UncheckedAccount<'info>,
+
+    /// The extension of the pool's config, if any, providing the position NFT metadata
+    #[account(
+        seeds = [b"config_extension", ai_dex_pool.load()?.ai_dex_config.as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Box<Account<'info, AiDexConfigExtension>>>,
+
+    /// The config of the pool, checked against the protocol-wide emergency pause
+    #[account(
+        address = ai_dex_pool.load()?.ai_dex_config,
+        constraint = !ai_dex_config.paused @ ErrorCode::ProtocolPausedError
+    )]
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    /// The liquidity allowlist of the pool, required while it is enabled
+    #[account(seeds = [b"lp_allowlist", ai_dex_pool.key().as_ref()], bump)]
+    pub lp_allowlist: Option<Box<Account<'info, PoolAllowlist>>>,
+
+    /// The token account of the owner holding a gating token of the liquidity allowlist, if any
+    pub lp_gating_token_account: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+}
+
+/// Opens a position whose NFT is a Token-2022 token carrying its own metadata.
+///
+/// The position mint uses the `MetadataPointer` and `TokenMetadata` extensions instead of a
+/// Metaplex metadata account, which saves the Metaplex CPI and the rent of the metadata account.
+/// The position is then managed with the same instructions as the other positions.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the accounts required for opening the position.
+/// * `tick_lower_index` - The lower tick index for the position.
+/// * `tick_upper_index` - The upper tick index for the position.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the position is successfully opened, otherwise returns an error.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The position cannot be opened.
+/// - The position mint, its metadata or the position token account cannot be created.
+pub fn open_position_token22_handler(
+    ctx: Context<OpenPositionToken22>,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> Result<()> {
+    verify_pool_allowlist(
+        &*ctx.accounts.ai_dex_pool.load()?,
+        PoolAllowlistKind::Liquidity,
+        ctx.accounts.lp_allowlist.as_deref(),
+        ctx.accounts.owner.key,
+        ctx.accounts.lp_gating_token_account.as_deref(),
+    )?;
+
+    let ai_dex = &ctx.accounts.ai_dex_pool;
+    let position_mint = &ctx.accounts.position_mint;
+    let position = &mut ctx.accounts.position;
+
+    position.open_position(
+        ai_dex,
+        position_mint.key(),
+        tick_lower_index,
+        tick_upper_index,
+    )?;
+
+    mint_position_token_2022_with_metadata_and_remove_authority(
+        ai_dex,
+        position_mint,
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.owner,
+        &ctx.accounts.metadata_update_auth,
+        &ctx.accounts.funder,
+        &ctx.accounts.token_2022_program,
+        &ctx.accounts.system_program,
+        &ctx.accounts.associated_token_program,
+        ctx.accounts.ai_dex_config_extension.as_deref().map(|extension| &**extension),
+    )?;
+
+    emit_cpi!(PositionToken22OpenedEvent {
+        funder: ctx.accounts.funder.key(),
+        ai_dex_pool: ai_dex.key(),
+        position: position.key(),
+        position_mint: position_mint.key(),
+        position_token_account: ctx.accounts.position_token_account.key(),
+        owner: ctx.accounts.owner.key(),
+        tick_lower_index,
+        tick_upper_index,
+        token_program: ctx.accounts.token_2022_program.key(),
+    });
+
+    Ok(())
+}
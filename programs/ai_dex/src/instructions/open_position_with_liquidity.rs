@@ -0,0 +1,296 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::memo::Memo;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{
+    Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface,
+};
+
+use crate::errors::ErrorCode;
+use crate::math::convert_to_liquidity_delta;
+use crate::orchestrator::liquidity_orchestrator::{
+    calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
+};
+use crate::state::*;
+use crate::util::{
+    calculate_transfer_fee_included_amount, check_deadline, mint_position_token_and_remove_authority,
+    parse_remaining_accounts, to_timestamp_u64, transfer_from_owner_to_vault, verify_pool_allowlist,
+    AccountsType, RemainingAccountsInfo, TickArrayAccount,
+};
+
+#[event]
+pub struct PositionOpenedWithLiquidityEvent {
+    pub funder: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub position: Pubkey,
+    pub position_mint: Pubkey,
+    pub position_token_account: Pubkey,
+    pub owner: Pubkey,
+    pub tick_lower_index: i32,
+    pub tick_upper_index: i32,
+    pub liquidity_amount: u128,
+    pub token_max_a: u64,
+    pub token_max_b: u64,
+    pub delta_a: u64,
+    pub delta_b: u64,
+    pub transfer_fee_included_delta_a: u64,
+    pub transfer_fee_included_delta_b: u64,
+    pub timestamp: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct OpenPositionWithLiquidity<'info> {
+    /// Pays for the new accounts and provides the initial liquidity.
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// CHECK: safe, the account that will be the owner of the position can be arbitrary
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = funder,
+        space = Position::LEN,
+        seeds = [b"position".as_ref(), position_mint.key().as_ref()],
+        bump,
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(
+        init,
+        payer = funder,
+        mint::authority = ai_dex_pool,
+        mint::decimals = 0,
+    )]
+    pub position_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = funder,
+        associated_token::mint = position_mint,
+        associated_token::authority = owner,
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
+    pub token_mint_a: Box<InterfaceAccount<'info, MintInterface>>,
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
+    pub token_mint_b: Box<InterfaceAccount<'info, MintInterface>>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == ai_dex_pool.load()?.token_mint_a)]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+    #[account(mut, constraint = token_owner_account_b.mint == ai_dex_pool.load()?.token_mint_b)]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    #[account(mut, constraint = token_vault_a.key() == ai_dex_pool.load()?.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+    #[account(mut, constraint = token_vault_b.key() == ai_dex_pool.load()?.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    /// CHECK: A fixed or dynamic tick array of the pool, checked in the handler
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: A fixed or dynamic tick array of the pool, checked in the handler
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    #[account(address = token_mint_a.to_account_info().owner.clone())]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = token_mint_b.to_account_info().owner.clone())]
+    pub token_program_b: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The config of the pool, checked against the protocol-wide emergency pause
+    #[account(
+        address = ai_dex_pool.load()?.ai_dex_config,
+        constraint = !ai_dex_config.paused @ ErrorCode::ProtocolPausedError
+    )]
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    /// The liquidity allowlist of the pool, required while it is enabled
+    #[account(seeds = [b"lp_allowlist", ai_dex_pool.key().as_ref()], bump)]
+    pub lp_allowlist: Option<Box<Account<'info, PoolAllowlist>>>,
+
+    /// The token account of the owner holding a gating token of the liquidity allowlist, if any
+    pub lp_gating_token_account: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+}
+
+/// Opens a position in the AI DEX and deposits its initial liquidity in the same instruction.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the accounts required for opening the position and depositing liquidity.
+/// * `tick_lower_index` - The lower tick index for the position.
+/// * `tick_upper_index` - The upper tick index for the position.
+/// * `liquidity_amount` - The amount of liquidity to be added.
+/// * `token_max_a` - The maximum amount of token A that can be transferred.
+/// * `token_max_b` - The maximum amount of token B that can be transferred.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+/// * `deadline_timestamp` - Optional unix timestamp after which the transaction is rejected.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the position is opened and funded, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::ZeroLiquidityError` - If the liquidity amount is zero.
+/// * `ErrorCode::TokenLimitExceededError` - If the transfer amount exceeds the specified token limits.
+/// * `ErrorCode::TransactionExpiredError` - If the deadline has passed.
+/// * `ErrorCode::AuthorityNotAllowlistedError` - If the liquidity allowlist of the pool is enabled and the owner is not allowed.
+/// * Any error from opening the position or minting the position token.
+pub fn open_position_with_liquidity_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, OpenPositionWithLiquidity<'info>>,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    liquidity_amount: u128,
+    token_max_a: u64,
+    token_max_b: u64,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+    deadline_timestamp: Option<i64>,
+) -> Result<()> {
+    verify_pool_allowlist(
+        &*ctx.accounts.ai_dex_pool.load()?,
+        PoolAllowlistKind::Liquidity,
+        ctx.accounts.lp_allowlist.as_deref(),
+        ctx.accounts.owner.key,
+        ctx.accounts.lp_gating_token_account.as_deref(),
+    )?;
+
+    if liquidity_amount == 0 {
+        return Err(ErrorCode::ZeroLiquidityError.into());
+    }
+
+    let clock = Clock::get()?;
+    check_deadline(deadline_timestamp, clock.unix_timestamp)?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+    )?;
+
+    // Open the position and mint its token
+    ctx.accounts.position.open_position(
+        &ctx.accounts.ai_dex_pool,
+        ctx.accounts.position_mint.key(),
+        tick_lower_index,
+        tick_upper_index,
+    )?;
+
+    mint_position_token_and_remove_authority(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.position_mint,
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.token_program,
+    )?;
+
+    // Deposit the initial liquidity
+    let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, true)?;
+
+    let ai_dex_pool_key = ctx.accounts.ai_dex_pool.key();
+    let tick_array_lower = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_lower, &ai_dex_pool_key)?;
+    let tick_array_upper = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_upper, &ai_dex_pool_key)?;
+
+    let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
+    let update = calculate_modify_liquidity(
+        &ai_dex,
+        &ctx.accounts.position,
+        &tick_array_lower,
+        &tick_array_upper,
+        liquidity_delta,
+        timestamp,
+    )?;
+
+    sync_modify_liquidity_values(
+        &mut ai_dex,
+        &mut ctx.accounts.position,
+        &tick_array_lower,
+        &tick_array_upper,
+        update,
+        timestamp,
+        &ctx.accounts.funder,
+        &ctx.accounts.system_program,
+    )?;
+
+    let (delta_a, delta_b) = calculate_liquidity_token_deltas(
+        ai_dex.tick_current_index,
+        ai_dex.sqrt_price,
+        &ctx.accounts.position,
+        liquidity_delta,
+    )?;
+    ctx.accounts.position.record_deposit(delta_a, delta_b, ai_dex.sqrt_price, timestamp);
+    drop(ai_dex);
+
+    let transfer_fee_included_delta_a = calculate_transfer_fee_included_amount(
+        &ctx.accounts.token_mint_a,
+        delta_a,
+    )?;
+    let transfer_fee_included_delta_b = calculate_transfer_fee_included_amount(
+        &ctx.accounts.token_mint_b,
+        delta_b,
+    )?;
+
+    // token_max_a and token_max_b should be applied to the transfer fee included amount
+    if transfer_fee_included_delta_a.amount > token_max_a {
+        return Err(ErrorCode::TokenLimitExceededError.into());
+    }
+    if transfer_fee_included_delta_b.amount > token_max_b {
+        return Err(ErrorCode::TokenLimitExceededError.into());
+    }
+
+    transfer_from_owner_to_vault(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.funder,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_owner_account_a,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_program_a,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_a,
+        transfer_fee_included_delta_a.amount,
+    )?;
+
+    transfer_from_owner_to_vault(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.funder,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.token_owner_account_b,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_program_b,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_b,
+        transfer_fee_included_delta_b.amount,
+    )?;
+
+    emit_cpi!(PositionOpenedWithLiquidityEvent {
+        funder: ctx.accounts.funder.key(),
+        ai_dex_pool: ai_dex_pool_key,
+        position: ctx.accounts.position.key(),
+        position_mint: ctx.accounts.position_mint.key(),
+        position_token_account: ctx.accounts.position_token_account.key(),
+        owner: ctx.accounts.owner.key(),
+        tick_lower_index,
+        tick_upper_index,
+        liquidity_amount,
+        token_max_a,
+        token_max_b,
+        delta_a,
+        delta_b,
+        transfer_fee_included_delta_a: transfer_fee_included_delta_a.amount,
+        transfer_fee_included_delta_b: transfer_fee_included_delta_b.amount,
+        timestamp,
+    });
+
+    Ok(())
+}
@@ -1,9 +1,17 @@
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::TokenAccount as TokenAccountInterface;
 use anchor_spl::metadata::Metadata;
 
-use crate::{state::*, util::mint_position_token_with_metadata_and_remove_authority};
+use crate::errors::ErrorCode;
+use crate::{
+    state::*,
+    util::{
+        mint_position_token_with_metadata_and_remove_authority, verify_pool_allowlist,
+        PositionCollectionAccounts,
+    },
+};
 use crate::state;
 
 use crate::constants::nft::ai_dex_nft_update_auth::ID as AD_NFT_UPDATE_AUTH;
@@ -22,6 +30,7 @@ pub struct PositionWithMetadataOpenedEvent {
     pub token_program: Pubkey,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct OpenPositionWithMetadata<'info> {
     #[account(mut)]
@@ -58,7 +67,7 @@ pub struct OpenPositionWithMetadata<'info> {
     )]
     pub position_token_account: Box<Account<'info, TokenAccount>>,
 
-    pub ai_dex_pool: Box<Account<'info, AiDexPool>>,
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
 
     #[account(address = token::ID)]
     pub token_program: Program<'info, Token>,
@@ -71,6 +80,38 @@ pub struct OpenPositionWithMetadata<'info> {
     /// CHECK: checked via account constraints
     #[account(address = AD_NFT_UPDATE_AUTH)]
     pub metadata_update_auth: UncheckedAccount<'info>,
+
+    /// The extension of the pool's config, if any, providing the position NFT metadata
+    #[account(
+        seeds = [b"config_extension", ai_dex_pool.load()?.ai_dex_config.as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Box<Account<'info, AiDexConfigExtension>>>,
+
+    /// The collection NFT of the pool, if the position is to be a verified member of it
+    #[account(
+        seeds = [b"position_collection", ai_dex_pool.key().as_ref()],
+        bump,
+    )]
+    pub position_collection_mint: Option<Box<Account<'info, Mint>>>,
+    /// CHECK: checked via the Metadata CPI call
+    pub position_collection_metadata: Option<UncheckedAccount<'info>>,
+    /// CHECK: checked via the Metadata CPI call
+    pub position_collection_master_edition: Option<UncheckedAccount<'info>>,
+
+    /// The config of the pool, checked against the protocol-wide emergency pause
+    #[account(
+        address = ai_dex_pool.load()?.ai_dex_config,
+        constraint = !ai_dex_config.paused @ ErrorCode::ProtocolPausedError
+    )]
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    /// The liquidity allowlist of the pool, required while it is enabled
+    #[account(seeds = [b"lp_allowlist", ai_dex_pool.key().as_ref()], bump)]
+    pub lp_allowlist: Option<Box<Account<'info, PoolAllowlist>>>,
+
+    /// The token account of the owner holding a gating token of the liquidity allowlist, if any
+    pub lp_gating_token_account: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
 }
 
 /// Opens a position with metadata in the AI DEX.
@@ -90,17 +131,40 @@ pub struct OpenPositionWithMetadata<'info> {
 ///
 /// This function will return an error if:
 /// - The position cannot be opened.
-/// - The position token with metadata cannot be minted or the authority cannot be removed.
+/// - Only some of the position collection accounts are provided.
+/// - The position token with metadata cannot be minted, verified in the collection, or the authority cannot be removed.
 pub fn open_position_with_metadata_handler(
     ctx: Context<OpenPositionWithMetadata>,
     _bumps: state::OpenPositionWithMetadataBumps,
     tick_lower_index: i32,
     tick_upper_index: i32,
 ) -> Result<()> {
+    verify_pool_allowlist(
+        &*ctx.accounts.ai_dex_pool.load()?,
+        PoolAllowlistKind::Liquidity,
+        ctx.accounts.lp_allowlist.as_deref(),
+        ctx.accounts.owner.key,
+        ctx.accounts.lp_gating_token_account.as_deref(),
+    )?;
+
     let ai_dex = &ctx.accounts.ai_dex_pool;
     let position_mint = &ctx.accounts.position_mint;
     let position = &mut ctx.accounts.position;
 
+    let position_collection = match (
+        &ctx.accounts.position_collection_mint,
+        &ctx.accounts.position_collection_metadata,
+        &ctx.accounts.position_collection_master_edition,
+    ) {
+        (Some(mint), Some(metadata), Some(master_edition)) => Some(PositionCollectionAccounts {
+            mint,
+            metadata,
+            master_edition,
+        }),
+        (None, None, None) => None,
+        _ => return Err(ErrorCode::InvalidPositionCollectionAccountsError.into()),
+    };
+
     // Open the position
     position.open_position(
         ai_dex,
@@ -121,9 +185,11 @@ pub fn open_position_with_metadata_handler(
         &ctx.accounts.token_program,
         &ctx.accounts.system_program,
         &ctx.accounts.rent,
+        ctx.accounts.ai_dex_config_extension.as_deref().map(|extension| &**extension),
+        position_collection,
     )?;
 
-    emit!(PositionWithMetadataOpenedEvent {
+    emit_cpi!(PositionWithMetadataOpenedEvent {
         funder: ctx.accounts.funder.key(),
         ai_dex_pool: ai_dex.key(),
         position: position.key(),
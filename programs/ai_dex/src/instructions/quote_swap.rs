@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    orchestrator::swap_orchestrator::PostSwapUpdate,
+    state::AiDexPool,
+    swap_with_transfer_fee_extension,
+    util::{
+        apply_tick_array_bitmap, calculate_transfer_fee_excluded_amount, load_supplemental_tick_arrays,
+        oracle_volatility_ticks, parse_remaining_accounts, to_timestamp_u64, AccountsType,
+        RemainingAccountsInfo, SwapTickSequence, TickArrayAccount,
+    },
+};
+
+/// The outcome of a swap, as quoted by `quote_swap`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SwapQuote {
+    /// The amount paid by the owner, transfer fee included.
+    pub amount_in: u64,
+    /// The amount received by the owner, transfer fee excluded.
+    pub amount_out: u64,
+    /// The swap fee in the input token, the protocol fee included.
+    pub fee_amount: u64,
+    pub end_sqrt_price: u128,
+    pub end_tick_index: i32,
+}
+
+impl SwapQuote {
+    /// Builds the quote of a swap from its update and the transfer-fee-excluded output amount.
+    pub fn new(swap_update: &PostSwapUpdate, a_to_b: bool, amount_out: u64) -> Self {
+        Self {
+            amount_in: if a_to_b { swap_update.amount_a } else { swap_update.amount_b },
+            amount_out,
+            fee_amount: swap_update.fee_amount,
+            end_sqrt_price: swap_update.next_sqrt_price,
+            end_tick_index: swap_update.next_tick_index,
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct QuoteSwap<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: The first tick array, fixed or dynamic, which must be associated with the AI DEX. Checked in the handler.
+    /// The swap runs on a copy of it, so it can be passed read-only.
+    pub tick_array_0: UncheckedAccount<'info>,
+
+    /// CHECK: The second tick array, fixed or dynamic. Checked in the handler.
+    pub tick_array_1: UncheckedAccount<'info>,
+
+    /// CHECK: The third tick array, fixed or dynamic. Checked in the handler.
+    pub tick_array_2: UncheckedAccount<'info>,
+
+    /// CHECK: The oracle account of the pool, measuring the volatility for the adaptive fee.
+    #[account(seeds = [b"oracle", ai_dex_pool.key().as_ref()], bump)]
+    pub oracle: UncheckedAccount<'info>,
+
+    /// CHECK: The tick array bitmap of the pool. Used only if it has been initialized.
+    #[account(seeds = [b"tick_array_bitmap", ai_dex_pool.key().as_ref()], bump)]
+    pub tick_array_bitmap: UncheckedAccount<'info>,
+}
+
+/// Quotes a swap without executing it.
+///
+/// The swap is computed as `swap` would at the current timestamp, without referrer nor fee
+/// discount, but no state is changed and no token is transferred. The quote is returned to the
+/// caller through the instruction return data, so other programs can price routes after a CPI.
+///
+/// The tick arrays are copied before the swap is computed, so none of them needs to be writable.
+/// A tick array passed more than once is only used at its first position, as `swap` would.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the pool and its tick arrays.
+/// * `amount` - The amount to swap, input or output depending on `amount_specified_is_input`.
+/// * `sqrt_price_limit` - The square root price limit for the swap. `0` means no limit in the swap direction.
+/// * `amount_specified_is_input` - Whether the amount is the input amount.
+/// * `a_to_b` - The direction of the swap.
+/// * `remaining_accounts_info` - Optional remaining accounts information. Up to three `SupplementalTickArrays`
+///   can be passed to continue the tick array sequence past `tick_array_2`, as for `swap`.
+///
+/// # Returns
+///
+/// * `Result<SwapQuote>` - The amounts, fee and end price of the swap.
+///
+/// # Errors
+///
+/// * Any error `swap` would fail with given the same pool state, e.g. `PoolPausedError`.
+pub fn quote_swap_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, QuoteSwap<'info>>,
+    amount: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<SwapQuote> {
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let ai_dex = ctx.accounts.ai_dex_pool.load()?;
+    let ai_dex_pool_key = ctx.accounts.ai_dex_pool.key();
+
+    let tick_array_0 = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_0, &ai_dex_pool_key)?;
+    let tick_array_1 = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_1, &ai_dex_pool_key)?;
+    let tick_array_2 = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_2, &ai_dex_pool_key)?;
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::SupplementalTickArrays],
+    )?;
+    let supplemental_tick_arrays = load_supplemental_tick_arrays(
+        &remaining_accounts.supplemental_tick_arrays,
+        &ai_dex_pool_key,
+    )?;
+
+    // A swap cannot borrow the same tick array twice, so it skips the repeated ones; the copies do the same
+    let mut tick_array_keys: Vec<Pubkey> = Vec::new();
+    let mut tick_array_copies = Vec::new();
+    for tick_array in [&tick_array_0, &tick_array_1, &tick_array_2]
+        .iter()
+        .copied()
+        .chain(supplemental_tick_arrays.iter())
+    {
+        if tick_array_keys.contains(&tick_array.key()) {
+            tick_array_copies.push(None);
+        } else {
+            tick_array_keys.push(tick_array.key());
+            tick_array_copies.push(Some(tick_array.load_copy()?));
+        }
+    }
+
+    let mut swap_tick_sequence = SwapTickSequence::new_with_tick_arrays(
+        tick_array_copies[0].as_ref().unwrap().load_mut(),
+        tick_array_copies[1].as_ref().map(|tick_array| tick_array.load_mut()),
+        tick_array_copies[2].as_ref().map(|tick_array| tick_array.load_mut()),
+    );
+    for tick_array in tick_array_copies[3..].iter().flatten() {
+        swap_tick_sequence.push_tick_array(tick_array.load_mut());
+    }
+    swap_tick_sequence.skip_tick_updates();
+    apply_tick_array_bitmap(
+        &ctx.accounts.tick_array_bitmap,
+        &mut swap_tick_sequence,
+        ai_dex.tick_spacing,
+        a_to_b,
+    )?;
+
+    let volatility_ticks = oracle_volatility_ticks(&ctx.accounts.oracle, &ai_dex, timestamp)?;
+    let swap_update = swap_with_transfer_fee_extension(
+        &ai_dex,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_mint_b,
+        &mut swap_tick_sequence,
+        amount,
+        sqrt_price_limit,
+        amount_specified_is_input,
+        a_to_b,
+        timestamp,
+        0,
+        0,
+        volatility_ticks,
+    )?;
+
+    let amount_out = if a_to_b {
+        calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_b, swap_update.amount_b)?.amount
+    } else {
+        calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_a, swap_update.amount_a)?.amount
+    };
+
+    Ok(SwapQuote::new(&swap_update, a_to_b, amount_out))
+}
@@ -0,0 +1,234 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::transfer_memo;
+use crate::errors::ErrorCode;
+use crate::math::convert_to_liquidity_delta;
+use crate::orchestrator::liquidity_orchestrator::{
+    calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
+};
+use crate::state::*;
+use crate::util::{
+    parse_remaining_accounts, to_timestamp_u64, transfer_from_vault_to_owner, AccountsType,
+    RemainingAccountsInfo, TickArrayAccount,
+};
+
+#[event]
+pub struct RangeOrderExecutedEvent {
+    pub keeper: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub position: Pubkey,
+    pub range_order: Pubkey,
+    pub a_to_b: bool,
+    pub liquidity_amount: u128,
+    pub amount: u64,
+    pub bounty: u64,
+    pub timestamp: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteRangeOrder<'info> {
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    /// Pays the rent if a tick of a dynamic tick array changes while removing the liquidity.
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    #[account(mut, has_one = position, has_one = funder, close = funder)]
+    pub range_order: Box<Account<'info, RangeOrder>>,
+
+    /// CHECK: safe, the account that paid the rent of the range order
+    #[account(mut)]
+    pub funder: UncheckedAccount<'info>,
+
+    #[account(mut, has_one = ai_dex_pool)]
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
+    pub token_mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
+    pub token_mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The account of the position owner receiving the converted tokens.
+    #[account(mut,
+        constraint = owner_token_account.owner == position_token_account.owner,
+        constraint = owner_token_account.mint == if range_order.a_to_b { token_mint_b.key() } else { token_mint_a.key() },
+    )]
+    pub owner_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// The account of the keeper receiving the bounty.
+    #[account(mut,
+        constraint = keeper_token_account.mint == owner_token_account.mint,
+    )]
+    pub keeper_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: A fixed or dynamic tick array of the pool, checked in the handler
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: A fixed or dynamic tick array of the pool, checked in the handler
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(address = token_mint_a.to_account_info().owner.clone())]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = token_mint_b.to_account_info().owner.clone())]
+    pub token_program_b: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Withdraws a filled range order to the position owner, paying the keeper its bounty.
+///
+/// The instruction is permissionless. Once the price has fully crossed the range of the position,
+/// all its liquidity is withdrawn in the converted token, the bounty is sent to the keeper and the
+/// rest to the position owner, and the range order is closed. Fees and rewards stay owed to the
+/// position.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the execution.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the range order is executed, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::RangeOrderNotFilledError` - If the price has not fully crossed the range.
+/// * `ErrorCode::PositionLockedError` - If the position is locked.
+/// * `ErrorCode::ZeroLiquidityError` - If the position has no liquidity.
+pub fn execute_range_order_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ExecuteRangeOrder<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    if ctx.accounts.position.is_locked(timestamp) {
+        return Err(ErrorCode::PositionLockedError.into());
+    }
+
+    let liquidity_amount = ctx.accounts.position.liquidity;
+    if liquidity_amount == 0 {
+        return Err(ErrorCode::ZeroLiquidityError.into());
+    }
+
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+    )?;
+
+    let a_to_b = ctx.accounts.range_order.a_to_b;
+    let ai_dex_pool_key = ctx.accounts.ai_dex_pool.key();
+    let tick_array_lower = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_lower, &ai_dex_pool_key)?;
+    let tick_array_upper = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_upper, &ai_dex_pool_key)?;
+
+    let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
+    if !ctx.accounts.range_order.is_filled(
+        ai_dex.tick_current_index,
+        ctx.accounts.position.tick_lower_index,
+        ctx.accounts.position.tick_upper_index,
+    ) {
+        return Err(ErrorCode::RangeOrderNotFilledError.into());
+    }
+
+    let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, false)?;
+    let update = calculate_modify_liquidity(
+        &ai_dex,
+        &ctx.accounts.position,
+        &tick_array_lower,
+        &tick_array_upper,
+        liquidity_delta,
+        timestamp,
+    )?;
+    sync_modify_liquidity_values(
+        &mut ai_dex,
+        &mut ctx.accounts.position,
+        &tick_array_lower,
+        &tick_array_upper,
+        update,
+        timestamp,
+        &ctx.accounts.keeper,
+        &ctx.accounts.system_program,
+    )?;
+
+    let (delta_a, delta_b) = calculate_liquidity_token_deltas(
+        ai_dex.tick_current_index,
+        ai_dex.sqrt_price,
+        &ctx.accounts.position,
+        liquidity_delta,
+    )?;
+    drop(ai_dex);
+    drop(tick_array_lower);
+    drop(tick_array_upper);
+
+    // The price crossed the whole range, so only the converted token is withdrawn
+    let (amount, token_mint, token_vault, token_program, transfer_hook) = if a_to_b {
+        (
+            delta_b,
+            &ctx.accounts.token_mint_b,
+            &ctx.accounts.token_vault_b,
+            &ctx.accounts.token_program_b,
+            &remaining_accounts.transfer_hook_b,
+        )
+    } else {
+        (
+            delta_a,
+            &ctx.accounts.token_mint_a,
+            &ctx.accounts.token_vault_a,
+            &ctx.accounts.token_program_a,
+            &remaining_accounts.transfer_hook_a,
+        )
+    };
+    let (bounty, owner_amount) = ctx.accounts.range_order.split_bounty(amount);
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.ai_dex_pool,
+        token_mint,
+        token_vault,
+        &ctx.accounts.keeper_token_account,
+        token_program,
+        &ctx.accounts.memo_program,
+        transfer_hook,
+        bounty,
+        transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
+    )?;
+    transfer_from_vault_to_owner(
+        &ctx.accounts.ai_dex_pool,
+        token_mint,
+        token_vault,
+        &ctx.accounts.owner_token_account,
+        token_program,
+        &ctx.accounts.memo_program,
+        transfer_hook,
+        owner_amount,
+        transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
+    )?;
+
+    emit_cpi!(RangeOrderExecutedEvent {
+        keeper: ctx.accounts.keeper.key(),
+        ai_dex_pool: ai_dex_pool_key,
+        position: ctx.accounts.position.key(),
+        range_order: ctx.accounts.range_order.key(),
+        a_to_b,
+        liquidity_amount,
+        amount,
+        bounty,
+        timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::util::verify_position_authority;
+
+#[event]
+pub struct RangeOrderInitializedEvent {
+    pub position_authority: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub position: Pubkey,
+    pub range_order: Pubkey,
+    pub a_to_b: bool,
+    pub bounty_bps: u16,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeRangeOrder<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut)]
+    pub position_authority: Signer<'info>,
+
+    #[account(has_one = ai_dex_pool)]
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = position_authority,
+        seeds = [
+            b"range_order",
+            position.key().as_ref(),
+        ],
+        bump,
+        space = RangeOrder::LEN
+    )]
+    pub range_order: Account<'info, RangeOrder>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Marks a single-sided position as a range order.
+///
+/// Once the price fully crosses the range of the position, any keeper can withdraw the converted
+/// tokens to the position owner with `execute_range_order`, keeping `bounty_bps` of them.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the operation.
+/// * `bounty_bps` - The share of the converted tokens paid to the keeper, in basis points.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the operation is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidRangeOrderError` - If the position has no liquidity or is in range.
+/// * `ErrorCode::InvalidRangeOrderBountyError` - If the bounty exceeds the maximum.
+pub fn initialize_range_order_handler(ctx: Context<InitializeRangeOrder>, bounty_bps: u16) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    if ctx.accounts.position.liquidity == 0 {
+        return Err(ErrorCode::InvalidRangeOrderError.into());
+    }
+
+    let tick_current_index = ctx.accounts.ai_dex_pool.load()?.tick_current_index;
+    ctx.accounts.range_order.initialize(
+        ctx.accounts.position.key(),
+        ctx.accounts.position_authority.key(),
+        tick_current_index,
+        ctx.accounts.position.tick_lower_index,
+        ctx.accounts.position.tick_upper_index,
+        bounty_bps,
+    )?;
+
+    emit_cpi!(RangeOrderInitializedEvent {
+        position_authority: ctx.accounts.position_authority.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        position: ctx.accounts.position.key(),
+        range_order: ctx.accounts.range_order.key(),
+        a_to_b: ctx.accounts.range_order.a_to_b,
+        bounty_bps,
+    });
+
+    Ok(())
+}
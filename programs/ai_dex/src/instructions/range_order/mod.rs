@@ -0,0 +1,7 @@
+pub mod cancel_range_order;
+pub mod execute_range_order;
+pub mod initialize_range_order;
+
+pub use cancel_range_order::*;
+pub use execute_range_order::*;
+pub use initialize_range_order::*;
@@ -0,0 +1,335 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::transfer_memo;
+use crate::errors::ErrorCode;
+use crate::math::{convert_to_liquidity_delta, get_liquidity_from_amounts, sqrt_price_from_tick_index};
+use crate::orchestrator::liquidity_orchestrator::{
+    calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
+};
+use crate::state::*;
+use crate::util::{
+    check_deadline, parse_remaining_accounts, to_timestamp_u64, transfer_from_vault_to_owner,
+    verify_position_authority_or_operator, AccountsType, ParsedRemainingAccounts,
+    RemainingAccountsInfo, TickArrayAccount,
+};
+
+#[event]
+pub struct PositionRebalancedEvent {
+    pub position_authority: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub position: Pubkey,
+    pub old_tick_lower_index: i32,
+    pub old_tick_upper_index: i32,
+    pub new_tick_lower_index: i32,
+    pub new_tick_upper_index: i32,
+    pub old_liquidity: u128,
+    pub new_liquidity: u128,
+    pub withdrawn_a: u64,
+    pub withdrawn_b: u64,
+    pub deposited_a: u64,
+    pub deposited_b: u64,
+    pub timestamp: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RebalancePosition<'info> {
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(address = token_mint_a.to_account_info().owner.clone())]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = token_mint_b.to_account_info().owner.clone())]
+    pub token_program_b: Interface<'info, TokenInterface>,
+
+    pub memo_program: Program<'info, Memo>,
+
+    /// Pays the rent when a tick of a dynamic tick array gets initialized.
+    #[account(mut)]
+    pub position_authority: Signer<'info>,
+
+    #[account(mut, has_one = ai_dex_pool)]
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
+    pub token_mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
+    pub token_mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == ai_dex_pool.load()?.token_mint_a)]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, constraint = token_owner_account_b.mint == ai_dex_pool.load()?.token_mint_b)]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: The tick array of the current lower tick, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: The tick array of the current upper tick, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    /// CHECK: The tick array of the new lower tick, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub new_tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: The tick array of the new upper tick, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub new_tick_array_upper: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The config of the pool, checked against the protocol-wide emergency pause
+    #[account(
+        address = ai_dex_pool.load()?.ai_dex_config,
+        constraint = !ai_dex_config.paused @ ErrorCode::ProtocolPausedError
+    )]
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+}
+
+/// Moves all liquidity of a position into a new tick range, keeping the position and its NFT.
+///
+/// The liquidity is withdrawn from the current range, the position is moved to the new range,
+/// and the largest liquidity the withdrawn amounts can fund is deposited back. The tokens stay in
+/// the vaults and only the amounts that do not fit the new range are sent to the owner. Fees and
+/// rewards owed are kept in the position.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the rebalance.
+/// * `new_tick_lower_index` - The new lower tick index of the position.
+/// * `new_tick_upper_index` - The new upper tick index of the position.
+/// * `liquidity_min` - The minimum liquidity to deposit into the new range.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+/// * `deadline_timestamp` - Optional unix timestamp after which the transaction is rejected.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the rebalance is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidTickIndexError` - If the new tick range is invalid.
+/// * `ErrorCode::LiquidityBelowMinimumError` - If the new liquidity is below `liquidity_min`.
+/// * `ErrorCode::TransactionExpiredError` - If the deadline has passed.
+/// * `ErrorCode::PositionLockedError` - If the position is locked.
+pub fn rebalance_position_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, RebalancePosition<'info>>,
+    new_tick_lower_index: i32,
+    new_tick_upper_index: i32,
+    liquidity_min: u128,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+    deadline_timestamp: Option<i64>,
+) -> Result<()> {
+    verify_position_authority_or_operator(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position,
+        &ctx.accounts.position_authority,
+        &[
+            ctx.accounts.token_owner_account_a.owner,
+            ctx.accounts.token_owner_account_b.owner,
+        ],
+    )?;
+
+    let clock = Clock::get()?;
+    check_deadline(deadline_timestamp, clock.unix_timestamp)?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+    )?;
+
+    let event = rebalance_liquidity(
+        ctx.accounts,
+        &remaining_accounts,
+        new_tick_lower_index,
+        new_tick_upper_index,
+        liquidity_min,
+        timestamp,
+    )?;
+    emit_cpi!(event);
+
+    Ok(())
+}
+
+/// Moves all liquidity of a position into a new tick range, once the caller has been authorized.
+///
+/// # Arguments
+///
+/// * `accounts` - The accounts of the rebalance.
+/// * `remaining_accounts` - The parsed remaining accounts holding the transfer hook accounts.
+/// * `new_tick_lower_index` - The new lower tick index of the position.
+/// * `new_tick_upper_index` - The new upper tick index of the position.
+/// * `liquidity_min` - The minimum liquidity to deposit into the new range.
+/// * `timestamp` - The current unix timestamp.
+///
+/// # Returns
+///
+/// * `Result<PositionRebalancedEvent>` - The event describing the rebalance, to be emitted by the caller.
+pub(crate) fn rebalance_liquidity<'info>(
+    accounts: &mut RebalancePosition<'info>,
+    remaining_accounts: &ParsedRemainingAccounts<'info>,
+    new_tick_lower_index: i32,
+    new_tick_upper_index: i32,
+    liquidity_min: u128,
+    timestamp: u64,
+) -> Result<PositionRebalancedEvent> {
+    if accounts.position.is_locked(timestamp) {
+        return Err(ErrorCode::PositionLockedError.into());
+    }
+
+    let ai_dex_pool_key = accounts.ai_dex_pool.key();
+    let old_tick_lower_index = accounts.position.tick_lower_index;
+    let old_tick_upper_index = accounts.position.tick_upper_index;
+    let old_liquidity = accounts.position.liquidity;
+
+    let mut ai_dex = accounts.ai_dex_pool.load_mut()?;
+
+    // Withdraw the liquidity from the current range, leaving the tokens in the vaults
+    let (withdrawn_a, withdrawn_b) = if old_liquidity > 0 {
+        let tick_array_lower = TickArrayAccount::try_from_with_pool(&accounts.tick_array_lower, &ai_dex_pool_key)?;
+        let tick_array_upper = TickArrayAccount::try_from_with_pool(&accounts.tick_array_upper, &ai_dex_pool_key)?;
+        let liquidity_delta = convert_to_liquidity_delta(old_liquidity, false)?;
+
+        let update = calculate_modify_liquidity(
+            &ai_dex,
+            &accounts.position,
+            &tick_array_lower,
+            &tick_array_upper,
+            liquidity_delta,
+            timestamp,
+        )?;
+        sync_modify_liquidity_values(
+            &mut ai_dex,
+            &mut accounts.position,
+            &tick_array_lower,
+            &tick_array_upper,
+            update,
+            timestamp,
+            &accounts.position_authority,
+            &accounts.system_program,
+        )?;
+
+        calculate_liquidity_token_deltas(
+            ai_dex.tick_current_index,
+            ai_dex.sqrt_price,
+            &accounts.position,
+            liquidity_delta,
+        )?
+    } else {
+        (0, 0)
+    };
+
+    accounts.position.reset_tick_range(
+        &ai_dex,
+        new_tick_lower_index,
+        new_tick_upper_index,
+    )?;
+
+    // Deposit the largest liquidity the withdrawn amounts can fund into the new range
+    let new_liquidity = get_liquidity_from_amounts(
+        ai_dex.sqrt_price,
+        sqrt_price_from_tick_index(new_tick_lower_index),
+        sqrt_price_from_tick_index(new_tick_upper_index),
+        withdrawn_a,
+        withdrawn_b,
+    )?;
+    if new_liquidity < liquidity_min {
+        return Err(ErrorCode::LiquidityBelowMinimumError.into());
+    }
+
+    let (deposited_a, deposited_b) = if new_liquidity > 0 {
+        let tick_array_lower = TickArrayAccount::try_from_with_pool(&accounts.new_tick_array_lower, &ai_dex_pool_key)?;
+        let tick_array_upper = TickArrayAccount::try_from_with_pool(&accounts.new_tick_array_upper, &ai_dex_pool_key)?;
+        let liquidity_delta = convert_to_liquidity_delta(new_liquidity, true)?;
+
+        let update = calculate_modify_liquidity(
+            &ai_dex,
+            &accounts.position,
+            &tick_array_lower,
+            &tick_array_upper,
+            liquidity_delta,
+            timestamp,
+        )?;
+        sync_modify_liquidity_values(
+            &mut ai_dex,
+            &mut accounts.position,
+            &tick_array_lower,
+            &tick_array_upper,
+            update,
+            timestamp,
+            &accounts.position_authority,
+            &accounts.system_program,
+        )?;
+
+        calculate_liquidity_token_deltas(
+            ai_dex.tick_current_index,
+            ai_dex.sqrt_price,
+            &accounts.position,
+            liquidity_delta,
+        )?
+    } else {
+        (0, 0)
+    };
+    drop(ai_dex);
+
+    // Send what does not fit the new range back to the owner
+    let refund_a = withdrawn_a
+        .checked_sub(deposited_a)
+        .ok_or(ErrorCode::TokenLimitExceededError)?;
+    let refund_b = withdrawn_b
+        .checked_sub(deposited_b)
+        .ok_or(ErrorCode::TokenLimitExceededError)?;
+
+    transfer_from_vault_to_owner(
+        &accounts.ai_dex_pool,
+        &accounts.token_mint_a,
+        &accounts.token_vault_a,
+        &accounts.token_owner_account_a,
+        &accounts.token_program_a,
+        &accounts.memo_program,
+        &remaining_accounts.transfer_hook_a,
+        refund_a,
+        transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
+    )?;
+    transfer_from_vault_to_owner(
+        &accounts.ai_dex_pool,
+        &accounts.token_mint_b,
+        &accounts.token_vault_b,
+        &accounts.token_owner_account_b,
+        &accounts.token_program_b,
+        &accounts.memo_program,
+        &remaining_accounts.transfer_hook_b,
+        refund_b,
+        transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
+    )?;
+
+    Ok(PositionRebalancedEvent {
+        position_authority: accounts.position_authority.key(),
+        ai_dex_pool: ai_dex_pool_key,
+        position: accounts.position.key(),
+        old_tick_lower_index,
+        old_tick_upper_index,
+        new_tick_lower_index,
+        new_tick_upper_index,
+        old_liquidity,
+        new_liquidity,
+        withdrawn_a,
+        withdrawn_b,
+        deposited_a,
+        deposited_b,
+        timestamp,
+    })
+}
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::AiDexPool;
+use crate::util::{parse_remaining_accounts, transfer_from_owner_to_vault, AccountsType, RemainingAccountsInfo};
+
+#[event]
+pub struct FlashSwapRepaidEvent {
+    pub token_authority: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub amount: u64,
+    pub repaid_in_a: bool,
+}
+
+// The pool must stay the second account, `flash_swap` looking it up in the instructions sysvar
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RepayFlashSwap<'info> {
+    /// The authority paying the input of the flash swap
+    pub token_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == ai_dex_pool.load()?.token_mint_a)]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, constraint = token_owner_account_b.mint == ai_dex_pool.load()?.token_mint_b)]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = token_mint_a.to_account_info().owner.clone())]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = token_mint_b.to_account_info().owner.clone())]
+    pub token_program_b: Interface<'info, TokenInterface>,
+
+    pub memo_program: Program<'info, Memo>,
+}
+
+/// Pays the input owed by the flash swap of a pool.
+///
+/// Any authority can repay the flash swap. The instruction must be a top-level instruction of
+/// the transaction of the flash swap, for `flash_swap` to find it.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the repayment.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the repayment is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::NoFlashSwapInProgressError` - If no flash swap of the pool awaits repayment.
+pub fn repay_flash_swap_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, RepayFlashSwap<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let (amount, repaid_in_a) = ctx.accounts.ai_dex_pool.load_mut()?.finish_flash_swap()?;
+
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+    )?;
+
+    if repaid_in_a {
+        transfer_from_owner_to_vault(
+            &ctx.accounts.ai_dex_pool,
+            &ctx.accounts.token_authority,
+            &ctx.accounts.token_mint_a,
+            &ctx.accounts.token_owner_account_a,
+            &ctx.accounts.token_vault_a,
+            &ctx.accounts.token_program_a,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_a,
+            amount,
+        )?;
+    } else {
+        transfer_from_owner_to_vault(
+            &ctx.accounts.ai_dex_pool,
+            &ctx.accounts.token_authority,
+            &ctx.accounts.token_mint_b,
+            &ctx.accounts.token_owner_account_b,
+            &ctx.accounts.token_vault_b,
+            &ctx.accounts.token_program_b,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_b,
+            amount,
+        )?;
+    }
+
+    emit_cpi!(FlashSwapRepaidEvent {
+        token_authority: ctx.accounts.token_authority.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        amount,
+        repaid_in_a,
+    });
+
+    Ok(())
+}
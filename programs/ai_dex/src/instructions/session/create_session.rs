@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+#[event]
+pub struct SessionCreatedEvent {
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub session: Pubkey,
+    pub expiry_timestamp: i64,
+    pub notional_mint: Pubkey,
+    pub max_notional: u64,
+    pub allowed_pools: Vec<Pubkey>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateSession<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: the ephemeral key the swap rights are delegated to
+    pub session_key: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"session", owner.key().as_ref(), session_key.key().as_ref()],
+        bump,
+        space = Session::LEN
+    )]
+    pub session: Account<'info, Session>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates a session delegating limited swap rights of the owner to a session key.
+///
+/// The owner then approves the session account as the delegate of the token accounts the
+/// swaps of the session key are paid from.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts and programs required for the operation.
+/// * `expiry_timestamp` - The unix timestamp after which the session key can no longer swap.
+/// * `notional_mint` - The mint the notional of the swaps is measured in.
+/// * `max_notional` - The maximum cumulative notional of the swaps.
+/// * `allowed_pools` - The pools the session key can swap on.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the session is created, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::SessionExpiredError` - If the expiry timestamp is not in the future.
+/// * `ErrorCode::InvalidSessionPoolsError` - If no pool or more than `MAX_SESSION_POOLS` pools are allowed.
+pub fn create_session_handler(
+    ctx: Context<CreateSession>,
+    expiry_timestamp: i64,
+    notional_mint: Pubkey,
+    max_notional: u64,
+    allowed_pools: Vec<Pubkey>,
+) -> Result<()> {
+    if expiry_timestamp <= Clock::get()?.unix_timestamp {
+        return Err(ErrorCode::SessionExpiredError.into());
+    }
+
+    ctx.accounts.session.initialize(
+        ctx.accounts.owner.key(),
+        ctx.accounts.session_key.key(),
+        expiry_timestamp,
+        notional_mint,
+        max_notional,
+        &allowed_pools,
+        ctx.bumps.session,
+    )?;
+
+    emit_cpi!(SessionCreatedEvent {
+        owner: ctx.accounts.owner.key(),
+        session_key: ctx.accounts.session_key.key(),
+        session: ctx.accounts.session.key(),
+        expiry_timestamp,
+        notional_mint,
+        max_notional,
+        allowed_pools,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,5 @@
+pub mod create_session;
+pub mod revoke_session;
+
+pub use create_session::*;
+pub use revoke_session::*;
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[event]
+pub struct SessionRevokedEvent {
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub session: Pubkey,
+    pub notional_used: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RevokeSession<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mut, has_one = owner, close = owner)]
+    pub session: Account<'info, Session>,
+}
+
+/// Revokes a session, closing it so its session key can no longer swap.
+///
+/// The delegations the owner approved to the session account are left to the owner to revoke,
+/// although they can no longer be used once the session is closed.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the operation.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the session is revoked, otherwise returns an error.
+pub fn revoke_session_handler(ctx: Context<RevokeSession>) -> Result<()> {
+    // The account closure happens automatically due to the `close = owner` constraint in the `Accounts` struct.
+
+    emit_cpi!(SessionRevokedEvent {
+        owner: ctx.accounts.owner.key(),
+        session_key: ctx.accounts.session.session_key,
+        session: ctx.accounts.session.key(),
+        notional_used: ctx.accounts.session.notional_used,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AiDexConfig, AiDexConfigExtension, AiDexPool, ConfigRole, config_role_authority};
+
+#[event]
+pub struct AntiSandwichSetEvent {
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub pause_authority: Pubkey,
+    pub enabled: bool,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetAntiSandwich<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::Pause)
+    )]
+    pub pause_authority: Signer<'info>,
+
+    /// The extension of the config, if any, holding the delegated pause authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+}
+
+/// Enables or disables the anti-sandwich protection of an AI DEX pool.
+///
+/// While enabled, the pool records the direction in which each authority swapped within the
+/// current slot, and a swap by the same authority in the opposite direction within the slot fails
+/// with `SandwichSwapError`, even if other authorities swapped in between. This rules out simple
+/// sandwiches from a single wallet. Up to `MAX_SLOT_SWAP_AUTHORITIES` authorities are tracked per
+/// slot, the oldest one being replaced beyond that.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the protection.
+/// * `enabled` - Whether the anti-sandwich protection is enabled.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the protection is successfully set,
+/// or an `Err` if an error occurs.
+pub fn set_anti_sandwich_handler(ctx: Context<SetAntiSandwich>, enabled: bool) -> Result<()> {
+    ctx.accounts.ai_dex_pool.load_mut()?.set_anti_sandwich_enabled(enabled);
+
+    emit_cpi!(AntiSandwichSetEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        pause_authority: ctx.accounts.pause_authority.key(),
+        enabled,
+    });
+
+    Ok(())
+}
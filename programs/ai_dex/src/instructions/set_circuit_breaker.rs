@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AiDexConfig, AiDexConfigExtension, AiDexPool, ConfigRole, config_role_authority};
+
+#[event]
+pub struct CircuitBreakerSetEvent {
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub pause_authority: Pubkey,
+    pub max_tick_move: u32,
+    pub window_seconds: u32,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetCircuitBreaker<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::Pause)
+    )]
+    pub pause_authority: Signer<'info>,
+
+    /// The extension of the config, if any, holding the delegated pause authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+}
+
+/// Sets the circuit breaker of an AI DEX pool.
+///
+/// Once set, a swap moving the price more than `max_tick_move` ticks away from the tick index at
+/// the start of the current window of `window_seconds` fails with `CircuitBreakerTrippedError`.
+/// This bounds how far the price can be pushed within a single block, protecting the liquidity
+/// providers against price manipulation.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the circuit breaker.
+/// * `max_tick_move` - The maximum number of ticks the price can move within a window, zero to disable.
+/// * `window_seconds` - The length of a window in seconds.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the circuit breaker is successfully set,
+/// or an `Err` if an error occurs.
+///
+/// # Errors
+///
+/// * `InvalidCircuitBreakerError` - If the circuit breaker is enabled with an empty window.
+pub fn set_circuit_breaker_handler(
+    ctx: Context<SetCircuitBreaker>,
+    max_tick_move: u32,
+    window_seconds: u32,
+) -> Result<()> {
+    ctx.accounts
+        .ai_dex_pool
+        .load_mut()?
+        .set_circuit_breaker(max_tick_move, window_seconds)?;
+
+    emit_cpi!(CircuitBreakerSetEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        pause_authority: ctx.accounts.pause_authority.key(),
+        max_tick_move,
+        window_seconds,
+    });
+
+    Ok(())
+}
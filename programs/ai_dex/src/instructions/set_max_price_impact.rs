@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AiDexConfig, AiDexConfigExtension, AiDexPool, ConfigRole, config_role_authority};
+
+#[event]
+pub struct MaxPriceImpactSetEvent {
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub pause_authority: Pubkey,
+    pub max_price_impact_bps: u16,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetMaxPriceImpact<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::Pause)
+    )]
+    pub pause_authority: Signer<'info>,
+
+    /// The extension of the config, if any, holding the delegated pause authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+}
+
+/// Sets the maximum price impact of a single swap in an AI DEX pool.
+///
+/// A swap moving the price by more than `max_price_impact_bps` fails with
+/// `PriceImpactExceededError`, so large orders have to be split, e.g. through a TWAP order.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the price impact cap.
+/// * `max_price_impact_bps` - The maximum price impact in basis points, zero to disable the cap.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the price impact cap is successfully set,
+/// or an `Err` if an error occurs.
+pub fn set_max_price_impact_handler(
+    ctx: Context<SetMaxPriceImpact>,
+    max_price_impact_bps: u16,
+) -> Result<()> {
+    ctx.accounts
+        .ai_dex_pool
+        .load_mut()?
+        .set_max_price_impact_bps(max_price_impact_bps);
+
+    emit_cpi!(MaxPriceImpactSetEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        pause_authority: ctx.accounts.pause_authority.key(),
+        max_price_impact_bps,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AiDexConfig, AiDexConfigExtension, AiDexPool, ConfigRole, config_role_authority};
+
+#[event]
+pub struct MaxSlotVolumeSetEvent {
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub pause_authority: Pubkey,
+    pub max_slot_volume_bps: u16,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetMaxSlotVolume<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::Pause)
+    )]
+    pub pause_authority: Signer<'info>,
+
+    /// The extension of the config, if any, holding the delegated pause authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+}
+
+/// Sets the maximum share of a vault balance that swaps can take out of an AI DEX pool within a slot.
+///
+/// Once the swaps of a slot have taken more than `max_slot_volume_bps` of the balance a vault held
+/// at the start of the slot, further swaps out of that vault fail with `SlotVolumeExceededError`
+/// until the next slot, which bounds flash-loan style manipulation of thin pools.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the slot volume cap.
+/// * `max_slot_volume_bps` - The maximum share in basis points, zero to disable the cap.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the slot volume cap is successfully set,
+/// or an `Err` if an error occurs.
+pub fn set_max_slot_volume_handler(
+    ctx: Context<SetMaxSlotVolume>,
+    max_slot_volume_bps: u16,
+) -> Result<()> {
+    ctx.accounts
+        .ai_dex_pool
+        .load_mut()?
+        .set_max_slot_volume_bps(max_slot_volume_bps);
+
+    emit_cpi!(MaxSlotVolumeSetEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        pause_authority: ctx.accounts.pause_authority.key(),
+        max_slot_volume_bps,
+    });
+
+    Ok(())
+}
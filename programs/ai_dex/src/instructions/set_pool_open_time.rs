@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AiDexConfig, AiDexConfigExtension, AiDexPool, ConfigRole, config_role_authority};
+use crate::util::to_timestamp_u64;
+
+#[event]
+pub struct PoolOpenTimeSetEvent {
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub pause_authority: Pubkey,
+    pub old_open_time: u64,
+    pub new_open_time: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPoolOpenTime<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::Pause)
+    )]
+    pub pause_authority: Signer<'info>,
+
+    /// The extension of the config, if any, holding the delegated pause authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+}
+
+/// Moves the open time of an AI DEX pool that has not opened yet.
+///
+/// Swaps fail with `PoolNotOpenError` until the open time, while liquidity can already be added,
+/// so projects can pre-create pools and seed liquidity ahead of a fair launch.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the open time.
+/// * `open_time` - The timestamp from which swaps are allowed.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the open time is successfully set,
+/// or an `Err` if an error occurs.
+///
+/// # Errors
+///
+/// * `ErrorCode::PoolAlreadyOpenError` - If the pool is already open.
+pub fn set_pool_open_time_handler(ctx: Context<SetPoolOpenTime>, open_time: u64) -> Result<()> {
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let mut ai_dex_pool = ctx.accounts.ai_dex_pool.load_mut()?;
+    let old_open_time = ai_dex_pool.open_time;
+    ai_dex_pool.set_open_time(open_time, timestamp)?;
+
+    emit_cpi!(PoolOpenTimeSetEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        pause_authority: ctx.accounts.pause_authority.key(),
+        old_open_time,
+        new_open_time: open_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AiDexConfig, AiDexConfigExtension, AiDexPool, ConfigRole, config_role_authority};
+
+#[event]
+pub struct PoolPauseSetEvent {
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub pause_authority: Pubkey,
+    pub paused: bool,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPoolPause<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::Pause)
+    )]
+    pub pause_authority: Signer<'info>,
+
+    /// The extension of the config, if any, holding the delegated pause authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+}
+
+/// Pauses or unpauses an AI DEX pool.
+///
+/// While the pool is paused, swaps and liquidity increases fail with `PoolPausedError`.
+/// Decreasing liquidity and collecting fees and rewards remain allowed, so the liquidity
+/// providers can always exit the pool.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for pausing the pool.
+/// * `paused` - Whether the pool is paused.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the pause is successfully set,
+/// or an `Err` if an error occurs.
+pub fn set_pool_pause_handler(ctx: Context<SetPoolPause>, paused: bool) -> Result<()> {
+    ctx.accounts.ai_dex_pool.load_mut()?.set_paused(paused);
+
+    emit_cpi!(PoolPauseSetEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        pause_authority: ctx.accounts.pause_authority.key(),
+        paused,
+    });
+
+    Ok(())
+}
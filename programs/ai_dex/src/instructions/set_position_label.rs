@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::state::*;
+use crate::util::verify_position_authority;
+
+#[event]
+pub struct PositionLabelSetEvent {
+    pub position_authority: Pubkey,
+    pub position: Pubkey,
+    pub old_label: [u8; 32],
+    pub new_label: [u8; 32],
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPositionLabel<'info> {
+    pub position_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub position: Account<'info, Position>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+}
+
+/// Sets the label of a position.
+///
+/// The label is a free-form tag, such as "grid-7" or "hedge", letting bots and vaults find their
+/// positions without an external database. It is not interpreted by the program.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the label.
+/// * `label` - The new label, zero-padded, or all zeros to remove the label.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the label is set, otherwise returns an error.
+///
+/// # Errors
+///
+/// * Any error from verifying the position authority.
+pub fn set_position_label_handler(ctx: Context<SetPositionLabel>, label: [u8; 32]) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    let old_label = ctx.accounts.position.label;
+    ctx.accounts.position.set_label(label);
+
+    emit_cpi!(PositionLabelSetEvent {
+        position_authority: ctx.accounts.position_authority.key(),
+        position: ctx.accounts.position.key(),
+        old_label,
+        new_label: label,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::state::*;
+use crate::util::verify_position_authority;
+
+#[event]
+pub struct PositionOperatorUpdatedEvent {
+    pub position_authority: Pubkey,
+    pub position: Pubkey,
+    pub old_operator: Pubkey,
+    pub new_operator: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPositionOperator<'info> {
+    pub position_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub position: Account<'info, Position>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: the account that will be the new operator can be arbitrary
+    pub new_operator: UncheckedAccount<'info>,
+}
+
+/// Sets the operator of a position.
+///
+/// The operator can increase and decrease the liquidity of the position, collect its fees and
+/// rebalance it without holding the position NFT. Tokens withdrawn by the operator can only be
+/// sent to accounts of the position owner. Passing the default pubkey removes the operator.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the operator.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the operator is set, otherwise returns an error.
+///
+/// # Errors
+///
+/// * Any error from verifying the position authority.
+pub fn set_position_operator_handler(ctx: Context<SetPositionOperator>) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    let old_operator = ctx.accounts.position.operator;
+    ctx.accounts.position.set_operator(ctx.accounts.new_operator.key());
+
+    emit_cpi!(PositionOperatorUpdatedEvent {
+        position_authority: ctx.accounts.position_authority.key(),
+        position: ctx.accounts.position.key(),
+        old_operator,
+        new_operator: ctx.accounts.new_operator.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::state::AiDexConfig;
+
+#[event]
+pub struct ProtocolPauseSetEvent {
+    pub ai_dex_config: Pubkey,
+    pub emergency_authority: Pubkey,
+    pub paused: bool,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetProtocolPause<'info> {
+    #[account(mut)]
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(address = ai_dex_config.emergency_authority)]
+    pub emergency_authority: Signer<'info>,
+}
+
+/// Pauses or unpauses all the pools of an AI DEX configuration at once.
+///
+/// While the protocol is paused, the swaps and the liquidity and position instructions of every
+/// pool of the config fail with `ProtocolPausedError`, without any per-pool operation. Only the
+/// emergency authority has permission to invoke this instruction.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for pausing the protocol.
+/// * `paused` - Whether the protocol is paused.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the pause is successfully set,
+/// or an `Err` if an error occurs.
+pub fn set_protocol_pause_handler(ctx: Context<SetProtocolPause>, paused: bool) -> Result<()> {
+    ctx.accounts.ai_dex_config.set_paused(paused);
+
+    emit_cpi!(ProtocolPauseSetEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        emergency_authority: ctx.accounts.emergency_authority.key(),
+        paused,
+    });
+
+    Ok(())
+}
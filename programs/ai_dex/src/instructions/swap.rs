@@ -3,11 +3,16 @@ use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use anchor_spl::memo::Memo;
 
 use crate::util::{calculate_transfer_fee_excluded_amount, calculate_transfer_fee_included_amount, parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
+use crate::util::{is_native_mint, parse_native_sol_system_program, unwrap_pool_native_sol, wrap_native_sol};
 use crate::{
     errors::ErrorCode,
     orchestrator::swap_orchestrator::*,
-    state::{TickArray, AiDexPool},
-    util::{to_timestamp_u64, update_and_swap_ai_dex, SwapTickSequence},
+    state::{AiDexConfig, AiDexPool, FeeDiscountRegistry, PoolAllowlist, PoolAllowlistKind, ReferralFees, Session, SwapHookSummary},
+    util::{
+        apply_tick_array_bitmap, check_deadline, invoke_swap_hook, load_supplemental_tick_arrays,
+        oracle_twap_sqrt_price_limit, oracle_volatility_ticks, record_oracle_observation, record_pool_stats, to_timestamp_u64, update_and_swap_ai_dex,
+        update_and_swap_ai_dex_from_delegate, verify_pool_allowlist, SwapTickSequence, TickArrayAccount,
+    },
     constants::transfer_memo,
 };
 
@@ -20,6 +25,9 @@ pub struct SwapExecutedEvent {
     pub sqrt_price_limit: u128,
     pub amount_specified_is_input: bool,
     pub a_to_b: bool,
+    pub allow_partial_fill: bool,
+    pub amount_filled: u64,
+    pub amount_unfilled: u64,
     pub token_mint_a: Pubkey,
     pub token_mint_b: Pubkey,
     pub token_owner_account_a: Pubkey,
@@ -32,8 +40,24 @@ pub struct SwapExecutedEvent {
     pub timestamp: u64,
     pub token_program_a: Pubkey,
     pub token_program_b: Pubkey,
+    pub referrer: Option<Pubkey>,
+    pub referral_fee: u64,
+    pub fee_discount_bps: u16,
+    pub fee_rate: u16,
+    pub input_amount: u64,
+    pub output_amount: u64,
+    pub lp_fee: u64,
+    pub protocol_fee: u64,
+    pub input_transfer_fee: u64,
+    pub output_transfer_fee: u64,
+    pub pre_sqrt_price: u128,
+    pub post_sqrt_price: u128,
+    pub post_tick_index: i32,
+    pub session: Option<Pubkey>,
+    pub twap_window_seconds: Option<u32>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Swap<'info> {
     /// The token program for token mint A
@@ -50,49 +74,95 @@ pub struct Swap<'info> {
     /// The authority that signs transactions
     pub token_authority: Signer<'info>,
 
-    /// The AI DEX account, which is mutable
+    /// The AI DEX account, which is mutable and loaded as a zero-copy account
     #[account(mut)]
-    pub ai_dex_pool: Box<Account<'info, AiDexPool>>,
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
 
     /// The token mint A account
-    #[account(address = ai_dex_pool.token_mint_a)]
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
     pub token_mint_a: InterfaceAccount<'info, Mint>,
     
     /// The token mint B account
-    #[account(address = ai_dex_pool.token_mint_b)]
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
     pub token_mint_b: InterfaceAccount<'info, Mint>,
     
     /// The token owner account for token mint A, which is mutable and must match the mint of token mint A
-    #[account(mut, constraint = token_owner_account_a.mint == ai_dex_pool.token_mint_a)]
+    #[account(mut, constraint = token_owner_account_a.mint == ai_dex_pool.load()?.token_mint_a)]
     pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
     
     /// The token vault account for token mint A, which is mutable and must match the address in the AI DEX
-    #[account(mut, address = ai_dex_pool.token_vault_a)]
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_a)]
     pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The token owner account for token mint B, which is mutable and must match the mint of token mint B
-    #[account(mut, constraint = token_owner_account_b.mint == ai_dex_pool.token_mint_b)]
+    #[account(mut, constraint = token_owner_account_b.mint == ai_dex_pool.load()?.token_mint_b)]
     pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
     
     /// The token vault account for token mint B, which is mutable and must match the address in the AI DEX
-    #[account(mut, address = ai_dex_pool.token_vault_b)]
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_b)]
     pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// The first tick array, which is mutable and must be associated with the AI DEX
-    #[account(mut, has_one = ai_dex_pool)]
-    pub tick_array_0: AccountLoader<'info, TickArray>,
+    /// CHECK: The first tick array, fixed or dynamic, which is mutable and must be associated with the AI DEX. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_0: UncheckedAccount<'info>,
 
-    /// The second tick array, which is mutable and must be associated with the AI DEX
-    #[account(mut, has_one = ai_dex_pool)]
-    pub tick_array_1: AccountLoader<'info, TickArray>,
+    /// CHECK: The second tick array, fixed or dynamic, which is mutable and must be associated with the AI DEX. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_1: UncheckedAccount<'info>,
 
-    /// The third tick array, which is mutable and must be associated with the AI DEX
-    #[account(mut, has_one = ai_dex_pool)]
-    pub tick_array_2: AccountLoader<'info, TickArray>,
+    /// CHECK: The third tick array, fixed or dynamic, which is mutable and must be associated with the AI DEX. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_2: UncheckedAccount<'info>,
 
-    /// CHECK: The oracle account, which is mutable and currently unused
+    /// CHECK: The oracle account of the pool. An observation is recorded only if it has been initialized.
     #[account(mut, seeds = [b"oracle", ai_dex_pool.key().as_ref()], bump)]
     pub oracle: UncheckedAccount<'info>,
+
+    /// CHECK: The statistics account of the pool. The swap is recorded only if it has been initialized.
+    #[account(mut, seeds = [b"pool_stats", ai_dex_pool.key().as_ref()], bump)]
+    pub pool_stats: UncheckedAccount<'info>,
+
+    /// CHECK: The tick array bitmap of the pool. Used only if it has been initialized.
+    #[account(seeds = [b"tick_array_bitmap", ai_dex_pool.key().as_ref()], bump)]
+    pub tick_array_bitmap: UncheckedAccount<'info>,
+
+    /// The config of the pool, checked against the protocol-wide emergency pause
+    #[account(
+        address = ai_dex_pool.load()?.ai_dex_config,
+        constraint = !ai_dex_config.paused @ ErrorCode::ProtocolPausedError
+    )]
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    /// The swap allowlist of the pool, required while it is enabled
+    #[account(seeds = [b"swap_allowlist", ai_dex_pool.key().as_ref()], bump)]
+    pub swap_allowlist: Option<Box<Account<'info, PoolAllowlist>>>,
+
+    /// The token account of the token authority holding a gating token of the swap allowlist, if any
+    pub swap_gating_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The referral fees of the referrer of the swap, if any, accruing the referral fee
+    #[account(
+        mut,
+        has_one = ai_dex_pool,
+        constraint = referral_fees.referrer != token_authority.key() @ ErrorCode::SelfReferralError
+    )]
+    pub referral_fees: Option<Box<Account<'info, ReferralFees>>>,
+
+    /// The fee discount registry of the config, if any, consulted with `fee_discount_token_account`
+    #[account(seeds = [b"fee_discount_registry", ai_dex_config.key().as_ref()], bump)]
+    pub fee_discount_registry: Option<Box<Account<'info, FeeDiscountRegistry>>>,
+
+    /// The token account of the token authority qualifying for a fee discount, if any
+    #[account(constraint = fee_discount_token_account.owner == token_authority.key())]
+    pub fee_discount_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: The swap hook program of the pool, required if the pool has one
+    #[account(address = ai_dex_pool.load()?.swap_hook_program)]
+    pub swap_hook_program: Option<UncheckedAccount<'info>>,
+
+    /// The session through which the token authority swaps on behalf of its owner, if any
+    #[account(mut, constraint = session.session_key == token_authority.key() @ ErrorCode::InvalidSessionError)]
+    pub session: Option<Box<Account<'info, Session>>>,
 }
 
 pub fn swap_handler<'a, 'b, 'c, 'info>(
@@ -102,11 +172,35 @@ pub fn swap_handler<'a, 'b, 'c, 'info>(
     sqrt_price_limit: u128,
     amount_specified_is_input: bool,
     a_to_b: bool, // Zero for one
+    allow_partial_fill: bool,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
+    deadline_timestamp: Option<i64>,
+    twap_window_seconds: Option<u32>,
 ) -> Result<()> {
-    let ai_dex = &mut ctx.accounts.ai_dex_pool;
+    let ai_dex = &ctx.accounts.ai_dex_pool;
+    let clock = Clock::get()?;
+    check_deadline(deadline_timestamp, clock.unix_timestamp)?;
+    // A session key swaps on behalf of the owner of the session, on the token accounts of the owner
+    let swap_authority = match &ctx.accounts.session {
+        Some(session) => {
+            if ctx.accounts.token_owner_account_a.owner != session.owner
+                || ctx.accounts.token_owner_account_b.owner != session.owner
+            {
+                return Err(ErrorCode::InvalidSessionError.into());
+            }
+            session.owner
+        }
+        None => ctx.accounts.token_authority.key(),
+    };
+    verify_pool_allowlist(
+        &*ai_dex.load()?,
+        PoolAllowlistKind::Swap,
+        ctx.accounts.swap_allowlist.as_deref(),
+        &swap_authority,
+        ctx.accounts.swap_gating_token_account.as_deref(),
+    )?;
     // Update the global reward growth which increases as a function of time.
-    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
 
     // Process remaining accounts
     let remaining_accounts = parse_remaining_accounts(
@@ -115,26 +209,116 @@ pub fn swap_handler<'a, 'b, 'c, 'info>(
         &[
             AccountsType::TransferHookA,
             AccountsType::TransferHookB,
+            AccountsType::SupplementalTickArrays,
+            AccountsType::NativeSol,
+            AccountsType::SwapHookAccounts,
         ],
     )?;
+    let native_sol_system_program = parse_native_sol_system_program(
+        &remaining_accounts.native_sol,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_mint_b,
+    )?;
+    // The lamports of the session key cannot pay for the owner of the session
+    if native_sol_system_program.is_some() && ctx.accounts.session.is_some() {
+        return Err(ErrorCode::InvalidSessionError.into());
+    }
 
-    let mut swap_tick_sequence = SwapTickSequence::new(
-        ctx.accounts.tick_array_0.load_mut().unwrap(),
-        ctx.accounts.tick_array_1.load_mut().ok(),
-        ctx.accounts.tick_array_2.load_mut().ok(),
+    let tick_array_0 = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_0, &ai_dex.key())?;
+    let tick_array_1 = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_1, &ai_dex.key())?;
+    let tick_array_2 = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_2, &ai_dex.key())?;
+    let supplemental_tick_arrays = load_supplemental_tick_arrays(
+        &remaining_accounts.supplemental_tick_arrays,
+        &ai_dex.key(),
+    )?;
+
+    let mut swap_tick_sequence = SwapTickSequence::new_with_tick_arrays(
+        tick_array_0.load_mut()?,
+        tick_array_1.load_mut().ok(),
+        tick_array_2.load_mut().ok(),
     );
+    // Supplemental tick arrays continue the sequence past the three context tick arrays
+    for tick_array in supplemental_tick_arrays.iter() {
+        swap_tick_sequence.push_tick_array(tick_array.load_mut()?);
+    }
+    apply_tick_array_bitmap(
+        &ctx.accounts.tick_array_bitmap,
+        &mut swap_tick_sequence,
+        ai_dex.load()?.tick_spacing,
+        a_to_b,
+    )?;
 
+    // Without referrer, the whole swap fee goes to the liquidity providers and the protocol
+    let referral_fee_rate = if ctx.accounts.referral_fees.is_some() {
+        ctx.accounts.ai_dex_config.referral_fee_rate
+    } else {
+        0
+    };
+    // Holders of a token of a tier of the fee discount registry swap at a discounted fee rate
+    let fee_discount_bps = match (&ctx.accounts.fee_discount_registry, &ctx.accounts.fee_discount_token_account) {
+        (Some(registry), Some(token_account)) => registry.discount_bps(&token_account.mint, token_account.amount),
+        _ => 0,
+    };
+    let volatility_ticks = oracle_volatility_ticks(&ctx.accounts.oracle, &*ai_dex.load()?, timestamp)?;
+    // With a TWAP window, the sqrt price limit is a maximum deviation in bps from the TWAP of the pool
+    let resolved_sqrt_price_limit = match twap_window_seconds {
+        Some(window_seconds) => oracle_twap_sqrt_price_limit(
+            &ctx.accounts.oracle,
+            &*ai_dex.load()?,
+            timestamp,
+            window_seconds,
+            sqrt_price_limit,
+            a_to_b,
+        )?,
+        None => sqrt_price_limit,
+    };
+    let pre_sqrt_price = ai_dex.load()?.sqrt_price;
     let swap_update = swap_with_transfer_fee_extension(
-        &ai_dex,
+        &*ai_dex.load()?,
         &ctx.accounts.token_mint_a,
         &ctx.accounts.token_mint_b,
         &mut swap_tick_sequence,
         amount,
-        sqrt_price_limit,
+        resolved_sqrt_price_limit,
         amount_specified_is_input,
         a_to_b,
         timestamp,
+        referral_fee_rate,
+        fee_discount_bps,
+        volatility_ticks,
     )?;
+    let referral_fee = swap_update.next_referral_fee;
+    let fee_rate = swap_update.fee_rate;
+    let lp_fee = swap_update.lp_fee();
+    let protocol_fee = swap_update.next_protocol_fee;
+    let post_sqrt_price = swap_update.next_sqrt_price;
+    let post_tick_index = swap_update.next_tick_index;
+
+    // The input amount is paid by the owner and the output amount received by the owner, net of
+    // the transfer fees of their mints
+    let (input_mint, output_mint, input_amount, vault_output_amount) = if a_to_b {
+        (&ctx.accounts.token_mint_a, &ctx.accounts.token_mint_b, swap_update.amount_a, swap_update.amount_b)
+    } else {
+        (&ctx.accounts.token_mint_b, &ctx.accounts.token_mint_a, swap_update.amount_b, swap_update.amount_a)
+    };
+    let input_transfer_fee = calculate_transfer_fee_excluded_amount(input_mint, input_amount)?.transfer_fee;
+    let output = calculate_transfer_fee_excluded_amount(output_mint, vault_output_amount)?;
+
+    // The swap stops early if the sqrt_price_limit is reached before the specified amount is filled.
+    let (amount_filled, amount_unfilled) = if amount_specified_is_input {
+        let input_amount = if a_to_b { swap_update.amount_a } else { swap_update.amount_b };
+        (input_amount, amount.saturating_sub(input_amount))
+    } else {
+        let output_amount = if a_to_b {
+            calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_b, swap_update.amount_b)?.amount
+        } else {
+            calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_a, swap_update.amount_a)?.amount
+        };
+        (output_amount, amount.saturating_sub(output_amount))
+    };
+    if amount_unfilled > 0 && !allow_partial_fill {
+        return Err(ErrorCode::PartialFillError.into());
+    }
 
     if amount_specified_is_input {
         let transfer_fee_excluded_output_amount = if a_to_b {
@@ -162,27 +346,128 @@ pub fn swap_handler<'a, 'b, 'c, 'info>(
         }
     }
 
-    update_and_swap_ai_dex(
-        ai_dex,
-        &ctx.accounts.token_authority,
-        &ctx.accounts.token_mint_a,
-        &ctx.accounts.token_mint_b,
-        &ctx.accounts.token_owner_account_a,
-        &ctx.accounts.token_owner_account_b,
-        &ctx.accounts.token_vault_a,
-        &ctx.accounts.token_vault_b,
-        &remaining_accounts.transfer_hook_a,
-        &remaining_accounts.transfer_hook_b,
-        &ctx.accounts.token_program_a,
-        &ctx.accounts.token_program_b,
-        &ctx.accounts.memo_program,
-        swap_update,
-        a_to_b,
-        timestamp,
-        transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
-    )?;
+    if let Some(session) = ctx.accounts.session.as_mut() {
+        session.record_swap(
+            &ai_dex.key(),
+            &ctx.accounts.token_mint_a.key(),
+            &ctx.accounts.token_mint_b.key(),
+            swap_update.amount_a,
+            swap_update.amount_b,
+            clock.unix_timestamp,
+        )?;
+    }
 
-    emit!(SwapExecutedEvent {
+    record_oracle_observation(&ctx.accounts.oracle, &*ai_dex.load()?, timestamp)?;
+    record_pool_stats(&ctx.accounts.pool_stats, &swap_update, a_to_b, timestamp)?;
+
+    // Pay the wSOL input in lamports
+    if let Some(system_program) = &native_sol_system_program {
+        let (input_mint, input_owner_account, input_token_program, input_amount) = if a_to_b {
+            (&ctx.accounts.token_mint_a, &ctx.accounts.token_owner_account_a, &ctx.accounts.token_program_a, swap_update.amount_a)
+        } else {
+            (&ctx.accounts.token_mint_b, &ctx.accounts.token_owner_account_b, &ctx.accounts.token_program_b, swap_update.amount_b)
+        };
+        if is_native_mint(&input_mint.key()) {
+            wrap_native_sol(
+                &ctx.accounts.token_authority,
+                input_owner_account,
+                input_token_program,
+                system_program,
+                input_amount,
+            )?;
+        }
+    }
+
+    match &ctx.accounts.session {
+        // The session account signs as the delegate of the input token account of its owner
+        Some(session) => update_and_swap_ai_dex_from_delegate(
+            ai_dex,
+            &session.owner,
+            &session.to_account_info(),
+            &session.seeds(),
+            &ctx.accounts.token_mint_a,
+            &ctx.accounts.token_mint_b,
+            &ctx.accounts.token_owner_account_a,
+            &ctx.accounts.token_owner_account_b,
+            &ctx.accounts.token_vault_a,
+            &ctx.accounts.token_vault_b,
+            &remaining_accounts.transfer_hook_a,
+            &remaining_accounts.transfer_hook_b,
+            &ctx.accounts.token_program_a,
+            &ctx.accounts.token_program_b,
+            &ctx.accounts.memo_program,
+            &swap_update,
+            a_to_b,
+            timestamp,
+            transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
+        )?,
+        None => update_and_swap_ai_dex(
+            ai_dex,
+            &ctx.accounts.token_authority,
+            &ctx.accounts.token_mint_a,
+            &ctx.accounts.token_mint_b,
+            &ctx.accounts.token_owner_account_a,
+            &ctx.accounts.token_owner_account_b,
+            &ctx.accounts.token_vault_a,
+            &ctx.accounts.token_vault_b,
+            &remaining_accounts.transfer_hook_a,
+            &remaining_accounts.transfer_hook_b,
+            &ctx.accounts.token_program_a,
+            &ctx.accounts.token_program_b,
+            &ctx.accounts.memo_program,
+            swap_update,
+            a_to_b,
+            timestamp,
+            transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
+        )?,
+    }
+
+    if let Some(referral_fees) = ctx.accounts.referral_fees.as_mut() {
+        ai_dex.load_mut()?.accrue_referral_fee(referral_fee, a_to_b)?;
+        referral_fees.accrue(referral_fee, a_to_b);
+    }
+
+    // Unwrap the wSOL side, input leftover or output, back to SOL
+    if native_sol_system_program.is_some() {
+        unwrap_pool_native_sol(
+            &ctx.accounts.token_authority,
+            &ctx.accounts.token_mint_a,
+            &ctx.accounts.token_owner_account_a,
+            &ctx.accounts.token_program_a,
+            &ctx.accounts.token_mint_b,
+            &ctx.accounts.token_owner_account_b,
+            &ctx.accounts.token_program_b,
+        )?;
+    }
+
+    if ai_dex.load()?.swap_hook_program().is_some() {
+        let swap_hook_program = ctx
+            .accounts
+            .swap_hook_program
+            .as_ref()
+            .ok_or(ErrorCode::SwapHookProgramMissingError)?;
+        invoke_swap_hook(
+            swap_hook_program,
+            &ai_dex.to_account_info(),
+            &remaining_accounts.swap_hook_accounts,
+            &SwapHookSummary {
+                ai_dex_pool: ai_dex.key(),
+                token_authority: swap_authority,
+                a_to_b,
+                input_amount,
+                output_amount: output.amount,
+                fee_rate,
+                lp_fee,
+                protocol_fee,
+                pre_sqrt_price,
+                post_sqrt_price,
+                post_tick_index,
+                timestamp,
+            },
+        )?;
+    }
+
+    emit_cpi!(SwapExecutedEvent {
         token_authority: ctx.accounts.token_authority.key(),
         ai_dex_pool: ai_dex.key(),
         amount,
@@ -190,6 +475,9 @@ pub fn swap_handler<'a, 'b, 'c, 'info>(
         sqrt_price_limit,
         amount_specified_is_input,
         a_to_b,
+        allow_partial_fill,
+        amount_filled,
+        amount_unfilled,
         token_mint_a: ctx.accounts.token_mint_a.key(),
         token_mint_b: ctx.accounts.token_mint_b.key(),
         token_owner_account_a: ctx.accounts.token_owner_account_a.key(),
@@ -202,6 +490,21 @@ pub fn swap_handler<'a, 'b, 'c, 'info>(
         timestamp,
         token_program_a: ctx.accounts.token_program_a.key(),
         token_program_b: ctx.accounts.token_program_b.key(),
+        referrer: ctx.accounts.referral_fees.as_ref().map(|referral_fees| referral_fees.referrer),
+        referral_fee,
+        fee_discount_bps,
+        fee_rate,
+        input_amount,
+        output_amount: output.amount,
+        lp_fee,
+        protocol_fee,
+        input_transfer_fee,
+        output_transfer_fee: output.transfer_fee,
+        pre_sqrt_price,
+        post_sqrt_price,
+        post_tick_index,
+        session: ctx.accounts.session.as_ref().map(|session| session.key()),
+        twap_window_seconds,
     });
     
     Ok(())
@@ -215,10 +518,13 @@ pub fn swap_handler<'a, 'b, 'c, 'info>(
 /// - `token_mint_b`: Interface account for the second token mint.
 /// - `swap_tick_sequence`: Mutable reference to the swap tick sequence.
 /// - `amount`: The amount to be swapped.
-/// - `sqrt_price_limit`: The square root price limit for the swap.
+/// - `sqrt_price_limit`: The square root price limit for the swap. `0` means no limit in the swap direction.
 /// - `amount_specified_is_input`: Boolean indicating if the specified amount is input.
 /// - `a_to_b`: Boolean indicating the direction of the swap (true for A to B, false for B to A).
 /// - `timestamp`: The timestamp of the swap.
+/// - `referral_fee_rate`: The share of the swap fee owed to the referrer of the swap, zero without referrer.
+/// - `fee_discount_bps`: The discount off the fee rate granted to the token authority, in basis points.
+/// - `volatility_ticks`: The volatility measured by the oracle of the pool, for the adaptive fee.
 ///
 /// # Returns
 /// - `Result<PostSwapUpdate>`: The result containing the post-swap update or an error.
@@ -232,6 +538,9 @@ pub fn swap_with_transfer_fee_extension<'info>(
     amount_specified_is_input: bool,
     a_to_b: bool,
     timestamp: u64,
+    referral_fee_rate: u16,
+    fee_discount_bps: u16,
+    volatility_ticks: u32,
 ) -> Result<PostSwapUpdate> {
     let (input_token_mint, output_token_mint) = if a_to_b {
         (token_mint_a, token_mint_b)
@@ -251,10 +560,13 @@ pub fn swap_with_transfer_fee_extension<'info>(
         ai_dex,
         swap_tick_sequence,
         transfer_fee_excluded_amount,
-        sqrt_price_limit,
+        resolve_sqrt_price_limit(ai_dex, sqrt_price_limit, a_to_b),
         amount_specified_is_input,
         a_to_b,
         timestamp,
+        referral_fee_rate,
+        fee_discount_bps,
+        volatility_ticks,
     )?;
 
     let (swap_update_amount_input, swap_update_amount_output) = if a_to_b {
@@ -288,5 +600,8 @@ pub fn swap_with_transfer_fee_extension<'info>(
         next_fee_growth_global: swap_update.next_fee_growth_global,
         next_reward_infos: swap_update.next_reward_infos,
         next_protocol_fee: swap_update.next_protocol_fee,
+        next_referral_fee: swap_update.next_referral_fee,
+        fee_rate: swap_update.fee_rate,
+        fee_amount: swap_update.fee_amount,
     })
 }
\ No newline at end of file
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[event]
+pub struct SwapHookDeletedEvent {
+    pub ai_dex_config: Pubkey,
+    pub config_authority: Pubkey,
+    pub swap_hook: Pubkey,
+    pub hook_program: Pubkey,
+    pub rent_receiver: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DeleteSwapHook<'info> {
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+
+    #[account(mut, has_one = ai_dex_config, close = rent_receiver)]
+    pub swap_hook: Account<'info, SwapHook>,
+
+    /// CHECK: safe, for receiving reclaimed rent only; may be any account (e.g. a treasury)
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+}
+
+/// Removes a program from the swap hooks whitelist of the config.
+///
+/// The pools which registered the program keep calling it until their swap hook is removed
+/// with `set_pool_swap_hook`, but no pool can register it anymore.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the operation.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the program is removed, otherwise returns an error.
+pub fn delete_swap_hook_handler(ctx: Context<DeleteSwapHook>) -> Result<()> {
+    // The account closure happens automatically due to the `close = rent_receiver` constraint in the `Accounts` struct.
+
+    emit_cpi!(SwapHookDeletedEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        swap_hook: ctx.accounts.swap_hook.key(),
+        hook_program: ctx.accounts.swap_hook.hook_program,
+        rent_receiver: ctx.accounts.rent_receiver.key(),
+    });
+
+    Ok(())
+}
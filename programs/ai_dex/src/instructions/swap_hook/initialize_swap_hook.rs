@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[event]
+pub struct SwapHookInitializedEvent {
+    pub ai_dex_config: Pubkey,
+    pub config_authority: Pubkey,
+    pub swap_hook: Pubkey,
+    pub hook_program: Pubkey,
+    pub funder: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeSwapHook<'info> {
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+
+    /// CHECK: the whitelisted program, required to be executable
+    #[account(executable)]
+    pub hook_program: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = funder,
+        seeds = [
+            b"swap_hook",
+            ai_dex_config.key().as_ref(),
+            hook_program.key().as_ref(),
+        ],
+        bump,
+        space = SwapHook::LEN
+    )]
+    pub swap_hook: Account<'info, SwapHook>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Whitelists a program as a swap hook the pools of the config can register.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts and programs required for the operation.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the program is whitelisted, otherwise returns an error.
+pub fn initialize_swap_hook_handler(ctx: Context<InitializeSwapHook>) -> Result<()> {
+    ctx.accounts.swap_hook.initialize(
+        ctx.accounts.ai_dex_config.key(),
+        ctx.accounts.hook_program.key(),
+    );
+
+    emit_cpi!(SwapHookInitializedEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        swap_hook: ctx.accounts.swap_hook.key(),
+        hook_program: ctx.accounts.hook_program.key(),
+        funder: ctx.accounts.funder.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,7 @@
+pub mod delete_swap_hook;
+pub mod initialize_swap_hook;
+pub mod set_pool_swap_hook;
+
+pub use delete_swap_hook::*;
+pub use initialize_swap_hook::*;
+pub use set_pool_swap_hook::*;
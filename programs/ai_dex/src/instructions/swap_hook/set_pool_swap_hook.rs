@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[event]
+pub struct PoolSwapHookSetEvent {
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub config_authority: Pubkey,
+    pub old_swap_hook_program: Pubkey,
+    pub new_swap_hook_program: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPoolSwapHook<'info> {
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+
+    /// The whitelist entry of the program to register, none to remove the swap hook of the pool
+    #[account(has_one = ai_dex_config)]
+    pub swap_hook: Option<Account<'info, SwapHook>>,
+}
+
+/// Registers a whitelisted program as the swap hook of a pool, or removes it.
+///
+/// The swap hook program is called by `swap` and `swap_with_permit` after each swap of the pool with a
+/// `SwapHookSummary`, and must then be passed along with the accounts it expects. The other instructions swapping
+/// through the pool, such as two-hop, multi-hop and flash swaps, zaps and order fills, reject it while a swap hook
+/// is set. Only the config authority can set it, as the hook program is invoked with the accounts of every swap of
+/// the pool.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the operation.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the swap hook is set, otherwise returns an error.
+pub fn set_pool_swap_hook_handler(ctx: Context<SetPoolSwapHook>) -> Result<()> {
+    let new_swap_hook_program = ctx
+        .accounts
+        .swap_hook
+        .as_ref()
+        .map_or(Pubkey::default(), |swap_hook| swap_hook.hook_program);
+
+    let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
+    let old_swap_hook_program = ai_dex.swap_hook_program;
+    ai_dex.set_swap_hook_program(new_swap_hook_program);
+    drop(ai_dex);
+
+    emit_cpi!(PoolSwapHookSetEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        old_swap_hook_program,
+        new_swap_hook_program,
+    });
+
+    Ok(())
+}
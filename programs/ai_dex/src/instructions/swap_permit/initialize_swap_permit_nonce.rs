@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[event]
+pub struct SwapPermitNonceInitializedEvent {
+    pub owner: Pubkey,
+    pub swap_permit_nonce: Pubkey,
+    pub funder: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeSwapPermitNonce<'info> {
+    /// CHECK: the owner of the permits, who does not need to sign since the nonce starts at zero
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = funder,
+        seeds = [b"swap_permit_nonce", owner.key().as_ref()],
+        bump,
+        space = SwapPermitNonce::LEN
+    )]
+    pub swap_permit_nonce: Account<'info, SwapPermitNonce>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Initializes the nonce of the swap permits of an owner.
+///
+/// The owner then approves the nonce account as the delegate of the token accounts its permit
+/// swaps are paid from, for the amount it is willing to trade through permits.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts and programs required for the operation.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the nonce is initialized, otherwise returns an error.
+pub fn initialize_swap_permit_nonce_handler(ctx: Context<InitializeSwapPermitNonce>) -> Result<()> {
+    ctx.accounts.swap_permit_nonce.initialize(
+        ctx.accounts.owner.key(),
+        ctx.bumps.swap_permit_nonce,
+    );
+
+    emit_cpi!(SwapPermitNonceInitializedEvent {
+        owner: ctx.accounts.owner.key(),
+        swap_permit_nonce: ctx.accounts.swap_permit_nonce.key(),
+        funder: ctx.accounts.funder.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,5 @@
+pub mod initialize_swap_permit_nonce;
+pub mod swap_with_permit;
+
+pub use initialize_swap_permit_nonce::*;
+pub use swap_with_permit::*;
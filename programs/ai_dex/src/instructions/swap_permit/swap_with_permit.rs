@@ -0,0 +1,339 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::util::{calculate_transfer_fee_excluded_amount, parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
+use crate::{
+    constants::transfer_memo,
+    errors::ErrorCode,
+    state::{AiDexConfig, AiDexPool, PoolAllowlist, PoolAllowlistKind, SwapHookSummary, SwapPermit, SwapPermitNonce},
+    swap_with_transfer_fee_extension,
+    util::{
+        apply_tick_array_bitmap, check_deadline, invoke_swap_hook, load_supplemental_tick_arrays,
+        oracle_volatility_ticks, record_oracle_observation, record_pool_stats, to_timestamp_u64,
+        update_and_swap_ai_dex_from_delegate, verify_ed25519_signature, verify_pool_allowlist,
+        SwapTickSequence, TickArrayAccount,
+    },
+};
+
+#[event]
+pub struct SwapWithPermitExecutedEvent {
+    pub relayer: Pubkey,
+    pub token_authority: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub nonce: u64,
+    pub amount: u64,
+    pub other_amount_threshold: u64,
+    pub sqrt_price_limit: u128,
+    pub amount_specified_is_input: bool,
+    pub a_to_b: bool,
+    pub fee_rate: u16,
+    pub input_amount: u64,
+    pub output_amount: u64,
+    pub lp_fee: u64,
+    pub protocol_fee: u64,
+    pub input_transfer_fee: u64,
+    pub output_transfer_fee: u64,
+    pub pre_sqrt_price: u128,
+    pub post_sqrt_price: u128,
+    pub post_tick_index: i32,
+    pub timestamp: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SwapWithPermit<'info> {
+    /// The token program for token mint A
+    #[account(address = token_mint_a.to_account_info().owner.clone())]
+    pub token_program_a: Interface<'info, TokenInterface>,
+
+    /// The token program for token mint B
+    #[account(address = token_mint_b.to_account_info().owner.clone())]
+    pub token_program_b: Interface<'info, TokenInterface>,
+
+    /// The memo program
+    pub memo_program: Program<'info, Memo>,
+
+    /// The relayer submitting the swap on behalf of the token authority
+    pub relayer: Signer<'info>,
+
+    /// CHECK: the authority signing the permit off-chain, checked against the ed25519 instruction of the transaction
+    pub token_authority: UncheckedAccount<'info>,
+
+    /// The nonce of the permits of the token authority, delegate of its input token account
+    #[account(
+        mut,
+        seeds = [b"swap_permit_nonce", token_authority.key().as_ref()],
+        bump = swap_permit_nonce.bump[0],
+    )]
+    pub swap_permit_nonce: Box<Account<'info, SwapPermitNonce>>,
+
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = token_owner_account_a.mint == ai_dex_pool.load()?.token_mint_a,
+        constraint = token_owner_account_a.owner == token_authority.key()
+    )]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = token_owner_account_b.mint == ai_dex_pool.load()?.token_mint_b,
+        constraint = token_owner_account_b.owner == token_authority.key()
+    )]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: The first tick array, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_0: UncheckedAccount<'info>,
+    /// CHECK: The second tick array, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_1: UncheckedAccount<'info>,
+    /// CHECK: The third tick array, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_2: UncheckedAccount<'info>,
+
+    /// CHECK: The oracle account of the pool. An observation is recorded only if it has been initialized.
+    #[account(mut, seeds = [b"oracle", ai_dex_pool.key().as_ref()], bump)]
+    pub oracle: UncheckedAccount<'info>,
+
+    /// CHECK: The statistics account of the pool. The swap is recorded only if it has been initialized.
+    #[account(mut, seeds = [b"pool_stats", ai_dex_pool.key().as_ref()], bump)]
+    pub pool_stats: UncheckedAccount<'info>,
+
+    /// CHECK: The tick array bitmap of the pool. Used only if it has been initialized.
+    #[account(seeds = [b"tick_array_bitmap", ai_dex_pool.key().as_ref()], bump)]
+    pub tick_array_bitmap: UncheckedAccount<'info>,
+
+    /// CHECK: The instructions sysvar, searched for the ed25519 instruction verifying the permit
+    #[account(address = solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    /// The config of the pool, checked against the protocol-wide emergency pause
+    #[account(
+        address = ai_dex_pool.load()?.ai_dex_config,
+        constraint = !ai_dex_config.paused @ ErrorCode::ProtocolPausedError
+    )]
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    /// The swap allowlist of the pool, required while it is enabled
+    #[account(seeds = [b"swap_allowlist", ai_dex_pool.key().as_ref()], bump)]
+    pub swap_allowlist: Option<Box<Account<'info, PoolAllowlist>>>,
+
+    /// The token account of the token authority holding a gating token of the swap allowlist, if any
+    pub swap_gating_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: The swap hook program of the pool, required if the pool has one
+    #[account(address = ai_dex_pool.load()?.swap_hook_program)]
+    pub swap_hook_program: Option<UncheckedAccount<'info>>,
+}
+
+/// Swaps tokens on behalf of a token authority that signed the swap parameters off-chain.
+///
+/// The token authority signs the message of the permit with its ed25519 key, and the relayer
+/// submits the swap in a transaction holding an ed25519 program instruction verifying that
+/// signature. The permit must carry the current nonce of the token authority, which is then
+/// incremented, so each permit can be used once. The input is paid from the input token account
+/// of the token authority through the delegation it approved to its nonce account, and the output
+/// is sent to its output token account. Referral fees, fee discounts, native SOL and input mints
+/// with a transfer hook are not supported.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the swap.
+/// * `permit` - The swap parameters signed by the token authority.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the swap is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidSwapPermitSignatureError` - If no ed25519 instruction verifies the permit signed by the token authority.
+/// * `ErrorCode::InvalidSwapPermitNonceError` - If the permit nonce is not the current nonce of the token authority.
+/// * `ErrorCode::AmountOutBelowMinimumError` - If the output is below `other_amount_threshold`.
+/// * `ErrorCode::AmountInAboveMaximumError` - If the input is above `other_amount_threshold`.
+pub fn swap_with_permit_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, SwapWithPermit<'info>>,
+    permit: SwapPermit,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let ai_dex = &ctx.accounts.ai_dex_pool;
+    let clock = Clock::get()?;
+    if permit.ai_dex_pool != ai_dex.key() {
+        return Err(ErrorCode::InvalidSwapPermitSignatureError.into());
+    }
+    verify_ed25519_signature(
+        &ctx.accounts.instructions_sysvar,
+        ctx.accounts.token_authority.key,
+        &permit.message()?,
+    )?;
+    ctx.accounts.swap_permit_nonce.use_nonce(permit.nonce)?;
+    check_deadline(Some(permit.deadline_timestamp), clock.unix_timestamp)?;
+    verify_pool_allowlist(
+        &*ai_dex.load()?,
+        PoolAllowlistKind::Swap,
+        ctx.accounts.swap_allowlist.as_deref(),
+        ctx.accounts.token_authority.key,
+        ctx.accounts.swap_gating_token_account.as_deref(),
+    )?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::SupplementalTickArrays,
+            AccountsType::SwapHookAccounts,
+        ],
+    )?;
+
+    let tick_array_0 = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_0, &ai_dex.key())?;
+    let tick_array_1 = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_1, &ai_dex.key())?;
+    let tick_array_2 = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_2, &ai_dex.key())?;
+    let supplemental_tick_arrays = load_supplemental_tick_arrays(
+        &remaining_accounts.supplemental_tick_arrays,
+        &ai_dex.key(),
+    )?;
+
+    let mut swap_tick_sequence = SwapTickSequence::new_with_tick_arrays(
+        tick_array_0.load_mut()?,
+        tick_array_1.load_mut().ok(),
+        tick_array_2.load_mut().ok(),
+    );
+    for tick_array in supplemental_tick_arrays.iter() {
+        swap_tick_sequence.push_tick_array(tick_array.load_mut()?);
+    }
+    apply_tick_array_bitmap(
+        &ctx.accounts.tick_array_bitmap,
+        &mut swap_tick_sequence,
+        ai_dex.load()?.tick_spacing,
+        permit.a_to_b,
+    )?;
+
+    let volatility_ticks = oracle_volatility_ticks(&ctx.accounts.oracle, &*ai_dex.load()?, timestamp)?;
+    let pre_sqrt_price = ai_dex.load()?.sqrt_price;
+    let swap_update = swap_with_transfer_fee_extension(
+        &*ai_dex.load()?,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_mint_b,
+        &mut swap_tick_sequence,
+        permit.amount,
+        permit.sqrt_price_limit,
+        permit.amount_specified_is_input,
+        permit.a_to_b,
+        timestamp,
+        0,
+        0,
+        volatility_ticks,
+    )?;
+
+    let (input_mint, output_mint, input_amount, vault_output_amount) = if permit.a_to_b {
+        (&ctx.accounts.token_mint_a, &ctx.accounts.token_mint_b, swap_update.amount_a, swap_update.amount_b)
+    } else {
+        (&ctx.accounts.token_mint_b, &ctx.accounts.token_mint_a, swap_update.amount_b, swap_update.amount_a)
+    };
+    let input_transfer_fee = calculate_transfer_fee_excluded_amount(input_mint, input_amount)?.transfer_fee;
+    let output = calculate_transfer_fee_excluded_amount(output_mint, vault_output_amount)?;
+
+    if permit.amount_specified_is_input {
+        if output.amount < permit.other_amount_threshold {
+            return Err(ErrorCode::AmountOutBelowMinimumError.into());
+        }
+    } else if input_amount > permit.other_amount_threshold {
+        return Err(ErrorCode::AmountInAboveMaximumError.into());
+    }
+
+    record_oracle_observation(&ctx.accounts.oracle, &*ai_dex.load()?, timestamp)?;
+    record_pool_stats(&ctx.accounts.pool_stats, &swap_update, permit.a_to_b, timestamp)?;
+
+    // The nonce account signs as the delegate of the input token account of the token authority
+    update_and_swap_ai_dex_from_delegate(
+        ai_dex,
+        ctx.accounts.token_authority.key,
+        &ctx.accounts.swap_permit_nonce.to_account_info(),
+        &ctx.accounts.swap_permit_nonce.seeds(),
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.token_owner_account_a,
+        &ctx.accounts.token_owner_account_b,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_vault_b,
+        &remaining_accounts.transfer_hook_a,
+        &remaining_accounts.transfer_hook_b,
+        &ctx.accounts.token_program_a,
+        &ctx.accounts.token_program_b,
+        &ctx.accounts.memo_program,
+        &swap_update,
+        permit.a_to_b,
+        timestamp,
+        transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
+    )?;
+
+    let swap_summary = SwapHookSummary {
+        ai_dex_pool: ai_dex.key(),
+        token_authority: ctx.accounts.token_authority.key(),
+        a_to_b: permit.a_to_b,
+        input_amount,
+        output_amount: output.amount,
+        fee_rate: swap_update.fee_rate,
+        lp_fee: swap_update.lp_fee(),
+        protocol_fee: swap_update.next_protocol_fee,
+        pre_sqrt_price,
+        post_sqrt_price: swap_update.next_sqrt_price,
+        post_tick_index: swap_update.next_tick_index,
+        timestamp,
+    };
+    if ai_dex.load()?.swap_hook_program().is_some() {
+        let swap_hook_program = ctx
+            .accounts
+            .swap_hook_program
+            .as_ref()
+            .ok_or(ErrorCode::SwapHookProgramMissingError)?;
+        invoke_swap_hook(
+            swap_hook_program,
+            &ai_dex.to_account_info(),
+            &remaining_accounts.swap_hook_accounts,
+            &swap_summary,
+        )?;
+    }
+
+    emit_cpi!(SwapWithPermitExecutedEvent {
+        relayer: ctx.accounts.relayer.key(),
+        token_authority: ctx.accounts.token_authority.key(),
+        ai_dex_pool: ai_dex.key(),
+        nonce: permit.nonce,
+        amount: permit.amount,
+        other_amount_threshold: permit.other_amount_threshold,
+        sqrt_price_limit: permit.sqrt_price_limit,
+        amount_specified_is_input: permit.amount_specified_is_input,
+        a_to_b: permit.a_to_b,
+        fee_rate: swap_summary.fee_rate,
+        input_amount,
+        output_amount: output.amount,
+        lp_fee: swap_summary.lp_fee,
+        protocol_fee: swap_summary.protocol_fee,
+        input_transfer_fee,
+        output_transfer_fee: output.transfer_fee,
+        pre_sqrt_price,
+        post_sqrt_price: swap_summary.post_sqrt_price,
+        post_tick_index: swap_summary.post_tick_index,
+        timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, SyncNative, TokenAccount, TokenInterface};
+
+use crate::errors::ErrorCode;
+use crate::state::AiDexPool;
+use crate::util::is_native_mint;
+
+#[event]
+pub struct NativeVaultSyncedEvent {
+    pub ai_dex_pool: Pubkey,
+    pub token_vault: Pubkey,
+    pub amount_before: u64,
+    pub amount_after: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SyncNativeVault<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        constraint = token_mint.key() == ai_dex_pool.load()?.token_mint_a
+            || token_mint.key() == ai_dex_pool.load()?.token_mint_b
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut,
+        constraint = (token_vault.key() == ai_dex_pool.load()?.token_vault_a && token_mint.key() == ai_dex_pool.load()?.token_mint_a)
+            || (token_vault.key() == ai_dex_pool.load()?.token_vault_b && token_mint.key() == ai_dex_pool.load()?.token_mint_b)
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = token_mint.to_account_info().owner.clone())]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Syncs the wSOL balance of a pool vault with the lamports it holds.
+///
+/// Lamports sent directly to a wSOL vault are not part of its token balance until the vault is
+/// synced. The instruction is permissionless: the synced lamports only add to the vault balance,
+/// without being credited to any position.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the accounts required for the sync.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the vault is successfully synced, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidNativeSolAccountsError` - If the vault does not hold wSOL.
+pub fn sync_native_vault_handler(ctx: Context<SyncNativeVault>) -> Result<()> {
+    if !is_native_mint(&ctx.accounts.token_mint.key()) {
+        return Err(ErrorCode::InvalidNativeSolAccountsError.into());
+    }
+
+    let amount_before = ctx.accounts.token_vault.amount;
+    token_interface::sync_native(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        SyncNative {
+            account: ctx.accounts.token_vault.to_account_info(),
+        },
+    ))?;
+    ctx.accounts.token_vault.reload()?;
+
+    emit_cpi!(NativeVaultSyncedEvent {
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        token_vault: ctx.accounts.token_vault.key(),
+        amount_before,
+        amount_after: ctx.accounts.token_vault.amount,
+    });
+
+    Ok(())
+}
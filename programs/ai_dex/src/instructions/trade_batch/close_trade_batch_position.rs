@@ -2,7 +2,10 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::TokenAccount;
 
 use crate::errors::ErrorCode;
-use crate::{state::*, util::verify_position_trade_batch_authority};
+use crate::{
+    state::*,
+    util::{to_timestamp_u64, update_trade_batch_index, verify_position_trade_batch_authority},
+};
 
 #[event]
 pub struct TradeBatchPositionClosedEvent {
@@ -11,14 +14,15 @@ pub struct TradeBatchPositionClosedEvent {
     pub position_trade_batch_token_account: Pubkey,
     pub position_trade_batch_authority: Pubkey,
     pub trade_batch_position: Pubkey,
-    pub receiver: Pubkey,
+    pub rent_receiver: Pubkey,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(trade_batch_index: u16)]
 pub struct CloseTradeBatchPosition<'info> {
     #[account(mut,
-        close = receiver,
+        close = rent_receiver,
         seeds = [
             b"trade_batch_position".as_ref(),
             position_trade_batch.position_trade_batch_mint.key().as_ref(),
@@ -40,9 +44,20 @@ pub struct CloseTradeBatchPosition<'info> {
 
     pub position_trade_batch_authority: Signer<'info>,
     
-    /// CHECK: safe, for receiving rent only
+    /// CHECK: safe, for receiving reclaimed rent only; may be any account (e.g. a treasury)
     #[account(mut)]
-    pub receiver: UncheckedAccount<'info>,
+    pub rent_receiver: UncheckedAccount<'info>,
+
+    /// Required only for a trade batch index tracked by the trade batch extension.
+    #[account(
+        mut,
+        seeds = [
+            b"trade_batch_extension".as_ref(),
+            position_trade_batch.position_trade_batch_mint.as_ref(),
+        ],
+        bump,
+    )]
+    pub trade_batch_extension: Option<AccountLoader<'info, TradeBatchExtension>>,
 }
 
 /// Closes a trade batch position if it is empty and the authority is verified.
@@ -64,7 +79,10 @@ pub struct CloseTradeBatchPosition<'info> {
 /// # Errors
 ///
 /// This function can return errors in the following cases:
+/// * PositionLockedError if the position is locked.
 /// * NonEmptyPositionCloseError if the position is not empty.
+/// * TradeBatchExtensionRequiredError if the index is tracked by the trade batch extension and
+///   the extension is not provided.
 pub fn close_trade_batch_position_handler(ctx: Context<CloseTradeBatchPosition>, trade_batch_index: u16) -> Result<()> {
     let position_trade_batch = &mut ctx.accounts.position_trade_batch;
 
@@ -74,21 +92,31 @@ pub fn close_trade_batch_position_handler(ctx: Context<CloseTradeBatchPosition>,
         &ctx.accounts.position_trade_batch_authority,
     )?;
 
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    if ctx.accounts.trade_batch_position.is_locked(timestamp) {
+        return Err(ErrorCode::PositionLockedError.into());
+    }
+
     if !Position::is_position_empty(&ctx.accounts.trade_batch_position) {
         return Err(ErrorCode::NonEmptyPositionCloseError.into());
     }
 
-    position_trade_batch.close_trade_batch_position(trade_batch_index)?;
+    update_trade_batch_index(
+        position_trade_batch,
+        &ctx.accounts.trade_batch_extension,
+        trade_batch_index,
+        false,
+    )?;
 
     // Anchor will close the Position account
 
-    emit!(TradeBatchPositionClosedEvent {
+    emit_cpi!(TradeBatchPositionClosedEvent {
         trade_batch_index,
         position_trade_batch: position_trade_batch.key(),
         position_trade_batch_token_account: ctx.accounts.position_trade_batch_token_account.key(),
         position_trade_batch_authority: ctx.accounts.position_trade_batch_authority.key(),
         trade_batch_position: ctx.accounts.trade_batch_position.key(),
-        receiver: ctx.accounts.receiver.key(),
+        rent_receiver: ctx.accounts.rent_receiver.key(),
     });
     
     Ok(())
@@ -0,0 +1,253 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use std::convert::TryFrom;
+
+use crate::constants::transfer_memo;
+use crate::errors::ErrorCode;
+use crate::instructions::{calculate_collect_reward, MAX_POSITIONS_PER_COLLECT};
+use crate::state::*;
+use crate::util::{
+    parse_remaining_accounts, remaining_accounts_slice, transfer_from_vault_to_owner,
+    verify_position_trade_batch_authority,
+    AccountsType, RemainingAccountsInfo,
+};
+
+// Accounts passed in the `RewardAccounts` slice for each initialized reward:
+// reward owner account, reward mint, reward vault and reward token program.
+const ACCOUNTS_PER_REWARD: usize = 4;
+
+#[event]
+pub struct TradeBatchFeesCollectedEvent {
+    pub ai_dex_pool: Pubkey,
+    pub position_trade_batch: Pubkey,
+    pub position_trade_batch_authority: Pubkey,
+    pub positions: Vec<Pubkey>,
+    pub fee_owed_a: u64,
+    pub fee_owed_b: u64,
+    pub reward_amounts: [u64; NUM_REWARDS],
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CollectTradeBatchFees<'info> {
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    pub position_trade_batch: Box<Account<'info, PositionTradeBatch>>,
+
+    #[account(
+        constraint = position_trade_batch_token_account.mint == position_trade_batch.position_trade_batch_mint,
+        constraint = position_trade_batch_token_account.amount == 1
+    )]
+    pub position_trade_batch_token_account: Box<Account<'info, token::TokenAccount>>,
+
+    pub position_trade_batch_authority: Signer<'info>,
+
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
+    pub token_mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
+    pub token_mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut,
+        constraint = token_owner_account_a.mint == ai_dex_pool.load()?.token_mint_a,
+        constraint = token_owner_account_a.owner == position_trade_batch_token_account.owner
+    )]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut,
+        constraint = token_owner_account_b.mint == ai_dex_pool.load()?.token_mint_b,
+        constraint = token_owner_account_b.owner == position_trade_batch_token_account.owner
+    )]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = token_mint_a.to_account_info().owner.clone())]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = token_mint_b.to_account_info().owner.clone())]
+    pub token_program_b: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+}
+
+/// Collects the fees and rewards of several positions of a position trade batch at once.
+///
+/// The trade batch positions are passed in the `PositionAccounts` slice of the remaining accounts,
+/// and the accounts of every initialized reward in the `RewardAccounts` slice, in reward index
+/// order: the reward owner account, the reward mint, the reward vault and the reward token program.
+/// Everything is sent to the token accounts of the owner of the position trade batch, in a single
+/// transfer per token. As in `collect_reward`, a reward vault short of tokens pays what it holds
+/// and the rest stays owed.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the collection.
+/// * `remaining_accounts_info` - Information about remaining accounts, including the positions and rewards.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the collection is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidPositionAccountsError` - If no position or more than `MAX_POSITIONS_PER_COLLECT`
+///   positions are passed, or a position does not belong to the pool or to the position trade batch.
+/// * `ErrorCode::InvalidRewardAccountsError` - If the reward accounts do not match the initialized rewards.
+/// * Any error from verifying the authority of the position trade batch.
+pub fn collect_trade_batch_fees_handler<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CollectTradeBatchFees<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    // Allow delegation
+    verify_position_trade_batch_authority(
+        &ctx.accounts.position_trade_batch_token_account,
+        &ctx.accounts.position_trade_batch_authority,
+    )?;
+
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::TransferHookReward,
+            AccountsType::PositionAccounts,
+            AccountsType::RewardAccounts,
+        ],
+    )?;
+
+    let position_accounts = remaining_accounts_slice(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        AccountsType::PositionAccounts,
+    );
+    if position_accounts.is_empty() || position_accounts.len() > MAX_POSITIONS_PER_COLLECT {
+        return Err(ErrorCode::InvalidPositionAccountsError.into());
+    }
+
+    // Load the accounts of every initialized reward
+    let reward_infos = ctx.accounts.ai_dex_pool.load()?.reward_infos;
+    let initialized_rewards = reward_infos.iter().filter(|reward| reward.initialized()).count();
+    let reward_accounts = remaining_accounts_slice(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        AccountsType::RewardAccounts,
+    );
+    if reward_accounts.len() != initialized_rewards * ACCOUNTS_PER_REWARD {
+        return Err(ErrorCode::InvalidRewardAccountsError.into());
+    }
+
+    let mut rewards = Vec::with_capacity(initialized_rewards);
+    let initialized_reward_infos = reward_infos
+        .iter()
+        .enumerate()
+        .filter(|(_, reward)| reward.initialized());
+    for ((index, reward_info), accounts) in
+        initialized_reward_infos.zip(reward_accounts.chunks(ACCOUNTS_PER_REWARD))
+    {
+        let reward_owner_account = InterfaceAccount::<TokenAccount>::try_from(&accounts[0])?;
+        let reward_mint = InterfaceAccount::<Mint>::try_from(&accounts[1])?;
+        let reward_vault = InterfaceAccount::<TokenAccount>::try_from(&accounts[2])?;
+        let reward_token_program = Interface::<TokenInterface>::try_from(&accounts[3])?;
+
+        if reward_mint.key() != reward_info.mint
+            || reward_vault.key() != reward_info.vault
+            || reward_owner_account.mint != reward_info.mint
+            || reward_owner_account.owner != ctx.accounts.position_trade_batch_token_account.owner
+            || reward_token_program.key() != *reward_mint.to_account_info().owner
+        {
+            return Err(ErrorCode::InvalidRewardAccountsError.into());
+        }
+
+        rewards.push((index, reward_owner_account, reward_mint, reward_vault, reward_token_program));
+    }
+
+    let ai_dex_pool_key = ctx.accounts.ai_dex_pool.key();
+    let position_trade_batch_mint = ctx.accounts.position_trade_batch.position_trade_batch_mint;
+
+    let mut positions = Vec::with_capacity(position_accounts.len());
+    let mut fee_owed_a: u64 = 0;
+    let mut fee_owed_b: u64 = 0;
+    let mut reward_amounts = [0u64; NUM_REWARDS];
+    for position_info in position_accounts.iter() {
+        let mut position = Account::<Position>::try_from(position_info)?;
+        if position.ai_dex_pool != ai_dex_pool_key || position.position_mint != position_trade_batch_mint {
+            return Err(ErrorCode::InvalidPositionAccountsError.into());
+        }
+
+        fee_owed_a = fee_owed_a
+            .checked_add(position.fee_owed_a)
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        fee_owed_b = fee_owed_b
+            .checked_add(position.fee_owed_b)
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        position.reset_fees_owed();
+
+        for (index, _, _, reward_vault, _) in rewards.iter() {
+            // The vault pays the positions in order until it runs out of tokens
+            let (transfer_amount, updated_amount_owed) = calculate_collect_reward(
+                position.reward_infos[*index],
+                reward_vault.amount - reward_amounts[*index],
+            );
+            position.update_reward_owed(*index, updated_amount_owed);
+            reward_amounts[*index] += transfer_amount;
+        }
+
+        // Written back right away so a position passed twice is only collected once
+        position.exit(&crate::ID)?;
+        positions.push(position.key());
+    }
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_owner_account_a,
+        &ctx.accounts.token_program_a,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_a,
+        fee_owed_a,
+        transfer_memo::TRANSFER_MEMO_COLLECT_FEES.as_bytes(),
+    )?;
+    transfer_from_vault_to_owner(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_owner_account_b,
+        &ctx.accounts.token_program_b,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_b,
+        fee_owed_b,
+        transfer_memo::TRANSFER_MEMO_COLLECT_FEES.as_bytes(),
+    )?;
+
+    for (index, reward_owner_account, reward_mint, reward_vault, reward_token_program) in rewards.iter() {
+        ctx.accounts.ai_dex_pool.load_mut()?.record_reward_collected(*index, reward_amounts[*index]);
+        transfer_from_vault_to_owner(
+            &ctx.accounts.ai_dex_pool,
+            reward_mint,
+            reward_vault,
+            reward_owner_account,
+            reward_token_program,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_reward,
+            reward_amounts[*index],
+            transfer_memo::TRANSFER_MEMO_COLLECT_REWARD.as_bytes(),
+        )?;
+    }
+
+    emit_cpi!(TradeBatchFeesCollectedEvent {
+        ai_dex_pool: ai_dex_pool_key,
+        position_trade_batch: ctx.accounts.position_trade_batch.key(),
+        position_trade_batch_authority: ctx.accounts.position_trade_batch_authority.key(),
+        positions,
+        fee_owed_a,
+        fee_owed_b,
+        reward_amounts,
+    });
+
+    Ok(())
+}
@@ -11,7 +11,7 @@ pub struct PositionTradeBatchDeletedEvent {
     pub position_trade_batch_mint: Pubkey,
     pub position_trade_batch_token_account: PositionTradeBatchTokenAccountData,
     pub position_trade_batch_owner: Pubkey,
-    pub receiver: Pubkey,
+    pub rent_receiver: Pubkey,
     pub token_program: Pubkey,
 }
 
@@ -22,9 +22,10 @@ pub struct PositionTradeBatchTokenAccountData {
     pub owner: Pubkey,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct DeletePositionTradeBatch<'info> {
-    #[account(mut, close = receiver)]
+    #[account(mut, close = rent_receiver)]
     pub position_trade_batch: Account<'info, PositionTradeBatch>,
 
     #[account(mut, address = position_trade_batch.position_trade_batch_mint)]
@@ -39,19 +40,32 @@ pub struct DeletePositionTradeBatch<'info> {
 
     pub position_trade_batch_owner: Signer<'info>,
 
-    /// CHECK: safe, for receiving rent only
+    /// CHECK: safe, for receiving reclaimed rent only; may be any account (e.g. a treasury)
     #[account(mut)]
-    pub receiver: UncheckedAccount<'info>,
+    pub rent_receiver: UncheckedAccount<'info>,
 
     #[account(address = token::ID)]
     pub token_program: Program<'info, Token>,
+
+    /// Required if the trade batch extension has been initialized, closed to `rent_receiver`.
+    #[account(
+        mut,
+        close = rent_receiver,
+        seeds = [
+            b"trade_batch_extension".as_ref(),
+            position_trade_batch.position_trade_batch_mint.as_ref(),
+        ],
+        bump,
+    )]
+    pub trade_batch_extension: Option<AccountLoader<'info, TradeBatchExtension>>,
 }
 
 /// Deletes a trade batch position if it is deletable.
 ///
 /// This function handles the deletion of a trade batch position. It first checks if the
 /// position trade batch is deletable. If it is not deletable, it returns an error. Otherwise,
-/// it proceeds to burn and close the position trade batch token.
+/// it proceeds to burn and close the position trade batch token. The trade batch extension, if
+/// any, must be provided and empty, and is closed as well.
 ///
 /// # Arguments
 ///
@@ -65,7 +79,8 @@ pub struct DeletePositionTradeBatch<'info> {
 /// # Errors
 ///
 /// This function can return errors in the following cases:
-/// * NonDeletablePositionTradeBatchError if the position trade batch is not deletable.
+/// * NonDeletablePositionTradeBatchError if the position trade batch or its extension is not deletable.
+/// * TradeBatchExtensionRequiredError if the extension has been initialized and is not provided.
 pub fn delete_trade_batch_position_handler(ctx: Context<DeletePositionTradeBatch>) -> Result<()> {
     let position_trade_batch = &ctx.accounts.position_trade_batch;
 
@@ -73,15 +88,26 @@ pub fn delete_trade_batch_position_handler(ctx: Context<DeletePositionTradeBatch
         return Err(ErrorCode::NonDeletablePositionTradeBatchError.into());
     }
 
+    if position_trade_batch.has_extension {
+        let trade_batch_extension = ctx
+            .accounts
+            .trade_batch_extension
+            .as_ref()
+            .ok_or(ErrorCode::TradeBatchExtensionRequiredError)?;
+        if !trade_batch_extension.load()?.is_empty() {
+            return Err(ErrorCode::NonDeletablePositionTradeBatchError.into());
+        }
+    }
+
     burn_and_close_position_trade_batch_token(
         &ctx.accounts.position_trade_batch_owner,
-        &ctx.accounts.receiver,
+        &ctx.accounts.rent_receiver,
         &ctx.accounts.position_trade_batch_mint,
         &ctx.accounts.position_trade_batch_token_account,
         &ctx.accounts.token_program,
     )?;
 
-    emit!(PositionTradeBatchDeletedEvent {
+    emit_cpi!(PositionTradeBatchDeletedEvent {
         position_trade_batch: ctx.accounts.position_trade_batch.key(),
         position_trade_batch_mint: ctx.accounts.position_trade_batch_mint.key(),
         position_trade_batch_token_account: PositionTradeBatchTokenAccountData {
@@ -90,7 +116,7 @@ pub fn delete_trade_batch_position_handler(ctx: Context<DeletePositionTradeBatch
             owner: ctx.accounts.position_trade_batch_token_account.owner,
         },
         position_trade_batch_owner: ctx.accounts.position_trade_batch_owner.key(),
-        receiver: ctx.accounts.receiver.key(),
+        rent_receiver: ctx.accounts.rent_receiver.key(),
         token_program: ctx.accounts.token_program.key(),
     });
     
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::{state::*, util::verify_position_trade_batch_authority};
+
+#[event]
+pub struct TradeBatchExtensionInitializedEvent {
+    pub position_trade_batch: Pubkey,
+    pub trade_batch_extension: Pubkey,
+    pub position_trade_batch_authority: Pubkey,
+    pub funder: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeTradeBatchExtension<'info> {
+    #[account(mut)]
+    pub position_trade_batch: Box<Account<'info, PositionTradeBatch>>,
+
+    #[account(
+        constraint = position_trade_batch_token_account.mint == position_trade_batch.position_trade_batch_mint,
+        constraint = position_trade_batch_token_account.amount == 1
+    )]
+    pub position_trade_batch_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub position_trade_batch_authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = funder,
+        space = TradeBatchExtension::LEN,
+        seeds = [
+            b"trade_batch_extension".as_ref(),
+            position_trade_batch.position_trade_batch_mint.as_ref(),
+        ],
+        bump,
+    )]
+    pub trade_batch_extension: AccountLoader<'info, TradeBatchExtension>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Initializes the trade batch extension of a position trade batch.
+///
+/// The extension tracks the trade batch indexes from `POSITION_TRADE_BATCH_SIZE` up to
+/// `MAX_TRADE_BATCH_SIZE`, so a single position trade batch can manage thousands of positions.
+/// Once initialized, the extension has to be passed to open or close a position at these indexes
+/// and is closed together with the position trade batch.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for initializing the extension.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the extension is successfully initialized,
+/// or an `Err` if an error occurs.
+pub fn initialize_trade_batch_extension_handler(ctx: Context<InitializeTradeBatchExtension>) -> Result<()> {
+    // Allow delegation
+    verify_position_trade_batch_authority(
+        &ctx.accounts.position_trade_batch_token_account,
+        &ctx.accounts.position_trade_batch_authority,
+    )?;
+
+    let position_trade_batch = &mut ctx.accounts.position_trade_batch;
+    ctx.accounts
+        .trade_batch_extension
+        .load_init()?
+        .initialize(position_trade_batch.key());
+    position_trade_batch.has_extension = true;
+
+    emit_cpi!(TradeBatchExtensionInitializedEvent {
+        position_trade_batch: position_trade_batch.key(),
+        trade_batch_extension: ctx.accounts.trade_batch_extension.key(),
+        position_trade_batch_authority: ctx.accounts.position_trade_batch_authority.key(),
+        funder: ctx.accounts.funder.key(),
+    });
+
+    Ok(())
+}
@@ -19,6 +19,7 @@ pub struct PositionTradeBatchTokenAccountData {
     pub mint: Pubkey,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct InitializePositionTradeBatch<'info> {
     #[account(
@@ -93,7 +94,7 @@ pub fn initialize_trade_batch_position_handler(ctx: Context<InitializePositionTr
         ],
     )?;
 
-    emit!(InitializeTradeBatchPositionEvent {
+    emit_cpi!(InitializeTradeBatchPositionEvent {
         position_trade_batch: ctx.accounts.position_trade_batch.key(),
         position_trade_batch_mint: position_trade_batch_mint.key(),
         position_trade_batch_token_account: PositionTradeBatchTokenAccountData {
@@ -26,6 +26,7 @@ pub struct PositionTradeBatchTokenAccountData {
     pub mint: Pubkey,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct InitializePositionTradeBatchWithMetadata<'info> {
     #[account(init,
@@ -115,7 +116,7 @@ pub fn initialize_trade_batch_position_with_metadata_handler(
         ],
     )?;
 
-    emit!(InitializePositionTradeBatchWithMetadataEvent {
+    emit_cpi!(InitializePositionTradeBatchWithMetadataEvent {
         funder: ctx.accounts.funder.key(),
         position_trade_batch: ctx.accounts.position_trade_batch.key(),
         position_trade_batch_mint: ctx.accounts.position_trade_batch_mint.key(),
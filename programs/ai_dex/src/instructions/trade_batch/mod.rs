@@ -1,10 +1,15 @@
 pub mod close_trade_batch_position;
+pub mod collect_trade_batch_fees;
 pub mod delete_trade_batch_position;
+pub mod initialize_trade_batch_extension;
 pub mod initialize_trade_batch_position;
 pub mod initialize_trade_batch_position_with_metadata;
 pub mod open_trade_batch_position;
+pub mod open_trade_batch_positions;
+pub mod transfer_trade_batch_position;
 
 pub use close_trade_batch_position::*;
+pub use collect_trade_batch_fees::*;
 // pub use delete_trade_batch_position::delete_trade_batch_position_handler;
 // pub use delete_trade_batch_position::DeletePositionTradeBatch;
 // pub use initialize_trade_batch_position::initialize_trade_batch_position_handler;
@@ -12,6 +17,9 @@ pub use close_trade_batch_position::*;
 // pub use initialize_trade_batch_position_with_metadata::initialize_trade_batch_position_with_metadata_handler;
 // pub use initialize_trade_batch_position_with_metadata::InitializePositionTradeBatchWithMetadata;
 pub use delete_trade_batch_position::*;
+pub use initialize_trade_batch_extension::*;
 pub use initialize_trade_batch_position::*;
 pub use initialize_trade_batch_position_with_metadata::*;
-pub use open_trade_batch_position::*;
\ No newline at end of file
+pub use open_trade_batch_position::*;
+pub use open_trade_batch_positions::*;
+pub use transfer_trade_batch_position::*;
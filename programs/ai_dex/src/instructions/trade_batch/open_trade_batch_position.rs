@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::TokenAccount;
 
-use crate::{state::*, util::verify_position_trade_batch_authority};
+use crate::{
+    state::*,
+    util::{update_trade_batch_index, verify_position_trade_batch_authority},
+};
 
 #[event]
 pub struct TradeBatchPositionOpenedEvent {
@@ -33,6 +36,7 @@ pub struct PositionTradeBatchTokenAccountData {
     pub amount: u64,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(trade_batch_index: u16)]
 pub struct OpenTradeBatchPosition<'info> {
@@ -59,20 +63,32 @@ pub struct OpenTradeBatchPosition<'info> {
 
     pub position_trade_batch_authority: Signer<'info>,
 
-    pub ai_dex_pool: Box<Account<'info, AiDexPool>>,
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
 
     #[account(mut)]
     pub funder: Signer<'info>,
 
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
+
+    /// Required only for a trade batch index tracked by the trade batch extension.
+    #[account(
+        mut,
+        seeds = [
+            b"trade_batch_extension".as_ref(),
+            position_trade_batch.position_trade_batch_mint.as_ref(),
+        ],
+        bump,
+    )]
+    pub trade_batch_extension: Option<AccountLoader<'info, TradeBatchExtension>>,
 }
 
 /// Opens a trade batch position with specified tick indices.
 ///
 /// This function handles the opening of a trade batch position. It first verifies the
 /// authority of the position trade batch token account. Then, it opens the trade batch
-/// position and sets the position with the specified tick indices.
+/// position and sets the position with the specified tick indices. Indexes from
+/// `POSITION_TRADE_BATCH_SIZE` are opened in the trade batch extension, which must be provided.
 ///
 /// # Arguments
 ///
@@ -101,7 +117,12 @@ pub fn open_trade_batch_position_handler(
         &ctx.accounts.position_trade_batch_authority,
     )?;
 
-    position_trade_batch.open_trade_batch_position(trade_batch_index)?;
+    update_trade_batch_index(
+        position_trade_batch,
+        &ctx.accounts.trade_batch_extension,
+        trade_batch_index,
+        true,
+    )?;
 
     position.open_position(
         ai_dex,
@@ -110,7 +131,7 @@ pub fn open_trade_batch_position_handler(
         tick_upper_index,
     )?;
 
-    emit!(TradeBatchPositionOpenedEvent {
+    emit_cpi!(TradeBatchPositionOpenedEvent {
         trade_batch_index,
         position_trade_batch: PositionTradeBatchData {
             key: position_trade_batch.key(),
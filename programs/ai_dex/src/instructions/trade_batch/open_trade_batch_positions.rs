@@ -0,0 +1,175 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::{
+    errors::ErrorCode,
+    state::*,
+    util::{create_program_account, update_trade_batch_index, verify_position_trade_batch_authority},
+};
+
+use super::open_trade_batch_position::{
+    PositionTradeBatchData, PositionTradeBatchTokenAccountData, TradeBatchPositionData,
+    TradeBatchPositionOpenedEvent,
+};
+
+/// The maximum number of trade batch positions that can be opened in a single instruction.
+pub const MAX_TRADE_BATCH_POSITIONS_PER_OPEN: usize = 8;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct OpenTradeBatchPositions<'info> {
+    #[account(mut)]
+    pub position_trade_batch: Box<Account<'info, PositionTradeBatch>>,
+
+    #[account(
+        constraint = position_trade_batch_token_account.mint == position_trade_batch.position_trade_batch_mint,
+        constraint = position_trade_batch_token_account.amount == 1
+    )]
+    pub position_trade_batch_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub position_trade_batch_authority: Signer<'info>,
+
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Required only for a trade batch index tracked by the trade batch extension.
+    #[account(
+        mut,
+        seeds = [
+            b"trade_batch_extension".as_ref(),
+            position_trade_batch.position_trade_batch_mint.as_ref(),
+        ],
+        bump,
+    )]
+    pub trade_batch_extension: Option<AccountLoader<'info, TradeBatchExtension>>,
+
+    // Remaining accounts, one for each trade batch index in the same order:
+    // - trade_batch_position (mut), the uninitialized trade batch position PDA
+}
+
+/// Opens several trade batch positions in a single instruction.
+///
+/// Each position is opened as in `open_trade_batch_position`, with the tick range at the same
+/// index in `tick_lower_indexes` and `tick_upper_indexes`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for opening the trade batch positions.
+/// * `trade_batch_indexes` - The index of each trade batch position to open.
+/// * `tick_lower_indexes` - The lower tick index of each position.
+/// * `tick_upper_indexes` - The upper tick index of each position.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if all positions are successfully opened,
+/// or an `Err` if an error occurs.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidTradeBatchPositionsError` - If no position or more than
+///   `MAX_TRADE_BATCH_POSITIONS_PER_OPEN` positions are given, the lengths do not match, or a
+///   remaining account is not the trade batch position PDA of its index.
+/// * `ErrorCode::PositionAlreadyOpenedError` - If a trade batch index is already opened.
+/// * `ErrorCode::TradeBatchExtensionRequiredError` - If an index is tracked by the trade batch
+///   extension and the extension is not provided.
+pub fn open_trade_batch_positions_handler<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, OpenTradeBatchPositions<'info>>,
+    trade_batch_indexes: Vec<u16>,
+    tick_lower_indexes: Vec<i32>,
+    tick_upper_indexes: Vec<i32>,
+) -> Result<()> {
+    let num_positions = trade_batch_indexes.len();
+    if num_positions == 0
+        || num_positions > MAX_TRADE_BATCH_POSITIONS_PER_OPEN
+        || tick_lower_indexes.len() != num_positions
+        || tick_upper_indexes.len() != num_positions
+        || ctx.remaining_accounts.len() != num_positions
+    {
+        return Err(ErrorCode::InvalidTradeBatchPositionsError.into());
+    }
+
+    // Allow delegation
+    verify_position_trade_batch_authority(
+        &ctx.accounts.position_trade_batch_token_account,
+        &ctx.accounts.position_trade_batch_authority,
+    )?;
+
+    let position_trade_batch_mint = ctx.accounts.position_trade_batch.position_trade_batch_mint;
+    let rent = Rent::get()?;
+
+    for (i, position_info) in ctx.remaining_accounts.iter().enumerate() {
+        let trade_batch_index = trade_batch_indexes[i];
+        let tick_lower_index = tick_lower_indexes[i];
+        let tick_upper_index = tick_upper_indexes[i];
+
+        let trade_batch_index_seed = trade_batch_index.to_string();
+        let (position_key, bump) = Pubkey::find_program_address(
+            &[
+                b"trade_batch_position",
+                position_trade_batch_mint.as_ref(),
+                trade_batch_index_seed.as_bytes(),
+            ],
+            &crate::ID,
+        );
+        if position_info.key() != position_key {
+            return Err(ErrorCode::InvalidTradeBatchPositionsError.into());
+        }
+
+        update_trade_batch_index(
+            &mut ctx.accounts.position_trade_batch,
+            &ctx.accounts.trade_batch_extension,
+            trade_batch_index,
+            true,
+        )?;
+
+        create_program_account(
+            &ctx.accounts.funder,
+            position_info,
+            &ctx.accounts.system_program,
+            &[
+                b"trade_batch_position",
+                position_trade_batch_mint.as_ref(),
+                trade_batch_index_seed.as_bytes(),
+                &[bump],
+            ],
+            Position::LEN,
+            &rent,
+        )?;
+
+        let mut position = Account::<Position>::try_from_unchecked(position_info)?;
+        position.open_position(
+            &ctx.accounts.ai_dex_pool,
+            position_trade_batch_mint,
+            tick_lower_index,
+            tick_upper_index,
+        )?;
+        // Write the account discriminator, as `init` would do for a single position
+        position.exit(&crate::ID)?;
+
+        emit_cpi!(TradeBatchPositionOpenedEvent {
+            trade_batch_index,
+            position_trade_batch: PositionTradeBatchData {
+                key: ctx.accounts.position_trade_batch.key(),
+                position_trade_batch_mint,
+            },
+            trade_batch_position: TradeBatchPositionData {
+                tick_lower_index,
+                tick_upper_index,
+            },
+            position_trade_batch_authority: ctx.accounts.position_trade_batch_authority.key(),
+            position_trade_batch_token_account: PositionTradeBatchTokenAccountData {
+                key: ctx.accounts.position_trade_batch_token_account.key(),
+                mint: ctx.accounts.position_trade_batch_token_account.mint,
+                amount: ctx.accounts.position_trade_batch_token_account.amount,
+            },
+            ai_dex: ctx.accounts.ai_dex_pool.key(),
+            funder: ctx.accounts.funder.key(),
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::{
+    state::*,
+    util::{update_trade_batch_index, verify_position_trade_batch_authority},
+};
+
+#[event]
+pub struct TradeBatchPositionTransferredEvent {
+    pub trade_batch_index: u16,
+    pub from_position_trade_batch: Pubkey,
+    pub to_position_trade_batch: Pubkey,
+    pub from_trade_batch_position: Pubkey,
+    pub to_trade_batch_position: Pubkey,
+    pub from_position_trade_batch_authority: Pubkey,
+    pub to_position_trade_batch_authority: Pubkey,
+    pub funder: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(trade_batch_index: u16)]
+pub struct TransferTradeBatchPosition<'info> {
+    #[account(mut,
+        close = funder,
+        seeds = [
+            b"trade_batch_position".as_ref(),
+            from_position_trade_batch.position_trade_batch_mint.key().as_ref(),
+            trade_batch_index.to_string().as_bytes()
+        ],
+        bump,
+    )]
+    pub from_trade_batch_position: Box<Account<'info, Position>>,
+
+    #[account(init,
+        payer = funder,
+        space = Position::LEN,
+        seeds = [
+            b"trade_batch_position".as_ref(),
+            to_position_trade_batch.position_trade_batch_mint.key().as_ref(),
+            trade_batch_index.to_string().as_bytes()
+        ],
+        bump,
+    )]
+    pub to_trade_batch_position: Box<Account<'info, Position>>,
+
+    #[account(mut)]
+    pub from_position_trade_batch: Box<Account<'info, PositionTradeBatch>>,
+    #[account(
+        constraint = from_position_trade_batch_token_account.mint == from_position_trade_batch.position_trade_batch_mint,
+        constraint = from_position_trade_batch_token_account.amount == 1
+    )]
+    pub from_position_trade_batch_token_account: Box<Account<'info, TokenAccount>>,
+    pub from_position_trade_batch_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub to_position_trade_batch: Box<Account<'info, PositionTradeBatch>>,
+    #[account(
+        constraint = to_position_trade_batch_token_account.mint == to_position_trade_batch.position_trade_batch_mint,
+        constraint = to_position_trade_batch_token_account.amount == 1
+    )]
+    pub to_position_trade_batch_token_account: Box<Account<'info, TokenAccount>>,
+    pub to_position_trade_batch_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Required only for a trade batch index tracked by the trade batch extension.
+    #[account(
+        mut,
+        seeds = [
+            b"trade_batch_extension".as_ref(),
+            from_position_trade_batch.position_trade_batch_mint.as_ref(),
+        ],
+        bump,
+    )]
+    pub from_trade_batch_extension: Option<AccountLoader<'info, TradeBatchExtension>>,
+    /// Required only for a trade batch index tracked by the trade batch extension.
+    #[account(
+        mut,
+        seeds = [
+            b"trade_batch_extension".as_ref(),
+            to_position_trade_batch.position_trade_batch_mint.as_ref(),
+        ],
+        bump,
+    )]
+    pub to_trade_batch_extension: Option<AccountLoader<'info, TradeBatchExtension>>,
+}
+
+/// Moves a trade batch position to another position trade batch, at the same trade batch index.
+///
+/// The position is recreated under the destination position trade batch with its liquidity, fees,
+/// rewards and lock unchanged, so nothing has to be withdrawn from the pool. The operator of the
+/// position is cleared since the position changes hands. The authorities of both position trade
+/// batches must sign. Orders and strategies referencing the source position account are not moved.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for moving the trade batch position.
+/// * `trade_batch_index` - The index of the position in both position trade batches.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the position is successfully moved,
+/// or an `Err` if an error occurs.
+///
+/// # Errors
+///
+/// * `ErrorCode::PositionAlreadyClosedError` - If the index is not opened in the source batch.
+/// * `ErrorCode::PositionAlreadyOpenedError` - If the index is already opened in the destination batch.
+/// * `ErrorCode::TradeBatchExtensionRequiredError` - If the index is tracked by the trade batch
+///   extensions and they are not provided.
+pub fn transfer_trade_batch_position_handler(
+    ctx: Context<TransferTradeBatchPosition>,
+    trade_batch_index: u16,
+) -> Result<()> {
+    // Allow delegation
+    verify_position_trade_batch_authority(
+        &ctx.accounts.from_position_trade_batch_token_account,
+        &ctx.accounts.from_position_trade_batch_authority,
+    )?;
+    verify_position_trade_batch_authority(
+        &ctx.accounts.to_position_trade_batch_token_account,
+        &ctx.accounts.to_position_trade_batch_authority,
+    )?;
+
+    update_trade_batch_index(
+        &mut ctx.accounts.from_position_trade_batch,
+        &ctx.accounts.from_trade_batch_extension,
+        trade_batch_index,
+        false,
+    )?;
+    update_trade_batch_index(
+        &mut ctx.accounts.to_position_trade_batch,
+        &ctx.accounts.to_trade_batch_extension,
+        trade_batch_index,
+        true,
+    )?;
+
+    let mut position = (**ctx.accounts.from_trade_batch_position).clone();
+    position.position_mint = ctx.accounts.to_position_trade_batch.position_trade_batch_mint;
+    position.set_operator(Pubkey::default());
+    ctx.accounts.to_trade_batch_position.set_inner(position);
+
+    // Anchor will close the source Position account
+
+    emit_cpi!(TradeBatchPositionTransferredEvent {
+        trade_batch_index,
+        from_position_trade_batch: ctx.accounts.from_position_trade_batch.key(),
+        to_position_trade_batch: ctx.accounts.to_position_trade_batch.key(),
+        from_trade_batch_position: ctx.accounts.from_trade_batch_position.key(),
+        to_trade_batch_position: ctx.accounts.to_trade_batch_position.key(),
+        from_position_trade_batch_authority: ctx.accounts.from_position_trade_batch_authority.key(),
+        to_position_trade_batch_authority: ctx.accounts.to_position_trade_batch_authority.key(),
+        funder: ctx.accounts.funder.key(),
+    });
+
+    Ok(())
+}
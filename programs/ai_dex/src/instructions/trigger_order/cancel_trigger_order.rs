@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::state::*;
+use crate::util::verify_position_authority;
+
+#[event]
+pub struct TriggerOrderCancelledEvent {
+    pub position_authority: Pubkey,
+    pub position: Pubkey,
+    pub trigger_order: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelTriggerOrder<'info> {
+    pub position_authority: Signer<'info>,
+
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, has_one = position, has_one = funder, close = funder)]
+    pub trigger_order: Account<'info, TriggerOrder>,
+
+    /// CHECK: safe, the account that paid the rent of the trigger order
+    #[account(mut)]
+    pub funder: UncheckedAccount<'info>,
+}
+
+/// Cancels a trigger order, leaving the position untouched.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the operation.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the operation is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// * Any error from verifying the position authority.
+pub fn cancel_trigger_order_handler(ctx: Context<CancelTriggerOrder>) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    emit_cpi!(TriggerOrderCancelledEvent {
+        position_authority: ctx.accounts.position_authority.key(),
+        position: ctx.accounts.position.key(),
+        trigger_order: ctx.accounts.trigger_order.key(),
+    });
+
+    Ok(())
+}
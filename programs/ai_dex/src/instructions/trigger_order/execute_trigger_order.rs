@@ -0,0 +1,301 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::transfer_memo;
+use crate::errors::ErrorCode;
+use crate::math::convert_to_liquidity_delta;
+use crate::orchestrator::liquidity_orchestrator::{
+    calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
+};
+use crate::state::*;
+use crate::util::{
+    calculate_transfer_fee_excluded_amount, parse_remaining_accounts, swap_within_vaults,
+    to_timestamp_u64, transfer_from_vault_to_owner, AccountsType, RemainingAccountsInfo,
+    TickArrayAccount,
+};
+
+#[event]
+pub struct TriggerOrderExecutedEvent {
+    pub keeper: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub position: Pubkey,
+    pub trigger_order: Pubkey,
+    pub sqrt_price: u128,
+    pub a_to_b: bool,
+    pub liquidity_amount: u128,
+    pub delta_a: u64,
+    pub delta_b: u64,
+    pub swap_amount_in: u64,
+    pub swap_amount_out: u64,
+    pub amount: u64,
+    pub bounty: u64,
+    pub timestamp: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteTriggerOrder<'info> {
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    /// Pays the rent if a tick of a dynamic tick array changes while removing the liquidity.
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    #[account(mut, has_one = position, has_one = funder, close = funder)]
+    pub trigger_order: Box<Account<'info, TriggerOrder>>,
+
+    /// CHECK: safe, the account that paid the rent of the trigger order
+    #[account(mut)]
+    pub funder: UncheckedAccount<'info>,
+
+    #[account(mut, has_one = ai_dex_pool)]
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
+    pub token_mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
+    pub token_mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The account of the position owner receiving the target token.
+    #[account(mut,
+        constraint = owner_token_account.owner == position_token_account.owner,
+        constraint = owner_token_account.mint == if trigger_order.a_to_b { token_mint_b.key() } else { token_mint_a.key() },
+    )]
+    pub owner_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// The account of the keeper receiving the bounty.
+    #[account(mut,
+        constraint = keeper_token_account.mint == owner_token_account.mint,
+    )]
+    pub keeper_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: A fixed or dynamic tick array of the pool, checked in the handler
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: A fixed or dynamic tick array of the pool, checked in the handler
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    /// CHECK: The first tick array of the swap, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_0: UncheckedAccount<'info>,
+    /// CHECK: The second tick array of the swap, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_1: UncheckedAccount<'info>,
+    /// CHECK: The third tick array of the swap, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_2: UncheckedAccount<'info>,
+
+    /// CHECK: The oracle account of the pool. An observation is recorded only if it has been initialized.
+    #[account(mut, seeds = [b"oracle", ai_dex_pool.key().as_ref()], bump)]
+    pub oracle: UncheckedAccount<'info>,
+
+    /// CHECK: The tick array bitmap of the pool. Used only if it has been initialized.
+    #[account(seeds = [b"tick_array_bitmap", ai_dex_pool.key().as_ref()], bump)]
+    pub tick_array_bitmap: UncheckedAccount<'info>,
+
+    #[account(address = token_mint_a.to_account_info().owner.clone())]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = token_mint_b.to_account_info().owner.clone())]
+    pub token_program_b: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Executes a triggered stop-loss or take-profit order, paying the keeper its bounty.
+///
+/// The instruction is permissionless. Once the sqrt price of the pool has crossed a trigger of the
+/// order, all the liquidity of the position is withdrawn and the counter token is swapped into the
+/// target token through the same pool without leaving the vaults. The bounty is sent to the keeper,
+/// the rest to the position owner, and the trigger order is closed. Fees and rewards stay owed to
+/// the position.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the execution.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the trigger order is executed, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::TriggerOrderNotTriggeredError` - If the price has not crossed a trigger.
+/// * `ErrorCode::PositionLockedError` - If the position is locked.
+/// * `ErrorCode::ZeroLiquidityError` - If the position has no liquidity.
+/// * `ErrorCode::PartialFillError` - If the tick arrays provided cannot fill the whole swap.
+/// * `ErrorCode::SlotVolumeExceededError` - If the volume swapped out within the slot exceeds the cap of the pool.
+/// * `ErrorCode::SandwichSwapError` - If the position owner swapped in the opposite direction within the slot.
+/// * `ErrorCode::SwapHookNotSupportedError` - If the pool has a swap hook, which order fills do not call.
+/// * `ErrorCode::AmountOutBelowMinimumError` - If the owner receives less than the minimum of the order.
+pub fn trigger_order_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ExecuteTriggerOrder<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    if ctx.accounts.position.is_locked(timestamp) {
+        return Err(ErrorCode::PositionLockedError.into());
+    }
+
+    let liquidity_amount = ctx.accounts.position.liquidity;
+    if liquidity_amount == 0 {
+        return Err(ErrorCode::ZeroLiquidityError.into());
+    }
+
+    let sqrt_price = ctx.accounts.ai_dex_pool.load()?.sqrt_price;
+    if !ctx.accounts.trigger_order.is_triggered(sqrt_price) {
+        return Err(ErrorCode::TriggerOrderNotTriggeredError.into());
+    }
+
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::SupplementalTickArrays,
+        ],
+    )?;
+
+    // Withdraw all the liquidity
+    let a_to_b = ctx.accounts.trigger_order.a_to_b;
+    let ai_dex_pool_key = ctx.accounts.ai_dex_pool.key();
+    let tick_array_lower = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_lower, &ai_dex_pool_key)?;
+    let tick_array_upper = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_upper, &ai_dex_pool_key)?;
+
+    let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
+    let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, false)?;
+    let update = calculate_modify_liquidity(
+        &ai_dex,
+        &ctx.accounts.position,
+        &tick_array_lower,
+        &tick_array_upper,
+        liquidity_delta,
+        timestamp,
+    )?;
+    sync_modify_liquidity_values(
+        &mut ai_dex,
+        &mut ctx.accounts.position,
+        &tick_array_lower,
+        &tick_array_upper,
+        update,
+        timestamp,
+        &ctx.accounts.keeper,
+        &ctx.accounts.system_program,
+    )?;
+
+    let (delta_a, delta_b) = calculate_liquidity_token_deltas(
+        ai_dex.tick_current_index,
+        ai_dex.sqrt_price,
+        &ctx.accounts.position,
+        liquidity_delta,
+    )?;
+    drop(tick_array_lower);
+    drop(tick_array_upper);
+
+    // The withdrawn counter token stays in its vault and is swapped in place into the target token
+    let (counter_delta, target_delta) = if a_to_b { (delta_a, delta_b) } else { (delta_b, delta_a) };
+    let (swap_amount_in, swap_amount_out) = if counter_delta > 0 {
+        swap_within_vaults(
+            &mut ai_dex,
+            &ai_dex_pool_key,
+            &ctx.accounts.position_token_account.owner,
+            &ctx.accounts.token_vault_a,
+            &ctx.accounts.token_vault_b,
+            &ctx.accounts.tick_array_0,
+            &ctx.accounts.tick_array_1,
+            &ctx.accounts.tick_array_2,
+            &remaining_accounts.supplemental_tick_arrays,
+            &ctx.accounts.tick_array_bitmap,
+            &ctx.accounts.oracle,
+            counter_delta,
+            0,
+            a_to_b,
+            timestamp,
+        )?
+    } else {
+        (0, 0)
+    };
+    drop(ai_dex);
+
+    let amount = target_delta
+        .checked_add(swap_amount_out)
+        .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+    let (bounty, owner_amount) = ctx.accounts.trigger_order.split_bounty(amount);
+
+    let (token_mint, token_vault, token_program, transfer_hook) = if a_to_b {
+        (
+            &ctx.accounts.token_mint_b,
+            &ctx.accounts.token_vault_b,
+            &ctx.accounts.token_program_b,
+            &remaining_accounts.transfer_hook_b,
+        )
+    } else {
+        (
+            &ctx.accounts.token_mint_a,
+            &ctx.accounts.token_vault_a,
+            &ctx.accounts.token_program_a,
+            &remaining_accounts.transfer_hook_a,
+        )
+    };
+    if calculate_transfer_fee_excluded_amount(token_mint, owner_amount)?.amount
+        < ctx.accounts.trigger_order.min_amount_out
+    {
+        return Err(ErrorCode::AmountOutBelowMinimumError.into());
+    }
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.ai_dex_pool,
+        token_mint,
+        token_vault,
+        &ctx.accounts.keeper_token_account,
+        token_program,
+        &ctx.accounts.memo_program,
+        transfer_hook,
+        bounty,
+        transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
+    )?;
+    transfer_from_vault_to_owner(
+        &ctx.accounts.ai_dex_pool,
+        token_mint,
+        token_vault,
+        &ctx.accounts.owner_token_account,
+        token_program,
+        &ctx.accounts.memo_program,
+        transfer_hook,
+        owner_amount,
+        transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
+    )?;
+
+    emit_cpi!(TriggerOrderExecutedEvent {
+        keeper: ctx.accounts.keeper.key(),
+        ai_dex_pool: ai_dex_pool_key,
+        position: ctx.accounts.position.key(),
+        trigger_order: ctx.accounts.trigger_order.key(),
+        sqrt_price,
+        a_to_b,
+        liquidity_amount,
+        delta_a,
+        delta_b,
+        swap_amount_in,
+        swap_amount_out,
+        amount,
+        bounty,
+        timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::util::{verify_pool_allowlist, verify_position_authority};
+
+#[event]
+pub struct TriggerOrderInitializedEvent {
+    pub position_authority: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub position: Pubkey,
+    pub trigger_order: Pubkey,
+    pub trigger_sqrt_price_lower: u128,
+    pub trigger_sqrt_price_upper: u128,
+    pub a_to_b: bool,
+    pub min_amount_out: u64,
+    pub bounty_bps: u16,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeTriggerOrder<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut)]
+    pub position_authority: Signer<'info>,
+
+    #[account(has_one = ai_dex_pool)]
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = position_authority,
+        seeds = [
+            b"trigger_order",
+            position.key().as_ref(),
+        ],
+        bump,
+        space = TriggerOrder::LEN
+    )]
+    pub trigger_order: Account<'info, TriggerOrder>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The swap allowlist of the pool, required while it is enabled
+    #[account(seeds = [b"swap_allowlist", ai_dex_pool.key().as_ref()], bump)]
+    pub swap_allowlist: Option<Box<Account<'info, PoolAllowlist>>>,
+
+    /// The token account of the position authority holding a gating token of the swap allowlist, if any
+    pub swap_gating_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+}
+
+/// Attaches a stop-loss or take-profit order to a position.
+///
+/// Once the sqrt price of the pool crosses one of the triggers, any keeper can withdraw all the
+/// liquidity of the position into the target token with `trigger_order`, keeping `bounty_bps` of it.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the operation.
+/// * `trigger_sqrt_price_lower` - The stop-loss sqrt price below the current price, `0` if unset.
+/// * `trigger_sqrt_price_upper` - The take-profit sqrt price above the current price, `0` if unset.
+/// * `a_to_b` - If true the target token is token B, otherwise token A.
+/// * `min_amount_out` - The minimum amount of the target token the owner receives.
+/// * `bounty_bps` - The share of the target token paid to the keeper, in basis points.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the operation is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::ZeroLiquidityError` - If the position has no liquidity.
+/// * `ErrorCode::InvalidTriggerOrderError` - If the triggers or the bounty are invalid.
+pub fn initialize_trigger_order_handler(
+    ctx: Context<InitializeTriggerOrder>,
+    trigger_sqrt_price_lower: u128,
+    trigger_sqrt_price_upper: u128,
+    a_to_b: bool,
+    min_amount_out: u64,
+    bounty_bps: u16,
+) -> Result<()> {
+    verify_pool_allowlist(
+        &*ctx.accounts.ai_dex_pool.load()?,
+        PoolAllowlistKind::Swap,
+        ctx.accounts.swap_allowlist.as_deref(),
+        ctx.accounts.position_authority.key,
+        ctx.accounts.swap_gating_token_account.as_deref(),
+    )?;
+
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    if ctx.accounts.position.liquidity == 0 {
+        return Err(ErrorCode::ZeroLiquidityError.into());
+    }
+
+    let sqrt_price = ctx.accounts.ai_dex_pool.load()?.sqrt_price;
+    ctx.accounts.trigger_order.initialize(
+        ctx.accounts.position.key(),
+        ctx.accounts.position_authority.key(),
+        sqrt_price,
+        trigger_sqrt_price_lower,
+        trigger_sqrt_price_upper,
+        a_to_b,
+        min_amount_out,
+        bounty_bps,
+    )?;
+
+    emit_cpi!(TriggerOrderInitializedEvent {
+        position_authority: ctx.accounts.position_authority.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        position: ctx.accounts.position.key(),
+        trigger_order: ctx.accounts.trigger_order.key(),
+        trigger_sqrt_price_lower,
+        trigger_sqrt_price_upper,
+        a_to_b,
+        min_amount_out,
+        bounty_bps,
+    });
+
+    Ok(())
+}
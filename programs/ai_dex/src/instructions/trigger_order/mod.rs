@@ -0,0 +1,7 @@
+pub mod cancel_trigger_order;
+pub mod execute_trigger_order;
+pub mod initialize_trigger_order;
+
+pub use cancel_trigger_order::*;
+pub use execute_trigger_order::*;
+pub use initialize_trigger_order::*;
@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::state::*;
+use crate::util::{
+    parse_remaining_accounts, swap_within_vaults, to_timestamp_u64, AccountsType,
+    RemainingAccountsInfo,
+};
+
+#[event]
+pub struct TwapTrancheExecutedEvent {
+    pub ai_dex_pool: Pubkey,
+    pub twap_order: Pubkey,
+    pub a_to_b: bool,
+    pub tranche_index: u16,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub amount_in_remaining: u64,
+    pub timestamp: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteTwapOrder<'info> {
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut, has_one = ai_dex_pool)]
+    pub twap_order: Box<Account<'info, TwapOrder>>,
+
+    /// CHECK: The first tick array of the swap, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_0: UncheckedAccount<'info>,
+    /// CHECK: The second tick array of the swap, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_1: UncheckedAccount<'info>,
+    /// CHECK: The third tick array of the swap, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_2: UncheckedAccount<'info>,
+
+    /// CHECK: The oracle account of the pool. An observation is recorded only if it has been initialized.
+    #[account(mut, seeds = [b"oracle", ai_dex_pool.key().as_ref()], bump)]
+    pub oracle: UncheckedAccount<'info>,
+
+    /// CHECK: The tick array bitmap of the pool. Used only if it has been initialized.
+    #[account(seeds = [b"tick_array_bitmap", ai_dex_pool.key().as_ref()], bump)]
+    pub tick_array_bitmap: UncheckedAccount<'info>,
+
+    /// The token vaults of the pool, whose balances bound the volume swapped out within a slot
+    #[account(address = ai_dex_pool.load()?.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = ai_dex_pool.load()?.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+}
+
+/// Executes the next tranche of a TWAP order.
+///
+/// The instruction is permissionless. Only one tranche is executed per call, and only once it is
+/// due, so a keeper can never swap more than the tranche size at a time. The input and the output
+/// never leave the vaults of the pool; the output is credited to the order.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the execution.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the tranche is executed, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::TwapTrancheNotDueError` - If the next tranche is not due yet.
+/// * `ErrorCode::NoTradableAmountError` - If all the tranches have been executed.
+/// * `ErrorCode::PartialFillError` - If the price guard or the tick arrays stop the swap early.
+/// * `ErrorCode::SlotVolumeExceededError` - If the volume swapped out within the slot exceeds the cap of the pool.
+/// * `ErrorCode::SandwichSwapError` - If the owner swapped in the opposite direction within the slot.
+/// * `ErrorCode::SwapHookNotSupportedError` - If the pool has a swap hook, which order fills do not call.
+pub fn execute_twap_order_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ExecuteTwapOrder<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let amount = ctx.accounts.twap_order.next_tranche_amount(timestamp)?;
+
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::SupplementalTickArrays],
+    )?;
+
+    let a_to_b = ctx.accounts.twap_order.a_to_b;
+    let tranche_index = ctx.accounts.twap_order.tranches_executed;
+    let ai_dex_pool_key = ctx.accounts.ai_dex_pool.key();
+    let (amount_in, amount_out) = swap_within_vaults(
+        &mut *ctx.accounts.ai_dex_pool.load_mut()?,
+        &ai_dex_pool_key,
+        &ctx.accounts.twap_order.owner,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.tick_array_0,
+        &ctx.accounts.tick_array_1,
+        &ctx.accounts.tick_array_2,
+        &remaining_accounts.supplemental_tick_arrays,
+        &ctx.accounts.tick_array_bitmap,
+        &ctx.accounts.oracle,
+        amount,
+        ctx.accounts.twap_order.sqrt_price_limit,
+        a_to_b,
+        timestamp,
+    )?;
+    ctx.accounts.twap_order.record_tranche(amount_in, amount_out)?;
+
+    emit_cpi!(TwapTrancheExecutedEvent {
+        ai_dex_pool: ai_dex_pool_key,
+        twap_order: ctx.accounts.twap_order.key(),
+        a_to_b,
+        tranche_index,
+        amount_in,
+        amount_out,
+        amount_in_remaining: ctx.accounts.twap_order.amount_in,
+        timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,7 @@
+pub mod close_twap_order;
+pub mod execute_twap_order;
+pub mod open_twap_order;
+
+pub use close_twap_order::*;
+pub use execute_twap_order::*;
+pub use open_twap_order::*;
@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::*;
+use crate::util::{
+    calculate_transfer_fee_excluded_amount, parse_remaining_accounts, to_timestamp_u64,
+    transfer_from_owner_to_vault, verify_pool_allowlist, AccountsType, RemainingAccountsInfo,
+};
+
+#[event]
+pub struct TwapOrderOpenedEvent {
+    pub owner: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub twap_order: Pubkey,
+    pub a_to_b: bool,
+    pub amount: u64,
+    pub amount_in: u64,
+    pub num_tranches: u16,
+    pub tranche_amount: u64,
+    pub tranche_interval: u64,
+    pub sqrt_price_limit: u128,
+    pub start_timestamp: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(a_to_b: bool)]
+pub struct OpenTwapOrder<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [
+            b"twap_order",
+            ai_dex_pool.key().as_ref(),
+            owner.key().as_ref(),
+            &[a_to_b as u8],
+        ],
+        bump,
+        space = TwapOrder::LEN
+    )]
+    pub twap_order: Box<Account<'info, TwapOrder>>,
+
+    #[account(address = ai_dex_pool.load()?.input_token_mint(a_to_b))]
+    pub token_mint_input: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, constraint = token_owner_account_input.mint == token_mint_input.key())]
+    pub token_owner_account_input: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = ai_dex_pool.load()?.input_token_vault(a_to_b))]
+    pub token_vault_input: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = token_mint_input.to_account_info().owner.clone())]
+    pub token_program_input: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+    pub system_program: Program<'info, System>,
+
+    /// The swap allowlist of the pool, required while it is enabled
+    #[account(seeds = [b"swap_allowlist", ai_dex_pool.key().as_ref()], bump)]
+    pub swap_allowlist: Option<Box<Account<'info, PoolAllowlist>>>,
+
+    /// The token account of the owner holding a gating token of the swap allowlist, if any
+    pub swap_gating_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+}
+
+/// Opens a TWAP order splitting a large swap into equal tranches over a time window.
+///
+/// The input is transferred into the vault of the pool. The first tranche is due immediately and
+/// the next ones every `duration / num_tranches` seconds; any keeper can execute a due tranche
+/// with `execute_twap_order`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required to open the order.
+/// * `a_to_b` - If true the order sells token A for token B, otherwise token B for token A.
+/// * `amount` - The amount of input token transferred from the owner.
+/// * `num_tranches` - The number of tranches the input is split into.
+/// * `duration` - The length of the window in seconds.
+/// * `sqrt_price_limit` - The square root price a tranche cannot move the price past. `0` means no limit.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the order is opened, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidTwapOrderError` - If a tranche would be empty or shorter than one second.
+pub fn open_twap_order_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, OpenTwapOrder<'info>>,
+    a_to_b: bool,
+    amount: u64,
+    num_tranches: u16,
+    duration: u64,
+    sqrt_price_limit: u128,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    verify_pool_allowlist(
+        &*ctx.accounts.ai_dex_pool.load()?,
+        PoolAllowlistKind::Swap,
+        ctx.accounts.swap_allowlist.as_deref(),
+        ctx.accounts.owner.key,
+        ctx.accounts.swap_gating_token_account.as_deref(),
+    )?;
+
+    let start_timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::TransferHookInput],
+    )?;
+
+    // The order only swaps what the vault receives
+    let amount_in = calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_input, amount)?.amount;
+    ctx.accounts.twap_order.initialize(
+        ctx.accounts.ai_dex_pool.key(),
+        ctx.accounts.owner.key(),
+        a_to_b,
+        amount_in,
+        num_tranches,
+        start_timestamp,
+        duration,
+        sqrt_price_limit,
+    )?;
+
+    transfer_from_owner_to_vault(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.owner,
+        &ctx.accounts.token_mint_input,
+        &ctx.accounts.token_owner_account_input,
+        &ctx.accounts.token_vault_input,
+        &ctx.accounts.token_program_input,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_input,
+        amount,
+    )?;
+
+    emit_cpi!(TwapOrderOpenedEvent {
+        owner: ctx.accounts.owner.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        twap_order: ctx.accounts.twap_order.key(),
+        a_to_b,
+        amount,
+        amount_in,
+        num_tranches,
+        tranche_amount: ctx.accounts.twap_order.tranche_amount,
+        tranche_interval: ctx.accounts.twap_order.tranche_interval,
+        sqrt_price_limit,
+        start_timestamp,
+    });
+
+    Ok(())
+}
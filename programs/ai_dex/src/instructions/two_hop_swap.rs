@@ -6,8 +6,12 @@ use crate::swap_with_transfer_fee_extension;
 use crate::util::{calculate_transfer_fee_excluded_amount, parse_remaining_accounts, update_and_two_hop_swap_ai_dex, AccountsType, RemainingAccountsInfo};
 use crate::{
     errors::ErrorCode,
-    state::{TickArray, AiDexPool},
-    util::{to_timestamp_u64, SwapTickSequence},
+    state::{AiDexConfig, AiDexPool, PoolAllowlist, PoolAllowlistKind},
+    util::{
+        apply_tick_array_bitmap, check_deadline, load_supplemental_tick_arrays, oracle_volatility_ticks,
+        record_oracle_observation, record_pool_stats, to_timestamp_u64, verify_pool_allowlist, SwapTickSequence,
+        TickArrayAccount,
+    },
     constants::transfer_memo,
 };
 
@@ -42,8 +46,25 @@ pub struct TwoHopSwapEvent {
     pub tick_array_two_0: Pubkey,
     pub tick_array_two_1: Pubkey,
     pub tick_array_two_2: Pubkey,
+    pub input_amount: u64,
+    pub intermediate_amount: u64,
+    pub output_amount: u64,
+    pub lp_fee_one: u64,
+    pub protocol_fee_one: u64,
+    pub lp_fee_two: u64,
+    pub protocol_fee_two: u64,
+    pub input_transfer_fee: u64,
+    pub intermediate_transfer_fee: u64,
+    pub output_transfer_fee: u64,
+    pub pre_sqrt_price_one: u128,
+    pub post_sqrt_price_one: u128,
+    pub post_tick_index_one: i32,
+    pub pre_sqrt_price_two: u128,
+    pub post_sqrt_price_two: u128,
+    pub post_tick_index_two: i32,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(
     amount: u64,
@@ -56,22 +77,22 @@ pub struct TwoHopSwapEvent {
 pub struct TwoHopSwap<'info> {
     /// The first AiDex instance involved in the swap.
     #[account(mut)]
-    pub ai_dex_one: Box<Account<'info, AiDexPool>>,
+    pub ai_dex_one: AccountLoader<'info, AiDexPool>,
     
     /// The second AiDex instance involved in the swap.
     #[account(mut)]
-    pub ai_dex_two: Box<Account<'info, AiDexPool>>,
+    pub ai_dex_two: AccountLoader<'info, AiDexPool>,
 
     /// The mint account for the input token.
-    #[account(address = ai_dex_one.input_token_mint(a_to_b_one))]
+    #[account(address = ai_dex_one.load()?.input_token_mint(a_to_b_one))]
     pub token_mint_input: InterfaceAccount<'info, Mint>,
     
     /// The mint account for the intermediate token.
-    #[account(address = ai_dex_one.output_token_mint(a_to_b_one))]
+    #[account(address = ai_dex_one.load()?.output_token_mint(a_to_b_one))]
     pub token_mint_intermediate: InterfaceAccount<'info, Mint>,
     
     /// The mint account for the output token.
-    #[account(address = ai_dex_two.output_token_mint(a_to_b_two))]
+    #[account(address = ai_dex_two.load()?.output_token_mint(a_to_b_two))]
     pub token_mint_output: InterfaceAccount<'info, Mint>,
 
     /// The token program for the input token.
@@ -91,19 +112,19 @@ pub struct TwoHopSwap<'info> {
     pub token_owner_account_input: Box<InterfaceAccount<'info, TokenAccount>>,
     
     /// The token vault for the input token in the first AiDex.
-    #[account(mut, address = ai_dex_one.input_token_vault(a_to_b_one))]
+    #[account(mut, address = ai_dex_one.load()?.input_token_vault(a_to_b_one))]
     pub token_vault_one_input: Box<InterfaceAccount<'info, TokenAccount>>,
     
     /// The token vault for the intermediate token in the first AiDex.
-    #[account(mut, address = ai_dex_one.output_token_vault(a_to_b_one))]
+    #[account(mut, address = ai_dex_one.load()?.output_token_vault(a_to_b_one))]
     pub token_vault_one_intermediate: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The token vault for the intermediate token in the second AiDex.
-    #[account(mut, address = ai_dex_two.input_token_vault(a_to_b_two))]
+    #[account(mut, address = ai_dex_two.load()?.input_token_vault(a_to_b_two))]
     pub token_vault_two_intermediate: Box<InterfaceAccount<'info, TokenAccount>>,
     
     /// The token vault for the output token in the second AiDex.
-    #[account(mut, address = ai_dex_two.output_token_vault(a_to_b_two))]
+    #[account(mut, address = ai_dex_two.load()?.output_token_vault(a_to_b_two))]
     pub token_vault_two_output: Box<InterfaceAccount<'info, TokenAccount>>,
     
     /// The token account of the owner for the output token.
@@ -113,41 +134,79 @@ pub struct TwoHopSwap<'info> {
     /// The authority that signs the transaction.
     pub token_authority: Signer<'info>,
 
-    /// The first tick array for the first AiDex.
-    #[account(mut, constraint = tick_array_one_0.load()?.ai_dex_pool == ai_dex_one.key())]
-    pub tick_array_one_0: AccountLoader<'info, TickArray>,
+    /// CHECK: The first tick array for the first AiDex, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_one_0: UncheckedAccount<'info>,
 
-    /// The second tick array for the first AiDex.
-    #[account(mut, constraint = tick_array_one_1.load()?.ai_dex_pool == ai_dex_one.key())]
-    pub tick_array_one_1: AccountLoader<'info, TickArray>,
+    /// CHECK: The second tick array for the first AiDex, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_one_1: UncheckedAccount<'info>,
 
-    /// The third tick array for the first AiDex.
-    #[account(mut, constraint = tick_array_one_2.load()?.ai_dex_pool == ai_dex_one.key())]
-    pub tick_array_one_2: AccountLoader<'info, TickArray>,
+    /// CHECK: The third tick array for the first AiDex, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_one_2: UncheckedAccount<'info>,
 
-    /// The first tick array for the second AiDex.
-    #[account(mut, constraint = tick_array_two_0.load()?.ai_dex_pool == ai_dex_two.key())]
-    pub tick_array_two_0: AccountLoader<'info, TickArray>,
+    /// CHECK: The first tick array for the second AiDex, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_two_0: UncheckedAccount<'info>,
 
-    /// The second tick array for the second AiDex.
-    #[account(mut, constraint = tick_array_two_1.load()?.ai_dex_pool == ai_dex_two.key())]
-    pub tick_array_two_1: AccountLoader<'info, TickArray>,
+    /// CHECK: The second tick array for the second AiDex, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_two_1: UncheckedAccount<'info>,
 
-    /// The third tick array for the second AiDex.
-    #[account(mut, constraint = tick_array_two_2.load()?.ai_dex_pool == ai_dex_two.key())]
-    pub tick_array_two_2: AccountLoader<'info, TickArray>,
+    /// CHECK: The third tick array for the second AiDex, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_two_2: UncheckedAccount<'info>,
 
-    /// CHECK: The oracle account for the first AiDex (is currently unused).
+    /// CHECK: The oracle account for the first AiDex. An observation is recorded only if it has been initialized.
     #[account(mut, seeds = [b"oracle", ai_dex_one.key().as_ref()], bump)]
     pub oracle_one: UncheckedAccount<'info>,
 
-    /// CHECK: The oracle account for the second AiDex (is currently unused).
+    /// CHECK: The oracle account for the second AiDex. An observation is recorded only if it has been initialized.
     #[account(mut, seeds = [b"oracle", ai_dex_two.key().as_ref()], bump)]
     pub oracle_two: UncheckedAccount<'info>,
 
+    /// CHECK: The statistics account for the first AiDex. The swap is recorded only if it has been initialized.
+    #[account(mut, seeds = [b"pool_stats", ai_dex_one.key().as_ref()], bump)]
+    pub pool_stats_one: UncheckedAccount<'info>,
+
+    /// CHECK: The statistics account for the second AiDex. The swap is recorded only if it has been initialized.
+    #[account(mut, seeds = [b"pool_stats", ai_dex_two.key().as_ref()], bump)]
+    pub pool_stats_two: UncheckedAccount<'info>,
+
+    /// CHECK: The tick array bitmap for the first AiDex. Used only if it has been initialized.
+    #[account(seeds = [b"tick_array_bitmap", ai_dex_one.key().as_ref()], bump)]
+    pub tick_array_bitmap_one: UncheckedAccount<'info>,
+
+    /// CHECK: The tick array bitmap for the second AiDex. Used only if it has been initialized.
+    #[account(seeds = [b"tick_array_bitmap", ai_dex_two.key().as_ref()], bump)]
+    pub tick_array_bitmap_two: UncheckedAccount<'info>,
+
     /// The memo program.
     pub memo_program: Program<'info, Memo>,
 
+    /// The config of both pools, checked against the protocol-wide emergency pause
+    #[account(
+        address = ai_dex_one.load()?.ai_dex_config,
+        constraint = ai_dex_two.load()?.ai_dex_config == ai_dex_config.key(),
+        constraint = !ai_dex_config.paused @ ErrorCode::ProtocolPausedError
+    )]
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    /// The swap allowlist of the first pool, required while it is enabled
+    #[account(seeds = [b"swap_allowlist", ai_dex_one.key().as_ref()], bump)]
+    pub swap_allowlist_one: Option<Box<Account<'info, PoolAllowlist>>>,
+
+    /// The swap allowlist of the second pool, required while it is enabled
+    #[account(seeds = [b"swap_allowlist", ai_dex_two.key().as_ref()], bump)]
+    pub swap_allowlist_two: Option<Box<Account<'info, PoolAllowlist>>>,
+
+    /// The token account of the token authority holding a gating token of the first swap allowlist, if any
+    pub swap_gating_token_account_one: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The token account of the token authority holding a gating token of the second swap allowlist, if any
+    pub swap_gating_token_account_two: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
     // Remaining accounts:
     // - Accounts for transfer hook program of token_mint_input
     // - Accounts for transfer hook program of token_mint_intermediate
@@ -171,6 +230,7 @@ pub struct TwoHopSwap<'info> {
 /// * `sqrt_price_limit_one` - The square root price limit for the first swap.
 /// * `sqrt_price_limit_two` - The square root price limit for the second swap.
 /// * `remaining_accounts_info` - Optional information about remaining accounts.
+/// * `deadline_timestamp` - Optional unix timestamp after which the transaction is rejected.
 ///
 /// # Returns
 ///
@@ -179,7 +239,10 @@ pub struct TwoHopSwap<'info> {
 /// # Errors
 ///
 /// This function can return errors in the following cases:
+/// * Transaction expired error if the deadline has passed.
 /// * Duplicate two-hop pool error if the same pool is used for both swaps.
+/// * Swap hook not supported error if a pool has a swap hook, which two-hop swaps do not call.
+/// * Authority not allowlisted error if a pool restricts swapping to its allowlist and the token authority is not allowed.
 /// * Invalid intermediary mint error if the intermediary token does not match.
 /// * Amount mismatch error if the output of the first swap does not match the input of the second swap.
 /// * Amount out below minimum error if the output amount is less than the specified threshold.
@@ -194,26 +257,45 @@ pub fn two_hop_swap_handler<'a, 'b, 'c, 'info>(
     sqrt_price_limit_one: u128,
     sqrt_price_limit_two: u128,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
+    deadline_timestamp: Option<i64>,
 ) -> Result<()> {
     let clock = Clock::get()?;
+    check_deadline(deadline_timestamp, clock.unix_timestamp)?;
     // Update the global reward growth which increases as a function of time.
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
 
-    let ai_dex_one = &mut ctx.accounts.ai_dex_one;
-    let ai_dex_two = &mut ctx.accounts.ai_dex_two;
-    // Don't allow swaps on the same ai_dex
-    if ai_dex_one.key() == ai_dex_two.key() {
-        return Err(ErrorCode::DuplicateTwoHopPoolError.into());
-    }
+    let ai_dex_one = &ctx.accounts.ai_dex_one;
+    let ai_dex_two = &ctx.accounts.ai_dex_two;
+    verify_two_hop_pools(
+        &ai_dex_one.key(),
+        &*ai_dex_one.load()?,
+        &ai_dex_two.key(),
+        &*ai_dex_two.load()?,
+    )?;
+
+    verify_pool_allowlist(
+        &*ai_dex_one.load()?,
+        PoolAllowlistKind::Swap,
+        ctx.accounts.swap_allowlist_one.as_deref(),
+        ctx.accounts.token_authority.key,
+        ctx.accounts.swap_gating_token_account_one.as_deref(),
+    )?;
+    verify_pool_allowlist(
+        &*ai_dex_two.load()?,
+        PoolAllowlistKind::Swap,
+        ctx.accounts.swap_allowlist_two.as_deref(),
+        ctx.accounts.token_authority.key,
+        ctx.accounts.swap_gating_token_account_two.as_deref(),
+    )?;
 
     let swap_one_output_mint = match a_to_b_one {
-        true => ai_dex_one.token_mint_b,
-        false => ai_dex_one.token_mint_a,
+        true => ai_dex_one.load()?.token_mint_b,
+        false => ai_dex_one.load()?.token_mint_a,
     };
 
     let swap_two_input_mint = match a_to_b_two {
-        true => ai_dex_two.token_mint_a,
-        false => ai_dex_two.token_mint_b,
+        true => ai_dex_two.load()?.token_mint_a,
+        false => ai_dex_two.load()?.token_mint_b,
     };
 
     if swap_one_output_mint != swap_two_input_mint {
@@ -227,29 +309,68 @@ pub fn two_hop_swap_handler<'a, 'b, 'c, 'info>(
             AccountsType::TransferHookInput,
             AccountsType::TransferHookIntermediate,
             AccountsType::TransferHookOutput,
+            AccountsType::SupplementalTickArraysOne,
+            AccountsType::SupplementalTickArraysTwo,
         ],
     )?;
 
-    let mut swap_tick_sequence_one = SwapTickSequence::new(
-        ctx.accounts.tick_array_one_0.load_mut().unwrap(),
-        ctx.accounts.tick_array_one_1.load_mut().ok(),
-        ctx.accounts.tick_array_one_2.load_mut().ok(),
+    let tick_array_one_0 = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_one_0, &ai_dex_one.key())?;
+    let tick_array_one_1 = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_one_1, &ai_dex_one.key())?;
+    let tick_array_one_2 = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_one_2, &ai_dex_one.key())?;
+    let tick_array_two_0 = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_two_0, &ai_dex_two.key())?;
+    let tick_array_two_1 = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_two_1, &ai_dex_two.key())?;
+    let tick_array_two_2 = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_two_2, &ai_dex_two.key())?;
+    let supplemental_tick_arrays_one = load_supplemental_tick_arrays(
+        &remaining_accounts.supplemental_tick_arrays_one,
+        &ai_dex_one.key(),
+    )?;
+    let supplemental_tick_arrays_two = load_supplemental_tick_arrays(
+        &remaining_accounts.supplemental_tick_arrays_two,
+        &ai_dex_two.key(),
+    )?;
+
+    let mut swap_tick_sequence_one = SwapTickSequence::new_with_tick_arrays(
+        tick_array_one_0.load_mut()?,
+        tick_array_one_1.load_mut().ok(),
+        tick_array_one_2.load_mut().ok(),
     );
+    for tick_array in supplemental_tick_arrays_one.iter() {
+        swap_tick_sequence_one.push_tick_array(tick_array.load_mut()?);
+    }
 
-    let mut swap_tick_sequence_two = SwapTickSequence::new(
-        ctx.accounts.tick_array_two_0.load_mut().unwrap(),
-        ctx.accounts.tick_array_two_1.load_mut().ok(),
-        ctx.accounts.tick_array_two_2.load_mut().ok(),
+    let mut swap_tick_sequence_two = SwapTickSequence::new_with_tick_arrays(
+        tick_array_two_0.load_mut()?,
+        tick_array_two_1.load_mut().ok(),
+        tick_array_two_2.load_mut().ok(),
     );
-    // TODO: WLOG, we could extend this to N-swaps, but the account inputs to the instruction would
-    // need to be jankier and we may need to programatically map/verify rather than using anchor constraints
+    for tick_array in supplemental_tick_arrays_two.iter() {
+        swap_tick_sequence_two.push_tick_array(tick_array.load_mut()?);
+    }
+    apply_tick_array_bitmap(
+        &ctx.accounts.tick_array_bitmap_one,
+        &mut swap_tick_sequence_one,
+        ai_dex_one.load()?.tick_spacing,
+        a_to_b_one,
+    )?;
+    apply_tick_array_bitmap(
+        &ctx.accounts.tick_array_bitmap_two,
+        &mut swap_tick_sequence_two,
+        ai_dex_two.load()?.tick_spacing,
+        a_to_b_two,
+    )?;
+    let volatility_ticks_one = oracle_volatility_ticks(&ctx.accounts.oracle_one, &*ai_dex_one.load()?, timestamp)?;
+    let volatility_ticks_two = oracle_volatility_ticks(&ctx.accounts.oracle_two, &*ai_dex_two.load()?, timestamp)?;
+    let pre_sqrt_price_one = ai_dex_one.load()?.sqrt_price;
+    let pre_sqrt_price_two = ai_dex_two.load()?.sqrt_price;
+    // Routes with more than two hops are handled by `multi_hop_swap`, which maps and verifies
+    // the accounts of each hop programmatically instead of using anchor constraints.
     let (swap_update_one, swap_update_two) = match amount_specified_is_input {
         true => {
             // If the amount specified is input, this means we are doing exact-in
             // and the swap calculations occur from Swap 1 => Swap 2
             // and the swaps occur from Swap 1 => Swap 2
             let swap_calc_one = swap_with_transfer_fee_extension(
-                &ai_dex_one,
+                &*ai_dex_one.load()?,
                 if a_to_b_one { &ctx.accounts.token_mint_input } else { &ctx.accounts.token_mint_intermediate },
                 if a_to_b_one { &ctx.accounts.token_mint_intermediate } else { &ctx.accounts.token_mint_input },
                 &mut swap_tick_sequence_one,
@@ -258,6 +379,9 @@ pub fn two_hop_swap_handler<'a, 'b, 'c, 'info>(
                 true,
                 a_to_b_one,
                 timestamp,
+                0,
+                0,
+                volatility_ticks_one,
             )?;
             // Swap two input is the output of swap one
             // We use vault to vault transfer, so transfer fee will be collected once.
@@ -267,7 +391,7 @@ pub fn two_hop_swap_handler<'a, 'b, 'c, 'info>(
             };
 
             let swap_calc_two = swap_with_transfer_fee_extension(
-                &ai_dex_two,
+                &*ai_dex_two.load()?,
                 if a_to_b_two { &ctx.accounts.token_mint_intermediate } else { &ctx.accounts.token_mint_output },
                 if a_to_b_two { &ctx.accounts.token_mint_output } else { &ctx.accounts.token_mint_intermediate },
                 &mut swap_tick_sequence_two,
@@ -276,6 +400,9 @@ pub fn two_hop_swap_handler<'a, 'b, 'c, 'info>(
                 true,
                 a_to_b_two,
                 timestamp,
+                0,
+                0,
+                volatility_ticks_two,
             )?;
             (swap_calc_one, swap_calc_two)
         },
@@ -284,7 +411,7 @@ pub fn two_hop_swap_handler<'a, 'b, 'c, 'info>(
             // and the swap calculations occur from Swap 2 => Swap 1
             // but the actual swaps occur from Swap 1 => Swap 2 (to ensure that the intermediate token exists in the account)
             let swap_calc_two = swap_with_transfer_fee_extension(
-                &ai_dex_two,
+                &*ai_dex_two.load()?,
                 if a_to_b_two { &ctx.accounts.token_mint_intermediate } else { &ctx.accounts.token_mint_output },
                 if a_to_b_two { &ctx.accounts.token_mint_output } else { &ctx.accounts.token_mint_intermediate },
                 &mut swap_tick_sequence_two,
@@ -293,6 +420,9 @@ pub fn two_hop_swap_handler<'a, 'b, 'c, 'info>(
                 false,
                 a_to_b_two,
                 timestamp,
+                0,
+                0,
+                volatility_ticks_two,
             )?;
             // The output of swap 1 is input of swap_calc_two
             let swap_one_output_amount = match a_to_b_two {
@@ -307,7 +437,7 @@ pub fn two_hop_swap_handler<'a, 'b, 'c, 'info>(
             };
 
             let swap_calc_one = swap_with_transfer_fee_extension(
-                &ai_dex_one,
+                &*ai_dex_one.load()?,
                 if a_to_b_one { &ctx.accounts.token_mint_input } else { &ctx.accounts.token_mint_intermediate },
                 if a_to_b_one { &ctx.accounts.token_mint_intermediate } else { &ctx.accounts.token_mint_input },
                 &mut swap_tick_sequence_one,
@@ -316,6 +446,9 @@ pub fn two_hop_swap_handler<'a, 'b, 'c, 'info>(
                 false,
                 a_to_b_one,
                 timestamp,
+                0,
+                0,
+                volatility_ticks_one,
             )?;
             (swap_calc_one, swap_calc_two)
         },
@@ -364,6 +497,30 @@ pub fn two_hop_swap_handler<'a, 'b, 'c, 'info>(
         }
     }
 
+    record_oracle_observation(&ctx.accounts.oracle_one, &*ai_dex_one.load()?, timestamp)?;
+    record_oracle_observation(&ctx.accounts.oracle_two, &*ai_dex_two.load()?, timestamp)?;
+    record_pool_stats(&ctx.accounts.pool_stats_one, &swap_update_one, a_to_b_one, timestamp)?;
+    record_pool_stats(&ctx.accounts.pool_stats_two, &swap_update_two, a_to_b_two, timestamp)?;
+
+    // The intermediate token moves from vault to vault, so its transfer fee is charged once
+    let input_amount = if a_to_b_one { swap_update_one.amount_a } else { swap_update_one.amount_b };
+    let input_transfer_fee = calculate_transfer_fee_excluded_amount(
+        &ctx.accounts.token_mint_input,
+        input_amount,
+    )?.transfer_fee;
+    let intermediate = calculate_transfer_fee_excluded_amount(
+        &ctx.accounts.token_mint_intermediate,
+        swap_calc_one_output,
+    )?;
+    let output = calculate_transfer_fee_excluded_amount(
+        &ctx.accounts.token_mint_output,
+        if a_to_b_two { swap_update_two.amount_b } else { swap_update_two.amount_a },
+    )?;
+    let (lp_fee_one, protocol_fee_one) = (swap_update_one.lp_fee(), swap_update_one.next_protocol_fee);
+    let (lp_fee_two, protocol_fee_two) = (swap_update_two.lp_fee(), swap_update_two.next_protocol_fee);
+    let (post_sqrt_price_one, post_tick_index_one) = (swap_update_one.next_sqrt_price, swap_update_one.next_tick_index);
+    let (post_sqrt_price_two, post_tick_index_two) = (swap_update_two.next_sqrt_price, swap_update_two.next_tick_index);
+
     update_and_two_hop_swap_ai_dex(
         swap_update_one,
         swap_update_two,
@@ -392,7 +549,7 @@ pub fn two_hop_swap_handler<'a, 'b, 'c, 'info>(
         transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
     )?;
 
-    emit!(TwoHopSwapEvent {
+    emit_cpi!(TwoHopSwapEvent {
         ai_dex_one: ai_dex_one.key(),
         ai_dex_two: ai_dex_two.key(),
         amount,
@@ -422,7 +579,64 @@ pub fn two_hop_swap_handler<'a, 'b, 'c, 'info>(
         tick_array_two_0: ctx.accounts.tick_array_two_0.key(),
         tick_array_two_1: ctx.accounts.tick_array_two_1.key(),
         tick_array_two_2: ctx.accounts.tick_array_two_2.key(),
+        input_amount,
+        intermediate_amount: intermediate.amount,
+        output_amount: output.amount,
+        lp_fee_one,
+        protocol_fee_one,
+        lp_fee_two,
+        protocol_fee_two,
+        input_transfer_fee,
+        intermediate_transfer_fee: intermediate.transfer_fee,
+        output_transfer_fee: output.transfer_fee,
+        pre_sqrt_price_one,
+        post_sqrt_price_one,
+        post_tick_index_one,
+        pre_sqrt_price_two,
+        post_sqrt_price_two,
+        post_tick_index_two,
     });
 
     Ok(())
 }
+
+// Don't allow swaps on the same ai_dex, nor on a pool whose swap hook would be skipped
+fn verify_two_hop_pools(
+    ai_dex_one_key: &Pubkey,
+    ai_dex_one: &AiDexPool,
+    ai_dex_two_key: &Pubkey,
+    ai_dex_two: &AiDexPool,
+) -> Result<()> {
+    if ai_dex_one_key == ai_dex_two_key {
+        return Err(ErrorCode::DuplicateTwoHopPoolError.into());
+    }
+    ai_dex_one.check_swap_hook_not_set()?;
+    ai_dex_two.check_swap_hook_not_set()
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_two_hop_swap_rejects_same_pool() {
+        let key = Pubkey::new_unique();
+        let ai_dex = AiDexPool::default();
+
+        let result = verify_two_hop_pools(&key, &ai_dex, &key, &ai_dex);
+        assert_eq!(result.unwrap_err(), ErrorCode::DuplicateTwoHopPoolError.into());
+        assert!(verify_two_hop_pools(&key, &ai_dex, &Pubkey::new_unique(), &ai_dex).is_ok());
+    }
+
+    #[test]
+    fn test_two_hop_swap_rejects_pool_with_swap_hook() {
+        let ai_dex = AiDexPool::default();
+        let mut hooked_ai_dex = AiDexPool::default();
+        hooked_ai_dex.set_swap_hook_program(Pubkey::new_unique());
+
+        for (ai_dex_one, ai_dex_two) in [(&hooked_ai_dex, &ai_dex), (&ai_dex, &hooked_ai_dex)] {
+            let result = verify_two_hop_pools(&Pubkey::new_unique(), ai_dex_one, &Pubkey::new_unique(), ai_dex_two);
+            assert_eq!(result.unwrap_err(), ErrorCode::SwapHookNotSupportedError.into());
+        }
+    }
+}
@@ -0,0 +1,393 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface};
+use std::convert::TryFrom;
+
+use crate::errors::ErrorCode;
+use crate::math::convert_to_liquidity_delta;
+use crate::orchestrator::liquidity_orchestrator::{calculate_fee_and_reward_growths, calculate_liquidity_token_deltas};
+use crate::state::*;
+use crate::util::{calculate_transfer_fee_included_amount, mint_vault_shares, to_timestamp_u64, transfer_from_owner_to_vault, TickArrayAccount};
+
+// Remaining accounts passed for each position of the vault: position, tick array lower and tick array upper.
+pub(crate) const ACCOUNTS_PER_VAULT_POSITION: usize = 3;
+
+// Remaining accounts passed for each initialized reward after the positions: reward owner account,
+// reward mint, reward vault and reward token program.
+pub(crate) const ACCOUNTS_PER_VAULT_REWARD: usize = 4;
+
+#[event]
+pub struct VaultDepositedEvent {
+    pub token_authority: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub vault: Pubkey,
+    pub share_amount: u64,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub reward_amounts: [u64; NUM_REWARDS],
+    pub transfer_fee_included_amount_a: u64,
+    pub transfer_fee_included_amount_b: u64,
+    pub timestamp: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ModifyVault<'info> {
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(has_one = ai_dex_pool, has_one = share_mint)]
+    pub vault: Box<Account<'info, Vault>>,
+
+    #[account(mut)]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut, token::mint = share_mint)]
+    pub share_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Pays the rent when a tick of a dynamic tick array gets initialized.
+    #[account(mut)]
+    pub token_authority: Signer<'info>,
+
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, MintInterface>,
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, MintInterface>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == ai_dex_pool.load()?.token_mint_a)]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+    #[account(mut, constraint = token_owner_account_b.mint == ai_dex_pool.load()?.token_mint_b)]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    #[account(mut, address = vault.token_account_a)]
+    pub vault_token_account_a: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+    #[account(mut, address = vault.token_account_b)]
+    pub vault_token_account_b: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    #[account(address = token_mint_a.to_account_info().owner.clone())]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = token_mint_b.to_account_info().owner.clone())]
+    pub token_program_b: Interface<'info, TokenInterface>,
+
+    pub memo_program: Program<'info, Memo>,
+    pub system_program: Program<'info, System>,
+
+    /// The config of the pool, checked against the protocol-wide emergency pause
+    #[account(
+        address = ai_dex_pool.load()?.ai_dex_config,
+        constraint = !ai_dex_config.paused @ ErrorCode::ProtocolPausedError
+    )]
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+}
+
+/// Loads a position of a vault from the remaining accounts, and updates its fees and rewards.
+///
+/// # Arguments
+///
+/// * `ai_dex` - The pool of the vault.
+/// * `ai_dex_pool_key` - The pubkey of the pool.
+/// * `vault` - The vault holding the position.
+/// * `index` - The index of the position in the vault.
+/// * `accounts` - The position followed by its lower and upper tick arrays.
+/// * `timestamp` - The current unix timestamp.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidVaultPositionAccountsError` - If the position is not the position of the vault at `index`.
+/// * `AccountNotMutable` - If the position is not writable.
+pub(crate) fn load_vault_position<'info>(
+    ai_dex: &mut AiDexPool,
+    ai_dex_pool_key: &Pubkey,
+    vault: &Vault,
+    index: usize,
+    accounts: &'info [AccountInfo<'info>],
+    timestamp: u64,
+) -> Result<(Account<'info, Position>, TickArrayAccount<'info>, TickArrayAccount<'info>)> {
+    if accounts[0].key() != vault.positions[index] {
+        return Err(ErrorCode::InvalidVaultPositionAccountsError.into());
+    }
+    if !accounts[0].is_writable {
+        return Err(anchor_lang::error::ErrorCode::AccountNotMutable.into());
+    }
+
+    let mut position = Account::<Position>::try_from(&accounts[0])?;
+    let tick_array_lower = TickArrayAccount::try_from_with_pool(&accounts[1], ai_dex_pool_key)?;
+    let tick_array_upper = TickArrayAccount::try_from_with_pool(&accounts[2], ai_dex_pool_key)?;
+
+    let (position_update, reward_infos) = calculate_fee_and_reward_growths(
+        ai_dex,
+        &position,
+        &tick_array_lower,
+        &tick_array_upper,
+        timestamp,
+    )?;
+    ai_dex.update_rewards(reward_infos, timestamp);
+    position.update(&position_update);
+
+    Ok((position, tick_array_lower, tick_array_upper))
+}
+
+/// The accounts of an initialized reward of the pool, passed in the remaining accounts.
+pub(crate) struct VaultRewardAccounts<'info> {
+    pub index: usize,
+    pub reward_owner_account: InterfaceAccount<'info, TokenAccountInterface>,
+    pub reward_mint: InterfaceAccount<'info, MintInterface>,
+    pub reward_vault: InterfaceAccount<'info, TokenAccountInterface>,
+    pub reward_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Splits the remaining accounts into the triples of the positions of the vault and the accounts
+/// of the initialized rewards of the pool.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidVaultPositionAccountsError` - If the number of remaining accounts does not match the positions of the vault and the rewards of the pool.
+pub(crate) fn split_vault_remaining_accounts<'c>(
+    vault: &Vault,
+    ai_dex: &AiDexPool,
+    accounts: &'c [AccountInfo<'c>],
+) -> Result<(&'c [AccountInfo<'c>], &'c [AccountInfo<'c>])> {
+    let position_accounts_len = vault.position_count() * ACCOUNTS_PER_VAULT_POSITION;
+    let initialized_rewards = ai_dex.reward_infos.iter().filter(|reward| reward.initialized()).count();
+    if accounts.len() != position_accounts_len + initialized_rewards * ACCOUNTS_PER_VAULT_REWARD {
+        return Err(ErrorCode::InvalidVaultPositionAccountsError.into());
+    }
+    Ok(accounts.split_at(position_accounts_len))
+}
+
+/// Loads the accounts of each initialized reward of the pool, in reward index order.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidRewardAccountsError` - If the accounts do not match the initialized rewards.
+pub(crate) fn load_vault_reward_accounts<'info>(
+    ai_dex: &AiDexPool,
+    accounts: &'info [AccountInfo<'info>],
+) -> Result<Vec<VaultRewardAccounts<'info>>> {
+    let initialized_reward_infos = ai_dex
+        .reward_infos
+        .iter()
+        .enumerate()
+        .filter(|(_, reward)| reward.initialized());
+
+    let mut reward_accounts = Vec::with_capacity(NUM_REWARDS);
+    for ((index, reward_info), accounts) in initialized_reward_infos.zip(accounts.chunks(ACCOUNTS_PER_VAULT_REWARD)) {
+        let reward_owner_account = InterfaceAccount::<TokenAccountInterface>::try_from(&accounts[0])?;
+        let reward_mint = InterfaceAccount::<MintInterface>::try_from(&accounts[1])?;
+        let reward_vault = InterfaceAccount::<TokenAccountInterface>::try_from(&accounts[2])?;
+        let reward_token_program = Interface::<TokenInterface>::try_from(&accounts[3])?;
+
+        if reward_mint.key() != reward_info.mint
+            || reward_vault.key() != reward_info.vault
+            || reward_owner_account.mint != reward_info.mint
+            || reward_token_program.key() != *reward_mint.to_account_info().owner
+        {
+            return Err(ErrorCode::InvalidRewardAccountsError.into());
+        }
+
+        reward_accounts.push(VaultRewardAccounts {
+            index,
+            reward_owner_account,
+            reward_mint,
+            reward_vault,
+            reward_token_program,
+        });
+    }
+    Ok(reward_accounts)
+}
+
+/// Deposits tokens into a vault, minting vault shares in exchange.
+///
+/// The depositor pays in the share of the tokens of the vault matching `share_amount` shares,
+/// rounded up: the tokens of its token accounts, the tokens backing the liquidity of its
+/// positions at the current price, and the fees and rewards its positions have earned but not yet
+/// collected. The rewards paid in are credited to the positions, and the other tokens are kept in the token accounts of the vault until the strategy authority deploys
+/// them. The first depositor sets the value of the shares, paying in `token_max_a` and
+/// `token_max_b`.
+///
+/// The remaining accounts are triples of each position of the vault, in order, followed by its
+/// lower and upper tick arrays, then the accounts of every initialized reward of the pool, in
+/// reward index order: the reward owner account, the reward mint, the reward vault and the reward
+/// token program. Mints with a transfer hook are not supported.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts and programs required for the operation.
+/// * `share_amount` - The amount of vault shares to mint.
+/// * `token_max_a` - The maximum amount of token A that can be transferred.
+/// * `token_max_b` - The maximum amount of token B that can be transferred.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the operation is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::ZeroVaultShareAmountError` - If the amount of shares is zero.
+/// * `ErrorCode::InvalidVaultPositionAccountsError` - If the remaining accounts do not match the positions of the vault.
+/// * `ErrorCode::InvalidRewardAccountsError` - If the reward accounts do not match the initialized rewards.
+/// * `ErrorCode::TokenLimitExceededError` - If the transfer amount exceeds the specified token limits.
+pub fn deposit_vault_handler<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ModifyVault<'info>>,
+    share_amount: u64,
+    token_max_a: u64,
+    token_max_b: u64,
+) -> Result<()> {
+    if share_amount == 0 {
+        return Err(ErrorCode::ZeroVaultShareAmountError.into());
+    }
+    let (position_accounts, reward_accounts) = split_vault_remaining_accounts(
+        &ctx.accounts.vault,
+        &*ctx.accounts.ai_dex_pool.load()?,
+        ctx.remaining_accounts,
+    )?;
+    let reward_accounts = load_vault_reward_accounts(&*ctx.accounts.ai_dex_pool.load()?, reward_accounts)?;
+
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let ai_dex_pool_key = ctx.accounts.ai_dex_pool.key();
+    let share_supply = ctx.accounts.share_mint.supply;
+
+    let mut reward_amounts = [0u64; NUM_REWARDS];
+    let (amount_a, amount_b) = if share_supply == 0 {
+        (token_max_a, token_max_b)
+    } else {
+        let mut total_a = u128::from(ctx.accounts.vault_token_account_a.amount);
+        let mut total_b = u128::from(ctx.accounts.vault_token_account_b.amount);
+
+        let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
+        for (index, accounts) in position_accounts.chunks(ACCOUNTS_PER_VAULT_POSITION).enumerate() {
+            let (mut position, _, _) = load_vault_position(
+                &mut ai_dex,
+                &ai_dex_pool_key,
+                &ctx.accounts.vault,
+                index,
+                accounts,
+                timestamp,
+            )?;
+
+            // Value the liquidity rounding up, in favor of the current holders
+            if position.liquidity > 0 {
+                let (liquidity_a, liquidity_b) = calculate_liquidity_token_deltas(
+                    ai_dex.tick_current_index,
+                    ai_dex.sqrt_price,
+                    &position,
+                    convert_to_liquidity_delta(position.liquidity, true)?,
+                )?;
+                total_a += u128::from(liquidity_a);
+                total_b += u128::from(liquidity_b);
+            }
+            total_a += u128::from(position.fee_owed_a);
+            total_b += u128::from(position.fee_owed_b);
+
+            // The share of the rewards owed is paid in reward tokens and credited to the position
+            let reward_shares = deposit_vault_position_rewards(&mut ai_dex, &mut position, share_amount, share_supply)?;
+            for (reward_amount, reward_share) in reward_amounts.iter_mut().zip(reward_shares) {
+                *reward_amount = reward_amount
+                    .checked_add(reward_share)
+                    .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+            }
+
+            position.exit(&crate::ID)?;
+        }
+        drop(ai_dex);
+
+        (
+            to_token_amount(get_vault_share_amount(share_amount, share_supply, total_a, true)?)?,
+            to_token_amount(get_vault_share_amount(share_amount, share_supply, total_b, true)?)?,
+        )
+    };
+
+    let transfer_fee_included_amount_a = calculate_transfer_fee_included_amount(
+        &ctx.accounts.token_mint_a,
+        amount_a,
+    )?;
+    let transfer_fee_included_amount_b = calculate_transfer_fee_included_amount(
+        &ctx.accounts.token_mint_b,
+        amount_b,
+    )?;
+
+    // token_max_a and token_max_b should be applied to the transfer fee included amount
+    if transfer_fee_included_amount_a.amount > token_max_a {
+        return Err(ErrorCode::TokenLimitExceededError.into());
+    }
+    if transfer_fee_included_amount_b.amount > token_max_b {
+        return Err(ErrorCode::TokenLimitExceededError.into());
+    }
+
+    transfer_from_owner_to_vault(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.token_authority,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_owner_account_a,
+        &ctx.accounts.vault_token_account_a,
+        &ctx.accounts.token_program_a,
+        &ctx.accounts.memo_program,
+        &None,
+        transfer_fee_included_amount_a.amount,
+    )?;
+
+    transfer_from_owner_to_vault(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.token_authority,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.token_owner_account_b,
+        &ctx.accounts.vault_token_account_b,
+        &ctx.accounts.token_program_b,
+        &ctx.accounts.memo_program,
+        &None,
+        transfer_fee_included_amount_b.amount,
+    )?;
+
+    for reward in reward_accounts.iter() {
+        let transfer_fee_included_reward_amount = calculate_transfer_fee_included_amount(
+            &reward.reward_mint,
+            reward_amounts[reward.index],
+        )?;
+        transfer_from_owner_to_vault(
+            &ctx.accounts.ai_dex_pool,
+            &ctx.accounts.token_authority,
+            &reward.reward_mint,
+            &reward.reward_owner_account,
+            &reward.reward_vault,
+            &reward.reward_token_program,
+            &ctx.accounts.memo_program,
+            &None,
+            transfer_fee_included_reward_amount.amount,
+        )?;
+    }
+
+    mint_vault_shares(
+        &ctx.accounts.vault,
+        &ctx.accounts.share_mint,
+        &ctx.accounts.share_token_account,
+        &ctx.accounts.token_program,
+        share_amount,
+    )?;
+
+    emit_cpi!(VaultDepositedEvent {
+        token_authority: ctx.accounts.token_authority.key(),
+        ai_dex_pool: ai_dex_pool_key,
+        vault: ctx.accounts.vault.key(),
+        share_amount,
+        amount_a,
+        amount_b,
+        reward_amounts,
+        transfer_fee_included_amount_a: transfer_fee_included_amount_a.amount,
+        transfer_fee_included_amount_b: transfer_fee_included_amount_b.amount,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+fn to_token_amount(amount: u128) -> Result<u64> {
+    u64::try_from(amount).map_err(|_| ErrorCode::TokenLimitExceededError.into())
+}
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token};
+use anchor_spl::token_interface::{Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface};
+
+use crate::state::*;
+
+#[event]
+pub struct VaultInitializedEvent {
+    pub funder: Pubkey,
+    pub strategy_authority: Pubkey,
+    pub agent: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub vault: Pubkey,
+    pub share_mint: Pubkey,
+    pub token_account_a: Pubkey,
+    pub token_account_b: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeVault<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub strategy_authority: Signer<'info>,
+
+    /// CHECK: the account that will be the agent can be arbitrary, the default pubkey for none
+    pub agent: UncheckedAccount<'info>,
+
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        init,
+        payer = funder,
+        mint::authority = vault,
+        mint::decimals = VAULT_SHARE_DECIMALS,
+    )]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = funder,
+        seeds = [b"vault", share_mint.key().as_ref()],
+        bump,
+        space = Vault::LEN
+    )]
+    pub vault: Box<Account<'info, Vault>>,
+
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
+    pub token_mint_a: Box<InterfaceAccount<'info, MintInterface>>,
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
+    pub token_mint_b: Box<InterfaceAccount<'info, MintInterface>>,
+
+    #[account(
+        init,
+        payer = funder,
+        token::token_program = token_program_a,
+        token::mint = token_mint_a,
+        token::authority = vault
+    )]
+    pub token_account_a: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    #[account(
+        init,
+        payer = funder,
+        token::token_program = token_program_b,
+        token::mint = token_mint_b,
+        token::authority = vault
+    )]
+    pub token_account_b: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    #[account(address = token_mint_a.to_account_info().owner.clone())]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = token_mint_b.to_account_info().owner.clone())]
+    pub token_program_b: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Initializes a vault holding positions of a pool on behalf of the holders of its shares.
+///
+/// The vault starts without positions. The strategy authority opens them with
+/// `open_vault_position` and deploys the deposited tokens with `rebalance_vault_position`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the accounts required for the initialization.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the vault is initialized, otherwise returns an error.
+pub fn initialize_vault_handler(ctx: Context<InitializeVault>) -> Result<()> {
+    let bump = ctx.bumps.vault;
+    ctx.accounts.vault.initialize(
+        ctx.accounts.ai_dex_pool.key(),
+        ctx.accounts.share_mint.key(),
+        ctx.accounts.strategy_authority.key(),
+        ctx.accounts.agent.key(),
+        ctx.accounts.token_account_a.key(),
+        ctx.accounts.token_account_b.key(),
+        bump,
+    );
+
+    emit_cpi!(VaultInitializedEvent {
+        funder: ctx.accounts.funder.key(),
+        strategy_authority: ctx.accounts.strategy_authority.key(),
+        agent: ctx.accounts.agent.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        vault: ctx.accounts.vault.key(),
+        share_mint: ctx.accounts.share_mint.key(),
+        token_account_a: ctx.accounts.token_account_a.key(),
+        token_account_b: ctx.accounts.token_account_b.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+pub mod deposit_vault;
+pub mod initialize_vault;
+pub mod open_vault_position;
+pub mod rebalance_vault_position;
+pub mod set_vault_agent;
+pub mod withdraw_vault;
+
+pub use deposit_vault::*;
+pub use initialize_vault::*;
+pub use open_vault_position::*;
+pub use rebalance_vault_position::*;
+pub use set_vault_agent::*;
+pub use withdraw_vault::*;
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[event]
+pub struct VaultPositionOpenedEvent {
+    pub strategy_authority: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub vault: Pubkey,
+    pub position: Pubkey,
+    pub position_index: u8,
+    pub tick_lower_index: i32,
+    pub tick_upper_index: i32,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct OpenVaultPosition<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub strategy_authority: Signer<'info>,
+
+    #[account(mut, has_one = ai_dex_pool, has_one = strategy_authority)]
+    pub vault: Box<Account<'info, Vault>>,
+
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    /// The position held on behalf of the vault, whose position mint is the vault account
+    #[account(
+        init,
+        payer = funder,
+        space = Position::LEN,
+        seeds = [b"vault_position".as_ref(), vault.key().as_ref(), &[vault.position_count() as u8]],
+        bump,
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens a position of a vault, without liquidity.
+///
+/// The position has no position token and can only be modified through the vault instructions.
+/// Its liquidity is added by `rebalance_vault_position`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the accounts required for the operation.
+/// * `tick_lower_index` - The lower tick index of the position.
+/// * `tick_upper_index` - The upper tick index of the position.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the position is opened, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::VaultPositionLimitError` - If the vault already holds the maximum number of positions.
+/// * `ErrorCode::InvalidTickIndexError` - If the tick range is invalid.
+pub fn open_vault_position_handler(
+    ctx: Context<OpenVaultPosition>,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> Result<()> {
+    let position_index = ctx.accounts.vault.position_count() as u8;
+
+    ctx.accounts.position.open_position(
+        &ctx.accounts.ai_dex_pool,
+        ctx.accounts.vault.key(),
+        tick_lower_index,
+        tick_upper_index,
+    )?;
+    ctx.accounts.vault.add_position(ctx.accounts.position.key())?;
+
+    emit_cpi!(VaultPositionOpenedEvent {
+        strategy_authority: ctx.accounts.strategy_authority.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        vault: ctx.accounts.vault.key(),
+        position: ctx.accounts.position.key(),
+        position_index,
+        tick_lower_index,
+        tick_upper_index,
+    });
+
+    Ok(())
+}
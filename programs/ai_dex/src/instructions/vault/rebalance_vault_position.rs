@@ -0,0 +1,325 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::transfer_memo;
+use crate::errors::ErrorCode;
+use crate::math::convert_to_liquidity_delta;
+use crate::orchestrator::liquidity_orchestrator::{
+    calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
+};
+use crate::state::*;
+use crate::util::{
+    calculate_transfer_fee_included_amount, to_timestamp_u64, transfer_from_program_account,
+    transfer_from_vault_to_owner, TickArrayAccount,
+};
+
+#[event]
+pub struct VaultPositionRebalancedEvent {
+    pub authority: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub vault: Pubkey,
+    pub position: Pubkey,
+    pub old_tick_lower_index: i32,
+    pub old_tick_upper_index: i32,
+    pub new_tick_lower_index: i32,
+    pub new_tick_upper_index: i32,
+    pub old_liquidity: u128,
+    pub new_liquidity: u128,
+    pub withdrawn_a: u64,
+    pub withdrawn_b: u64,
+    pub fees_collected_a: u64,
+    pub fees_collected_b: u64,
+    pub deposited_a: u64,
+    pub deposited_b: u64,
+    pub timestamp: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RebalanceVaultPosition<'info> {
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    /// The strategy authority or the agent of the vault. Pays the rent when a tick of a dynamic tick array gets initialized.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(has_one = ai_dex_pool)]
+    pub vault: Box<Account<'info, Vault>>,
+
+    #[account(
+        mut,
+        constraint = vault.positions.contains(&position.key()) @ ErrorCode::InvalidVaultPositionAccountsError
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
+    pub token_mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
+    pub token_mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, address = vault.token_account_a)]
+    pub vault_token_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = vault.token_account_b)]
+    pub vault_token_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: The tick array of the current lower tick, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: The tick array of the current upper tick, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    /// CHECK: The tick array of the new lower tick, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub new_tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: The tick array of the new upper tick, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub new_tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(address = token_mint_a.to_account_info().owner.clone())]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = token_mint_b.to_account_info().owner.clone())]
+    pub token_program_b: Interface<'info, TokenInterface>,
+
+    pub memo_program: Program<'info, Memo>,
+    pub system_program: Program<'info, System>,
+
+    /// The config of the pool, checked against the protocol-wide emergency pause
+    #[account(
+        address = ai_dex_pool.load()?.ai_dex_config,
+        constraint = !ai_dex_config.paused @ ErrorCode::ProtocolPausedError
+    )]
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+}
+
+/// Moves a position of a vault into a new tick range, with a new liquidity.
+///
+/// All liquidity of the position is withdrawn and its fees are collected into the token accounts
+/// of the vault, then `liquidity_amount` is deposited into the new range from the token accounts
+/// of the vault. Only the difference between the two is transferred. Passing the current range
+/// compounds the fees, and a zero liquidity parks the tokens in the token accounts of the vault.
+/// Mints with a transfer hook are not supported.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the rebalance.
+/// * `new_tick_lower_index` - The new lower tick index of the position.
+/// * `new_tick_upper_index` - The new upper tick index of the position.
+/// * `liquidity_amount` - The liquidity to deposit into the new range.
+/// * `token_max_a` - The maximum amount of token A to deposit into the new range.
+/// * `token_max_b` - The maximum amount of token B to deposit into the new range.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the rebalance is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::VaultRebalanceAuthorityError` - If the signer is neither the strategy authority nor the agent of the vault.
+/// * `ErrorCode::InvalidTickIndexError` - If the new tick range is invalid.
+/// * `ErrorCode::TokenLimitExceededError` - If the amounts deposited exceed the specified token limits.
+pub fn rebalance_vault_position_handler(
+    ctx: Context<RebalanceVaultPosition>,
+    new_tick_lower_index: i32,
+    new_tick_upper_index: i32,
+    liquidity_amount: u128,
+    token_max_a: u64,
+    token_max_b: u64,
+) -> Result<()> {
+    if !ctx.accounts.vault.is_rebalance_authority(ctx.accounts.authority.key) {
+        return Err(ErrorCode::VaultRebalanceAuthorityError.into());
+    }
+
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let ai_dex_pool_key = ctx.accounts.ai_dex_pool.key();
+    let old_tick_lower_index = ctx.accounts.position.tick_lower_index;
+    let old_tick_upper_index = ctx.accounts.position.tick_upper_index;
+    let old_liquidity = ctx.accounts.position.liquidity;
+
+    let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
+
+    // Withdraw the liquidity from the current range, leaving the tokens in the vaults
+    let (withdrawn_a, withdrawn_b) = if old_liquidity > 0 {
+        let tick_array_lower = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_lower, &ai_dex_pool_key)?;
+        let tick_array_upper = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_upper, &ai_dex_pool_key)?;
+        let liquidity_delta = convert_to_liquidity_delta(old_liquidity, false)?;
+
+        let update = calculate_modify_liquidity(
+            &ai_dex,
+            &ctx.accounts.position,
+            &tick_array_lower,
+            &tick_array_upper,
+            liquidity_delta,
+            timestamp,
+        )?;
+        sync_modify_liquidity_values(
+            &mut ai_dex,
+            &mut ctx.accounts.position,
+            &tick_array_lower,
+            &tick_array_upper,
+            update,
+            timestamp,
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+        )?;
+
+        let (delta_a, delta_b) = calculate_liquidity_token_deltas(
+            ai_dex.tick_current_index,
+            ai_dex.sqrt_price,
+            &ctx.accounts.position,
+            liquidity_delta,
+        )?;
+        ctx.accounts.position.record_withdrawal(delta_a, delta_b);
+        (delta_a, delta_b)
+    } else {
+        (0, 0)
+    };
+
+    let fees_collected_a = ctx.accounts.position.fee_owed_a;
+    let fees_collected_b = ctx.accounts.position.fee_owed_b;
+    ctx.accounts.position.reset_fees_owed();
+
+    ctx.accounts.position.reset_tick_range(
+        &ai_dex,
+        new_tick_lower_index,
+        new_tick_upper_index,
+    )?;
+
+    let (deposited_a, deposited_b) = if liquidity_amount > 0 {
+        let tick_array_lower = TickArrayAccount::try_from_with_pool(&ctx.accounts.new_tick_array_lower, &ai_dex_pool_key)?;
+        let tick_array_upper = TickArrayAccount::try_from_with_pool(&ctx.accounts.new_tick_array_upper, &ai_dex_pool_key)?;
+        let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, true)?;
+
+        let update = calculate_modify_liquidity(
+            &ai_dex,
+            &ctx.accounts.position,
+            &tick_array_lower,
+            &tick_array_upper,
+            liquidity_delta,
+            timestamp,
+        )?;
+        sync_modify_liquidity_values(
+            &mut ai_dex,
+            &mut ctx.accounts.position,
+            &tick_array_lower,
+            &tick_array_upper,
+            update,
+            timestamp,
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+        )?;
+
+        let (delta_a, delta_b) = calculate_liquidity_token_deltas(
+            ai_dex.tick_current_index,
+            ai_dex.sqrt_price,
+            &ctx.accounts.position,
+            liquidity_delta,
+        )?;
+        ctx.accounts.position.record_deposit(delta_a, delta_b, ai_dex.sqrt_price, timestamp);
+        (delta_a, delta_b)
+    } else {
+        (0, 0)
+    };
+    drop(ai_dex);
+
+    if deposited_a > token_max_a || deposited_b > token_max_b {
+        return Err(ErrorCode::TokenLimitExceededError.into());
+    }
+
+    let released_a = withdrawn_a
+        .checked_add(fees_collected_a)
+        .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+    let released_b = withdrawn_b
+        .checked_add(fees_collected_b)
+        .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+
+    settle_vault_token(
+        &ctx.accounts,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.vault_token_account_a,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_program_a,
+        released_a,
+        deposited_a,
+    )?;
+    settle_vault_token(
+        &ctx.accounts,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.vault_token_account_b,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_program_b,
+        released_b,
+        deposited_b,
+    )?;
+
+    emit_cpi!(VaultPositionRebalancedEvent {
+        authority: ctx.accounts.authority.key(),
+        ai_dex_pool: ai_dex_pool_key,
+        vault: ctx.accounts.vault.key(),
+        position: ctx.accounts.position.key(),
+        old_tick_lower_index,
+        old_tick_upper_index,
+        new_tick_lower_index,
+        new_tick_upper_index,
+        old_liquidity,
+        new_liquidity: liquidity_amount,
+        withdrawn_a,
+        withdrawn_b,
+        fees_collected_a,
+        fees_collected_b,
+        deposited_a,
+        deposited_b,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// Transfers the difference between the tokens released by a position and the tokens deposited
+/// back into it, between the token account of the vault and the token vault of the pool.
+fn settle_vault_token<'info>(
+    accounts: &RebalanceVaultPosition<'info>,
+    token_mint: &InterfaceAccount<'info, Mint>,
+    vault_token_account: &InterfaceAccount<'info, TokenAccount>,
+    token_vault: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    released: u64,
+    deposited: u64,
+) -> Result<()> {
+    if released >= deposited {
+        transfer_from_vault_to_owner(
+            &accounts.ai_dex_pool,
+            token_mint,
+            token_vault,
+            vault_token_account,
+            token_program,
+            &accounts.memo_program,
+            &None,
+            released - deposited,
+            transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
+        )
+    } else {
+        // The pool must receive the deposited amount in full
+        let transfer_fee_included_amount =
+            calculate_transfer_fee_included_amount(token_mint, deposited - released)?;
+        transfer_from_program_account(
+            &accounts.ai_dex_pool,
+            &accounts.vault.to_account_info(),
+            &accounts.vault.seeds(),
+            token_mint,
+            vault_token_account,
+            token_vault,
+            token_program,
+            &accounts.memo_program,
+            transfer_fee_included_amount.amount,
+            &[],
+        )
+    }
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[event]
+pub struct VaultAgentUpdatedEvent {
+    pub strategy_authority: Pubkey,
+    pub vault: Pubkey,
+    pub old_agent: Pubkey,
+    pub new_agent: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetVaultAgent<'info> {
+    pub strategy_authority: Signer<'info>,
+
+    #[account(mut, has_one = strategy_authority)]
+    pub vault: Box<Account<'info, Vault>>,
+
+    /// CHECK: the account that will be the new agent can be arbitrary
+    pub new_agent: UncheckedAccount<'info>,
+}
+
+/// Sets the agent allowed to rebalance the positions of a vault.
+///
+/// Passing the default pubkey as the new agent revokes the agent.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the operation.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the operation is successful, otherwise returns an error.
+pub fn set_vault_agent_handler(ctx: Context<SetVaultAgent>) -> Result<()> {
+    let old_agent = ctx.accounts.vault.agent;
+    ctx.accounts.vault.set_agent(ctx.accounts.new_agent.key());
+
+    emit_cpi!(VaultAgentUpdatedEvent {
+        strategy_authority: ctx.accounts.strategy_authority.key(),
+        vault: ctx.accounts.vault.key(),
+        old_agent,
+        new_agent: ctx.accounts.new_agent.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,284 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::transfer_memo;
+use crate::errors::ErrorCode;
+use crate::math::convert_to_liquidity_delta;
+use crate::orchestrator::liquidity_orchestrator::{
+    calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
+};
+use crate::state::*;
+use crate::util::{calculate_transfer_fee_excluded_amount, transfer_from_program_account, transfer_from_vault_to_owner};
+use crate::util::{burn_vault_shares, to_timestamp_u64};
+
+use super::{
+    load_vault_position, load_vault_reward_accounts, split_vault_remaining_accounts, ModifyVault,
+    ACCOUNTS_PER_VAULT_POSITION,
+};
+
+#[event]
+pub struct VaultWithdrawnEvent {
+    pub token_authority: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub vault: Pubkey,
+    pub share_amount: u64,
+    pub idle_amount_a: u64,
+    pub idle_amount_b: u64,
+    pub position_amount_a: u64,
+    pub position_amount_b: u64,
+    pub reward_amounts: [u64; NUM_REWARDS],
+    pub transfer_fee_excluded_amount_a: u64,
+    pub transfer_fee_excluded_amount_b: u64,
+    pub timestamp: u64,
+}
+
+/// Burns vault shares, withdrawing their share of the tokens of the vault.
+///
+/// The holder receives the share of the token accounts of the vault matching `share_amount`
+/// shares, along with the same share of the liquidity of each position and of the fees and rewards
+/// it has earned but not yet collected, all rounded down. The share of a reward is capped by the
+/// balance of the reward vault.
+///
+/// The remaining accounts are triples of each position of the vault, in order, followed by its
+/// lower and upper tick arrays, then the accounts of every initialized reward of the pool, in
+/// reward index order: the reward owner account, the reward mint, the reward vault and the reward
+/// token program. Mints with a transfer hook are not supported.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts and programs required for the operation.
+/// * `share_amount` - The amount of vault shares to burn.
+/// * `token_min_a` - The minimum amount of token A to receive.
+/// * `token_min_b` - The minimum amount of token B to receive.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the operation is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::ZeroVaultShareAmountError` - If the amount of shares is zero.
+/// * `ErrorCode::InvalidVaultPositionAccountsError` - If the remaining accounts do not match the positions of the vault.
+/// * `ErrorCode::InvalidRewardAccountsError` - If the reward accounts do not match the initialized rewards.
+/// * `ErrorCode::TokenAmountBelowMinimumError` - If the amount received is below the specified minimum.
+pub fn withdraw_vault_handler<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ModifyVault<'info>>,
+    share_amount: u64,
+    token_min_a: u64,
+    token_min_b: u64,
+) -> Result<()> {
+    if share_amount == 0 {
+        return Err(ErrorCode::ZeroVaultShareAmountError.into());
+    }
+    let (position_accounts, reward_accounts) = split_vault_remaining_accounts(
+        &ctx.accounts.vault,
+        &*ctx.accounts.ai_dex_pool.load()?,
+        ctx.remaining_accounts,
+    )?;
+    let reward_accounts = load_vault_reward_accounts(&*ctx.accounts.ai_dex_pool.load()?, reward_accounts)?;
+
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let ai_dex_pool_key = ctx.accounts.ai_dex_pool.key();
+    let share_supply = ctx.accounts.share_mint.supply;
+
+    let idle_amount_a = get_vault_share_token_amount(
+        share_amount,
+        share_supply,
+        ctx.accounts.vault_token_account_a.amount,
+        false,
+    )?;
+    let idle_amount_b = get_vault_share_token_amount(
+        share_amount,
+        share_supply,
+        ctx.accounts.vault_token_account_b.amount,
+        false,
+    )?;
+
+    let mut position_amount_a: u64 = 0;
+    let mut position_amount_b: u64 = 0;
+    let mut reward_amounts = [0u64; NUM_REWARDS];
+    let mut reward_vault_amounts = [0u64; NUM_REWARDS];
+    for reward in reward_accounts.iter() {
+        reward_vault_amounts[reward.index] = reward.reward_vault.amount;
+    }
+
+    let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
+    for (index, accounts) in position_accounts.chunks(ACCOUNTS_PER_VAULT_POSITION).enumerate() {
+        let (mut position, tick_array_lower, tick_array_upper) = load_vault_position(
+            &mut ai_dex,
+            &ai_dex_pool_key,
+            &ctx.accounts.vault,
+            index,
+            accounts,
+            timestamp,
+        )?;
+
+        let liquidity_amount = get_vault_share_amount(share_amount, share_supply, position.liquidity, false)?;
+        let (delta_a, delta_b) = if liquidity_amount > 0 {
+            let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, false)?;
+            let update = calculate_modify_liquidity(
+                &ai_dex,
+                &position,
+                &tick_array_lower,
+                &tick_array_upper,
+                liquidity_delta,
+                timestamp,
+            )?;
+            sync_modify_liquidity_values(
+                &mut ai_dex,
+                &mut position,
+                &tick_array_lower,
+                &tick_array_upper,
+                update,
+                timestamp,
+                &ctx.accounts.token_authority,
+                &ctx.accounts.system_program,
+            )?;
+
+            let (delta_a, delta_b) = calculate_liquidity_token_deltas(
+                ai_dex.tick_current_index,
+                ai_dex.sqrt_price,
+                &position,
+                liquidity_delta,
+            )?;
+            position.record_withdrawal(delta_a, delta_b);
+            (delta_a, delta_b)
+        } else {
+            (0, 0)
+        };
+
+        // The fees owed have been updated, so the share paid out is up to date
+        let fee_share_a = get_vault_share_token_amount(share_amount, share_supply, position.fee_owed_a, false)?;
+        let fee_share_b = get_vault_share_token_amount(share_amount, share_supply, position.fee_owed_b, false)?;
+        position.remove_fees_owed(fee_share_a, fee_share_b)?;
+
+        let reward_shares = withdraw_vault_position_rewards(
+            &mut ai_dex,
+            &mut position,
+            share_amount,
+            share_supply,
+            &mut reward_vault_amounts,
+        )?;
+        for (reward_amount, reward_share) in reward_amounts.iter_mut().zip(reward_shares) {
+            *reward_amount = reward_amount
+                .checked_add(reward_share)
+                .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        }
+        position.exit(&crate::ID)?;
+
+        position_amount_a = position_amount_a
+            .checked_add(delta_a)
+            .and_then(|amount| amount.checked_add(fee_share_a))
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        position_amount_b = position_amount_b
+            .checked_add(delta_b)
+            .and_then(|amount| amount.checked_add(fee_share_b))
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+    }
+    drop(ai_dex);
+
+    burn_vault_shares(
+        &ctx.accounts.token_authority,
+        &ctx.accounts.share_mint,
+        &ctx.accounts.share_token_account,
+        &ctx.accounts.token_program,
+        share_amount,
+    )?;
+
+    // The tokens of the token accounts and of the positions are sent in separate transfers,
+    // each paying its own transfer fee
+    let transfer_fee_excluded_amount_a = calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_a, idle_amount_a)?
+        .amount
+        .checked_add(calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_a, position_amount_a)?.amount)
+        .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+    let transfer_fee_excluded_amount_b = calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_b, idle_amount_b)?
+        .amount
+        .checked_add(calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_b, position_amount_b)?.amount)
+        .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+
+    if transfer_fee_excluded_amount_a < token_min_a {
+        return Err(ErrorCode::TokenAmountBelowMinimumError.into());
+    }
+    if transfer_fee_excluded_amount_b < token_min_b {
+        return Err(ErrorCode::TokenAmountBelowMinimumError.into());
+    }
+
+    let vault_seeds = ctx.accounts.vault.seeds();
+    transfer_from_program_account(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.vault.to_account_info(),
+        &vault_seeds,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.vault_token_account_a,
+        &ctx.accounts.token_owner_account_a,
+        &ctx.accounts.token_program_a,
+        &ctx.accounts.memo_program,
+        idle_amount_a,
+        transfer_memo::TRANSFER_MEMO_WITHDRAW_VAULT.as_bytes(),
+    )?;
+    transfer_from_program_account(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.vault.to_account_info(),
+        &vault_seeds,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.vault_token_account_b,
+        &ctx.accounts.token_owner_account_b,
+        &ctx.accounts.token_program_b,
+        &ctx.accounts.memo_program,
+        idle_amount_b,
+        transfer_memo::TRANSFER_MEMO_WITHDRAW_VAULT.as_bytes(),
+    )?;
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_owner_account_a,
+        &ctx.accounts.token_program_a,
+        &ctx.accounts.memo_program,
+        &None,
+        position_amount_a,
+        transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
+    )?;
+    transfer_from_vault_to_owner(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_owner_account_b,
+        &ctx.accounts.token_program_b,
+        &ctx.accounts.memo_program,
+        &None,
+        position_amount_b,
+        transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
+    )?;
+
+    for reward in reward_accounts.iter() {
+        transfer_from_vault_to_owner(
+            &ctx.accounts.ai_dex_pool,
+            &reward.reward_mint,
+            &reward.reward_vault,
+            &reward.reward_owner_account,
+            &reward.reward_token_program,
+            &ctx.accounts.memo_program,
+            &None,
+            reward_amounts[reward.index],
+            transfer_memo::TRANSFER_MEMO_COLLECT_REWARD.as_bytes(),
+        )?;
+    }
+
+    emit_cpi!(VaultWithdrawnEvent {
+        token_authority: ctx.accounts.token_authority.key(),
+        ai_dex_pool: ai_dex_pool_key,
+        vault: ctx.accounts.vault.key(),
+        share_amount,
+        idle_amount_a,
+        idle_amount_b,
+        position_amount_a,
+        position_amount_b,
+        reward_amounts,
+        transfer_fee_excluded_amount_a,
+        transfer_fee_excluded_amount_b,
+        timestamp,
+    });
+
+    Ok(())
+}
@@ -8,14 +8,17 @@ pub struct TokenWrapperDeletedEvent {
     pub token_wrapper_authority: Pubkey,
     pub token_mint: Pubkey,
     pub token_wrapper: Pubkey,
-    pub receiver: Pubkey,
+    pub rent_receiver: Pubkey,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct DeleteTokenWrapper<'info> {
     pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
 
-    #[account(address = ai_dex_config.config_authority)]
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::TokenWrapper)
+    )]
     pub token_wrapper_authority: Signer<'info>,
 
     pub token_mint: InterfaceAccount<'info, Mint>,
@@ -29,18 +32,25 @@ pub struct DeleteTokenWrapper<'info> {
         ],
         bump,
         has_one = ai_dex_config,
-        close = receiver
+        close = rent_receiver
     )]
     pub token_wrapper: Account<'info, TokenWrapper>,
 
-    /// CHECK: safe, for receiving rent only
+    /// CHECK: safe, for receiving reclaimed rent only; may be any account (e.g. a treasury)
     #[account(mut)]
-    pub receiver: UncheckedAccount<'info>,
+    pub rent_receiver: UncheckedAccount<'info>,
+
+    /// The extension of the config, if any, holding the delegated token wrapper authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
 }
 
 /// Handles the deletion of a token wrapper in the protocol.
 ///
-/// This function ensures that the token wrapper is properly closed and any remaining rent is transferred to the receiver account.
+/// This function ensures that the token wrapper is properly closed and any remaining rent is transferred to the rent receiver account.
 ///
 /// # Arguments
 ///
@@ -54,18 +64,18 @@ pub struct DeleteTokenWrapper<'info> {
 ///
 /// This function will return an error if:
 /// * The token wrapper account cannot be closed.
-/// * The rent cannot be transferred to the receiver account.
+/// * The rent cannot be transferred to the rent receiver account.
 pub fn delete_token_wrapper_handler(
     ctx: Context<DeleteTokenWrapper>,
 ) -> Result<()> {
-    // The account closure happens automatically due to the `close = receiver` constraint in the `Accounts` struct.
+    // The account closure happens automatically due to the `close = rent_receiver` constraint in the `Accounts` struct.
 
-    emit!(TokenWrapperDeletedEvent {
+    emit_cpi!(TokenWrapperDeletedEvent {
         ai_dex_config: ctx.accounts.ai_dex_config.key(),
         token_wrapper_authority: ctx.accounts.token_wrapper_authority.key(),
         token_mint: ctx.accounts.token_mint.key(),
         token_wrapper: ctx.accounts.token_wrapper.key(),
-        receiver: ctx.accounts.receiver.key(),
+        rent_receiver: ctx.accounts.rent_receiver.key(),
     });
     
     Ok(())
@@ -1,4 +1,5 @@
 use crate::state::*;
+use crate::util::get_transfer_hook_program_id;
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::Mint;
 
@@ -9,13 +10,17 @@ pub struct TokenWrapperInitializedEvent {
     pub token_mint: Pubkey,
     pub token_wrapper: Pubkey,
     pub funder: Pubkey,
+    pub transfer_hook_program_id: Pubkey,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct InitializeTokenWrapper<'info> {
     pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
 
-    #[account(address = ai_dex_config.config_authority)]
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::TokenWrapper)
+    )]
     pub token_wrapper_authority: Signer<'info>,
 
     pub token_mint: InterfaceAccount<'info, Mint>,
@@ -37,10 +42,19 @@ pub struct InitializeTokenWrapper<'info> {
     pub funder: Signer<'info>,
 
     pub system_program: Program<'info, System>,
+
+    /// The extension of the config, if any, holding the delegated token wrapper authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
 }
 
 /// Initializes a token wrapper in the protocol.
 ///
+/// The current transfer hook program of the mint, if any, becomes the allowlisted one.
+///
 /// # Arguments
 ///
 /// * `ctx` - The context containing all the accounts and programs required for the operation.
@@ -62,13 +76,17 @@ pub fn initialize_token_wrapper_handler(
             ctx.accounts.ai_dex_config.key(),
             ctx.accounts.token_mint.key(),
         )?;
+
+    let transfer_hook_program_id = get_transfer_hook_program_id(&ctx.accounts.token_mint)?.unwrap_or_default();
+    ctx.accounts.token_wrapper.set_transfer_hook_program_id(transfer_hook_program_id);
         
-        emit!(TokenWrapperInitializedEvent {
+        emit_cpi!(TokenWrapperInitializedEvent {
             ai_dex_config: ctx.accounts.ai_dex_config.key(),
             token_wrapper_authority: ctx.accounts.token_wrapper_authority.key(),
             token_mint: ctx.accounts.token_mint.key(),
             token_wrapper: ctx.accounts.token_wrapper.key(),
             funder: ctx.accounts.funder.key(),
+            transfer_hook_program_id,
         });        
 
     Ok(())
@@ -1,5 +1,9 @@
 pub mod delete_token_wrapper;
 pub mod initialize_token_wrapper;
+pub mod set_token_wrapper_flags;
+pub mod set_token_wrapper_transfer_hook_program;
 
 pub use delete_token_wrapper::*;
-pub use initialize_token_wrapper::*;
\ No newline at end of file
+pub use initialize_token_wrapper::*;
+pub use set_token_wrapper_flags::*;
+pub use set_token_wrapper_transfer_hook_program::*;
@@ -0,0 +1,87 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct TokenWrapperFlagsSetEvent {
+    pub ai_dex_config: Pubkey,
+    pub token_wrapper_authority: Pubkey,
+    pub token_wrapper: Pubkey,
+    pub allow_permanent_delegate: bool,
+    pub allow_transfer_hook: bool,
+    pub allow_mint_close_authority: bool,
+    pub allow_freeze_authority: bool,
+    pub allow_default_account_state: bool,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetTokenWrapperFlags<'info> {
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::TokenWrapper)
+    )]
+    pub token_wrapper_authority: Signer<'info>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub token_wrapper: Account<'info, TokenWrapper>,
+
+    /// The extension of the config, if any, holding the delegated token wrapper authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+}
+
+/// Sets the capability flags of a token wrapper.
+///
+/// Each flag allows one extension or authority of the token mint, so a wrapper can allow e.g. a
+/// freeze authority without allowing a permanent delegate. The flags are only checked when a pool
+/// or a reward is initialized with the token mint.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the accounts required for the update.
+/// * `allow_permanent_delegate` - Whether the mint may have a permanent delegate.
+/// * `allow_transfer_hook` - Whether the mint may have a transfer hook.
+/// * `allow_mint_close_authority` - Whether the mint may have a close authority.
+/// * `allow_freeze_authority` - Whether the mint may have a freeze authority.
+/// * `allow_default_account_state` - Whether the mint may have a default account state.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the flags are successfully set, otherwise returns an error.
+///
+/// # Errors
+///
+/// * Any error that occurs during the validation of the accounts.
+pub fn set_token_wrapper_flags_handler(
+    ctx: Context<SetTokenWrapperFlags>,
+    allow_permanent_delegate: bool,
+    allow_transfer_hook: bool,
+    allow_mint_close_authority: bool,
+    allow_freeze_authority: bool,
+    allow_default_account_state: bool,
+) -> Result<()> {
+    ctx.accounts.token_wrapper.set_flags(
+        allow_permanent_delegate,
+        allow_transfer_hook,
+        allow_mint_close_authority,
+        allow_freeze_authority,
+        allow_default_account_state,
+    );
+
+    emit_cpi!(TokenWrapperFlagsSetEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        token_wrapper_authority: ctx.accounts.token_wrapper_authority.key(),
+        token_wrapper: ctx.accounts.token_wrapper.key(),
+        allow_permanent_delegate,
+        allow_transfer_hook,
+        allow_mint_close_authority,
+        allow_freeze_authority,
+        allow_default_account_state,
+    });
+
+    Ok(())
+}
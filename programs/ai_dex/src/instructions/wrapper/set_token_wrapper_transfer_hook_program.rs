@@ -0,0 +1,67 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct TokenWrapperTransferHookProgramSetEvent {
+    pub ai_dex_config: Pubkey,
+    pub token_wrapper_authority: Pubkey,
+    pub token_wrapper: Pubkey,
+    pub old_transfer_hook_program_id: Pubkey,
+    pub new_transfer_hook_program_id: Pubkey,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetTokenWrapperTransferHookProgram<'info> {
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    #[account(
+        address = config_role_authority(&ai_dex_config, ai_dex_config_extension.as_deref(), ConfigRole::TokenWrapper)
+    )]
+    pub token_wrapper_authority: Signer<'info>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub token_wrapper: Account<'info, TokenWrapper>,
+
+    /// The extension of the config, if any, holding the delegated token wrapper authority
+    #[account(
+        seeds = [b"config_extension", ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub ai_dex_config_extension: Option<Account<'info, AiDexConfigExtension>>,
+}
+
+/// Sets the transfer hook program allowlisted by a token wrapper.
+///
+/// Mints with a transfer hook are only supported if their hook program matches the allowlisted
+/// one, so the authority must update it before a mint authority may switch the hook program.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the accounts required for the update.
+/// * `transfer_hook_program_id` - The new allowlisted transfer hook program.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the program is successfully set, otherwise returns an error.
+///
+/// # Errors
+///
+/// * Any error that occurs during the validation of the accounts.
+pub fn set_token_wrapper_transfer_hook_program_handler(
+    ctx: Context<SetTokenWrapperTransferHookProgram>,
+    transfer_hook_program_id: Pubkey,
+) -> Result<()> {
+    let old_transfer_hook_program_id = ctx.accounts.token_wrapper.transfer_hook_program_id;
+    ctx.accounts.token_wrapper.set_transfer_hook_program_id(transfer_hook_program_id);
+
+    emit_cpi!(TokenWrapperTransferHookProgramSetEvent {
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        token_wrapper_authority: ctx.accounts.token_wrapper_authority.key(),
+        token_wrapper: ctx.accounts.token_wrapper.key(),
+        old_transfer_hook_program_id,
+        new_transfer_hook_program_id: transfer_hook_program_id,
+    });
+
+    Ok(())
+}
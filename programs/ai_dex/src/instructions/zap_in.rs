@@ -0,0 +1,481 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::transfer_memo;
+use crate::errors::ErrorCode;
+use crate::math::{convert_to_liquidity_delta, get_liquidity_from_amounts, sqrt_price_from_tick_index};
+use crate::orchestrator::liquidity_orchestrator::{
+    calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
+};
+use crate::state::*;
+use crate::swap_with_transfer_fee_extension;
+use crate::util::{
+    apply_tick_array_bitmap, calculate_transfer_fee_excluded_amount,
+    calculate_transfer_fee_included_amount, check_deadline, load_supplemental_tick_arrays,
+    oracle_volatility_ticks, parse_remaining_accounts, record_oracle_observation, to_timestamp_u64,
+    transfer_from_owner_to_vault, update_and_swap_ai_dex, verify_pool_allowlist,
+    verify_position_authority, AccountsType, ParsedRemainingAccounts, RemainingAccountsInfo,
+    SwapTickSequence, TickArrayAccount,
+};
+
+#[event]
+pub struct ZapInEvent {
+    pub position_authority: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub position: Pubkey,
+    pub a_to_b: bool,
+    pub amount: u64,
+    pub swap_amount: u64,
+    pub swap_amount_in: u64,
+    pub swap_amount_out: u64,
+    pub liquidity_amount: u128,
+    pub delta_a: u64,
+    pub delta_b: u64,
+    pub transfer_fee_included_delta_a: u64,
+    pub transfer_fee_included_delta_b: u64,
+    pub timestamp: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Zap<'info> {
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(address = token_mint_a.to_account_info().owner.clone())]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = token_mint_b.to_account_info().owner.clone())]
+    pub token_program_b: Interface<'info, TokenInterface>,
+
+    pub memo_program: Program<'info, Memo>,
+
+    /// Owns the token accounts and pays the rent when a tick of a dynamic tick array gets initialized.
+    #[account(mut)]
+    pub position_authority: Signer<'info>,
+
+    #[account(mut, has_one = ai_dex_pool)]
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
+    pub token_mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
+    pub token_mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == ai_dex_pool.load()?.token_mint_a)]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, constraint = token_owner_account_b.mint == ai_dex_pool.load()?.token_mint_b)]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: The first tick array of the swap, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_0: UncheckedAccount<'info>,
+    /// CHECK: The second tick array of the swap, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_1: UncheckedAccount<'info>,
+    /// CHECK: The third tick array of the swap, fixed or dynamic. Checked in the handler.
+    #[account(mut)]
+    pub tick_array_2: UncheckedAccount<'info>,
+
+    /// CHECK: A fixed or dynamic tick array of the pool, checked in the handler
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: A fixed or dynamic tick array of the pool, checked in the handler
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    /// CHECK: The oracle account of the pool. An observation is recorded only if it has been initialized.
+    #[account(mut, seeds = [b"oracle", ai_dex_pool.key().as_ref()], bump)]
+    pub oracle: UncheckedAccount<'info>,
+
+    /// CHECK: The tick array bitmap of the pool. Used only if it has been initialized.
+    #[account(seeds = [b"tick_array_bitmap", ai_dex_pool.key().as_ref()], bump)]
+    pub tick_array_bitmap: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The config of the pool, checked against the protocol-wide emergency pause
+    #[account(
+        address = ai_dex_pool.load()?.ai_dex_config,
+        constraint = !ai_dex_config.paused @ ErrorCode::ProtocolPausedError
+    )]
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    /// The liquidity allowlist of the pool, required while it is enabled
+    #[account(seeds = [b"lp_allowlist", ai_dex_pool.key().as_ref()], bump)]
+    pub lp_allowlist: Option<Box<Account<'info, PoolAllowlist>>>,
+
+    /// The token account of the position authority holding a gating token of the liquidity allowlist, if any
+    pub lp_gating_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The swap allowlist of the pool, required while it is enabled
+    #[account(seeds = [b"swap_allowlist", ai_dex_pool.key().as_ref()], bump)]
+    pub swap_allowlist: Option<Box<Account<'info, PoolAllowlist>>>,
+
+    /// The token account of the position authority holding a gating token of the swap allowlist, if any
+    pub swap_gating_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+}
+
+/// Deposits a single token into a position, swapping part of it through the same pool first.
+///
+/// `swap_amount` of the input token is swapped into the other token, and the largest liquidity
+/// that the remaining input and the swap output can fund is deposited into the position.
+/// Whatever cannot be deposited stays with the owner.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the zap.
+/// * `amount` - The total amount of the input token to use, swap included.
+/// * `swap_amount` - The part of `amount` to swap into the other token.
+/// * `a_to_b` - If true the input token is token A, otherwise token B.
+/// * `sqrt_price_limit` - The square root price limit for the swap. `0` means no limit in the swap direction.
+/// * `liquidity_min` - The minimum liquidity to deposit, covering the slippage of both the swap and the deposit.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+/// * `deadline_timestamp` - Optional unix timestamp after which the transaction is rejected.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the zap is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::ZapSwapAmountExceedsInputError` - If `swap_amount` is greater than `amount`.
+/// * `ErrorCode::ZeroLiquidityError` - If the available amounts cannot fund any liquidity.
+/// * `ErrorCode::LiquidityBelowMinimumError` - If the deposited liquidity is below `liquidity_min`.
+/// * `ErrorCode::TransactionExpiredError` - If the deadline has passed.
+/// * `ErrorCode::AuthorityNotAllowlistedError` - If the liquidity or swap allowlist of the pool is enabled and the position authority is not allowed.
+/// * `ErrorCode::SwapHookNotSupportedError` - If the pool has a swap hook, which zaps do not call.
+pub fn zap_in_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, Zap<'info>>,
+    amount: u64,
+    swap_amount: u64,
+    a_to_b: bool,
+    sqrt_price_limit: u128,
+    liquidity_min: u128,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+    deadline_timestamp: Option<i64>,
+) -> Result<()> {
+    verify_pool_allowlist(
+        &*ctx.accounts.ai_dex_pool.load()?,
+        PoolAllowlistKind::Liquidity,
+        ctx.accounts.lp_allowlist.as_deref(),
+        ctx.accounts.position_authority.key,
+        ctx.accounts.lp_gating_token_account.as_deref(),
+    )?;
+    verify_zap_swap_allowed(
+        &*ctx.accounts.ai_dex_pool.load()?,
+        ctx.accounts.swap_allowlist.as_deref(),
+        ctx.accounts.position_authority.key,
+        ctx.accounts.swap_gating_token_account.as_deref(),
+    )?;
+
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    if swap_amount > amount {
+        return Err(ErrorCode::ZapSwapAmountExceedsInputError.into());
+    }
+
+    let clock = Clock::get()?;
+    check_deadline(deadline_timestamp, clock.unix_timestamp)?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::SupplementalTickArrays,
+        ],
+    )?;
+
+    // Swap part of the input token into the other token
+    let (swap_amount_in, swap_amount_out) = if swap_amount > 0 {
+        swap_within_pool(
+            &ctx.accounts,
+            &remaining_accounts,
+            swap_amount,
+            a_to_b,
+            sqrt_price_limit,
+            timestamp,
+        )?
+    } else {
+        (0, 0)
+    };
+
+    // Amounts the owner now holds for the deposit: the unswapped input and the swap output
+    let (input_mint, output_mint) = if a_to_b {
+        (&ctx.accounts.token_mint_a, &ctx.accounts.token_mint_b)
+    } else {
+        (&ctx.accounts.token_mint_b, &ctx.accounts.token_mint_a)
+    };
+    let available_input = amount.saturating_sub(swap_amount_in);
+    let available_output = calculate_transfer_fee_excluded_amount(output_mint, swap_amount_out)?.amount;
+    let (available_a, available_b) = if a_to_b {
+        (available_input, available_output)
+    } else {
+        (available_output, available_input)
+    };
+    let (deposit_limit_a, deposit_limit_b) = if a_to_b {
+        (
+            calculate_transfer_fee_excluded_amount(input_mint, available_a)?.amount,
+            calculate_transfer_fee_excluded_amount(output_mint, available_b)?.amount,
+        )
+    } else {
+        (
+            calculate_transfer_fee_excluded_amount(output_mint, available_a)?.amount,
+            calculate_transfer_fee_excluded_amount(input_mint, available_b)?.amount,
+        )
+    };
+
+    // Deposit the largest liquidity the available amounts can fund
+    let ai_dex_pool_key = ctx.accounts.ai_dex_pool.key();
+    let tick_array_lower = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_lower, &ai_dex_pool_key)?;
+    let tick_array_upper = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_upper, &ai_dex_pool_key)?;
+
+    let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
+    let liquidity_amount = get_liquidity_from_amounts(
+        ai_dex.sqrt_price,
+        sqrt_price_from_tick_index(ctx.accounts.position.tick_lower_index),
+        sqrt_price_from_tick_index(ctx.accounts.position.tick_upper_index),
+        deposit_limit_a,
+        deposit_limit_b,
+    )?;
+    if liquidity_amount == 0 {
+        return Err(ErrorCode::ZeroLiquidityError.into());
+    }
+    if liquidity_amount < liquidity_min {
+        return Err(ErrorCode::LiquidityBelowMinimumError.into());
+    }
+    let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, true)?;
+
+    let update = calculate_modify_liquidity(
+        &ai_dex,
+        &ctx.accounts.position,
+        &tick_array_lower,
+        &tick_array_upper,
+        liquidity_delta,
+        timestamp,
+    )?;
+    sync_modify_liquidity_values(
+        &mut ai_dex,
+        &mut ctx.accounts.position,
+        &tick_array_lower,
+        &tick_array_upper,
+        update,
+        timestamp,
+        &ctx.accounts.position_authority,
+        &ctx.accounts.system_program,
+    )?;
+
+    let (delta_a, delta_b) = calculate_liquidity_token_deltas(
+        ai_dex.tick_current_index,
+        ai_dex.sqrt_price,
+        &ctx.accounts.position,
+        liquidity_delta,
+    )?;
+    drop(ai_dex);
+
+    let transfer_fee_included_delta_a = calculate_transfer_fee_included_amount(
+        &ctx.accounts.token_mint_a,
+        delta_a,
+    )?;
+    let transfer_fee_included_delta_b = calculate_transfer_fee_included_amount(
+        &ctx.accounts.token_mint_b,
+        delta_b,
+    )?;
+    if transfer_fee_included_delta_a.amount > available_a {
+        return Err(ErrorCode::TokenLimitExceededError.into());
+    }
+    if transfer_fee_included_delta_b.amount > available_b {
+        return Err(ErrorCode::TokenLimitExceededError.into());
+    }
+
+    transfer_from_owner_to_vault(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.position_authority,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_owner_account_a,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_program_a,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_a,
+        transfer_fee_included_delta_a.amount,
+    )?;
+    transfer_from_owner_to_vault(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.position_authority,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.token_owner_account_b,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_program_b,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_b,
+        transfer_fee_included_delta_b.amount,
+    )?;
+
+    emit_cpi!(ZapInEvent {
+        position_authority: ctx.accounts.position_authority.key(),
+        ai_dex_pool: ai_dex_pool_key,
+        position: ctx.accounts.position.key(),
+        a_to_b,
+        amount,
+        swap_amount,
+        swap_amount_in,
+        swap_amount_out,
+        liquidity_amount,
+        delta_a,
+        delta_b,
+        transfer_fee_included_delta_a: transfer_fee_included_delta_a.amount,
+        transfer_fee_included_delta_b: transfer_fee_included_delta_b.amount,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// Swaps an exact input amount through the pool of a zap, from and to the owner's token accounts.
+///
+/// # Arguments
+///
+/// * `accounts` - The accounts of the zap.
+/// * `remaining_accounts` - The parsed remaining accounts, with the transfer hook and supplemental tick array accounts.
+/// * `amount` - The exact input amount to swap.
+/// * `a_to_b` - The direction of the swap.
+/// * `sqrt_price_limit` - The square root price limit for the swap. `0` means no limit in the swap direction.
+/// * `timestamp` - The current timestamp.
+///
+/// # Returns
+///
+/// * `Result<(u64, u64)>` - The transfer fee included input amount and the output amount of the swap.
+pub(crate) fn swap_within_pool<'info>(
+    accounts: &Zap<'info>,
+    remaining_accounts: &ParsedRemainingAccounts<'info>,
+    amount: u64,
+    a_to_b: bool,
+    sqrt_price_limit: u128,
+    timestamp: u64,
+) -> Result<(u64, u64)> {
+    let ai_dex = &accounts.ai_dex_pool;
+    let tick_array_0 = TickArrayAccount::try_from_with_pool(&accounts.tick_array_0, &ai_dex.key())?;
+    let tick_array_1 = TickArrayAccount::try_from_with_pool(&accounts.tick_array_1, &ai_dex.key())?;
+    let tick_array_2 = TickArrayAccount::try_from_with_pool(&accounts.tick_array_2, &ai_dex.key())?;
+    let supplemental_tick_arrays = load_supplemental_tick_arrays(
+        &remaining_accounts.supplemental_tick_arrays,
+        &ai_dex.key(),
+    )?;
+
+    let mut swap_tick_sequence = SwapTickSequence::new_with_tick_arrays(
+        tick_array_0.load_mut()?,
+        tick_array_1.load_mut().ok(),
+        tick_array_2.load_mut().ok(),
+    );
+    for tick_array in supplemental_tick_arrays.iter() {
+        swap_tick_sequence.push_tick_array(tick_array.load_mut()?);
+    }
+    apply_tick_array_bitmap(
+        &accounts.tick_array_bitmap,
+        &mut swap_tick_sequence,
+        ai_dex.load()?.tick_spacing,
+        a_to_b,
+    )?;
+
+    let volatility_ticks = oracle_volatility_ticks(&accounts.oracle, &*ai_dex.load()?, timestamp)?;
+    let swap_update = swap_with_transfer_fee_extension(
+        &*ai_dex.load()?,
+        &accounts.token_mint_a,
+        &accounts.token_mint_b,
+        &mut swap_tick_sequence,
+        amount,
+        sqrt_price_limit,
+        true,
+        a_to_b,
+        timestamp,
+        0,
+        0,
+        volatility_ticks,
+    )?;
+    drop(swap_tick_sequence);
+
+    let (amount_in, amount_out) = if a_to_b {
+        (swap_update.amount_a, swap_update.amount_b)
+    } else {
+        (swap_update.amount_b, swap_update.amount_a)
+    };
+
+    record_oracle_observation(&accounts.oracle, &*ai_dex.load()?, timestamp)?;
+
+    update_and_swap_ai_dex(
+        ai_dex,
+        &accounts.position_authority,
+        &accounts.token_mint_a,
+        &accounts.token_mint_b,
+        &accounts.token_owner_account_a,
+        &accounts.token_owner_account_b,
+        &accounts.token_vault_a,
+        &accounts.token_vault_b,
+        &remaining_accounts.transfer_hook_a,
+        &remaining_accounts.transfer_hook_b,
+        &accounts.token_program_a,
+        &accounts.token_program_b,
+        &accounts.memo_program,
+        swap_update,
+        a_to_b,
+        timestamp,
+        transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
+    )?;
+
+    Ok((amount_in, amount_out))
+}
+
+/// Checks that the position authority may swap through the pool as part of a zap.
+///
+/// The swap of a zap does not call the swap hook of the pool, so pools with a swap hook are rejected.
+///
+/// # Errors
+///
+/// * `ErrorCode::SwapHookNotSupportedError` - If the pool has a swap hook.
+/// * `ErrorCode::AuthorityNotAllowlistedError` - If the swap allowlist of the pool is enabled and the position authority is not allowed.
+pub(crate) fn verify_zap_swap_allowed(
+    ai_dex: &AiDexPool,
+    swap_allowlist: Option<&Account<'_, PoolAllowlist>>,
+    position_authority: &Pubkey,
+    swap_gating_token_account: Option<&InterfaceAccount<'_, TokenAccount>>,
+) -> Result<()> {
+    ai_dex.check_swap_hook_not_set()?;
+    verify_pool_allowlist(
+        ai_dex,
+        PoolAllowlistKind::Swap,
+        swap_allowlist,
+        position_authority,
+        swap_gating_token_account,
+    )
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_zap_in_rejects_pool_with_swap_hook() {
+        let mut ai_dex = AiDexPool::default();
+        assert!(verify_zap_swap_allowed(&ai_dex, None, &Pubkey::new_unique(), None).is_ok());
+
+        ai_dex.set_swap_hook_program(Pubkey::new_unique());
+        let result = verify_zap_swap_allowed(&ai_dex, None, &Pubkey::new_unique(), None);
+        assert_eq!(result.unwrap_err(), ErrorCode::SwapHookNotSupportedError.into());
+    }
+}
@@ -0,0 +1,273 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::transfer_memo;
+use crate::errors::ErrorCode;
+use crate::math::convert_to_liquidity_delta;
+use crate::orchestrator::liquidity_orchestrator::{
+    calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
+};
+use crate::util::{
+    calculate_transfer_fee_excluded_amount, check_deadline, parse_remaining_accounts,
+    to_timestamp_u64, transfer_from_vault_to_owner, verify_position_authority,
+    AccountsType, RemainingAccountsInfo, TickArrayAccount,
+};
+
+use super::{swap_within_pool, verify_zap_swap_allowed, Zap};
+
+#[event]
+pub struct ZapOutEvent {
+    pub position_authority: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub position: Pubkey,
+    pub a_to_b: bool,
+    pub liquidity_amount: u128,
+    pub delta_a: u64,
+    pub delta_b: u64,
+    pub swap_amount_in: u64,
+    pub swap_amount_out: u64,
+    pub amount_out: u64,
+    pub min_out: u64,
+    pub timestamp: u64,
+}
+
+/// Withdraws liquidity from a position into a single token.
+///
+/// The liquidity is withdrawn to the owner's token accounts like `decrease_liquidity`, then the
+/// whole amount received of the counter token is swapped into the output token through the same pool.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the zap.
+/// * `liquidity_amount` - The amount of liquidity to withdraw.
+/// * `a_to_b` - If true the counter token is token A and the output token is token B, otherwise the reverse.
+/// * `sqrt_price_limit` - The square root price limit for the swap. `0` means no limit in the swap direction.
+/// * `min_out` - The minimum total amount of the output token the owner receives.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+/// * `deadline_timestamp` - Optional unix timestamp after which the transaction is rejected.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the zap is successful, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::ZeroLiquidityError` - If the liquidity amount is zero.
+/// * `ErrorCode::AmountOutBelowMinimumError` - If the owner receives less than `min_out` of the output token.
+/// * `ErrorCode::TransactionExpiredError` - If the deadline has passed.
+/// * `ErrorCode::PositionLockedError` - If the position is locked.
+/// * `ErrorCode::AuthorityNotAllowlistedError` - If the swap allowlist of the pool is enabled and the position authority is not allowed.
+/// * `ErrorCode::SwapHookNotSupportedError` - If the pool has a swap hook, which zaps do not call.
+pub fn zap_out_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, Zap<'info>>,
+    liquidity_amount: u128,
+    a_to_b: bool,
+    sqrt_price_limit: u128,
+    min_out: u64,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+    deadline_timestamp: Option<i64>,
+) -> Result<()> {
+    verify_zap_swap_allowed(
+        &*ctx.accounts.ai_dex_pool.load()?,
+        ctx.accounts.swap_allowlist.as_deref(),
+        ctx.accounts.position_authority.key,
+        ctx.accounts.swap_gating_token_account.as_deref(),
+    )?;
+
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    if liquidity_amount == 0 {
+        return Err(ErrorCode::ZeroLiquidityError.into());
+    }
+
+    let clock = Clock::get()?;
+    check_deadline(deadline_timestamp, clock.unix_timestamp)?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    if ctx.accounts.position.is_locked(timestamp) {
+        return Err(ErrorCode::PositionLockedError.into());
+    }
+
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::SupplementalTickArrays,
+        ],
+    )?;
+
+    // Withdraw the liquidity
+    let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, false)?;
+
+    let ai_dex_pool_key = ctx.accounts.ai_dex_pool.key();
+    let tick_array_lower = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_lower, &ai_dex_pool_key)?;
+    let tick_array_upper = TickArrayAccount::try_from_with_pool(&ctx.accounts.tick_array_upper, &ai_dex_pool_key)?;
+
+    let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
+    let update = calculate_modify_liquidity(
+        &ai_dex,
+        &ctx.accounts.position,
+        &tick_array_lower,
+        &tick_array_upper,
+        liquidity_delta,
+        timestamp,
+    )?;
+    sync_modify_liquidity_values(
+        &mut ai_dex,
+        &mut ctx.accounts.position,
+        &tick_array_lower,
+        &tick_array_upper,
+        update,
+        timestamp,
+        &ctx.accounts.position_authority,
+        &ctx.accounts.system_program,
+    )?;
+
+    let (delta_a, delta_b) = calculate_liquidity_token_deltas(
+        ai_dex.tick_current_index,
+        ai_dex.sqrt_price,
+        &ctx.accounts.position,
+        liquidity_delta,
+    )?;
+    drop(ai_dex);
+    drop(tick_array_lower);
+    drop(tick_array_upper);
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_owner_account_a,
+        &ctx.accounts.token_program_a,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_a,
+        delta_a,
+        transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
+    )?;
+    transfer_from_vault_to_owner(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_owner_account_b,
+        &ctx.accounts.token_program_b,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_b,
+        delta_b,
+        transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
+    )?;
+
+    // Swap everything received of the counter token into the output token
+    let (counter_mint, output_mint, counter_delta, output_delta) = if a_to_b {
+        (&ctx.accounts.token_mint_a, &ctx.accounts.token_mint_b, delta_a, delta_b)
+    } else {
+        (&ctx.accounts.token_mint_b, &ctx.accounts.token_mint_a, delta_b, delta_a)
+    };
+    let counter_received = calculate_transfer_fee_excluded_amount(counter_mint, counter_delta)?.amount;
+    let output_received = calculate_transfer_fee_excluded_amount(output_mint, output_delta)?.amount;
+
+    let (swap_amount_in, swap_amount_out) = if counter_received > 0 {
+        swap_within_pool(
+            &ctx.accounts,
+            &remaining_accounts,
+            counter_received,
+            a_to_b,
+            sqrt_price_limit,
+            timestamp,
+        )?
+    } else {
+        (0, 0)
+    };
+
+    let amount_out = output_received
+        .checked_add(calculate_transfer_fee_excluded_amount(output_mint, swap_amount_out)?.amount)
+        .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+    if amount_out < min_out {
+        return Err(ErrorCode::AmountOutBelowMinimumError.into());
+    }
+
+    emit_cpi!(ZapOutEvent {
+        position_authority: ctx.accounts.position_authority.key(),
+        ai_dex_pool: ai_dex_pool_key,
+        position: ctx.accounts.position.key(),
+        a_to_b,
+        liquidity_amount,
+        delta_a,
+        delta_b,
+        swap_amount_in,
+        swap_amount_out,
+        amount_out,
+        min_out,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::state::{AiDexPool, PoolAllowlist, PoolAllowlistKind};
+
+    fn swap_gated_pool() -> AiDexPool {
+        let mut ai_dex = AiDexPool::default();
+        ai_dex.set_allowlist_enabled(PoolAllowlistKind::Swap, true);
+        ai_dex
+    }
+
+    fn swap_allowlist_data(authority: Pubkey) -> Vec<u8> {
+        let mut allowlist = PoolAllowlist::default();
+        allowlist.add_authority(authority).unwrap();
+        let mut data = Vec::new();
+        allowlist.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    fn verify_zap_out_swap(ai_dex: &AiDexPool, allowlist_data: &mut [u8], position_authority: &Pubkey) -> Result<()> {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0u64;
+        let owner = crate::ID;
+        let account_info = AccountInfo::new(&key, false, false, &mut lamports, allowlist_data, &owner, false, 0);
+        let swap_allowlist = Account::<PoolAllowlist>::try_from(&account_info)?;
+
+        verify_zap_swap_allowed(ai_dex, Some(&swap_allowlist), position_authority, None)
+    }
+
+    #[test]
+    fn test_zap_out_rejects_owner_not_on_swap_allowlist() {
+        let ai_dex = swap_gated_pool();
+        let mut allowlist_data = swap_allowlist_data(Pubkey::new_unique());
+
+        let result = verify_zap_out_swap(&ai_dex, &mut allowlist_data, &Pubkey::new_unique());
+        assert_eq!(result.unwrap_err(), ErrorCode::AuthorityNotAllowlistedError.into());
+    }
+
+    #[test]
+    fn test_zap_out_allows_owner_on_swap_allowlist() {
+        let ai_dex = swap_gated_pool();
+        let position_authority = Pubkey::new_unique();
+        let mut allowlist_data = swap_allowlist_data(position_authority);
+
+        assert!(verify_zap_out_swap(&ai_dex, &mut allowlist_data, &position_authority).is_ok());
+    }
+
+    #[test]
+    fn test_zap_out_rejects_swap_gated_pool_without_allowlist() {
+        let ai_dex = swap_gated_pool();
+
+        let result = verify_zap_swap_allowed(&ai_dex, None, &Pubkey::new_unique(), None);
+        assert_eq!(result.unwrap_err(), ErrorCode::AuthorityNotAllowlistedError.into());
+    }
+
+    #[test]
+    fn test_zap_out_rejects_pool_with_swap_hook() {
+        let mut ai_dex = AiDexPool::default();
+        ai_dex.set_swap_hook_program(Pubkey::new_unique());
+
+        let result = verify_zap_swap_allowed(&ai_dex, None, &Pubkey::new_unique(), None);
+        assert_eq!(result.unwrap_err(), ErrorCode::SwapHookNotSupportedError.into());
+    }
+}
@@ -1,29 +1,55 @@
 //! A CLMM contract for Dex AI.
+//!
+//! Other programs integrate with ai_dex by depending on this crate with the `cpi` feature, which
+//! drops the entrypoint and exposes the instruction builders under `ai_dex::cpi`, their account
+//! structs under `ai_dex::cpi::accounts`, and the account states, events and return types of the
+//! instructions at the crate root.
+//!
+//! The `math` module, holding the tick, sqrt price and liquidity math of the program, can be used
+//! on its own by off-chain clients, simulators and keepers to reproduce the on-chain arithmetic
+//! exactly. Depending on this crate with `default-features = false` drops the `program` feature,
+//! and with it the anchor and solana dependencies, leaving only the `math` module.
+//!
+//! Off-chain routers quote swaps with `quote::compute_swap`, which runs the swap computation of
+//! the program on a snapshot of a pool and its tick arrays.
+#[cfg(feature = "program")]
 use anchor_lang::prelude::*;
 
+#[cfg(feature = "program")]
 declare_id!("aij9zGKP31THhYTKFVbkrbzXdWywBSvFJYi6TSWqkzE");
+#[cfg(feature = "program")]
 #[doc(hidden)]
 pub mod constants;
-#[doc(hidden)]
+#[cfg(feature = "program")]
 pub mod errors;
-#[doc(hidden)]
+#[cfg(feature = "program")]
 pub mod instructions;
+#[cfg(feature = "program")]
 #[doc(hidden)]
 pub mod orchestrator;
-#[doc(hidden)]
 pub mod math;
+#[cfg(feature = "program")]
+pub mod quote;
+#[cfg(feature = "program")]
 pub mod state;
+#[cfg(feature = "program")]
 #[doc(hidden)]
 pub mod tests;
+#[cfg(feature = "program")]
 #[doc(hidden)]
 pub mod util;
+#[cfg(feature = "program")]
 #[doc(hidden)]
 pub mod security;
 
-use crate::state::{OpenPositionBumps, OpenPositionWithMetadataBumps};
+#[cfg(feature = "program")]
+use crate::state::{ConfigRole, EmissionSegment, FeeDiscountTier, FeeRecipient, FeeTierParams, OpenPositionBumps, OpenPositionWithMetadataBumps, PoolAllowlistKind, SwapPermit, VolumeFeeTier};
+#[cfg(feature = "program")]
 use crate::util::RemainingAccountsInfo;
-use instructions::*;
+#[cfg(feature = "program")]
+pub use instructions::*;
 
+#[cfg(feature = "program")]
 #[program]
 pub mod ai_dex {
     use super::*;
@@ -55,10 +81,179 @@ pub mod ai_dex {
         );
     }
 
+    /// Initializes the extension of an ai dex config.
+    ///
+    /// The extension holds the settings added to the config after its launch, starting with
+    /// the name, symbol and URI given to the position NFTs.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `InitializeConfigExtension` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the config extension is successfully initialized,
+    /// or an error if it fails.
+    pub fn initialize_config_extension(ctx: Context<InitializeConfigExtension>) -> Result<()> {
+        return instructions::initialize_config_extension::initialize_config_extension_handler(ctx);
+    }
+
+    /// Initializes the price oracle of an ai_dex pool.
+    ///
+    /// This function creates the oracle PDA of the pool. Once initialized, every swap
+    /// through the pool records an observation into the oracle ring buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `InitializeOracle` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the oracle initialization is successful,
+    /// or an error if it fails.
+    pub fn initialize_oracle(ctx: Context<InitializeOracle>) -> Result<()> {
+        return instructions::initialize_oracle::initialize_oracle_handler(ctx);
+    }
+
+    /// Initializes the cumulative statistics account of an ai_dex pool.
+    ///
+    /// Once initialized, every swap through the pool adds its volume and fees to the statistics.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `InitializePoolStats` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the statistics initialization is successful,
+    /// or an error if it fails.
+    pub fn initialize_pool_stats(ctx: Context<InitializePoolStats>) -> Result<()> {
+        return instructions::initialize_pool_stats::initialize_pool_stats_handler(ctx);
+    }
+
+    /// Initializes the collection NFT grouping the position NFTs of an ai_dex pool.
+    ///
+    /// Once initialized, the positions opened with metadata can be minted as verified
+    /// members of the collection by passing its mint, metadata and master edition.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `InitializePositionCollection` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the collection initialization is successful,
+    /// or an error if it fails.
+    pub fn initialize_position_collection(ctx: Context<InitializePositionCollection>) -> Result<()> {
+        return instructions::initialize_position_collection::initialize_position_collection_handler(ctx);
+    }
+
+    /// Syncs the wSOL balance of an ai_dex pool vault with the lamports it holds.
+    ///
+    /// This function is permissionless. Lamports sent directly to the vault become part of
+    /// its token balance, without being credited to any position.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SyncNativeVault` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the vault is successfully synced,
+    /// or an error if it fails.
+    pub fn sync_native_vault(ctx: Context<SyncNativeVault>) -> Result<()> {
+        return instructions::sync_native_vault::sync_native_vault_handler(ctx);
+    }
+
+    /// Returns the time-weighted average tick of an ai_dex pool over the given window.
+    ///
+    /// The tick is computed from the pool's oracle observations and is written to the
+    /// instruction return data, so lending protocols and other integrators can CPI into
+    /// ai_dex for manipulation-resistant prices.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `GetTwap` instruction.
+    /// * `window_seconds` - The length of the averaging window in seconds, represented as a `u32`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` containing the time-weighted average tick index,
+    /// or an error if the oracle does not cover the requested window.
+    pub fn get_twap(ctx: Context<GetTwap>, window_seconds: u32) -> Result<i32> {
+        return instructions::get_twap::get_twap_handler(ctx, window_seconds);
+    }
+
+    /// Returns the seconds per liquidity inside a tick range of an ai_dex pool.
+    ///
+    /// The Q64.64 value is written to the instruction return data. The difference between two
+    /// snapshots of the same range, multiplied by the liquidity of a position of the range, is the
+    /// time the position was in range, so incentive programs can reward in-range time.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `GetSecondsPerLiquidityInside` instruction.
+    /// * `tick_lower_index` - The lower tick index of the range, represented as an `i32`.
+    /// * `tick_upper_index` - The upper tick index of the range, represented as an `i32`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` containing the seconds per liquidity inside the range,
+    /// or an error if a tick of the range is not initialized.
+    pub fn get_seconds_per_liquidity_inside(
+        ctx: Context<GetSecondsPerLiquidityInside>,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+    ) -> Result<u128> {
+        return instructions::get_seconds_per_liquidity_inside::get_seconds_per_liquidity_inside_handler(
+            ctx,
+            tick_lower_index,
+            tick_upper_index,
+        );
+    }
+
+    /// Quotes a swap through an ai_dex pool without executing it.
+    ///
+    /// The quote is written to the instruction return data, so aggregators and other programs
+    /// can price routes through CPI. No state is changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `QuoteSwap` instruction.
+    /// * `amount` - The amount to swap, represented as a `u64`.
+    /// * `sqrt_price_limit` - The square root price limit, `0` for none, represented as a `u128`.
+    /// * `amount_specified_is_input` - Whether the amount is the input amount, represented as a `bool`.
+    /// * `a_to_b` - The direction of the swap, represented as a `bool`.
+    /// * `remaining_accounts_info` - Optional remaining accounts information. Up to three
+    ///   `SupplementalTickArrays` can be passed to continue the tick array sequence past `tick_array_2`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` containing the amounts in and out, the fee and the end
+    /// price of the swap, or an error if the swap would fail.
+    pub fn quote_swap<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, QuoteSwap<'info>>,
+        amount: u64,
+        sqrt_price_limit: u128,
+        amount_specified_is_input: bool,
+        a_to_b: bool,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<SwapQuote> {
+        return instructions::quote_swap::quote_swap_handler(
+            ctx,
+            amount,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+            remaining_accounts_info,
+        );
+    }
+
     /// Initializes a new tick array with the given start tick index.
     ///
     /// This function sets up a new tick array starting at the specified tick index.
-    /// It uses the provided context to initialize the tick array.
+    /// It uses the provided context to initialize the tick array, and marks it in the
+    /// pool's tick array bitmap if the pool has one.
     ///
     /// # Arguments
     ///
@@ -76,6 +271,54 @@ pub mod ai_dex {
         return instructions::initialize_tick_array::initialize_tick_array_handler(ctx, start_tick_index);
     }
 
+    /// Initializes several tick arrays with the given start tick indexes in a single instruction.
+    ///
+    /// This function creates up to `MAX_TICK_ARRAYS_PER_INITIALIZE` tick array PDAs, all funded by
+    /// the same funder, so that a wide position can be prepared without one transaction per tick array.
+    /// The tick array accounts are passed as remaining accounts, in the same order as the start tick indexes.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `InitializeTickArrays` instruction.
+    /// * `start_tick_indexes` - The starting index of each tick array, represented as a `Vec<i32>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if all tick arrays are initialized,
+    /// or an error if any of them fails.
+    pub fn initialize_tick_arrays<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, InitializeTickArrays<'info>>,
+        start_tick_indexes: Vec<i32>,
+    ) -> Result<()> {
+        return instructions::initialize_tick_arrays::initialize_tick_arrays_handler(ctx, start_tick_indexes);
+    }
+
+    /// Initializes a new dynamic tick array with the given start tick index.
+    ///
+    /// A dynamic tick array lives at the same address as the fixed tick array it replaces, but only
+    /// stores its initialized ticks. It grows when a position initializes one of its ticks, with the
+    /// position authority paying the additional rent, which makes it cheaper for sparse pools.
+    /// Swaps and liquidity instructions accept both kinds of tick arrays.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `InitializeDynamicTickArray` instruction.
+    /// * `start_tick_index` - The starting index for the tick array, represented as an `i32`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the tick array initialization is successful,
+    /// or an error if it fails.
+    pub fn initialize_dynamic_tick_array(
+        ctx: Context<InitializeDynamicTickArray>,
+        start_tick_index: i32,
+    ) -> Result<()> {
+        return instructions::initialize_dynamic_tick_array::initialize_dynamic_tick_array_handler(
+            ctx,
+            start_tick_index,
+        );
+    }
+
     /// Initializes a new fee tier with the given parameters.
     ///
     /// This function sets up a new fee tier with the specified tick spacing and default fee rate.
@@ -103,6 +346,24 @@ pub mod ai_dex {
         );
     }
 
+    /// Initializes several fee tiers in a single instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `InitializeFeeTiers` instruction, with the fee tier PDAs as remaining accounts.
+    /// * `fee_tiers` - The tick spacing and default fee rate of each fee tier, represented as a `Vec<FeeTierParams>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if all fee tiers are initialized,
+    /// or an error if any of them fails.
+    pub fn initialize_fee_tiers<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, InitializeFeeTiers<'info>>,
+        fee_tiers: Vec<FeeTierParams>,
+    ) -> Result<()> {
+        return instructions::initialize_fee_tiers::initialize_fee_tiers_handler(ctx, fee_tiers);
+    }
+
     /// Opens a new position within the specified tick range. NFT will be minted to represent the position.
     ///
     /// This function sets up a new position with the given lower and upper tick indices.
@@ -133,6 +394,49 @@ pub mod ai_dex {
         );
     }
 
+    /// Opens a new position within the specified tick range and deposits its initial liquidity.
+    /// NFT will be minted to represent the position.
+    ///
+    /// This function combines `open_position` and `increase_liquidity` so the position is never
+    /// left open without liquidity. The funder pays for the new accounts and provides the tokens.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `OpenPositionWithLiquidity` instruction.
+    /// * `tick_lower_index` - The lower tick index for the position, represented as an `i32`.
+    /// * `tick_upper_index` - The upper tick index for the position, represented as an `i32`.
+    /// * `liquidity_amount` - The amount of liquidity to deposit, represented as a `u128`.
+    /// * `token_max_a` - The maximum amount of token A to deposit, represented as a `u64`.
+    /// * `token_max_b` - The maximum amount of token B to deposit, represented as a `u64`.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    /// * `deadline_timestamp` - Optional unix timestamp after which the transaction is rejected with `TransactionExpiredError`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the position is opened and funded,
+    /// or an error if it fails.
+    pub fn open_position_with_liquidity<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, OpenPositionWithLiquidity<'info>>,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+        liquidity_amount: u128,
+        token_max_a: u64,
+        token_max_b: u64,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+        deadline_timestamp: Option<i64>,
+    ) -> Result<()> {
+        return instructions::open_position_with_liquidity::open_position_with_liquidity_handler(
+            ctx,
+            tick_lower_index,
+            tick_upper_index,
+            liquidity_amount,
+            token_max_a,
+            token_max_b,
+            remaining_accounts_info,
+            deadline_timestamp,
+        );
+    }
+
     /// Opens a new position with metadata within the specified tick range.
     /// NFT will be minted to represent the position.
     ///
@@ -165,6 +469,33 @@ pub mod ai_dex {
         );
     }
 
+    /// Opens a new position within the specified tick range, represented by a Token-2022 NFT.
+    ///
+    /// The NFT carries its metadata in its mint through the `MetadataPointer` and `TokenMetadata`
+    /// extensions, so no Metaplex metadata account is created.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `OpenPositionToken22` instruction.
+    /// * `tick_lower_index` - The lower tick index for the position, represented as an `i32`.
+    /// * `tick_upper_index` - The upper tick index for the position, represented as an `i32`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the position is successfully opened,
+    /// or an error if it fails.
+    pub fn open_position_token22(
+        ctx: Context<OpenPositionToken22>,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+    ) -> Result<()> {
+        return instructions::open_position_token22::open_position_token22_handler(
+            ctx,
+            tick_lower_index,
+            tick_upper_index,
+        );
+    }
+
     /// Updates the fees and rewards for a position.
     ///
     /// This function updates the fees and rewards for the specified context.
@@ -182,10 +513,49 @@ pub mod ai_dex {
         return instructions::update_fees_and_rewards::update_fees_and_rewards_handler(ctx);
     }
 
+    /// Updates the fees and rewards of many positions of a pool in a single instruction.
+    ///
+    /// This permissionless crank lets indexers and reward dashboards keep the accrued values of
+    /// positions fresh without one transaction per position.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `UpdateFeesAndRewardsMulti` instruction. The remaining accounts are triples of a writable position and its lower and upper tick arrays.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if all positions are successfully updated,
+    /// or an error if it fails.
+    pub fn update_fees_and_rewards_multi<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, UpdateFeesAndRewardsMulti<'info>>,
+    ) -> Result<()> {
+        return instructions::update_fees_and_rewards_multi::update_fees_and_rewards_multi_handler(ctx);
+    }
+
+    /// Advances the reward accumulators of every pool passed in the remaining accounts.
+    ///
+    /// This permissionless crank lets incentive operators keep the reward accounting of
+    /// low-activity pools fresh without touching any position.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `RefreshPoolRewards` instruction. Each remaining account must be a writable ai_dex pool.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if all pools are successfully refreshed,
+    /// or an error if it fails.
+    pub fn refresh_pool_rewards<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, RefreshPoolRewards>,
+    ) -> Result<()> {
+        return instructions::refresh_pool_rewards::refresh_pool_rewards_handler(ctx);
+    }
+
     /// Closes an existing position in the ai dex pool.
     ///
     /// This function closes an existing position using the provided context.
     /// It ensures that the position is properly closed and any associated resources are released.
+    /// The reclaimed rent of the position account and the position token account is sent to `rent_receiver`.
     ///
     /// # Arguments
     ///
@@ -199,6 +569,41 @@ pub mod ai_dex {
         return instructions::close_position::close_position_handler(ctx);
     }
 
+    /// Removes all liquidity of a position, collects its fees and rewards, and closes it.
+    ///
+    /// This function replaces the `decrease_liquidity`, `collect_fees`, `collect_reward` and
+    /// `close_position` sequence with a single instruction. The accounts of every initialized reward
+    /// (owner account, mint, vault and token program) are passed in the `RewardAccounts` slice of the
+    /// remaining accounts. The reclaimed rent is sent to `rent_receiver`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ClosePositionWithCollect` instruction.
+    /// * `token_min_a` - The minimum amount of token A to receive for the removed liquidity, represented as a `u64`.
+    /// * `token_min_b` - The minimum amount of token B to receive for the removed liquidity, represented as a `u64`.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    /// * `deadline_timestamp` - Optional unix timestamp after which the transaction is rejected with `TransactionExpiredError`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the position is successfully closed,
+    /// or an error if it fails.
+    pub fn close_position_with_collect<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ClosePositionWithCollect<'info>>,
+        token_min_a: u64,
+        token_min_b: u64,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+        deadline_timestamp: Option<i64>,
+    ) -> Result<()> {
+        return instructions::close_position_with_collect::close_position_with_collect_handler(
+            ctx,
+            token_min_a,
+            token_min_b,
+            remaining_accounts_info,
+            deadline_timestamp,
+        );
+    }
+
     /// Sets the default fee rate for the fee tier.
     ///
     /// It uses the provided context (fee authority) and fee rate to update the default fee rate.
@@ -219,6 +624,26 @@ pub mod ai_dex {
         return instructions::set_default_fee_rate::set_default_fee_rate_handler(ctx, default_fee_rate);
     }
 
+    /// Sets whether the pools initialized from the fee tier only allow full range positions.
+    ///
+    /// The pools already initialized from the fee tier are unaffected.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SetFeeTierFullRangeOnly` instruction.
+    /// * `full_range_only` - Whether positions can only be opened on the full range of ticks.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the flag is successfully set,
+    /// or an error if it fails.
+    pub fn set_fee_tier_full_range_only(
+        ctx: Context<SetFeeTierFullRangeOnly>,
+        full_range_only: bool,
+    ) -> Result<()> {
+        return instructions::set_fee_tier_full_range_only::set_fee_tier_full_range_only_handler(ctx, full_range_only);
+    }
+
     /// Sets the default protocol fee rate for the ai dex config.
     /// It uses the provided context (fee authority) and fee rate to update the default protocol fee rate.
     ///
@@ -241,483 +666,2301 @@ pub mod ai_dex {
         );
     }
 
-    /// Sets the fee rate for the ai_dex.
-    ///
-    /// The fee rate is represented as hundredths of a basis point.
+    /// Sets the fee collected from the funder of every new pool of the ai dex config.
     ///
     /// # Arguments
     ///
-    /// * `ctx` - The context (fee authority) for the `SetFeeRate` instruction.
-    /// * `fee_rate` - The fee rate to set, represented as a `u16`.
+    /// * `ctx` - The context for the `SetPoolCreationFee` instruction.
+    /// * `pool_creation_fee` - The fee amount, represented as a `u64`. Zero disables the fee.
+    /// * `pool_creation_fee_mint` - The mint of the fee, represented as a `Pubkey`. The default pubkey means lamports.
+    /// * `pool_creation_fee_destination` - The wallet or token account receiving the fee, represented as a `Pubkey`.
     ///
     /// # Returns
     ///
-    /// This function returns a `Result` which is `Ok` if the fee rate is successfully set,
+    /// This function returns a `Result` which is `Ok` if the pool creation fee is successfully set,
     /// or an error if it fails.
-    pub fn set_fee_rate(ctx: Context<SetFeeRate>, fee_rate: u16) -> Result<()> {
-        return instructions::set_fee_rate::set_fee_rate_handler(ctx, fee_rate);
+    pub fn set_pool_creation_fee(
+        ctx: Context<SetPoolCreationFee>,
+        pool_creation_fee: u64,
+        pool_creation_fee_mint: Pubkey,
+        pool_creation_fee_destination: Pubkey,
+    ) -> Result<()> {
+        return instructions::set_pool_creation_fee::set_pool_creation_fee_handler(
+            ctx,
+            pool_creation_fee,
+            pool_creation_fee_mint,
+            pool_creation_fee_destination,
+        );
     }
 
-    /// Sets the protocol fee rate for an ai_dex.
-    ///
-    /// This function sets the protocol fee rate for the specified ai_dex.
-    /// The protocol fee rate is represented as a basis point.
+    /// Sets the referral fee rate of the ai dex config, the share of the swap fee owed to the referrer of a swap.
     ///
     /// # Arguments
     ///
-    /// * `ctx` - The context (fee authority) for the `SetProtocolFeeRate` instruction.
-    /// * `protocol_fee_rate` - The protocol fee rate to set, represented as a `u16`.
+    /// * `ctx` - The context for the `SetReferralFeeRate` instruction.
+    /// * `referral_fee_rate` - The referral fee rate to set, in basis points of the swap fee, represented as a `u16`.
     ///
     /// # Returns
     ///
-    /// This function returns a `Result` which is `Ok` if the protocol fee rate is successfully set,
+    /// This function returns a `Result` which is `Ok` if the referral fee rate is successfully set,
     /// or an error if it fails.
-    pub fn set_protocol_fee_rate(
-        ctx: Context<SetProtocolFeeRate>,
-        protocol_fee_rate: u16,
-    ) -> Result<()> {
-        return instructions::set_protocol_fee_rate::set_protocol_fee_rate_handler(ctx, protocol_fee_rate);
+    pub fn set_referral_fee_rate(ctx: Context<SetReferralFeeRate>, referral_fee_rate: u16) -> Result<()> {
+        return instructions::set_referral_fee_rate::set_referral_fee_rate_handler(ctx, referral_fee_rate);
     }
 
-    /// Sets the fee authority for an ai dex config.
-    /// The fee authority can set the fee and protocol fee rate for individual pools or
-    /// set the default fee rate for newly minted pools.
+    /// Sets the name, symbol and URI given to the position NFTs minted with metadata.
+    /// It uses the provided context (config authority) to update the config extension.
     ///
     /// # Arguments
     ///
-    /// * `ctx` - The context for the `SetFeeAuthority` instruction.
+    /// * `ctx` - The context for the `SetNftMetadataDefaults` instruction.
+    /// * `name` - The name of the position NFTs, represented as a `String`.
+    /// * `symbol` - The symbol of the position NFTs, represented as a `String`.
+    /// * `uri` - The URI of the position NFTs metadata, represented as a `String`.
     ///
     /// # Returns
     ///
-    /// This function returns a `Result` which is `Ok` if the fee authority is successfully set,
+    /// This function returns a `Result` which is `Ok` if the metadata defaults are successfully set,
     /// or an error if it fails.
-    pub fn set_fee_authority(ctx: Context<SetFeeAuthority>) -> Result<()> {
-        return instructions::set_fee_authority::set_fee_authority_handler(ctx);
+    pub fn set_nft_metadata_defaults(
+        ctx: Context<SetNftMetadataDefaults>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        return instructions::set_nft_metadata_defaults::set_nft_metadata_defaults_handler(ctx, name, symbol, uri);
     }
 
-    /// Sets the ai dex pool reward authority for a specific reward index.
+    /// Sets the fee rate for the ai_dex.
     ///
-    /// This function sets the reward authority for the specified reward index in the context.
-    /// The reward authority can manage the distribution and collection of rewards for the given index.
+    /// The fee rate is represented as hundredths of a basis point.
     ///
     /// # Arguments
     ///
-    /// * `ctx` - The context for the `SetRewardAuthority` instruction.
-    /// * `reward_index` - The index of the reward to set the authority for, represented as a `u8`.
+    /// * `ctx` - The context (fee authority or fee manager of the ai_dex) for the `SetFeeRate` instruction.
+    /// * `fee_rate` - The fee rate to set, represented as a `u16`.
     ///
     /// # Returns
     ///
-    /// This function returns a `Result` which is `Ok` if the reward authority is successfully set,
+    /// This function returns a `Result` which is `Ok` if the fee rate is successfully set,
     /// or an error if it fails.
-    pub fn set_reward_authority(ctx: Context<SetRewardAuthority>, reward_index: u8) -> Result<()> {
-        return instructions::set_reward_authority::set_reward_authority_handler(ctx, reward_index);
+    pub fn set_fee_rate(ctx: Context<SetFeeRate>, fee_rate: u16) -> Result<()> {
+        return instructions::set_fee_rate::set_fee_rate_handler(ctx, fee_rate);
     }
 
-    /// Sets the reward authority for a specific reward index by a super authority.
-    ///
-    /// The super authority has the power to manage the distribution
-    /// and collection of rewards for the given index.
+    /// Sets the fee manager of the ai_dex, allowed to set the fee rate of this ai_dex only.
     ///
     /// # Arguments
     ///
-    /// * `ctx` - The context for the `SetRewardAuthorityBySuperAuthority` instruction.
-    /// * `reward_index` - The index of the reward to set the authority for, represented as a `u8`.
+    /// * `ctx` - The context (fee authority) for the `SetFeeManager` instruction.
+    /// * `fee_manager` - The new fee manager, represented as a `Pubkey`. The default pubkey removes it.
     ///
     /// # Returns
     ///
-    /// This function returns a `Result` which is `Ok` if the reward authority is successfully set,
+    /// This function returns a `Result` which is `Ok` if the fee manager is successfully set,
     /// or an error if it fails.
-    pub fn set_reward_authority_by_config_authority(
-        ctx: Context<SetRewardAuthorityByConfigAuthority>,
-        reward_index: u8,
-    ) -> Result<()> {
-        return instructions::set_reward_authority_by_config_authority::set_reward_authority_by_config_authority_handler(ctx, reward_index);
+    pub fn set_fee_manager(ctx: Context<SetFeeManager>, fee_manager: Pubkey) -> Result<()> {
+        return instructions::set_fee_manager::set_fee_manager_handler(ctx, fee_manager);
     }
 
-    /// Initializes a position trade batch.
-    /// An NFT will be minted to represent the position trade batch in the user's wallet.
-    ///
-    /// It sets up the necessary state and configurations for a batch of trades.
+    /// Sets the volume fee tiers of the ai_dex, stepping its fee rate with its recent volume.
     ///
     /// # Arguments
     ///
-    /// * `ctx` - The context for the `InitializePositionTradeBatch` instruction.
+    /// * `ctx` - The context (fee authority) for the `SetVolumeFeeTiers` instruction.
+    /// * `window_seconds` - The duration of the volume windows, represented as a `u32`. Zero disables the tiers.
+    /// * `tiers` - The fee tiers sorted by increasing minimum volume, represented as a `Vec<VolumeFeeTier>`.
     ///
     /// # Returns
     ///
-    /// This function returns a `Result` which is `Ok` if the position trade batch is successfully initialized,
+    /// This function returns a `Result` which is `Ok` if the volume fee tiers are successfully set,
     /// or an error if it fails.
-    pub fn initialize_position_trade_batch(ctx: Context<InitializePositionTradeBatch>) -> Result<()> {
-        return instructions::initialize_trade_batch_position::initialize_trade_batch_position_handler(ctx);
+    pub fn set_volume_fee_tiers(
+        ctx: Context<SetVolumeFeeTiers>,
+        window_seconds: u32,
+        tiers: Vec<VolumeFeeTier>,
+    ) -> Result<()> {
+        return instructions::set_volume_fee_tiers::set_volume_fee_tiers_handler(ctx, window_seconds, tiers);
     }
 
-    /// Initializes a position trade batch with metaplex metadata.
-    ///
-    /// It sets up the necessary state and configurations for a batch of trades, 
-    /// including additional METAPLEX metadata to identify the token.
+    /// Sets the adaptive fee of the ai_dex, scaling its fee rate with the volatility measured by its oracle.
     ///
     /// # Arguments
     ///
-    /// * `ctx` - The context for the `InitializePositionTradeBatchWithMetadata` instruction.
+    /// * `ctx` - The context (fee authority) for the `SetAdaptiveFee` instruction.
+    /// * `window_seconds` - The oracle window of the volatility, represented as a `u32`. Zero disables the adaptive fee.
+    /// * `fee_rate_per_tick` - The fee rate added per tick of volatility, represented as a `u16`.
+    /// * `min_fee_rate` - The lower bound of the adaptive fee rate, represented as a `u16`.
+    /// * `max_fee_rate` - The upper bound of the adaptive fee rate, represented as a `u16`.
     ///
     /// # Returns
     ///
-    /// This function returns a `Result` which is `Ok` if the position trade batch with metadata is successfully initialized,
+    /// This function returns a `Result` which is `Ok` if the adaptive fee is successfully set,
     /// or an error if it fails.
-    pub fn initialize_position_trade_batch_with_metadata(
-        ctx: Context<InitializePositionTradeBatchWithMetadata>,
+    pub fn set_adaptive_fee(
+        ctx: Context<SetAdaptiveFee>,
+        window_seconds: u32,
+        fee_rate_per_tick: u16,
+        min_fee_rate: u16,
+        max_fee_rate: u16,
     ) -> Result<()> {
-        return instructions::initialize_trade_batch_position_with_metadata::initialize_trade_batch_position_with_metadata_handler(ctx);
+        return instructions::set_adaptive_fee::set_adaptive_fee_handler(
+            ctx,
+            window_seconds,
+            fee_rate_per_tick,
+            min_fee_rate,
+            max_fee_rate,
+        );
     }
 
-    /// Delete a PositionTradeBatch account. Burns the position trade batch token in the owner's wallet.
+    /// Sets the launch fee schedule of the ai_dex, as long as the ai_dex has not opened yet.
     ///
-    /// ### Authority
-    /// - `position_trade_batch_owner` - The owner that owns the position trade batch token.
+    /// # Arguments
     ///
-    /// ### Special Errors
-    /// - `NonDeletablePositionTradeBatchError` - The provided position trade batch has open positions.
-    pub fn delete_position_trade_batch(ctx: Context<DeletePositionTradeBatch>) -> Result<()> {
-        return instructions::delete_trade_batch_position::delete_trade_batch_position_handler(ctx);
+    /// * `ctx` - The context (fee authority) for the `SetLaunchFee` instruction.
+    /// * `start_fee_rate` - The fee rate at the open time, represented as a `u16`.
+    /// * `end_fee_rate` - The fee rate at the end of the schedule, represented as a `u16`.
+    /// * `duration_seconds` - The duration of the schedule after the open time, represented as a `u32`. Zero disables the schedule.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the launch fee is successfully set,
+    /// or an error if it fails.
+    pub fn set_launch_fee(
+        ctx: Context<SetLaunchFee>,
+        start_fee_rate: u16,
+        end_fee_rate: u16,
+        duration_seconds: u32,
+    ) -> Result<()> {
+        return instructions::set_launch_fee::set_launch_fee_handler(ctx, start_fee_rate, end_fee_rate, duration_seconds);
     }
 
-    /// Opens a trade batch position in an ai_dex pool.
+    /// Sets the protocol fee rate for an ai_dex.
     ///
-    /// No new tokens are issued because the owner of the position trade batch becomes the owner of the position.
-    /// The position will start off with 0 liquidity.
+    /// This function sets the protocol fee rate for the specified ai_dex.
+    /// The protocol fee rate is represented as a basis point.
     ///
     /// # Arguments
     ///
-    /// * `ctx` - The context for the `OpenTradeBatchPosition` instruction.
-    /// * `trade_batch_index` - The index of the trade batch to open the position for, represented as a `u16`.
-    /// * `tick_lower_index` - The lower tick index for the position, represented as an `i32`.
-    /// * `tick_upper_index` - The upper tick index for the position, represented as an `i32`.
+    /// * `ctx` - The context (fee authority) for the `SetProtocolFeeRate` instruction.
+    /// * `protocol_fee_rate` - The protocol fee rate to set, represented as a `u16`.
     ///
     /// # Returns
     ///
-    /// This function returns a `Result` which is `Ok` if the trade batch position is successfully opened,
+    /// This function returns a `Result` which is `Ok` if the protocol fee rate is successfully set,
     /// or an error if it fails.
-    pub fn open_trade_batch_position(
-        ctx: Context<OpenTradeBatchPosition>,
-        trade_batch_index: u16,
-        tick_lower_index: i32,
-        tick_upper_index: i32,
+    pub fn set_protocol_fee_rate(
+        ctx: Context<SetProtocolFeeRate>,
+        protocol_fee_rate: u16,
     ) -> Result<()> {
-        return instructions::open_trade_batch_position::open_trade_batch_position_handler(
-            ctx,
-            trade_batch_index,
-            tick_lower_index,
-            tick_upper_index,
-        );
+        return instructions::set_protocol_fee_rate::set_protocol_fee_rate_handler(ctx, protocol_fee_rate);
     }
 
-    /// Closes a trade batch position in the ai dex pool.
+    /// Freezes the fee rate and protocol fee rate of an ai_dex pool.
     ///
-    /// This function closes a trade batch position using the provided context and trade batch index.
-    /// It finalizes the position and performs any necessary cleanup.
+    /// After the freeze, the pool's fee parameters can no longer be changed through
+    /// `set_fee_rate` or `set_protocol_fee_rate`. The freeze cannot be undone.
     ///
     /// # Arguments
     ///
-    /// * `ctx` - The context for the `CloseTradeBatchPosition` instruction.
-    /// * `trade_batch_index` - The index of the trade batch to close the position for, represented as a `u16`.
+    /// * `ctx` - The context (fee authority) for the `FreezeFeeRates` instruction.
     ///
     /// # Returns
     ///
-    /// This function returns a `Result` which is `Ok` if the trade batch position is successfully closed,
+    /// This function returns a `Result` which is `Ok` if the fee rates are successfully frozen,
     /// or an error if it fails.
-    pub fn close_trade_batch_position(
-        ctx: Context<CloseTradeBatchPosition>,
-        trade_batch_index: u16,
-    ) -> Result<()> {
-        return instructions::close_trade_batch_position::close_trade_batch_position_handler(ctx, trade_batch_index);
+    pub fn freeze_fee_rates(ctx: Context<FreezeFeeRates>) -> Result<()> {
+        return instructions::freeze_fee_rates::freeze_fee_rates_handler(ctx);
     }
 
-    /// Collects fees of the protocol.
+    /// Pauses or unpauses an ai dex pool.
     ///
-    /// This function collects fees using the provided context and optional remaining accounts information.
-    /// It handles the fee collection process of the protocol.
+    /// While paused, swaps and liquidity increases are rejected, while decreasing liquidity and
+    /// collecting fees and rewards remain allowed so liquidity providers can always exit.
     ///
     /// # Arguments
     ///
-    /// * `ctx` - The context for the `CollectFees` instruction.
-    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    /// * `ctx` - The context for the `SetPoolPause` instruction.
+    /// * `paused` - Whether the pool is paused, represented as a `bool`.
     ///
     /// # Returns
     ///
-    /// This function returns a `Result` which is `Ok` if the fees are successfully collected,
+    /// This function returns a `Result` which is `Ok` if the pause is successfully set,
     /// or an error if it fails.
-    pub fn collect_fees<'a, 'b, 'c, 'info>(
-        ctx: Context<'a, 'b, 'c, 'info, CollectFees<'info>>,
-        remaining_accounts_info: Option<RemainingAccountsInfo>,
-    ) -> Result<()> {
-        return instructions::collect_fees::collect_fees_handler(ctx, remaining_accounts_info);
+    pub fn set_pool_pause(ctx: Context<SetPoolPause>, paused: bool) -> Result<()> {
+        return instructions::set_pool_pause::set_pool_pause_handler(ctx, paused);
     }
 
-    /// Collects protocol fees for ai dex of the protocol.
-    ///
-    /// This function collects protocol fees using the provided context and optional remaining accounts information.
-    /// It handles the fee collection process of the protocol.
+    /// Sets the emergency authority for an ai dex config.
+    /// The emergency authority is the only one able to pause the whole protocol.
     ///
     /// # Arguments
     ///
-    /// * `ctx` - The context for the `CollectProtocolFees` instruction.
-    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    /// * `ctx` - The context for the `SetEmergencyAuthority` instruction.
     ///
     /// # Returns
     ///
-    /// This function returns a `Result` which is `Ok` if the protocol fees are successfully collected,
+    /// This function returns a `Result` which is `Ok` if the emergency authority is successfully set,
     /// or an error if it fails.
-    pub fn collect_protocol_fees<'a, 'b, 'c, 'info>(
-        ctx: Context<'a, 'b, 'c, 'info, CollectProtocolFees<'info>>,
-        remaining_accounts_info: Option<RemainingAccountsInfo>,
-    ) -> Result<()> {
-        return instructions::collect_protocol_fees::collect_protocol_fees_handler(ctx, remaining_accounts_info);
+    pub fn set_emergency_authority(ctx: Context<SetEmergencyAuthority>) -> Result<()> {
+        return instructions::set_emergency_authority::set_emergency_authority_handler(ctx);
     }
 
-    /// Collects rewards for the position.
+    /// Pauses or unpauses all the pools of an ai dex config.
     ///
-    /// This function collects rewards using the provided context, reward index, and optional remaining accounts information.
-    /// It handles the reward collection process of the protocol.
+    /// While paused, the swaps and the liquidity and position instructions of every pool of the
+    /// config are rejected.
     ///
     /// # Arguments
     ///
-    /// * `ctx` - The context for the `CollectReward` instruction.
-    /// * `reward_index` - The index of the reward to collect, represented as a `u8`.
-    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    /// * `ctx` - The context for the `SetProtocolPause` instruction.
+    /// * `paused` - Whether the protocol is paused, represented as a `bool`.
     ///
     /// # Returns
     ///
-    /// This function returns a `Result` which is `Ok` if the rewards are successfully collected,
+    /// This function returns a `Result` which is `Ok` if the pause is successfully set,
     /// or an error if it fails.
-    pub fn collect_reward<'a, 'b, 'c, 'info>(
-        ctx: Context<'a, 'b, 'c, 'info, CollectReward<'info>>,
-        reward_index: u8,
-        remaining_accounts_info: Option<RemainingAccountsInfo>,
-    ) -> Result<()> {
-        return instructions::collect_reward::collect_reward_handler(ctx, reward_index, remaining_accounts_info);
+    pub fn set_protocol_pause(ctx: Context<SetProtocolPause>, paused: bool) -> Result<()> {
+        return instructions::set_protocol_pause::set_protocol_pause_handler(ctx, paused);
     }
 
-    /// Decreases the liquidity for a position in the ai dex pool with additional account information.
+    /// Puts an ai dex pool in emergency mode for good.
     ///
-    /// This function reduces the liquidity for the specified position, ensuring that the minimum
-    /// token amounts are met. It uses the provided context and optional remaining accounts information
-    /// to perform the operation.
+    /// In emergency mode, swaps and liquidity increases are rejected and the positions can
+    /// withdraw their principal without the tick arrays.
     ///
     /// # Arguments
     ///
-    /// * `ctx` - The context for the `ModifyLiquidity` instruction.
-    /// * `liquidity_amount` - The amount of liquidity to be decreased, represented as a `u128`.
-    /// * `token_min_a` - The minimum amount of token A to be received, represented as a `u64`.
-    /// * `token_min_b` - The minimum amount of token B to be received, represented as a `u64`.
-    /// * `remaining_accounts_info` - Optional additional account information for the operation.
+    /// * `ctx` - The context (emergency authority) for the `EnablePoolEmergencyMode` instruction.
     ///
     /// # Returns
     ///
-    /// This function returns a `Result` which is `Ok` if the liquidity decrease is successful,
+    /// This function returns a `Result` which is `Ok` if the emergency mode is successfully enabled,
     /// or an error if it fails.
-    pub fn decrease_liquidity<'a, 'b, 'c, 'info>(
-        ctx: Context<'a, 'b, 'c, 'info, ModifyLiquidity<'info>>,
-        liquidity_amount: u128,
-        token_min_a: u64,
-        token_min_b: u64,
-        remaining_accounts_info: Option<RemainingAccountsInfo>,
-    ) -> Result<()> {
-        return instructions::decrease_liquidity::decrease_liquidity_handler(
-            ctx,
-            liquidity_amount,
-            token_min_a,
-            token_min_b,
-            remaining_accounts_info,
-        );
+    pub fn enable_pool_emergency_mode(ctx: Context<EnablePoolEmergencyMode>) -> Result<()> {
+        return instructions::enable_pool_emergency_mode::enable_pool_emergency_mode_handler(ctx);
     }
 
-    /// Increases the liquidity for a position in the ai dex pool with additional parameters.
-    ///
-    /// This function increases the liquidity for a position using the specified amounts of tokens.
-    /// It also allows for additional account information to be provided.
+    /// Upgrades an ai dex pool to the current layout, growing its account if needed. Permissionless.
     ///
     /// # Arguments
     ///
-    /// * `ctx` - The context for the `ModifyLiquidity` instruction.
-    /// * `liquidity_amount` - The amount of liquidity to add, represented as a `u128`.
-    /// * `token_max_a` - The maximum amount of token A to use, represented as a `u64`.
-    /// * `token_max_b` - The maximum amount of token B to use, represented as a `u64`.
-    /// * `remaining_accounts_info` - Optional additional account information.
+    /// * `ctx` - The context (funder) for the `MigratePool` instruction.
     ///
     /// # Returns
     ///
-    /// This function returns a `Result` which is `Ok` if the liquidity increase is successful,
+    /// This function returns a `Result` which is `Ok` if the pool is successfully migrated,
     /// or an error if it fails.
-    pub fn increase_liquidity<'a, 'b, 'c, 'info>(
-        ctx: Context<'a, 'b, 'c, 'info, ModifyLiquidity<'info>>,
-        liquidity_amount: u128,
-        token_max_a: u64,
-        token_max_b: u64,
-        remaining_accounts_info: Option<RemainingAccountsInfo>,
-    ) -> Result<()> {
-        return instructions::increase_liquidity::increase_liquidity_handler(
-            ctx,
-            liquidity_amount,
-            token_max_a,
-            token_max_b,
-            remaining_accounts_info,
-        );
+    pub fn migrate_pool(ctx: Context<MigratePool>) -> Result<()> {
+        return instructions::migrate_pool::migrate_pool_handler(ctx);
     }
 
-    /// Initializes a new ai dex pool with the given parameters.
-    ///
-    /// This function sets up a new pool with the specified tick spacing and initial square root price.
-    /// It uses the provided context to initialize the pool.
+    /// Upgrades a position, position trade batch, fee tier or config to its current layout,
+    /// growing its account if needed. Permissionless.
     ///
     /// # Arguments
     ///
-    /// * `ctx` - The context for the `InitializePool` instruction.
-    /// * `tick_spacing` - The spacing between ticks in the pool, represented as a `u16`.
-    /// * `initial_sqrt_price` - The initial square root price of the pool, represented as a `u128`.
+    /// * `ctx` - The context (funder) for the `MigrateAccount` instruction.
     ///
     /// # Returns
     ///
-    /// This function returns a `Result` which is `Ok` if the pool initialization is successful,
+    /// This function returns a `Result` which is `Ok` if the account is successfully migrated,
     /// or an error if it fails.
-    pub fn initialize_pool(
-        ctx: Context<InitializePool>,
-        tick_spacing: u16,
-        initial_sqrt_price: u128,
-    ) -> Result<()> {
-        return instructions::initialize_pool::initialize_pool_handler(
-            ctx,
-            tick_spacing,
-            initial_sqrt_price,
-        );
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        return instructions::migrate_account::migrate_account_handler(ctx);
     }
 
-    /// Initializes a new reward for an ai dex. 
-    ///
-    /// A pool can only support up to a set number of rewards.
-    ///
-    /// This function sets up a new reward for the specified pool. It uses the provided context
-    /// and reward index to initialize the reward.
+    /// Withdraws the whole liquidity of a position of a pool in emergency mode, without the tick arrays.
     ///
     /// # Arguments
     ///
-    /// * `ctx` - The context for the `InitializeReward` instruction.
-    /// * `reward_index` - The index of the reward to be initialized, represented as a `u8`.
+    /// * `ctx` - The context for the `EmergencyWithdraw` instruction.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
     ///
     /// # Returns
     ///
-    /// This function returns a `Result` which is `Ok` if the reward initialization is successful,
+    /// This function returns a `Result` which is `Ok` if the principal is successfully withdrawn,
     /// or an error if it fails.
-    pub fn initialize_reward(ctx: Context<InitializeReward>, reward_index: u8) -> Result<()> {
-        return instructions::initialize_reward::initialize_reward_handler(ctx, reward_index);
+    pub fn emergency_withdraw<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, EmergencyWithdraw<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::emergency_withdraw::emergency_withdraw_handler(ctx, remaining_accounts_info);
     }
 
-    /// Sets the reward emissions rate for a specific reward in the ai dex pool (version 2).
+    /// Sets the circuit breaker of an ai dex pool.
     ///
-    /// This function updates the emissions rate for the specified reward index in the pool.
-    /// It uses the provided context to set the emissions rate.
+    /// Swaps moving the price more than `max_tick_move` ticks within a window of `window_seconds`
+    /// are rejected.
     ///
     /// # Arguments
     ///
-    /// * `ctx` - The context for the `SetRewardEmissions` instruction.
-    /// * `reward_index` - The index of the reward to update, represented as a `u8`.
-    /// * `emissions_per_second_x64` - The emissions rate per second for the reward, represented as a `u128`.
+    /// * `ctx` - The context for the `SetCircuitBreaker` instruction.
+    /// * `max_tick_move` - The maximum tick move within a window, represented as a `u32`. Zero disables the circuit breaker.
+    /// * `window_seconds` - The length of a window in seconds, represented as a `u32`.
     ///
     /// # Returns
     ///
-    /// This function returns a `Result` which is `Ok` if the emissions rate is successfully set,
+    /// This function returns a `Result` which is `Ok` if the circuit breaker is successfully set,
     /// or an error if it fails.
-    pub fn set_reward_emissions(
-        ctx: Context<SetRewardEmissions>,
-        reward_index: u8,
-        emissions_per_second_x64: u128,
+    pub fn set_circuit_breaker(
+        ctx: Context<SetCircuitBreaker>,
+        max_tick_move: u32,
+        window_seconds: u32,
     ) -> Result<()> {
-        return instructions::set_reward_emissions::set_reward_emissions_handler(
-            ctx,
-            reward_index,
-            emissions_per_second_x64,
-        );
+        return instructions::set_circuit_breaker::set_circuit_breaker_handler(ctx, max_tick_move, window_seconds);
     }
 
-    /// Executes a swap operation in the AI DEX protocol.
+    /// Sets the maximum price impact of a single swap in an ai dex pool.
     ///
-    /// This function performs a swap operation with the specified parameters. It uses the provided context
-    /// and additional parameters to execute the swap.
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SetMaxPriceImpact` instruction.
+    /// * `max_price_impact_bps` - The maximum price impact in basis points, represented as a `u16`. Zero disables the cap.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the price impact cap is successfully set,
+    /// or an error if it fails.
+    pub fn set_max_price_impact(ctx: Context<SetMaxPriceImpact>, max_price_impact_bps: u16) -> Result<()> {
+        return instructions::set_max_price_impact::set_max_price_impact_handler(ctx, max_price_impact_bps);
+    }
+
+    /// Sets the maximum share of a vault balance that swaps can take out of an ai dex pool within a slot.
     ///
     /// # Arguments
     ///
-    /// * `ctx` - The context for the `Swap` instruction.
-    /// * `amount` - The amount to be swapped, represented as a `u64`.
-    /// * `other_amount_threshold` - The threshold for the other amount in the swap, represented as a `u64`.
-    /// * `sqrt_price_limit` - The square root price limit for the swap, represented as a `u128`.
-    /// * `amount_specified_is_input` - A boolean indicating whether the specified amount is the input amount.
-    /// * `a_to_b` - A boolean indicating the direction of the swap (true for A to B, false for B to A).
-    /// * `remaining_accounts_info` - Optional remaining accounts information for the swap.
+    /// * `ctx` - The context for the `SetMaxSlotVolume` instruction.
+    /// * `max_slot_volume_bps` - The maximum share in basis points, represented as a `u16`. Zero disables the cap.
     ///
     /// # Returns
     ///
-    /// This function returns a `Result` which is `Ok` if the swap is successful, or an error if it fails.
-    pub fn swap<'a, 'b, 'c, 'info>(
-        ctx: Context<'a, 'b, 'c, 'info, Swap<'info>>,
+    /// This function returns a `Result` which is `Ok` if the slot volume cap is successfully set,
+    /// or an error if it fails.
+    pub fn set_max_slot_volume(ctx: Context<SetMaxSlotVolume>, max_slot_volume_bps: u16) -> Result<()> {
+        return instructions::set_max_slot_volume::set_max_slot_volume_handler(ctx, max_slot_volume_bps);
+    }
+
+    /// Enables or disables the anti-sandwich protection of an ai dex pool.
+    /// While enabled, an authority cannot reverse its own swap within the same slot.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SetAntiSandwich` instruction.
+    /// * `enabled` - Whether the protection is enabled, represented as a `bool`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the protection is successfully set,
+    /// or an error if it fails.
+    pub fn set_anti_sandwich(ctx: Context<SetAntiSandwich>, enabled: bool) -> Result<()> {
+        return instructions::set_anti_sandwich::set_anti_sandwich_handler(ctx, enabled);
+    }
+
+    /// Sets the open time of an ai dex pool, as long as the pool has not opened yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SetPoolOpenTime` instruction.
+    /// * `open_time` - The timestamp from which swaps are allowed, represented as a `u64`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the open time is successfully set,
+    /// or an error if it fails.
+    pub fn set_pool_open_time(ctx: Context<SetPoolOpenTime>, open_time: u64) -> Result<()> {
+        return instructions::set_pool_open_time::set_pool_open_time_handler(ctx, open_time);
+    }
+
+    /// Initializes an empty allowlist of an ai dex pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `InitializePoolAllowlist` instruction.
+    /// * `kind` - The operations of the pool the allowlist restricts, represented as a `PoolAllowlistKind`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the allowlist is successfully initialized,
+    /// or an error if it fails.
+    pub fn initialize_pool_allowlist(ctx: Context<InitializePoolAllowlist>, kind: PoolAllowlistKind) -> Result<()> {
+        return instructions::allowlist::initialize_pool_allowlist::initialize_pool_allowlist_handler(ctx, kind);
+    }
+
+    /// Adds an authority to or removes it from an allowlist of an ai dex pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SetPoolAllowlistAuthority` instruction.
+    /// * `kind` - The operations of the pool the allowlist restricts, represented as a `PoolAllowlistKind`.
+    /// * `authority` - The authority to add or remove, represented as a `Pubkey`.
+    /// * `allowed` - Whether the authority is added or removed, represented as a `bool`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the allowlist is successfully updated,
+    /// or an error if it fails.
+    pub fn set_pool_allowlist_authority(
+        ctx: Context<SetPoolAllowlistAuthority>,
+        kind: PoolAllowlistKind,
+        authority: Pubkey,
+        allowed: bool,
+    ) -> Result<()> {
+        return instructions::allowlist::set_pool_allowlist_authority::set_pool_allowlist_authority_handler(ctx, kind, authority, allowed);
+    }
+
+    /// Sets the gating mint of an allowlist of an ai dex pool, whose holders are allowed without being listed.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SetPoolAllowlistGatingMint` instruction.
+    /// * `kind` - The operations of the pool the allowlist restricts, represented as a `PoolAllowlistKind`.
+    /// * `gating_mint` - The gating mint, represented as a `Pubkey`. The default pubkey removes it.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the gating mint is successfully set,
+    /// or an error if it fails.
+    pub fn set_pool_allowlist_gating_mint(
+        ctx: Context<SetPoolAllowlistGatingMint>,
+        kind: PoolAllowlistKind,
+        gating_mint: Pubkey,
+    ) -> Result<()> {
+        return instructions::allowlist::set_pool_allowlist_gating_mint::set_pool_allowlist_gating_mint_handler(ctx, kind, gating_mint);
+    }
+
+    /// Enables or disables an allowlist of an ai dex pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SetPoolAllowlistEnabled` instruction.
+    /// * `kind` - The operations of the pool the allowlist restricts, represented as a `PoolAllowlistKind`.
+    /// * `enabled` - Whether the allowlist is enabled, represented as a `bool`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the allowlist is successfully enabled or disabled,
+    /// or an error if it fails.
+    pub fn set_pool_allowlist_enabled(
+        ctx: Context<SetPoolAllowlistEnabled>,
+        kind: PoolAllowlistKind,
+        enabled: bool,
+    ) -> Result<()> {
+        return instructions::allowlist::set_pool_allowlist_enabled::set_pool_allowlist_enabled_handler(ctx, kind, enabled);
+    }
+
+    /// Sets the fee authority for an ai dex config.
+    /// The fee authority can set the fee and protocol fee rate for individual pools or
+    /// set the default fee rate for newly minted pools.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SetFeeAuthority` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the fee authority is successfully set,
+    /// or an error if it fails.
+    pub fn set_fee_authority(ctx: Context<SetFeeAuthority>) -> Result<()> {
+        return instructions::set_fee_authority::set_fee_authority_handler(ctx);
+    }
+
+    /// Proposes a new config authority for an ai dex config.
+    /// The current config authority stays in place until the proposed one accepts.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ProposeConfigAuthority` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the config authority is successfully proposed,
+    /// or an error if it fails.
+    pub fn propose_config_authority(ctx: Context<ProposeConfigAuthority>) -> Result<()> {
+        return instructions::propose_config_authority::propose_config_authority_handler(ctx);
+    }
+
+    /// Accepts the config authority proposed for an ai dex config.
+    /// The pending config authority must sign.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `AcceptConfigAuthority` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the config authority is successfully accepted,
+    /// or an error if it fails.
+    pub fn accept_config_authority(ctx: Context<AcceptConfigAuthority>) -> Result<()> {
+        return instructions::accept_config_authority::accept_config_authority_handler(ctx);
+    }
+
+    /// Proposes a new fee authority for an ai dex config.
+    /// The current fee authority stays in place until the proposed one accepts.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ProposeFeeAuthority` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the fee authority is successfully proposed,
+    /// or an error if it fails.
+    pub fn propose_fee_authority(ctx: Context<ProposeFeeAuthority>) -> Result<()> {
+        return instructions::propose_fee_authority::propose_fee_authority_handler(ctx);
+    }
+
+    /// Accepts the fee authority proposed for an ai dex config.
+    /// The pending fee authority must sign.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `AcceptFeeAuthority` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the fee authority is successfully accepted,
+    /// or an error if it fails.
+    pub fn accept_fee_authority(ctx: Context<AcceptFeeAuthority>) -> Result<()> {
+        return instructions::accept_fee_authority::accept_fee_authority_handler(ctx);
+    }
+
+    /// Sets the fee authority of an ai dex config, which sets the fee rates and initializes the fee tiers.
+    /// The role falls back to the config authority while it is not delegated.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SetConfigExtensionAuthority` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the fee authority is successfully set,
+    /// or an error if it fails.
+    pub fn set_config_fee_authority(ctx: Context<SetConfigExtensionAuthority>) -> Result<()> {
+        return instructions::set_config_extension_authority::set_config_extension_authority_handler(ctx, ConfigRole::Fee);
+    }
+
+    /// Sets the collect protocol fees authority of an ai dex config, which collects the protocol fees of the pools.
+    /// The role falls back to the config authority while it is not delegated.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SetConfigExtensionAuthority` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the collect protocol fees authority is successfully set,
+    /// or an error if it fails.
+    pub fn set_config_collect_protocol_fees_authority(ctx: Context<SetConfigExtensionAuthority>) -> Result<()> {
+        return instructions::set_config_extension_authority::set_config_extension_authority_handler(ctx, ConfigRole::CollectProtocolFees);
+    }
+
+    /// Sets the reward emissions super authority of an ai dex config, which sets the reward authorities of the pools.
+    /// The role falls back to the config authority while it is not delegated.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SetConfigExtensionAuthority` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the reward emissions super authority is successfully set,
+    /// or an error if it fails.
+    pub fn set_config_reward_emissions_super_authority(ctx: Context<SetConfigExtensionAuthority>) -> Result<()> {
+        return instructions::set_config_extension_authority::set_config_extension_authority_handler(ctx, ConfigRole::RewardEmissionsSuper);
+    }
+
+    /// Sets the token wrapper authority of an ai dex config, which initializes, updates and deletes the token wrappers.
+    /// The role falls back to the config authority while it is not delegated.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SetConfigExtensionAuthority` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the token wrapper authority is successfully set,
+    /// or an error if it fails.
+    pub fn set_config_token_wrapper_authority(ctx: Context<SetConfigExtensionAuthority>) -> Result<()> {
+        return instructions::set_config_extension_authority::set_config_extension_authority_handler(ctx, ConfigRole::TokenWrapper);
+    }
+
+    /// Sets the pause authority of an ai dex config, which pauses and unpauses the pools.
+    /// The role falls back to the config authority while it is not delegated.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SetConfigExtensionAuthority` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the pause authority is successfully set,
+    /// or an error if it fails.
+    pub fn set_config_pause_authority(ctx: Context<SetConfigExtensionAuthority>) -> Result<()> {
+        return instructions::set_config_extension_authority::set_config_extension_authority_handler(ctx, ConfigRole::Pause);
+    }
+
+    /// Sets the allowlist authority of an ai dex config, which manages the allowlists of the pools.
+    /// The role falls back to the config authority while it is not delegated.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SetConfigExtensionAuthority` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the allowlist authority is successfully set,
+    /// or an error if it fails.
+    pub fn set_config_allowlist_authority(ctx: Context<SetConfigExtensionAuthority>) -> Result<()> {
+        return instructions::set_config_extension_authority::set_config_extension_authority_handler(ctx, ConfigRole::Allowlist);
+    }
+
+    /// Sets the ai dex pool reward authority for a specific reward index.
+    ///
+    /// This function sets the reward authority for the specified reward index in the context.
+    /// The reward authority can manage the distribution and collection of rewards for the given index.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SetRewardAuthority` instruction.
+    /// * `reward_index` - The index of the reward to set the authority for, represented as a `u8`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the reward authority is successfully set,
+    /// or an error if it fails.
+    pub fn set_reward_authority(ctx: Context<SetRewardAuthority>, reward_index: u8) -> Result<()> {
+        return instructions::set_reward_authority::set_reward_authority_handler(ctx, reward_index);
+    }
+
+    /// Proposes a new reward authority for a specific reward index.
+    /// The current reward authority stays in place until the proposed one accepts.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ProposeRewardAuthority` instruction.
+    /// * `reward_index` - The index of the reward to propose the authority for, represented as a `u8`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the reward authority is successfully proposed,
+    /// or an error if it fails.
+    pub fn propose_reward_authority(ctx: Context<ProposeRewardAuthority>, reward_index: u8) -> Result<()> {
+        return instructions::propose_reward_authority::propose_reward_authority_handler(ctx, reward_index);
+    }
+
+    /// Accepts the reward authority proposed for a specific reward index.
+    /// The pending reward authority must sign.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `AcceptRewardAuthority` instruction.
+    /// * `reward_index` - The index of the reward to accept the authority for, represented as a `u8`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the reward authority is successfully accepted,
+    /// or an error if it fails.
+    pub fn accept_reward_authority(ctx: Context<AcceptRewardAuthority>, reward_index: u8) -> Result<()> {
+        return instructions::accept_reward_authority::accept_reward_authority_handler(ctx, reward_index);
+    }
+
+    /// Sets the reward authority for a specific reward index by a super authority.
+    ///
+    /// The super authority has the power to manage the distribution
+    /// and collection of rewards for the given index.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SetRewardAuthorityBySuperAuthority` instruction.
+    /// * `reward_index` - The index of the reward to set the authority for, represented as a `u8`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the reward authority is successfully set,
+    /// or an error if it fails.
+    pub fn set_reward_authority_by_config_authority(
+        ctx: Context<SetRewardAuthorityByConfigAuthority>,
+        reward_index: u8,
+    ) -> Result<()> {
+        return instructions::set_reward_authority_by_config_authority::set_reward_authority_by_config_authority_handler(ctx, reward_index);
+    }
+
+    /// Initializes a position trade batch.
+    /// An NFT will be minted to represent the position trade batch in the user's wallet.
+    ///
+    /// It sets up the necessary state and configurations for a batch of trades.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `InitializePositionTradeBatch` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the position trade batch is successfully initialized,
+    /// or an error if it fails.
+    pub fn initialize_position_trade_batch(ctx: Context<InitializePositionTradeBatch>) -> Result<()> {
+        return instructions::initialize_trade_batch_position::initialize_trade_batch_position_handler(ctx);
+    }
+
+    /// Initializes a position trade batch with metaplex metadata.
+    ///
+    /// It sets up the necessary state and configurations for a batch of trades, 
+    /// including additional METAPLEX metadata to identify the token.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `InitializePositionTradeBatchWithMetadata` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the position trade batch with metadata is successfully initialized,
+    /// or an error if it fails.
+    pub fn initialize_position_trade_batch_with_metadata(
+        ctx: Context<InitializePositionTradeBatchWithMetadata>,
+    ) -> Result<()> {
+        return instructions::initialize_trade_batch_position_with_metadata::initialize_trade_batch_position_with_metadata_handler(ctx);
+    }
+
+    /// Delete a PositionTradeBatch account. Burns the position trade batch token in the owner's wallet.
+    /// The reclaimed rent is sent to `rent_receiver`.
+    ///
+    /// ### Authority
+    /// - `position_trade_batch_owner` - The owner that owns the position trade batch token.
+    ///
+    /// ### Special Errors
+    /// - `NonDeletablePositionTradeBatchError` - The provided position trade batch or its extension has open positions.
+    /// - `TradeBatchExtensionRequiredError` - The trade batch extension has been initialized and is not provided.
+    pub fn delete_position_trade_batch(ctx: Context<DeletePositionTradeBatch>) -> Result<()> {
+        return instructions::delete_trade_batch_position::delete_trade_batch_position_handler(ctx);
+    }
+
+    /// Initializes the trade batch extension of a position trade batch.
+    ///
+    /// The extension tracks the trade batch indexes from `POSITION_TRADE_BATCH_SIZE` up to
+    /// `MAX_TRADE_BATCH_SIZE`. It must be passed to open or close a position at these indexes.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `InitializeTradeBatchExtension` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the trade batch extension is successfully initialized,
+    /// or an error if it fails.
+    pub fn initialize_trade_batch_extension(ctx: Context<InitializeTradeBatchExtension>) -> Result<()> {
+        return instructions::initialize_trade_batch_extension::initialize_trade_batch_extension_handler(ctx);
+    }
+
+    /// Opens a trade batch position in an ai_dex pool.
+    ///
+    /// No new tokens are issued because the owner of the position trade batch becomes the owner of the position.
+    /// The position will start off with 0 liquidity.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `OpenTradeBatchPosition` instruction.
+    /// * `trade_batch_index` - The index of the trade batch to open the position for, represented as a `u16`.
+    /// * `tick_lower_index` - The lower tick index for the position, represented as an `i32`.
+    /// * `tick_upper_index` - The upper tick index for the position, represented as an `i32`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the trade batch position is successfully opened,
+    /// or an error if it fails.
+    pub fn open_trade_batch_position(
+        ctx: Context<OpenTradeBatchPosition>,
+        trade_batch_index: u16,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+    ) -> Result<()> {
+        return instructions::open_trade_batch_position::open_trade_batch_position_handler(
+            ctx,
+            trade_batch_index,
+            tick_lower_index,
+            tick_upper_index,
+        );
+    }
+
+    /// Opens several trade batch positions in an ai_dex pool in a single instruction.
+    ///
+    /// The trade batch position PDAs are passed as remaining accounts, in the same order as the indexes.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `OpenTradeBatchPositions` instruction.
+    /// * `trade_batch_indexes` - The index of each position to open, represented as a `Vec<u16>`.
+    /// * `tick_lower_indexes` - The lower tick index of each position, represented as a `Vec<i32>`.
+    /// * `tick_upper_indexes` - The upper tick index of each position, represented as a `Vec<i32>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the trade batch positions are successfully opened,
+    /// or an error if it fails.
+    pub fn open_trade_batch_positions<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, OpenTradeBatchPositions<'info>>,
+        trade_batch_indexes: Vec<u16>,
+        tick_lower_indexes: Vec<i32>,
+        tick_upper_indexes: Vec<i32>,
+    ) -> Result<()> {
+        return instructions::open_trade_batch_positions::open_trade_batch_positions_handler(
+            ctx,
+            trade_batch_indexes,
+            tick_lower_indexes,
+            tick_upper_indexes,
+        );
+    }
+
+    /// Closes a trade batch position in the ai dex pool.
+    ///
+    /// This function closes a trade batch position using the provided context and trade batch index.
+    /// It finalizes the position and performs any necessary cleanup.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `CloseTradeBatchPosition` instruction.
+    /// * `trade_batch_index` - The index of the trade batch to close the position for, represented as a `u16`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the trade batch position is successfully closed,
+    /// or an error if it fails.
+    pub fn close_trade_batch_position(
+        ctx: Context<CloseTradeBatchPosition>,
+        trade_batch_index: u16,
+    ) -> Result<()> {
+        return instructions::close_trade_batch_position::close_trade_batch_position_handler(ctx, trade_batch_index);
+    }
+
+    /// Collects the fees and rewards of several positions of a position trade batch in a single instruction.
+    ///
+    /// The positions and the accounts of the initialized rewards are passed through the remaining accounts.
+    /// Everything is sent to the token accounts of the owner of the position trade batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `CollectTradeBatchFees` instruction.
+    /// * `remaining_accounts_info` - Information about remaining accounts including the positions and rewards, represented as `Option<RemainingAccountsInfo>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the fees and rewards are successfully collected,
+    /// or an error if it fails.
+    pub fn collect_trade_batch_fees<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CollectTradeBatchFees<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::collect_trade_batch_fees::collect_trade_batch_fees_handler(ctx, remaining_accounts_info);
+    }
+
+    /// Moves a trade batch position to another position trade batch without withdrawing its liquidity.
+    ///
+    /// The position keeps its trade batch index, liquidity, fees, rewards and lock. The authorities of
+    /// both position trade batches must sign.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `TransferTradeBatchPosition` instruction.
+    /// * `trade_batch_index` - The index of the position in both trade batches, represented as a `u16`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the trade batch position is successfully moved,
+    /// or an error if it fails.
+    pub fn transfer_trade_batch_position(
+        ctx: Context<TransferTradeBatchPosition>,
+        trade_batch_index: u16,
+    ) -> Result<()> {
+        return instructions::transfer_trade_batch_position::transfer_trade_batch_position_handler(ctx, trade_batch_index);
+    }
+
+    /// Collects fees of the protocol.
+    ///
+    /// This function collects fees using the provided context and optional remaining accounts information.
+    /// It handles the fee collection process of the protocol.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `CollectFees` instruction.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    ///   A `NativeSol` slice holding the system program delivers the wSOL fees as SOL.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the fees are successfully collected,
+    /// or an error if it fails.
+    pub fn collect_fees<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CollectFees<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::collect_fees::collect_fees_handler(ctx, remaining_accounts_info);
+    }
+
+    /// Collects the fees owed to several positions of the same pool in a single instruction.
+    ///
+    /// The positions and their position token accounts are passed through the remaining accounts.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `CollectFeesMulti` instruction.
+    /// * `remaining_accounts_info` - Information about remaining accounts including the positions, represented as `Option<RemainingAccountsInfo>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the fees are successfully collected,
+    /// or an error if it fails.
+    pub fn collect_fees_multi<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CollectFeesMulti<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::collect_fees_multi::collect_fees_multi_handler(ctx, remaining_accounts_info);
+    }
+
+    /// Collects protocol fees for ai dex of the protocol.
+    ///
+    /// This function collects protocol fees using the provided context and optional remaining accounts information.
+    /// It handles the fee collection process of the protocol, splitting the fees across the recipients of the
+    /// fee distribution of the config if it is passed.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `CollectProtocolFees` instruction.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the protocol fees are successfully collected,
+    /// or an error if it fails.
+    pub fn collect_protocol_fees<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CollectProtocolFees<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::collect_protocol_fees::collect_protocol_fees_handler(ctx, remaining_accounts_info);
+    }
+
+    /// Sweeps the protocol fees owed by a pool into the treasury of its config.
+    ///
+    /// This function is permissionless: the fees can only be moved into token accounts owned by
+    /// the treasury PDA of the config.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SweepProtocolFees` instruction.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the protocol fees are successfully swept,
+    /// or an error if it fails.
+    pub fn sweep_protocol_fees<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, SweepProtocolFees<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::sweep_protocol_fees::sweep_protocol_fees_handler(ctx, remaining_accounts_info);
+    }
+
+    /// Skims the tokens sent to the vaults of a pool outside of the program into the treasury of its config.
+    ///
+    /// This function is permissionless: the excess over the reserves accounted by the pool can only
+    /// be moved into token accounts owned by the treasury PDA of the config.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SkimExcess` instruction.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the excess is successfully skimmed,
+    /// or an error if it fails.
+    pub fn skim_excess<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, SkimExcess<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::skim_excess::skim_excess_handler(ctx, remaining_accounts_info);
+    }
+
+    /// Withdraws tokens from a treasury token account of the config.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `WithdrawTreasury` instruction.
+    /// * `amount` - The amount of tokens to withdraw, represented as a `u64`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the tokens are successfully withdrawn,
+    /// or an error if it fails.
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        return instructions::withdraw_treasury::withdraw_treasury_handler(ctx, amount);
+    }
+
+    /// Initializes the fee distribution of an ai dex config.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `InitializeFeeDistribution` instruction.
+    /// * `recipients` - The recipients of the protocol fees and their weights, represented as a `Vec<FeeRecipient>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the fee distribution is successfully initialized,
+    /// or an error if it fails.
+    pub fn initialize_fee_distribution(
+        ctx: Context<InitializeFeeDistribution>,
+        recipients: Vec<FeeRecipient>,
+    ) -> Result<()> {
+        return instructions::initialize_fee_distribution::initialize_fee_distribution_handler(ctx, recipients);
+    }
+
+    /// Replaces the recipients of the fee distribution of an ai dex config.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SetFeeDistribution` instruction.
+    /// * `recipients` - The recipients of the protocol fees and their weights, represented as a `Vec<FeeRecipient>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the fee distribution is successfully set,
+    /// or an error if it fails.
+    pub fn set_fee_distribution(ctx: Context<SetFeeDistribution>, recipients: Vec<FeeRecipient>) -> Result<()> {
+        return instructions::set_fee_distribution::set_fee_distribution_handler(ctx, recipients);
+    }
+
+    /// Initializes the fee discount registry of an ai dex config.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `InitializeFeeDiscountRegistry` instruction.
+    /// * `tiers` - The fee discount tiers, represented as a `Vec<FeeDiscountTier>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the fee discount registry is successfully initialized,
+    /// or an error if it fails.
+    pub fn initialize_fee_discount_registry(
+        ctx: Context<InitializeFeeDiscountRegistry>,
+        tiers: Vec<FeeDiscountTier>,
+    ) -> Result<()> {
+        return instructions::initialize_fee_discount_registry::initialize_fee_discount_registry_handler(ctx, tiers);
+    }
+
+    /// Replaces the tiers of the fee discount registry of an ai dex config.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SetFeeDiscountTiers` instruction.
+    /// * `tiers` - The fee discount tiers, represented as a `Vec<FeeDiscountTier>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the fee discount tiers are successfully set,
+    /// or an error if it fails.
+    pub fn set_fee_discount_tiers(ctx: Context<SetFeeDiscountTiers>, tiers: Vec<FeeDiscountTier>) -> Result<()> {
+        return instructions::set_fee_discount_tiers::set_fee_discount_tiers_handler(ctx, tiers);
+    }
+
+    /// Initializes the account accruing the referral fees of a referrer on an ai dex pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `InitializeReferralFees` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the referral fees are successfully initialized,
+    /// or an error if it fails.
+    pub fn initialize_referral_fees(ctx: Context<InitializeReferralFees>) -> Result<()> {
+        return instructions::initialize_referral_fees::initialize_referral_fees_handler(ctx);
+    }
+
+    /// Collects the referral fees accrued by a referrer on an ai dex pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `CollectReferralFees` instruction.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the referral fees are successfully collected,
+    /// or an error if it fails.
+    pub fn collect_referral_fees<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CollectReferralFees<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::collect_referral_fees::collect_referral_fees_handler(ctx, remaining_accounts_info);
+    }
+
+    /// Collects rewards for the position.
+    ///
+    /// This function collects rewards using the provided context, reward index, and optional remaining accounts information.
+    /// It handles the reward collection process of the protocol.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `CollectReward` instruction.
+    /// * `reward_index` - The index of the reward to collect, represented as a `u8`.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the rewards are successfully collected,
+    /// or an error if it fails.
+    pub fn collect_reward<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CollectReward<'info>>,
+        reward_index: u8,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::collect_reward::collect_reward_handler(ctx, reward_index, remaining_accounts_info);
+    }
+
+    /// Quotes the fees and rewards a position can collect now, without changing any state.
+    ///
+    /// The amounts are written to the instruction return data, giving wallets and other programs
+    /// the exact claimables without simulating a collect.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `QuoteCollect` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` containing the fees and rewards owed to the position,
+    /// or an error if it fails.
+    pub fn quote_collect(ctx: Context<QuoteCollect>) -> Result<CollectQuote> {
+        return instructions::quote_collect::quote_collect_handler(ctx);
+    }
+
+    /// Withdraws the reward tokens of a vault in excess of the rewards still owed to the positions.
+    ///
+    /// This function lets the reward authority recover the leftover budget of a reward campaign
+    /// once its emissions have ended.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context (reward authority) for the `ClawbackReward` instruction.
+    /// * `reward_index` - The index of the reward to claw back, represented as a `u8`.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the surplus is successfully withdrawn,
+    /// or an error if it fails.
+    pub fn clawback_reward<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ClawbackReward<'info>>,
+        reward_index: u8,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::clawback_reward::clawback_reward_handler(ctx, reward_index, remaining_accounts_info);
+    }
+
+    /// Decreases the liquidity for a position in the ai dex pool with additional account information.
+    ///
+    /// This function reduces the liquidity for the specified position, ensuring that the minimum
+    /// token amounts are met. It uses the provided context and optional remaining accounts information
+    /// to perform the operation.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ModifyLiquidity` instruction.
+    /// * `liquidity_amount` - The amount of liquidity to be decreased, represented as a `u128`.
+    /// * `token_min_a` - The minimum amount of token A to be received, represented as a `u64`.
+    /// * `token_min_b` - The minimum amount of token B to be received, represented as a `u64`.
+    /// * `remaining_accounts_info` - Optional additional account information for the operation.
+    ///   A `NativeSol` slice holding the system program delivers the wSOL side as SOL.
+    /// * `deadline_timestamp` - Optional unix timestamp after which the transaction is rejected with `TransactionExpiredError`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the liquidity decrease is successful,
+    /// or an error if it fails.
+    pub fn decrease_liquidity<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ModifyLiquidity<'info>>,
+        liquidity_amount: u128,
+        token_min_a: u64,
+        token_min_b: u64,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+        deadline_timestamp: Option<i64>,
+    ) -> Result<()> {
+        return instructions::decrease_liquidity::decrease_liquidity_handler(
+            ctx,
+            liquidity_amount,
+            token_min_a,
+            token_min_b,
+            remaining_accounts_info,
+            deadline_timestamp,
+        );
+    }
+
+    /// Increases the liquidity for a position in the ai dex pool with additional parameters.
+    ///
+    /// This function increases the liquidity for a position using the specified amounts of tokens.
+    /// It also allows for additional account information to be provided.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ModifyLiquidity` instruction.
+    /// * `liquidity_amount` - The amount of liquidity to add, represented as a `u128`.
+    /// * `token_max_a` - The maximum amount of token A to use, represented as a `u64`.
+    /// * `token_max_b` - The maximum amount of token B to use, represented as a `u64`.
+    /// * `remaining_accounts_info` - Optional additional account information. A `NativeSol` slice holding
+    ///   the system program pays the wSOL deposit in lamports and closes the wSOL token account of the
+    ///   authority back to SOL afterwards.
+    /// * `deadline_timestamp` - Optional unix timestamp after which the transaction is rejected with `TransactionExpiredError`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the liquidity increase is successful,
+    /// or an error if it fails.
+    pub fn increase_liquidity<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ModifyLiquidity<'info>>,
+        liquidity_amount: u128,
+        token_max_a: u64,
+        token_max_b: u64,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+        deadline_timestamp: Option<i64>,
+    ) -> Result<()> {
+        return instructions::increase_liquidity::increase_liquidity_handler(
+            ctx,
+            liquidity_amount,
+            token_max_a,
+            token_max_b,
+            remaining_accounts_info,
+            deadline_timestamp,
+        );
+    }
+
+    /// Initializes a new ai dex pool with the given parameters.
+    ///
+    /// This function sets up a new pool with the specified tick spacing and initial square root price.
+    /// It uses the provided context to initialize the pool, together with the tick array bitmap
+    /// that tracks which of its tick arrays are initialized. If a reference pool of the same token mints
+    /// and its oracle are passed, the initial price must be close to the time-weighted average price of
+    /// the reference pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `InitializePool` instruction.
+    /// * `tick_spacing` - The spacing between ticks in the pool, represented as a `u16`.
+    /// * `initial_sqrt_price` - The initial square root price of the pool, represented as a `u128`.
+    /// * `open_time` - The timestamp from which swaps are allowed, represented as a `u64`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the pool initialization is successful,
+    /// or an error if it fails.
+    pub fn initialize_pool(
+        ctx: Context<InitializePool>,
+        tick_spacing: u16,
+        initial_sqrt_price: u128,
+        open_time: u64,
+    ) -> Result<()> {
+        return instructions::initialize_pool::initialize_pool_handler(
+            ctx,
+            tick_spacing,
+            initial_sqrt_price,
+            open_time,
+        );
+    }
+
+    /// Initializes a new reward for an ai dex. 
+    ///
+    /// A pool can only support up to a set number of rewards.
+    ///
+    /// This function sets up a new reward for the specified pool. It uses the provided context
+    /// and reward index to initialize the reward.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `InitializeReward` instruction.
+    /// * `reward_index` - The index of the reward to be initialized, represented as a `u8`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the reward initialization is successful,
+    /// or an error if it fails.
+    pub fn initialize_reward(ctx: Context<InitializeReward>, reward_index: u8) -> Result<()> {
+        return instructions::initialize_reward::initialize_reward_handler(ctx, reward_index);
+    }
+
+    /// Sets the reward emissions rate for a specific reward in the ai dex pool (version 2).
+    ///
+    /// This function updates the emissions rate for the specified reward index in the pool.
+    /// It uses the provided context to set the emissions rate.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SetRewardEmissions` instruction.
+    /// * `reward_index` - The index of the reward to update, represented as a `u8`.
+    /// * `emissions_per_second_x64` - The emissions rate per second for the reward, represented as a `u128`.
+    /// * `emissions_end_timestamp` - The timestamp after which no more rewards are emitted, represented as a `u64`. Zero for no end.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the emissions rate is successfully set,
+    /// or an error if it fails.
+    pub fn set_reward_emissions(
+        ctx: Context<SetRewardEmissions>,
+        reward_index: u8,
+        emissions_per_second_x64: u128,
+        emissions_end_timestamp: u64,
+    ) -> Result<()> {
+        return instructions::set_reward_emissions::set_reward_emissions_handler(
+            ctx,
+            reward_index,
+            emissions_per_second_x64,
+            emissions_end_timestamp,
+        );
+    }
+
+    /// Initializes the emission schedule of a reward of an ai dex pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context (reward authority) for the `InitializeEmissionSchedule` instruction.
+    /// * `reward_index` - The index of the reward, represented as a `u8`.
+    /// * `segments` - The emission segments sorted by time, represented as a `Vec<EmissionSegment>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the emission schedule is successfully initialized,
+    /// or an error if it fails.
+    pub fn initialize_emission_schedule(
+        ctx: Context<InitializeEmissionSchedule>,
+        reward_index: u8,
+        segments: Vec<EmissionSegment>,
+    ) -> Result<()> {
+        return instructions::initialize_emission_schedule::initialize_emission_schedule_handler(ctx, reward_index, segments);
+    }
+
+    /// Replaces the segments of the emission schedule of a reward of an ai dex pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context (reward authority) for the `SetEmissionSchedule` instruction.
+    /// * `segments` - The emission segments sorted by time, represented as a `Vec<EmissionSegment>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the emission schedule is successfully set,
+    /// or an error if it fails.
+    pub fn set_emission_schedule(ctx: Context<SetEmissionSchedule>, segments: Vec<EmissionSegment>) -> Result<()> {
+        return instructions::set_emission_schedule::set_emission_schedule_handler(ctx, segments);
+    }
+
+    /// Applies the emission rate of the active segment of an emission schedule. Permissionless.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `AdvanceEmissionSchedule` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the emission schedule is successfully advanced,
+    /// or an error if it fails.
+    pub fn advance_emission_schedule(ctx: Context<AdvanceEmissionSchedule>) -> Result<()> {
+        return instructions::advance_emission_schedule::advance_emission_schedule_handler(ctx);
+    }
+
+    /// Sets the multiplier of the rewards of the locked positions of an ai dex pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context (reward emissions super authority) for the `SetLockedRewardBoost` instruction.
+    /// * `locked_reward_boost_bps` - The reward boost in basis points, represented as a `u16`. Zero disables it.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the reward boost is successfully set,
+    /// or an error if it fails.
+    pub fn set_locked_reward_boost(ctx: Context<SetLockedRewardBoost>, locked_reward_boost_bps: u16) -> Result<()> {
+        return instructions::set_locked_reward_boost::set_locked_reward_boost_handler(ctx, locked_reward_boost_bps);
+    }
+
+    /// Settles the rewards of a position and refreshes its reward boost from its lock. Permissionless.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `RefreshPositionRewardBoost` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the reward boost is successfully refreshed,
+    /// or an error if it fails.
+    pub fn refresh_position_reward_boost(ctx: Context<RefreshPositionRewardBoost>) -> Result<()> {
+        return instructions::refresh_position_reward_boost::refresh_position_reward_boost_handler(ctx);
+    }
+
+    /// Executes a swap operation in the AI DEX protocol.
+    ///
+    /// This function performs a swap operation with the specified parameters. It uses the provided context
+    /// and additional parameters to execute the swap. If the pool has a tick array bitmap, a swap that runs
+    /// past its last tick array with no initialized tick arrays beyond it can continue to the price bound.
+    /// If the referral fees of a referrer are passed, the referral fee rate of the config is carved out of
+    /// the swap fee and accrued to the referrer.
+    /// If the fee discount registry of the config and a token account of the token authority are passed,
+    /// the swap fee rate is discounted by the best tier the token account qualifies for.
+    /// If the pool has a swap hook program, it must be passed and is called after the swap with a
+    /// `SwapHookSummary`, along with the accounts of a `SwapHookAccounts` slice.
+    /// If a session is passed, the token authority is its session key and swaps on behalf of the owner
+    /// of the session, within its limits, on token accounts of the owner delegated to the session.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `Swap` instruction.
+    /// * `amount` - The amount to be swapped, represented as a `u64`.
+    /// * `other_amount_threshold` - The threshold for the other amount in the swap, represented as a `u64`.
+    /// * `sqrt_price_limit` - The square root price limit for the swap, represented as a `u128`. Pass `0` for no limit.
+    /// * `amount_specified_is_input` - A boolean indicating whether the specified amount is the input amount.
+    /// * `a_to_b` - A boolean indicating the direction of the swap (true for A to B, false for B to A).
+    /// * `allow_partial_fill` - A boolean indicating whether the swap may stop at `sqrt_price_limit` before the specified
+    ///   amount is filled. If false, such a swap is rejected with `PartialFillError`.
+    /// * `remaining_accounts_info` - Optional remaining accounts information for the swap. Up to three
+    ///   `SupplementalTickArrays` can be passed to continue the tick array sequence past `tick_array_2`.
+    ///   A `NativeSol` slice holding the system program pays the wSOL input in lamports and closes
+    ///   the wSOL token account of the authority back to SOL after the swap.
+    /// * `deadline_timestamp` - Optional unix timestamp after which the transaction is rejected with `TransactionExpiredError`.
+    /// * `twap_window_seconds` - Optional TWAP window, represented as an `Option<u32>`. If set, `sqrt_price_limit` is the maximum
+    ///   deviation in basis points, one tick each, past the time-weighted average price of the pool over the window.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the swap is successful, or an error if it fails.
+    pub fn swap<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, Swap<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        sqrt_price_limit: u128,
+        amount_specified_is_input: bool,
+        a_to_b: bool,
+        allow_partial_fill: bool,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+        deadline_timestamp: Option<i64>,
+        twap_window_seconds: Option<u32>,
+    ) -> Result<()> {
+        return instructions::swap::swap_handler(
+            ctx,
+            amount,
+            other_amount_threshold,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+            allow_partial_fill,
+            remaining_accounts_info,
+            deadline_timestamp,
+            twap_window_seconds,
+        );
+    }
+
+    /// Swaps tokens, sending the output before the input is paid.
+    ///
+    /// A later top-level `repay_flash_swap` instruction of the same transaction on the same pool
+    /// must pay the input, which is checked through the instructions sysvar.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `FlashSwap` instruction.
+    /// * `amount` - The amount to be swapped, represented as a `u64`.
+    /// * `other_amount_threshold` - The threshold for the other amount in the swap, represented as a `u64`.
+    /// * `sqrt_price_limit` - The square root price limit for the swap, represented as a `u128`. Pass `0` for no limit.
+    /// * `amount_specified_is_input` - A boolean indicating whether the specified amount is the input amount.
+    /// * `a_to_b` - A boolean indicating the direction of the swap (true for A to B, false for B to A).
+    /// * `remaining_accounts_info` - Optional remaining accounts information for the swap.
+    /// * `deadline_timestamp` - Optional unix timestamp after which the transaction is rejected with `TransactionExpiredError`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the flash swap is successful, or an error if it fails.
+    pub fn flash_swap<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, FlashSwap<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        sqrt_price_limit: u128,
+        amount_specified_is_input: bool,
+        a_to_b: bool,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+        deadline_timestamp: Option<i64>,
+    ) -> Result<()> {
+        return instructions::flash_swap::flash_swap_handler(
+            ctx,
+            amount,
+            other_amount_threshold,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+            remaining_accounts_info,
+            deadline_timestamp,
+        );
+    }
+
+    /// Pays the input owed by the flash swap of a pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `RepayFlashSwap` instruction.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the repayment is successful, or an error if it fails.
+    pub fn repay_flash_swap<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, RepayFlashSwap<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::repay_flash_swap::repay_flash_swap_handler(ctx, remaining_accounts_info);
+    }
+
+    /// Executes a two-hop swap with the given parameters.
+    ///
+    /// This function performs a two-hop swap operation, which involves swapping tokens
+    /// through two different pools. It uses the provided context and parameters to execute
+    /// the swap.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `TwoHopSwap` instruction.
+    /// * `amount` - The amount of tokens to swap.
+    /// * `other_amount_threshold` - The threshold for the other amount in the swap.
+    /// * `amount_specified_is_input` - A boolean indicating if the specified amount is the input amount.
+    /// * `a_to_b_one` - A boolean indicating the direction of the first swap (A to B).
+    /// * `a_to_b_two` - A boolean indicating the direction of the second swap (A to B).
+    /// * `sqrt_price_limit_one` - The square root price limit for the first swap. Pass `0` for no limit.
+    /// * `sqrt_price_limit_two` - The square root price limit for the second swap. Pass `0` for no limit.
+    /// * `remaining_accounts_info` - Optional remaining accounts information. `SupplementalTickArraysOne` and
+    ///   `SupplementalTickArraysTwo` extend the tick array sequence of the first and second swap.
+    /// * `deadline_timestamp` - Optional unix timestamp after which the transaction is rejected with `TransactionExpiredError`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the swap is successful,
+    /// or an error if it fails.
+    pub fn two_hop_swap<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, TwoHopSwap<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        amount_specified_is_input: bool,
+        a_to_b_one: bool,
+        a_to_b_two: bool,
+        sqrt_price_limit_one: u128,
+        sqrt_price_limit_two: u128,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+        deadline_timestamp: Option<i64>,
+    ) -> Result<()> {
+        return instructions::two_hop_swap::two_hop_swap_handler(
+            ctx,
+            amount,
+            other_amount_threshold,
+            amount_specified_is_input,
+            a_to_b_one,
+            a_to_b_two,
+            sqrt_price_limit_one,
+            sqrt_price_limit_two,
+            remaining_accounts_info,
+            deadline_timestamp,
+        );
+    }
+
+    /// Executes a swap routed through up to four ai_dex pools.
+    ///
+    /// The accounts of each hop (pool, mints, vaults, token programs, tick arrays and oracle)
+    /// are passed as remaining accounts in route order. Supports both exact-in and exact-out routes.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `MultiHopSwap` instruction.
+    /// * `amount` - The amount to be swapped, represented as a `u64`.
+    /// * `other_amount_threshold` - The threshold for the other amount in the swap, represented as a `u64`.
+    /// * `amount_specified_is_input` - A boolean indicating whether the specified amount is the input amount.
+    /// * `route` - The direction and square root price limit of each hop.
+    /// * `deadline_timestamp` - Optional unix timestamp after which the transaction is rejected with `TransactionExpiredError`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the swap is successful, or an error if it fails.
+    pub fn multi_hop_swap<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, MultiHopSwap<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        amount_specified_is_input: bool,
+        route: Vec<MultiHopSwapHop>,
+        deadline_timestamp: Option<i64>,
+    ) -> Result<()> {
+        return instructions::multi_hop_swap::multi_hop_swap_handler(
+            ctx,
+            amount,
+            other_amount_threshold,
+            amount_specified_is_input,
+            route,
+            deadline_timestamp,
+        );
+    }
+
+    /// Deposits a single token into a position, swapping part of it through the same pool first.
+    ///
+    /// `swap_amount` of the input token is swapped into the other token, then the largest liquidity
+    /// the remaining input and the swap output can fund is deposited into the position. `liquidity_min`
+    /// bounds the slippage of the swap and the deposit together.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `Zap` instruction.
+    /// * `amount` - The total amount of the input token to use, represented as a `u64`.
+    /// * `swap_amount` - The part of `amount` to swap into the other token, represented as a `u64`.
+    /// * `a_to_b` - A boolean indicating whether the input token is token A (true) or token B (false).
+    /// * `sqrt_price_limit` - The square root price limit for the swap, represented as a `u128`. Pass `0` for no limit.
+    /// * `liquidity_min` - The minimum liquidity to deposit, represented as a `u128`.
+    /// * `remaining_accounts_info` - Optional remaining accounts information, including `SupplementalTickArrays` for the swap.
+    /// * `deadline_timestamp` - Optional unix timestamp after which the transaction is rejected with `TransactionExpiredError`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the zap is successful, or an error if it fails.
+    pub fn zap_in<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, Zap<'info>>,
+        amount: u64,
+        swap_amount: u64,
+        a_to_b: bool,
+        sqrt_price_limit: u128,
+        liquidity_min: u128,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+        deadline_timestamp: Option<i64>,
+    ) -> Result<()> {
+        return instructions::zap_in::zap_in_handler(
+            ctx,
+            amount,
+            swap_amount,
+            a_to_b,
+            sqrt_price_limit,
+            liquidity_min,
+            remaining_accounts_info,
+            deadline_timestamp,
+        );
+    }
+
+    /// Withdraws liquidity from a position into a single token.
+    ///
+    /// The liquidity is withdrawn like `decrease_liquidity`, then everything received of the counter
+    /// token is swapped into the output token through the same pool. `min_out` bounds the total amount
+    /// of the output token the owner receives.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `Zap` instruction.
+    /// * `liquidity_amount` - The amount of liquidity to withdraw, represented as a `u128`.
+    /// * `a_to_b` - A boolean indicating whether the output token is token B (true) or token A (false).
+    /// * `sqrt_price_limit` - The square root price limit for the swap, represented as a `u128`. Pass `0` for no limit.
+    /// * `min_out` - The minimum amount of the output token to receive, represented as a `u64`.
+    /// * `remaining_accounts_info` - Optional remaining accounts information, including `SupplementalTickArrays` for the swap.
+    /// * `deadline_timestamp` - Optional unix timestamp after which the transaction is rejected with `TransactionExpiredError`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the zap is successful, or an error if it fails.
+    pub fn zap_out<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, Zap<'info>>,
+        liquidity_amount: u128,
+        a_to_b: bool,
+        sqrt_price_limit: u128,
+        min_out: u64,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+        deadline_timestamp: Option<i64>,
+    ) -> Result<()> {
+        return instructions::zap_out::zap_out_handler(
+            ctx,
+            liquidity_amount,
+            a_to_b,
+            sqrt_price_limit,
+            min_out,
+            remaining_accounts_info,
+            deadline_timestamp,
+        );
+    }
+
+    /// Moves all liquidity of a position into a new tick range in a single instruction.
+    ///
+    /// The position and its NFT are kept. The liquidity is withdrawn from the current range and the
+    /// largest liquidity the withdrawn amounts can fund is deposited into the new range; only the
+    /// leftover tokens leave the vaults. Fees and rewards owed stay in the position.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `RebalancePosition` instruction.
+    /// * `new_tick_lower_index` - The new lower tick index of the position, represented as an `i32`.
+    /// * `new_tick_upper_index` - The new upper tick index of the position, represented as an `i32`.
+    /// * `liquidity_min` - The minimum liquidity to deposit into the new range, represented as a `u128`.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    /// * `deadline_timestamp` - Optional unix timestamp after which the transaction is rejected with `TransactionExpiredError`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the position is rebalanced, or an error if it fails.
+    pub fn rebalance_position<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, RebalancePosition<'info>>,
+        new_tick_lower_index: i32,
+        new_tick_upper_index: i32,
+        liquidity_min: u128,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+        deadline_timestamp: Option<i64>,
+    ) -> Result<()> {
+        return instructions::rebalance_position::rebalance_position_handler(
+            ctx,
+            new_tick_lower_index,
+            new_tick_upper_index,
+            liquidity_min,
+            remaining_accounts_info,
+            deadline_timestamp,
+        );
+    }
+
+    /// Merges a position into another position of the same pool and tick range.
+    ///
+    /// Both positions must be held by the signer. The liquidity and the fees and rewards owed of
+    /// the source position are added to the destination position, and the source position and its
+    /// NFT are closed. The reclaimed rent is sent to `rent_receiver`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `MergePositions` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the positions are merged, or an error if it fails.
+    pub fn merge_positions(ctx: Context<MergePositions>) -> Result<()> {
+        return instructions::merge_positions::merge_positions_handler(ctx);
+    }
+
+    /// Locks the liquidity of a position until the given timestamp.
+    ///
+    /// While locked, `decrease_liquidity` and `close_position` are rejected for the position; fees
+    /// and rewards can still be collected. An existing lock can only be extended.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `LockPosition` instruction.
+    /// * `locked_until` - The unix timestamp until which the liquidity is locked, represented as a `u64`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the position is locked, or an error if it fails.
+    pub fn lock_position(ctx: Context<LockPosition>, locked_until: u64) -> Result<()> {
+        return instructions::lock_position::lock_position_handler(ctx, locked_until);
+    }
+
+    /// Locks the liquidity of a position forever.
+    ///
+    /// Unlike `lock_position`, this lock has no expiry and cannot be undone, so projects can
+    /// verifiably lock launch liquidity while the NFT holder keeps collecting fees and rewards.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `LockPosition` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the position is locked, or an error if it fails.
+    pub fn lock_position_permanently(ctx: Context<LockPosition>) -> Result<()> {
+        return instructions::lock_position_permanently::lock_position_permanently_handler(ctx);
+    }
+
+    /// Reinvests the fees owed to a position as liquidity of the same position.
+    ///
+    /// The fees are collected into the position's liquidity without leaving the vaults, avoiding
+    /// the collect, swap and deposit round trip. Fees that do not fit the current price ratio of
+    /// the position stay owed.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `CompoundPositionFees` instruction.
+    /// * `liquidity_min` - The minimum liquidity to add to the position, represented as a `u128`.
+    /// * `deadline_timestamp` - Optional unix timestamp after which the transaction is rejected with `TransactionExpiredError`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the fees are compounded, or an error if it fails.
+    pub fn compound_position_fees(
+        ctx: Context<CompoundPositionFees>,
+        liquidity_min: u128,
+        deadline_timestamp: Option<i64>,
+    ) -> Result<()> {
+        return instructions::compound_position_fees::compound_position_fees_handler(
+            ctx,
+            liquidity_min,
+            deadline_timestamp,
+        );
+    }
+
+    /// Sets the operator of a position.
+    ///
+    /// The operator can call `increase_liquidity`, `decrease_liquidity`, `collect_fees` and
+    /// `rebalance_position` for the position without holding its NFT, while the withdrawn tokens
+    /// can only go to accounts of the position owner. Passing the default pubkey as the new
+    /// operator removes it.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SetPositionOperator` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the operator is set, or an error if it fails.
+    pub fn set_position_operator(ctx: Context<SetPositionOperator>) -> Result<()> {
+        return instructions::set_position_operator::set_position_operator_handler(ctx);
+    }
+
+    /// Sets the label of a position, a free-form tag for bots and vaults to find their positions.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SetPositionLabel` instruction.
+    /// * `label` - The new label, zero-padded, or all zeros to remove the label, represented as a `[u8; 32]`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the label is set, or an error if it fails.
+    pub fn set_position_label(ctx: Context<SetPositionLabel>, label: [u8; 32]) -> Result<()> {
+        return instructions::set_position_label::set_position_label_handler(ctx, label);
+    }
+
+    /// Initializes the agent strategy of a position.
+    ///
+    /// The strategy designates an agent key that can rebalance the position through
+    /// `agent_rebalance_position`, bounded by the width of the new range, the time between two
+    /// rebalances and the distance between the pool price and its TWAP.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `InitializeAgentStrategy` instruction.
+    /// * `max_tick_width` - The maximum width of the ranges the agent can move the position to, represented as a `u32`.
+    /// * `min_rebalance_interval` - The minimum number of seconds between two rebalances, represented as a `u64`.
+    /// * `max_slippage_bps` - The maximum distance between the pool price and its TWAP, represented as a `u16`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the strategy is initialized, or an error if it fails.
+    pub fn initialize_agent_strategy(
+        ctx: Context<InitializeAgentStrategy>,
+        max_tick_width: u32,
+        min_rebalance_interval: u64,
+        max_slippage_bps: u16,
+    ) -> Result<()> {
+        return instructions::agent::initialize_agent_strategy::initialize_agent_strategy_handler(
+            ctx,
+            max_tick_width,
+            min_rebalance_interval,
+            max_slippage_bps,
+        );
+    }
+
+    /// Updates the agent and the constraints of the agent strategy of a position.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SetAgentStrategy` instruction.
+    /// * `max_tick_width` - The maximum width of the ranges the agent can move the position to, represented as a `u32`.
+    /// * `min_rebalance_interval` - The minimum number of seconds between two rebalances, represented as a `u64`.
+    /// * `max_slippage_bps` - The maximum distance between the pool price and its TWAP, represented as a `u16`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the strategy is updated, or an error if it fails.
+    pub fn set_agent_strategy(
+        ctx: Context<SetAgentStrategy>,
+        max_tick_width: u32,
+        min_rebalance_interval: u64,
+        max_slippage_bps: u16,
+    ) -> Result<()> {
+        return instructions::agent::set_agent_strategy::set_agent_strategy_handler(
+            ctx,
+            max_tick_width,
+            min_rebalance_interval,
+            max_slippage_bps,
+        );
+    }
+
+    /// Rebalances a position on behalf of its holder, signed by the agent of its strategy.
+    ///
+    /// Works like `rebalance_position` within the constraints of the agent strategy, and emits an
+    /// `AgentRebalanceEvent` recording the pool and TWAP ticks the rebalance was made at.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `AgentRebalancePosition` instruction.
+    /// * `new_tick_lower_index` - The new lower tick index of the position, represented as an `i32`.
+    /// * `new_tick_upper_index` - The new upper tick index of the position, represented as an `i32`.
+    /// * `liquidity_min` - The minimum liquidity to deposit into the new range, represented as a `u128`.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    /// * `deadline_timestamp` - Optional unix timestamp after which the transaction is rejected with `TransactionExpiredError`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the position is rebalanced, or an error if it fails.
+    pub fn agent_rebalance_position<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, AgentRebalancePosition<'info>>,
+        new_tick_lower_index: i32,
+        new_tick_upper_index: i32,
+        liquidity_min: u128,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+        deadline_timestamp: Option<i64>,
+    ) -> Result<()> {
+        return instructions::agent::agent_rebalance_position::agent_rebalance_position_handler(
+            ctx,
+            new_tick_lower_index,
+            new_tick_upper_index,
+            liquidity_min,
+            remaining_accounts_info,
+            deadline_timestamp,
+        );
+    }
+
+    /// Marks a single-sided position as a range order.
+    ///
+    /// Once the price fully crosses the range of the position, any keeper can call
+    /// `execute_range_order` to withdraw the converted tokens to the position owner.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `InitializeRangeOrder` instruction.
+    /// * `bounty_bps` - The share of the converted tokens paid to the keeper in basis points, represented as a `u16`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the range order is initialized, or an error if it fails.
+    pub fn initialize_range_order(ctx: Context<InitializeRangeOrder>, bounty_bps: u16) -> Result<()> {
+        return instructions::range_order::initialize_range_order::initialize_range_order_handler(ctx, bounty_bps);
+    }
+
+    /// Cancels a range order, leaving the position untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `CancelRangeOrder` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the range order is cancelled, or an error if it fails.
+    pub fn cancel_range_order(ctx: Context<CancelRangeOrder>) -> Result<()> {
+        return instructions::range_order::cancel_range_order::cancel_range_order_handler(ctx);
+    }
+
+    /// Withdraws a filled range order to the position owner, paying the keeper its bounty.
+    ///
+    /// This instruction is permissionless. It fails until the price has fully crossed the range
+    /// of the position.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ExecuteRangeOrder` instruction.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the range order is executed, or an error if it fails.
+    pub fn execute_range_order<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ExecuteRangeOrder<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::range_order::execute_range_order::execute_range_order_handler(
+            ctx,
+            remaining_accounts_info,
+        );
+    }
+
+    /// Places a limit order resting at a tick of a pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `PlaceLimitOrder` instruction.
+    /// * `tick_index` - The tick whose price the order sells at, represented as an `i32`.
+    /// * `a_to_b` - The direction of the order, represented as a `bool`.
+    /// * `amount` - The amount of input token of the order, represented as a `u64`.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the order is placed, or an error if it fails.
+    pub fn place_limit_order<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, PlaceLimitOrder<'info>>,
+        tick_index: i32,
+        a_to_b: bool,
+        amount: u64,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::limit_order::place_limit_order::place_limit_order_handler(
+            ctx,
+            tick_index,
+            a_to_b,
+            amount,
+            remaining_accounts_info,
+        );
+    }
+
+    /// Cancels a limit order, returning its unfilled input and filled output tokens to the owner.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `CancelLimitOrder` instruction.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the order is cancelled, or an error if it fails.
+    pub fn cancel_limit_order<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CancelLimitOrder<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::limit_order::cancel_limit_order::cancel_limit_order_handler(
+            ctx,
+            remaining_accounts_info,
+        );
+    }
+
+    /// Fills a limit order, fully or partially, at the exact price of its tick.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `FillLimitOrder` instruction.
+    /// * `amount` - The amount of input token of the order to buy, represented as a `u64`.
+    /// * `other_amount_threshold` - The maximum amount of output token to pay, represented as a `u64`.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the order is filled, or an error if it fails.
+    pub fn fill_limit_order<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, FillLimitOrder<'info>>,
         amount: u64,
         other_amount_threshold: u64,
-        sqrt_price_limit: u128,
-        amount_specified_is_input: bool,
-        a_to_b: bool,
         remaining_accounts_info: Option<RemainingAccountsInfo>,
     ) -> Result<()> {
-        return instructions::swap::swap_handler(
+        return instructions::limit_order::fill_limit_order::fill_limit_order_handler(
             ctx,
             amount,
             other_amount_threshold,
+            remaining_accounts_info,
+        );
+    }
+
+    /// Attaches a stop-loss or take-profit order to a position.
+    ///
+    /// Once the pool price crosses a trigger, any keeper can call `trigger_order` to withdraw the
+    /// position into the target token for the position owner.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `InitializeTriggerOrder` instruction.
+    /// * `trigger_sqrt_price_lower` - The stop-loss sqrt price, `0` if unset, represented as a `u128`.
+    /// * `trigger_sqrt_price_upper` - The take-profit sqrt price, `0` if unset, represented as a `u128`.
+    /// * `a_to_b` - If true the target token is token B, otherwise token A, represented as a `bool`.
+    /// * `min_amount_out` - The minimum amount of the target token for the owner, represented as a `u64`.
+    /// * `bounty_bps` - The share of the target token paid to the keeper in basis points, represented as a `u16`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the trigger order is initialized, or an error if it fails.
+    pub fn initialize_trigger_order(
+        ctx: Context<InitializeTriggerOrder>,
+        trigger_sqrt_price_lower: u128,
+        trigger_sqrt_price_upper: u128,
+        a_to_b: bool,
+        min_amount_out: u64,
+        bounty_bps: u16,
+    ) -> Result<()> {
+        return instructions::trigger_order::initialize_trigger_order::initialize_trigger_order_handler(
+            ctx,
+            trigger_sqrt_price_lower,
+            trigger_sqrt_price_upper,
+            a_to_b,
+            min_amount_out,
+            bounty_bps,
+        );
+    }
+
+    /// Cancels a trigger order.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `CancelTriggerOrder` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the trigger order is cancelled, or an error if it fails.
+    pub fn cancel_trigger_order(ctx: Context<CancelTriggerOrder>) -> Result<()> {
+        return instructions::trigger_order::cancel_trigger_order::cancel_trigger_order_handler(ctx);
+    }
+
+    /// Executes a triggered stop-loss or take-profit order, paying the keeper its bounty.
+    ///
+    /// This instruction is permissionless. It fails until the pool price has crossed a trigger of
+    /// the order.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ExecuteTriggerOrder` instruction.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the trigger order is executed, or an error if it fails.
+    pub fn trigger_order<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ExecuteTriggerOrder<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::trigger_order::execute_trigger_order::trigger_order_handler(
+            ctx,
+            remaining_accounts_info,
+        );
+    }
+
+    /// Opens a DCA schedule swapping a deposit through a pool in recurring executions.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `OpenDcaSchedule` instruction.
+    /// * `a_to_b` - The direction of the swaps, represented as a `bool`.
+    /// * `amount` - The amount of input token deposited, represented as a `u64`.
+    /// * `amount_per_interval` - The amount of input token swapped per execution, represented as a `u64`.
+    /// * `interval` - The minimum number of seconds between two executions, represented as a `u64`.
+    /// * `sqrt_price_limit` - The price guard of every execution, `0` if unset, represented as a `u128`.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the schedule is opened, or an error if it fails.
+    pub fn open_dca_schedule<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, OpenDcaSchedule<'info>>,
+        a_to_b: bool,
+        amount: u64,
+        amount_per_interval: u64,
+        interval: u64,
+        sqrt_price_limit: u128,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::dca::open_dca_schedule::open_dca_schedule_handler(
+            ctx,
+            a_to_b,
+            amount,
+            amount_per_interval,
+            interval,
             sqrt_price_limit,
-            amount_specified_is_input,
+            remaining_accounts_info,
+        );
+    }
+
+    /// Executes the next swap of a DCA schedule.
+    ///
+    /// This instruction is permissionless. It fails until the interval of the schedule has
+    /// elapsed since the last execution.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ExecuteDca` instruction.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the execution is successful, or an error if it fails.
+    pub fn execute_dca<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ExecuteDca<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::dca::execute_dca::execute_dca_handler(ctx, remaining_accounts_info);
+    }
+
+    /// Closes a DCA schedule, returning the unswapped deposit and the swapped output to the owner.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `CloseDcaSchedule` instruction.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the schedule is closed, or an error if it fails.
+    pub fn close_dca_schedule<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CloseDcaSchedule<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::dca::close_dca_schedule::close_dca_schedule_handler(
+            ctx,
+            remaining_accounts_info,
+        );
+    }
+
+    /// Opens a TWAP order splitting a large swap into equal tranches over a time window.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `OpenTwapOrder` instruction.
+    /// * `a_to_b` - The direction of the swaps, represented as a `bool`.
+    /// * `amount` - The amount of input token committed to the order, represented as a `u64`.
+    /// * `num_tranches` - The number of tranches, represented as a `u16`.
+    /// * `duration` - The length of the window in seconds, represented as a `u64`.
+    /// * `sqrt_price_limit` - The price guard of every tranche, `0` if unset, represented as a `u128`.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the order is opened, or an error if it fails.
+    pub fn open_twap_order<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, OpenTwapOrder<'info>>,
+        a_to_b: bool,
+        amount: u64,
+        num_tranches: u16,
+        duration: u64,
+        sqrt_price_limit: u128,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::twap_order::open_twap_order::open_twap_order_handler(
+            ctx,
             a_to_b,
+            amount,
+            num_tranches,
+            duration,
+            sqrt_price_limit,
+            remaining_accounts_info,
+        );
+    }
+
+    /// Executes the next due tranche of a TWAP order.
+    ///
+    /// This instruction is permissionless.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ExecuteTwapOrder` instruction.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the tranche is executed, or an error if it fails.
+    pub fn execute_twap_order<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ExecuteTwapOrder<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::twap_order::execute_twap_order::execute_twap_order_handler(
+            ctx,
             remaining_accounts_info,
         );
     }
 
-    /// Executes a two-hop swap with the given parameters.
-    ///
-    /// This function performs a two-hop swap operation, which involves swapping tokens
-    /// through two different pools. It uses the provided context and parameters to execute
-    /// the swap.
+    /// Closes a TWAP order, returning the unswapped input and the swapped output to the owner.
     ///
     /// # Arguments
     ///
-    /// * `ctx` - The context for the `TwoHopSwap` instruction.
-    /// * `amount` - The amount of tokens to swap.
-    /// * `other_amount_threshold` - The threshold for the other amount in the swap.
-    /// * `amount_specified_is_input` - A boolean indicating if the specified amount is the input amount.
-    /// * `a_to_b_one` - A boolean indicating the direction of the first swap (A to B).
-    /// * `a_to_b_two` - A boolean indicating the direction of the second swap (A to B).
-    /// * `sqrt_price_limit_one` - The square root price limit for the first swap.
-    /// * `sqrt_price_limit_two` - The square root price limit for the second swap.
-    /// * `remaining_accounts_info` - Optional remaining accounts information.
+    /// * `ctx` - The context for the `CloseTwapOrder` instruction.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
     ///
     /// # Returns
     ///
-    /// This function returns a `Result` which is `Ok` if the swap is successful,
-    /// or an error if it fails.
-    pub fn two_hop_swap<'a, 'b, 'c, 'info>(
-        ctx: Context<'a, 'b, 'c, 'info, TwoHopSwap<'info>>,
-        amount: u64,
-        other_amount_threshold: u64,
-        amount_specified_is_input: bool,
-        a_to_b_one: bool,
-        a_to_b_two: bool,
-        sqrt_price_limit_one: u128,
-        sqrt_price_limit_two: u128,
+    /// This function returns a `Result` which is `Ok` if the order is closed, or an error if it fails.
+    pub fn close_twap_order<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CloseTwapOrder<'info>>,
         remaining_accounts_info: Option<RemainingAccountsInfo>,
     ) -> Result<()> {
-        return instructions::two_hop_swap::two_hop_swap_handler(
+        return instructions::twap_order::close_twap_order::close_twap_order_handler(
             ctx,
-            amount,
-            other_amount_threshold,
-            amount_specified_is_input,
-            a_to_b_one,
-            a_to_b_two,
-            sqrt_price_limit_one,
-            sqrt_price_limit_two,
             remaining_accounts_info,
         );
     }
@@ -755,4 +2998,408 @@ pub mod ai_dex {
     pub fn delete_token_wrapper(ctx: Context<DeleteTokenWrapper>) -> Result<()> {
         return instructions::wrapper::delete_token_wrapper::delete_token_wrapper_handler(ctx);
     }
+
+    /// Sets the transfer hook program allowlisted by a token wrapper.
+    ///
+    /// Mints with a transfer hook are only supported if their hook program matches the
+    /// allowlisted one.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SetTokenWrapperTransferHookProgram` instruction.
+    /// * `transfer_hook_program_id` - The new allowlisted transfer hook program, represented as a `Pubkey`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the program is successfully set,
+    /// or an error if it fails.
+    pub fn set_token_wrapper_transfer_hook_program(
+        ctx: Context<SetTokenWrapperTransferHookProgram>,
+        transfer_hook_program_id: Pubkey,
+    ) -> Result<()> {
+        return instructions::wrapper::set_token_wrapper_transfer_hook_program::set_token_wrapper_transfer_hook_program_handler(
+            ctx,
+            transfer_hook_program_id,
+        );
+    }
+
+    /// Sets the capability flags of a token wrapper.
+    ///
+    /// Each flag allows one extension or authority of the wrapped token mint.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SetTokenWrapperFlags` instruction.
+    /// * `allow_permanent_delegate` - Whether the mint may have a permanent delegate, represented as a `bool`.
+    /// * `allow_transfer_hook` - Whether the mint may have a transfer hook, represented as a `bool`.
+    /// * `allow_mint_close_authority` - Whether the mint may have a close authority, represented as a `bool`.
+    /// * `allow_freeze_authority` - Whether the mint may have a freeze authority, represented as a `bool`.
+    /// * `allow_default_account_state` - Whether the mint may have a default account state, represented as a `bool`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the flags are successfully set,
+    /// or an error if it fails.
+    pub fn set_token_wrapper_flags(
+        ctx: Context<SetTokenWrapperFlags>,
+        allow_permanent_delegate: bool,
+        allow_transfer_hook: bool,
+        allow_mint_close_authority: bool,
+        allow_freeze_authority: bool,
+        allow_default_account_state: bool,
+    ) -> Result<()> {
+        return instructions::wrapper::set_token_wrapper_flags::set_token_wrapper_flags_handler(
+            ctx,
+            allow_permanent_delegate,
+            allow_transfer_hook,
+            allow_mint_close_authority,
+            allow_freeze_authority,
+            allow_default_account_state,
+        );
+    }
+
+    /// Initializes the fungible LP token of a full range only pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `InitializeLpToken` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the LP token is initialized, or an error if it fails.
+    pub fn initialize_lp_token(ctx: Context<InitializeLpToken>) -> Result<()> {
+        return instructions::lp_token::initialize_lp_token::initialize_lp_token_handler(ctx);
+    }
+
+    /// Deposits tokens into the full range position of an LP token, minting LP tokens in exchange.
+    ///
+    /// The depositor also pays in its share of the fees and rewards owed by the position. The
+    /// accounts of every initialized reward (owner account, mint, vault and token program) are
+    /// passed in the `RewardAccounts` slice of the remaining accounts.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ModifyLpToken` instruction.
+    /// * `lp_amount` - The amount of LP tokens to mint, represented as a `u64`.
+    /// * `token_max_a` - The maximum amount of token A to deposit, represented as a `u64`.
+    /// * `token_max_b` - The maximum amount of token B to deposit, represented as a `u64`.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the deposit is successful, or an error if it fails.
+    pub fn deposit_lp_token<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ModifyLpToken<'info>>,
+        lp_amount: u64,
+        token_max_a: u64,
+        token_max_b: u64,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::lp_token::deposit_lp_token::deposit_lp_token_handler(
+            ctx,
+            lp_amount,
+            token_max_a,
+            token_max_b,
+            remaining_accounts_info,
+        );
+    }
+
+    /// Burns LP tokens, withdrawing their share of the full range position of the LP token.
+    ///
+    /// The holder also receives its share of the fees and rewards owed by the position. The
+    /// accounts of every initialized reward (owner account, mint, vault and token program) are
+    /// passed in the `RewardAccounts` slice of the remaining accounts.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ModifyLpToken` instruction.
+    /// * `lp_amount` - The amount of LP tokens to burn, represented as a `u64`.
+    /// * `token_min_a` - The minimum amount of token A to receive, represented as a `u64`.
+    /// * `token_min_b` - The minimum amount of token B to receive, represented as a `u64`.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the withdrawal is successful, or an error if it fails.
+    pub fn withdraw_lp_token<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ModifyLpToken<'info>>,
+        lp_amount: u64,
+        token_min_a: u64,
+        token_min_b: u64,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::lp_token::withdraw_lp_token::withdraw_lp_token_handler(
+            ctx,
+            lp_amount,
+            token_min_a,
+            token_min_b,
+            remaining_accounts_info,
+        );
+    }
+
+    /// Initializes a vault holding positions of a pool on behalf of the holders of its shares.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `InitializeVault` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the initialization is successful, or an error if it fails.
+    pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
+        return instructions::vault::initialize_vault::initialize_vault_handler(ctx);
+    }
+
+    /// Opens a position of a vault, without liquidity.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `OpenVaultPosition` instruction.
+    /// * `tick_lower_index` - The lower tick index of the position, represented as an `i32`.
+    /// * `tick_upper_index` - The upper tick index of the position, represented as an `i32`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the position is opened, or an error if it fails.
+    pub fn open_vault_position(
+        ctx: Context<OpenVaultPosition>,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+    ) -> Result<()> {
+        return instructions::vault::open_vault_position::open_vault_position_handler(
+            ctx,
+            tick_lower_index,
+            tick_upper_index,
+        );
+    }
+
+    /// Sets the agent allowed to rebalance the positions of a vault.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SetVaultAgent` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the agent is set, or an error if it fails.
+    pub fn set_vault_agent(ctx: Context<SetVaultAgent>) -> Result<()> {
+        return instructions::vault::set_vault_agent::set_vault_agent_handler(ctx);
+    }
+
+    /// Deposits tokens into a vault, minting vault shares in exchange.
+    ///
+    /// The depositor also pays in its share of the fees and rewards owed by the positions of the vault.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ModifyVault` instruction, with the positions of the vault and their tick arrays, then
+    ///   the accounts of every initialized reward (owner account, mint, vault and token program), as remaining accounts.
+    /// * `share_amount` - The amount of vault shares to mint, represented as a `u64`.
+    /// * `token_max_a` - The maximum amount of token A to deposit, represented as a `u64`.
+    /// * `token_max_b` - The maximum amount of token B to deposit, represented as a `u64`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the deposit is successful, or an error if it fails.
+    pub fn deposit_vault<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ModifyVault<'info>>,
+        share_amount: u64,
+        token_max_a: u64,
+        token_max_b: u64,
+    ) -> Result<()> {
+        return instructions::vault::deposit_vault::deposit_vault_handler(
+            ctx,
+            share_amount,
+            token_max_a,
+            token_max_b,
+        );
+    }
+
+    /// Burns vault shares, withdrawing their share of the tokens of the vault.
+    ///
+    /// The holder also receives its share of the fees and rewards owed by the positions of the vault.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `ModifyVault` instruction, with the positions of the vault and their tick arrays, then
+    ///   the accounts of every initialized reward (owner account, mint, vault and token program), as remaining accounts.
+    /// * `share_amount` - The amount of vault shares to burn, represented as a `u64`.
+    /// * `token_min_a` - The minimum amount of token A to receive, represented as a `u64`.
+    /// * `token_min_b` - The minimum amount of token B to receive, represented as a `u64`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the withdrawal is successful, or an error if it fails.
+    pub fn withdraw_vault<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ModifyVault<'info>>,
+        share_amount: u64,
+        token_min_a: u64,
+        token_min_b: u64,
+    ) -> Result<()> {
+        return instructions::vault::withdraw_vault::withdraw_vault_handler(
+            ctx,
+            share_amount,
+            token_min_a,
+            token_min_b,
+        );
+    }
+
+    /// Moves a position of a vault into a new tick range, with a new liquidity.
+    ///
+    /// Callable by the strategy authority or the agent of the vault.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `RebalanceVaultPosition` instruction.
+    /// * `new_tick_lower_index` - The new lower tick index of the position, represented as an `i32`.
+    /// * `new_tick_upper_index` - The new upper tick index of the position, represented as an `i32`.
+    /// * `liquidity_amount` - The liquidity to deposit into the new range, represented as a `u128`.
+    /// * `token_max_a` - The maximum amount of token A to deposit, represented as a `u64`.
+    /// * `token_max_b` - The maximum amount of token B to deposit, represented as a `u64`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the rebalance is successful, or an error if it fails.
+    pub fn rebalance_vault_position(
+        ctx: Context<RebalanceVaultPosition>,
+        new_tick_lower_index: i32,
+        new_tick_upper_index: i32,
+        liquidity_amount: u128,
+        token_max_a: u64,
+        token_max_b: u64,
+    ) -> Result<()> {
+        return instructions::vault::rebalance_vault_position::rebalance_vault_position_handler(
+            ctx,
+            new_tick_lower_index,
+            new_tick_upper_index,
+            liquidity_amount,
+            token_max_a,
+            token_max_b,
+        );
+    }
+
+    /// Whitelists a program as a swap hook the pools of the config can register.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `InitializeSwapHook` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the program is whitelisted, or an error if it fails.
+    pub fn initialize_swap_hook(ctx: Context<InitializeSwapHook>) -> Result<()> {
+        return instructions::swap_hook::initialize_swap_hook::initialize_swap_hook_handler(ctx);
+    }
+
+    /// Removes a program from the swap hooks whitelist of the config.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `DeleteSwapHook` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the program is removed, or an error if it fails.
+    pub fn delete_swap_hook(ctx: Context<DeleteSwapHook>) -> Result<()> {
+        return instructions::swap_hook::delete_swap_hook::delete_swap_hook_handler(ctx);
+    }
+
+    /// Registers a whitelisted program as the swap hook of a pool, or removes it.
+    ///
+    /// The swap hook program is called by `swap` and `swap_with_permit` after each swap of the pool. The other
+    /// instructions swapping through the pool reject it while a swap hook is set. Callable by the config authority
+    /// only.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SetPoolSwapHook` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the swap hook is set, or an error if it fails.
+    pub fn set_pool_swap_hook(ctx: Context<SetPoolSwapHook>) -> Result<()> {
+        return instructions::swap_hook::set_pool_swap_hook::set_pool_swap_hook_handler(ctx);
+    }
+
+    /// Initializes the nonce of the swap permits of an owner.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `InitializeSwapPermitNonce` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the nonce is initialized, or an error if it fails.
+    pub fn initialize_swap_permit_nonce(ctx: Context<InitializeSwapPermitNonce>) -> Result<()> {
+        return instructions::swap_permit::initialize_swap_permit_nonce::initialize_swap_permit_nonce_handler(ctx);
+    }
+
+    /// Swaps tokens on behalf of a token authority that signed the swap parameters off-chain.
+    ///
+    /// The transaction must hold an ed25519 program instruction verifying the signature of the
+    /// permit by the token authority, whose input is paid through the delegation it approved to
+    /// its nonce account.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `SwapWithPermit` instruction.
+    /// * `permit` - The swap parameters and nonce signed by the token authority, represented as a `SwapPermit`.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the swap is successful, or an error if it fails.
+    pub fn swap_with_permit<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, SwapWithPermit<'info>>,
+        permit: SwapPermit,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::swap_permit::swap_with_permit::swap_with_permit_handler(
+            ctx,
+            permit,
+            remaining_accounts_info,
+        );
+    }
+
+    /// Creates a session delegating limited swap rights of the owner to a session key.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `CreateSession` instruction.
+    /// * `expiry_timestamp` - The unix timestamp after which the session key can no longer swap, represented as an `i64`.
+    /// * `notional_mint` - The mint the notional of the swaps is measured in, represented as a `Pubkey`.
+    /// * `max_notional` - The maximum cumulative notional of the swaps, represented as a `u64`.
+    /// * `allowed_pools` - The pools the session key can swap on, represented as a `Vec<Pubkey>`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the session is created, or an error if it fails.
+    pub fn create_session(
+        ctx: Context<CreateSession>,
+        expiry_timestamp: i64,
+        notional_mint: Pubkey,
+        max_notional: u64,
+        allowed_pools: Vec<Pubkey>,
+    ) -> Result<()> {
+        return instructions::session::create_session::create_session_handler(
+            ctx,
+            expiry_timestamp,
+            notional_mint,
+            max_notional,
+            allowed_pools,
+        );
+    }
+
+    /// Revokes a session, closing it so its session key can no longer swap.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `RevokeSession` instruction.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the session is revoked, or an error if it fails.
+    pub fn revoke_session(ctx: Context<RevokeSession>) -> Result<()> {
+        return instructions::session::revoke_session::revoke_session_handler(ctx);
+    }
 }
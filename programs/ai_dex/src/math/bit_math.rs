@@ -1,6 +1,6 @@
-use crate::errors::ErrorCode;
+use crate::math::error::ErrorCode;
 
-use super::U256Muldiv;
+use super::{mul_u256, U256Muldiv};
 
 pub const Q64_RESOLUTION: u8 = 64;
 pub const TO_Q64: u128 = 1u128 << Q64_RESOLUTION;
@@ -23,10 +23,22 @@ pub fn checked_mul_div_round_up_if(
         return Err(ErrorCode::DivisionByZeroError);
     }
 
-    let p = n0.checked_mul(n1).ok_or(ErrorCode::MulDivOverflowError)?;
-    let n = p / d;
+    if let Some(p) = n0.checked_mul(n1) {
+        let n = p / d;
+        return Ok(if round_up && p % d > 0 { n + 1 } else { n });
+    }
+
+    // The product overflows u128, so divide it in 256 bits. Only a quotient above u128::MAX overflows.
+    let (quotient, remainder) = mul_u256(n0, n1).div(U256Muldiv::new(0, d), round_up);
+    let n = quotient
+        .try_into_u128()
+        .map_err(|_| ErrorCode::MulDivOverflowError)?;
 
-    Ok(if round_up && p % d > 0 { n + 1 } else { n })
+    if round_up && !remainder.is_zero() {
+        n.checked_add(1).ok_or(ErrorCode::MulDivOverflowError)
+    } else {
+        Ok(n)
+    }
 }
 
 pub fn checked_mul_shift_right(n0: u128, n1: u128) -> Result<u64, ErrorCode> {
@@ -153,21 +165,26 @@ mod fuzz_tests {
 
             if d == 0 {
                 assert!(result.is_err());
-            } else if n0.checked_mul(n1).is_none() {
-                assert!(result.is_err());
             } else {
                 let other_n0 = U256::from(n0);
                 let other_n1 = U256::from(n1);
                 let other_p = other_n0 * other_n1;
                 let other_d = U256::from(d);
                 let other_result = other_p / other_d;
+                let other_has_remainder = other_p % other_d > U256::from(0);
 
-                let unrounded = checked_mul_div_round_up_if(n0, n1, d, false).unwrap();
-                assert!(U256::from(unrounded) == other_result);
+                if other_result > U256::from(u128::MAX)
+                    || (other_result == U256::from(u128::MAX) && other_has_remainder)
+                {
+                    assert!(result.is_err());
+                } else {
+                    let unrounded = checked_mul_div_round_up_if(n0, n1, d, false).unwrap();
+                    assert!(U256::from(unrounded) == other_result);
 
-                let diff = U256::from(result.unwrap()) - other_result;
-                assert!(diff <= U256::from(1));
-                assert!((diff == U256::from(1)) == (other_p % other_d > U256::from(0)));
+                    let diff = U256::from(result.unwrap()) - other_result;
+                    assert!(diff <= U256::from(1));
+                    assert!((diff == U256::from(1)) == other_has_remainder);
+                }
             }
         }
 
@@ -234,13 +251,25 @@ mod test_bit_math {
             assert_eq!(checked_mul_div(u128::MAX - 1, 1, u128::MAX).unwrap(), 0);
         }
 
+        #[test]
+        fn test_mul_div_product_overflow_ok() {
+            assert_eq!(checked_mul_div(u128::MAX, 2, u128::MAX).unwrap(), 2);
+            assert_eq!(checked_mul_div(u128::MAX, u128::MAX, u128::MAX).unwrap(), u128::MAX);
+            assert_eq!(
+                checked_mul_div(u128::MAX, u128::MAX - 1, u128::MAX).unwrap(),
+                u128::MAX - 1
+            );
+            assert_eq!(
+                checked_mul_div(MAX_FLOOR.0, MAX_FLOOR.1, MAX_FLOOR.2).unwrap(),
+                u128::MAX
+            );
+        }
+
         #[test]
         fn test_mul_div_overflows() {
-            assert!(checked_mul_div(u128::MAX, 2, u128::MAX).is_err());
-            assert!(checked_mul_div(u128::MAX, u128::MAX, u128::MAX).is_err());
-            assert!(checked_mul_div(u128::MAX, u128::MAX - 1, u128::MAX).is_err());
             assert!(checked_mul_div(u128::MAX, 2, 1).is_err());
-            assert!(checked_mul_div(MAX_FLOOR.0, MAX_FLOOR.1, MAX_FLOOR.2).is_err());
+            assert!(checked_mul_div(u128::MAX, u128::MAX, u128::MAX - 1).is_err());
+            assert!(checked_mul_div(u128::MAX, 2, 0).is_err());
         }
 
         #[test]
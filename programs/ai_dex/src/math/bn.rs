@@ -26,7 +26,7 @@ use std::io::{Error, ErrorKind, Write};
 use std::mem::size_of;
 use uint::construct_uint;
 
-use crate::errors::ErrorCode;
+use crate::math::error::ErrorCode;
 
 macro_rules! impl_borsh_serialize_for_bn {
     ($type: ident) => {
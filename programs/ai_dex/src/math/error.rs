@@ -0,0 +1,48 @@
+//! The errors returned by the math functions.
+//!
+//! With the `program` feature these are the program's `ErrorCode`. Without it, the math module
+//! is built on its own and returns a standalone error carrying the same variants and messages,
+//! so that the arithmetic can be reused off-chain without the anchor and solana dependencies.
+#[cfg(feature = "program")]
+pub use crate::errors::ErrorCode;
+
+#[cfg(not(feature = "program"))]
+use std::num::TryFromIntError;
+
+#[cfg(not(feature = "program"))]
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    #[error("Division by zero is not allowed.")]
+    DivisionByZeroError,
+    #[error("Failed to cast number to BigInt.")]
+    BigIntCastError,
+    #[error("Failed to downcast number.")]
+    NumberDowncastError,
+    #[error("Sqrt price is out of bounds.")]
+    SqrtPriceOutOfBoundsError,
+    #[error("Liquidity amount exceeds maximum allowed.")]
+    ExcessiveLiquidityError,
+    #[error("Liquidity overflow error.")]
+    LiquidityOverflowError,
+    #[error("Liquidity underflow error.")]
+    LiquidityUnderflowError,
+    #[error("Exceeded maximum token limit.")]
+    TokenLimitExceededError,
+    #[error("Token amount below minimum required.")]
+    TokenAmountBelowMinimumError,
+    #[error("Multiplication with shift right overflow.")]
+    MultiplicationShiftRightOverflowError,
+    #[error("MulDiv overflow error.")]
+    MulDivOverflowError,
+    #[error("Multiplication overflow error.")]
+    MultiplicationOverflowError,
+    #[error("Calculated amount overflows.")]
+    AmountCalculationOverflowError,
+}
+
+#[cfg(not(feature = "program"))]
+impl From<TryFromIntError> for ErrorCode {
+    fn from(_: TryFromIntError) -> Self {
+        ErrorCode::BigIntCastError
+    }
+}
@@ -0,0 +1,91 @@
+use crate::math::error::ErrorCode;
+
+use super::{mul_u256, U256Muldiv};
+
+// Gets the amount a taker pays to buy amount_in of the input token of a limit order at the
+// price of its tick. Rounds up in favor of the order owner.
+//
+// a_to_b: the order sells token A, the taker pays amount_in * sqrt_price^2 of token B
+// b_to_a: the order sells token B, the taker pays amount_in / sqrt_price^2 of token A
+pub fn get_limit_order_fill_amount(
+    sqrt_price: u128,
+    amount_in: u64,
+    a_to_b: bool,
+) -> Result<u64, ErrorCode> {
+    if sqrt_price == 0 {
+        return Err(ErrorCode::DivisionByZeroError);
+    }
+
+    let amount = if a_to_b {
+        let partial = mul_shift_64_round_up(amount_in as u128, sqrt_price)?;
+        mul_shift_64_round_up(partial, sqrt_price)?
+    } else {
+        let partial = div_shift_64_round_up(amount_in as u128, sqrt_price)?;
+        div_shift_64_round_up(partial, sqrt_price)?
+    };
+
+    if amount > u64::MAX as u128 {
+        return Err(ErrorCode::AmountCalculationOverflowError);
+    }
+    Ok(amount as u64)
+}
+
+// ceil(a * b / 2^64)
+fn mul_shift_64_round_up(a: u128, b: u128) -> Result<u128, ErrorCode> {
+    let product = mul_u256(a, b);
+    let result = product.shift_word_right().try_into_u128()?;
+    if product.get_word(0) != 0 {
+        return result.checked_add(1).ok_or(ErrorCode::MultiplicationOverflowError);
+    }
+    Ok(result)
+}
+
+// ceil(a * 2^64 / b)
+fn div_shift_64_round_up(a: u128, b: u128) -> Result<u128, ErrorCode> {
+    let numerator = U256Muldiv::new(0, a).shift_word_left();
+    let (quotient, remainder) = numerator.div(U256Muldiv::new(0, b), true);
+    let result = quotient.try_into_u128()?;
+    if !remainder.is_zero() {
+        return result.checked_add(1).ok_or(ErrorCode::MultiplicationOverflowError);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod limit_order_math_tests {
+    use super::get_limit_order_fill_amount;
+    use crate::math::sqrt_price_from_tick_index;
+
+    const SQRT_PRICE_ONE: u128 = 1 << 64;
+
+    #[test]
+    fn test_fill_amount_at_price_one() {
+        assert_eq!(get_limit_order_fill_amount(SQRT_PRICE_ONE, 1_000, true).unwrap(), 1_000);
+        assert_eq!(get_limit_order_fill_amount(SQRT_PRICE_ONE, 1_000, false).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn test_fill_amount_at_price_four() {
+        // sqrt_price = 2, so one token A is worth four tokens B
+        let sqrt_price = SQRT_PRICE_ONE * 2;
+        assert_eq!(get_limit_order_fill_amount(sqrt_price, 1_000, true).unwrap(), 4_000);
+        assert_eq!(get_limit_order_fill_amount(sqrt_price, 1_000, false).unwrap(), 250);
+        // rounds up in favor of the order owner
+        assert_eq!(get_limit_order_fill_amount(sqrt_price, 1_001, false).unwrap(), 251);
+    }
+
+    #[test]
+    fn test_fill_amount_round_trip_at_tick() {
+        let sqrt_price = sqrt_price_from_tick_index(1_000);
+        let amount_b = get_limit_order_fill_amount(sqrt_price, 1_000_000, true).unwrap();
+        let amount_a = get_limit_order_fill_amount(sqrt_price, amount_b, false).unwrap();
+        assert!(amount_a >= 1_000_000);
+        assert!(amount_a <= 1_000_002);
+    }
+
+    #[test]
+    fn test_fill_amount_overflow() {
+        let sqrt_price = sqrt_price_from_tick_index(400_000);
+        assert!(get_limit_order_fill_amount(sqrt_price, u64::MAX, true).is_err());
+    }
+}
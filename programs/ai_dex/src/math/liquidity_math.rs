@@ -1,4 +1,6 @@
-use crate::errors::ErrorCode;
+use crate::math::error::ErrorCode;
+
+use super::{increasing_price_order, mul_u256, U256Muldiv};
 
 // Adds a signed liquidity delta to a given integer liquidity amount.
 // Errors on overflow or underflow.
@@ -30,10 +32,75 @@ pub fn convert_to_liquidity_delta(
     Ok(if positive { delta } else { -delta })
 }
 
+// Gets the largest liquidity that can be deposited in the price range [sqrt_price_lower, sqrt_price_upper)
+// at the current sqrt price with at most amount_a of token A and amount_b of token B.
+// Rounds down, so depositing the returned liquidity never needs more than the given amounts.
+pub fn get_liquidity_from_amounts(
+    sqrt_price_current: u128,
+    sqrt_price_lower: u128,
+    sqrt_price_upper: u128,
+    amount_a: u64,
+    amount_b: u64,
+) -> Result<u128, ErrorCode> {
+    if sqrt_price_current <= sqrt_price_lower {
+        get_liquidity_from_amount_a(sqrt_price_lower, sqrt_price_upper, amount_a)
+    } else if sqrt_price_current >= sqrt_price_upper {
+        get_liquidity_from_amount_b(sqrt_price_lower, sqrt_price_upper, amount_b)
+    } else {
+        let liquidity_a = get_liquidity_from_amount_a(sqrt_price_current, sqrt_price_upper, amount_a)?;
+        let liquidity_b = get_liquidity_from_amount_b(sqrt_price_lower, sqrt_price_current, amount_b)?;
+        Ok(liquidity_a.min(liquidity_b))
+    }
+}
+
+// Inverse of get_amount_delta_a
+// liquidity = amount_a * sqrt_price_lower * sqrt_price_upper / (sqrt_price_upper - sqrt_price_lower)
+pub fn get_liquidity_from_amount_a(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    amount_a: u64,
+) -> Result<u128, ErrorCode> {
+    let (sqrt_price_lower, sqrt_price_upper) = increasing_price_order(sqrt_price_0, sqrt_price_1);
+    let sqrt_price_diff = sqrt_price_upper - sqrt_price_lower;
+    if sqrt_price_diff == 0 {
+        return Err(ErrorCode::DivisionByZeroError);
+    }
+
+    // The product of the sqrt prices exceeds 128 bits near the max sqrt price, so it is kept in 256 bits
+    let sqrt_price_product = mul_u256(sqrt_price_lower, sqrt_price_upper).shift_word_right();
+    let numerator = sqrt_price_product.mul(U256Muldiv::new(0, amount_a as u128));
+    let denominator = U256Muldiv::new(0, sqrt_price_diff);
+
+    numerator.div(denominator, false).0.try_into_u128()
+}
+
+// Inverse of get_amount_delta_b
+// liquidity = amount_b / (sqrt_price_upper - sqrt_price_lower)
+pub fn get_liquidity_from_amount_b(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    amount_b: u64,
+) -> Result<u128, ErrorCode> {
+    let (sqrt_price_lower, sqrt_price_upper) = increasing_price_order(sqrt_price_0, sqrt_price_1);
+    let sqrt_price_diff = sqrt_price_upper - sqrt_price_lower;
+    if sqrt_price_diff == 0 {
+        return Err(ErrorCode::DivisionByZeroError);
+    }
+
+    let numerator = U256Muldiv::new(0, amount_b as u128).shift_word_left();
+    let denominator = U256Muldiv::new(0, sqrt_price_diff);
+
+    numerator.div(denominator, false).0.try_into_u128()
+}
+
 #[cfg(test)]
 mod liquidity_math_tests {
     use super::add_liquidity_delta;
     use super::ErrorCode;
+    use super::get_liquidity_from_amounts;
+    use crate::math::{
+        get_amount_delta_a, get_amount_delta_b, sqrt_price_from_tick_index, MAX_SQRT_PRICE_X64,
+    };
 
     #[test]
     fn test_valid_add_liquidity_delta() {
@@ -53,4 +120,75 @@ mod liquidity_math_tests {
         let result = add_liquidity_delta(u128::MIN, -1);
         assert_eq!(result.unwrap_err(), ErrorCode::LiquidityUnderflowError);
     }
+
+    #[test]
+    fn test_get_liquidity_from_amounts_round_trip() {
+        let sqrt_price_lower = sqrt_price_from_tick_index(-1000);
+        let sqrt_price_upper = sqrt_price_from_tick_index(1000);
+        let sqrt_price_current = sqrt_price_from_tick_index(200);
+
+        let liquidity = get_liquidity_from_amounts(
+            sqrt_price_current,
+            sqrt_price_lower,
+            sqrt_price_upper,
+            1_000_000,
+            1_000_000,
+        )
+        .unwrap();
+        assert!(liquidity > 0);
+
+        let amount_a = get_amount_delta_a(sqrt_price_current, sqrt_price_upper, liquidity, true).unwrap();
+        let amount_b = get_amount_delta_b(sqrt_price_lower, sqrt_price_current, liquidity, true).unwrap();
+        assert!(amount_a <= 1_000_000);
+        assert!(amount_b <= 1_000_000);
+        // One side is the limiting one and is almost fully used
+        assert!(amount_a >= 999_990 || amount_b >= 999_990);
+    }
+
+    #[test]
+    fn test_get_liquidity_from_amounts_out_of_range() {
+        let sqrt_price_lower = sqrt_price_from_tick_index(-1000);
+        let sqrt_price_upper = sqrt_price_from_tick_index(1000);
+
+        // Below the range only token A is used
+        let below = get_liquidity_from_amounts(
+            sqrt_price_from_tick_index(-2000),
+            sqrt_price_lower,
+            sqrt_price_upper,
+            1_000_000,
+            0,
+        )
+        .unwrap();
+        assert!(below > 0);
+
+        // Above the range only token B is used
+        let above = get_liquidity_from_amounts(
+            sqrt_price_from_tick_index(2000),
+            sqrt_price_lower,
+            sqrt_price_upper,
+            1_000_000,
+            0,
+        )
+        .unwrap();
+        assert_eq!(above, 0);
+    }
+
+    #[test]
+    fn test_get_liquidity_from_amounts_near_max_sqrt_price() {
+        // The product of the sqrt prices exceeds 128 bits
+        let sqrt_price_lower = sqrt_price_from_tick_index(450_000);
+
+        let liquidity = get_liquidity_from_amounts(
+            sqrt_price_lower,
+            sqrt_price_lower,
+            MAX_SQRT_PRICE_X64,
+            1_000_000,
+            0,
+        )
+        .unwrap();
+        assert!(liquidity > 0);
+
+        let amount_a = get_amount_delta_a(sqrt_price_lower, MAX_SQRT_PRICE_X64, liquidity, true).unwrap();
+        assert!(amount_a <= 1_000_000);
+    }
 }
@@ -1,6 +1,6 @@
 use std::convert::TryInto;
 
-use crate::errors::ErrorCode;
+use crate::math::error::ErrorCode;
 use crate::math::*;
 
 #[derive(PartialEq, Debug)]
@@ -147,6 +147,31 @@ fn get_amount_unfixed_delta(
     }
 }
 
+/// Denominator of the price impacts expressed in basis points.
+pub const PRICE_IMPACT_BPS_DENOMINATOR: u128 = 10_000;
+
+/// Computes the price impact of a move from `sqrt_price_before` to `sqrt_price_after`, in basis
+/// points of the price before the move, rounded down.
+///
+/// The impact is measured on the price itself, i.e. the square of the square root prices, in
+/// either direction. It saturates at `u128::MAX`.
+pub fn get_price_impact_bps(sqrt_price_before: u128, sqrt_price_after: u128) -> u128 {
+    let price_before = U256::from(sqrt_price_before) * U256::from(sqrt_price_before);
+    let price_after = U256::from(sqrt_price_after) * U256::from(sqrt_price_after);
+    if price_before.is_zero() {
+        return 0;
+    }
+
+    let price_delta = if price_after > price_before {
+        price_after - price_before
+    } else {
+        price_before - price_after
+    };
+    (price_delta * U256::from(PRICE_IMPACT_BPS_DENOMINATOR) / price_before)
+        .try_into_u128()
+        .unwrap_or(u128::MAX)
+}
+
 #[cfg(test)]
 mod fuzz_tests {
     use super::*;
@@ -156,7 +181,8 @@ mod fuzz_tests {
         #[test]
         fn test_compute_swap(
             amount in 1..u64::MAX,
-            liquidity in 1..u32::MAX as u128,
+            // Keeps liquidity * sqrt_price below 2^128, so that any amount out fits in a u64
+            liquidity in 1..u128::MAX / MAX_SQRT_PRICE_X64,
             fee_rate in 1..u16::MAX,
             price_0 in MIN_SQRT_PRICE_X64..MAX_SQRT_PRICE_X64,
             price_1 in MIN_SQRT_PRICE_X64..MAX_SQRT_PRICE_X64,
@@ -206,7 +232,8 @@ mod fuzz_tests {
         #[test]
         fn test_compute_swap_inversion(
             amount in 1..u64::MAX,
-            liquidity in 1..u32::MAX as u128,
+            // Keeps liquidity * sqrt_price below 2^128, so that any amount out fits in a u64
+            liquidity in 1..u128::MAX / MAX_SQRT_PRICE_X64,
             fee_rate in 1..u16::MAX,
             price_0 in MIN_SQRT_PRICE_X64..MAX_SQRT_PRICE_X64,
             price_1 in MIN_SQRT_PRICE_X64..MAX_SQRT_PRICE_X64,
@@ -936,3 +963,28 @@ mod unit_tests {
         assert_eq!(swap_computation.ok().unwrap(), expected);
     }
 }
+
+#[cfg(test)]
+mod price_impact_tests {
+    use super::*;
+
+    #[test]
+    fn test_no_move_has_no_impact() {
+        assert_eq!(get_price_impact_bps(1 << 64, 1 << 64), 0);
+    }
+
+    #[test]
+    fn test_impact_is_measured_on_the_price() {
+        // sqrt price x 1.1 => price x 1.21
+        let sqrt_price_before: u128 = 10 << 64;
+        let sqrt_price_after: u128 = 11 << 64;
+        assert_eq!(get_price_impact_bps(sqrt_price_before, sqrt_price_after), 2_100);
+        // price x 100/121 => -17.35%
+        assert_eq!(get_price_impact_bps(sqrt_price_after, sqrt_price_before), 1_735);
+    }
+
+    #[test]
+    fn test_impact_saturates() {
+        assert_eq!(get_price_impact_bps(MIN_SQRT_PRICE_X64, MAX_SQRT_PRICE_X64), u128::MAX);
+    }
+}
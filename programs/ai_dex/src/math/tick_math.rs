@@ -1,9 +1,14 @@
-use crate::math::u256_math::*;
+use crate::math::error::ErrorCode;
+use crate::math::U256;
 use std::convert::TryInto;
 
 // Max/Min sqrt_price derived from max/min tick-index
-pub const MAX_SQRT_PRICE_X64: u128 = 79226673515401279992447579055;
-pub const MIN_SQRT_PRICE_X64: u128 = 4295048016;
+pub const MAX_SQRT_PRICE_X64: u128 = 3585389580189110950060011814015;
+pub const MIN_SQRT_PRICE_X64: u128 = 94908059;
+
+// Max/Min sqrt_price of the pools initialized before the tick range was widened
+pub const LEGACY_MAX_SQRT_PRICE_X64: u128 = 79226673515401279992447579055;
+pub const LEGACY_MIN_SQRT_PRICE_X64: u128 = 4295048016;
 
 const LOG_B_2_X32: i128 = 59543866431248i128;
 const BIT_PRECISION: u32 = 14;
@@ -97,17 +102,18 @@ pub fn tick_index_from_sqrt_price(sqrt_price_x64: &u128) -> i32 {
     result_tick
 }
 
-fn mul_shift_96(n0: u128, n1: u128) -> u128 {
-    mul_u256(n0, n1).shift_right(96).try_into_u128().unwrap()
+// The Q32.96 ratio exceeds 128 bits above tick 443636, so it is kept in 256 bits
+fn mul_shift_96(n0: U256, n1: u128) -> U256 {
+    (n0 * U256::from(n1)) >> 96
 }
 
 // Performs the exponential conversion with Q64.64 precision
 fn get_sqrt_price_positive_tick(tick: i32) -> u128 {
-    let mut ratio: u128 = if tick & 1 != 0 {
-        79232123823359799118286999567
+    let mut ratio = U256::from(if tick & 1 != 0 {
+        79232123823359799118286999567u128
     } else {
-        79228162514264337593543950336
-    };
+        79228162514264337593543950336u128
+    });
 
     if tick & 2 != 0 {
         ratio = mul_shift_96(ratio, 79236085330515764027303304731);
@@ -164,7 +170,15 @@ fn get_sqrt_price_positive_tick(tick: i32) -> u128 {
         ratio = mul_shift_96(ratio, 38992368544603139932233054999993551);
     }
 
-    ratio >> 32
+    downcast_sqrt_price(ratio >> 32).unwrap()
+}
+
+// Downcasts a Q64.64 sqrt-price, which only fits in the supported range up to the max tick-index
+fn downcast_sqrt_price(sqrt_price_x64: U256) -> Result<u128, ErrorCode> {
+    match sqrt_price_x64.try_into_u128() {
+        Ok(sqrt_price_x64) if sqrt_price_x64 <= MAX_SQRT_PRICE_X64 => Ok(sqrt_price_x64),
+        _ => Err(ErrorCode::NumberDowncastError),
+    }
 }
 
 fn get_sqrt_price_negative_tick(tick: i32) -> u128 {
@@ -234,13 +248,13 @@ fn get_sqrt_price_negative_tick(tick: i32) -> u128 {
     ratio
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "program"))]
 mod fuzz_tests {
 
     use super::*;
     use crate::{
         math::U256,
-        state::{MAX_TICK_INDEX, MIN_TICK_INDEX},
+        state::{LEGACY_MIN_TICK_INDEX, MAX_TICK_INDEX, MIN_TICK_INDEX},
     };
     use proptest::prelude::*;
 
@@ -270,8 +284,10 @@ mod fuzz_tests {
 
     proptest! {
         #[test]
+        // Below the legacy min tick-index, the sqrt-prices are too small for the ratio of adjacent
+        // ticks to keep 32 bits of precision, their ordering is verified in the extended range below
         fn test_tick_index_to_sqrt_price (
-            tick in MIN_TICK_INDEX..MAX_TICK_INDEX,
+            tick in LEGACY_MIN_TICK_INDEX..MAX_TICK_INDEX,
         ) {
             let sqrt_price = sqrt_price_from_tick_index(tick);
 
@@ -325,6 +341,19 @@ mod fuzz_tests {
             assert!(last_sqrt_price_x64 < sqrt_price_x64);
         }
 
+        #[test]
+        // Verify the round trip on the ticks beyond the former +/-443636 bounds
+        fn test_tick_index_and_sqrt_price_symmetry_in_extended_range (
+            tick in 443637..MAX_TICK_INDEX,
+            negative in any::<bool>(),
+        ) {
+            let tick = if negative { -tick } else { tick };
+            let sqrt_price_x64 = sqrt_price_from_tick_index(tick);
+            assert_eq!(tick_index_from_sqrt_price(&sqrt_price_x64), tick);
+            assert_eq!(tick_index_from_sqrt_price(&(sqrt_price_x64 - 1)), tick - 1);
+            assert!(sqrt_price_from_tick_index(tick + 1) - 1 > sqrt_price_x64);
+        }
+
         #[test]
         fn test_tick_index_from_sqrt_price_is_sequence (
             sqrt_price in (MIN_SQRT_PRICE_X64 + 10)..MAX_SQRT_PRICE_X64
@@ -337,7 +366,7 @@ mod fuzz_tests {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "program"))]
 mod test_tick_index_from_sqrt_price {
     use super::*;
     use crate::state::{MAX_TICK_INDEX, MIN_TICK_INDEX};
@@ -412,7 +441,7 @@ mod test_tick_index_from_sqrt_price {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "program"))]
 mod sqrt_price_from_tick_index_tests {
     use super::*;
     use crate::state::{MAX_TICK_INDEX, MIN_TICK_INDEX};
@@ -453,6 +482,15 @@ mod sqrt_price_from_tick_index_tests {
         assert_eq!(r, MIN_SQRT_PRICE_X64);
     }
 
+    #[test]
+    fn test_former_bounds() {
+        // The prices of the ticks within the former +/-443636 bounds are unchanged
+        assert_eq!(sqrt_price_from_tick_index(443636), 79226673515401279992447579055);
+        assert_eq!(sqrt_price_from_tick_index(-443636), 4295048016);
+        assert_eq!(tick_index_from_sqrt_price(&79226673515401279992447579055), 443636);
+        assert_eq!(tick_index_from_sqrt_price(&4295048016), -443636);
+    }
+
     #[test]
     fn test_exact_bit_values() {
         let conditions = &[
@@ -1,4 +1,4 @@
-use crate::errors::ErrorCode;
+use crate::math::error::ErrorCode;
 use crate::math::Q64_RESOLUTION;
 
 use super::{
@@ -24,6 +24,12 @@ pub const MAX_PROTOCOL_FEE_RATE: u16 = 2_500;
 // We want PROTOCOL_FEE_RATE_MUL_VALUE = 1/PROTOCOL_FEE_UNIT, so 1e4
 pub const PROTOCOL_FEE_RATE_MUL_VALUE: u128 = 10_000;
 
+// Referral fee rate is represented as a basis point of the fee amount.
+// Referral fee amount = fee_amount * referral_fee_rate / 10_000.
+// Max referral fee rate supported is 50% of the fee rate.
+pub const MAX_REFERRAL_FEE_RATE: u16 = 5_000;
+pub const REFERRAL_FEE_RATE_MUL_VALUE: u128 = 10_000;
+
 //
 // Get change in token_a corresponding to a change in price
 //
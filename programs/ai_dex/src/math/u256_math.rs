@@ -4,7 +4,7 @@ use std::{
     str::from_utf8_unchecked,
 };
 
-use crate::errors::ErrorCode;
+use crate::math::error::ErrorCode;
 
 const NUM_WORDS: usize = 4;
 
@@ -1,5 +1,5 @@
 use crate::errors::ErrorCode;
-use crate::math::{add_liquidity_delta, checked_mul_div};
+use crate::math::{add_liquidity_delta, checked_mul_div, Q64_RESOLUTION};
 use crate::state::*;
 
 // Calculates the next global reward growth variables based on the given timestamp.
@@ -22,14 +22,17 @@ pub fn next_ai_dex_reward_infos(
 
     // Calculate new global reward growth
     let mut next_reward_infos = ai_dex.reward_infos;
-    let time_delta = u128::from(next_timestamp - curr_timestamp);
 
     // Iterate through each reward info and calculate the new reward growth
-    for reward_info in &mut next_reward_infos {
+    let reward_emissions = ai_dex.reward_emissions;
+    for (reward_info, reward_emissions) in next_reward_infos.iter_mut().zip(reward_emissions.iter()) {
         if !reward_info.initialized() {
             continue;
         }
 
+        // Rewards stop accruing past the end of their emissions
+        let time_delta = u128::from(reward_emissions.emission_seconds(curr_timestamp, next_timestamp));
+
         // Calculate the new reward growth delta.
         // If the calculation overflows, set the delta value to zero.
         // This will halt reward distributions for this reward.
@@ -47,6 +50,28 @@ pub fn next_ai_dex_reward_infos(
     Ok(next_reward_infos)
 }
 
+// Calculates the next global seconds per liquidity based on the given timestamp.
+// The provided timestamp must be greater than or equal to the last updated timestamp.
+pub fn next_seconds_per_liquidity_global(ai_dex: &AiDexPool, next_timestamp: u64) -> Result<u128, ErrorCode> {
+    let curr_timestamp = ai_dex.reward_last_updated_timestamp;
+
+    // Check if the next timestamp is earlier than the current timestamp
+    if next_timestamp < curr_timestamp {
+        return Err(ErrorCode::InvalidTimestampError.into());
+    }
+
+    // No-op if there is no liquidity or no change in timestamp
+    if ai_dex.liquidity == 0 || next_timestamp == curr_timestamp {
+        return Ok(ai_dex.seconds_per_liquidity_global_x64);
+    }
+
+    // Time delta is at most a u64, so shifting it as a Q64.64 cannot overflow
+    let time_delta = u128::from(next_timestamp - curr_timestamp);
+    Ok(ai_dex
+        .seconds_per_liquidity_global_x64
+        .wrapping_add((time_delta << Q64_RESOLUTION) / ai_dex.liquidity))
+}
+
 // Calculates the next global liquidity for an AiDex depending on its position relative
 // to the lower and upper tick indexes and the liquidity_delta.
 pub fn next_ai_dex_liquidity(
@@ -71,9 +96,9 @@ mod ai_dex_orchestrator_tests {
 
     use anchor_lang::prelude::Pubkey;
 
-    use crate::orchestrator::ai_dex_orchestrator::next_ai_dex_reward_infos;
+    use crate::orchestrator::ai_dex_orchestrator::{next_ai_dex_reward_infos, next_seconds_per_liquidity_global};
     use crate::math::Q64_RESOLUTION;
-    use crate::state::ai_dex::AiDexRewardInfo;
+    use crate::state::ai_dex::{AiDexRewardEmissions, AiDexRewardInfo};
     use crate::state::ai_dex::NUM_REWARDS;
     use crate::state::ai_dex_builder::AiDexBuilder;
     use crate::state::AiDexPool;
@@ -177,12 +202,39 @@ mod ai_dex_orchestrator_tests {
 
         let new_timestamp = 1577854800 + 300;
         let result = next_ai_dex_reward_infos(ai_dex, new_timestamp).unwrap();
-        assert_eq!(result[0].growth_global_x64, 3 << Q64_RESOLUTION);
+        assert_eq!({ result[0].growth_global_x64 }, 3 << Q64_RESOLUTION);
         for i in 1..NUM_REWARDS {
-            assert_eq!(ai_dex.reward_infos[i].growth_global_x64, 0);
+            assert_eq!({ ai_dex.reward_infos[i].growth_global_x64 }, 0);
         }
     }
 
+    #[test]
+    fn test_next_ai_dex_reward_infos_stop_at_emissions_end() {
+        let ai_dex = &AiDexBuilder::new()
+            .liquidity(100)
+            .reward_last_updated_timestamp(1577854800) // Jan 1 2021 EST
+            .reward_info(
+                0,
+                AiDexRewardInfo {
+                    mint: Pubkey::new_unique(),
+                    emissions_per_second_x64: 1 << Q64_RESOLUTION,
+                    ..Default::default()
+                },
+            )
+            .reward_emissions(
+                0,
+                AiDexRewardEmissions {
+                    emissions_end_timestamp: 1577854800 + 200,
+                    ..Default::default()
+                },
+            )
+            .build();
+
+        let new_timestamp = 1577854800 + 300;
+        let result = next_ai_dex_reward_infos(ai_dex, new_timestamp).unwrap();
+        assert_eq!({ result[0].growth_global_x64 }, 2 << Q64_RESOLUTION);
+    }
+
     #[test]
     fn test_next_ai_dex_reward_infos_delta_zero_on_overflow() {
         let ai_dex = &AiDexBuilder::new()
@@ -201,7 +253,29 @@ mod ai_dex_orchestrator_tests {
 
         let new_timestamp = i64::MAX as u64;
         let result = next_ai_dex_reward_infos(ai_dex, new_timestamp).unwrap();
-        assert_eq!(result[0].growth_global_x64, 100);
+        assert_eq!({ result[0].growth_global_x64 }, 100);
+    }
+
+    #[test]
+    fn test_next_ai_dex_reward_infos_product_overflow_ok() {
+        // time_delta * emissions_per_second_x64 exceeds u128, but the reward growth delta fits
+        let ai_dex = &AiDexBuilder::new()
+            .liquidity(1 << 100)
+            .reward_last_updated_timestamp(1577854800)
+            .reward_info(
+                0,
+                AiDexRewardInfo {
+                    mint: Pubkey::new_unique(),
+                    emissions_per_second_x64: 1 << 120,
+                    growth_global_x64: 100,
+                    ..Default::default()
+                },
+            )
+            .build();
+
+        let new_timestamp = 1577854800 + 300;
+        let result = next_ai_dex_reward_infos(ai_dex, new_timestamp).unwrap();
+        assert_eq!({ result[0].growth_global_x64 }, 100 + (300 << 20));
     }
 
     #[test]
@@ -210,14 +284,27 @@ mod ai_dex_orchestrator_tests {
 
         let new_timestamp = 1577854800 + 300;
         let result = next_ai_dex_reward_infos(&ai_dex, new_timestamp).unwrap();
-        assert_eq!(result[0].growth_global_x64, 130 << Q64_RESOLUTION);
+        assert_eq!({ result[0].growth_global_x64 }, 130 << Q64_RESOLUTION);
         assert_eq!(
-            result[1].growth_global_x64,
+            { result[1].growth_global_x64 },
             0b110011001 << (Q64_RESOLUTION - 1) // 204.5
         );
         assert_eq!(
-            result[2].growth_global_x64,
+            { result[2].growth_global_x64 },
             0b1001011011 << (Q64_RESOLUTION - 1) // 301.5
         );
     }
+
+    #[test]
+    fn test_next_seconds_per_liquidity_global() {
+        let ai_dex = init_test_ai_dex(100, 1577854800);
+
+        let result = next_seconds_per_liquidity_global(&ai_dex, 1577854800 + 300).unwrap();
+        assert_eq!(result, 3 << Q64_RESOLUTION);
+        assert_eq!(next_seconds_per_liquidity_global(&ai_dex, 1577854800).unwrap(), 0);
+        assert!(next_seconds_per_liquidity_global(&ai_dex, 1577854799).is_err());
+
+        let ai_dex = init_test_ai_dex(0, 1577854800);
+        assert_eq!(next_seconds_per_liquidity_global(&ai_dex, 1577854800 + 300).unwrap(), 0);
+    }
 }
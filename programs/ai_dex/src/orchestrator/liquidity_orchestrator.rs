@@ -3,14 +3,15 @@ use super::{
     tick_orchestrator::{
         next_fee_growths_inside, next_reward_growths_inside, next_tick_modify_liquidity_update,
     },
-    ai_dex_orchestrator::{next_ai_dex_liquidity, next_ai_dex_reward_infos},
+    ai_dex_orchestrator::{next_ai_dex_liquidity, next_ai_dex_reward_infos, next_seconds_per_liquidity_global},
 };
 use crate::{
     errors::ErrorCode,
     math::{get_amount_delta_a, get_amount_delta_b, sqrt_price_from_tick_index},
     state::*,
+    util::TickArrayAccount,
 };
-use anchor_lang::prelude::{AccountLoader, *};
+use anchor_lang::prelude::*;
 
 #[derive(Debug)]
 pub struct ModifyLiquidityUpdate {
@@ -27,20 +28,16 @@ pub struct ModifyLiquidityUpdate {
 pub fn calculate_modify_liquidity<'info>(
     ai_dex: &AiDexPool,
     position: &Position,
-    tick_array_lower: &AccountLoader<'info, TickArray>,
-    tick_array_upper: &AccountLoader<'info, TickArray>,
+    tick_array_lower: &TickArrayAccount<'info>,
+    tick_array_upper: &TickArrayAccount<'info>,
     liquidity_delta: i128,
     timestamp: u64,
 ) -> Result<ModifyLiquidityUpdate> {
-    // Load the tick array for the lower tick index
-    let tick_array_lower = tick_array_lower.load()?;
     // Get the tick at the lower tick index
     // The lower tick value obtained from the `tick_array_lower` at the specified index.
     let tick_lower =
         tick_array_lower.get_tick(position.tick_lower_index, ai_dex.tick_spacing)?;
 
-    // Load the tick array for the upper tick index
-    let tick_array_upper = tick_array_upper.load()?;
     // Get the tick at the upper tick index
     let tick_upper =
         tick_array_upper.get_tick(position.tick_upper_index, ai_dex.tick_spacing)?;
@@ -49,8 +46,8 @@ pub fn calculate_modify_liquidity<'info>(
     Ok(_calculate_modify_liquidity(
         ai_dex,
         position,
-        tick_lower,
-        tick_upper,
+        &tick_lower,
+        &tick_upper,
         position.tick_lower_index,
         position.tick_upper_index,
         liquidity_delta,
@@ -78,15 +75,13 @@ pub fn calculate_modify_liquidity<'info>(
 pub fn calculate_fee_and_reward_growths<'info>(
     ai_dex: &AiDexPool,
     position: &Position,
-    tick_array_lower: &AccountLoader<'info, TickArray>,
-    tick_array_upper: &AccountLoader<'info, TickArray>,
+    tick_array_lower: &TickArrayAccount<'info>,
+    tick_array_upper: &TickArrayAccount<'info>,
     timestamp: u64,
 ) -> Result<(PositionUpdate, [AiDexRewardInfo; NUM_REWARDS])> {
-    let tick_array_lower = tick_array_lower.load()?;
     let tick_lower =
         tick_array_lower.get_tick(position.tick_lower_index, ai_dex.tick_spacing)?;
 
-    let tick_array_upper = tick_array_upper.load()?;
     let tick_upper =
         tick_array_upper.get_tick(position.tick_upper_index, ai_dex.tick_spacing)?;
 
@@ -95,8 +90,8 @@ pub fn calculate_fee_and_reward_growths<'info>(
     let update = _calculate_modify_liquidity(
         ai_dex,
         position,
-        tick_lower,
-        tick_upper,
+        &tick_lower,
+        &tick_upper,
         position.tick_lower_index,
         position.tick_upper_index,
         0,
@@ -121,9 +116,17 @@ fn _calculate_modify_liquidity(
         return Err(ErrorCode::ZeroLiquidityError.into());
     }
 
+    // Disallow adding liquidity to a paused pool, removing it remains allowed
+    if liquidity_delta > 0 && ai_dex.is_paused() {
+        return Err(ErrorCode::PoolPausedError.into());
+    }
+
     // Calculate the next reward infos
     let next_reward_infos = next_ai_dex_reward_infos(ai_dex, timestamp)?;
 
+    // Calculate the next global seconds per liquidity
+    let next_seconds_per_liquidity_global_x64 = next_seconds_per_liquidity_global(ai_dex, timestamp)?;
+
     // Calculate the next global liquidity
     let next_global_liquidity = next_ai_dex_liquidity(
         ai_dex,
@@ -140,6 +143,7 @@ fn _calculate_modify_liquidity(
         ai_dex.fee_growth_global_a,
         ai_dex.fee_growth_global_b,
         &next_reward_infos,
+        next_seconds_per_liquidity_global_x64,
         liquidity_delta,
         false,
     )?;
@@ -152,6 +156,7 @@ fn _calculate_modify_liquidity(
         ai_dex.fee_growth_global_a,
         ai_dex.fee_growth_global_b,
         &next_reward_infos,
+        next_seconds_per_liquidity_global_x64,
         liquidity_delta,
         true,
     )?;
@@ -184,6 +189,7 @@ fn _calculate_modify_liquidity(
         fee_growth_inside_a,
         fee_growth_inside_b,
         &reward_growths_inside,
+        position.active_reward_boost_bps(timestamp),
     )?;
 
     Ok(ModifyLiquidityUpdate {
@@ -264,6 +270,8 @@ pub fn calculate_liquidity_token_deltas(
 /// * `tick_array_upper` - The upper tick array.
 /// * `modify_liquidity_update` - The ModifyLiquidityUpdate struct containing the updated values.
 /// * `reward_last_updated_timestamp` - The timestamp when the rewards were last updated.
+/// * `funder` - The account paying for the growth of dynamic tick arrays.
+/// * `system_program` - The system program, used to fund the growth of dynamic tick arrays.
 ///
 /// # Returns
 ///
@@ -271,28 +279,32 @@ pub fn calculate_liquidity_token_deltas(
 pub fn sync_modify_liquidity_values<'info>(
     ai_dex: &mut AiDexPool,
     position: &mut Position,
-    tick_array_lower: &AccountLoader<'info, TickArray>,
-    tick_array_upper: &AccountLoader<'info, TickArray>,
+    tick_array_lower: &TickArrayAccount<'info>,
+    tick_array_upper: &TickArrayAccount<'info>,
     modify_liquidity_update: ModifyLiquidityUpdate,
     reward_last_updated_timestamp: u64,
+    funder: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
 ) -> Result<()> {
     // Update the position with the new values
     position.update(&modify_liquidity_update.position_update);
 
     // Update the lower tick in the tick array
-    let mut tick_array_lower_mut = tick_array_lower.load_mut()?;
-    tick_array_lower_mut.update_tick(
+    tick_array_lower.update_tick(
         position.tick_lower_index,
         ai_dex.tick_spacing,
         &modify_liquidity_update.tick_lower_update,
+        funder,
+        system_program,
     )?;
 
     // Update the upper tick in the tick array
-    let mut tick_array_upper_mut = tick_array_upper.load_mut()?;
-    tick_array_upper_mut.update_tick(
+    tick_array_upper.update_tick(
         position.tick_upper_index,
         ai_dex.tick_spacing,
         &modify_liquidity_update.tick_upper_update,
+        funder,
+        system_program,
     )?;
 
     // Update the rewards and liquidity in the AiDex instance
@@ -578,16 +590,16 @@ mod calculate_modify_liquidity_unit_tests {
                 test.increment_ai_dex_reward_growths_by_time(100);
                 test.cross_tick(TickLabel::Upper, Direction::Left);
                 // Check crossing an upper tick with liquidity added new ai_dex liquidity
-                assert_eq!(test.ai_dex.liquidity, 110);
+                assert_eq!({ test.ai_dex.liquidity }, 110);
                 // 1 = 0 + (100/100)
-                assert_ai_dex_reward_growths(&test.ai_dex.reward_infos, to_x64(1));
+                assert_ai_dex_reward_growths(&{ test.ai_dex.reward_infos }, to_x64(1));
                 test.increment_ai_dex_fee_growths(to_x64(10), to_x64(10));
                 test.increment_ai_dex_reward_growths_by_time(100);
                 test.cross_tick(TickLabel::Lower, Direction::Left);
                 // Lower tick has 0 net liquidity, so crossing does not affect ai_dex liquidity
-                assert_eq!(test.ai_dex.liquidity, 110);
+                assert_eq!({ test.ai_dex.liquidity }, 110);
                 // 1.909 = 1 + (100/110)
-                assert_ai_dex_reward_growths(&test.ai_dex.reward_infos, 35216511413445507630);
+                assert_ai_dex_reward_growths(&{ test.ai_dex.reward_infos }, 35216511413445507630);
 
                 // Create position which initializes the lower tick
                 let update = _calculate_modify_liquidity(
@@ -634,6 +646,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: to_x64(10),
                             fee_growth_outside_b: to_x64(10),
                             reward_growths_outside: create_reward_growths(to_x64(1)),
+                            seconds_per_liquidity_outside_x64: to_x64(1),
                         },
                     },
                 );
@@ -796,6 +809,7 @@ mod calculate_modify_liquidity_unit_tests {
                             liquidity_gross: 10,
                             liquidity_net: 10,
                             reward_growths_outside: create_reward_growths(to_x64(1)),
+                            seconds_per_liquidity_outside_x64: to_x64(1),
                             ..Default::default()
                         },
                         tick_upper_update: TickUpdate {
@@ -853,6 +867,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: to_x64(10),
                             fee_growth_outside_b: to_x64(20),
                             reward_growths_outside: create_reward_growths(to_x64(1)),
+                            seconds_per_liquidity_outside_x64: to_x64(1),
                         },
                         tick_upper_update: TickUpdate {
                             initialized: true,
@@ -892,9 +907,9 @@ mod calculate_modify_liquidity_unit_tests {
                 test.increment_ai_dex_reward_growths_by_time(100);
                 test.cross_tick(TickLabel::Upper, Direction::Left);
                 // Check crossing an upper tick with liquidity added new ai_dex liquidity
-                assert_eq!(test.ai_dex.liquidity, 110);
+                assert_eq!({ test.ai_dex.liquidity }, 110);
                 // 1 = 0 + (100/100)
-                assert_ai_dex_reward_growths(&test.ai_dex.reward_infos, to_x64(1));
+                assert_ai_dex_reward_growths(&{ test.ai_dex.reward_infos }, to_x64(1));
 
                 // Create position which initializes the lower tick
                 let update = _calculate_modify_liquidity(
@@ -936,6 +951,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_b: to_x64(10),
                             // 1.909
                             reward_growths_outside: create_reward_growths(35216511413445507630),
+                            seconds_per_liquidity_outside_x64: 35216511413445507630,
                         },
                         tick_upper_update: TickUpdate {
                             initialized: true,
@@ -944,6 +960,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: to_x64(10),
                             fee_growth_outside_b: to_x64(10),
                             reward_growths_outside: create_reward_growths(to_x64(1)),
+                            seconds_per_liquidity_outside_x64: to_x64(1),
                         },
                     },
                 );
@@ -1113,6 +1130,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: to_x64(10),
                             fee_growth_outside_b: to_x64(20),
                             reward_growths_outside: create_reward_growths(to_x64(3)),
+                            seconds_per_liquidity_outside_x64: to_x64(1),
                         },
                         tick_upper_update: TickUpdate {
                             initialized: true,
@@ -1121,6 +1139,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: to_x64(10),
                             fee_growth_outside_b: to_x64(20),
                             reward_growths_outside: create_reward_growths(to_x64(3)),
+                            seconds_per_liquidity_outside_x64: to_x64(1),
                         },
                     },
                 );
@@ -1177,6 +1196,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: to_x64(10),
                             fee_growth_outside_b: to_x64(20),
                             reward_growths_outside: create_reward_growths(to_x64(3)),
+                            seconds_per_liquidity_outside_x64: to_x64(1),
                         },
                         tick_upper_update: TickUpdate {
                             initialized: true,
@@ -1245,6 +1265,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: to_x64(10),
                             fee_growth_outside_b: to_x64(10),
                             reward_growths_outside: create_reward_growths(to_x64(1)),
+                            seconds_per_liquidity_outside_x64: to_x64(1),
                         },
                         tick_upper_update: TickUpdate {
                             initialized: true,
@@ -1265,7 +1286,7 @@ mod calculate_modify_liquidity_unit_tests {
                 test.increment_ai_dex_reward_growths_by_time(100);
                 test.cross_tick(TickLabel::Upper, Direction::Left);
 
-                assert_ai_dex_reward_growths(&test.ai_dex.reward_infos, to_x64(2));
+                assert_ai_dex_reward_growths(&{ test.ai_dex.reward_infos }, to_x64(2));
                 assert_eq!(
                     test.tick_upper,
                     Tick {
@@ -1278,6 +1299,7 @@ mod calculate_modify_liquidity_unit_tests {
                         fee_growth_outside_b: to_x64(20),
                         // 2 = (1 + (100/100)) - 0
                         reward_growths_outside: create_reward_growths(to_x64(2)),
+                        seconds_per_liquidity_outside_x64: to_x64(2),
                         ..Default::default()
                     }
                 );
@@ -1289,7 +1311,7 @@ mod calculate_modify_liquidity_unit_tests {
                 test.increment_ai_dex_fee_growths(to_x64(15), to_x64(15));
                 test.increment_ai_dex_reward_growths_by_time(100);
                 // 2.83 = 2 + 100/120
-                assert_ai_dex_reward_growths(&test.ai_dex.reward_infos, 52265774875510396245);
+                assert_ai_dex_reward_growths(&{ test.ai_dex.reward_infos }, 52265774875510396245);
 
                 test.cross_tick(TickLabel::Upper, Direction::Right);
                 assert_eq!(
@@ -1304,6 +1326,7 @@ mod calculate_modify_liquidity_unit_tests {
                         fee_growth_outside_b: to_x64(15),
                         // 0.83 = 2.83 - 2
                         reward_growths_outside: create_reward_growths(15372286728091293013),
+                        seconds_per_liquidity_outside_x64: 15372286728091293013,
                     }
                 );
 
@@ -1345,6 +1368,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: to_x64(10),
                             fee_growth_outside_b: to_x64(10),
                             reward_growths_outside: create_reward_growths(to_x64(1)),
+                            seconds_per_liquidity_outside_x64: to_x64(1),
                         },
                         tick_upper_update: TickUpdate {
                             initialized: true,
@@ -1356,6 +1380,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_b: to_x64(15),
                             // 0.83
                             reward_growths_outside: create_reward_growths(15372286728091293013),
+                            seconds_per_liquidity_outside_x64: 15372286728091293013,
                         },
                     },
                 );
@@ -1413,6 +1438,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: to_x64(10),
                             fee_growth_outside_b: to_x64(20),
                             reward_growths_outside: create_reward_growths(to_x64(3)),
+                            seconds_per_liquidity_outside_x64: to_x64(1),
                         },
                     },
                 );
@@ -1532,6 +1558,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: to_x64(10),
                             fee_growth_outside_b: to_x64(10),
                             reward_growths_outside: create_reward_growths(to_x64(1)),
+                            seconds_per_liquidity_outside_x64: 0,
                         },
                         tick_upper_update: TickUpdate {
                             initialized: true,
@@ -1540,6 +1567,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: to_x64(20),
                             fee_growth_outside_b: to_x64(20),
                             reward_growths_outside: create_reward_growths(to_x64(2)),
+                            seconds_per_liquidity_outside_x64: 0,
                         },
                     },
                 );
@@ -1702,6 +1730,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: to_x64(100),
                             fee_growth_outside_b: to_x64(100),
                             reward_growths_outside: create_reward_growths(to_x64(30)),
+                            seconds_per_liquidity_outside_x64: 0,
                         },
                         tick_upper_update: TickUpdate {
                             initialized: true,
@@ -1710,6 +1739,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: to_x64(50),
                             fee_growth_outside_b: to_x64(50),
                             reward_growths_outside: create_reward_growths(to_x64(10)),
+                            seconds_per_liquidity_outside_x64: 0,
                         },
                     },
                 );
@@ -2370,6 +2400,61 @@ mod calculate_modify_liquidity_unit_tests {
         }
     }
 
+    mod paused_pool {
+        use crate::{orchestrator::liquidity_orchestrator::_calculate_modify_liquidity, util::*};
+
+        fn paused_test_fixture() -> LiquidityTestFixture {
+            let mut test = LiquidityTestFixture::new(LiquidityTestFixtureInfo {
+                curr_index_loc: CurrIndex::Inside,
+                ai_dex_liquidity: 100,
+                position_liquidity: 100,
+                tick_lower_liquidity_gross: 100,
+                tick_upper_liquidity_gross: 100,
+                fee_growth_global_a: 0,
+                fee_growth_global_b: 0,
+                reward_infos: create_ai_dex_reward_infos(to_x64(1), 0),
+            });
+            test.ai_dex.set_paused(true);
+            test
+        }
+
+        // Adding liquidity to a paused pool is not allowed
+        #[test]
+        #[should_panic(expected = "PoolPausedError")]
+        fn pos_delta_on_paused_pool_not_allowed() {
+            let test = paused_test_fixture();
+            _calculate_modify_liquidity(
+                &test.ai_dex,
+                &test.position,
+                &test.tick_lower,
+                &test.tick_upper,
+                test.position.tick_lower_index,
+                test.position.tick_upper_index,
+                10,
+                100,
+            )
+            .unwrap();
+        }
+
+        // Removing liquidity from a paused pool remains allowed
+        #[test]
+        fn neg_delta_on_paused_pool_allowed() {
+            let test = paused_test_fixture();
+            let update = _calculate_modify_liquidity(
+                &test.ai_dex,
+                &test.position,
+                &test.tick_lower,
+                &test.tick_upper,
+                test.position.tick_lower_index,
+                test.position.tick_upper_index,
+                -100,
+                100,
+            )
+            .unwrap();
+            assert_eq!(update.position_update.liquidity, 0);
+        }
+    }
+
     mod fees_and_rewards {
         use crate::{orchestrator::liquidity_orchestrator::_calculate_modify_liquidity, state::*, util::*};
 
@@ -2491,6 +2576,7 @@ mod calculate_modify_liquidity_unit_tests {
                         fee_growth_outside_a: to_x64(10),
                         fee_growth_outside_b: to_x64(20),
                         reward_growths_outside: create_reward_growths(to_x64(2)),
+                        seconds_per_liquidity_outside_x64: 0,
                     },
                     tick_upper_update: TickUpdate {
                         initialized: true,
@@ -2499,6 +2585,7 @@ mod calculate_modify_liquidity_unit_tests {
                         fee_growth_outside_a: to_x64(1),
                         fee_growth_outside_b: to_x64(2),
                         reward_growths_outside: create_reward_growths(to_x64(1)),
+                        seconds_per_liquidity_outside_x64: 0,
                     },
                 },
             );
@@ -2564,15 +2651,15 @@ mod calculate_modify_liquidity_unit_tests {
                         },
                     },
                 );
-                assert_eq!(test.ai_dex.fee_growth_global_a, u128::MAX - to_x64(100));
-                assert_eq!(test.ai_dex.fee_growth_global_b, u128::MAX - to_x64(100));
+                assert_eq!({ test.ai_dex.fee_growth_global_a }, u128::MAX - to_x64(100));
+                assert_eq!({ test.ai_dex.fee_growth_global_b }, u128::MAX - to_x64(100));
 
                 test.apply_update(&update, 100);
                 test.increment_ai_dex_fee_growths(to_x64(20), to_x64(20)); // fees at -80
                 test.increment_ai_dex_reward_growths_by_time(100);
                 // time: 200, rewards at MAX - 3
                 assert_ai_dex_reward_growths(
-                    &test.ai_dex.reward_infos,
+                    &{ test.ai_dex.reward_infos },
                     u128::MAX - to_x64(3),
                 );
 
@@ -2583,7 +2670,7 @@ mod calculate_modify_liquidity_unit_tests {
 
                 // time: 300, rewards at -2.0909
                 assert_ai_dex_reward_growths(
-                    &test.ai_dex.reward_infos,
+                    &{ test.ai_dex.reward_infos },
                     340282366920938463424804142550375512621,
                 );
 
@@ -2626,6 +2713,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: to_x64(20),
                             fee_growth_outside_b: to_x64(20),
                             reward_growths_outside: create_reward_growths(16769767339735956014), // 0.9090909
+                            seconds_per_liquidity_outside_x64: 167697673397359560,
                         },
                         tick_upper_update: TickUpdate {
                             initialized: true,
@@ -2636,8 +2724,8 @@ mod calculate_modify_liquidity_unit_tests {
                     },
                 );
                 // 10
-                assert_eq!(test.ai_dex.fee_growth_global_a, 184467440737095516159);
-                assert_eq!(test.ai_dex.fee_growth_global_b, 184467440737095516159);
+                assert_eq!({ test.ai_dex.fee_growth_global_a }, 184467440737095516159);
+                assert_eq!({ test.ai_dex.fee_growth_global_b }, 184467440737095516159);
             }
 
             // t1 |--------l-------c1-------u--------| open position (checkpoint)
@@ -2687,6 +2775,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: u128::MAX - to_x64(100),
                             fee_growth_outside_b: u128::MAX - to_x64(100),
                             reward_growths_outside: create_reward_growths(u128::MAX - to_x64(4)),
+                            seconds_per_liquidity_outside_x64: 184467440737095516,
                         },
                         tick_upper_update: TickUpdate {
                             initialized: true,
@@ -2696,8 +2785,8 @@ mod calculate_modify_liquidity_unit_tests {
                         },
                     },
                 );
-                assert_eq!(test.ai_dex.fee_growth_global_a, u128::MAX - to_x64(100));
-                assert_eq!(test.ai_dex.fee_growth_global_b, u128::MAX - to_x64(100));
+                assert_eq!({ test.ai_dex.fee_growth_global_a }, u128::MAX - to_x64(100));
+                assert_eq!({ test.ai_dex.fee_growth_global_b }, u128::MAX - to_x64(100));
 
                 test.apply_update(&update, 100);
 
@@ -2706,7 +2795,7 @@ mod calculate_modify_liquidity_unit_tests {
                 test.increment_ai_dex_reward_growths_by_time(100);
                 // time: 200, rewards at MAX - 3.0909
                 assert_ai_dex_reward_growths(
-                    &test.ai_dex.reward_infos,
+                    &{ test.ai_dex.reward_infos },
                     340282366920938463406357398476665961005,
                 );
                 test.cross_tick(TickLabel::Lower, Direction::Left);
@@ -2750,6 +2839,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: to_x64(20),
                             fee_growth_outside_b: to_x64(20),
                             reward_growths_outside: create_reward_growths(16769767339735956014), // 0.9090909
+                            seconds_per_liquidity_outside_x64: 167697673397359560,
                         },
                         tick_upper_update: TickUpdate {
                             initialized: true,
@@ -2760,8 +2850,8 @@ mod calculate_modify_liquidity_unit_tests {
                     },
                 );
                 // 10
-                assert_eq!(test.ai_dex.fee_growth_global_a, 184467440737095516159);
-                assert_eq!(test.ai_dex.fee_growth_global_b, 184467440737095516159);
+                assert_eq!({ test.ai_dex.fee_growth_global_a }, 184467440737095516159);
+                assert_eq!({ test.ai_dex.fee_growth_global_b }, 184467440737095516159);
             }
 
             // t1 |--------l----------------u---c1---| open position (checkpoint), cross left
@@ -2811,6 +2901,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: u128::MAX - to_x64(100),
                             fee_growth_outside_b: u128::MAX - to_x64(100),
                             reward_growths_outside: create_reward_growths(u128::MAX - to_x64(4)),
+                            seconds_per_liquidity_outside_x64: 184467440737095516,
                         },
                         tick_upper_update: TickUpdate {
                             initialized: true,
@@ -2819,11 +2910,12 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: u128::MAX - to_x64(100),
                             fee_growth_outside_b: u128::MAX - to_x64(100),
                             reward_growths_outside: create_reward_growths(u128::MAX - to_x64(4)),
+                            seconds_per_liquidity_outside_x64: 184467440737095516,
                         },
                     },
                 );
-                assert_eq!(test.ai_dex.fee_growth_global_a, u128::MAX - to_x64(100));
-                assert_eq!(test.ai_dex.fee_growth_global_b, u128::MAX - to_x64(100));
+                assert_eq!({ test.ai_dex.fee_growth_global_a }, u128::MAX - to_x64(100));
+                assert_eq!({ test.ai_dex.fee_growth_global_b }, u128::MAX - to_x64(100));
 
                 test.apply_update(&update, 100);
 
@@ -2831,7 +2923,7 @@ mod calculate_modify_liquidity_unit_tests {
                 test.increment_ai_dex_reward_growths_by_time(100);
                 // time: 200, rewards at MAX - 3
                 assert_ai_dex_reward_growths(
-                    &test.ai_dex.reward_infos,
+                    &{ test.ai_dex.reward_infos },
                     u128::MAX - to_x64(3),
                 );
                 test.cross_tick(TickLabel::Upper, Direction::Left);
@@ -2841,7 +2933,7 @@ mod calculate_modify_liquidity_unit_tests {
                 test.increment_ai_dex_reward_growths_by_time(100);
                 // time: 300, rewards at MAX - 2.0909
                 assert_ai_dex_reward_growths(
-                    &test.ai_dex.reward_infos,
+                    &{ test.ai_dex.reward_infos },
                     340282366920938463424804142550375512621,
                 );
                 test.cross_tick(TickLabel::Lower, Direction::Left);
@@ -2885,6 +2977,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: to_x64(40),
                             fee_growth_outside_b: to_x64(40),
                             reward_growths_outside: create_reward_growths(35216511413445507630), // 1.9090909
+                            seconds_per_liquidity_outside_x64: 352165114134455076,
                         },
                         tick_upper_update: TickUpdate {
                             initialized: true,
@@ -2893,12 +2986,13 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: to_x64(20),
                             fee_growth_outside_b: to_x64(20),
                             reward_growths_outside: create_reward_growths(to_x64(1)), // 1
+                            seconds_per_liquidity_outside_x64: 184467440737095516,
                         },
                     },
                 );
                 // 10
-                assert_eq!(test.ai_dex.fee_growth_global_a, 184467440737095516159);
-                assert_eq!(test.ai_dex.fee_growth_global_b, 184467440737095516159);
+                assert_eq!({ test.ai_dex.fee_growth_global_a }, 184467440737095516159);
+                assert_eq!({ test.ai_dex.fee_growth_global_b }, 184467440737095516159);
             }
 
             // t1 |---c1---l----------------u--------| open position (checkpoint), cross right
@@ -2954,8 +3048,8 @@ mod calculate_modify_liquidity_unit_tests {
                         },
                     },
                 );
-                assert_eq!(test.ai_dex.fee_growth_global_a, u128::MAX - to_x64(100));
-                assert_eq!(test.ai_dex.fee_growth_global_b, u128::MAX - to_x64(100));
+                assert_eq!({ test.ai_dex.fee_growth_global_a }, u128::MAX - to_x64(100));
+                assert_eq!({ test.ai_dex.fee_growth_global_b }, u128::MAX - to_x64(100));
 
                 test.apply_update(&update, 100);
 
@@ -2963,7 +3057,7 @@ mod calculate_modify_liquidity_unit_tests {
                 test.increment_ai_dex_reward_growths_by_time(100);
                 // time: 200, rewards at MAX - 3
                 assert_ai_dex_reward_growths(
-                    &test.ai_dex.reward_infos,
+                    &{ test.ai_dex.reward_infos },
                     u128::MAX - to_x64(3),
                 );
                 test.cross_tick(TickLabel::Lower, Direction::Right);
@@ -3006,6 +3100,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: u128::MAX - to_x64(80),
                             fee_growth_outside_b: u128::MAX - to_x64(80),
                             reward_growths_outside: create_reward_growths(u128::MAX - to_x64(3)),
+                            seconds_per_liquidity_outside_x64: 368934881474191032,
                         },
                         tick_upper_update: TickUpdate {
                             initialized: true,
@@ -3016,8 +3111,8 @@ mod calculate_modify_liquidity_unit_tests {
                     },
                 );
                 // 10
-                assert_eq!(test.ai_dex.fee_growth_global_a, 184467440737095516159);
-                assert_eq!(test.ai_dex.fee_growth_global_b, 184467440737095516159);
+                assert_eq!({ test.ai_dex.fee_growth_global_a }, 184467440737095516159);
+                assert_eq!({ test.ai_dex.fee_growth_global_b }, 184467440737095516159);
             }
 
             // t1 |--------l-------c1-------u--------| open position (checkpoint)
@@ -3070,6 +3165,7 @@ mod calculate_modify_liquidity_unit_tests {
                             reward_growths_outside: create_reward_growths(
                                 340282366920938463391637269367342177392,
                             ),
+                            seconds_per_liquidity_outside_x64: 204963823041217240,
                         },
                         tick_upper_update: TickUpdate {
                             initialized: true,
@@ -3079,8 +3175,8 @@ mod calculate_modify_liquidity_unit_tests {
                         },
                     },
                 );
-                assert_eq!(test.ai_dex.fee_growth_global_a, u128::MAX - to_x64(100));
-                assert_eq!(test.ai_dex.fee_growth_global_b, u128::MAX - to_x64(100));
+                assert_eq!({ test.ai_dex.fee_growth_global_a }, u128::MAX - to_x64(100));
+                assert_eq!({ test.ai_dex.fee_growth_global_b }, u128::MAX - to_x64(100));
 
                 test.apply_update(&update, 100);
 
@@ -3124,6 +3220,7 @@ mod calculate_modify_liquidity_unit_tests {
                             reward_growths_outside: create_reward_growths(
                                 340282366920938463391637269367342177392,
                             ),
+                            seconds_per_liquidity_outside_x64: 204963823041217240,
                         },
                         tick_upper_update: TickUpdate {
                             initialized: true,
@@ -3134,8 +3231,8 @@ mod calculate_modify_liquidity_unit_tests {
                     },
                 );
                 // 10
-                assert_eq!(test.ai_dex.fee_growth_global_a, 184467440737095516159);
-                assert_eq!(test.ai_dex.fee_growth_global_b, 184467440737095516159);
+                assert_eq!({ test.ai_dex.fee_growth_global_a }, 184467440737095516159);
+                assert_eq!({ test.ai_dex.fee_growth_global_b }, 184467440737095516159);
             }
 
             // t1 |--------l----------------u---c1---| open position (checkpoint), cross left
@@ -3188,6 +3285,7 @@ mod calculate_modify_liquidity_unit_tests {
                             reward_growths_outside: create_reward_growths(
                                 340282366920938463391637269367342177392,
                             ),
+                            seconds_per_liquidity_outside_x64: 204963823041217240,
                         },
                         tick_upper_update: TickUpdate {
                             initialized: true,
@@ -3198,18 +3296,19 @@ mod calculate_modify_liquidity_unit_tests {
                             reward_growths_outside: create_reward_growths(
                                 340282366920938463391637269367342177392,
                             ),
+                            seconds_per_liquidity_outside_x64: 204963823041217240,
                         },
                     },
                 );
-                assert_eq!(test.ai_dex.fee_growth_global_a, u128::MAX - to_x64(100));
-                assert_eq!(test.ai_dex.fee_growth_global_b, u128::MAX - to_x64(100));
+                assert_eq!({ test.ai_dex.fee_growth_global_a }, u128::MAX - to_x64(100));
+                assert_eq!({ test.ai_dex.fee_growth_global_b }, u128::MAX - to_x64(100));
 
                 test.apply_update(&update, 100);
                 test.increment_ai_dex_fee_growths(to_x64(20), to_x64(20)); // fees at -80
                 test.increment_ai_dex_reward_growths_by_time(100);
                 // -2.777 = -3.888 + (100 * 100 / 9000)
                 assert_ai_dex_reward_growths(
-                    &test.ai_dex.reward_infos,
+                    &{ test.ai_dex.reward_infos },
                     340282366920938463412133651671463901409,
                 );
                 test.cross_tick(TickLabel::Upper, Direction::Left);
@@ -3254,6 +3353,7 @@ mod calculate_modify_liquidity_unit_tests {
                             reward_growths_outside: create_reward_growths(
                                 340282366920938463391637269367342177392,
                             ),
+                            seconds_per_liquidity_outside_x64: 204963823041217240,
                         },
                         tick_upper_update: TickUpdate {
                             initialized: true,
@@ -3262,12 +3362,13 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: to_x64(20),
                             fee_growth_outside_b: to_x64(20),
                             reward_growths_outside: create_reward_growths(20496382304121724017),
+                            seconds_per_liquidity_outside_x64: 204963823041217240,
                         },
                     },
                 );
                 // 10
-                assert_eq!(test.ai_dex.fee_growth_global_a, 184467440737095516159);
-                assert_eq!(test.ai_dex.fee_growth_global_b, 184467440737095516159);
+                assert_eq!({ test.ai_dex.fee_growth_global_a }, 184467440737095516159);
+                assert_eq!({ test.ai_dex.fee_growth_global_b }, 184467440737095516159);
             }
 
             // t1 |---c1---l----------------u--------| open position (checkpoint), cross right
@@ -3324,15 +3425,15 @@ mod calculate_modify_liquidity_unit_tests {
                         },
                     },
                 );
-                assert_eq!(test.ai_dex.fee_growth_global_a, u128::MAX - to_x64(100));
-                assert_eq!(test.ai_dex.fee_growth_global_b, u128::MAX - to_x64(100));
+                assert_eq!({ test.ai_dex.fee_growth_global_a }, u128::MAX - to_x64(100));
+                assert_eq!({ test.ai_dex.fee_growth_global_b }, u128::MAX - to_x64(100));
 
                 test.apply_update(&update, 100);
                 test.increment_ai_dex_fee_growths(to_x64(20), to_x64(20)); // fees at -80
                 test.increment_ai_dex_reward_growths_by_time(100);
                 // time: 200, rewards at -3
                 assert_ai_dex_reward_growths(
-                    &test.ai_dex.reward_infos,
+                    &{ test.ai_dex.reward_infos },
                     u128::MAX - to_x64(3),
                 );
                 test.cross_tick(TickLabel::Lower, Direction::Right);
@@ -3342,7 +3443,7 @@ mod calculate_modify_liquidity_unit_tests {
                 test.increment_ai_dex_reward_growths_by_time(100);
                 // time: 300, rewards at -2.0909 =
                 assert_ai_dex_reward_growths(
-                    &test.ai_dex.reward_infos,
+                    &{ test.ai_dex.reward_infos },
                     340282366920938463424804142550375512621,
                 );
                 test.cross_tick(TickLabel::Upper, Direction::Right);
@@ -3387,6 +3488,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: u128::MAX - to_x64(80),
                             fee_growth_outside_b: u128::MAX - to_x64(80),
                             reward_growths_outside: create_reward_growths(u128::MAX - to_x64(3)),
+                            seconds_per_liquidity_outside_x64: 368934881474191032,
                         },
                         tick_upper_update: TickUpdate {
                             initialized: true,
@@ -3397,12 +3499,13 @@ mod calculate_modify_liquidity_unit_tests {
                             reward_growths_outside: create_reward_growths(
                                 340282366920938463424804142550375512621,
                             ),
+                            seconds_per_liquidity_outside_x64: 536632554871550592,
                         },
                     },
                 );
                 // 10
-                assert_eq!(test.ai_dex.fee_growth_global_a, 184467440737095516159);
-                assert_eq!(test.ai_dex.fee_growth_global_b, 184467440737095516159);
+                assert_eq!({ test.ai_dex.fee_growth_global_a }, 184467440737095516159);
+                assert_eq!({ test.ai_dex.fee_growth_global_b }, 184467440737095516159);
             }
 
             // t1 |--------l-------c1-------u--------| open position (checkpoint)
@@ -3452,6 +3555,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: u128::MAX - to_x64(100),
                             fee_growth_outside_b: u128::MAX - to_x64(100),
                             reward_growths_outside: create_reward_growths(u128::MAX - to_x64(4)),
+                            seconds_per_liquidity_outside_x64: 184467440737095516,
                         },
                         tick_upper_update: TickUpdate {
                             initialized: true,
@@ -3461,8 +3565,8 @@ mod calculate_modify_liquidity_unit_tests {
                         },
                     },
                 );
-                assert_eq!(test.ai_dex.fee_growth_global_a, u128::MAX - to_x64(100));
-                assert_eq!(test.ai_dex.fee_growth_global_b, u128::MAX - to_x64(100));
+                assert_eq!({ test.ai_dex.fee_growth_global_a }, u128::MAX - to_x64(100));
+                assert_eq!({ test.ai_dex.fee_growth_global_b }, u128::MAX - to_x64(100));
 
                 test.apply_update(&update, 100);
 
@@ -3471,7 +3575,7 @@ mod calculate_modify_liquidity_unit_tests {
                 test.increment_ai_dex_reward_growths_by_time(100);
                 // time: 200, rewards at MAX - 3.0909
                 assert_ai_dex_reward_growths(
-                    &test.ai_dex.reward_infos,
+                    &{ test.ai_dex.reward_infos },
                     340282366920938463406357398476665961005,
                 );
                 test.cross_tick(TickLabel::Upper, Direction::Right);
@@ -3514,6 +3618,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: u128::MAX - to_x64(100),
                             fee_growth_outside_b: u128::MAX - to_x64(100),
                             reward_growths_outside: create_reward_growths(u128::MAX - to_x64(4)),
+                            seconds_per_liquidity_outside_x64: 184467440737095516,
                         },
                         tick_upper_update: TickUpdate {
                             initialized: true,
@@ -3524,12 +3629,13 @@ mod calculate_modify_liquidity_unit_tests {
                             reward_growths_outside: create_reward_growths(
                                 340282366920938463406357398476665961005,
                             ),
+                            seconds_per_liquidity_outside_x64: 352165114134455076,
                         },
                     },
                 );
                 // 10
-                assert_eq!(test.ai_dex.fee_growth_global_a, 184467440737095516159);
-                assert_eq!(test.ai_dex.fee_growth_global_b, 184467440737095516159);
+                assert_eq!({ test.ai_dex.fee_growth_global_a }, 184467440737095516159);
+                assert_eq!({ test.ai_dex.fee_growth_global_b }, 184467440737095516159);
             }
 
             // t1 |--------l----------------u---c1---| open position (checkpoint), cross left
@@ -3579,6 +3685,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: u128::MAX - to_x64(100),
                             fee_growth_outside_b: u128::MAX - to_x64(100),
                             reward_growths_outside: create_reward_growths(u128::MAX - to_x64(4)),
+                            seconds_per_liquidity_outside_x64: 184467440737095516,
                         },
                         tick_upper_update: TickUpdate {
                             initialized: true,
@@ -3587,11 +3694,12 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: u128::MAX - to_x64(100),
                             fee_growth_outside_b: u128::MAX - to_x64(100),
                             reward_growths_outside: create_reward_growths(u128::MAX - to_x64(4)),
+                            seconds_per_liquidity_outside_x64: 184467440737095516,
                         },
                     },
                 );
-                assert_eq!(test.ai_dex.fee_growth_global_a, u128::MAX - to_x64(100));
-                assert_eq!(test.ai_dex.fee_growth_global_b, u128::MAX - to_x64(100));
+                assert_eq!({ test.ai_dex.fee_growth_global_a }, u128::MAX - to_x64(100));
+                assert_eq!({ test.ai_dex.fee_growth_global_b }, u128::MAX - to_x64(100));
 
                 test.apply_update(&update, 100);
 
@@ -3599,7 +3707,7 @@ mod calculate_modify_liquidity_unit_tests {
                 test.increment_ai_dex_reward_growths_by_time(100);
                 // time: 200, rewards at MAX - 3
                 assert_ai_dex_reward_growths(
-                    &test.ai_dex.reward_infos,
+                    &{ test.ai_dex.reward_infos },
                     u128::MAX - to_x64(3),
                 );
                 test.cross_tick(TickLabel::Upper, Direction::Left);
@@ -3609,7 +3717,7 @@ mod calculate_modify_liquidity_unit_tests {
                 test.increment_ai_dex_reward_growths_by_time(100);
                 // time: 300, rewards at MAX - 2.0909
                 assert_ai_dex_reward_growths(
-                    &test.ai_dex.reward_infos,
+                    &{ test.ai_dex.reward_infos },
                     340282366920938463424804142550375512621,
                 );
                 test.cross_tick(TickLabel::Upper, Direction::Right);
@@ -3654,6 +3762,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_outside_a: u128::MAX - to_x64(100),
                             fee_growth_outside_b: u128::MAX - to_x64(100),
                             reward_growths_outside: create_reward_growths(u128::MAX - to_x64(4)),
+                            seconds_per_liquidity_outside_x64: 184467440737095516,
                         },
                         tick_upper_update: TickUpdate {
                             initialized: true,
@@ -3664,12 +3773,13 @@ mod calculate_modify_liquidity_unit_tests {
                             reward_growths_outside: create_reward_growths(
                                 340282366920938463406357398476665961005,
                             ),
+                            seconds_per_liquidity_outside_x64: 352165114134455076,
                         },
                     },
                 );
                 // 10
-                assert_eq!(test.ai_dex.fee_growth_global_a, 184467440737095516159);
-                assert_eq!(test.ai_dex.fee_growth_global_b, 184467440737095516159);
+                assert_eq!({ test.ai_dex.fee_growth_global_a }, 184467440737095516159);
+                assert_eq!({ test.ai_dex.fee_growth_global_b }, 184467440737095516159);
             }
         }
     }
@@ -1,7 +1,9 @@
+use std::convert::TryFrom;
+
 use crate::{
     errors::ErrorCode,
-    math::{add_liquidity_delta, checked_mul_shift_right},
-    state::{Position, PositionUpdate, NUM_REWARDS},
+    math::{add_liquidity_delta, checked_mul_div, checked_mul_shift_right},
+    state::{Position, PositionUpdate, NUM_REWARDS, REWARD_BOOST_BPS_DENOMINATOR},
 };
 
 /// Calculates the position update after modifying the liquidity of a position.
@@ -17,6 +19,7 @@ use crate::{
 /// * `fee_growth_inside_a` - The fee growth inside token A.
 /// * `fee_growth_inside_b` - The fee growth inside token B.
 /// * `reward_growths_inside` - An array of reward growths inside each reward token.
+/// * `reward_boost_bps` - The multiplier of the rewards of a locked position, in basis points, zero for none.
 /// 
 /// # Returns
 /// 
@@ -28,6 +31,7 @@ pub fn next_position_modify_liquidity_update(
     fee_growth_inside_a: u128,
     fee_growth_inside_b: u128,
     reward_growths_inside: &[u128; NUM_REWARDS],
+    reward_boost_bps: u16,
 ) -> Result<PositionUpdate, ErrorCode> {
     let mut update = PositionUpdate::default();
 
@@ -56,8 +60,16 @@ pub fn next_position_modify_liquidity_update(
         // rewards earned since the last time the position was modified or rewards were collected.
         let reward_growth_delta =
             reward_growth_inside.wrapping_sub(curr_reward_info.growth_inside_checkpoint);
-        let amount_owed_delta =
+        let mut amount_owed_delta =
             checked_mul_shift_right(position.liquidity, reward_growth_delta).unwrap_or(0);
+        if reward_boost_bps != 0 {
+            amount_owed_delta = checked_mul_div(
+                amount_owed_delta as u128,
+                reward_boost_bps as u128,
+                REWARD_BOOST_BPS_DENOMINATOR as u128,
+            )
+            .map_or(0, |boosted| u64::try_from(boosted).unwrap_or(0));
+        }
 
         update.reward_infos[i].growth_inside_checkpoint = reward_growth_inside;
 
@@ -95,6 +107,7 @@ mod position_orchestrator_unit_tests {
             1000 << Q64_RESOLUTION,
             2000 << Q64_RESOLUTION,
             &[0, 0, 0],
+            0,
         )
         .unwrap();
 
@@ -123,6 +136,7 @@ mod position_orchestrator_unit_tests {
             120 << Q64_RESOLUTION,
             250 << Q64_RESOLUTION,
             &[0, 0, 0],
+            0,
         )
         .unwrap();
 
@@ -142,14 +156,14 @@ mod position_orchestrator_unit_tests {
     #[should_panic(expected = "LiquidityUnderflowError")]
     fn liquidity_underflow() {
         let position = PositionBuilder::new(-10, 10).build();
-        next_position_modify_liquidity_update(&position, -100, 0, 0, &[0, 0, 0]).unwrap();
+        next_position_modify_liquidity_update(&position, -100, 0, 0, &[0, 0, 0], 0).unwrap();
     }
 
     #[test]
     #[should_panic(expected = "LiquidityOverflowError")]
     fn liquidity_overflow() {
         let position = PositionBuilder::new(-10, 10).liquidity(u128::MAX).build();
-        next_position_modify_liquidity_update(&position, i128::MAX, 0, 0, &[0, 0, 0]).unwrap();
+        next_position_modify_liquidity_update(&position, i128::MAX, 0, 0, &[0, 0, 0], 0).unwrap();
     }
 
     #[test]
@@ -165,6 +179,7 @@ mod position_orchestrator_unit_tests {
             u128::MAX,
             u128::MAX,
             &[0, 0, 0],
+            0,
         )
         .unwrap();
         assert_eq!(update.fee_growth_checkpoint_a, u128::MAX);
@@ -268,6 +283,7 @@ mod position_orchestrator_unit_tests {
                 0,
                 0,
                 &test.reward_growths_inside,
+                0,
             )
             .unwrap();
             assert_eq!(
@@ -292,6 +308,24 @@ mod position_orchestrator_unit_tests {
         }
     }
 
+    #[test]
+    fn ok_reward_delta_boosted() {
+        let position = PositionBuilder::new(-10, 10).liquidity(100).build();
+        let update = next_position_modify_liquidity_update(
+            &position,
+            0,
+            0,
+            0,
+            &[10 << Q64_RESOLUTION, 20 << Q64_RESOLUTION, 0],
+            15_000,
+        )
+        .unwrap();
+        assert_eq!(update.reward_infos[0].amount_owed, 1500);
+        assert_eq!(update.reward_infos[1].amount_owed, 3000);
+        assert_eq!(update.reward_infos[2].amount_owed, 0);
+        assert_eq!(update.reward_infos[0].growth_inside_checkpoint, 10 << Q64_RESOLUTION);
+    }
+
     #[test]
     fn reward_delta_overflow_defaults_zero() {
         let position = PositionBuilder::new(-10, 10)
@@ -317,6 +351,7 @@ mod position_orchestrator_unit_tests {
             0,
             0,
             &[u128::MAX, u128::MAX, u128::MAX],
+            0,
         )
         .unwrap();
         assert_eq!(
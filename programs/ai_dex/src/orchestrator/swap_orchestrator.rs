@@ -3,7 +3,8 @@ use solana_program::msg;
 use crate::{
     errors::ErrorCode,
     orchestrator::{
-        tick_orchestrator::next_tick_cross_update, ai_dex_orchestrator::next_ai_dex_reward_infos,
+        tick_orchestrator::next_tick_cross_update,
+        ai_dex_orchestrator::{next_ai_dex_reward_infos, next_seconds_per_liquidity_global},
     },
     math::*,
     state::*,
@@ -22,6 +23,19 @@ pub struct PostSwapUpdate {
     pub next_fee_growth_global: u128,
     pub next_reward_infos: [AiDexRewardInfo; NUM_REWARDS],
     pub next_protocol_fee: u64,
+    pub next_referral_fee: u64,
+    pub fee_rate: u16,
+    // The whole swap fee in the input token, the protocol and referral fees included
+    pub fee_amount: u64,
+}
+
+impl PostSwapUpdate {
+    /// Returns the share of the swap fee accrued by the liquidity providers.
+    pub fn lp_fee(&self) -> u64 {
+        self.fee_amount
+            .saturating_sub(self.next_protocol_fee)
+            .saturating_sub(self.next_referral_fee)
+    }
 }
 
 /// Performs a swap operation on the AiDex pool.
@@ -35,6 +49,9 @@ pub struct PostSwapUpdate {
 /// * `amount_specified_is_input` - Indicates whether the specified amount is the input amount.
 /// * `a_to_b` - Indicates the direction of the swap.
 /// * `timestamp` - The timestamp of the swap.
+/// * `referral_fee_rate` - The share of the swap fee owed to the referrer of the swap, zero without referrer.
+/// * `fee_discount_bps` - The discount off the fee rate granted to the token authority, in basis points.
+/// * `volatility_ticks` - The volatility measured by the oracle of the pool, for the adaptive fee.
 ///
 /// # Returns
 ///
@@ -47,9 +64,23 @@ pub fn swap(
     amount_specified_is_input: bool,
     a_to_b: bool,
     timestamp: u64,
+    referral_fee_rate: u16,
+    fee_discount_bps: u16,
+    volatility_ticks: u32,
 ) -> Result<PostSwapUpdate> {
-    // Check if the square root price limit is within the valid range
-    if sqrt_price_limit < MIN_SQRT_PRICE_X64 || sqrt_price_limit > MAX_SQRT_PRICE_X64 {
+    // Check if the pool is paused
+    if ai_dex.is_paused() {
+        return Err(ErrorCode::PoolPausedError.into());
+    }
+
+    // Check if the pool is open for swaps
+    if !ai_dex.is_open(timestamp) {
+        return Err(ErrorCode::PoolNotOpenError.into());
+    }
+
+    // Check if the square root price limit is within the valid range of the pool
+    let (min_sqrt_price, max_sqrt_price) = ai_dex.sqrt_price_bounds();
+    if sqrt_price_limit < min_sqrt_price || sqrt_price_limit > max_sqrt_price {
         return Err(ErrorCode::SqrtPriceOutOfBoundsError.into());
     }
 
@@ -67,12 +98,15 @@ pub fn swap(
 
     // Get the tick spacing and fee rate from the AiDex instance
     let tick_spacing = ai_dex.tick_spacing;
-    let fee_rate = ai_dex.fee_rate;
+    let fee_rate = discounted_fee_rate(ai_dex.effective_fee_rate(timestamp, volatility_ticks), fee_discount_bps);
     let protocol_fee_rate = ai_dex.protocol_fee_rate;
 
     // Get the next reward infos
     let next_reward_infos = next_ai_dex_reward_infos(ai_dex, timestamp)?;
 
+    // Get the next global seconds per liquidity, crossed ticks flip it at the swap timestamp
+    let next_seconds_per_liquidity_global_x64 = next_seconds_per_liquidity_global(ai_dex, timestamp)?;
+
     // Initialize variables
     let mut amount_remaining: u64 = amount;
     let mut amount_calculated: u64 = 0;
@@ -80,6 +114,8 @@ pub fn swap(
     let mut curr_tick_index = ai_dex.tick_current_index;
     let mut curr_liquidity = ai_dex.liquidity;
     let mut curr_protocol_fee: u64 = 0;
+    let mut curr_referral_fee: u64 = 0;
+    let mut curr_fee_amount: u64 = 0;
     let mut curr_array_index: usize = 0;
     let mut curr_fee_growth_global_input = if a_to_b {
         ai_dex.fee_growth_global_a
@@ -87,6 +123,9 @@ pub fn swap(
         ai_dex.fee_growth_global_b
     };
 
+    // Stop at the tick range of the pool, which is narrower for pools created before it was widened
+    swap_tick_sequence.set_tick_index_bounds(ai_dex.tick_index_bounds());
+
     // Loop until the amount is fully swapped or the square root price limit is reached
     while amount_remaining > 0 && sqrt_price_limit != curr_sqrt_price {
         // Get the next initialized tick index and array index
@@ -133,15 +172,22 @@ pub fn swap(
                 .ok_or(ErrorCode::AmountCalculationOverflowError)?;
         }
 
+        curr_fee_amount = curr_fee_amount
+            .checked_add(swap_computation.fee_amount)
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+
         // Calculate the fees
-        let (next_protocol_fee, next_fee_growth_global_input) = calculate_fees(
+        let (next_protocol_fee, next_referral_fee, next_fee_growth_global_input) = calculate_fees(
             swap_computation.fee_amount,
             protocol_fee_rate,
+            referral_fee_rate,
             curr_liquidity,
             curr_protocol_fee,
+            curr_referral_fee,
             curr_fee_growth_global_input,
         );
         curr_protocol_fee = next_protocol_fee;
+        curr_referral_fee = next_referral_fee;
         curr_fee_growth_global_input = next_fee_growth_global_input;
 
         // Update the tick and liquidity if the next tick is initialized
@@ -164,6 +210,7 @@ pub fn swap(
                     fee_growth_global_a,
                     fee_growth_global_b,
                     &next_reward_infos,
+                    next_seconds_per_liquidity_global_x64,
                 )?;
 
                 curr_liquidity = next_liquidity;
@@ -220,6 +267,11 @@ pub fn swap(
         curr_fee_growth_global_input - ai_dex.fee_growth_global_b
     };
 
+    // Abort the swap if it moves the price further than the circuit breaker of the pool allows
+    ai_dex.check_circuit_breaker(curr_tick_index, timestamp)?;
+    // Abort the swap if its price impact exceeds the cap of the pool
+    ai_dex.check_price_impact(curr_sqrt_price)?;
+
     // Log delta in fee growth to track pool usage over time with off-chain analytics
     msg!("fee_growth: {}", fee_growth);
 
@@ -232,33 +284,60 @@ pub fn swap(
         next_fee_growth_global: curr_fee_growth_global_input,
         next_reward_infos,
         next_protocol_fee: curr_protocol_fee,
+        next_referral_fee: curr_referral_fee,
+        fee_rate,
+        fee_amount: curr_fee_amount,
     })
 }
 
+/// Applies a fee discount, in basis points, to a fee rate. The discounted fee rate is rounded up.
+pub fn discounted_fee_rate(fee_rate: u16, fee_discount_bps: u16) -> u16 {
+    let denominator = FEE_DISCOUNT_BPS_DENOMINATOR as u32;
+    let discount_bps = (fee_discount_bps as u32).min(denominator);
+    ((fee_rate as u32 * (denominator - discount_bps) + denominator - 1) / denominator) as u16
+}
+
 /// Calculates the fees for the swap operation.
 ///
+/// The referral fee is carved out of the swap fee first, then the protocol fee out of the rest.
+///
 /// # Arguments
 ///
 /// * `fee_amount` - The amount of fees.
 /// * `protocol_fee_rate` - The protocol fee rate.
+/// * `referral_fee_rate` - The referral fee rate.
 /// * `curr_liquidity` - The current liquidity.
 /// * `curr_protocol_fee` - The current protocol fee.
+/// * `curr_referral_fee` - The current referral fee.
 /// * `curr_fee_growth_global_input` - The current fee growth global input.
 ///
 /// # Returns
 ///
-/// Returns a tuple containing the next protocol fee and the next fee growth global input.
+/// Returns a tuple containing the next protocol fee, the next referral fee and the next fee growth global input.
 fn calculate_fees(
     fee_amount: u64,
     protocol_fee_rate: u16,
+    referral_fee_rate: u16,
     curr_liquidity: u128,
     curr_protocol_fee: u64,
+    curr_referral_fee: u64,
     curr_fee_growth_global_input: u128,
-) -> (u64, u128) {
+) -> (u64, u64, u128) {
     let mut next_protocol_fee = curr_protocol_fee;
+    let mut next_referral_fee = curr_referral_fee;
     let mut next_fee_growth_global_input = curr_fee_growth_global_input;
     let mut global_fee = fee_amount;
 
+    // Calculate the referral fee
+    if referral_fee_rate > 0 {
+        let delta: u64 = ((global_fee as u128) * (referral_fee_rate as u128) / REFERRAL_FEE_RATE_MUL_VALUE)
+            .try_into()
+            .unwrap();
+
+        global_fee -= delta;
+        next_referral_fee = next_referral_fee.wrapping_add(delta);
+    }
+
     // Calculate the protocol fee
     // Calculate the protocol fee
     if protocol_fee_rate > 0 {
@@ -280,7 +359,7 @@ fn calculate_fees(
             .wrapping_add(((global_fee as u128) << Q64_RESOLUTION) / curr_liquidity);
     }
 
-    (next_protocol_fee, next_fee_growth_global_input)
+    (next_protocol_fee, next_referral_fee, next_fee_growth_global_input)
 }
 
 /// Returns a `Result` containing the `TickUpdate` struct and the next liquidity if the calculation is successful, or an `ErrorCode` if there is an error.
@@ -294,6 +373,7 @@ fn calculate_fees(
 /// * `fee_growth_global_a` - The global fee growth for token A.
 /// * `fee_growth_global_b` - The global fee growth for token B.
 /// * `reward_infos` - The array of AiDexRewardInfo structs.
+/// * `seconds_per_liquidity_global_x64` - The global seconds per liquidity.
 ///
 /// # Returns
 ///
@@ -305,6 +385,7 @@ fn calculate_update(
     fee_growth_global_a: u128,
     fee_growth_global_b: u128,
     reward_infos: &[AiDexRewardInfo; NUM_REWARDS],
+    seconds_per_liquidity_global_x64: u128,
 ) -> Result<(TickUpdate, u128)> {
     // Calculate the signed liquidity net based on the swap direction
     let signed_liquidity_net = if a_to_b {
@@ -314,12 +395,43 @@ fn calculate_update(
     };
 
     // Calculate the tick update and the next liquidity
-    let update = next_tick_cross_update(tick, fee_growth_global_a, fee_growth_global_b, reward_infos)?;
+    let update = next_tick_cross_update(
+        tick,
+        fee_growth_global_a,
+        fee_growth_global_b,
+        reward_infos,
+        seconds_per_liquidity_global_x64,
+    )?;
     let next_liquidity = add_liquidity_delta(liquidity, signed_liquidity_net)?;
 
     Ok((update, next_liquidity))
 }
 
+/// Resolves the `0` "no limit" sentinel of a sqrt_price_limit to the boundary in the swap direction.
+///
+/// # Arguments
+///
+/// * `ai_dex` - The AiDex the swap is made on.
+/// * `sqrt_price_limit` - The square root price limit provided by the user.
+/// * `a_to_b` - Indicates the direction of the swap.
+///
+/// # Returns
+///
+/// Returns the min sqrt price of the AiDex for a_to_b swaps and its max sqrt price for b_to_a swaps
+/// if the limit is `0`, otherwise returns the limit unchanged.
+pub fn resolve_sqrt_price_limit(ai_dex: &AiDexPool, sqrt_price_limit: u128, a_to_b: bool) -> u128 {
+    if sqrt_price_limit != 0 {
+        return sqrt_price_limit;
+    }
+
+    let (min_sqrt_price, max_sqrt_price) = ai_dex.sqrt_price_bounds();
+    if a_to_b {
+        min_sqrt_price
+    } else {
+        max_sqrt_price
+    }
+}
+
 /// Calculates the next square root prices based on the next tick index, square root price limit, and swap direction.
 ///
 /// # Arguments
@@ -1944,7 +2056,7 @@ mod swap_sqrt_price_tests {
             curr_tick_index: 442500, // c1
             start_tick_index: 442368,
             trade_amount: 100_000_000_000_000_000,
-            sqrt_price_limit: MAX_SQRT_PRICE_X64 + 1,
+            sqrt_price_limit: LEGACY_MAX_SQRT_PRICE_X64 + 1,
             amount_specified_is_input: false,
             a_to_b: false,
             ..Default::default()
@@ -1971,7 +2083,7 @@ mod swap_sqrt_price_tests {
             curr_tick_index: 443635, // c1
             start_tick_index: 442368,
             trade_amount: 100_000_000_000_000_000,
-            sqrt_price_limit: MAX_SQRT_PRICE_X64,
+            sqrt_price_limit: LEGACY_MAX_SQRT_PRICE_X64,
             amount_specified_is_input: false,
             a_to_b: false,
             ..Default::default()
@@ -1994,6 +2106,72 @@ mod swap_sqrt_price_tests {
         )
     }
 
+    #[test]
+    #[should_panic(expected = "SqrtPriceOutOfBoundsError")]
+    /// A swap with the price limit over the max price limit of a pool initialized before the tick
+    /// range was widened.
+    /// |__p1_____p1_____c1___max|...limit|
+    ///
+    /// Expectation:
+    /// Fail on out of bounds sqrt-price-limit.
+    fn sqrt_price_limit_over_legacy_max_tick() {
+        let swap_test_info = SwapTestFixture::new(SwapTestFixtureInfo {
+            tick_spacing: TS_128,
+            liquidity: 500,
+            curr_tick_index: 442500, // c1
+            start_tick_index: 442368,
+            trade_amount: 100_000_000_000_000_000,
+            sqrt_price_limit: MAX_SQRT_PRICE_X64,
+            amount_specified_is_input: false,
+            a_to_b: false,
+            ..Default::default()
+        });
+        let mut tick_sequence = SwapTickSequence::new(
+            swap_test_info.tick_arrays[0].borrow_mut(),
+            Some(swap_test_info.tick_arrays[1].borrow_mut()),
+            Some(swap_test_info.tick_arrays[2].borrow_mut()),
+        );
+        swap_test_info.run(&mut tick_sequence, 100);
+    }
+
+    #[test]
+    /// An attempt to swap to the maximum tick of the widened tick range without the last
+    /// initializable tick being initialized
+    /// |__p1_____p1_____c1___c2,max,limit|
+    ///
+    /// Expectation:
+    /// Successfully swap to the maximum tick / maximum sqrt-price
+    fn sqrt_price_limit_at_max_b_to_a_extended_tick_range() {
+        let swap_test_info = SwapTestFixture::new(SwapTestFixtureInfo {
+            tick_spacing: TS_128,
+            liquidity: 10,
+            curr_tick_index: 519885, // c1
+            start_tick_index: 518144,
+            trade_amount: 100_000_000_000_000_000,
+            sqrt_price_limit: MAX_SQRT_PRICE_X64,
+            amount_specified_is_input: false,
+            a_to_b: false,
+            extended_tick_range: true,
+            ..Default::default()
+        });
+        let mut tick_sequence = SwapTickSequence::new(
+            swap_test_info.tick_arrays[0].borrow_mut(),
+            Some(swap_test_info.tick_arrays[1].borrow_mut()),
+            Some(swap_test_info.tick_arrays[2].borrow_mut()),
+        );
+        let post_swap = swap_test_info.run(&mut tick_sequence, 100);
+        assert_swap(
+            &post_swap,
+            &SwapTestExpectation {
+                traded_amount_a: 0,
+                traded_amount_b: 97174892,
+                end_tick_index: 519886,
+                end_liquidity: 10,
+                end_reward_growths: [0, 0, 0],
+            },
+        )
+    }
+
     #[test]
     /// A rightward swap that is limited by the max price limit.
     /// |____p1______c1______p1_c2,max,limit|
@@ -2007,7 +2185,7 @@ mod swap_sqrt_price_tests {
             curr_tick_index: 442500, // c1
             start_tick_index: 442368,
             trade_amount: 100_000_000_000_000_000,
-            sqrt_price_limit: MAX_SQRT_PRICE_X64, // c2, limit
+            sqrt_price_limit: LEGACY_MAX_SQRT_PRICE_X64, // c2, limit
             amount_specified_is_input: false,
             a_to_b: false,
             array_1_ticks: &vec![
@@ -2060,7 +2238,7 @@ mod swap_sqrt_price_tests {
             curr_tick_index: -443500, // c1
             start_tick_index: -451584,
             trade_amount: 100_000_000_000_000_000,
-            sqrt_price_limit: MIN_SQRT_PRICE_X64 - 1,
+            sqrt_price_limit: LEGACY_MIN_SQRT_PRICE_X64 - 1,
             amount_specified_is_input: false,
             a_to_b: true,
             array_1_ticks: &vec![],
@@ -2399,6 +2577,81 @@ mod swap_sqrt_price_tests {
     }
 }
 
+#[cfg(test)]
+mod resolve_sqrt_price_limit_tests {
+    use super::*;
+
+    #[test]
+    fn test_zero_limit_resolves_to_boundary() {
+        let ai_dex = AiDexPool { extended_tick_range: true, ..Default::default() };
+        assert_eq!(resolve_sqrt_price_limit(&ai_dex, 0, true), MIN_SQRT_PRICE_X64);
+        assert_eq!(resolve_sqrt_price_limit(&ai_dex, 0, false), MAX_SQRT_PRICE_X64);
+    }
+
+    #[test]
+    fn test_zero_limit_resolves_to_legacy_boundary() {
+        let ai_dex = AiDexPool::default();
+        assert_eq!(resolve_sqrt_price_limit(&ai_dex, 0, true), LEGACY_MIN_SQRT_PRICE_X64);
+        assert_eq!(resolve_sqrt_price_limit(&ai_dex, 0, false), LEGACY_MAX_SQRT_PRICE_X64);
+    }
+
+    #[test]
+    fn test_non_zero_limit_is_unchanged() {
+        let ai_dex = AiDexPool::default();
+        let sqrt_price_limit = sqrt_price_from_tick_index(100);
+        assert_eq!(resolve_sqrt_price_limit(&ai_dex, sqrt_price_limit, true), sqrt_price_limit);
+        assert_eq!(resolve_sqrt_price_limit(&ai_dex, sqrt_price_limit, false), sqrt_price_limit);
+    }
+}
+
+#[cfg(test)]
+mod calculate_fees_tests {
+    use super::*;
+
+    #[test]
+    fn test_discounted_fee_rate() {
+        assert_eq!(discounted_fee_rate(3_000, 0), 3_000);
+        assert_eq!(discounted_fee_rate(3_000, 2_500), 2_250);
+        assert_eq!(discounted_fee_rate(1, 5_000), 1);
+        assert_eq!(discounted_fee_rate(3_000, 10_000), 0);
+    }
+
+    #[test]
+    fn test_referral_fee_is_carved_out_before_protocol_fee() {
+        // 10% referral fee, then 25% protocol fee of the rest
+        let (protocol_fee, referral_fee, fee_growth) = calculate_fees(10_000, 2_500, 1_000, 1 << 64, 0, 0, 0);
+        assert_eq!(referral_fee, 1_000);
+        assert_eq!(protocol_fee, 2_250);
+        assert_eq!(fee_growth, 6_750);
+    }
+
+    #[test]
+    fn test_no_referral_fee() {
+        let (protocol_fee, referral_fee, fee_growth) = calculate_fees(10_000, 2_500, 0, 1 << 64, 7, 3, 0);
+        assert_eq!(referral_fee, 3);
+        assert_eq!(protocol_fee, 2_507);
+        assert_eq!(fee_growth, 7_500);
+    }
+
+    #[test]
+    fn test_lp_fee() {
+        let swap_update = PostSwapUpdate {
+            amount_a: 0,
+            amount_b: 0,
+            next_liquidity: 0,
+            next_tick_index: 0,
+            next_sqrt_price: 0,
+            next_fee_growth_global: 0,
+            next_reward_infos: [AiDexRewardInfo::default(); NUM_REWARDS],
+            next_protocol_fee: 2_250,
+            next_referral_fee: 1_000,
+            fee_rate: 3_000,
+            fee_amount: 10_000,
+        };
+        assert_eq!(swap_update.lp_fee(), 6_750);
+    }
+}
+
 #[cfg(test)]
 mod swap_error_tests {
     use super::*;
@@ -2599,7 +2852,7 @@ mod swap_error_tests {
         let swap_test_info = SwapTestFixture::new(SwapTestFixtureInfo {
             tick_spacing: TS_128,
             liquidity: (u32::MAX as u128) << 2,
-            curr_tick_index: MAX_TICK_INDEX - 1, // c1
+            curr_tick_index: LEGACY_MAX_TICK_INDEX - 1, // c1
             start_tick_index: 439296,
             trade_amount: 1_000_000_000_000,
             sqrt_price_limit: sqrt_price_from_tick_index(0), // limit
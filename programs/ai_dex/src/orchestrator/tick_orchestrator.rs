@@ -7,7 +7,7 @@ use crate::{
 /// Updates the tick state when crossing a tick boundary.
 ///
 /// This function calculates the new state of a tick when crossing its boundary,
-/// updating the fee growth and reward growth outside the tick.
+/// updating the fee growth, reward growth and seconds per liquidity outside the tick.
 ///
 /// # Parameters
 ///
@@ -15,6 +15,7 @@ use crate::{
 /// - `fee_growth_global_a`: The global fee growth for token A.
 /// - `fee_growth_global_b`: The global fee growth for token B.
 /// - `reward_infos`: An array of `AiDexRewardInfo` objects representing the reward information.
+/// - `seconds_per_liquidity_global_x64`: The global seconds per liquidity.
 ///
 /// # Returns
 ///
@@ -36,6 +37,7 @@ pub fn next_tick_cross_update(
     fee_growth_global_a: u128,
     fee_growth_global_b: u128,
     reward_infos: &[AiDexRewardInfo; NUM_REWARDS],
+    seconds_per_liquidity_global_x64: u128,
 ) -> Result<TickUpdate, ErrorCode> {
     let mut update = TickUpdate::from(tick);
 
@@ -54,13 +56,17 @@ pub fn next_tick_cross_update(
         }
     }
 
+    // Calculate the seconds per liquidity outside the tick
+    update.seconds_per_liquidity_outside_x64 =
+        seconds_per_liquidity_global_x64.wrapping_sub(tick.seconds_per_liquidity_outside_x64);
+
     Ok(update)
 }
 
 /// Updates the tick state when modifying liquidity.
 ///
 /// This function calculates the new state of a tick when modifying its liquidity,
-/// updating the liquidity, fee growth, and reward growth outside the tick.
+/// updating the liquidity, fee growth, reward growth and seconds per liquidity outside the tick.
 ///
 /// # Parameters
 ///
@@ -70,6 +76,7 @@ pub fn next_tick_cross_update(
 /// - `fee_growth_global_a`: The global fee growth for token A.
 /// - `fee_growth_global_b`: The global fee growth for token B.
 /// - `reward_infos`: An array of `AiDexRewardInfo` objects representing the reward information.
+/// - `seconds_per_liquidity_global_x64`: The global seconds per liquidity.
 /// - `liquidity_delta`: The change in liquidity.
 /// - `is_upper_tick`: A flag indicating if the tick being modified is an upper tick.
 ///
@@ -92,6 +99,7 @@ pub fn next_tick_modify_liquidity_update(
     fee_growth_global_a: u128,
     fee_growth_global_b: u128,
     reward_infos: &[AiDexRewardInfo; NUM_REWARDS],
+    seconds_per_liquidity_global_x64: u128,
     liquidity_delta: i128,
     is_upper_tick: bool,
 ) -> Result<TickUpdate, ErrorCode> {
@@ -108,9 +116,17 @@ pub fn next_tick_modify_liquidity_update(
         return Ok(TickUpdate::default());
     }
 
-    // Calculate the fee growth and reward growth outside the tick
-    let (fee_growth_outside_a, fee_growth_outside_b, reward_growths_outside) =
-        calculate_growths(tick, tick_index, tick_current_index, fee_growth_global_a, fee_growth_global_b, reward_infos);
+    // Calculate the fee growth, reward growth and seconds per liquidity outside the tick
+    let (fee_growth_outside_a, fee_growth_outside_b, reward_growths_outside, seconds_per_liquidity_outside_x64) =
+        calculate_growths(
+            tick,
+            tick_index,
+            tick_current_index,
+            fee_growth_global_a,
+            fee_growth_global_b,
+            reward_infos,
+            seconds_per_liquidity_global_x64,
+        );
 
     // Calculate the new liquidity net
     let liquidity_net = calculate_liquidity_net(tick.liquidity_net, liquidity_delta, is_upper_tick)?;
@@ -123,10 +139,12 @@ pub fn next_tick_modify_liquidity_update(
         fee_growth_outside_a,
         fee_growth_outside_b,
         reward_growths_outside,
+        seconds_per_liquidity_outside_x64,
     })
 }
 
-/// Calculates the fee growth and reward growth outside the tick based on the tick state and provided parameters.
+/// Calculates the fee growth, reward growth and seconds per liquidity outside the tick based on the tick state
+/// and provided parameters.
 ///
 /// # Parameters
 ///
@@ -136,6 +154,7 @@ pub fn next_tick_modify_liquidity_update(
 /// - `fee_growth_global_a`: The global fee growth for token A.
 /// - `fee_growth_global_b`: The global fee growth for token B.
 /// - `reward_infos`: An array of `AiDexRewardInfo` objects representing the reward information.
+/// - `seconds_per_liquidity_global_x64`: The global seconds per liquidity.
 ///
 /// # Returns
 ///
@@ -143,11 +162,12 @@ pub fn next_tick_modify_liquidity_update(
 /// - `u128`: The fee growth outside the tick for token A.
 /// - `u128`: The fee growth outside the tick for token B.
 /// - `[u128; NUM_REWARDS]`: The reward growths outside the tick for each reward.
+/// - `u128`: The seconds per liquidity outside the tick.
 ///
 /// # Notes
 ///
-/// - If the liquidity gross of the tick is zero, the function returns the global fee growth and reward growths.
-/// - If the liquidity gross of the tick is non-zero, the function returns the fee growth and reward growths outside the tick.
+/// - If the liquidity gross of the tick is zero, the function returns the global growths.
+/// - If the liquidity gross of the tick is non-zero, the function returns the growths outside the tick.
 fn calculate_growths(
     tick: &Tick,
     tick_index: i32,
@@ -155,7 +175,8 @@ fn calculate_growths(
     fee_growth_global_a: u128,
     fee_growth_global_b: u128,
     reward_infos: &[AiDexRewardInfo; NUM_REWARDS],
-) -> (u128, u128, [u128; NUM_REWARDS]) {
+    seconds_per_liquidity_global_x64: u128,
+) -> (u128, u128, [u128; NUM_REWARDS], u128) {
     if tick.liquidity_gross == 0 {
         // By convention, assume all prior growth happened below the tick
         if tick_current_index >= tick_index {
@@ -163,15 +184,17 @@ fn calculate_growths(
                 fee_growth_global_a,
                 fee_growth_global_b,
                 AiDexRewardInfo::to_reward_growths(reward_infos),
+                seconds_per_liquidity_global_x64,
             )
         } else {
-            (0, 0, [0; NUM_REWARDS])
+            (0, 0, [0; NUM_REWARDS], 0)
         }
     } else {
         (
             tick.fee_growth_outside_a,
             tick.fee_growth_outside_b,
             tick.reward_growths_outside,
+            tick.seconds_per_liquidity_outside_x64,
         )
     }
 }
@@ -327,6 +350,52 @@ pub fn next_reward_growths_inside(
     reward_growths_inside
 }
 
+/// Calculates the seconds per liquidity inside of tick_lower and tick_upper based on their positions
+/// relative to tick_current_index.
+///
+/// The difference between two values returned for the same range, multiplied by the liquidity of a
+/// position constant over the period, is the number of seconds the position was in range. Values
+/// returned for different ranges, or after a tick of the range was uninitialized, are not comparable.
+///
+/// # Parameters
+///
+/// - `tick_current_index`: The index of the current tick being processed.
+/// - `tick_lower`: A reference to the lower tick.
+/// - `tick_lower_index`: The index of the lower tick.
+/// - `tick_upper`: A reference to the upper tick.
+/// - `tick_upper_index`: The index of the upper tick.
+/// - `seconds_per_liquidity_global_x64`: The global seconds per liquidity.
+///
+/// # Returns
+///
+/// The seconds per liquidity inside the ticks, as a Q64.64.
+pub fn next_seconds_per_liquidity_inside(
+    tick_current_index: i32,
+    tick_lower: &Tick,
+    tick_lower_index: i32,
+    tick_upper: &Tick,
+    tick_upper_index: i32,
+    seconds_per_liquidity_global_x64: u128,
+) -> u128 {
+    // By convention, assume all prior time passed below the tick
+    let seconds_per_liquidity_below = match (tick_lower.initialized, tick_current_index < tick_lower_index) {
+        (false, _) => seconds_per_liquidity_global_x64,
+        (true, true) => seconds_per_liquidity_global_x64.wrapping_sub(tick_lower.seconds_per_liquidity_outside_x64),
+        (true, false) => tick_lower.seconds_per_liquidity_outside_x64,
+    };
+
+    // By convention, assume all prior time passed below the tick, not above
+    let seconds_per_liquidity_above = match (tick_upper.initialized, tick_current_index < tick_upper_index) {
+        (false, _) => 0,
+        (true, true) => tick_upper.seconds_per_liquidity_outside_x64,
+        (true, false) => seconds_per_liquidity_global_x64.wrapping_sub(tick_upper.seconds_per_liquidity_outside_x64),
+    };
+
+    seconds_per_liquidity_global_x64
+        .wrapping_sub(seconds_per_liquidity_below)
+        .wrapping_sub(seconds_per_liquidity_above)
+}
+
 #[cfg(test)]
 mod tick_orchestrator_tests {
     use anchor_lang::prelude::Pubkey;
@@ -334,8 +403,8 @@ mod tick_orchestrator_tests {
     use crate::{
         errors::ErrorCode,
         orchestrator::tick_orchestrator::{
-            next_fee_growths_inside, next_tick_cross_update, next_tick_modify_liquidity_update,
-            TickUpdate,
+            next_fee_growths_inside, next_seconds_per_liquidity_inside, next_tick_cross_update,
+            next_tick_modify_liquidity_update, TickUpdate,
         },
         math::Q64_RESOLUTION,
         state::{tick_builder::TickBuilder, Tick, AiDexRewardInfo, NUM_REWARDS},
@@ -601,6 +670,7 @@ mod tick_orchestrator_tests {
             fee_growth_global_a: u128,
             fee_growth_global_b: u128,
             reward_infos: [AiDexRewardInfo; NUM_REWARDS],
+            seconds_per_liquidity_global_x64: u128,
             liquidity_delta: i128,
             is_upper_tick: bool,
             expected_update: TickUpdate,
@@ -639,6 +709,7 @@ mod tick_orchestrator_tests {
                 fee_growth_global_a: 100,
                 fee_growth_global_b: 100,
                 reward_infos,
+                seconds_per_liquidity_global_x64: 100,
                 expected_update: TickUpdate {
                     initialized: true,
                     liquidity_net: 42069,
@@ -656,6 +727,7 @@ mod tick_orchestrator_tests {
                 fee_growth_global_a: 100,
                 fee_growth_global_b: 100,
                 reward_infos,
+                seconds_per_liquidity_global_x64: 100,
                 expected_update: TickUpdate {
                     initialized: true,
                     liquidity_net: 42069,
@@ -667,6 +739,7 @@ mod tick_orchestrator_tests {
                         100 << Q64_RESOLUTION,
                         100 << Q64_RESOLUTION,
                     ],
+                    seconds_per_liquidity_outside_x64: 100,
                 },
                 ..Default::default()
             },
@@ -803,6 +876,7 @@ mod tick_orchestrator_tests {
                     reward_growths_outside: [0, 250, 0],
                     ..Default::default()
                 },
+                ..Default::default()
             }
         ] {
             // System under test
@@ -813,6 +887,7 @@ mod tick_orchestrator_tests {
                 test.fee_growth_global_a,
                 test.fee_growth_global_b,
                 &test.reward_infos,
+                test.seconds_per_liquidity_global_x64,
                 test.liquidity_delta,
                 test.is_upper_tick,
             )
@@ -848,6 +923,12 @@ mod tick_orchestrator_tests {
                 "{}: reward_growths_outside invalid",
                 test.name
             );
+            assert_eq!(
+                update.seconds_per_liquidity_outside_x64,
+                test.expected_update.seconds_per_liquidity_outside_x64,
+                "{}: seconds_per_liquidity_outside_x64 invalid",
+                test.name
+            );
         }
     }
 
@@ -915,6 +996,7 @@ mod tick_orchestrator_tests {
                 0,
                 0,
                 &[AiDexRewardInfo::default(); NUM_REWARDS],
+                0,
                 test.liquidity_delta,
                 test.is_upper_tick,
             )
@@ -932,6 +1014,7 @@ mod tick_orchestrator_tests {
             fee_growth_global_a: u128,
             fee_growth_global_b: u128,
             reward_infos: [AiDexRewardInfo; NUM_REWARDS],
+            seconds_per_liquidity_global_x64: u128,
             expected_update: TickUpdate,
         }
 
@@ -941,6 +1024,7 @@ mod tick_orchestrator_tests {
                 .fee_growth_outside_a(1000)
                 .fee_growth_outside_b(1000)
                 .reward_growths_outside([500, 250, 100])
+                .seconds_per_liquidity_outside_x64(1200)
                 .build(),
             fee_growth_global_a: 2500,
             fee_growth_global_b: 6750,
@@ -964,10 +1048,12 @@ mod tick_orchestrator_tests {
                     ..Default::default()
                 },
             ],
+            seconds_per_liquidity_global_x64: 3000,
             expected_update: TickUpdate {
                 fee_growth_outside_a: 1500,
                 fee_growth_outside_b: 5750,
                 reward_growths_outside: [500, 750, 900],
+                seconds_per_liquidity_outside_x64: 1800,
                 ..Default::default()
             },
         }] {
@@ -977,6 +1063,7 @@ mod tick_orchestrator_tests {
                 test.fee_growth_global_a,
                 test.fee_growth_global_b,
                 &test.reward_infos,
+                test.seconds_per_liquidity_global_x64,
             )
             .unwrap();
 
@@ -1000,6 +1087,79 @@ mod tick_orchestrator_tests {
                     test.name, i
                 );
             }
+            assert_eq!(
+                update.seconds_per_liquidity_outside_x64,
+                test.expected_update.seconds_per_liquidity_outside_x64,
+                "{}: seconds_per_liquidity_outside_x64 invalid",
+                test.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_next_seconds_per_liquidity_inside() {
+        struct Test<'a> {
+            name: &'a str,
+            tick_current_index: i32,
+            tick_lower: Tick,
+            tick_upper: Tick,
+            seconds_per_liquidity_global_x64: u128,
+            expected_seconds_per_liquidity_inside: u128,
+        }
+
+        let tick_lower = TickBuilder::default()
+            .initialized(true)
+            .seconds_per_liquidity_outside_x64(300)
+            .build();
+        let tick_upper = TickBuilder::default()
+            .initialized(true)
+            .seconds_per_liquidity_outside_x64(200)
+            .build();
+
+        for test in [
+            Test {
+                name: "current tick index below ticks",
+                tick_current_index: -200,
+                tick_lower,
+                tick_upper,
+                seconds_per_liquidity_global_x64: 1000,
+                expected_seconds_per_liquidity_inside: 100,
+            },
+            Test {
+                name: "current tick index between ticks",
+                tick_current_index: 0,
+                tick_lower,
+                tick_upper,
+                seconds_per_liquidity_global_x64: 1000,
+                expected_seconds_per_liquidity_inside: 500,
+            },
+            Test {
+                name: "current tick index above ticks",
+                tick_current_index: 200,
+                tick_lower,
+                tick_upper,
+                seconds_per_liquidity_global_x64: 1000,
+                expected_seconds_per_liquidity_inside: u128::MAX - 99,
+            },
+            Test {
+                name: "uninitialized ticks",
+                tick_current_index: 0,
+                tick_lower: Tick::default(),
+                tick_upper: Tick::default(),
+                seconds_per_liquidity_global_x64: 1000,
+                expected_seconds_per_liquidity_inside: 0,
+            },
+        ] {
+            // System under test
+            let result = next_seconds_per_liquidity_inside(
+                test.tick_current_index,
+                &test.tick_lower,
+                -100,
+                &test.tick_upper,
+                100,
+                test.seconds_per_liquidity_global_x64,
+            );
+            assert_eq!(result, test.expected_seconds_per_liquidity_inside, "{}", test.name);
         }
     }
 }
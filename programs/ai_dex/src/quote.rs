@@ -0,0 +1,210 @@
+//! Off-chain quoting of swaps.
+//!
+//! `compute_swap` runs the tick-walking computation of the swap orchestrator on a snapshot of a
+//! pool and its tick arrays, so that routers and SDKs can quote a swap with the very amounts,
+//! fees and end price the program would produce, without reimplementing the swap loop.
+use std::cell::RefCell;
+
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::ErrorCode,
+    orchestrator::swap_orchestrator::{resolve_sqrt_price_limit, swap},
+    state::{AiDexPool, TickArray, TickArrayBitmap},
+    util::{SwapTickSequence, TickArrayRefMut},
+};
+
+/// The parameters of a swap to compute, as passed to the `swap` instruction.
+#[derive(Clone, Copy, Default)]
+pub struct SwapParams<'a> {
+    /// The amount to swap, input or output depending on `amount_specified_is_input`.
+    pub amount: u64,
+    /// The square root price limit of the swap. `0` means no limit in the swap direction.
+    pub sqrt_price_limit: u128,
+    pub amount_specified_is_input: bool,
+    pub a_to_b: bool,
+    /// The timestamp the swap is computed at, for the rewards and the time-based fees.
+    pub timestamp: u64,
+    /// The share of the swap fee owed to the referrer, zero without referrer.
+    pub referral_fee_rate: u16,
+    /// The fee discount granted to the token authority, in basis points.
+    pub fee_discount_bps: u16,
+    /// The volatility measured by the oracle of the pool, for the adaptive fee.
+    pub volatility_ticks: u32,
+    /// The tick array bitmap of the pool, if it has been initialized.
+    pub tick_array_bitmap: Option<&'a TickArrayBitmap>,
+}
+
+/// The outcome of a swap computed by `compute_swap`.
+///
+/// The amounts exclude the transfer fees of the token mints, which are applied on top of them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SwapResult {
+    pub amount_a: u64,
+    pub amount_b: u64,
+    /// The amount paid into the pool.
+    pub amount_in: u64,
+    /// The amount paid out of the pool.
+    pub amount_out: u64,
+    /// The swap fee in the input token, the protocol and referral fees included.
+    pub fee_amount: u64,
+    pub protocol_fee: u64,
+    pub referral_fee: u64,
+    /// The fee rate the swap was charged, after the adaptive fee and the fee discount.
+    pub fee_rate: u16,
+    pub end_sqrt_price: u128,
+    pub end_tick_index: i32,
+    pub end_liquidity: u128,
+}
+
+/// Computes a swap on a snapshot of a pool, without changing any state.
+///
+/// The tick arrays must follow each other in the swap direction, starting with the one holding
+/// the current tick, as for the `swap` instruction. Like the instruction, the swap stops at the
+/// end of the last tick array unless the tick array bitmap shows no initialized tick array beyond.
+///
+/// # Arguments
+///
+/// * `pool_snapshot` - The state of the pool to swap through.
+/// * `tick_arrays` - The tick arrays the swap may cross, at least one.
+/// * `params` - The parameters of the swap.
+///
+/// # Returns
+///
+/// * `Result<SwapResult>` - The amounts, fees and end state of the swap.
+///
+/// # Errors
+///
+/// * `InvalidTickArraySequenceError` - If no tick array is given.
+/// * Any error the `swap` instruction would fail with given the same pool state.
+pub fn compute_swap(
+    pool_snapshot: &AiDexPool,
+    tick_arrays: &[TickArray],
+    params: &SwapParams,
+) -> Result<SwapResult> {
+    let tick_arrays: Vec<RefCell<TickArray>> = tick_arrays.iter().copied().map(RefCell::new).collect();
+    let mut tick_arrays_iter = tick_arrays
+        .iter()
+        .map(|tick_array| TickArrayRefMut::Fixed(tick_array.borrow_mut()));
+    let mut swap_tick_sequence = SwapTickSequence::new_with_tick_arrays(
+        tick_arrays_iter
+            .next()
+            .ok_or(ErrorCode::InvalidTickArraySequenceError)?,
+        None,
+        None,
+    );
+    for tick_array in tick_arrays_iter {
+        swap_tick_sequence.push_tick_array(tick_array);
+    }
+    swap_tick_sequence.skip_tick_updates();
+    if let Some(tick_array_bitmap) = params.tick_array_bitmap {
+        swap_tick_sequence.apply_tick_array_bitmap(
+            tick_array_bitmap,
+            pool_snapshot.tick_spacing,
+            params.a_to_b,
+        )?;
+    }
+
+    let swap_update = swap(
+        pool_snapshot,
+        &mut swap_tick_sequence,
+        params.amount,
+        resolve_sqrt_price_limit(pool_snapshot, params.sqrt_price_limit, params.a_to_b),
+        params.amount_specified_is_input,
+        params.a_to_b,
+        params.timestamp,
+        params.referral_fee_rate,
+        params.fee_discount_bps,
+        params.volatility_ticks,
+    )?;
+
+    let (amount_in, amount_out) = if params.a_to_b {
+        (swap_update.amount_a, swap_update.amount_b)
+    } else {
+        (swap_update.amount_b, swap_update.amount_a)
+    };
+
+    Ok(SwapResult {
+        amount_a: swap_update.amount_a,
+        amount_b: swap_update.amount_b,
+        amount_in,
+        amount_out,
+        fee_amount: swap_update.fee_amount,
+        protocol_fee: swap_update.next_protocol_fee,
+        referral_fee: swap_update.next_referral_fee,
+        fee_rate: swap_update.fee_rate,
+        end_sqrt_price: swap_update.next_sqrt_price,
+        end_tick_index: swap_update.next_tick_index,
+        end_liquidity: swap_update.next_liquidity,
+    })
+}
+
+#[cfg(test)]
+mod compute_swap_tests {
+    use super::*;
+    use crate::math::sqrt_price_from_tick_index;
+    use crate::util::test_utils::swap_test_fixture::*;
+
+    #[test]
+    fn test_compute_swap_matches_swap() {
+        let swap_test_info = SwapTestFixture::new(SwapTestFixtureInfo {
+            tick_spacing: TS_128,
+            liquidity: 5_000_000_000,
+            curr_tick_index: 1_000,
+            start_tick_index: 0,
+            trade_amount: 1_000_000,
+            sqrt_price_limit: sqrt_price_from_tick_index(-20_000),
+            amount_specified_is_input: true,
+            a_to_b: true,
+            array_1_ticks: &build_filled_tick_array(0, TS_128),
+            array_2_ticks: Some(&build_filled_tick_array(-11_264, TS_128)),
+            array_3_ticks: Some(&build_filled_tick_array(-22_528, TS_128)),
+            fee_rate: 3_000,
+            protocol_fee_rate: 300,
+            ..Default::default()
+        });
+        let tick_arrays: Vec<TickArray> = swap_test_info
+            .tick_arrays
+            .iter()
+            .map(|tick_array| *tick_array.borrow())
+            .collect();
+
+        let result = compute_swap(
+            &swap_test_info.ai_dex,
+            &tick_arrays,
+            &SwapParams {
+                amount: swap_test_info.trade_amount,
+                sqrt_price_limit: swap_test_info.sqrt_price_limit,
+                amount_specified_is_input: true,
+                a_to_b: true,
+                timestamp: 100,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let mut tick_sequence = SwapTickSequence::new(
+            swap_test_info.tick_arrays[0].borrow_mut(),
+            Some(swap_test_info.tick_arrays[1].borrow_mut()),
+            Some(swap_test_info.tick_arrays[2].borrow_mut()),
+        );
+        let post_swap = swap_test_info.run(&mut tick_sequence, 100);
+
+        assert_eq!(result.amount_a, post_swap.amount_a);
+        assert_eq!(result.amount_b, post_swap.amount_b);
+        assert_eq!(result.amount_in, post_swap.amount_a);
+        assert_eq!(result.amount_out, post_swap.amount_b);
+        assert_eq!(result.fee_amount, post_swap.fee_amount);
+        assert_eq!(result.protocol_fee, post_swap.next_protocol_fee);
+        assert_eq!(result.end_sqrt_price, post_swap.next_sqrt_price);
+        assert_eq!(result.end_tick_index, post_swap.next_tick_index);
+        assert_eq!(result.end_liquidity, post_swap.next_liquidity);
+    }
+
+    #[test]
+    fn test_compute_swap_without_tick_arrays() {
+        let swap_test_info = SwapTestFixture::new(SwapTestFixtureInfo::default());
+        let result = compute_swap(&swap_test_info.ai_dex, &[], &SwapParams::default());
+        assert!(result.is_err());
+    }
+}
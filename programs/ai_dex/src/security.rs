@@ -1,3 +1,4 @@
+#[cfg(not(feature = "no-entrypoint"))]
 use solana_security_txt::security_txt;
 
 #[cfg(not(feature = "no-entrypoint"))]
@@ -0,0 +1,194 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+/// Window of the TWAP the pool price is compared against before an agent rebalance.
+pub const AGENT_TWAP_WINDOW_SECONDS: u32 = 300;
+
+pub const MAX_AGENT_SLIPPAGE_BPS: u16 = 10_000;
+
+#[account]
+#[derive(Default)]
+pub struct AgentStrategy {
+    pub position: Pubkey, // 32
+    pub agent: Pubkey,    // 32
+
+    pub max_tick_width: u32,         // 4
+    pub min_rebalance_interval: u64, // 8
+    // Compared against the tick distance to the TWAP, one tick being about one basis point
+    pub max_slippage_bps: u16, // 2
+
+    pub last_rebalance_timestamp: u64, // 8
+    // 64 RESERVE
+}
+
+/// Struct representing the strategy an agent follows to rebalance a position.
+///
+/// The position holder designates an agent key and bounds what it can do: the width of the ranges
+/// it can move the position to, how often it can rebalance and how far the pool price can be from
+/// its TWAP when it does.
+impl AgentStrategy {
+    /// Length of the `AgentStrategy` struct in bytes.
+    pub const LEN: usize = 8 + 32 + 32 + 4 + 8 + 2 + 8 + 64;
+
+    /// Initializes the `AgentStrategy` struct for the given position and agent.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The position pubkey.
+    /// * `agent` - The agent pubkey.
+    /// * `max_tick_width` - The maximum width of a range, in ticks.
+    /// * `min_rebalance_interval` - The minimum number of seconds between two rebalances.
+    /// * `max_slippage_bps` - The maximum distance between the pool price and its TWAP.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the constraints are invalid.
+    pub fn initialize(
+        &mut self,
+        position: Pubkey,
+        agent: Pubkey,
+        max_tick_width: u32,
+        min_rebalance_interval: u64,
+        max_slippage_bps: u16,
+    ) -> Result<()> {
+        self.position = position;
+        self.update(agent, max_tick_width, min_rebalance_interval, max_slippage_bps)
+    }
+
+    /// Updates the agent and the constraints of the strategy.
+    ///
+    /// # Arguments
+    ///
+    /// * `agent` - The agent pubkey.
+    /// * `max_tick_width` - The maximum width of a range, in ticks.
+    /// * `min_rebalance_interval` - The minimum number of seconds between two rebalances.
+    /// * `max_slippage_bps` - The maximum distance between the pool price and its TWAP.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the constraints are invalid.
+    pub fn update(
+        &mut self,
+        agent: Pubkey,
+        max_tick_width: u32,
+        min_rebalance_interval: u64,
+        max_slippage_bps: u16,
+    ) -> Result<()> {
+        if max_tick_width == 0 || max_slippage_bps > MAX_AGENT_SLIPPAGE_BPS {
+            return Err(ErrorCode::InvalidAgentStrategyError.into());
+        }
+
+        self.agent = agent;
+        self.max_tick_width = max_tick_width;
+        self.min_rebalance_interval = min_rebalance_interval;
+        self.max_slippage_bps = max_slippage_bps;
+        Ok(())
+    }
+
+    /// Checks that a rebalance is allowed by the strategy.
+    ///
+    /// # Arguments
+    ///
+    /// * `tick_lower_index` - The new lower tick index of the position.
+    /// * `tick_upper_index` - The new upper tick index of the position.
+    /// * `tick_current_index` - The current tick index of the pool.
+    /// * `twap_tick_index` - The time-weighted average tick index of the pool.
+    /// * `timestamp` - The current unix timestamp.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rebalance is too frequent, the range is too wide or the pool
+    /// price is too far from its TWAP.
+    pub fn check_rebalance(
+        &self,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+        tick_current_index: i32,
+        twap_tick_index: i32,
+        timestamp: u64,
+    ) -> Result<()> {
+        if self.last_rebalance_timestamp != 0
+            && timestamp < self.last_rebalance_timestamp.saturating_add(self.min_rebalance_interval)
+        {
+            return Err(ErrorCode::AgentRebalanceTooFrequentError.into());
+        }
+
+        let tick_width = (tick_upper_index as i64) - (tick_lower_index as i64);
+        if tick_width > self.max_tick_width as i64 {
+            return Err(ErrorCode::AgentTickWidthExceededError.into());
+        }
+
+        let deviation = ((tick_current_index as i64) - (twap_tick_index as i64)).abs();
+        if deviation > self.max_slippage_bps as i64 {
+            return Err(ErrorCode::AgentSlippageExceededError.into());
+        }
+
+        Ok(())
+    }
+
+    /// Records a rebalance of the agent.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp` - The unix timestamp of the rebalance.
+    pub fn record_rebalance(&mut self, timestamp: u64) {
+        self.last_rebalance_timestamp = timestamp;
+    }
+}
+
+#[cfg(test)]
+mod agent_strategy_tests {
+    use super::*;
+
+    fn build_strategy() -> AgentStrategy {
+        let mut strategy = AgentStrategy::default();
+        strategy
+            .initialize(Pubkey::new_unique(), Pubkey::new_unique(), 1_000, 3_600, 50)
+            .unwrap();
+        strategy
+    }
+
+    #[test]
+    fn test_initialize_invalid_constraints() {
+        let mut strategy = AgentStrategy::default();
+
+        assert!(strategy
+            .initialize(Pubkey::new_unique(), Pubkey::new_unique(), 0, 3_600, 50)
+            .is_err());
+        assert!(strategy
+            .initialize(Pubkey::new_unique(), Pubkey::new_unique(), 1_000, 3_600, 10_001)
+            .is_err());
+    }
+
+    #[test]
+    fn test_check_rebalance_within_constraints() {
+        let strategy = build_strategy();
+
+        assert!(strategy.check_rebalance(-500, 500, 10, -40, 100).is_ok());
+    }
+
+    #[test]
+    fn test_check_rebalance_too_wide() {
+        let strategy = build_strategy();
+
+        assert!(strategy.check_rebalance(-500, 501, 0, 0, 100).is_err());
+    }
+
+    #[test]
+    fn test_check_rebalance_too_far_from_twap() {
+        let strategy = build_strategy();
+
+        assert!(strategy.check_rebalance(-500, 500, 51, 0, 100).is_err());
+        assert!(strategy.check_rebalance(-500, 500, -51, 0, 100).is_err());
+    }
+
+    #[test]
+    fn test_check_rebalance_too_frequent() {
+        let mut strategy = build_strategy();
+        strategy.record_rebalance(100);
+
+        assert!(strategy.check_rebalance(-500, 500, 0, 0, 3_699).is_err());
+        assert!(strategy.check_rebalance(-500, 500, 0, 0, 3_700).is_ok());
+    }
+}
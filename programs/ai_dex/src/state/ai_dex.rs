@@ -1,15 +1,19 @@
 use crate::{
     errors::ErrorCode,
     math::{
-        tick_index_from_sqrt_price, MAX_FEE_RATE, MAX_PROTOCOL_FEE_RATE, MAX_SQRT_PRICE_X64,
-        MIN_SQRT_PRICE_X64,
+        checked_mul_div, checked_mul_shift_right_round_up_if, get_price_impact_bps,
+        tick_index_from_sqrt_price, FULL_RANGE_ONLY_TICK_SPACING_THRESHOLD, MAX_FEE_RATE,
+        LEGACY_MAX_SQRT_PRICE_X64, LEGACY_MIN_SQRT_PRICE_X64, MAX_PROTOCOL_FEE_RATE,
+        MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64, Q64_RESOLUTION,
     },
 };
 use anchor_lang::prelude::*;
 
-use super::AiDexConfig;
+use super::{AiDexConfig, PoolAllowlistKind, Tick, MAX_REWARD_BOOST_BPS, REWARD_BOOST_BPS_DENOMINATOR};
 
-#[account]
+#[account(zero_copy(unsafe))]
+// Fields keep their declaration order, which is the layout of the pools serialized before zero-copy
+#[repr(C, packed)]
 #[derive(Default)]
 /// Represents the state of the AiDex program.
 pub struct AiDexPool {
@@ -66,15 +70,171 @@ pub struct AiDexPool {
 
     /// The reward information for each reward.
     pub reward_infos: [AiDexRewardInfo; NUM_REWARDS], // 384
+
+    /// Whether the fee rate and protocol fee rate are frozen against further updates.
+    /// Once set, this flag cannot be cleared.
+    pub fee_rates_frozen: bool, // 1
+
+    /// The authorities proposed for each reward, which take over once they accept.
+    pub pending_reward_authorities: [Pubkey; NUM_REWARDS], // 96
+
+    /// Whether swaps and liquidity increases are paused. Decreasing liquidity and collecting
+    /// fees and rewards remain allowed, so the liquidity providers can always exit.
+    pub paused: bool, // 1
+
+    /// The maximum number of ticks the price can move within a circuit breaker window.
+    /// Zero disables the circuit breaker.
+    pub circuit_breaker_max_tick_move: u32, // 4
+    /// The length of a circuit breaker window in seconds.
+    pub circuit_breaker_window_seconds: u32, // 4
+    /// The timestamp when the current circuit breaker window started.
+    pub circuit_breaker_window_start_timestamp: u64, // 8
+    /// The tick index when the current circuit breaker window started.
+    pub circuit_breaker_window_start_tick: i32, // 4
+
+    /// The maximum price impact of a single swap, in basis points. Zero disables the cap.
+    pub max_price_impact_bps: u16, // 2
+
+    /// The maximum share of a vault balance that swaps can take out within a single slot, in
+    /// basis points. Zero disables the cap.
+    pub max_slot_volume_bps: u16, // 2
+    /// The slot of the tracked swap volume.
+    pub volume_slot: u64, // 8
+    /// The amount of token A swapped out of the vault within `volume_slot`.
+    pub slot_volume_out_a: u64, // 8
+    /// The amount of token B swapped out of the vault within `volume_slot`.
+    pub slot_volume_out_b: u64, // 8
+
+    /// Whether a swap is rejected if the same authority swapped in the opposite direction earlier
+    /// within the same slot.
+    pub anti_sandwich_enabled: bool, // 1
+    /// The slot of the last swap, and of the authorities tracked in `slot_swap_authorities`.
+    pub last_swap_slot: u64, // 8
+    /// The authority of the last swap.
+    pub last_swap_authority: Pubkey, // 32
+    /// The direction of the last swap.
+    pub last_swap_a_to_b: bool, // 1
+
+    /// The timestamp from which swaps are allowed. Liquidity can be added before it.
+    pub open_time: u64, // 8
+
+    /// Whether opening positions and increasing liquidity is restricted to the liquidity allowlist.
+    pub liquidity_allowlist_enabled: bool, // 1
+
+    /// Whether swapping is restricted to the swap allowlist.
+    pub swap_allowlist_enabled: bool, // 1
+
+    /// The referral fees of token A accrued by the referrers and not yet collected.
+    pub referral_fee_owed_a: u64, // 8
+    /// The referral fees of token B accrued by the referrers and not yet collected.
+    pub referral_fee_owed_b: u64, // 8
+
+    /// The duration of the windows over which the volume is tracked, zero to disable the volume fee tiers.
+    pub volume_fee_window_seconds: u32, // 4
+    /// The minimum volume, in token A, of the previous window from which each fee tier applies.
+    pub volume_fee_tier_min_volumes: [u64; MAX_VOLUME_FEE_TIERS], // 32
+    /// The fee rate of each fee tier, zero for an unused tier.
+    pub volume_fee_tier_rates: [u16; MAX_VOLUME_FEE_TIERS], // 8
+    /// The timestamp at which the current volume window started.
+    pub volume_window_start_timestamp: u64, // 8
+    /// The volume, in token A, swapped within the current window.
+    pub window_volume_a: u64, // 8
+    /// The volume, in token A, swapped within the previous window.
+    pub previous_window_volume_a: u64, // 8
+
+    /// The oracle window over which the volatility is measured, zero to disable the adaptive fee.
+    pub adaptive_fee_window_seconds: u32, // 4
+    /// The fee rate added per tick between the current tick and the oracle average tick.
+    pub adaptive_fee_rate_per_tick: u16, // 2
+    /// The lower bound of the adaptive fee rate.
+    pub adaptive_fee_min_rate: u16, // 2
+    /// The upper bound of the adaptive fee rate.
+    pub adaptive_fee_max_rate: u16, // 2
+
+    /// The fee rate at the open time of the launch fee schedule.
+    pub launch_fee_start_rate: u16, // 2
+    /// The fee rate at the end of the launch fee schedule.
+    pub launch_fee_end_rate: u16, // 2
+    /// The duration of the launch fee schedule after the open time, zero to disable it.
+    pub launch_fee_duration_seconds: u32, // 4
+
+    /// The authority allowed to set the fee rate of this AiDex only, the default pubkey for none.
+    pub fee_manager: Pubkey, // 32
+
+    /// The multiplier of the rewards of locked positions, in basis points, zero for no boost.
+    pub locked_reward_boost_bps: u16, // 2
+
+    /// Whether the vault reserves have been tracked since the AiDex was initialized.
+    pub vault_reserves_tracked: bool, // 1
+    /// The balance of token vault A accounted for by the transfers of the program.
+    pub vault_reserve_a: u64, // 8
+    /// The balance of token vault B accounted for by the transfers of the program.
+    pub vault_reserve_b: u64, // 8
+
+    /// Whether the AiDex is in emergency mode, where the positions withdraw their principal
+    /// without the tick arrays. Once set, this flag cannot be cleared.
+    pub emergency_mode: bool, // 1
+
+    /// The version of the layout of the AiDex, raised by `migrate_pool`.
+    pub version: u8, // 1
+
+    /// Whether positions can only be opened on the full range of ticks, set from the fee tier
+    /// the AiDex was initialized with.
+    pub full_range_only: bool, // 1
+
+    /// Whether a flash swap has sent its output and awaits the repayment of its input.
+    pub flash_swap_in_progress: bool, // 1
+    /// Whether the input owed by the flash swap in progress is token A.
+    pub flash_swap_owed_in_a: bool, // 1
+    /// The input amount owed by the flash swap in progress, transfer fee included.
+    pub flash_swap_amount_owed: u64, // 8
+
+    /// The program called after each swap with a summary of the swap, the default pubkey for none.
+    /// Only a program whitelisted by the config authority can be set.
+    pub swap_hook_program: Pubkey, // 32
+
+    /// The seconds elapsed per unit of in-range liquidity since the AiDex was initialized, as a
+    /// Q64.64 accumulator wrapping on overflow, updated along with the rewards.
+    pub seconds_per_liquidity_global_x64: u128, // 16
+
+    /// The emissions of each reward, kept apart from `reward_infos` so their layout is unchanged.
+    pub reward_emissions: [AiDexRewardEmissions; NUM_REWARDS], // 51
+
+    /// Whether the ticks and prices of the AiDex span the widened tick range. The AiDexs initialized
+    /// before it was widened keep the legacy bounds, so their full range and swaps are unchanged.
+    pub extended_tick_range: bool, // 1
+
+    /// The authorities that swapped within `last_swap_slot` and their direction, tracked by the
+    /// anti-sandwich protection. Once the table is full, the oldest entry is replaced.
+    pub slot_swap_authorities: [SlotSwapAuthority; MAX_SLOT_SWAP_AUTHORITIES], // 264
+    /// The index of `slot_swap_authorities` recording the next authority of the slot.
+    pub next_slot_swap_authority_index: u8, // 1
+    // 128 RESERVE
+}
+
+/// A fee rate applying once the volume of the previous window reaches `min_volume`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VolumeFeeTier {
+    pub min_volume: u64,
+    pub fee_rate: u16,
 }
 
+// Number of volume fee tiers supported by AiDex
+pub const MAX_VOLUME_FEE_TIERS: usize = 4;
+
+// Number of authorities whose swap direction is tracked within a slot by the anti-sandwich protection
+pub const MAX_SLOT_SWAP_AUTHORITIES: usize = 8;
+
 // Number of rewards supported by AiDex
 pub const NUM_REWARDS: usize = 3;
 
+// Version of the current layout of AiDex, raised whenever fields are appended to it
+pub const AI_DEX_POOL_VERSION: u8 = 7;
+
 /// The AiDex struct represents the state of the AiDex program.
 impl AiDexPool {
     /// The total length of the AiDex struct.
-    pub const LEN: usize = 8 + 261 + 384;
+    pub const LEN: usize = 8 + 261 + 384 + 1 + 96 + 1 + 20 + 2 + 26 + 42 + 8 + 1 + 1 + 16 + 68 + 10 + 8 + 32 + 2 + 17 + 1 + 1 + 1 + 10 + 32 + 16 + 51 + 1 + 264 + 1 + 128;
 
     /// Returns an array of references to the seeds used for program address generation.
     pub fn seeds(&self) -> [&[u8]; 6] {
@@ -160,6 +320,8 @@ impl AiDexPool {
     /// - `token_vault_a` - The vault of token A.
     /// - `token_mint_b` - The mint of token B.
     /// - `token_vault_b` - The vault of token B.
+    /// - `open_time` - The timestamp from which swaps are allowed.
+    /// - `full_range_only` - Whether positions can only be opened on the full range of ticks.
     ///
     /// # Errors
     /// This function returns an error if the token mint order is invalid or if the square root price is out of bounds.
@@ -174,6 +336,8 @@ impl AiDexPool {
         token_vault_a: Pubkey,
         token_mint_b: Pubkey,
         token_vault_b: Pubkey,
+        open_time: u64,
+        full_range_only: bool,
     ) -> Result<()> {
         // Check if the token mint order is valid
         if token_mint_a.ge(&token_mint_b) {
@@ -214,11 +378,21 @@ impl AiDexPool {
             [AiDexRewardInfo::new(ai_dex_config.config_authority);
                 NUM_REWARDS];
 
+        self.fee_rates_frozen = false;
+        self.open_time = open_time;
+        self.full_range_only = full_range_only;
+        self.extended_tick_range = true;
+        self.vault_reserves_tracked = true;
+        self.version = AI_DEX_POOL_VERSION;
+
         Ok(())
     }
 
     /// Update all reward values for the AiDex.
     ///
+    /// The seconds per liquidity and the outstanding emissions accrue up to the timestamp with the
+    /// liquidity and the emissions before the update.
+    ///
     /// # Parameters
     /// - `reward_infos` - An array of all updated ai_dex rewards
     /// - `reward_last_updated_timestamp` - The timestamp when the rewards were last updated
@@ -227,10 +401,53 @@ impl AiDexPool {
         reward_infos: [AiDexRewardInfo; NUM_REWARDS],
         reward_last_updated_timestamp: u64,
     ) {
+        self.accrue_seconds_per_liquidity(reward_last_updated_timestamp);
+        self.accrue_reward_emissions(reward_last_updated_timestamp);
         self.reward_last_updated_timestamp = reward_last_updated_timestamp;
         self.reward_infos = reward_infos;
     }
 
+    /// Accrue the seconds per liquidity elapsed since the rewards were last updated.
+    fn accrue_seconds_per_liquidity(&mut self, timestamp: u64) {
+        let curr_timestamp = self.reward_last_updated_timestamp;
+        if self.liquidity == 0 || timestamp <= curr_timestamp {
+            return;
+        }
+
+        let time_delta = u128::from(timestamp - curr_timestamp);
+        self.seconds_per_liquidity_global_x64 = self
+            .seconds_per_liquidity_global_x64
+            .wrapping_add((time_delta << Q64_RESOLUTION) / self.liquidity);
+    }
+
+    /// Accrue the reward tokens emitted to the positions since the rewards were last updated, which
+    /// remain outstanding until the positions collect them. Emissions are rounded up in favor of the
+    /// positions, and left out whenever their reward growth is zero or overflows.
+    fn accrue_reward_emissions(&mut self, timestamp: u64) {
+        let curr_timestamp = self.reward_last_updated_timestamp;
+        if self.liquidity == 0 || timestamp <= curr_timestamp {
+            return;
+        }
+
+        for index in 0..NUM_REWARDS {
+            let reward_info = self.reward_infos[index];
+            if !reward_info.initialized() {
+                continue;
+            }
+
+            let mut reward_emissions = self.reward_emissions[index];
+            let time_delta = u128::from(reward_emissions.emission_seconds(curr_timestamp, timestamp));
+            let reward_growth_delta =
+                checked_mul_div(time_delta, reward_info.emissions_per_second_x64, self.liquidity).unwrap_or(0);
+            if reward_growth_delta > 0 {
+                let emitted = checked_mul_shift_right_round_up_if(time_delta, reward_info.emissions_per_second_x64, true)
+                    .unwrap_or(u64::MAX);
+                reward_emissions.emissions_outstanding = reward_emissions.emissions_outstanding.saturating_add(emitted);
+                self.reward_emissions[index] = reward_emissions;
+            }
+        }
+    }
+
     /// Update the rewards and liquidity values for the AiDex.
     ///
     /// # Parameters
@@ -264,6 +481,40 @@ impl AiDexPool {
         Ok(())
     }
 
+    /// Propose a new reward authority at the specified AiDex reward index.
+    ///
+    /// # Parameters
+    /// - `index` - The index of the reward to update.
+    /// - `pending_authority` - The proposed authority for the reward.
+    ///
+    /// # Errors
+    /// This function returns an error if the reward index is invalid.
+    pub fn propose_reward_authority(&mut self, index: usize, pending_authority: Pubkey) -> Result<()> {
+        if index >= NUM_REWARDS {
+            return Err(ErrorCode::InvalidRewardIndexError.into());
+        }
+        self.pending_reward_authorities[index] = pending_authority;
+
+        Ok(())
+    }
+
+    /// Make the pending reward authority the reward authority at the specified AiDex reward index.
+    ///
+    /// # Parameters
+    /// - `index` - The index of the reward to update.
+    ///
+    /// # Errors
+    /// This function returns an error if the reward index is invalid.
+    pub fn accept_reward_authority(&mut self, index: usize) -> Result<()> {
+        if index >= NUM_REWARDS {
+            return Err(ErrorCode::InvalidRewardIndexError.into());
+        }
+        self.reward_infos[index].authority = self.pending_reward_authorities[index];
+        self.pending_reward_authorities[index] = Pubkey::default();
+
+        Ok(())
+    }
+
     /// Update the emissions for the specified AiDex reward index.
     ///
     /// # Parameters
@@ -271,21 +522,27 @@ impl AiDexPool {
     /// - `reward_infos` - An array of all updated ai_dex rewards.
     /// - `timestamp` - The timestamp when the emissions were last updated.
     /// - `emissions_per_second_x64` - The new emissions per second value.
+    /// - `emissions_end_timestamp` - The timestamp after which no more rewards are emitted, zero for no end.
     ///
     /// # Errors
-    /// This function returns an error if the reward index is invalid.
+    /// This function returns an error if the reward index is invalid or if the end timestamp is not in the future.
     pub fn update_emissions(
         &mut self,
         index: usize,
         reward_infos: [AiDexRewardInfo; NUM_REWARDS],
         timestamp: u64,
         emissions_per_second_x64: u128,
+        emissions_end_timestamp: u64,
     ) -> Result<()> {
         if index >= NUM_REWARDS {
             return Err(ErrorCode::InvalidRewardIndexError.into());
         }
+        if emissions_end_timestamp != 0 && emissions_end_timestamp <= timestamp {
+            return Err(ErrorCode::InvalidEmissionsEndTimestampError.into());
+        }
         self.update_rewards(reward_infos, timestamp);
         self.reward_infos[index].emissions_per_second_x64 = emissions_per_second_x64;
+        self.reward_emissions[index].emissions_end_timestamp = emissions_end_timestamp;
 
         Ok(())
     }
@@ -304,7 +561,8 @@ impl AiDexPool {
             return Err(ErrorCode::InvalidRewardIndexError.into());
         }
 
-        let lowest_index = self.reward_infos.iter().position(|r| !r.initialized())
+        let reward_infos = self.reward_infos;
+        let lowest_index = reward_infos.iter().position(|r| !r.initialized())
             .ok_or(ErrorCode::InvalidRewardIndexError)?;
 
         if lowest_index != index {
@@ -313,6 +571,7 @@ impl AiDexPool {
 
         self.reward_infos[index].mint = mint;
         self.reward_infos[index].vault = vault;
+        self.reward_emissions[index].emissions_outstanding_tracked = true;
 
         Ok(())
     }
@@ -339,6 +598,9 @@ impl AiDexPool {
         is_token_fee_in_a: bool,
         reward_last_updated_timestamp: u64,
     ) {
+        self.roll_circuit_breaker_window(reward_last_updated_timestamp);
+        self.accrue_seconds_per_liquidity(reward_last_updated_timestamp);
+        self.accrue_reward_emissions(reward_last_updated_timestamp);
         self.tick_current_index = tick_index;
         self.sqrt_price = sqrt_price;
         self.liquidity = liquidity;
@@ -361,8 +623,11 @@ impl AiDexPool {
     /// - `fee_rate` - The new fee rate value.
     ///
     /// # Errors
-    /// This function returns an error if the fee rate exceeds the maximum fee rate.
+    /// This function returns an error if the fee rates are frozen or if the fee rate exceeds the maximum fee rate.
     pub fn update_fee_rate(&mut self, fee_rate: u16) -> Result<()> {
+        if self.fee_rates_frozen {
+            return Err(ErrorCode::FeeRatesFrozenError.into());
+        }
         if fee_rate > MAX_FEE_RATE {
             return Err(ErrorCode::FeeRateExceededError.into());
         }
@@ -371,14 +636,216 @@ impl AiDexPool {
         Ok(())
     }
 
+    /// Set the fee tiers of the AiDex stepping the fee rate with the volume of the previous window.
+    ///
+    /// # Parameters
+    /// - `window_seconds` - The duration of the volume windows, zero to disable the tiers.
+    /// - `tiers` - The fee tiers, sorted by strictly increasing minimum volume.
+    ///
+    /// # Errors
+    /// This function returns an error if the fee rates are frozen, if there are too many tiers, if the
+    /// tiers are not sorted or have a zero fee rate or a fee rate above the maximum fee rate, or if
+    /// tiers are set without a window.
+    pub fn set_volume_fee_tiers(&mut self, window_seconds: u32, tiers: &[VolumeFeeTier]) -> Result<()> {
+        if self.fee_rates_frozen {
+            return Err(ErrorCode::FeeRatesFrozenError.into());
+        }
+        if tiers.len() > MAX_VOLUME_FEE_TIERS || (window_seconds == 0) != tiers.is_empty() {
+            return Err(ErrorCode::InvalidVolumeFeeTiersError.into());
+        }
+        for (i, tier) in tiers.iter().enumerate() {
+            if tier.fee_rate == 0
+                || tier.fee_rate > MAX_FEE_RATE
+                || (i > 0 && tier.min_volume <= tiers[i - 1].min_volume)
+            {
+                return Err(ErrorCode::InvalidVolumeFeeTiersError.into());
+            }
+        }
+
+        let mut min_volumes = [0; MAX_VOLUME_FEE_TIERS];
+        let mut rates = [0; MAX_VOLUME_FEE_TIERS];
+        for (i, tier) in tiers.iter().enumerate() {
+            min_volumes[i] = tier.min_volume;
+            rates[i] = tier.fee_rate;
+        }
+        self.volume_fee_window_seconds = window_seconds;
+        self.volume_fee_tier_min_volumes = min_volumes;
+        self.volume_fee_tier_rates = rates;
+        Ok(())
+    }
+
+    /// Returns the volume of the last complete window at the given timestamp.
+    fn reference_window_volume(&self, timestamp: u64) -> u64 {
+        let window_seconds = self.volume_fee_window_seconds as u64;
+        let window_end = self.volume_window_start_timestamp.saturating_add(window_seconds);
+        if timestamp < window_end {
+            self.previous_window_volume_a
+        } else if timestamp < window_end.saturating_add(window_seconds) {
+            self.window_volume_a
+        } else {
+            // No swap happened within the last complete window
+            0
+        }
+    }
+
+    /// Returns the fee rate of the volume fee tier reached by the volume of the last complete window.
+    fn volume_fee_rate(&self, timestamp: u64) -> u16 {
+        if self.volume_fee_window_seconds == 0 {
+            return self.fee_rate;
+        }
+
+        let volume = self.reference_window_volume(timestamp);
+        let min_volumes = self.volume_fee_tier_min_volumes;
+        let rates = self.volume_fee_tier_rates;
+        min_volumes
+            .iter()
+            .zip(rates.iter())
+            .filter(|(min_volume, rate)| **rate != 0 && volume >= **min_volume)
+            .last()
+            .map_or(self.fee_rate, |(_, rate)| *rate)
+    }
+
+    /// Returns the fee rate applying to a swap at the given timestamp.
+    ///
+    /// With volume fee tiers, the fee rate of the highest tier reached by the volume of the last
+    /// complete window applies, the base fee rate below the first tier. The volume of the current
+    /// window is not used, so a swap cannot move its own fee rate.
+    ///
+    /// With the adaptive fee, the fee rate grows with the volatility measured by the oracle,
+    /// within the bounds set by the fee authority.
+    ///
+    /// During the launch fee schedule, the decaying launch fee rate applies while it is higher.
+    ///
+    /// # Parameters
+    /// - `timestamp` - The timestamp of the swap.
+    /// - `volatility_ticks` - The distance between the current tick and the oracle average tick.
+    pub fn effective_fee_rate(&self, timestamp: u64, volatility_ticks: u32) -> u16 {
+        let mut fee_rate = self.volume_fee_rate(timestamp);
+        if self.adaptive_fee_window_seconds != 0 {
+            let adaptive_fee_rate = fee_rate as u64
+                + volatility_ticks as u64 * self.adaptive_fee_rate_per_tick as u64;
+            fee_rate = adaptive_fee_rate
+                .clamp(self.adaptive_fee_min_rate as u64, self.adaptive_fee_max_rate as u64) as u16;
+        }
+
+        match self.launch_fee_rate(timestamp) {
+            Some(launch_fee_rate) => fee_rate.max(launch_fee_rate),
+            None => fee_rate,
+        }
+    }
+
+    /// Returns the launch fee rate at the given timestamp, decaying linearly from the start rate
+    /// at the open time to the end rate, or `None` outside of the launch fee schedule.
+    fn launch_fee_rate(&self, timestamp: u64) -> Option<u16> {
+        let duration = self.launch_fee_duration_seconds as u64;
+        let elapsed = timestamp.saturating_sub(self.open_time);
+        if duration == 0 || elapsed >= duration {
+            return None;
+        }
+
+        let start_rate = self.launch_fee_start_rate as u64;
+        let end_rate = self.launch_fee_end_rate as u64;
+        Some((start_rate - (start_rate - end_rate) * elapsed / duration) as u16)
+    }
+
+    /// Set the launch fee schedule of the AiDex. Only possible before the AiDex opens.
+    ///
+    /// # Parameters
+    /// - `start_fee_rate` - The fee rate at the open time.
+    /// - `end_fee_rate` - The fee rate at the end of the schedule.
+    /// - `duration_seconds` - The duration of the schedule after the open time, zero to disable it.
+    /// - `timestamp` - The current timestamp.
+    ///
+    /// # Errors
+    /// This function returns an error if the AiDex is already open, if the fee rates are frozen,
+    /// or if the start fee rate exceeds the maximum fee rate or is lower than the end fee rate.
+    pub fn set_launch_fee(
+        &mut self,
+        start_fee_rate: u16,
+        end_fee_rate: u16,
+        duration_seconds: u32,
+        timestamp: u64,
+    ) -> Result<()> {
+        if self.is_open(timestamp) {
+            return Err(ErrorCode::PoolAlreadyOpenError.into());
+        }
+        if self.fee_rates_frozen {
+            return Err(ErrorCode::FeeRatesFrozenError.into());
+        }
+        if duration_seconds != 0 && (start_fee_rate > MAX_FEE_RATE || end_fee_rate > start_fee_rate) {
+            return Err(ErrorCode::InvalidLaunchFeeError.into());
+        }
+
+        self.launch_fee_start_rate = start_fee_rate;
+        self.launch_fee_end_rate = end_fee_rate;
+        self.launch_fee_duration_seconds = duration_seconds;
+        Ok(())
+    }
+
+    /// Set the adaptive fee of the AiDex, scaling the fee rate with the volatility measured by its oracle.
+    ///
+    /// # Parameters
+    /// - `window_seconds` - The oracle window over which the volatility is measured, zero to disable the adaptive fee.
+    /// - `fee_rate_per_tick` - The fee rate added per tick of volatility.
+    /// - `min_fee_rate` - The lower bound of the adaptive fee rate.
+    /// - `max_fee_rate` - The upper bound of the adaptive fee rate.
+    ///
+    /// # Errors
+    /// This function returns an error if the fee rates are frozen, or if the bounds are inverted or
+    /// exceed the maximum fee rate.
+    pub fn set_adaptive_fee(
+        &mut self,
+        window_seconds: u32,
+        fee_rate_per_tick: u16,
+        min_fee_rate: u16,
+        max_fee_rate: u16,
+    ) -> Result<()> {
+        if self.fee_rates_frozen {
+            return Err(ErrorCode::FeeRatesFrozenError.into());
+        }
+        if window_seconds != 0 && (min_fee_rate > max_fee_rate || max_fee_rate > MAX_FEE_RATE) {
+            return Err(ErrorCode::InvalidAdaptiveFeeError.into());
+        }
+
+        self.adaptive_fee_window_seconds = window_seconds;
+        self.adaptive_fee_rate_per_tick = fee_rate_per_tick;
+        self.adaptive_fee_min_rate = min_fee_rate;
+        self.adaptive_fee_max_rate = max_fee_rate;
+        Ok(())
+    }
+
+    /// Record the volume of a swap in the current volume window, starting a new window if the
+    /// current one has ended.
+    ///
+    /// # Parameters
+    /// - `volume_a` - The amount of token A swapped in or out.
+    /// - `timestamp` - The timestamp of the swap.
+    pub fn record_window_volume(&mut self, volume_a: u64, timestamp: u64) {
+        if self.volume_fee_window_seconds == 0 {
+            return;
+        }
+        let window_end = self
+            .volume_window_start_timestamp
+            .saturating_add(self.volume_fee_window_seconds as u64);
+        if timestamp >= window_end {
+            self.previous_window_volume_a = self.reference_window_volume(timestamp);
+            self.volume_window_start_timestamp = timestamp;
+            self.window_volume_a = 0;
+        }
+        self.window_volume_a = self.window_volume_a.saturating_add(volume_a);
+    }
+
     /// Update the protocol fee rate for the AiDex.
     ///
     /// # Parameters
     /// - `protocol_fee_rate` - The new protocol fee rate value.
     ///
     /// # Errors
-    /// This function returns an error if the protocol fee rate exceeds the maximum protocol fee rate.
+    /// This function returns an error if the fee rates are frozen or if the protocol fee rate exceeds the maximum protocol fee rate.
     pub fn update_protocol_fee_rate(&mut self, protocol_fee_rate: u16) -> Result<()> {
+        if self.fee_rates_frozen {
+            return Err(ErrorCode::FeeRatesFrozenError.into());
+        }
         if protocol_fee_rate > MAX_PROTOCOL_FEE_RATE {
             return Err(ErrorCode::ProtocolFeeRateExceededError.into());
         }
@@ -387,18 +854,595 @@ impl AiDexPool {
         Ok(())
     }
 
+    /// Freeze the fee rate and protocol fee rate of the AiDex.
+    ///
+    /// # Errors
+    /// This function returns an error if the fee rates are already frozen.
+    pub fn freeze_fee_rates(&mut self) -> Result<()> {
+        if self.fee_rates_frozen {
+            return Err(ErrorCode::FeeRatesFrozenError.into());
+        }
+        self.fee_rates_frozen = true;
+
+        Ok(())
+    }
+
+    /// Pause or unpause the swaps and liquidity increases of the AiDex.
+    ///
+    /// # Parameters
+    /// - `paused` - Whether the AiDex is paused.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Returns true if swaps and liquidity increases are disallowed, because the AiDex is paused
+    /// or in emergency mode.
+    pub fn is_paused(&self) -> bool {
+        self.paused || self.emergency_mode
+    }
+
+    /// Returns true if positions can only be opened on the full range of ticks, because the AiDex
+    /// was initialized from a full range only fee tier or its tick spacing is at least 2^15.
+    pub fn is_full_range_only(&self) -> bool {
+        self.full_range_only || self.tick_spacing >= FULL_RANGE_ONLY_TICK_SPACING_THRESHOLD
+    }
+
+    /// Returns the min & max tick index of the AiDex, the legacy ones unless it was initialized
+    /// after the tick range was widened.
+    pub fn tick_index_bounds(&self) -> (i32, i32) {
+        Tick::tick_index_bounds(self.extended_tick_range)
+    }
+
+    /// Returns the min & max sqrt price of the AiDex, the legacy ones unless it was initialized
+    /// after the tick range was widened.
+    pub fn sqrt_price_bounds(&self) -> (u128, u128) {
+        if self.extended_tick_range {
+            (MIN_SQRT_PRICE_X64, MAX_SQRT_PRICE_X64)
+        } else {
+            (LEGACY_MIN_SQRT_PRICE_X64, LEGACY_MAX_SQRT_PRICE_X64)
+        }
+    }
+
+    /// Returns the tick indexes of the full range positions of the AiDex.
+    pub fn full_range_indexes(&self) -> (i32, i32) {
+        Tick::full_range_indexes(self.tick_spacing, self.extended_tick_range)
+    }
+
+    /// Records the input owed by a flash swap, whose output has been sent before its input is paid.
+    ///
+    /// # Parameters
+    /// - `amount_owed` - The input amount owed, transfer fee included.
+    /// - `owed_in_a` - Whether the input is token A.
+    ///
+    /// # Errors
+    /// This function returns an error if another flash swap awaits repayment, or if the pool has a
+    /// swap hook, which flash swaps do not call.
+    pub fn start_flash_swap(&mut self, amount_owed: u64, owed_in_a: bool) -> Result<()> {
+        self.check_swap_hook_not_set()?;
+        if self.flash_swap_in_progress {
+            return Err(ErrorCode::FlashSwapInProgressError.into());
+        }
+        self.flash_swap_in_progress = true;
+        self.flash_swap_owed_in_a = owed_in_a;
+        self.flash_swap_amount_owed = amount_owed;
+
+        Ok(())
+    }
+
+    /// Clears the flash swap in progress, once its input is being repaid.
+    ///
+    /// # Returns
+    /// The input amount owed, transfer fee included, and whether the input is token A.
+    ///
+    /// # Errors
+    /// This function returns an error if no flash swap awaits repayment.
+    pub fn finish_flash_swap(&mut self) -> Result<(u64, bool)> {
+        if !self.flash_swap_in_progress {
+            return Err(ErrorCode::NoFlashSwapInProgressError.into());
+        }
+        let owed = (self.flash_swap_amount_owed, self.flash_swap_owed_in_a);
+        self.flash_swap_in_progress = false;
+        self.flash_swap_owed_in_a = false;
+        self.flash_swap_amount_owed = 0;
+
+        Ok(owed)
+    }
+
+    /// Sets the program called after each swap, the default pubkey to remove it.
+    ///
+    /// # Parameters
+    /// - `swap_hook_program` - The whitelisted swap hook program.
+    pub fn set_swap_hook_program(&mut self, swap_hook_program: Pubkey) {
+        self.swap_hook_program = swap_hook_program;
+    }
+
+    /// Returns the program called after each swap, if any.
+    pub fn swap_hook_program(&self) -> Option<Pubkey> {
+        let swap_hook_program = self.swap_hook_program;
+        if swap_hook_program == Pubkey::default() {
+            None
+        } else {
+            Some(swap_hook_program)
+        }
+    }
+
+    /// Checks that no swap hook is set, for the swaps that do not call it.
+    ///
+    /// # Errors
+    /// This function returns `SwapHookNotSupportedError` if the pool has a swap hook program.
+    pub fn check_swap_hook_not_set(&self) -> Result<()> {
+        if self.swap_hook_program().is_some() {
+            return Err(ErrorCode::SwapHookNotSupportedError.into());
+        }
+        Ok(())
+    }
+
+    /// Put the AiDex in emergency mode for good. The ticks stop tracking the liquidity of the
+    /// positions withdrawn in emergency, so swaps and liquidity increases can never resume.
+    pub fn enable_emergency_mode(&mut self) {
+        self.emergency_mode = true;
+    }
+
+    /// Set the circuit breaker of the AiDex, bounding the price move within a time window.
+    ///
+    /// # Parameters
+    /// - `max_tick_move` - The maximum number of ticks the price can move within a window, zero to disable.
+    /// - `window_seconds` - The length of a window in seconds.
+    ///
+    /// # Errors
+    /// This function returns an error if the circuit breaker is enabled with an empty window.
+    pub fn set_circuit_breaker(&mut self, max_tick_move: u32, window_seconds: u32) -> Result<()> {
+        if max_tick_move != 0 && window_seconds == 0 {
+            return Err(ErrorCode::InvalidCircuitBreakerError.into());
+        }
+        self.circuit_breaker_max_tick_move = max_tick_move;
+        self.circuit_breaker_window_seconds = window_seconds;
+        // Start a new window with the next swap
+        self.circuit_breaker_window_start_timestamp = 0;
+        self.circuit_breaker_window_start_tick = self.tick_current_index;
+
+        Ok(())
+    }
+
+    /// Returns true if the current circuit breaker window has ended at the given timestamp.
+    fn circuit_breaker_window_ended(&self, timestamp: u64) -> bool {
+        timestamp
+            >= self
+                .circuit_breaker_window_start_timestamp
+                .saturating_add(self.circuit_breaker_window_seconds as u64)
+    }
+
+    /// Check that a swap ending at the given tick index does not trip the circuit breaker.
+    ///
+    /// The move is measured from the tick index at the start of the current window, or from the
+    /// current tick index if the window has ended, since the swap starts a new window.
+    ///
+    /// # Parameters
+    /// - `next_tick_index` - The tick index after the swap.
+    /// - `timestamp` - The timestamp of the swap.
+    ///
+    /// # Errors
+    /// This function returns an error if the price moves more than allowed within the window.
+    pub fn check_circuit_breaker(&self, next_tick_index: i32, timestamp: u64) -> Result<()> {
+        if self.circuit_breaker_max_tick_move == 0 {
+            return Ok(());
+        }
+
+        let reference_tick_index = if self.circuit_breaker_window_ended(timestamp) {
+            self.tick_current_index
+        } else {
+            self.circuit_breaker_window_start_tick
+        };
+        if next_tick_index.abs_diff(reference_tick_index) > self.circuit_breaker_max_tick_move {
+            return Err(ErrorCode::CircuitBreakerTrippedError.into());
+        }
+
+        Ok(())
+    }
+
+    /// Start a new circuit breaker window from the current tick index if the current one has ended.
+    fn roll_circuit_breaker_window(&mut self, timestamp: u64) {
+        if self.circuit_breaker_max_tick_move != 0 && self.circuit_breaker_window_ended(timestamp) {
+            self.circuit_breaker_window_start_timestamp = timestamp;
+            self.circuit_breaker_window_start_tick = self.tick_current_index;
+        }
+    }
+
+    /// Set the maximum price impact of a single swap of the AiDex.
+    ///
+    /// # Parameters
+    /// - `max_price_impact_bps` - The maximum price impact in basis points, zero to disable the cap.
+    pub fn set_max_price_impact_bps(&mut self, max_price_impact_bps: u16) {
+        self.max_price_impact_bps = max_price_impact_bps;
+    }
+
+    /// Check that a swap ending at the given square root price does not exceed the maximum price impact.
+    ///
+    /// # Parameters
+    /// - `next_sqrt_price` - The square root price after the swap.
+    ///
+    /// # Errors
+    /// This function returns an error if the price impact of the swap exceeds the maximum price impact.
+    pub fn check_price_impact(&self, next_sqrt_price: u128) -> Result<()> {
+        if self.max_price_impact_bps == 0 {
+            return Ok(());
+        }
+        if get_price_impact_bps(self.sqrt_price, next_sqrt_price) > self.max_price_impact_bps as u128 {
+            return Err(ErrorCode::PriceImpactExceededError.into());
+        }
+
+        Ok(())
+    }
+
+    /// Set the maximum share of a vault balance that swaps can take out within a single slot.
+    ///
+    /// # Parameters
+    /// - `max_slot_volume_bps` - The maximum share in basis points, zero to disable the cap.
+    pub fn set_max_slot_volume_bps(&mut self, max_slot_volume_bps: u16) {
+        self.max_slot_volume_bps = max_slot_volume_bps;
+    }
+
+    /// Record the amount a swap takes out of a vault within the given slot, and check it against
+    /// the per-slot volume cap.
+    ///
+    /// The cap is relative to the vault balance at the start of the slot, which is the current
+    /// balance plus the amount already swapped out within the slot.
+    ///
+    /// # Parameters
+    /// - `slot` - The slot of the swap.
+    /// - `amount_out` - The amount swapped out of the vault.
+    /// - `vault_out_balance` - The balance of the output vault before the swap.
+    /// - `a_to_b` - The direction of the swap.
+    ///
+    /// # Errors
+    /// This function returns an error if the volume swapped out within the slot exceeds the cap.
+    pub fn record_slot_volume(
+        &mut self,
+        slot: u64,
+        amount_out: u64,
+        vault_out_balance: u64,
+        a_to_b: bool,
+    ) -> Result<()> {
+        if slot != self.volume_slot {
+            self.volume_slot = slot;
+            self.slot_volume_out_a = 0;
+            self.slot_volume_out_b = 0;
+        }
+
+        let previous_volume_out = if a_to_b { self.slot_volume_out_b } else { self.slot_volume_out_a };
+        let volume_out = previous_volume_out.saturating_add(amount_out);
+        if a_to_b {
+            self.slot_volume_out_b = volume_out;
+        } else {
+            self.slot_volume_out_a = volume_out;
+        }
+
+        if self.max_slot_volume_bps != 0 {
+            let slot_start_balance = vault_out_balance as u128 + previous_volume_out as u128;
+            if volume_out as u128 * 10_000 > slot_start_balance * self.max_slot_volume_bps as u128 {
+                return Err(ErrorCode::SlotVolumeExceededError.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Enable or disable the anti-sandwich protection of the AiDex.
+    ///
+    /// # Parameters
+    /// - `enabled` - Whether the anti-sandwich protection is enabled.
+    pub fn set_anti_sandwich_enabled(&mut self, enabled: bool) {
+        self.anti_sandwich_enabled = enabled;
+    }
+
+    /// Record the slot, the authority and the direction of a swap, rejecting it if the same
+    /// authority swapped in the opposite direction earlier within the same slot.
+    ///
+    /// The direction of each authority is tracked in `slot_swap_authorities` for the current slot,
+    /// so the swaps of other authorities in between, such as the victim of a sandwich, do not hide
+    /// an earlier swap. The table is reset on a new slot.
+    ///
+    /// # Parameters
+    /// - `slot` - The slot of the swap.
+    /// - `authority` - The authority of the swap.
+    /// - `a_to_b` - The direction of the swap.
+    ///
+    /// # Errors
+    /// This function returns an error if the anti-sandwich protection is enabled and the same
+    /// authority swapped in the opposite direction within the slot.
+    pub fn record_swap_authority(&mut self, slot: u64, authority: Pubkey, a_to_b: bool) -> Result<()> {
+        if !self.anti_sandwich_enabled {
+            return Ok(());
+        }
+        if self.last_swap_slot != slot {
+            self.slot_swap_authorities = [SlotSwapAuthority::default(); MAX_SLOT_SWAP_AUTHORITIES];
+            self.next_slot_swap_authority_index = 0;
+        }
+
+        let recorded = self
+            .slot_swap_authorities
+            .iter()
+            .find(|slot_swap_authority| slot_swap_authority.authority == authority);
+        match recorded {
+            Some(recorded) if recorded.a_to_b != a_to_b => {
+                return Err(ErrorCode::SandwichSwapError.into());
+            }
+            Some(_) => {}
+            None => {
+                let index = self.next_slot_swap_authority_index as usize % MAX_SLOT_SWAP_AUTHORITIES;
+                self.slot_swap_authorities[index] = SlotSwapAuthority { authority, a_to_b };
+                self.next_slot_swap_authority_index = ((index + 1) % MAX_SLOT_SWAP_AUTHORITIES) as u8;
+            }
+        }
+
+        self.last_swap_slot = slot;
+        self.last_swap_authority = authority;
+        self.last_swap_a_to_b = a_to_b;
+
+        Ok(())
+    }
+
+    /// Returns true if the authority is the fee manager of the AiDex.
+    pub fn is_fee_manager(&self, authority: &Pubkey) -> bool {
+        *authority != Pubkey::default() && *authority == self.fee_manager
+    }
+
+    /// Set the fee manager of the AiDex, or the default pubkey to remove it.
+    ///
+    /// # Parameters
+    /// - `fee_manager` - The authority allowed to set the fee rate of this AiDex only.
+    pub fn set_fee_manager(&mut self, fee_manager: Pubkey) {
+        self.fee_manager = fee_manager;
+    }
+
+    /// Set the multiplier of the rewards of locked positions. The boosted rewards are paid from
+    /// the reward vaults, which the reward authorities must fund accordingly.
+    ///
+    /// # Parameters
+    /// - `locked_reward_boost_bps` - The reward boost in basis points, zero to disable it.
+    ///
+    /// # Errors
+    /// This function returns an error if the boost is below 1x or above `MAX_REWARD_BOOST_BPS`.
+    pub fn set_locked_reward_boost(&mut self, locked_reward_boost_bps: u16) -> Result<()> {
+        if locked_reward_boost_bps != 0
+            && !(REWARD_BOOST_BPS_DENOMINATOR..=MAX_REWARD_BOOST_BPS).contains(&locked_reward_boost_bps)
+        {
+            return Err(ErrorCode::InvalidRewardBoostError.into());
+        }
+        self.locked_reward_boost_bps = locked_reward_boost_bps;
+
+        Ok(())
+    }
+
+    /// Record reward tokens collected by a position, which are no longer outstanding.
+    ///
+    /// # Parameters
+    /// - `index` - The index of the reward.
+    /// - `amount` - The amount of reward tokens collected.
+    pub fn record_reward_collected(&mut self, index: usize, amount: u64) {
+        let emissions_outstanding = self.reward_emissions[index].emissions_outstanding;
+        self.reward_emissions[index].emissions_outstanding = emissions_outstanding.saturating_sub(amount);
+    }
+
+    /// Record reward tokens paid into the vault on behalf of a position, which are outstanding
+    /// until the position collects them.
+    ///
+    /// # Parameters
+    /// - `index` - The index of the reward.
+    /// - `amount` - The amount of reward tokens paid in.
+    pub fn record_reward_deposited(&mut self, index: usize, amount: u64) {
+        let emissions_outstanding = self.reward_emissions[index].emissions_outstanding;
+        self.reward_emissions[index].emissions_outstanding = emissions_outstanding.saturating_add(amount);
+    }
+
+    /// Returns the amount of reward tokens in the vault beyond what the positions may still
+    /// collect, once the emissions of the reward have ended. The outstanding emissions are scaled
+    /// by the boost of locked positions, since boosted positions collect more than emitted.
+    ///
+    /// # Parameters
+    /// - `index` - The index of the reward.
+    /// - `vault_amount` - The balance of the reward vault.
+    /// - `timestamp` - The current timestamp, to which the rewards must be updated.
+    ///
+    /// # Errors
+    /// This function returns an error if the reward index is invalid, if the outstanding emissions
+    /// of the reward are not tracked or if its emissions have no end or have not ended yet.
+    pub fn reward_surplus(&self, index: usize, vault_amount: u64, timestamp: u64) -> Result<u64> {
+        if index >= NUM_REWARDS {
+            return Err(ErrorCode::InvalidRewardIndexError.into());
+        }
+        let reward_emissions = self.reward_emissions[index];
+        if !reward_emissions.emissions_outstanding_tracked {
+            return Err(ErrorCode::RewardEmissionsNotTrackedError.into());
+        }
+        let emissions_end_timestamp = reward_emissions.emissions_end_timestamp;
+        if emissions_end_timestamp == 0 || timestamp < emissions_end_timestamp {
+            return Err(ErrorCode::RewardEmissionsNotEndedError.into());
+        }
+
+        let boost_bps = self.locked_reward_boost_bps.max(REWARD_BOOST_BPS_DENOMINATOR);
+        let emissions_owed = u128::from(reward_emissions.emissions_outstanding) * u128::from(boost_bps)
+            / u128::from(REWARD_BOOST_BPS_DENOMINATOR);
+
+        Ok(u128::from(vault_amount).saturating_sub(emissions_owed) as u64)
+    }
+
+    /// Account for a transfer of the program into or out of a token vault of the AiDex. Transfers
+    /// of other token accounts are ignored.
+    ///
+    /// # Parameters
+    /// - `token_vault` - The token account of the transfer.
+    /// - `amount_before` - The balance of the token account before the transfer.
+    /// - `amount_after` - The balance of the token account after the transfer.
+    pub fn track_vault_balance(&mut self, token_vault: &Pubkey, amount_before: u64, amount_after: u64) {
+        let track = |reserve: u64| {
+            reserve
+                .saturating_add(amount_after.saturating_sub(amount_before))
+                .saturating_sub(amount_before.saturating_sub(amount_after))
+        };
+        if *token_vault == self.token_vault_a {
+            self.vault_reserve_a = track(self.vault_reserve_a);
+        } else if *token_vault == self.token_vault_b {
+            self.vault_reserve_b = track(self.vault_reserve_b);
+        }
+    }
+
+    /// Returns the balances of the token vaults in excess of their accounted reserves, which were
+    /// sent to the vaults outside of the program. The reserves are raised to the balances, so the
+    /// transfers of the surplus out of the vaults leave them unchanged.
+    ///
+    /// # Parameters
+    /// - `vault_amount_a` - The balance of token vault A.
+    /// - `vault_amount_b` - The balance of token vault B.
+    ///
+    /// # Errors
+    /// This function returns an error if the vault reserves of the AiDex are not tracked.
+    pub fn take_vault_surplus(&mut self, vault_amount_a: u64, vault_amount_b: u64) -> Result<(u64, u64)> {
+        if !self.vault_reserves_tracked {
+            return Err(ErrorCode::VaultReservesNotTrackedError.into());
+        }
+        let surplus_a = vault_amount_a.saturating_sub(self.vault_reserve_a);
+        let surplus_b = vault_amount_b.saturating_sub(self.vault_reserve_b);
+        self.vault_reserve_a = self.vault_reserve_a.max(vault_amount_a);
+        self.vault_reserve_b = self.vault_reserve_b.max(vault_amount_b);
+
+        Ok((surplus_a, surplus_b))
+    }
+
+    /// Upgrade the AiDex to the current layout, once its account has been grown to `LEN`.
+    ///
+    /// Fields are only ever appended to the layout, after `reward_infos` or in the reserved space,
+    /// so the fields of past versions keep their offsets. The fields appended since the version of
+    /// the AiDex read as zero, which disables the features they configure. The vault reserves stay
+    /// untracked, since the transfers made before the migration were not accounted for.
+    ///
+    /// # Returns
+    /// The version of the AiDex before the migration.
+    pub fn migrate(&mut self) -> u8 {
+        let old_version = self.version;
+        self.version = AI_DEX_POOL_VERSION;
+        old_version
+    }
+
+    /// Returns true if swaps are allowed at the given timestamp.
+    pub fn is_open(&self, timestamp: u64) -> bool {
+        timestamp >= self.open_time
+    }
+
+    /// Set the timestamp from which swaps are allowed. Only possible before the AiDex opens.
+    ///
+    /// # Parameters
+    /// - `open_time` - The timestamp from which swaps are allowed.
+    /// - `timestamp` - The current timestamp.
+    ///
+    /// # Errors
+    /// This function returns an error if the AiDex is already open.
+    pub fn set_open_time(&mut self, open_time: u64, timestamp: u64) -> Result<()> {
+        if self.is_open(timestamp) {
+            return Err(ErrorCode::PoolAlreadyOpenError.into());
+        }
+        self.open_time = open_time;
+
+        Ok(())
+    }
+
+    /// Returns true if the allowlist of the given kind is enabled.
+    pub fn allowlist_enabled(&self, kind: PoolAllowlistKind) -> bool {
+        match kind {
+            PoolAllowlistKind::Liquidity => self.liquidity_allowlist_enabled,
+            PoolAllowlistKind::Swap => self.swap_allowlist_enabled,
+        }
+    }
+
+    /// Enable or disable the allowlist of the given kind.
+    ///
+    /// # Parameters
+    /// - `kind` - The kind of the allowlist.
+    /// - `enabled` - Whether the allowlist is enabled.
+    pub fn set_allowlist_enabled(&mut self, kind: PoolAllowlistKind, enabled: bool) {
+        match kind {
+            PoolAllowlistKind::Liquidity => self.liquidity_allowlist_enabled = enabled,
+            PoolAllowlistKind::Swap => self.swap_allowlist_enabled = enabled,
+        }
+    }
+
+    /// Add amounts to the protocol fees owed by the AiDex, such as fees left in a position without owner.
+    ///
+    /// # Parameters
+    /// - `amount_a` - The amount of token A to add.
+    /// - `amount_b` - The amount of token B to add.
+    ///
+    /// # Errors
+    /// This function returns an error if a protocol fee owed overflows.
+    pub fn add_protocol_fees_owed(&mut self, amount_a: u64, amount_b: u64) -> Result<()> {
+        self.protocol_fee_owed_a = self
+            .protocol_fee_owed_a
+            .checked_add(amount_a)
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        self.protocol_fee_owed_b = self
+            .protocol_fee_owed_b
+            .checked_add(amount_b)
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        Ok(())
+    }
+
     /// Reset the protocol fees owed by the AiDex.
     pub fn reset_protocol_fees_owed(&mut self) {
         self.protocol_fee_owed_a = 0;
         self.protocol_fee_owed_b = 0;
     }
+
+    /// Adds the referral fee of a swap to the referral fees owed by the AiDex.
+    ///
+    /// # Parameters
+    /// - `referral_fee` - The referral fee, in the input token of the swap.
+    /// - `is_token_fee_in_a` - Whether the fee is in token A.
+    ///
+    /// # Errors
+    /// This function returns an error if the referral fee owed overflows.
+    pub fn accrue_referral_fee(&mut self, referral_fee: u64, is_token_fee_in_a: bool) -> Result<()> {
+        if is_token_fee_in_a {
+            self.referral_fee_owed_a = self
+                .referral_fee_owed_a
+                .checked_add(referral_fee)
+                .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        } else {
+            self.referral_fee_owed_b = self
+                .referral_fee_owed_b
+                .checked_add(referral_fee)
+                .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        }
+        Ok(())
+    }
+
+    /// Removes collected referral fees from the referral fees owed by the AiDex.
+    ///
+    /// # Parameters
+    /// - `amount_a` - The collected referral fees of token A.
+    /// - `amount_b` - The collected referral fees of token B.
+    ///
+    /// # Errors
+    /// This function returns an error if more referral fees are collected than owed.
+    pub fn collect_referral_fees(&mut self, amount_a: u64, amount_b: u64) -> Result<()> {
+        self.referral_fee_owed_a = self
+            .referral_fee_owed_a
+            .checked_sub(amount_a)
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        self.referral_fee_owed_b = self
+            .referral_fee_owed_b
+            .checked_sub(amount_b)
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        Ok(())
+    }
 }
 
 /// Stores the state relevant for tracking liquidity mining rewards at the `AiDex` level.
 /// These values are used in conjunction with `PositionRewardInfo`, `Tick.reward_growths_outside`,
 /// and `AiDex.reward_last_updated_timestamp` to determine how many rewards are earned by open
 /// positions.
-#[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Default, Debug, PartialEq)]
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+#[derive(Default, Debug, PartialEq)]
 pub struct AiDexRewardInfo {
     /// Reward token mint.
     pub mint: Pubkey,
@@ -413,6 +1457,36 @@ pub struct AiDexRewardInfo {
     pub growth_global_x64: u128,
 }
 
+// The struct is packed, so its fields are copied out instead of being borrowed by a derive.
+impl AnchorSerialize for AiDexRewardInfo {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let AiDexRewardInfo {
+            mint,
+            vault,
+            authority,
+            emissions_per_second_x64,
+            growth_global_x64,
+        } = *self;
+        mint.serialize(writer)?;
+        vault.serialize(writer)?;
+        authority.serialize(writer)?;
+        emissions_per_second_x64.serialize(writer)?;
+        growth_global_x64.serialize(writer)
+    }
+}
+
+impl AnchorDeserialize for AiDexRewardInfo {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        Ok(AiDexRewardInfo {
+            mint: Pubkey::deserialize_reader(reader)?,
+            vault: Pubkey::deserialize_reader(reader)?,
+            authority: Pubkey::deserialize_reader(reader)?,
+            emissions_per_second_x64: u128::deserialize_reader(reader)?,
+            growth_global_x64: u128::deserialize_reader(reader)?,
+        })
+    }
+}
+
 impl AiDexRewardInfo {
     /// Creates a new `AiDexRewardInfo` with the authority set
     pub fn new(authority: Pubkey) -> Self {
@@ -440,6 +1514,44 @@ impl AiDexRewardInfo {
     }
 }
 
+/// Stores the emissions schedule of a reward at the `AiDex` level, next to its `AiDexRewardInfo`,
+/// along with the reward tokens emitted to the positions and not collected yet.
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+#[derive(Default, Debug, PartialEq)]
+pub struct AiDexRewardEmissions {
+    /// Timestamp after which no more rewards are emitted, zero for emissions without end.
+    pub emissions_end_timestamp: u64,
+    /// Amount of reward tokens emitted to the positions and not collected yet, rounded up.
+    pub emissions_outstanding: u64,
+    /// Whether the outstanding emissions have been tracked since the reward was initialized.
+    /// Rewards initialized before the pool was migrated are not tracked.
+    pub emissions_outstanding_tracked: bool,
+}
+
+impl AiDexRewardEmissions {
+    /// Returns the number of seconds between the two timestamps during which rewards are emitted.
+    pub fn emission_seconds(&self, curr_timestamp: u64, next_timestamp: u64) -> u64 {
+        let end_timestamp = match self.emissions_end_timestamp {
+            0 => next_timestamp,
+            emissions_end_timestamp => next_timestamp.min(emissions_end_timestamp),
+        };
+        end_timestamp.saturating_sub(curr_timestamp)
+    }
+}
+
+/// The direction in which an authority swapped within the slot tracked by the anti-sandwich
+/// protection of the `AiDex`.
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+#[derive(Default, Debug, PartialEq)]
+pub struct SlotSwapAuthority {
+    /// The authority of the swap, the default pubkey for an unused entry.
+    pub authority: Pubkey,
+    /// The direction of the swap.
+    pub a_to_b: bool,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Copy)]
 pub struct AiDexBumps {
     pub ai_dex_bump: u8,
@@ -458,9 +1570,605 @@ fn test_ai_dex_reward_info_initialized() {
     assert_eq!(reward_info.initialized(), true);
 }
 
+#[test]
+fn test_ai_dex_migrate() {
+    let mut ai_dex = AiDexPool::default();
+    assert_eq!(ai_dex.migrate(), 0);
+    assert_eq!({ ai_dex.version }, AI_DEX_POOL_VERSION);
+    assert!(!ai_dex.vault_reserves_tracked);
+    assert_eq!(ai_dex.migrate(), AI_DEX_POOL_VERSION);
+}
+
+#[test]
+fn test_ai_dex_migrate_unversioned_layout() {
+    use anchor_lang::Discriminator;
+
+    // A pool of the layout predating versioning, which ends with the reward infos
+    let ai_dex_config = Pubkey::new_unique();
+    let token_mint_a = Pubkey::new_unique();
+    let token_mint_b = Pubkey::new_unique();
+    let token_vault_a = Pubkey::new_unique();
+    let token_vault_b = Pubkey::new_unique();
+    let reward_mints = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+    let reward_vaults = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+    let reward_authority = Pubkey::new_unique();
+
+    let mut data = AiDexPool::discriminator().to_vec();
+    data.extend_from_slice(ai_dex_config.as_ref());
+    data.push(254);
+    data.extend_from_slice(&64u16.to_le_bytes());
+    data.extend_from_slice(&64u16.to_le_bytes());
+    data.extend_from_slice(&3000u16.to_le_bytes());
+    data.extend_from_slice(&300u16.to_le_bytes());
+    data.extend_from_slice(&1_000_000u128.to_le_bytes());
+    data.extend_from_slice(&(1u128 << 64).to_le_bytes());
+    data.extend_from_slice(&(-128i32).to_le_bytes());
+    data.extend_from_slice(&10u64.to_le_bytes());
+    data.extend_from_slice(&20u64.to_le_bytes());
+    data.extend_from_slice(token_mint_a.as_ref());
+    data.extend_from_slice(token_mint_b.as_ref());
+    data.extend_from_slice(token_vault_a.as_ref());
+    data.extend_from_slice(token_vault_b.as_ref());
+    data.extend_from_slice(&(3u128 << 64).to_le_bytes());
+    data.extend_from_slice(&(5u128 << 64).to_le_bytes());
+    data.extend_from_slice(&1_700_000_000u64.to_le_bytes());
+    for i in 0..NUM_REWARDS {
+        data.extend_from_slice(reward_mints[i].as_ref());
+        data.extend_from_slice(reward_vaults[i].as_ref());
+        data.extend_from_slice(reward_authority.as_ref());
+        data.extend_from_slice(&(1u128 << 64).to_le_bytes());
+        data.extend_from_slice(&((i as u128 + 1) << 64).to_le_bytes());
+    }
+    assert_eq!(data.len(), 8 + 261 + 384);
+
+    // `migrate_pool` grows the account to the current size, zeroing the added bytes
+    data.resize(AiDexPool::LEN, 0);
+    let ai_dex: &mut AiDexPool = bytemuck::from_bytes_mut(&mut data[8..8 + std::mem::size_of::<AiDexPool>()]);
+    assert_eq!(ai_dex.migrate(), 0);
+
+    assert_eq!(ai_dex.ai_dex_config, ai_dex_config);
+    assert_eq!(ai_dex.ai_dex_bump, [254]);
+    assert_eq!({ ai_dex.tick_spacing }, 64);
+    assert_eq!({ ai_dex.fee_rate }, 3000);
+    assert_eq!({ ai_dex.protocol_fee_rate }, 300);
+    assert_eq!({ ai_dex.liquidity }, 1_000_000);
+    assert_eq!({ ai_dex.sqrt_price }, 1 << 64);
+    assert_eq!({ ai_dex.tick_current_index }, -128);
+    assert_eq!({ ai_dex.protocol_fee_owed_b }, 20);
+    assert_eq!(ai_dex.token_mint_b, token_mint_b);
+    assert_eq!(ai_dex.token_vault_b, token_vault_b);
+    assert_eq!({ ai_dex.fee_growth_global_a }, 3 << 64);
+    assert_eq!({ ai_dex.fee_growth_global_b }, 5 << 64);
+    assert_eq!({ ai_dex.reward_last_updated_timestamp }, 1_700_000_000);
+    for i in 0..NUM_REWARDS {
+        assert_eq!(ai_dex.reward_infos[i].mint, reward_mints[i]);
+        assert_eq!(ai_dex.reward_infos[i].vault, reward_vaults[i]);
+        assert_eq!(ai_dex.reward_infos[i].authority, reward_authority);
+        assert_eq!({ ai_dex.reward_infos[i].growth_global_x64 }, (i as u128 + 1) << 64);
+    }
+
+    // The appended fields read as zero
+    assert_eq!({ ai_dex.version }, AI_DEX_POOL_VERSION);
+    assert_eq!(ai_dex.reward_emissions, [AiDexRewardEmissions::default(); NUM_REWARDS]);
+    assert!(!ai_dex.vault_reserves_tracked);
+    assert_eq!({ ai_dex.seconds_per_liquidity_global_x64 }, 0);
+    assert!(!ai_dex.extended_tick_range);
+    assert_eq!(ai_dex.slot_swap_authorities, [SlotSwapAuthority::default(); MAX_SLOT_SWAP_AUTHORITIES]);
+}
+
+#[test]
+fn test_ai_dex_keeps_its_reserve() {
+    assert_eq!(AiDexPool::LEN - 8 - std::mem::size_of::<AiDexPool>(), 128);
+}
+
+#[test]
+fn test_ai_dex_accrue_seconds_per_liquidity() {
+    let mut ai_dex = AiDexPool {
+        liquidity: 100,
+        reward_last_updated_timestamp: 1000,
+        ..Default::default()
+    };
+    let reward_infos = [AiDexRewardInfo::default(); NUM_REWARDS];
+
+    // Accrues with the liquidity before the update
+    ai_dex.update_rewards_and_liquidity(reward_infos, 200, 1100);
+    assert_eq!({ ai_dex.seconds_per_liquidity_global_x64 }, 1 << Q64_RESOLUTION);
+
+    ai_dex.update_rewards(reward_infos, 1300);
+    assert_eq!({ ai_dex.seconds_per_liquidity_global_x64 }, 2 << Q64_RESOLUTION);
+
+    // 2.5 = 2 + 100 / 200, then no time elapsed or no liquidity in range
+    ai_dex.update_rewards(reward_infos, 1300);
+    ai_dex.update_rewards_and_liquidity(reward_infos, 0, 1400);
+    ai_dex.update_rewards(reward_infos, 1500);
+    assert_eq!({ ai_dex.seconds_per_liquidity_global_x64 }, 0b101 << (Q64_RESOLUTION - 1));
+}
+
+#[test]
+fn test_ai_dex_accrue_reward_emissions() {
+    let mut ai_dex = AiDexPool {
+        liquidity: 100,
+        reward_last_updated_timestamp: 1000,
+        ..Default::default()
+    };
+    ai_dex.initialize_reward(0, Pubkey::new_unique(), Pubkey::new_unique()).unwrap();
+    ai_dex.reward_infos[0].emissions_per_second_x64 = 1 << Q64_RESOLUTION;
+    ai_dex.reward_emissions[0].emissions_end_timestamp = 1200;
+    let reward_infos = ai_dex.reward_infos;
+    assert!(ai_dex.reward_emissions[0].emissions_outstanding_tracked);
+
+    ai_dex.update_rewards(reward_infos, 1100);
+    assert_eq!({ ai_dex.reward_emissions[0].emissions_outstanding }, 100);
+
+    // Nothing is emitted past the end of the emissions
+    ai_dex.update_rewards(reward_infos, 1300);
+    assert_eq!({ ai_dex.reward_emissions[0].emissions_outstanding }, 200);
+
+    ai_dex.record_reward_collected(0, 150);
+    assert_eq!({ ai_dex.reward_emissions[0].emissions_outstanding }, 50);
+
+    ai_dex.record_reward_deposited(0, 25);
+    assert_eq!({ ai_dex.reward_emissions[0].emissions_outstanding }, 75);
+}
+
+#[test]
+fn test_ai_dex_is_full_range_only() {
+    let mut ai_dex = AiDexPool { tick_spacing: 64, ..Default::default() };
+    assert!(!ai_dex.is_full_range_only());
+
+    ai_dex.full_range_only = true;
+    assert!(ai_dex.is_full_range_only());
+
+    ai_dex.full_range_only = false;
+    ai_dex.tick_spacing = FULL_RANGE_ONLY_TICK_SPACING_THRESHOLD;
+    assert!(ai_dex.is_full_range_only());
+}
+
+#[test]
+fn test_ai_dex_tick_range_bounds() {
+    use crate::state::{LEGACY_MAX_TICK_INDEX, LEGACY_MIN_TICK_INDEX, MAX_TICK_INDEX, MIN_TICK_INDEX};
+
+    let mut ai_dex = AiDexPool { tick_spacing: 128, ..Default::default() };
+    assert_eq!(ai_dex.tick_index_bounds(), (LEGACY_MIN_TICK_INDEX, LEGACY_MAX_TICK_INDEX));
+    assert_eq!(ai_dex.sqrt_price_bounds(), (LEGACY_MIN_SQRT_PRICE_X64, LEGACY_MAX_SQRT_PRICE_X64));
+    assert_eq!(ai_dex.full_range_indexes(), (-443520, 443520));
+
+    ai_dex.extended_tick_range = true;
+    assert_eq!(ai_dex.tick_index_bounds(), (MIN_TICK_INDEX, MAX_TICK_INDEX));
+    assert_eq!(ai_dex.sqrt_price_bounds(), (MIN_SQRT_PRICE_X64, MAX_SQRT_PRICE_X64));
+    assert_eq!(ai_dex.full_range_indexes(), (-519808, 519808));
+}
+
+#[test]
+fn test_ai_dex_flash_swap() {
+    let mut ai_dex = AiDexPool::default();
+    assert!(ai_dex.finish_flash_swap().is_err());
+
+    ai_dex.start_flash_swap(1_000, true).unwrap();
+    assert!(ai_dex.start_flash_swap(500, false).is_err());
+    assert_eq!(ai_dex.finish_flash_swap().unwrap(), (1_000, true));
+    assert!(ai_dex.finish_flash_swap().is_err());
+
+    ai_dex.start_flash_swap(500, false).unwrap();
+    assert_eq!(ai_dex.finish_flash_swap().unwrap(), (500, false));
+}
+
+#[test]
+fn test_ai_dex_flash_swap_rejects_swap_hook() {
+    let mut ai_dex = AiDexPool::default();
+    assert!(ai_dex.check_swap_hook_not_set().is_ok());
+
+    ai_dex.set_swap_hook_program(Pubkey::new_unique());
+    assert_eq!(
+        ai_dex.check_swap_hook_not_set().unwrap_err(),
+        ErrorCode::SwapHookNotSupportedError.into()
+    );
+    assert_eq!(
+        ai_dex.start_flash_swap(1_000, true).unwrap_err(),
+        ErrorCode::SwapHookNotSupportedError.into()
+    );
+    assert!(!{ ai_dex.flash_swap_in_progress });
+
+    ai_dex.set_swap_hook_program(Pubkey::default());
+    ai_dex.start_flash_swap(1_000, true).unwrap();
+}
+
+#[test]
+fn test_ai_dex_reward_emissions_emission_seconds() {
+    let reward_emissions = &mut AiDexRewardEmissions::default();
+    assert_eq!(reward_emissions.emission_seconds(100, 400), 300);
+
+    reward_emissions.emissions_end_timestamp = 250;
+    assert_eq!(reward_emissions.emission_seconds(100, 400), 150);
+    assert_eq!(reward_emissions.emission_seconds(100, 200), 100);
+    assert_eq!(reward_emissions.emission_seconds(300, 400), 0);
+}
+
+#[test]
+fn test_ai_dex_frozen_fee_rates_reject_updates() {
+    let ai_dex = &mut AiDexPool::default();
+    ai_dex.update_fee_rate(3000).unwrap();
+    ai_dex.update_protocol_fee_rate(300).unwrap();
+    ai_dex.freeze_fee_rates().unwrap();
+
+    assert_eq!(ai_dex.update_fee_rate(500).unwrap_err(), ErrorCode::FeeRatesFrozenError.into());
+    assert_eq!(ai_dex.update_protocol_fee_rate(100).unwrap_err(), ErrorCode::FeeRatesFrozenError.into());
+    assert_eq!(ai_dex.freeze_fee_rates().unwrap_err(), ErrorCode::FeeRatesFrozenError.into());
+    assert_eq!({ ai_dex.fee_rate }, 3000);
+    assert_eq!({ ai_dex.protocol_fee_rate }, 300);
+}
+
+#[test]
+fn test_ai_dex_referral_fees_owed() {
+    let ai_dex = &mut AiDexPool::default();
+    ai_dex.accrue_referral_fee(30, true).unwrap();
+    ai_dex.accrue_referral_fee(20, false).unwrap();
+    ai_dex.collect_referral_fees(30, 5).unwrap();
+    assert_eq!({ ai_dex.referral_fee_owed_a }, 0);
+    assert_eq!({ ai_dex.referral_fee_owed_b }, 15);
+
+    assert_eq!(
+        ai_dex.collect_referral_fees(1, 0).unwrap_err(),
+        ErrorCode::AmountCalculationOverflowError.into()
+    );
+    ai_dex.referral_fee_owed_b = u64::MAX;
+    assert!(ai_dex.accrue_referral_fee(1, false).is_err());
+}
+
+#[cfg(test)]
+mod risk_limit_tests {
+    use super::*;
+
+    fn ai_dex_with_circuit_breaker(max_tick_move: u32, window_seconds: u32) -> AiDexPool {
+        let mut ai_dex = AiDexPool {
+            tick_current_index: 100,
+            ..Default::default()
+        };
+        ai_dex.set_circuit_breaker(max_tick_move, window_seconds).unwrap();
+        ai_dex
+    }
+
+    fn swap_to(ai_dex: &mut AiDexPool, tick_index: i32, timestamp: u64) {
+        ai_dex.update_after_swap(0, tick_index, 0, 0, [AiDexRewardInfo::default(); NUM_REWARDS], 0, true, timestamp);
+    }
+
+    #[test]
+    fn test_emergency_mode_pauses_pool() {
+        let mut ai_dex = AiDexPool::default();
+        assert!(!ai_dex.is_paused());
+        ai_dex.set_paused(true);
+        assert!(ai_dex.is_paused());
+
+        ai_dex.set_paused(false);
+        ai_dex.enable_emergency_mode();
+        assert!(ai_dex.is_paused());
+        ai_dex.set_paused(false);
+        assert!(ai_dex.is_paused());
+    }
+
+    #[test]
+    fn test_disabled_circuit_breaker_allows_any_move() {
+        let ai_dex = ai_dex_with_circuit_breaker(0, 0);
+        assert!(ai_dex.check_circuit_breaker(100_000, 1_000).is_ok());
+    }
+
+    #[test]
+    fn test_empty_window_is_invalid() {
+        let mut ai_dex = AiDexPool::default();
+        assert!(ai_dex.set_circuit_breaker(10, 0).is_err());
+    }
+
+    #[test]
+    fn test_price_impact_is_capped() {
+        let mut ai_dex = AiDexPool {
+            sqrt_price: 10 << 64,
+            ..Default::default()
+        };
+        assert!(ai_dex.check_price_impact(20 << 64).is_ok());
+
+        ai_dex.set_max_price_impact_bps(2_100);
+        assert!(ai_dex.check_price_impact(11 << 64).is_ok());
+        assert!(ai_dex.check_price_impact((1101 << 64) / 100).is_err());
+    }
+
+    #[test]
+    fn test_slot_volume_is_capped() {
+        let mut ai_dex = AiDexPool::default();
+        assert!(ai_dex.record_slot_volume(10, 1_000, 1_000, true).is_ok());
+
+        ai_dex.set_max_slot_volume_bps(1_000);
+        assert!(ai_dex.record_slot_volume(11, 60, 1_000, true).is_ok());
+        // The volume within the slot adds up, against the balance at the start of the slot
+        assert!(ai_dex.record_slot_volume(11, 40, 940, true).is_ok());
+        assert!(ai_dex.record_slot_volume(11, 1, 900, true).is_err());
+        // Each vault is tracked separately
+        assert!(ai_dex.record_slot_volume(11, 100, 1_000, false).is_ok());
+
+        // A new slot starts from zero
+        assert!(ai_dex.record_slot_volume(12, 90, 900, true).is_ok());
+        assert!(ai_dex.record_slot_volume(12, 1, 810, true).is_err());
+    }
+
+    #[test]
+    fn test_anti_sandwich_rejects_reversal_within_slot() {
+        let trader = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let mut ai_dex = AiDexPool::default();
+        assert!(ai_dex.record_swap_authority(10, trader, true).is_ok());
+        assert!(ai_dex.record_swap_authority(10, trader, false).is_ok());
+
+        ai_dex.set_anti_sandwich_enabled(true);
+        assert!(ai_dex.record_swap_authority(11, trader, true).is_ok());
+        assert!(ai_dex.record_swap_authority(11, trader, true).is_ok());
+        assert!(ai_dex.record_swap_authority(11, trader, false).is_err());
+
+        // Another authority or a later slot may reverse the swap
+        assert!(ai_dex.record_swap_authority(11, other, false).is_ok());
+        assert!(ai_dex.record_swap_authority(12, other, true).is_ok());
+    }
+
+    #[test]
+    fn test_anti_sandwich_rejects_back_run_after_victim_swap() {
+        let attacker = Pubkey::new_unique();
+        let victim = Pubkey::new_unique();
+        let mut ai_dex = AiDexPool::default();
+        ai_dex.set_anti_sandwich_enabled(true);
+
+        // Attacker buy, victim buy, attacker sell within the same slot
+        assert!(ai_dex.record_swap_authority(20, attacker, false).is_ok());
+        assert!(ai_dex.record_swap_authority(20, victim, false).is_ok());
+        assert_eq!(
+            ai_dex.record_swap_authority(20, attacker, true).unwrap_err(),
+            ErrorCode::SandwichSwapError.into()
+        );
+
+        // The table is reset on a new slot
+        assert!(ai_dex.record_swap_authority(21, attacker, true).is_ok());
+        assert!(ai_dex.record_swap_authority(21, victim, true).is_ok());
+    }
+
+    #[test]
+    fn test_anti_sandwich_replaces_oldest_authority_when_full() {
+        let first = Pubkey::new_unique();
+        let mut ai_dex = AiDexPool::default();
+        ai_dex.set_anti_sandwich_enabled(true);
+
+        assert!(ai_dex.record_swap_authority(30, first, true).is_ok());
+        for _ in 1..MAX_SLOT_SWAP_AUTHORITIES {
+            assert!(ai_dex.record_swap_authority(30, Pubkey::new_unique(), false).is_ok());
+        }
+        assert!(ai_dex.record_swap_authority(30, first, false).is_err());
+
+        // The entry of the first authority is replaced once the table is full
+        assert!(ai_dex.record_swap_authority(30, Pubkey::new_unique(), false).is_ok());
+        assert!(ai_dex.record_swap_authority(30, first, false).is_ok());
+    }
+
+    #[test]
+    fn test_open_time_can_only_be_moved_before_launch() {
+        let mut ai_dex = AiDexPool {
+            open_time: 1_000,
+            ..Default::default()
+        };
+        assert!(!ai_dex.is_open(999));
+        assert!(ai_dex.is_open(1_000));
+
+        ai_dex.set_open_time(2_000, 999).unwrap();
+        assert!(!ai_dex.is_open(1_500));
+        assert_eq!(ai_dex.set_open_time(3_000, 2_000).unwrap_err(), ErrorCode::PoolAlreadyOpenError.into());
+        assert_eq!({ ai_dex.open_time }, 2_000);
+    }
+
+    #[test]
+    fn test_move_is_bounded_within_window() {
+        let mut ai_dex = ai_dex_with_circuit_breaker(50, 60);
+        assert!(ai_dex.check_circuit_breaker(150, 1_000).is_ok());
+        assert!(ai_dex.check_circuit_breaker(151, 1_000).is_err());
+        assert!(ai_dex.check_circuit_breaker(49, 1_000).is_err());
+
+        // The moves within the window add up
+        swap_to(&mut ai_dex, 130, 1_000);
+        assert!(ai_dex.check_circuit_breaker(150, 1_030).is_ok());
+        assert!(ai_dex.check_circuit_breaker(160, 1_030).is_err());
+
+        // A new window measures the moves from the current tick index
+        assert!(ai_dex.check_circuit_breaker(180, 1_060).is_ok());
+        swap_to(&mut ai_dex, 180, 1_060);
+        assert!(ai_dex.check_circuit_breaker(80, 1_070).is_ok());
+        assert!(ai_dex.check_circuit_breaker(79, 1_070).is_err());
+    }
+}
+
+#[cfg(test)]
+mod fee_rate_tests {
+    use super::*;
+
+    fn ai_dex_with_volume_fee_tiers() -> AiDexPool {
+        let mut ai_dex = AiDexPool {
+            fee_rate: 3_000,
+            ..Default::default()
+        };
+        ai_dex
+            .set_volume_fee_tiers(
+                3_600,
+                &[
+                    VolumeFeeTier { min_volume: 1_000, fee_rate: 5_000 },
+                    VolumeFeeTier { min_volume: 10_000, fee_rate: 10_000 },
+                ],
+            )
+            .unwrap();
+        ai_dex
+    }
+
+    #[test]
+    fn test_fee_rate_steps_with_previous_window_volume() {
+        let mut ai_dex = ai_dex_with_volume_fee_tiers();
+        assert_eq!(ai_dex.effective_fee_rate(1_000, 0), 3_000);
+
+        // The volume of the current window does not move the fee rate
+        ai_dex.record_window_volume(2_000, 1_000);
+        assert_eq!(ai_dex.effective_fee_rate(1_000, 0), 3_000);
+
+        // Once the window is complete, its volume sets the fee rate of the next one
+        assert_eq!(ai_dex.effective_fee_rate(4_600, 0), 5_000);
+        ai_dex.record_window_volume(20_000, 4_600);
+        assert_eq!(ai_dex.effective_fee_rate(5_000, 0), 5_000);
+        assert_eq!(ai_dex.effective_fee_rate(8_200, 0), 10_000);
+
+        // A window without swaps resets the fee rate
+        assert_eq!(ai_dex.effective_fee_rate(11_800, 0), 3_000);
+        ai_dex.record_window_volume(1, 11_800);
+        assert_eq!(ai_dex.effective_fee_rate(11_800, 0), 3_000);
+    }
+
+    #[test]
+    fn test_adaptive_fee_scales_with_volatility_within_bounds() {
+        let mut ai_dex = AiDexPool {
+            fee_rate: 3_000,
+            ..Default::default()
+        };
+        assert_eq!(ai_dex.effective_fee_rate(0, 1_000), 3_000);
+
+        ai_dex.set_adaptive_fee(600, 10, 1_000, 8_000).unwrap();
+        assert_eq!(ai_dex.effective_fee_rate(0, 0), 3_000);
+        assert_eq!(ai_dex.effective_fee_rate(0, 200), 5_000);
+        assert_eq!(ai_dex.effective_fee_rate(0, u32::MAX), 8_000);
+
+        ai_dex.set_adaptive_fee(600, 10, 4_000, 8_000).unwrap();
+        assert_eq!(ai_dex.effective_fee_rate(0, 0), 4_000);
+
+        assert!(ai_dex.set_adaptive_fee(600, 10, 8_001, 8_000).is_err());
+        assert!(ai_dex.set_adaptive_fee(600, 10, 0, MAX_FEE_RATE + 1).is_err());
+        ai_dex.set_adaptive_fee(0, 0, 0, 0).unwrap();
+        assert_eq!(ai_dex.effective_fee_rate(0, 1_000), 3_000);
+    }
+
+    #[test]
+    fn test_fee_manager() {
+        let mut ai_dex = AiDexPool::default();
+        let fee_manager = Pubkey::new_unique();
+        assert!(!ai_dex.is_fee_manager(&Pubkey::default()));
+
+        ai_dex.set_fee_manager(fee_manager);
+        assert!(ai_dex.is_fee_manager(&fee_manager));
+        assert!(!ai_dex.is_fee_manager(&Pubkey::new_unique()));
+
+        ai_dex.set_fee_manager(Pubkey::default());
+        assert!(!ai_dex.is_fee_manager(&fee_manager));
+        assert!(!ai_dex.is_fee_manager(&Pubkey::default()));
+    }
+
+    #[test]
+    fn test_locked_reward_boost_bounds() {
+        let mut ai_dex = AiDexPool::default();
+        ai_dex.set_locked_reward_boost(15_000).unwrap();
+        assert_eq!({ ai_dex.locked_reward_boost_bps }, 15_000);
+        ai_dex.set_locked_reward_boost(0).unwrap();
+        assert!(ai_dex.set_locked_reward_boost(REWARD_BOOST_BPS_DENOMINATOR - 1).is_err());
+        assert!(ai_dex.set_locked_reward_boost(MAX_REWARD_BOOST_BPS + 1).is_err());
+    }
+
+    #[test]
+    fn test_reward_surplus() {
+        let mut ai_dex = AiDexPool::default();
+        ai_dex.reward_emissions[0].emissions_outstanding = 400;
+        ai_dex.reward_emissions[0].emissions_end_timestamp = 2_000;
+        assert_eq!(
+            ai_dex.reward_surplus(0, 1_000, 2_000).unwrap_err(),
+            ErrorCode::RewardEmissionsNotTrackedError.into()
+        );
+
+        ai_dex.reward_emissions[0].emissions_outstanding_tracked = true;
+        ai_dex.reward_emissions[0].emissions_end_timestamp = 0;
+        assert!(ai_dex.reward_surplus(0, 1_000, 2_000).is_err());
+
+        ai_dex.reward_emissions[0].emissions_end_timestamp = 2_000;
+        assert!(ai_dex.reward_surplus(0, 1_000, 1_999).is_err());
+        assert_eq!(ai_dex.reward_surplus(0, 1_000, 2_000).unwrap(), 600);
+
+        ai_dex.record_reward_collected(0, 100);
+        assert_eq!(ai_dex.reward_surplus(0, 900, 2_000).unwrap(), 600);
+
+        ai_dex.set_locked_reward_boost(20_000).unwrap();
+        assert_eq!(ai_dex.reward_surplus(0, 900, 2_000).unwrap(), 300);
+        assert_eq!(ai_dex.reward_surplus(0, 500, 2_000).unwrap(), 0);
+        assert!(ai_dex.reward_surplus(NUM_REWARDS, 900, 2_000).is_err());
+    }
+
+    #[test]
+    fn test_take_vault_surplus() {
+        let token_vault_a = Pubkey::new_unique();
+        let token_vault_b = Pubkey::new_unique();
+        let mut ai_dex = AiDexPool {
+            token_vault_a,
+            token_vault_b,
+            ..Default::default()
+        };
+        assert!(ai_dex.take_vault_surplus(0, 0).is_err());
+        ai_dex.vault_reserves_tracked = true;
+
+        ai_dex.track_vault_balance(&token_vault_a, 0, 1_000);
+        ai_dex.track_vault_balance(&token_vault_b, 0, 500);
+        ai_dex.track_vault_balance(&token_vault_b, 500, 200);
+        ai_dex.track_vault_balance(&Pubkey::new_unique(), 0, 700);
+        assert_eq!({ ai_dex.vault_reserve_a }, 1_000);
+        assert_eq!({ ai_dex.vault_reserve_b }, 200);
+
+        // 50 tokens of A were sent to the vault directly
+        assert_eq!(ai_dex.take_vault_surplus(1_050, 200).unwrap(), (50, 0));
+        ai_dex.track_vault_balance(&token_vault_a, 1_050, 1_000);
+        assert_eq!({ ai_dex.vault_reserve_a }, 1_000);
+        assert_eq!(ai_dex.take_vault_surplus(1_000, 200).unwrap(), (0, 0));
+    }
+
+    #[test]
+    fn test_launch_fee_decays_to_fee_rate() {
+        let mut ai_dex = AiDexPool {
+            fee_rate: 3_000,
+            open_time: 1_000,
+            ..Default::default()
+        };
+        assert!(ai_dex.set_launch_fee(20_000, 25_000, 3_600, 0).is_err());
+        assert!(ai_dex.set_launch_fee(MAX_FEE_RATE + 1, 0, 3_600, 0).is_err());
+        ai_dex.set_launch_fee(30_000, 1_000, 3_600, 0).unwrap();
+
+        assert_eq!(ai_dex.effective_fee_rate(1_000, 0), 30_000);
+        assert_eq!(ai_dex.effective_fee_rate(2_800, 0), 15_500);
+        // The launch fee rate applies only while higher than the fee rate
+        assert_eq!(ai_dex.effective_fee_rate(4_500, 0), 3_000);
+        assert_eq!(ai_dex.effective_fee_rate(4_600, 0), 3_000);
+
+        assert_eq!(
+            ai_dex.set_launch_fee(0, 0, 0, 1_000).unwrap_err(),
+            ErrorCode::PoolAlreadyOpenError.into()
+        );
+    }
+
+    #[test]
+    fn test_set_volume_fee_tiers_invalid() {
+        let mut ai_dex = AiDexPool::default();
+        let tier = VolumeFeeTier { min_volume: 1_000, fee_rate: 5_000 };
+        assert!(ai_dex.set_volume_fee_tiers(0, &[tier]).is_err());
+        assert!(ai_dex.set_volume_fee_tiers(3_600, &[]).is_err());
+        assert!(ai_dex.set_volume_fee_tiers(3_600, &[tier, tier]).is_err());
+        assert!(ai_dex.set_volume_fee_tiers(3_600, &[VolumeFeeTier { min_volume: 1, fee_rate: 0 }]).is_err());
+        assert!(ai_dex
+            .set_volume_fee_tiers(3_600, &[VolumeFeeTier { min_volume: 1, fee_rate: MAX_FEE_RATE + 1 }])
+            .is_err());
+        assert!(ai_dex.set_volume_fee_tiers(3_600, &[tier; MAX_VOLUME_FEE_TIERS + 1]).is_err());
+        ai_dex.set_volume_fee_tiers(0, &[]).unwrap();
+
+        ai_dex.fee_rates_frozen = true;
+        assert_eq!(
+            ai_dex.set_volume_fee_tiers(3_600, &[tier]).unwrap_err(),
+            ErrorCode::FeeRatesFrozenError.into()
+        );
+    }
+}
+
 #[cfg(test)]
 pub mod ai_dex_builder {
-    use super::{AiDexPool, AiDexRewardInfo, NUM_REWARDS};
+    use super::{AiDexPool, AiDexRewardEmissions, AiDexRewardInfo, NUM_REWARDS};
 
     #[derive(Default)]
     pub struct AiDexBuilder {
@@ -474,12 +2182,15 @@ pub mod ai_dex_builder {
         fee_growth_global_b: u128,
         reward_last_updated_timestamp: u64,
         reward_infos: [AiDexRewardInfo; NUM_REWARDS],
+        reward_emissions: [AiDexRewardEmissions; NUM_REWARDS],
+        extended_tick_range: bool,
     }
 
     impl AiDexBuilder {
         pub fn new() -> Self {
             Self {
                 reward_infos: [AiDexRewardInfo::default(); NUM_REWARDS],
+                reward_emissions: [AiDexRewardEmissions::default(); NUM_REWARDS],
                 ..Default::default()
             }
         }
@@ -504,6 +2215,16 @@ pub mod ai_dex_builder {
             self
         }
 
+        pub fn reward_emissions(mut self, index: usize, reward_emissions: AiDexRewardEmissions) -> Self {
+            self.reward_emissions[index] = reward_emissions;
+            self
+        }
+
+        pub fn extended_tick_range(mut self, extended_tick_range: bool) -> Self {
+            self.extended_tick_range = extended_tick_range;
+            self
+        }
+
         pub fn tick_spacing(mut self, tick_spacing: u16) -> Self {
             self.tick_spacing = tick_spacing;
             self
@@ -544,6 +2265,7 @@ pub mod ai_dex_builder {
                 liquidity: self.liquidity,
                 reward_last_updated_timestamp: self.reward_last_updated_timestamp,
                 reward_infos: self.reward_infos,
+                reward_emissions: self.reward_emissions,
                 tick_current_index: self.tick_current_index,
                 sqrt_price: self.sqrt_price,
                 tick_spacing: self.tick_spacing,
@@ -551,6 +2273,7 @@ pub mod ai_dex_builder {
                 fee_growth_global_b: self.fee_growth_global_b,
                 fee_rate: self.fee_rate,
                 protocol_fee_rate: self.protocol_fee_rate,
+                extended_tick_range: self.extended_tick_range,
                 ..Default::default()
             }
         }
@@ -1,17 +1,29 @@
 use anchor_lang::prelude::*;
 
-use crate::{errors::ErrorCode, math::MAX_PROTOCOL_FEE_RATE};
+use crate::{errors::ErrorCode, math::{MAX_PROTOCOL_FEE_RATE, MAX_REFERRAL_FEE_RATE}};
+
+// Version of the current layout of a config, raised whenever fields are appended to it
+pub const AI_DEX_CONFIG_VERSION: u8 = 1;
 
 #[account]
 pub struct AiDexConfig {
     pub config_authority: Pubkey,
     pub default_protocol_fee_rate: u16,
+    pub pending_config_authority: Pubkey,
+    pub emergency_authority: Pubkey,
+    pub paused: bool,
+    pub pool_creation_fee: u64,
+    pub pool_creation_fee_mint: Pubkey,
+    pub pool_creation_fee_destination: Pubkey,
+    pub referral_fee_rate: u16,
+    pub version: u8,
+    // 64 RESERVE
 }
 
 /// Implementation of the AiDexConfig struct.
 impl AiDexConfig {
     /// Length of the AiDexConfig struct.
-    pub const LEN: usize = 8 + 96 + 4;
+    pub const LEN: usize = 8 + 96 + 4 + 8 + 32 + 32 + 2 + 1 + 64;
 
     /// Updates the fee authority.
     ///
@@ -22,6 +34,66 @@ impl AiDexConfig {
         self.config_authority = config_authority;
     }
 
+    /// Proposes a new config authority, which takes over once it accepts.
+    ///
+    /// # Arguments
+    ///
+    /// * `pending_config_authority` - The proposed config authority public key.
+    pub fn propose_config_authority(&mut self, pending_config_authority: Pubkey) {
+        self.pending_config_authority = pending_config_authority;
+    }
+
+    /// Updates the emergency authority, the only one able to pause the protocol.
+    ///
+    /// # Arguments
+    ///
+    /// * `emergency_authority` - The new emergency authority public key.
+    pub fn update_emergency_authority(&mut self, emergency_authority: Pubkey) {
+        self.emergency_authority = emergency_authority;
+    }
+
+    /// Pauses or unpauses the user-facing instructions of all the pools of the config.
+    ///
+    /// # Arguments
+    ///
+    /// * `paused` - Whether the protocol is paused.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Sets the fee collected from the funder of every new pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool_creation_fee` - The fee amount, zero to disable the fee.
+    /// * `pool_creation_fee_mint` - The mint of the fee, or the default pubkey for a fee in lamports.
+    /// * `pool_creation_fee_destination` - The wallet receiving a fee in lamports, or the token account receiving a fee in tokens.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the fee is non-zero without a destination.
+    pub fn update_pool_creation_fee(
+        &mut self,
+        pool_creation_fee: u64,
+        pool_creation_fee_mint: Pubkey,
+        pool_creation_fee_destination: Pubkey,
+    ) -> Result<()> {
+        if pool_creation_fee != 0 && pool_creation_fee_destination == Pubkey::default() {
+            return Err(ErrorCode::InvalidPoolCreationFeeError.into());
+        }
+        self.pool_creation_fee = pool_creation_fee;
+        self.pool_creation_fee_mint = pool_creation_fee_mint;
+        self.pool_creation_fee_destination = pool_creation_fee_destination;
+
+        Ok(())
+    }
+
+    /// Makes the pending config authority the config authority.
+    pub fn accept_config_authority(&mut self) {
+        self.config_authority = self.pending_config_authority;
+        self.pending_config_authority = Pubkey::default();
+    }
+
     /// Initializes the AiDexConfig struct.
     ///
     /// # Arguments
@@ -38,6 +110,7 @@ impl AiDexConfig {
         default_protocol_fee_rate: u16,
     ) -> Result<()> {
         self.config_authority = config_authority;
+        self.version = AI_DEX_CONFIG_VERSION;
         self.update_default_protocol_fee_rate(default_protocol_fee_rate)?;
 
         Ok(())
@@ -63,4 +136,22 @@ impl AiDexConfig {
 
         Ok(())
     }
+
+    /// Updates the share of the swap fee owed to the referrer of a swap.
+    ///
+    /// # Arguments
+    ///
+    /// * `referral_fee_rate` - The new referral fee rate, in basis points of the swap fee.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the referral fee rate exceeds the maximum referral fee rate.
+    pub fn update_referral_fee_rate(&mut self, referral_fee_rate: u16) -> Result<()> {
+        if referral_fee_rate > MAX_REFERRAL_FEE_RATE {
+            return Err(ErrorCode::ReferralFeeRateExceededError.into());
+        }
+        self.referral_fee_rate = referral_fee_rate;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,250 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::nft::{AD_METADATA_NAME, AD_METADATA_SYMBOL, AD_METADATA_URI};
+use crate::errors::ErrorCode;
+use crate::state::AiDexConfig;
+
+pub const MAX_NFT_METADATA_NAME_LEN: usize = 32;
+pub const MAX_NFT_METADATA_SYMBOL_LEN: usize = 10;
+pub const MAX_NFT_METADATA_URI_LEN: usize = 200;
+
+/// The roles of a config whose authority can be delegated by the config authority.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigRole {
+    /// Sets the fee rates and initializes the fee tiers.
+    Fee,
+    /// Collects the protocol fees of the pools.
+    CollectProtocolFees,
+    /// Sets the reward authorities of the pools.
+    RewardEmissionsSuper,
+    /// Initializes, updates and deletes the token wrappers.
+    TokenWrapper,
+    /// Pauses and unpauses the pools.
+    Pause,
+    /// Manages the allowlists of the pools.
+    Allowlist,
+}
+
+#[account]
+#[derive(Default)]
+pub struct AiDexConfigExtension {
+    pub ai_dex_config: Pubkey, // 32
+    pub position_nft_name: String, // 4 + 32
+    pub position_nft_symbol: String, // 4 + 10
+    pub position_nft_uri: String, // 4 + 200
+    pub fee_authority: Pubkey, // 32
+    pub collect_protocol_fees_authority: Pubkey, // 32
+    pub reward_emissions_super_authority: Pubkey, // 32
+    pub token_wrapper_authority: Pubkey, // 32
+    pub pause_authority: Pubkey, // 32
+    pub pending_fee_authority: Pubkey, // 32
+    pub allowlist_authority: Pubkey, // 32
+                                  // 64 RESERVE
+}
+
+impl AiDexConfigExtension {
+    pub const LEN: usize = 8
+        + 32
+        + 4 + MAX_NFT_METADATA_NAME_LEN
+        + 4 + MAX_NFT_METADATA_SYMBOL_LEN
+        + 4 + MAX_NFT_METADATA_URI_LEN
+        + 32 * 5
+        + 32
+        + 32
+        + 64;
+
+    /// Initializes the config extension with the default position NFT metadata.
+    ///
+    /// The role authorities are left unset, so the config authority holds every role.
+    ///
+    /// # Arguments
+    ///
+    /// * `ai_dex_config` - The config being extended.
+    pub fn initialize(&mut self, ai_dex_config: Pubkey) -> Result<()> {
+        self.ai_dex_config = ai_dex_config;
+        self.update_position_nft_metadata(
+            AD_METADATA_NAME.to_string(),
+            AD_METADATA_SYMBOL.to_string(),
+            AD_METADATA_URI.to_string(),
+        )
+    }
+
+    /// Updates the name, symbol and URI given to the position NFTs minted with metadata.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidNftMetadataError` - If the name, symbol or URI is empty or longer than allowed by Metaplex.
+    pub fn update_position_nft_metadata(&mut self, name: String, symbol: String, uri: String) -> Result<()> {
+        if name.is_empty()
+            || name.len() > MAX_NFT_METADATA_NAME_LEN
+            || symbol.is_empty()
+            || symbol.len() > MAX_NFT_METADATA_SYMBOL_LEN
+            || uri.len() > MAX_NFT_METADATA_URI_LEN
+        {
+            return Err(ErrorCode::InvalidNftMetadataError.into());
+        }
+
+        self.position_nft_name = name;
+        self.position_nft_symbol = symbol;
+        self.position_nft_uri = uri;
+        Ok(())
+    }
+
+    /// Returns the authority set for a role, or the default pubkey if the role is not delegated.
+    pub fn authority(&self, role: ConfigRole) -> Pubkey {
+        match role {
+            ConfigRole::Fee => self.fee_authority,
+            ConfigRole::CollectProtocolFees => self.collect_protocol_fees_authority,
+            ConfigRole::RewardEmissionsSuper => self.reward_emissions_super_authority,
+            ConfigRole::TokenWrapper => self.token_wrapper_authority,
+            ConfigRole::Pause => self.pause_authority,
+            ConfigRole::Allowlist => self.allowlist_authority,
+        }
+    }
+
+    /// Delegates a role to an authority. Setting the default pubkey gives the role back to the config authority.
+    pub fn update_authority(&mut self, role: ConfigRole, authority: Pubkey) {
+        match role {
+            ConfigRole::Fee => self.fee_authority = authority,
+            ConfigRole::CollectProtocolFees => self.collect_protocol_fees_authority = authority,
+            ConfigRole::RewardEmissionsSuper => self.reward_emissions_super_authority = authority,
+            ConfigRole::TokenWrapper => self.token_wrapper_authority = authority,
+            ConfigRole::Pause => self.pause_authority = authority,
+            ConfigRole::Allowlist => self.allowlist_authority = authority,
+        }
+    }
+
+    /// Proposes a new fee authority, which takes over once it accepts.
+    pub fn propose_fee_authority(&mut self, pending_fee_authority: Pubkey) {
+        self.pending_fee_authority = pending_fee_authority;
+    }
+
+    /// Makes the pending fee authority the fee authority.
+    pub fn accept_fee_authority(&mut self) {
+        self.fee_authority = self.pending_fee_authority;
+        self.pending_fee_authority = Pubkey::default();
+    }
+}
+
+/// Returns the authority of a role of a config: the delegated authority from the config extension
+/// if there is one, otherwise the config authority.
+pub fn config_role_authority(
+    ai_dex_config: &AiDexConfig,
+    config_extension: Option<&AiDexConfigExtension>,
+    role: ConfigRole,
+) -> Pubkey {
+    match config_extension.map(|config_extension| config_extension.authority(role)) {
+        Some(authority) if authority != Pubkey::default() => authority,
+        _ => ai_dex_config.config_authority,
+    }
+}
+
+/// Returns the name, symbol and URI of a position NFT, from the config extension if there is one.
+pub fn position_nft_metadata(config_extension: Option<&AiDexConfigExtension>) -> (String, String, String) {
+    match config_extension {
+        Some(config_extension) => (
+            config_extension.position_nft_name.clone(),
+            config_extension.position_nft_symbol.clone(),
+            config_extension.position_nft_uri.clone(),
+        ),
+        None => (
+            AD_METADATA_NAME.to_string(),
+            AD_METADATA_SYMBOL.to_string(),
+            AD_METADATA_URI.to_string(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod config_extension_tests {
+    use super::*;
+
+    #[test]
+    fn test_initialize_uses_default_metadata() {
+        let mut config_extension = AiDexConfigExtension::default();
+        config_extension.initialize(Pubkey::new_unique()).unwrap();
+
+        assert_eq!(
+            position_nft_metadata(Some(&config_extension)),
+            position_nft_metadata(None)
+        );
+    }
+
+    #[test]
+    fn test_update_position_nft_metadata() {
+        let mut config_extension = AiDexConfigExtension::default();
+        config_extension
+            .update_position_nft_metadata("My Dex Position".to_string(), "MDP".to_string(), "https://x.y/p.json".to_string())
+            .unwrap();
+
+        assert_eq!(
+            position_nft_metadata(Some(&config_extension)),
+            ("My Dex Position".to_string(), "MDP".to_string(), "https://x.y/p.json".to_string())
+        );
+    }
+
+    #[test]
+    fn test_config_role_authority() {
+        let ai_dex_config = AiDexConfig {
+            config_authority: Pubkey::new_unique(),
+            default_protocol_fee_rate: 0,
+            pending_config_authority: Pubkey::default(),
+            emergency_authority: Pubkey::default(),
+            paused: false,
+            pool_creation_fee: 0,
+            pool_creation_fee_mint: Pubkey::default(),
+            pool_creation_fee_destination: Pubkey::default(),
+            referral_fee_rate: 0,
+            version: crate::state::AI_DEX_CONFIG_VERSION,
+        };
+        let mut config_extension = AiDexConfigExtension::default();
+        config_extension.initialize(Pubkey::new_unique()).unwrap();
+
+        assert_eq!(config_role_authority(&ai_dex_config, None, ConfigRole::Fee), ai_dex_config.config_authority);
+        assert_eq!(
+            config_role_authority(&ai_dex_config, Some(&config_extension), ConfigRole::Fee),
+            ai_dex_config.config_authority
+        );
+
+        let fee_authority = Pubkey::new_unique();
+        config_extension.update_authority(ConfigRole::Fee, fee_authority);
+        assert_eq!(config_role_authority(&ai_dex_config, Some(&config_extension), ConfigRole::Fee), fee_authority);
+        assert_eq!(
+            config_role_authority(&ai_dex_config, Some(&config_extension), ConfigRole::Pause),
+            ai_dex_config.config_authority
+        );
+
+        config_extension.update_authority(ConfigRole::Fee, Pubkey::default());
+        assert_eq!(
+            config_role_authority(&ai_dex_config, Some(&config_extension), ConfigRole::Fee),
+            ai_dex_config.config_authority
+        );
+    }
+
+    #[test]
+    fn test_propose_and_accept_fee_authority() {
+        let mut config_extension = AiDexConfigExtension::default();
+        let fee_authority = Pubkey::new_unique();
+
+        config_extension.propose_fee_authority(fee_authority);
+        assert_eq!(config_extension.fee_authority, Pubkey::default());
+        assert_eq!(config_extension.pending_fee_authority, fee_authority);
+
+        config_extension.accept_fee_authority();
+        assert_eq!(config_extension.fee_authority, fee_authority);
+        assert_eq!(config_extension.pending_fee_authority, Pubkey::default());
+    }
+
+    #[test]
+    fn test_update_position_nft_metadata_too_long() {
+        let mut config_extension = AiDexConfigExtension::default();
+        let name = "n".repeat(MAX_NFT_METADATA_NAME_LEN + 1);
+        let symbol = "s".repeat(MAX_NFT_METADATA_SYMBOL_LEN + 1);
+        let uri = "u".repeat(MAX_NFT_METADATA_URI_LEN + 1);
+
+        assert!(config_extension.update_position_nft_metadata(name, "S".to_string(), String::new()).is_err());
+        assert!(config_extension.update_position_nft_metadata("N".to_string(), symbol, String::new()).is_err());
+        assert!(config_extension.update_position_nft_metadata("N".to_string(), "S".to_string(), uri).is_err());
+        assert!(config_extension.update_position_nft_metadata(String::new(), "S".to_string(), String::new()).is_err());
+    }
+}
@@ -0,0 +1,172 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+#[account]
+#[derive(Default)]
+pub struct DcaSchedule {
+    pub ai_dex_pool: Pubkey, // 32
+    pub owner: Pubkey,       // 32
+    // Sells token A for token B if true, token B for token A otherwise
+    pub a_to_b: bool,        // 1
+
+    // Input token not swapped yet
+    pub amount_in: u64,  // 8
+    // Output token received from executions
+    pub amount_out: u64, // 8
+
+    pub amount_per_interval: u64,      // 8
+    pub interval: u64,                 // 8
+    // Price guard of every execution, 0 if unset
+    pub sqrt_price_limit: u128,        // 16
+    pub last_execution_timestamp: u64, // 8
+    // 64 RESERVE
+}
+
+/// Struct representing a recurring swap of a deposit through a single pool.
+///
+/// The deposit and the output are held by the vaults of the pool until the schedule is closed.
+/// Every `interval` seconds, any keeper can swap `amount_per_interval` of the deposit, as long as
+/// the swap does not move the price past `sqrt_price_limit`.
+impl DcaSchedule {
+    /// Length of the `DcaSchedule` struct in bytes.
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 8 + 8 + 8 + 8 + 16 + 8 + 64;
+
+    /// Initializes the `DcaSchedule` struct.
+    ///
+    /// # Arguments
+    ///
+    /// * `ai_dex_pool` - The pool pubkey.
+    /// * `owner` - The owner of the schedule.
+    /// * `a_to_b` - The direction of the swaps.
+    /// * `amount_in` - The amount of input token deposited.
+    /// * `amount_per_interval` - The amount of input token swapped per execution.
+    /// * `interval` - The minimum number of seconds between two executions.
+    /// * `sqrt_price_limit` - The price guard of every execution, `0` if unset.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the deposit is zero, or the amount per interval or the interval is zero.
+    pub fn initialize(
+        &mut self,
+        ai_dex_pool: Pubkey,
+        owner: Pubkey,
+        a_to_b: bool,
+        amount_in: u64,
+        amount_per_interval: u64,
+        interval: u64,
+        sqrt_price_limit: u128,
+    ) -> Result<()> {
+        if amount_in == 0 {
+            return Err(ErrorCode::NoTradableAmountError.into());
+        }
+        if amount_per_interval == 0 || interval == 0 {
+            return Err(ErrorCode::InvalidDcaScheduleError.into());
+        }
+
+        self.ai_dex_pool = ai_dex_pool;
+        self.owner = owner;
+        self.a_to_b = a_to_b;
+        self.amount_in = amount_in;
+        self.amount_out = 0;
+        self.amount_per_interval = amount_per_interval;
+        self.interval = interval;
+        self.sqrt_price_limit = sqrt_price_limit;
+        self.last_execution_timestamp = 0;
+        Ok(())
+    }
+
+    /// Returns the amount of input token to swap in an execution at `timestamp`.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp` - The current timestamp.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the interval has not elapsed since the last execution, or if the
+    /// deposit has been fully swapped.
+    pub fn next_execution_amount(&self, timestamp: u64) -> Result<u64> {
+        if self.last_execution_timestamp != 0
+            && timestamp < self.last_execution_timestamp.saturating_add(self.interval)
+        {
+            return Err(ErrorCode::DcaIntervalNotElapsedError.into());
+        }
+        if self.amount_in == 0 {
+            return Err(ErrorCode::NoTradableAmountError.into());
+        }
+        Ok(self.amount_in.min(self.amount_per_interval))
+    }
+
+    /// Records an execution of the schedule.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount_in` - The amount of input token swapped.
+    /// * `amount_out` - The amount of output token received.
+    /// * `timestamp` - The current timestamp.
+    pub fn record_execution(&mut self, amount_in: u64, amount_out: u64, timestamp: u64) -> Result<()> {
+        self.amount_in = self
+            .amount_in
+            .checked_sub(amount_in)
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        self.amount_out = self
+            .amount_out
+            .checked_add(amount_out)
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        self.last_execution_timestamp = timestamp;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod dca_schedule_tests {
+    use super::*;
+
+    fn build_schedule(amount_in: u64, amount_per_interval: u64, interval: u64) -> DcaSchedule {
+        let mut schedule = DcaSchedule::default();
+        schedule
+            .initialize(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                true,
+                amount_in,
+                amount_per_interval,
+                interval,
+                0,
+            )
+            .unwrap();
+        schedule
+    }
+
+    #[test]
+    fn test_initialize_invalid() {
+        let mut schedule = DcaSchedule::default();
+        let pool = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+
+        assert!(schedule.initialize(pool, owner, true, 0, 100, 60, 0).is_err());
+        assert!(schedule.initialize(pool, owner, true, 1_000, 0, 60, 0).is_err());
+        assert!(schedule.initialize(pool, owner, true, 1_000, 100, 0, 0).is_err());
+    }
+
+    #[test]
+    fn test_executions() {
+        let mut schedule = build_schedule(250, 100, 60);
+
+        assert_eq!(schedule.next_execution_amount(1_000).unwrap(), 100);
+        schedule.record_execution(100, 95, 1_000).unwrap();
+
+        assert!(schedule.next_execution_amount(1_059).is_err());
+        assert_eq!(schedule.next_execution_amount(1_060).unwrap(), 100);
+        schedule.record_execution(100, 94, 1_060).unwrap();
+
+        // The last execution swaps what is left of the deposit
+        assert_eq!(schedule.next_execution_amount(1_200).unwrap(), 50);
+        schedule.record_execution(50, 47, 1_200).unwrap();
+
+        assert_eq!(schedule.amount_in, 0);
+        assert_eq!(schedule.amount_out, 236);
+        assert!(schedule.next_execution_amount(2_000).is_err());
+    }
+}
@@ -0,0 +1,363 @@
+use anchor_lang::prelude::*;
+use std::convert::TryInto;
+
+use crate::errors::ErrorCode;
+
+use super::{
+    AiDexPool, Tick, TickArrayType, TickUpdate, NUM_REWARDS, TICK_ARRAY_SIZE,
+    TICK_ARRAY_SIZE_USIZE,
+};
+
+// Byte offsets of the header fields, relative to the end of the account discriminator.
+const START_TICK_INDEX_OFFSET: usize = 0;
+const AI_DEX_POOL_OFFSET: usize = 4;
+const TICK_BITMAP_OFFSET: usize = 36;
+const TICKS_OFFSET: usize = 52;
+
+// Returned for ticks that are not stored in a dynamic tick array.
+static UNINITIALIZED_TICK: Tick = Tick {
+    initialized: false,
+    liquidity_net: 0,
+    liquidity_gross: 0,
+    fee_growth_outside_a: 0,
+    fee_growth_outside_b: 0,
+    reward_growths_outside: [0; NUM_REWARDS],
+    seconds_per_liquidity_outside_x64: 0,
+};
+
+/// Header of a tick array that only stores its initialized ticks.
+///
+/// The initialized ticks follow the header in offset order, one `Tick` for each bit set in
+/// `tick_bitmap`. The account grows when a tick is initialized and shrinks when one is
+/// uninitialized, so pools with sparse liquidity pay less rent than with a `TickArray`.
+#[account]
+#[derive(Default)]
+pub struct DynamicTickArray {
+    pub start_tick_index: i32,
+    pub ai_dex_pool: Pubkey,
+    pub tick_bitmap: u128,
+}
+
+impl DynamicTickArray {
+    pub const MIN_LEN: usize = 8 + TICKS_OFFSET;
+    pub const MAX_LEN: usize = Self::MIN_LEN + Tick::LEN * TICK_ARRAY_SIZE_USIZE;
+
+    /// Returns the account size needed to store the given number of initialized ticks.
+    pub fn len_for_ticks(initialized_tick_count: usize) -> usize {
+        Self::MIN_LEN + Tick::LEN * initialized_tick_count
+    }
+
+    /// Initialize the DynamicTickArray object with no initialized ticks
+    ///
+    /// # Parameters
+    /// - `ai_dex` - The pool this tick array belongs to
+    /// - `start_tick_index` - The start tick index of this tick array
+    ///
+    /// # Errors
+    /// - `InvalidStartTickIndex`: - The provided start-tick-index is not an initializable tick index in this AiDex w/ this tick-spacing.
+    pub fn initialize(
+        &mut self,
+        ai_dex: &AccountLoader<AiDexPool>,
+        start_tick_index: i32,
+    ) -> Result<()> {
+        let tick_spacing = ai_dex.load()?.tick_spacing;
+        if !Tick::check_is_valid_start_tick(start_tick_index, tick_spacing) {
+            return Err(ErrorCode::InvalidStartTickIndex.into());
+        }
+
+        self.ai_dex_pool = ai_dex.key();
+        self.start_tick_index = start_tick_index;
+        self.tick_bitmap = 0;
+        Ok(())
+    }
+}
+
+/// Zero-copy view over the data of a `DynamicTickArray` account, after its discriminator.
+#[repr(transparent)]
+pub struct DynamicTickArrayLoader([u8]);
+
+impl DynamicTickArrayLoader {
+    pub fn load(data: &[u8]) -> &DynamicTickArrayLoader {
+        // Safe because DynamicTickArrayLoader is a transparent wrapper around [u8]
+        unsafe { &*(data as *const [u8] as *const DynamicTickArrayLoader) }
+    }
+
+    pub fn load_mut(data: &mut [u8]) -> &mut DynamicTickArrayLoader {
+        // Safe because DynamicTickArrayLoader is a transparent wrapper around [u8]
+        unsafe { &mut *(data as *mut [u8] as *mut DynamicTickArrayLoader) }
+    }
+
+    pub fn tick_bitmap(&self) -> u128 {
+        u128::from_le_bytes(self.0[TICK_BITMAP_OFFSET..TICKS_OFFSET].try_into().unwrap())
+    }
+
+    fn set_tick_bitmap(&mut self, tick_bitmap: u128) {
+        self.0[TICK_BITMAP_OFFSET..TICKS_OFFSET].copy_from_slice(&tick_bitmap.to_le_bytes());
+    }
+
+    pub fn initialized_tick_count(&self) -> usize {
+        self.tick_bitmap().count_ones() as usize
+    }
+
+    fn is_offset_initialized(&self, offset: usize) -> bool {
+        self.tick_bitmap() & (1u128 << offset) != 0
+    }
+
+    // Byte position of the tick at the given offset, after the initialized ticks before it
+    fn tick_position(&self, offset: usize) -> usize {
+        let ticks_before = (self.tick_bitmap() & ((1u128 << offset) - 1)).count_ones() as usize;
+        TICKS_OFFSET + Tick::LEN * ticks_before
+    }
+
+    fn stored_tick(&self, offset: usize) -> &Tick {
+        let position = self.tick_position(offset);
+        let bytes = &self.0[position..position + Tick::LEN];
+        // Safe because Tick is a packed plain-old-data struct of Tick::LEN bytes
+        unsafe { &*(bytes.as_ptr() as *const Tick) }
+    }
+
+    fn stored_tick_mut(&mut self, offset: usize) -> &mut Tick {
+        let position = self.tick_position(offset);
+        let bytes = &mut self.0[position..position + Tick::LEN];
+        // Safe because Tick is a packed plain-old-data struct of Tick::LEN bytes
+        unsafe { &mut *(bytes.as_mut_ptr() as *mut Tick) }
+    }
+
+    // Offset of a usable tick index inside this array
+    fn usable_tick_offset(&self, tick_index: i32, tick_spacing: u16) -> Result<usize> {
+        if !self.check_in_array_bounds(tick_index, tick_spacing)
+            || !Tick::check_is_usable_tick(tick_index, tick_spacing)
+        {
+            return Err(ErrorCode::TickNotFoundError.into());
+        }
+        let offset = self.tick_offset(tick_index, tick_spacing)?;
+        if offset < 0 {
+            return Err(ErrorCode::TickNotFoundError.into());
+        }
+        Ok(offset as usize)
+    }
+
+    /// Stores a newly initialized tick.
+    ///
+    /// The data must already have room for one more tick at its end.
+    ///
+    /// # Errors
+    /// - `TickNotFoundError`: - The provided tick-index is not an initializable tick index in this AiDex w/ this tick-spacing.
+    /// - `DynamicTickArrayResizeError` - The tick is already stored or there is no room for it.
+    pub fn insert_tick(
+        &mut self,
+        tick_index: i32,
+        tick_spacing: u16,
+        update: &TickUpdate,
+    ) -> Result<()> {
+        let offset = self.usable_tick_offset(tick_index, tick_spacing)?;
+        let used_len = TICKS_OFFSET + Tick::LEN * self.initialized_tick_count();
+        if self.is_offset_initialized(offset) || self.0.len() < used_len + Tick::LEN {
+            return Err(ErrorCode::DynamicTickArrayResizeError.into());
+        }
+
+        let position = self.tick_position(offset);
+        self.0.copy_within(position..used_len, position + Tick::LEN);
+        self.set_tick_bitmap(self.tick_bitmap() | (1u128 << offset));
+        self.stored_tick_mut(offset).update(update);
+        Ok(())
+    }
+
+    /// Removes a stored tick, leaving `Tick::LEN` zeroed bytes at the end of the data.
+    ///
+    /// # Errors
+    /// - `TickNotFoundError`: - The provided tick-index is not an initializable tick index in this AiDex w/ this tick-spacing.
+    /// - `DynamicTickArrayResizeError` - The tick is not stored.
+    pub fn remove_tick(&mut self, tick_index: i32, tick_spacing: u16) -> Result<()> {
+        let offset = self.usable_tick_offset(tick_index, tick_spacing)?;
+        if !self.is_offset_initialized(offset) {
+            return Err(ErrorCode::DynamicTickArrayResizeError.into());
+        }
+
+        let used_len = TICKS_OFFSET + Tick::LEN * self.initialized_tick_count();
+        let position = self.tick_position(offset);
+        self.0.copy_within(position + Tick::LEN..used_len, position);
+        self.0[used_len - Tick::LEN..used_len].fill(0);
+        self.set_tick_bitmap(self.tick_bitmap() & !(1u128 << offset));
+        Ok(())
+    }
+}
+
+impl TickArrayType for DynamicTickArrayLoader {
+    fn start_tick_index(&self) -> i32 {
+        i32::from_le_bytes(
+            self.0[START_TICK_INDEX_OFFSET..AI_DEX_POOL_OFFSET]
+                .try_into()
+                .unwrap(),
+        )
+    }
+
+    fn ai_dex_pool(&self) -> Pubkey {
+        Pubkey::new_from_array(self.0[AI_DEX_POOL_OFFSET..TICK_BITMAP_OFFSET].try_into().unwrap())
+    }
+
+    fn get_next_init_tick_index(
+        &self,
+        tick_index: i32,
+        tick_spacing: u16,
+        a_to_b: bool,
+    ) -> Result<Option<i32>> {
+        if !self.in_search_range(tick_index, tick_spacing, !a_to_b) {
+            return Err(ErrorCode::InvalidTickArraySequenceError.into());
+        }
+
+        let mut curr_offset = self.tick_offset(tick_index, tick_spacing)? as i32;
+
+        // For b_to_a searches, the next possible init-tick cannot be within the current offset
+        if !a_to_b {
+            curr_offset += 1;
+        }
+
+        while curr_offset >= 0 && curr_offset < TICK_ARRAY_SIZE {
+            if self.is_offset_initialized(curr_offset as usize) {
+                return Ok(Some(
+                    (curr_offset * tick_spacing as i32) + self.start_tick_index(),
+                ));
+            }
+
+            curr_offset = if a_to_b {
+                curr_offset - 1
+            } else {
+                curr_offset + 1
+            };
+        }
+
+        Ok(None)
+    }
+
+    fn get_tick(&self, tick_index: i32, tick_spacing: u16) -> Result<&Tick> {
+        let offset = self.usable_tick_offset(tick_index, tick_spacing)?;
+        if !self.is_offset_initialized(offset) {
+            return Ok(&UNINITIALIZED_TICK);
+        }
+        Ok(self.stored_tick(offset))
+    }
+
+    /// Updates a stored tick in place.
+    ///
+    /// Initializing or uninitializing a tick changes the size of the account, which is done
+    /// with `insert_tick` and `remove_tick` after resizing the account instead.
+    fn update_tick(
+        &mut self,
+        tick_index: i32,
+        tick_spacing: u16,
+        update: &TickUpdate,
+    ) -> Result<()> {
+        let offset = self.usable_tick_offset(tick_index, tick_spacing)?;
+        match (self.is_offset_initialized(offset), update.initialized) {
+            (true, true) => {
+                self.stored_tick_mut(offset).update(update);
+                Ok(())
+            }
+            // Uninitialized ticks are not stored
+            (false, false) => Ok(()),
+            _ => Err(ErrorCode::DynamicTickArrayResizeError.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod dynamic_tick_array_tests {
+    use super::*;
+
+    const TS_8: u16 = 8;
+
+    fn build_dynamic_tick_array(start_tick_index: i32, initialized_tick_count: usize) -> Vec<u8> {
+        let mut data = vec![0u8; DynamicTickArray::len_for_ticks(initialized_tick_count) - 8];
+        data[START_TICK_INDEX_OFFSET..AI_DEX_POOL_OFFSET]
+            .copy_from_slice(&start_tick_index.to_le_bytes());
+        data
+    }
+
+    fn initialized_update(liquidity_gross: u128) -> TickUpdate {
+        TickUpdate {
+            initialized: true,
+            liquidity_gross,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_insert_keeps_ticks_in_offset_order() {
+        let mut data = build_dynamic_tick_array(0, 3);
+        let array = DynamicTickArrayLoader::load_mut(&mut data);
+        array.insert_tick(40, TS_8, &initialized_update(5)).unwrap();
+        array.insert_tick(8, TS_8, &initialized_update(1)).unwrap();
+        array.insert_tick(16, TS_8, &initialized_update(2)).unwrap();
+
+        assert_eq!(array.initialized_tick_count(), 3);
+        assert_eq!({ array.get_tick(8, TS_8).unwrap().liquidity_gross }, 1);
+        assert_eq!({ array.get_tick(16, TS_8).unwrap().liquidity_gross }, 2);
+        assert_eq!({ array.get_tick(40, TS_8).unwrap().liquidity_gross }, 5);
+        assert!(!array.get_tick(24, TS_8).unwrap().initialized);
+    }
+
+    #[test]
+    fn test_insert_requires_room() {
+        let mut data = build_dynamic_tick_array(0, 1);
+        let array = DynamicTickArrayLoader::load_mut(&mut data);
+        array.insert_tick(8, TS_8, &initialized_update(1)).unwrap();
+
+        assert_eq!(
+            array.insert_tick(16, TS_8, &initialized_update(2)).unwrap_err(),
+            ErrorCode::DynamicTickArrayResizeError.into()
+        );
+        assert_eq!(
+            array.insert_tick(8, TS_8, &initialized_update(2)).unwrap_err(),
+            ErrorCode::DynamicTickArrayResizeError.into()
+        );
+    }
+
+    #[test]
+    fn test_remove_tick() {
+        let mut data = build_dynamic_tick_array(-704, 2);
+        let array = DynamicTickArrayLoader::load_mut(&mut data);
+        array.insert_tick(-704, TS_8, &initialized_update(1)).unwrap();
+        array.insert_tick(-8, TS_8, &initialized_update(2)).unwrap();
+        array.remove_tick(-704, TS_8).unwrap();
+
+        assert_eq!(array.initialized_tick_count(), 1);
+        assert!(!array.get_tick(-704, TS_8).unwrap().initialized);
+        assert_eq!({ array.get_tick(-8, TS_8).unwrap().liquidity_gross }, 2);
+        assert_eq!(
+            array.remove_tick(-704, TS_8).unwrap_err(),
+            ErrorCode::DynamicTickArrayResizeError.into()
+        );
+    }
+
+    #[test]
+    fn test_update_tick_does_not_resize() {
+        let mut data = build_dynamic_tick_array(0, 1);
+        let array = DynamicTickArrayLoader::load_mut(&mut data);
+        array.insert_tick(8, TS_8, &initialized_update(1)).unwrap();
+
+        array.update_tick(8, TS_8, &initialized_update(3)).unwrap();
+        assert_eq!({ array.get_tick(8, TS_8).unwrap().liquidity_gross }, 3);
+        array.update_tick(16, TS_8, &TickUpdate::default()).unwrap();
+        assert_eq!(
+            array.update_tick(16, TS_8, &initialized_update(1)).unwrap_err(),
+            ErrorCode::DynamicTickArrayResizeError.into()
+        );
+        assert_eq!(
+            array.update_tick(8, TS_8, &TickUpdate::default()).unwrap_err(),
+            ErrorCode::DynamicTickArrayResizeError.into()
+        );
+    }
+
+    #[test]
+    fn test_get_next_init_tick_index() {
+        let mut data = build_dynamic_tick_array(0, 2);
+        let array = DynamicTickArrayLoader::load_mut(&mut data);
+        array.insert_tick(80, TS_8, &initialized_update(1)).unwrap();
+        array.insert_tick(320, TS_8, &initialized_update(1)).unwrap();
+
+        assert_eq!(array.get_next_init_tick_index(200, TS_8, true).unwrap(), Some(80));
+        assert_eq!(array.get_next_init_tick_index(200, TS_8, false).unwrap(), Some(320));
+        assert_eq!(array.get_next_init_tick_index(72, TS_8, true).unwrap(), None);
+    }
+}
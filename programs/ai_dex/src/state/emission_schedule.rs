@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+use super::NUM_REWARDS;
+
+pub const MAX_EMISSION_SEGMENTS: usize = 8;
+
+/// A period during which a reward is emitted at a constant rate.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EmissionSegment {
+    pub start_timestamp: u64, // 8
+    pub end_timestamp: u64, // 8
+    pub emissions_per_second_x64: u128, // 16
+}
+
+#[account]
+#[derive(Default)]
+pub struct EmissionSchedule {
+    pub ai_dex_pool: Pubkey, // 32
+    pub reward_index: u8, // 1
+    pub segments: [EmissionSegment; MAX_EMISSION_SEGMENTS], // 32 * 8
+                                                           // 64 RESERVE
+}
+
+/// The emission rates of a reward of a pool over time, applied by a permissionless crank.
+///
+/// Unused slots hold the default segment, which is never active.
+impl EmissionSchedule {
+    pub const LEN: usize = 8 + 32 + 1 + 32 * MAX_EMISSION_SEGMENTS + 64;
+
+    /// Initializes the emission schedule of a reward of a pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `ai_dex_pool` - The pool the reward belongs to.
+    /// * `reward_index` - The index of the reward.
+    /// * `segments` - The emission segments.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidRewardIndexError` - If the reward index is out of bounds.
+    /// * `InvalidEmissionScheduleError` - If the segments are invalid.
+    pub fn initialize(&mut self, ai_dex_pool: Pubkey, reward_index: u8, segments: &[EmissionSegment]) -> Result<()> {
+        if reward_index as usize >= NUM_REWARDS {
+            return Err(ErrorCode::InvalidRewardIndexError.into());
+        }
+        self.ai_dex_pool = ai_dex_pool;
+        self.reward_index = reward_index;
+        self.set_segments(segments)
+    }
+
+    /// Replaces the emission segments. Passing no segment stops the emissions at the next crank.
+    ///
+    /// # Arguments
+    ///
+    /// * `segments` - The emission segments, sorted by time.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidEmissionScheduleError` - If there are too many segments, or a segment is empty or
+    ///   starts before the end of the previous one.
+    pub fn set_segments(&mut self, segments: &[EmissionSegment]) -> Result<()> {
+        if segments.len() > MAX_EMISSION_SEGMENTS {
+            return Err(ErrorCode::InvalidEmissionScheduleError.into());
+        }
+        let mut previous_end_timestamp = 0;
+        for segment in segments {
+            if segment.start_timestamp >= segment.end_timestamp || segment.start_timestamp < previous_end_timestamp {
+                return Err(ErrorCode::InvalidEmissionScheduleError.into());
+            }
+            previous_end_timestamp = segment.end_timestamp;
+        }
+
+        self.segments = [EmissionSegment::default(); MAX_EMISSION_SEGMENTS];
+        self.segments[..segments.len()].copy_from_slice(segments);
+        Ok(())
+    }
+
+    /// Returns the segment active at the given timestamp, if any.
+    pub fn active_segment(&self, timestamp: u64) -> Option<EmissionSegment> {
+        self.segments
+            .iter()
+            .find(|segment| segment.start_timestamp <= timestamp && timestamp < segment.end_timestamp)
+            .copied()
+    }
+}
+
+#[cfg(test)]
+mod emission_schedule_tests {
+    use super::*;
+
+    fn segment(start_timestamp: u64, end_timestamp: u64, emissions_per_second_x64: u128) -> EmissionSegment {
+        EmissionSegment { start_timestamp, end_timestamp, emissions_per_second_x64 }
+    }
+
+    #[test]
+    fn test_active_segment() {
+        let mut schedule = EmissionSchedule::default();
+        schedule
+            .initialize(Pubkey::new_unique(), 0, &[segment(100, 200, 3), segment(200, 300, 2), segment(400, 500, 1)])
+            .unwrap();
+
+        assert_eq!(schedule.active_segment(99), None);
+        assert_eq!(schedule.active_segment(100), Some(segment(100, 200, 3)));
+        assert_eq!(schedule.active_segment(200), Some(segment(200, 300, 2)));
+        assert_eq!(schedule.active_segment(350), None);
+        assert_eq!(schedule.active_segment(499), Some(segment(400, 500, 1)));
+        assert_eq!(schedule.active_segment(500), None);
+        assert_eq!(schedule.active_segment(0), None);
+    }
+
+    #[test]
+    fn test_set_segments_invalid() {
+        let mut schedule = EmissionSchedule::default();
+        assert!(schedule.initialize(Pubkey::new_unique(), NUM_REWARDS as u8, &[]).is_err());
+        assert!(schedule.set_segments(&[segment(200, 200, 1)]).is_err());
+        assert!(schedule.set_segments(&[segment(100, 200, 1), segment(150, 300, 1)]).is_err());
+        assert!(schedule.set_segments(&[]).is_ok());
+        let too_many: Vec<EmissionSegment> = (0..=MAX_EMISSION_SEGMENTS as u64)
+            .map(|i| segment(i * 10, i * 10 + 10, 1))
+            .collect();
+        assert!(schedule.set_segments(&too_many).is_err());
+        assert!(schedule.set_segments(&too_many[..MAX_EMISSION_SEGMENTS]).is_ok());
+    }
+}
@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+pub const MAX_FEE_DISCOUNT_TIERS: usize = 8;
+// Max fee discount supported is 50% of the fee rate.
+pub const MAX_FEE_DISCOUNT_BPS: u16 = 5_000;
+pub const FEE_DISCOUNT_BPS_DENOMINATOR: u16 = 10_000;
+
+/// A fee discount granted to the holders of at least `min_amount` tokens of `mint`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeDiscountTier {
+    pub mint: Pubkey, // 32
+    pub min_amount: u64, // 8
+    pub discount_bps: u16, // 2
+}
+
+#[account]
+#[derive(Default)]
+pub struct FeeDiscountRegistry {
+    pub ai_dex_config: Pubkey, // 32
+    pub tiers: [FeeDiscountTier; MAX_FEE_DISCOUNT_TIERS], // 42 * 8
+                                                         // 64 RESERVE
+}
+
+/// The fee discounts of the swaps of a config, granted to the holders of a token or NFT.
+///
+/// Unused slots hold the default tier, which never matches.
+impl FeeDiscountRegistry {
+    pub const LEN: usize = 8 + 32 + 42 * MAX_FEE_DISCOUNT_TIERS + 64;
+
+    /// Initializes the fee discount registry of a config.
+    ///
+    /// # Arguments
+    ///
+    /// * `ai_dex_config` - The config the registry belongs to.
+    /// * `tiers` - The fee discount tiers.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidFeeDiscountTiersError` - If the tiers are invalid.
+    pub fn initialize(&mut self, ai_dex_config: Pubkey, tiers: &[FeeDiscountTier]) -> Result<()> {
+        self.ai_dex_config = ai_dex_config;
+        self.set_tiers(tiers)
+    }
+
+    /// Replaces the fee discount tiers. Passing no tier disables the discounts.
+    ///
+    /// # Arguments
+    ///
+    /// * `tiers` - The fee discount tiers.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidFeeDiscountTiersError` - If there are too many tiers, or a tier has no mint, a
+    ///   zero discount or a discount above `MAX_FEE_DISCOUNT_BPS`.
+    pub fn set_tiers(&mut self, tiers: &[FeeDiscountTier]) -> Result<()> {
+        if tiers.len() > MAX_FEE_DISCOUNT_TIERS {
+            return Err(ErrorCode::InvalidFeeDiscountTiersError.into());
+        }
+        for tier in tiers {
+            if tier.mint == Pubkey::default()
+                || tier.discount_bps == 0
+                || tier.discount_bps > MAX_FEE_DISCOUNT_BPS
+            {
+                return Err(ErrorCode::InvalidFeeDiscountTiersError.into());
+            }
+        }
+
+        self.tiers = [FeeDiscountTier::default(); MAX_FEE_DISCOUNT_TIERS];
+        self.tiers[..tiers.len()].copy_from_slice(tiers);
+        Ok(())
+    }
+
+    /// Returns the fee discount granted to the holder of an amount of tokens of a mint.
+    ///
+    /// If several tiers match, the largest discount applies.
+    pub fn discount_bps(&self, mint: &Pubkey, amount: u64) -> u16 {
+        self.tiers
+            .iter()
+            .filter(|tier| tier.discount_bps > 0 && tier.mint == *mint && amount >= tier.min_amount)
+            .map(|tier| tier.discount_bps)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod fee_discount_registry_tests {
+    use super::*;
+
+    #[test]
+    fn test_discount_bps() {
+        let mint = Pubkey::new_unique();
+        let mut registry = FeeDiscountRegistry::default();
+        registry
+            .initialize(
+                Pubkey::new_unique(),
+                &[
+                    FeeDiscountTier { mint, min_amount: 100, discount_bps: 1_000 },
+                    FeeDiscountTier { mint, min_amount: 1_000, discount_bps: 2_500 },
+                ],
+            )
+            .unwrap();
+
+        assert_eq!(registry.discount_bps(&mint, 99), 0);
+        assert_eq!(registry.discount_bps(&mint, 100), 1_000);
+        assert_eq!(registry.discount_bps(&mint, 5_000), 2_500);
+        assert_eq!(registry.discount_bps(&Pubkey::new_unique(), 5_000), 0);
+        assert_eq!(registry.discount_bps(&Pubkey::default(), 0), 0);
+    }
+
+    #[test]
+    fn test_set_tiers_invalid() {
+        let mint = Pubkey::new_unique();
+        let mut registry = FeeDiscountRegistry::default();
+        assert!(registry
+            .set_tiers(&[FeeDiscountTier { mint, min_amount: 1, discount_bps: MAX_FEE_DISCOUNT_BPS + 1 }])
+            .is_err());
+        assert!(registry.set_tiers(&[FeeDiscountTier { mint, min_amount: 1, discount_bps: 0 }]).is_err());
+        assert!(registry
+            .set_tiers(&[FeeDiscountTier { mint: Pubkey::default(), min_amount: 1, discount_bps: 100 }])
+            .is_err());
+        assert!(registry
+            .set_tiers(&[FeeDiscountTier { mint, min_amount: 1, discount_bps: 100 }; MAX_FEE_DISCOUNT_TIERS + 1])
+            .is_err());
+
+        registry.set_tiers(&[FeeDiscountTier { mint, min_amount: 1, discount_bps: 100 }]).unwrap();
+        registry.set_tiers(&[]).unwrap();
+        assert_eq!(registry.discount_bps(&mint, 1), 0);
+    }
+}
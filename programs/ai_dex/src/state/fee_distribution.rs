@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+pub const MAX_FEE_RECIPIENTS: usize = 8;
+pub const FEE_DISTRIBUTION_BPS_DENOMINATOR: u16 = 10_000;
+
+/// A recipient of the protocol fees and its share, in basis points.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeRecipient {
+    pub owner: Pubkey, // 32
+    pub weight_bps: u16, // 2
+}
+
+#[account]
+#[derive(Default)]
+pub struct FeeDistribution {
+    pub ai_dex_config: Pubkey, // 32
+    pub recipients: [FeeRecipient; MAX_FEE_RECIPIENTS], // 34 * 8
+                                                        // 64 RESERVE
+}
+
+/// The split of the protocol fees of a config across its recipients.
+///
+/// Unused slots hold the default recipient. The weights of the recipients always sum up to
+/// `FEE_DISTRIBUTION_BPS_DENOMINATOR`.
+impl FeeDistribution {
+    pub const LEN: usize = 8 + 32 + 34 * MAX_FEE_RECIPIENTS + 64;
+
+    /// Initializes the fee distribution of a config.
+    ///
+    /// # Arguments
+    ///
+    /// * `ai_dex_config` - The config the fee distribution belongs to.
+    /// * `recipients` - The recipients of the protocol fees.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidFeeDistributionError` - If the recipients are invalid.
+    pub fn initialize(&mut self, ai_dex_config: Pubkey, recipients: &[FeeRecipient]) -> Result<()> {
+        self.ai_dex_config = ai_dex_config;
+        self.set_recipients(recipients)
+    }
+
+    /// Replaces the recipients of the protocol fees.
+    ///
+    /// # Arguments
+    ///
+    /// * `recipients` - The recipients of the protocol fees.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidFeeDistributionError` - If there are no or too many recipients, a recipient is
+    ///   the default pubkey, has a zero weight or is listed twice, or the weights do not sum up
+    ///   to `FEE_DISTRIBUTION_BPS_DENOMINATOR`.
+    pub fn set_recipients(&mut self, recipients: &[FeeRecipient]) -> Result<()> {
+        if recipients.is_empty() || recipients.len() > MAX_FEE_RECIPIENTS {
+            return Err(ErrorCode::InvalidFeeDistributionError.into());
+        }
+
+        let mut total_weight_bps: u32 = 0;
+        for (i, recipient) in recipients.iter().enumerate() {
+            if recipient.owner == Pubkey::default()
+                || recipient.weight_bps == 0
+                || recipients[..i].iter().any(|other| other.owner == recipient.owner)
+            {
+                return Err(ErrorCode::InvalidFeeDistributionError.into());
+            }
+            total_weight_bps += recipient.weight_bps as u32;
+        }
+        if total_weight_bps != FEE_DISTRIBUTION_BPS_DENOMINATOR as u32 {
+            return Err(ErrorCode::InvalidFeeDistributionError.into());
+        }
+
+        self.recipients = [FeeRecipient::default(); MAX_FEE_RECIPIENTS];
+        self.recipients[..recipients.len()].copy_from_slice(recipients);
+        Ok(())
+    }
+
+    /// Returns the recipients of the protocol fees, skipping the unused slots.
+    pub fn active_recipients(&self) -> impl Iterator<Item = &FeeRecipient> {
+        self.recipients.iter().filter(|recipient| recipient.weight_bps > 0)
+    }
+
+    /// Returns the share of an amount owed to a recipient, rounded down.
+    ///
+    /// The rounding dust of a split is left to the caller.
+    pub fn share(recipient: &FeeRecipient, amount: u64) -> u64 {
+        ((amount as u128 * recipient.weight_bps as u128) / FEE_DISTRIBUTION_BPS_DENOMINATOR as u128) as u64
+    }
+}
+
+#[cfg(test)]
+mod fee_distribution_tests {
+    use super::*;
+
+    fn recipient(weight_bps: u16) -> FeeRecipient {
+        FeeRecipient {
+            owner: Pubkey::new_unique(),
+            weight_bps,
+        }
+    }
+
+    #[test]
+    fn test_set_recipients() {
+        let mut fee_distribution = FeeDistribution::default();
+        let recipients = [recipient(6_000), recipient(3_000), recipient(1_000)];
+        fee_distribution.initialize(Pubkey::new_unique(), &recipients).unwrap();
+        assert_eq!(fee_distribution.active_recipients().count(), 3);
+
+        fee_distribution.set_recipients(&[recipient(10_000)]).unwrap();
+        assert_eq!(fee_distribution.active_recipients().count(), 1);
+    }
+
+    #[test]
+    fn test_set_recipients_invalid() {
+        let mut fee_distribution = FeeDistribution::default();
+        assert!(fee_distribution.set_recipients(&[]).is_err());
+        assert!(fee_distribution.set_recipients(&[recipient(5_000), recipient(4_999)]).is_err());
+        assert!(fee_distribution.set_recipients(&[recipient(10_000), recipient(0)]).is_err());
+        assert!(fee_distribution
+            .set_recipients(&[FeeRecipient { owner: Pubkey::default(), weight_bps: 10_000 }])
+            .is_err());
+        assert!(fee_distribution.set_recipients(&[recipient(1_250); MAX_FEE_RECIPIENTS + 1]).is_err());
+
+        let duplicate = recipient(5_000);
+        assert!(fee_distribution.set_recipients(&[duplicate, duplicate]).is_err());
+    }
+
+    #[test]
+    fn test_share() {
+        let recipients = [recipient(6_000), recipient(3_000), recipient(1_000)];
+        let shares: Vec<u64> = recipients.iter().map(|r| FeeDistribution::share(r, 1_001)).collect();
+        assert_eq!(shares, vec![600, 300, 100]);
+        assert_eq!(FeeDistribution::share(&recipients[0], u64::MAX), (u64::MAX as u128 * 6 / 10) as u64);
+    }
+}
@@ -2,17 +2,31 @@ use crate::state::AiDexConfig;
 use crate::{errors::ErrorCode, math::MAX_FEE_RATE};
 use anchor_lang::prelude::*;
 
+/// The parameters of a fee tier to initialize.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeTierParams {
+    pub tick_spacing: u16,
+    pub default_fee_rate: u16,
+}
+
+// Version of the current layout of a fee tier, raised whenever fields are appended to it
+pub const FEE_TIER_VERSION: u8 = 2;
+
 #[account]
 pub struct FeeTier {
     pub ai_dex_config: Pubkey,
     pub tick_spacing: u16,
     pub default_fee_rate: u16,
+    pub version: u8,
+    // Whether the pools initialized from this fee tier only allow full range positions
+    pub full_range_only: bool,
+    // 63 RESERVE
 }
 
 /// Represents a fee tier in the AiDex system.
 impl FeeTier {
     /// The length of a fee tier in bytes.
-    pub const LEN: usize = 8 + 32 + 4;
+    pub const LEN: usize = 8 + 32 + 4 + 1 + 1 + 63;
 
     /// Initializes the fee tier with the given parameters.
     ///
@@ -33,6 +47,7 @@ impl FeeTier {
     ) -> Result<()> {
         self.ai_dex_config = ai_dex_config.key();
         self.tick_spacing = tick_spacing;
+        self.version = FEE_TIER_VERSION;
         self.update_default_fee_rate(default_fee_rate)?;
         Ok(())
     }
@@ -54,4 +69,15 @@ impl FeeTier {
 
         Ok(())
     }
+
+    /// Sets whether the pools initialized from the fee tier only allow full range positions.
+    ///
+    /// The pools already initialized from the fee tier are unaffected.
+    ///
+    /// # Arguments
+    ///
+    /// * `full_range_only` - Whether positions can only be opened on the full range of ticks.
+    pub fn set_full_range_only(&mut self, full_range_only: bool) {
+        self.full_range_only = full_range_only;
+    }
 }
@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+use super::Tick;
+
+#[account]
+#[derive(Default)]
+pub struct LimitOrder {
+    pub ai_dex_pool: Pubkey, // 32
+    pub owner: Pubkey,       // 32
+    pub tick_index: i32,     // 4
+    // Sells token A for token B if true, token B for token A otherwise
+    pub a_to_b: bool,        // 1
+
+    // Input token not filled yet
+    pub amount_in: u64,  // 8
+    // Output token received from fills
+    pub amount_out: u64, // 8
+    // 64 RESERVE
+}
+
+/// Struct representing an order resting at a single tick of a pool.
+///
+/// The order sells its input token at the exact price of its tick. The input and the output
+/// tokens are held by the vaults of the pool until the order is cancelled.
+impl LimitOrder {
+    /// Length of the `LimitOrder` struct in bytes.
+    pub const LEN: usize = 8 + 32 + 32 + 4 + 1 + 8 + 8 + 64;
+
+    /// Initializes the `LimitOrder` struct.
+    ///
+    /// # Arguments
+    ///
+    /// * `ai_dex_pool` - The pool pubkey.
+    /// * `owner` - The owner of the order.
+    /// * `tick_index` - The tick the order rests at.
+    /// * `a_to_b` - The direction of the order.
+    /// * `amount_in` - The amount of input token of the order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tick index is out of bounds or the amount is zero.
+    pub fn initialize(
+        &mut self,
+        ai_dex_pool: Pubkey,
+        owner: Pubkey,
+        tick_index: i32,
+        a_to_b: bool,
+        amount_in: u64,
+    ) -> Result<()> {
+        if Tick::check_is_out_of_bounds(tick_index) {
+            return Err(ErrorCode::InvalidTickIndexError.into());
+        }
+        if amount_in == 0 {
+            return Err(ErrorCode::NoTradableAmountError.into());
+        }
+
+        self.ai_dex_pool = ai_dex_pool;
+        self.owner = owner;
+        self.tick_index = tick_index;
+        self.a_to_b = a_to_b;
+        self.amount_in = amount_in;
+        self.amount_out = 0;
+        Ok(())
+    }
+
+    /// Records a fill of the order.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount_in` - The amount of input token bought by the taker.
+    /// * `amount_out` - The amount of output token paid by the taker.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the fill exceeds the open amount of the order.
+    pub fn fill(&mut self, amount_in: u64, amount_out: u64) -> Result<()> {
+        self.amount_in = self
+            .amount_in
+            .checked_sub(amount_in)
+            .ok_or(ErrorCode::LimitOrderFillExceedsAmountError)?;
+        self.amount_out = self
+            .amount_out
+            .checked_add(amount_out)
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod limit_order_tests {
+    use super::*;
+    use crate::state::MAX_TICK_INDEX;
+
+    fn build_order(amount_in: u64) -> LimitOrder {
+        let mut order = LimitOrder::default();
+        order
+            .initialize(Pubkey::new_unique(), Pubkey::new_unique(), 128, true, amount_in)
+            .unwrap();
+        order
+    }
+
+    #[test]
+    fn test_initialize_invalid() {
+        let mut order = LimitOrder::default();
+
+        assert!(order
+            .initialize(Pubkey::new_unique(), Pubkey::new_unique(), 128, true, 0)
+            .is_err());
+        assert!(order
+            .initialize(Pubkey::new_unique(), Pubkey::new_unique(), MAX_TICK_INDEX + 1, true, 100)
+            .is_err());
+    }
+
+    #[test]
+    fn test_partial_and_full_fill() {
+        let mut order = build_order(1_000);
+
+        order.fill(400, 500).unwrap();
+        assert_eq!(order.amount_in, 600);
+        assert_eq!(order.amount_out, 500);
+
+        order.fill(600, 750).unwrap();
+        assert_eq!(order.amount_in, 0);
+        assert_eq!(order.amount_out, 1_250);
+    }
+
+    #[test]
+    fn test_fill_exceeds_amount() {
+        let mut order = build_order(1_000);
+
+        assert!(order.fill(1_001, 0).is_err());
+        assert_eq!(order.amount_in, 1_000);
+    }
+}
@@ -0,0 +1,204 @@
+use anchor_lang::prelude::*;
+use std::convert::TryFrom;
+
+use crate::{errors::ErrorCode, math::checked_mul_div_round_up_if};
+
+use super::{AiDexPool, Position, NUM_REWARDS};
+
+// Decimals of the LP tokens, whose first units are backed by one unit of liquidity each
+pub const LP_TOKEN_DECIMALS: u8 = 6;
+
+#[account]
+#[derive(Default)]
+pub struct LpToken {
+    pub ai_dex_pool: Pubkey, // 32
+    pub lp_mint: Pubkey,     // 32
+    // The full range position held by the protocol on behalf of the LP token holders
+    pub position: Pubkey,    // 32
+    // 64 RESERVE
+}
+
+/// Struct representing the fungible LP token of a full range only pool.
+///
+/// The LP tokens are pro-rata shares of a full range position of the pool held by the protocol,
+/// including the fees and rewards the position has earned but not yet collected. The position has
+/// no position token, its position mint being set to the `LpToken` account, so it can only be
+/// modified through `deposit_lp_token` and `withdraw_lp_token`.
+impl LpToken {
+    /// Length of the `LpToken` struct in bytes.
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 64;
+
+    /// Initializes the `LpToken` struct.
+    ///
+    /// # Arguments
+    ///
+    /// * `ai_dex_pool` - The pool pubkey.
+    /// * `lp_mint` - The mint of the LP token.
+    /// * `position` - The full range position held on behalf of the LP token holders.
+    pub fn initialize(&mut self, ai_dex_pool: Pubkey, lp_mint: Pubkey, position: Pubkey) {
+        self.ai_dex_pool = ai_dex_pool;
+        self.lp_mint = lp_mint;
+        self.position = position;
+    }
+}
+
+/// Calculates the liquidity of the position backing an amount of LP tokens.
+///
+/// The first LP tokens minted are backed by one unit of liquidity each.
+///
+/// # Arguments
+///
+/// * `lp_amount` - The amount of LP tokens.
+/// * `lp_supply` - The supply of the LP token.
+/// * `liquidity` - The liquidity of the position.
+/// * `round_up` - Whether to round up, when LP tokens are minted.
+///
+/// # Errors
+///
+/// Returns an error if the liquidity overflows.
+pub fn get_lp_token_liquidity(
+    lp_amount: u64,
+    lp_supply: u64,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<u128> {
+    if lp_supply == 0 {
+        return Ok(lp_amount.into());
+    }
+
+    Ok(checked_mul_div_round_up_if(
+        lp_amount.into(),
+        liquidity,
+        lp_supply.into(),
+        round_up,
+    )?)
+}
+
+/// Calculates the share of an amount owed by the position backing an amount of LP tokens.
+///
+/// # Arguments
+///
+/// * `lp_amount` - The amount of LP tokens.
+/// * `lp_supply` - The supply of the LP token.
+/// * `amount_owed` - The amount owed by the position, such as its uncollected fees.
+/// * `round_up` - Whether to round up, when LP tokens are minted.
+///
+/// # Errors
+///
+/// Returns an error if the share exceeds the maximum token amount.
+pub fn get_lp_token_amount_owed(
+    lp_amount: u64,
+    lp_supply: u64,
+    amount_owed: u64,
+    round_up: bool,
+) -> Result<u64> {
+    if lp_supply == 0 {
+        return Ok(0);
+    }
+
+    let share = checked_mul_div_round_up_if(
+        lp_amount.into(),
+        amount_owed.into(),
+        lp_supply.into(),
+        round_up,
+    )?;
+    u64::try_from(share).map_err(|_| ErrorCode::TokenLimitExceededError.into())
+}
+
+/// Sweeps the fees and rewards owed by the position of an LP token whose supply is zero, so that
+/// they do not go to the next depositor. The fees are added to the protocol fees of the pool, and
+/// the rewards are no longer outstanding, so they can be withdrawn as surplus of the reward vaults.
+///
+/// # Arguments
+///
+/// * `ai_dex` - The pool of the LP token.
+/// * `position` - The full range position of the LP token.
+///
+/// # Returns
+///
+/// The fees of token A and B, and the rewards, swept from the position.
+///
+/// # Errors
+///
+/// Returns an error if a protocol fee owed overflows.
+pub fn sweep_lp_token_amounts_owed(
+    ai_dex: &mut AiDexPool,
+    position: &mut Position,
+) -> Result<(u64, u64, [u64; NUM_REWARDS])> {
+    let (fee_owed_a, fee_owed_b) = (position.fee_owed_a, position.fee_owed_b);
+    ai_dex.add_protocol_fees_owed(fee_owed_a, fee_owed_b)?;
+    position.reset_fees_owed();
+
+    let mut rewards_owed = [0u64; NUM_REWARDS];
+    for (index, reward_owed) in rewards_owed.iter_mut().enumerate() {
+        *reward_owed = position.reward_infos[index].amount_owed;
+        ai_dex.record_reward_collected(index, *reward_owed);
+        position.update_reward_owed(index, 0);
+    }
+
+    Ok((fee_owed_a, fee_owed_b, rewards_owed))
+}
+
+#[cfg(test)]
+mod lp_token_tests {
+    use super::*;
+    use crate::state::{position_builder::PositionBuilder, PositionRewardInfo};
+
+    #[test]
+    fn test_get_lp_token_liquidity_first_mint() {
+        assert_eq!(get_lp_token_liquidity(1_000, 0, 0, true).unwrap(), 1_000);
+        assert_eq!(get_lp_token_liquidity(1_000, 0, 500, false).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn test_get_lp_token_liquidity_pro_rata() {
+        assert_eq!(get_lp_token_liquidity(1, 3, 1_000, false).unwrap(), 333);
+        assert_eq!(get_lp_token_liquidity(1, 3, 1_000, true).unwrap(), 334);
+        assert_eq!(get_lp_token_liquidity(3, 3, 1_000, false).unwrap(), 1_000);
+        assert_eq!(
+            get_lp_token_liquidity(u64::MAX, u64::MAX, u128::MAX, false).unwrap(),
+            u128::MAX
+        );
+    }
+
+    #[test]
+    fn test_get_lp_token_amount_owed() {
+        assert_eq!(get_lp_token_amount_owed(1_000, 0, 100, true).unwrap(), 0);
+        assert_eq!(get_lp_token_amount_owed(1, 3, 100, false).unwrap(), 33);
+        assert_eq!(get_lp_token_amount_owed(1, 3, 100, true).unwrap(), 34);
+        assert_eq!(get_lp_token_amount_owed(3, 3, u64::MAX, true).unwrap(), u64::MAX);
+        assert!(get_lp_token_amount_owed(4, 3, u64::MAX, true).is_err());
+    }
+
+    #[test]
+    fn test_sweep_lp_token_amounts_owed() {
+        let mut ai_dex = AiDexPool {
+            protocol_fee_owed_a: 10,
+            protocol_fee_owed_b: 20,
+            ..Default::default()
+        };
+        ai_dex.reward_emissions[1].emissions_outstanding = 100;
+
+        let mut position = PositionBuilder::new(-10, 10)
+            .fee_owed_a(3)
+            .fee_owed_b(4)
+            .reward_info(1, PositionRewardInfo { amount_owed: 30, ..Default::default() })
+            .build();
+
+        let (fee_owed_a, fee_owed_b, rewards_owed) =
+            sweep_lp_token_amounts_owed(&mut ai_dex, &mut position).unwrap();
+        assert_eq!((fee_owed_a, fee_owed_b, rewards_owed), (3, 4, [0, 30, 0]));
+        assert_eq!({ ai_dex.protocol_fee_owed_a }, 13);
+        assert_eq!({ ai_dex.protocol_fee_owed_b }, 24);
+        assert_eq!({ ai_dex.reward_emissions[1].emissions_outstanding }, 70);
+        assert!(Position::is_position_empty(&position));
+    }
+
+    #[test]
+    fn test_sweep_lp_token_amounts_owed_overflow() {
+        let mut ai_dex = AiDexPool { protocol_fee_owed_a: u64::MAX, ..Default::default() };
+        let mut position = PositionBuilder::new(-10, 10).fee_owed_a(1).build();
+
+        assert!(sweep_lp_token_amounts_owed(&mut ai_dex, &mut position).is_err());
+    }
+}
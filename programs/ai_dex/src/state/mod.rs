@@ -1,16 +1,62 @@
+pub mod agent_strategy;
 pub mod config;
+pub mod config_extension;
+pub mod dca_schedule;
+pub mod dynamic_tick_array;
+pub mod emission_schedule;
+pub mod fee_discount_registry;
+pub mod fee_distribution;
 pub mod fee_tier;
+pub mod limit_order;
+pub mod lp_token;
+pub mod oracle;
+pub mod pool_allowlist;
+pub mod pool_stats;
 pub mod position;
 pub mod position_trade_batch;
+pub mod range_order;
+pub mod referral_fees;
+pub mod session;
+pub mod swap_hook;
+pub mod swap_permit;
 pub mod tick;
+pub mod tick_array_bitmap;
+pub mod trade_batch_extension;
+pub mod trigger_order;
+pub mod twap_order;
+pub mod vault;
+pub mod versioned;
 pub mod ai_dex;
 pub mod token_wrapper;
 
 pub use self::ai_dex::*;
 pub use ai_dex::NUM_REWARDS;
+pub use agent_strategy::*;
 pub use config::*;
+pub use config_extension::*;
+pub use dca_schedule::*;
+pub use dynamic_tick_array::*;
+pub use emission_schedule::*;
+pub use fee_discount_registry::*;
+pub use fee_distribution::*;
 pub use fee_tier::*;
+pub use limit_order::*;
+pub use lp_token::*;
+pub use oracle::*;
+pub use pool_allowlist::*;
+pub use pool_stats::*;
 pub use position::*;
 pub use position_trade_batch::*;
+pub use range_order::*;
+pub use referral_fees::*;
+pub use session::*;
+pub use swap_hook::*;
+pub use swap_permit::*;
 pub use tick::*;
+pub use tick_array_bitmap::*;
+pub use trade_batch_extension::*;
+pub use trigger_order::*;
+pub use twap_order::*;
+pub use vault::*;
+pub use versioned::*;
 pub use token_wrapper::*;
@@ -0,0 +1,392 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::math::sqrt_price_from_tick_index;
+use crate::state::{MAX_TICK_INDEX, MIN_TICK_INDEX};
+
+// Number of observations stored in the oracle ring buffer.
+pub const ORACLE_OBSERVATION_SIZE: u16 = 256;
+pub const ORACLE_OBSERVATION_SIZE_USIZE: usize = 256;
+
+#[zero_copy(unsafe)]
+#[repr(packed)]
+#[derive(Default, Debug, PartialEq)]
+pub struct Observation {
+    // Total 32 bytes
+    pub timestamp: u64,       // 8
+    pub tick_cumulative: i64, // 8
+    pub liquidity: u128,      // 16
+}
+
+impl Observation {
+    pub const LEN: usize = 32;
+}
+
+#[account(zero_copy(unsafe))]
+#[repr(packed)]
+pub struct Oracle {
+    pub ai_dex_pool: Pubkey,
+    pub observation_index: u16,
+    pub observation_count: u16,
+    pub observations: [Observation; ORACLE_OBSERVATION_SIZE_USIZE],
+}
+
+impl Default for Oracle {
+    #[inline]
+    fn default() -> Oracle {
+        Oracle {
+            ai_dex_pool: Pubkey::default(),
+            observation_index: 0,
+            observation_count: 0,
+            observations: [Observation::default(); ORACLE_OBSERVATION_SIZE_USIZE],
+        }
+    }
+}
+
+impl Oracle {
+    pub const LEN: usize = 8 + 36 + (Observation::LEN * ORACLE_OBSERVATION_SIZE_USIZE);
+
+    /// Initialize the Oracle object and write its first observation
+    ///
+    /// # Parameters
+    /// - `ai_dex_pool` - The pool this oracle observes
+    /// - `timestamp` - The timestamp of the first observation
+    /// - `liquidity` - The in-range liquidity of the pool at `timestamp`
+    pub fn initialize(&mut self, ai_dex_pool: Pubkey, timestamp: u64, liquidity: u128) {
+        self.ai_dex_pool = ai_dex_pool;
+        self.observation_index = 0;
+        self.observation_count = 1;
+        self.observations[0] = Observation {
+            timestamp,
+            tick_cumulative: 0,
+            liquidity,
+        };
+    }
+
+    /// Returns the most recently written observation.
+    pub fn latest_observation(&self) -> Observation {
+        self.observations[self.observation_index as usize]
+    }
+
+    /// Record a new observation, accumulating the tick that was in effect since the latest one.
+    ///
+    /// At most one observation is written per timestamp. Calls with a timestamp that is not
+    /// newer than the latest observation are a no-op.
+    ///
+    /// # Parameters
+    /// - `timestamp` - The current timestamp
+    /// - `tick_current_index` - The pool's tick index before it is updated at `timestamp`
+    /// - `liquidity` - The pool's in-range liquidity before it is updated at `timestamp`
+    pub fn record(&mut self, timestamp: u64, tick_current_index: i32, liquidity: u128) {
+        let latest = self.latest_observation();
+        if timestamp <= latest.timestamp {
+            return;
+        }
+
+        let time_delta = (timestamp - latest.timestamp) as i64;
+        let tick_cumulative = latest
+            .tick_cumulative
+            .wrapping_add((tick_current_index as i64).wrapping_mul(time_delta));
+
+        let next_index = (self.observation_index + 1) % ORACLE_OBSERVATION_SIZE;
+        self.observations[next_index as usize] = Observation {
+            timestamp,
+            tick_cumulative,
+            liquidity,
+        };
+        self.observation_index = next_index;
+        if self.observation_count < ORACLE_OBSERVATION_SIZE {
+            self.observation_count += 1;
+        }
+    }
+
+    /// Returns the observation at the given chronological offset, where offset 0 is the oldest one.
+    fn observation_at(&self, offset: u16) -> Observation {
+        let oldest_index = if self.observation_count < ORACLE_OBSERVATION_SIZE {
+            0
+        } else {
+            (self.observation_index + 1) % ORACLE_OBSERVATION_SIZE
+        };
+        self.observations[((oldest_index + offset) % ORACLE_OBSERVATION_SIZE) as usize]
+    }
+
+    /// Computes the tick cumulative at the given target timestamp.
+    ///
+    /// Between two observations the tick is constant, so values in between are interpolated exactly.
+    /// Targets after the latest observation are extrapolated with the pool's current tick.
+    ///
+    /// # Parameters
+    /// - `target_timestamp` - The timestamp to compute the tick cumulative for
+    /// - `tick_current_index` - The pool's current tick index
+    ///
+    /// # Errors
+    /// - `OracleObservationTooOldError` - The target is older than the oldest stored observation
+    pub fn tick_cumulative_at(&self, target_timestamp: u64, tick_current_index: i32) -> Result<i64> {
+        let latest = self.latest_observation();
+        if target_timestamp >= latest.timestamp {
+            let time_delta = (target_timestamp - latest.timestamp) as i64;
+            return Ok(latest
+                .tick_cumulative
+                .wrapping_add((tick_current_index as i64).wrapping_mul(time_delta)));
+        }
+
+        let oldest = self.observation_at(0);
+        if target_timestamp < oldest.timestamp {
+            return Err(ErrorCode::OracleObservationTooOldError.into());
+        }
+
+        // Binary search for the last observation at or before the target.
+        // The latest observation is strictly after the target, so `after` always exists.
+        let mut low = 0u16;
+        let mut high = self.observation_count - 1;
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            if self.observation_at(mid).timestamp <= target_timestamp {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+
+        let before = self.observation_at(low);
+        if before.timestamp == target_timestamp {
+            return Ok(before.tick_cumulative);
+        }
+        let after = self.observation_at(high);
+
+        let tick = after.tick_cumulative.wrapping_sub(before.tick_cumulative)
+            / (after.timestamp - before.timestamp) as i64;
+        Ok(before
+            .tick_cumulative
+            .wrapping_add(tick.wrapping_mul((target_timestamp - before.timestamp) as i64)))
+    }
+
+    /// Computes the time-weighted average tick over a window ending at `timestamp`.
+    ///
+    /// The average tick is the log base 1.0001 of the geometric-mean price over the window,
+    /// rounded towards negative infinity.
+    ///
+    /// # Parameters
+    /// - `timestamp` - The current timestamp
+    /// - `window_seconds` - The length of the averaging window
+    /// - `tick_current_index` - The pool's current tick index
+    ///
+    /// # Errors
+    /// - `InvalidTwapWindowError` - The window is zero
+    /// - `OracleObservationTooOldError` - The oracle does not cover the whole window
+    pub fn twap_tick(&self, timestamp: u64, window_seconds: u32, tick_current_index: i32) -> Result<i32> {
+        if window_seconds == 0 {
+            return Err(ErrorCode::InvalidTwapWindowError.into());
+        }
+        let window_start = timestamp
+            .checked_sub(window_seconds as u64)
+            .ok_or(ErrorCode::OracleObservationTooOldError)?;
+
+        let tick_cumulative_end = self.tick_cumulative_at(timestamp, tick_current_index)?;
+        let tick_cumulative_start = self.tick_cumulative_at(window_start, tick_current_index)?;
+
+        let tick_cumulative_delta = tick_cumulative_end.wrapping_sub(tick_cumulative_start);
+        let mut twap_tick = tick_cumulative_delta / window_seconds as i64;
+        if tick_cumulative_delta < 0 && tick_cumulative_delta % window_seconds as i64 != 0 {
+            twap_tick -= 1;
+        }
+
+        Ok(twap_tick as i32)
+    }
+
+    /// Computes the sqrt price limit of a swap allowed to move the price at most `max_deviation_bps`
+    /// past the time-weighted average price over a window ending at `timestamp`.
+    ///
+    /// Each basis point of deviation allows one tick, a price ratio of 1.0001, below the average
+    /// tick for a_to_b swaps and above it for b_to_a swaps, bounded by the tick range.
+    ///
+    /// # Parameters
+    /// - `timestamp` - The current timestamp
+    /// - `window_seconds` - The length of the averaging window
+    /// - `tick_current_index` - The pool's current tick index
+    /// - `max_deviation_bps` - The maximum deviation from the average price, in basis points
+    /// - `a_to_b` - The direction of the swap
+    ///
+    /// # Errors
+    /// - `InvalidTwapWindowError` - The window is zero
+    /// - `OracleObservationTooOldError` - The oracle does not cover the whole window
+    pub fn twap_sqrt_price_limit(
+        &self,
+        timestamp: u64,
+        window_seconds: u32,
+        tick_current_index: i32,
+        max_deviation_bps: u128,
+        a_to_b: bool,
+    ) -> Result<u128> {
+        let twap_tick = self.twap_tick(timestamp, window_seconds, tick_current_index)? as i64;
+        let max_deviation_ticks = max_deviation_bps.min((MAX_TICK_INDEX - MIN_TICK_INDEX) as u128) as i64;
+        let limit_tick = if a_to_b {
+            twap_tick - max_deviation_ticks
+        } else {
+            twap_tick + max_deviation_ticks
+        };
+
+        Ok(sqrt_price_from_tick_index(
+            limit_tick.clamp(MIN_TICK_INDEX as i64, MAX_TICK_INDEX as i64) as i32,
+        ))
+    }
+
+    /// Measures the recent volatility of the pool as the distance, in ticks, between its current
+    /// tick and its time-weighted average tick over a window ending at `timestamp`.
+    ///
+    /// The window is shortened to the observations available, so a fresh oracle reports no volatility.
+    ///
+    /// # Parameters
+    /// - `timestamp` - The current timestamp
+    /// - `window_seconds` - The length of the averaging window
+    /// - `tick_current_index` - The pool's current tick index
+    pub fn tick_volatility(&self, timestamp: u64, window_seconds: u32, tick_current_index: i32) -> u32 {
+        let covered_seconds = timestamp.saturating_sub(self.observation_at(0).timestamp);
+        let window_seconds = (window_seconds as u64).min(covered_seconds) as u32;
+        match self.twap_tick(timestamp, window_seconds, tick_current_index) {
+            Ok(twap_tick) => (tick_current_index as i64 - twap_tick as i64).unsigned_abs() as u32,
+            Err(_) => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod oracle_record_tests {
+    use super::*;
+
+    #[test]
+    fn test_initialize_writes_first_observation() {
+        let oracle = &mut Oracle::default();
+        let ai_dex_pool = Pubkey::new_unique();
+        oracle.initialize(ai_dex_pool, 100, 500);
+
+        assert_eq!({ oracle.ai_dex_pool }, ai_dex_pool);
+        assert_eq!({ oracle.observation_count }, 1);
+        assert_eq!(
+            oracle.latest_observation(),
+            Observation {
+                timestamp: 100,
+                tick_cumulative: 0,
+                liquidity: 500,
+            }
+        );
+    }
+
+    #[test]
+    fn test_record_accumulates_tick() {
+        let oracle = &mut Oracle::default();
+        oracle.initialize(Pubkey::new_unique(), 100, 0);
+        oracle.record(110, -20, 1_000);
+        oracle.record(115, 40, 2_000);
+
+        assert_eq!({ oracle.observation_index }, 2);
+        assert_eq!({ oracle.observation_count }, 3);
+        let latest = oracle.latest_observation();
+        assert_eq!({ latest.timestamp }, 115);
+        assert_eq!({ latest.tick_cumulative }, -20 * 10 + 40 * 5);
+        assert_eq!({ latest.liquidity }, 2_000);
+    }
+
+    #[test]
+    fn test_record_same_timestamp_is_noop() {
+        let oracle = &mut Oracle::default();
+        oracle.initialize(Pubkey::new_unique(), 100, 0);
+        oracle.record(100, 50, 1_000);
+        oracle.record(90, 50, 1_000);
+
+        assert_eq!({ oracle.observation_index }, 0);
+        assert_eq!({ oracle.observation_count }, 1);
+    }
+
+    #[test]
+    fn test_twap_tick_interpolates_between_observations() {
+        let oracle = &mut Oracle::default();
+        oracle.initialize(Pubkey::new_unique(), 100, 0);
+        // tick 10 during [100, 200), tick -30 during [200, 300), current tick 50 afterwards
+        oracle.record(200, 10, 0);
+        oracle.record(300, -30, 0);
+
+        // window [250, 350): 50s at -30, 50s at 50
+        assert_eq!(oracle.twap_tick(350, 100, 50).unwrap(), 10);
+        // window [150, 250): 50s at 10, 50s at -30
+        assert_eq!(oracle.twap_tick(250, 100, 50).unwrap(), -10);
+        // window [200, 300) exactly on observations
+        assert_eq!(oracle.twap_tick(300, 100, 50).unwrap(), -30);
+    }
+
+    #[test]
+    fn test_twap_tick_rounds_towards_negative_infinity() {
+        let oracle = &mut Oracle::default();
+        oracle.initialize(Pubkey::new_unique(), 0, 0);
+        oracle.record(1, -1, 0);
+        oracle.record(3, 0, 0);
+
+        // window [0, 3): tick -1 during [0, 1), tick 0 during [1, 3)
+        assert_eq!(oracle.twap_tick(3, 3, 0).unwrap(), -1);
+        // window [1, 3): tick 0 only
+        assert_eq!(oracle.twap_tick(3, 2, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_twap_tick_errors() {
+        let oracle = &mut Oracle::default();
+        oracle.initialize(Pubkey::new_unique(), 100, 0);
+        oracle.record(200, 10, 0);
+
+        assert_eq!(oracle.twap_tick(300, 0, 0).unwrap_err(), ErrorCode::InvalidTwapWindowError.into());
+        assert_eq!(oracle.twap_tick(300, 201, 0).unwrap_err(), ErrorCode::OracleObservationTooOldError.into());
+        assert_eq!(oracle.twap_tick(50, 100, 0).unwrap_err(), ErrorCode::OracleObservationTooOldError.into());
+        assert_eq!(oracle.twap_tick(300, 200, 0).unwrap(), 5);
+    }
+
+    #[test]
+    fn test_twap_sqrt_price_limit() {
+        let oracle = &mut Oracle::default();
+        oracle.initialize(Pubkey::new_unique(), 100, 0);
+        oracle.record(200, 10, 0);
+
+        // the average tick over [100, 300) is 5
+        assert_eq!(oracle.twap_sqrt_price_limit(300, 200, 0, 50, true).unwrap(), sqrt_price_from_tick_index(-45));
+        assert_eq!(oracle.twap_sqrt_price_limit(300, 200, 0, 50, false).unwrap(), sqrt_price_from_tick_index(55));
+        assert_eq!(oracle.twap_sqrt_price_limit(300, 200, 0, 0, true).unwrap(), sqrt_price_from_tick_index(5));
+        assert_eq!(
+            oracle.twap_sqrt_price_limit(300, 200, 0, u128::MAX, true).unwrap(),
+            sqrt_price_from_tick_index(MIN_TICK_INDEX)
+        );
+        assert_eq!(
+            oracle.twap_sqrt_price_limit(300, 200, 0, u128::MAX, false).unwrap(),
+            sqrt_price_from_tick_index(MAX_TICK_INDEX)
+        );
+        assert!(oracle.twap_sqrt_price_limit(300, 201, 0, 50, true).is_err());
+    }
+
+    #[test]
+    fn test_tick_volatility() {
+        let oracle = &mut Oracle::default();
+        oracle.initialize(Pubkey::new_unique(), 100, 0);
+        assert_eq!(oracle.tick_volatility(100, 100, 50), 0);
+
+        // tick 10 during [100, 200), current tick 50 afterwards
+        oracle.record(200, 10, 0);
+        // window [200, 300): tick 50 only
+        assert_eq!(oracle.tick_volatility(300, 100, 50), 0);
+        // window shortened to [100, 300): average tick 30
+        assert_eq!(oracle.tick_volatility(300, 1_000, 50), 20);
+        assert_eq!(oracle.tick_volatility(300, 1_000, -10), 10);
+    }
+
+    #[test]
+    fn test_record_wraps_around() {
+        let oracle = &mut Oracle::default();
+        oracle.initialize(Pubkey::new_unique(), 0, 0);
+        for i in 1..=ORACLE_OBSERVATION_SIZE as u64 {
+            oracle.record(i, 1, 0);
+        }
+
+        assert_eq!({ oracle.observation_index }, 0);
+        assert_eq!({ oracle.observation_count }, ORACLE_OBSERVATION_SIZE);
+        let latest = oracle.latest_observation();
+        assert_eq!({ latest.timestamp }, ORACLE_OBSERVATION_SIZE as u64);
+        assert_eq!({ latest.tick_cumulative }, ORACLE_OBSERVATION_SIZE as i64);
+    }
+}
@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+pub const MAX_POOL_ALLOWLIST_AUTHORITIES: usize = 32;
+
+/// The operations of a pool that an allowlist can restrict.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PoolAllowlistKind {
+    /// Opening positions and increasing liquidity.
+    Liquidity,
+    /// Swapping, directly or through orders.
+    Swap,
+}
+
+impl PoolAllowlistKind {
+    /// Returns the seed prefix of the allowlist account of this kind.
+    pub fn seed(&self) -> &'static [u8] {
+        match self {
+            PoolAllowlistKind::Liquidity => b"lp_allowlist",
+            PoolAllowlistKind::Swap => b"swap_allowlist",
+        }
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct PoolAllowlist {
+    pub ai_dex_pool: Pubkey, // 32
+    pub gating_mint: Pubkey, // 32
+    pub authorities: [Pubkey; MAX_POOL_ALLOWLIST_AUTHORITIES], // 32 * 32
+                                                               // 64 RESERVE
+}
+
+/// The authorities allowed to perform an operation on a pool while its allowlist is enabled.
+///
+/// Besides the listed authorities, the holders of a token of the gating mint, typically an NFT,
+/// are allowed as well.
+impl PoolAllowlist {
+    pub const LEN: usize = 8 + 32 + 32 + 32 * MAX_POOL_ALLOWLIST_AUTHORITIES + 64;
+
+    /// Initializes the allowlist of a pool, without any authority nor gating mint.
+    ///
+    /// # Arguments
+    ///
+    /// * `ai_dex_pool` - The pool the allowlist belongs to.
+    pub fn initialize(&mut self, ai_dex_pool: Pubkey) {
+        self.ai_dex_pool = ai_dex_pool;
+    }
+
+    /// Sets the mint whose holders are allowed.
+    ///
+    /// # Arguments
+    ///
+    /// * `gating_mint` - The gating mint, or the default pubkey for none.
+    pub fn set_gating_mint(&mut self, gating_mint: Pubkey) {
+        self.gating_mint = gating_mint;
+    }
+
+    /// Returns true if the authority is on the allowlist.
+    pub fn contains(&self, authority: &Pubkey) -> bool {
+        *authority != Pubkey::default() && self.authorities.contains(authority)
+    }
+
+    /// Returns true if a token of the given mint held by the authority passes the gate.
+    pub fn is_gating_token(&self, mint: &Pubkey, amount: u64) -> bool {
+        self.gating_mint != Pubkey::default() && *mint == self.gating_mint && amount > 0
+    }
+
+    /// Adds an authority to the allowlist. Adding a listed authority does nothing.
+    ///
+    /// # Errors
+    ///
+    /// * `PoolAllowlistFullError` - If every slot of the allowlist is taken.
+    pub fn add_authority(&mut self, authority: Pubkey) -> Result<()> {
+        if authority == Pubkey::default() || self.contains(&authority) {
+            return Ok(());
+        }
+        match self.authorities.iter_mut().find(|slot| **slot == Pubkey::default()) {
+            Some(slot) => {
+                *slot = authority;
+                Ok(())
+            }
+            None => Err(ErrorCode::PoolAllowlistFullError.into()),
+        }
+    }
+
+    /// Removes an authority from the allowlist.
+    ///
+    /// # Errors
+    ///
+    /// * `AuthorityNotAllowlistedError` - If the authority is not on the allowlist.
+    pub fn remove_authority(&mut self, authority: Pubkey) -> Result<()> {
+        if !self.contains(&authority) {
+            return Err(ErrorCode::AuthorityNotAllowlistedError.into());
+        }
+        for slot in self.authorities.iter_mut().filter(|slot| **slot == authority) {
+            *slot = Pubkey::default();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod pool_allowlist_tests {
+    use super::*;
+
+    #[test]
+    fn test_add_and_remove_authority() {
+        let mut allowlist = PoolAllowlist::default();
+        let authority = Pubkey::new_unique();
+        assert!(!allowlist.contains(&authority));
+        assert!(!allowlist.contains(&Pubkey::default()));
+
+        allowlist.add_authority(authority).unwrap();
+        allowlist.add_authority(authority).unwrap();
+        assert!(allowlist.contains(&authority));
+        assert_eq!(allowlist.authorities.iter().filter(|slot| **slot == authority).count(), 1);
+
+        allowlist.remove_authority(authority).unwrap();
+        assert!(!allowlist.contains(&authority));
+        assert!(allowlist.remove_authority(authority).is_err());
+    }
+
+    #[test]
+    fn test_add_authority_when_full() {
+        let mut allowlist = PoolAllowlist::default();
+        for _ in 0..MAX_POOL_ALLOWLIST_AUTHORITIES {
+            allowlist.add_authority(Pubkey::new_unique()).unwrap();
+        }
+        assert!(allowlist.add_authority(Pubkey::new_unique()).is_err());
+
+        let removed = allowlist.authorities[3];
+        allowlist.remove_authority(removed).unwrap();
+        allowlist.add_authority(Pubkey::new_unique()).unwrap();
+    }
+
+    #[test]
+    fn test_gating_token() {
+        let mut allowlist = PoolAllowlist::default();
+        let gating_mint = Pubkey::new_unique();
+        assert!(!allowlist.is_gating_token(&Pubkey::default(), 1));
+
+        allowlist.set_gating_mint(gating_mint);
+        assert!(allowlist.is_gating_token(&gating_mint, 1));
+        assert!(!allowlist.is_gating_token(&gating_mint, 0));
+        assert!(!allowlist.is_gating_token(&Pubkey::new_unique(), 1));
+    }
+}
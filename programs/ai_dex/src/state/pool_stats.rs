@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default)]
+pub struct PoolStats {
+    pub ai_dex_pool: Pubkey, // 32
+    // Lifetime amounts of each token swapped through the pool, input and output sides
+    pub volume_a: u128, // 16
+    pub volume_b: u128, // 16
+    // Lifetime swap fees paid in each token, the protocol and referral fees included
+    pub fees_a: u128, // 16
+    pub fees_b: u128, // 16
+    // Lifetime protocol fees accrued in each token
+    pub protocol_fees_a: u128, // 16
+    pub protocol_fees_b: u128, // 16
+    pub swap_count: u64, // 8
+    pub last_swap_timestamp: u64, // 8
+                                  // 64 RESERVE
+}
+
+/// The cumulative statistics of a pool, updated by every swap once initialized.
+impl PoolStats {
+    pub const LEN: usize = 8 + 32 + 96 + 16 + 64;
+
+    /// Initializes the statistics of a pool, starting from zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `ai_dex_pool` - The pool the statistics belong to.
+    pub fn initialize(&mut self, ai_dex_pool: Pubkey) {
+        self.ai_dex_pool = ai_dex_pool;
+    }
+
+    /// Records a swap.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount_a` - The amount of token A swapped.
+    /// * `amount_b` - The amount of token B swapped.
+    /// * `fee_amount` - The swap fee paid in the input token.
+    /// * `protocol_fee` - The protocol fee accrued in the input token.
+    /// * `a_to_b` - The direction of the swap.
+    /// * `timestamp` - The timestamp of the swap.
+    pub fn record_swap(
+        &mut self,
+        amount_a: u64,
+        amount_b: u64,
+        fee_amount: u64,
+        protocol_fee: u64,
+        a_to_b: bool,
+        timestamp: u64,
+    ) {
+        self.volume_a = self.volume_a.saturating_add(amount_a as u128);
+        self.volume_b = self.volume_b.saturating_add(amount_b as u128);
+        let (fees, protocol_fees) = if a_to_b {
+            (&mut self.fees_a, &mut self.protocol_fees_a)
+        } else {
+            (&mut self.fees_b, &mut self.protocol_fees_b)
+        };
+        *fees = fees.saturating_add(fee_amount as u128);
+        *protocol_fees = protocol_fees.saturating_add(protocol_fee as u128);
+        self.swap_count = self.swap_count.saturating_add(1);
+        self.last_swap_timestamp = timestamp;
+    }
+}
+
+#[cfg(test)]
+mod pool_stats_tests {
+    use super::*;
+
+    #[test]
+    fn test_record_swap() {
+        let mut pool_stats = PoolStats::default();
+        pool_stats.record_swap(1_000, 2_000, 3, 1, true, 100);
+        pool_stats.record_swap(500, 1_000, 5, 2, false, 200);
+
+        assert_eq!(pool_stats.volume_a, 1_500);
+        assert_eq!(pool_stats.volume_b, 3_000);
+        assert_eq!((pool_stats.fees_a, pool_stats.fees_b), (3, 5));
+        assert_eq!((pool_stats.protocol_fees_a, pool_stats.protocol_fees_b), (1, 2));
+        assert_eq!(pool_stats.swap_count, 2);
+        assert_eq!(pool_stats.last_swap_timestamp, 200);
+    }
+}
@@ -1,9 +1,16 @@
 use anchor_lang::prelude::*;
 
-use crate::{errors::ErrorCode, math::FULL_RANGE_ONLY_TICK_SPACING_THRESHOLD, state::NUM_REWARDS};
+use crate::{errors::ErrorCode, state::NUM_REWARDS};
 
 use super::{Tick, AiDexPool};
 
+pub const REWARD_BOOST_BPS_DENOMINATOR: u16 = 10_000;
+// Max reward boost supported is 3x the rewards of an unlocked position.
+pub const MAX_REWARD_BOOST_BPS: u16 = 30_000;
+
+// Version of the current layout of a position, raised whenever fields are appended to it
+pub const POSITION_VERSION: u8 = 3;
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Copy)]
 pub struct OpenPositionBumps {
     pub position_bump: u8,
@@ -32,12 +39,39 @@ pub struct Position {
     pub fee_owed_b: u64,               // 8
 
     pub reward_infos: [PositionRewardInfo; NUM_REWARDS], // 72
+
+    // Unix timestamp until which the liquidity cannot be withdrawn
+    pub locked_until: u64, // 8
+    // The liquidity can never be withdrawn
+    pub permanently_locked: bool, // 1
+
+    // Manages the liquidity and fees on behalf of the holder, default pubkey if none
+    pub operator: Pubkey, // 32
+
+    // Multiplier of the rewards earned while locked, in basis points, zero if not boosted
+    pub reward_boost_bps: u16, // 2
+
+    // Version of the layout of the position, see `POSITION_VERSION`
+    pub version: u8, // 1
+
+    // Sqrt price of the pool and timestamp of the first deposit into the position, zero before it
+    pub entry_sqrt_price: u128, // 16
+    pub entry_timestamp: u64, // 8
+    // Cumulative amounts deposited into and withdrawn from the vaults by the position
+    pub deposited_a: u64, // 8
+    pub deposited_b: u64, // 8
+    pub withdrawn_a: u64, // 8
+    pub withdrawn_b: u64, // 8
+
+    // Tag set by the owner, zero-padded, all zeros if none
+    pub label: [u8; 32], // 32
+    // 64 RESERVE
 }
 
 /// Represents a position in the AiDex program.
 impl Position {
     /// The length of a position in bytes.
-    pub const LEN: usize = 8 + 136 + 72;
+    pub const LEN: usize = 8 + 136 + 72 + 8 + 1 + 32 + 2 + 1 + 56 + 32 + 64;
 
     /// Checks if a position is empty.
     ///
@@ -88,42 +122,296 @@ impl Position {
     /// * An error if the tick indexes are invalid or the pool is full range only.
     pub fn open_position(
         &mut self,
-        ai_dex: &Account<AiDexPool>,
+        ai_dex: &AccountLoader<AiDexPool>,
         position_mint: Pubkey,
         tick_lower_index: i32,
         tick_upper_index: i32,
     ) -> Result<()> {
-        if !Tick::check_is_usable_tick(tick_lower_index, ai_dex.tick_spacing)
-            || !Tick::check_is_usable_tick(tick_upper_index, ai_dex.tick_spacing)
-            || tick_lower_index >= tick_upper_index
+        check_tick_range(&*ai_dex.load()?, tick_lower_index, tick_upper_index)?;
+
+        self.ai_dex_pool = ai_dex.key();
+        self.position_mint = position_mint;
+
+        self.tick_lower_index = tick_lower_index;
+        self.tick_upper_index = tick_upper_index;
+        self.version = POSITION_VERSION;
+        Ok(())
+    }
+
+    /// Moves a position without liquidity to a new tick range.
+    ///
+    /// The fees and rewards owed are kept. The growth checkpoints are set for the new range
+    /// the next time liquidity is added.
+    ///
+    /// # Arguments
+    ///
+    /// * `ai_dex` - The pool of the position.
+    /// * `tick_lower_index` - The new lower tick index of the position.
+    /// * `tick_upper_index` - The new upper tick index of the position.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the range was changed successfully.
+    /// * An error if the position has liquidity, the tick indexes are invalid or the pool is full range only.
+    pub fn reset_tick_range(
+        &mut self,
+        ai_dex: &AiDexPool,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+    ) -> Result<()> {
+        if self.liquidity != 0 {
+            return Err(ErrorCode::NonZeroLiquidityRangeChangeError.into());
+        }
+        check_tick_range(ai_dex, tick_lower_index, tick_upper_index)?;
+
+        self.tick_lower_index = tick_lower_index;
+        self.tick_upper_index = tick_upper_index;
+        Ok(())
+    }
+
+    /// Merges the liquidity and the amounts owed of another position into this one.
+    ///
+    /// Both positions must be in the same pool and tick range, and their fees and rewards must
+    /// have been updated to the same point. The growth checkpoints of a position without
+    /// liquidity are stale, so the checkpoints of the position with liquidity are kept. The
+    /// merged position keeps the later of the two locks, and is permanently locked if either is.
+    /// It keeps the lower of the two reward boosts, until its boost is refreshed, and the earlier
+    /// of the two entries, the amounts deposited and withdrawn being added up.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The position to merge into this one.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the positions were merged successfully.
+    /// * An error if the positions are incompatible or an amount overflows.
+    pub fn merge(&mut self, other: &Position) -> Result<()> {
+        if self.ai_dex_pool != other.ai_dex_pool
+            || self.tick_lower_index != other.tick_lower_index
+            || self.tick_upper_index != other.tick_upper_index
         {
-            return Err(ErrorCode::InvalidTickIndexError.into());
+            return Err(ErrorCode::IncompatiblePositionsMergeError.into());
         }
 
-        // On tick spacing >= 2^15, should only be able to open full range positions
-        if ai_dex.tick_spacing >= FULL_RANGE_ONLY_TICK_SPACING_THRESHOLD {
-            let (full_range_lower_index, full_range_upper_index) = Tick::full_range_indexes(ai_dex.tick_spacing);
-            if tick_lower_index != full_range_lower_index
-                || tick_upper_index != full_range_upper_index
-            {
-                return Err(ErrorCode::FullRangeOnlyPoolError.into());
+        if self.liquidity == 0 {
+            self.fee_growth_checkpoint_a = other.fee_growth_checkpoint_a;
+            self.fee_growth_checkpoint_b = other.fee_growth_checkpoint_b;
+            for (reward, other_reward) in self.reward_infos.iter_mut().zip(other.reward_infos.iter()) {
+                reward.growth_inside_checkpoint = other_reward.growth_inside_checkpoint;
             }
         }
 
-        self.ai_dex_pool = ai_dex.key();
-        self.position_mint = position_mint;
+        self.liquidity = self
+            .liquidity
+            .checked_add(other.liquidity)
+            .ok_or(ErrorCode::LiquidityOverflowError)?;
+        self.fee_owed_a = self
+            .fee_owed_a
+            .checked_add(other.fee_owed_a)
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        self.fee_owed_b = self
+            .fee_owed_b
+            .checked_add(other.fee_owed_b)
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        for (reward, other_reward) in self.reward_infos.iter_mut().zip(other.reward_infos.iter()) {
+            reward.amount_owed = reward
+                .amount_owed
+                .checked_add(other_reward.amount_owed)
+                .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        }
+        self.locked_until = self.locked_until.max(other.locked_until);
+        self.permanently_locked |= other.permanently_locked;
+        self.reward_boost_bps = self.reward_boost_bps.min(other.reward_boost_bps);
+        if self.entry_timestamp == 0
+            || (other.entry_timestamp != 0 && other.entry_timestamp < self.entry_timestamp)
+        {
+            self.entry_sqrt_price = other.entry_sqrt_price;
+            self.entry_timestamp = other.entry_timestamp;
+        }
+        self.deposited_a = self.deposited_a.saturating_add(other.deposited_a);
+        self.deposited_b = self.deposited_b.saturating_add(other.deposited_b);
+        self.withdrawn_a = self.withdrawn_a.saturating_add(other.withdrawn_a);
+        self.withdrawn_b = self.withdrawn_b.saturating_add(other.withdrawn_b);
+        Ok(())
+    }
 
-        self.tick_lower_index = tick_lower_index;
-        self.tick_upper_index = tick_upper_index;
+    /// Records the amounts deposited into the vaults by the position.
+    ///
+    /// The first deposit into the position is its entry, whose price and timestamp are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount_a` - The amount of token A deposited, before transfer fees.
+    /// * `amount_b` - The amount of token B deposited, before transfer fees.
+    /// * `sqrt_price` - The sqrt price of the pool at the deposit.
+    /// * `timestamp` - The timestamp of the deposit.
+    pub fn record_deposit(&mut self, amount_a: u64, amount_b: u64, sqrt_price: u128, timestamp: u64) {
+        if self.entry_timestamp == 0 {
+            self.entry_sqrt_price = sqrt_price;
+            self.entry_timestamp = timestamp;
+        }
+        self.deposited_a = self.deposited_a.saturating_add(amount_a);
+        self.deposited_b = self.deposited_b.saturating_add(amount_b);
+    }
+
+    /// Records the amounts withdrawn from the vaults by the position, fees and rewards excluded.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount_a` - The amount of token A withdrawn, before transfer fees.
+    /// * `amount_b` - The amount of token B withdrawn, before transfer fees.
+    pub fn record_withdrawal(&mut self, amount_a: u64, amount_b: u64) {
+        self.withdrawn_a = self.withdrawn_a.saturating_add(amount_a);
+        self.withdrawn_b = self.withdrawn_b.saturating_add(amount_b);
+    }
+
+    /// Checks if the liquidity of the position is locked at the given timestamp.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp` - The current unix timestamp.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the position is locked, `false` otherwise.
+    pub fn is_locked(&self, timestamp: u64) -> bool {
+        self.permanently_locked || timestamp < self.locked_until
+    }
+
+    /// Locks the liquidity of the position until the given timestamp.
+    ///
+    /// A lock can only be extended, never shortened.
+    ///
+    /// # Arguments
+    ///
+    /// * `locked_until` - The unix timestamp until which the liquidity is locked.
+    /// * `timestamp` - The current unix timestamp.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the position was locked successfully.
+    /// * An error if `locked_until` is not in the future or is earlier than the current lock.
+    pub fn lock(&mut self, locked_until: u64, timestamp: u64) -> Result<()> {
+        if locked_until <= timestamp || locked_until < self.locked_until {
+            return Err(ErrorCode::InvalidPositionLockError.into());
+        }
+
+        self.locked_until = locked_until;
         Ok(())
     }
 
+    /// Locks the liquidity of the position forever.
+    ///
+    /// The lock cannot be undone. Fees and rewards can still be collected.
+    pub fn lock_permanently(&mut self) {
+        self.permanently_locked = true;
+    }
+
+    /// Returns the reward boost applying to the rewards settled at the given timestamp.
+    ///
+    /// The boost only applies while the position is locked, so rewards must be settled before
+    /// the lock expires to be boosted.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp` - The current unix timestamp.
+    pub fn active_reward_boost_bps(&self, timestamp: u64) -> u16 {
+        if self.is_locked(timestamp) {
+            self.reward_boost_bps
+        } else {
+            0
+        }
+    }
+
+    /// Sets the reward boost of the position. The rewards of the position must have been settled
+    /// beforehand, so the boost does not apply to rewards earned before.
+    ///
+    /// # Arguments
+    ///
+    /// * `reward_boost_bps` - The reward boost, or zero to remove it.
+    pub fn set_reward_boost(&mut self, reward_boost_bps: u16) {
+        self.reward_boost_bps = reward_boost_bps;
+    }
+
+    /// Sets the operator of the position.
+    ///
+    /// # Arguments
+    ///
+    /// * `operator` - The new operator, or the default pubkey to remove the operator.
+    pub fn set_operator(&mut self, operator: Pubkey) {
+        self.operator = operator;
+    }
+
+    /// Sets the label of the position.
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - The new label, zero-padded, or all zeros to remove the label.
+    pub fn set_label(&mut self, label: [u8; 32]) {
+        self.label = label;
+    }
+
+    /// Checks if the given key is the operator of the position.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to check.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the position has an operator and it is `key`, `false` otherwise.
+    pub fn is_operator(&self, key: &Pubkey) -> bool {
+        self.operator != Pubkey::default() && self.operator == *key
+    }
+
+    /// Resets the liquidity of the position to zero, without any tick update.
+    pub fn reset_liquidity(&mut self) {
+        self.liquidity = 0;
+    }
+
     /// Resets the fees owed by the position to zero.
     pub fn reset_fees_owed(&mut self) {
         self.fee_owed_a = 0;
         self.fee_owed_b = 0;
     }
 
+    /// Adds amounts to the fees owed by the position, such as the fees paid in by the depositors of
+    /// an LP token for their share of the fees earned so far.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a fee owed overflows.
+    pub fn add_fees_owed(&mut self, amount_a: u64, amount_b: u64) -> Result<()> {
+        self.fee_owed_a = self
+            .fee_owed_a
+            .checked_add(amount_a)
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        self.fee_owed_b = self
+            .fee_owed_b
+            .checked_add(amount_b)
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        Ok(())
+    }
+
+    /// Removes amounts from the fees owed by the position, such as the share of the fees paid out
+    /// to the holders of an LP token.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an amount exceeds the fee owed.
+    pub fn remove_fees_owed(&mut self, amount_a: u64, amount_b: u64) -> Result<()> {
+        self.fee_owed_a = self
+            .fee_owed_a
+            .checked_sub(amount_a)
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        self.fee_owed_b = self
+            .fee_owed_b
+            .checked_sub(amount_b)
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        Ok(())
+    }
+
     /// Updates the amount owed for a specific reward in the position.
     ///
     /// # Arguments
@@ -135,6 +423,33 @@ impl Position {
     }
 }
 
+/// Checks that the tick indexes form a valid position range for the tick spacing and the tick
+/// range of the pool.
+fn check_tick_range(ai_dex: &AiDexPool, tick_lower_index: i32, tick_upper_index: i32) -> Result<()> {
+    let tick_spacing = ai_dex.tick_spacing;
+    let (min_tick_index, max_tick_index) = ai_dex.tick_index_bounds();
+    if !Tick::check_is_usable_tick(tick_lower_index, tick_spacing)
+        || !Tick::check_is_usable_tick(tick_upper_index, tick_spacing)
+        || tick_lower_index < min_tick_index
+        || tick_upper_index > max_tick_index
+        || tick_lower_index >= tick_upper_index
+    {
+        return Err(ErrorCode::InvalidTickIndexError.into());
+    }
+
+    // On full range only pools, should only be able to open full range positions
+    if ai_dex.is_full_range_only() {
+        let (full_range_lower_index, full_range_upper_index) = ai_dex.full_range_indexes();
+        if tick_lower_index != full_range_lower_index
+            || tick_upper_index != full_range_upper_index
+        {
+            return Err(ErrorCode::FullRangeOnlyPoolError.into());
+        }
+    }
+
+    Ok(())
+}
+
 #[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Default, Debug, PartialEq)]
 pub struct PositionRewardInfo {
     // Q64.64
@@ -189,6 +504,18 @@ mod is_position_empty_tests {
                     amount_owed: reward_owed_2,
                 },
             ],
+            locked_until: 0,
+            permanently_locked: false,
+            operator: Pubkey::default(),
+            reward_boost_bps: 0,
+            version: POSITION_VERSION,
+            entry_sqrt_price: 0,
+            entry_timestamp: 0,
+            deposited_a: 0,
+            deposited_b: 0,
+            withdrawn_a: 0,
+            withdrawn_b: 0,
+            label: [0; 32],
         }
     }
 
@@ -235,6 +562,273 @@ mod is_position_empty_tests {
     }
 }
 
+#[cfg(test)]
+mod reset_tick_range_tests {
+    use super::*;
+
+    fn build_pool(full_range_only: bool) -> AiDexPool {
+        AiDexPool { tick_spacing: 64, full_range_only, ..Default::default() }
+    }
+
+    #[test]
+    fn test_reset_tick_range_keeps_amounts_owed() {
+        let mut position = Position {
+            tick_lower_index: -128,
+            tick_upper_index: 128,
+            fee_owed_a: 10,
+            fee_owed_b: 20,
+            ..Default::default()
+        };
+
+        position.reset_tick_range(&build_pool(false), 256, 512).unwrap();
+        assert_eq!(position.tick_lower_index, 256);
+        assert_eq!(position.tick_upper_index, 512);
+        assert_eq!(position.fee_owed_a, 10);
+        assert_eq!(position.fee_owed_b, 20);
+    }
+
+    #[test]
+    fn test_reset_tick_range_with_liquidity() {
+        let mut position = Position {
+            liquidity: 100,
+            tick_lower_index: -128,
+            tick_upper_index: 128,
+            ..Default::default()
+        };
+
+        assert!(position.reset_tick_range(&build_pool(false), 256, 512).is_err());
+        assert_eq!(position.tick_lower_index, -128);
+    }
+
+    #[test]
+    fn test_reset_tick_range_invalid_ticks() {
+        let mut position = Position::default();
+
+        assert!(position.reset_tick_range(&build_pool(false), 512, 256).is_err());
+        assert!(position.reset_tick_range(&build_pool(false), 100, 256).is_err());
+    }
+
+    #[test]
+    fn test_reset_tick_range_full_range_only() {
+        let mut position = Position::default();
+        let (full_range_lower_index, full_range_upper_index) = build_pool(true).full_range_indexes();
+
+        assert!(position.reset_tick_range(&build_pool(true), 256, 512).is_err());
+        assert!(position.reset_tick_range(&build_pool(true), full_range_lower_index, 512).is_err());
+        position
+            .reset_tick_range(&build_pool(true), full_range_lower_index, full_range_upper_index)
+            .unwrap();
+        assert_eq!(position.tick_lower_index, full_range_lower_index);
+        assert_eq!(position.tick_upper_index, full_range_upper_index);
+    }
+
+    #[test]
+    fn test_reset_tick_range_legacy_tick_range() {
+        let mut position = Position::default();
+        let mut ai_dex = build_pool(false);
+
+        // The ticks beyond the legacy bounds are only usable once the tick range is extended
+        assert!(position.reset_tick_range(&ai_dex, 0, 443648).is_err());
+        assert!(position.reset_tick_range(&ai_dex, -443648, 0).is_err());
+        ai_dex.extended_tick_range = true;
+        position.reset_tick_range(&ai_dex, -443648, 443648).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod merge_tests {
+    use super::*;
+
+    fn build_position(liquidity: u128, fee_owed_a: u64, checkpoint: u128) -> Position {
+        Position {
+            liquidity,
+            tick_lower_index: -128,
+            tick_upper_index: 128,
+            fee_growth_checkpoint_a: checkpoint,
+            fee_owed_a,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_merge_adds_liquidity_and_amounts_owed() {
+        let mut position = build_position(100, 10, 5);
+        let mut other = build_position(50, 7, 5);
+        other.reward_infos[1].amount_owed = 3;
+
+        position.merge(&other).unwrap();
+        assert_eq!(position.liquidity, 150);
+        assert_eq!(position.fee_owed_a, 17);
+        assert_eq!(position.reward_infos[1].amount_owed, 3);
+        assert_eq!(position.fee_growth_checkpoint_a, 5);
+    }
+
+    #[test]
+    fn test_merge_into_position_without_liquidity_takes_checkpoints() {
+        let mut position = build_position(0, 10, 1);
+        let other = build_position(50, 0, 9);
+
+        position.merge(&other).unwrap();
+        assert_eq!(position.liquidity, 50);
+        assert_eq!(position.fee_growth_checkpoint_a, 9);
+    }
+
+    #[test]
+    fn test_merge_different_range() {
+        let mut position = build_position(100, 0, 0);
+        let mut other = build_position(50, 0, 0);
+        other.tick_upper_index = 256;
+
+        assert!(position.merge(&other).is_err());
+    }
+
+    #[test]
+    fn test_merge_liquidity_overflow() {
+        let mut position = build_position(u128::MAX, 0, 0);
+        let other = build_position(1, 0, 0);
+
+        assert!(position.merge(&other).is_err());
+    }
+}
+
+#[cfg(test)]
+mod deposit_tests {
+    use super::*;
+
+    #[test]
+    fn test_first_deposit_is_entry() {
+        let mut position = Position::default();
+        position.record_deposit(10, 20, 1 << 64, 100);
+        position.record_deposit(1, 2, 2 << 64, 200);
+        position.record_withdrawal(3, 4);
+
+        assert_eq!(position.entry_sqrt_price, 1 << 64);
+        assert_eq!(position.entry_timestamp, 100);
+        assert_eq!((position.deposited_a, position.deposited_b), (11, 22));
+        assert_eq!((position.withdrawn_a, position.withdrawn_b), (3, 4));
+    }
+}
+
+#[cfg(test)]
+mod lock_tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_position() {
+        let mut position = Position::default();
+        assert!(!position.is_locked(100));
+
+        position.lock(200, 100).unwrap();
+        assert!(position.is_locked(100));
+        assert!(position.is_locked(199));
+        assert!(!position.is_locked(200));
+    }
+
+    #[test]
+    fn test_active_reward_boost() {
+        let mut position = Position::default();
+        position.set_reward_boost(15_000);
+        assert_eq!(position.active_reward_boost_bps(100), 0);
+
+        position.lock(200, 100).unwrap();
+        assert_eq!(position.active_reward_boost_bps(199), 15_000);
+        assert_eq!(position.active_reward_boost_bps(200), 0);
+
+        position.lock_permanently();
+        assert_eq!(position.active_reward_boost_bps(u64::MAX), 15_000);
+    }
+
+    #[test]
+    fn test_lock_in_the_past() {
+        let mut position = Position::default();
+
+        assert!(position.lock(100, 100).is_err());
+        assert!(position.lock(50, 100).is_err());
+    }
+
+    #[test]
+    fn test_lock_cannot_be_shortened() {
+        let mut position = Position::default();
+        position.lock(300, 100).unwrap();
+
+        assert!(position.lock(200, 100).is_err());
+        position.lock(400, 100).unwrap();
+        assert_eq!(position.locked_until, 400);
+    }
+
+    #[test]
+    fn test_lock_permanently() {
+        let mut position = Position::default();
+        position.lock_permanently();
+
+        assert!(position.is_locked(0));
+        assert!(position.is_locked(u64::MAX));
+    }
+
+    #[test]
+    fn test_merge_keeps_permanent_lock() {
+        let mut position = Position::default();
+        let mut other = Position::default();
+        other.lock_permanently();
+
+        position.merge(&other).unwrap();
+        assert!(position.permanently_locked);
+    }
+
+    #[test]
+    fn test_merge_keeps_earlier_entry() {
+        let mut position = Position::default();
+        position.record_deposit(10, 20, 1 << 64, 200);
+        let mut other = Position::default();
+        other.record_deposit(5, 5, 2 << 64, 100);
+        other.record_withdrawal(1, 2);
+
+        position.merge(&other).unwrap();
+        assert_eq!(position.entry_sqrt_price, 2 << 64);
+        assert_eq!(position.entry_timestamp, 100);
+        assert_eq!((position.deposited_a, position.deposited_b), (15, 25));
+        assert_eq!((position.withdrawn_a, position.withdrawn_b), (1, 2));
+    }
+
+    #[test]
+    fn test_merge_keeps_later_lock() {
+        let mut position = Position::default();
+        let other = Position {
+            locked_until: 500,
+            ..Default::default()
+        };
+
+        position.merge(&other).unwrap();
+        assert_eq!(position.locked_until, 500);
+    }
+}
+
+#[cfg(test)]
+mod operator_tests {
+    use super::*;
+
+    #[test]
+    fn test_no_operator() {
+        let position = Position::default();
+
+        assert!(!position.is_operator(&Pubkey::default()));
+        assert!(!position.is_operator(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_set_and_remove_operator() {
+        let mut position = Position::default();
+        let operator = Pubkey::new_unique();
+
+        position.set_operator(operator);
+        assert!(position.is_operator(&operator));
+        assert!(!position.is_operator(&Pubkey::new_unique()));
+
+        position.set_operator(Pubkey::default());
+        assert!(!position.is_operator(&operator));
+    }
+}
+
 #[cfg(test)]
 pub mod position_builder {
     use anchor_lang::prelude::Pubkey;
@@ -4,18 +4,23 @@ use anchor_lang::prelude::*;
 pub const POSITION_BITMAP_USIZE: usize = 32;
 pub const POSITION_TRADE_BATCH_SIZE: u16 = 8 * POSITION_BITMAP_USIZE as u16;
 
+// Version of the current layout of a position trade batch, raised whenever fields are appended to it
+pub const POSITION_TRADE_BATCH_VERSION: u8 = 1;
+
 #[account]
 #[derive(Default)]
 pub struct PositionTradeBatch {
     pub position_trade_batch_mint: Pubkey, // 32
     pub position_bitmap: [u8; POSITION_BITMAP_USIZE], // 32
-                                      // 64 RESERVE
+    pub has_extension: bool, // 1
+    pub version: u8, // 1
+                                      // 62 RESERVE
 }
 
 /// Represents a position trade batch.
 impl PositionTradeBatch {
     /// The length of the position trade batch in bytes.
-    pub const LEN: usize = 8 + 32 + 32 + 64;
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 1 + 62;
 
     /// Initializes the position trade batch with the given mint.
     ///
@@ -28,6 +33,7 @@ impl PositionTradeBatch {
     /// Returns an error if the operation fails.
     pub fn initialize(&mut self, position_trade_batch_mint: Pubkey) -> Result<()> {
         self.position_trade_batch_mint = position_trade_batch_mint;
+        self.version = POSITION_TRADE_BATCH_VERSION;
         // position_bitmap is initialized using Default trait
         Ok(())
     }
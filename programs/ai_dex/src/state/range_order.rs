@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+pub const MAX_RANGE_ORDER_BOUNTY_BPS: u16 = 1_000;
+
+#[account]
+#[derive(Default)]
+pub struct RangeOrder {
+    pub position: Pubkey, // 32
+    pub funder: Pubkey,   // 32
+    // Token A is converted into token B if true, token B into token A otherwise
+    pub a_to_b: bool,     // 1
+    pub bounty_bps: u16,  // 2
+    // 64 RESERVE
+}
+
+/// Struct representing a single-sided position that any keeper can withdraw once converted.
+///
+/// A position above the current price only holds token A and is converted into token B once the
+/// price crosses its upper tick; a position below the current price is converted into token A once
+/// the price crosses its lower tick.
+impl RangeOrder {
+    /// Length of the `RangeOrder` struct in bytes.
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 2 + 64;
+
+    /// Initializes the `RangeOrder` struct for a single-sided position.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The position pubkey.
+    /// * `funder` - The account paying the rent, refunded when the order is closed.
+    /// * `tick_current_index` - The current tick index of the pool.
+    /// * `tick_lower_index` - The lower tick index of the position.
+    /// * `tick_upper_index` - The upper tick index of the position.
+    /// * `bounty_bps` - The share of the converted tokens paid to the keeper, in basis points.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the position is not single-sided or the bounty is too large.
+    pub fn initialize(
+        &mut self,
+        position: Pubkey,
+        funder: Pubkey,
+        tick_current_index: i32,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+        bounty_bps: u16,
+    ) -> Result<()> {
+        if bounty_bps > MAX_RANGE_ORDER_BOUNTY_BPS {
+            return Err(ErrorCode::InvalidRangeOrderBountyError.into());
+        }
+
+        self.a_to_b = if tick_current_index < tick_lower_index {
+            true
+        } else if tick_current_index >= tick_upper_index {
+            false
+        } else {
+            return Err(ErrorCode::InvalidRangeOrderError.into());
+        };
+        self.position = position;
+        self.funder = funder;
+        self.bounty_bps = bounty_bps;
+        Ok(())
+    }
+
+    /// Checks if the price has fully crossed the range of the position.
+    ///
+    /// # Arguments
+    ///
+    /// * `tick_current_index` - The current tick index of the pool.
+    /// * `tick_lower_index` - The lower tick index of the position.
+    /// * `tick_upper_index` - The upper tick index of the position.
+    pub fn is_filled(&self, tick_current_index: i32, tick_lower_index: i32, tick_upper_index: i32) -> bool {
+        if self.a_to_b {
+            tick_current_index >= tick_upper_index
+        } else {
+            tick_current_index < tick_lower_index
+        }
+    }
+
+    /// Splits the converted tokens between the keeper bounty and the owner.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The amount of converted tokens withdrawn from the position.
+    ///
+    /// # Returns
+    ///
+    /// * `(u64, u64)` - The bounty of the keeper and the amount of the owner.
+    pub fn split_bounty(&self, amount: u64) -> (u64, u64) {
+        let bounty = ((amount as u128) * (self.bounty_bps as u128) / 10_000) as u64;
+        (bounty, amount - bounty)
+    }
+}
+
+#[cfg(test)]
+mod range_order_tests {
+    use super::*;
+
+    #[test]
+    fn test_initialize_below_price() {
+        let mut range_order = RangeOrder::default();
+        range_order
+            .initialize(Pubkey::new_unique(), Pubkey::new_unique(), 200, -128, 128, 10)
+            .unwrap();
+
+        assert!(!range_order.a_to_b);
+        assert!(!range_order.is_filled(-128, -128, 128));
+        assert!(range_order.is_filled(-129, -128, 128));
+    }
+
+    #[test]
+    fn test_initialize_above_price() {
+        let mut range_order = RangeOrder::default();
+        range_order
+            .initialize(Pubkey::new_unique(), Pubkey::new_unique(), -200, -128, 128, 10)
+            .unwrap();
+
+        assert!(range_order.a_to_b);
+        assert!(!range_order.is_filled(127, -128, 128));
+        assert!(range_order.is_filled(128, -128, 128));
+    }
+
+    #[test]
+    fn test_initialize_in_range() {
+        let mut range_order = RangeOrder::default();
+
+        assert!(range_order
+            .initialize(Pubkey::new_unique(), Pubkey::new_unique(), 0, -128, 128, 10)
+            .is_err());
+    }
+
+    #[test]
+    fn test_initialize_bounty_too_large() {
+        let mut range_order = RangeOrder::default();
+
+        assert!(range_order
+            .initialize(Pubkey::new_unique(), Pubkey::new_unique(), 200, -128, 128, 1_001)
+            .is_err());
+    }
+
+    #[test]
+    fn test_split_bounty() {
+        let range_order = RangeOrder {
+            bounty_bps: 25,
+            ..Default::default()
+        };
+
+        assert_eq!(range_order.split_bounty(1_000_000), (2_500, 997_500));
+        assert_eq!(range_order.split_bounty(39), (0, 39));
+    }
+}
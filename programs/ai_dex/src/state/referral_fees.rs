@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default)]
+pub struct ReferralFees {
+    pub ai_dex_pool: Pubkey, // 32
+    pub referrer: Pubkey, // 32
+    pub fee_owed_a: u64, // 8
+    pub fee_owed_b: u64, // 8
+                         // 64 RESERVE
+}
+
+/// The referral fees accrued by a referrer on the swaps of a pool.
+///
+/// The fees stay in the vaults of the pool, tracked by `referral_fee_owed_a` and
+/// `referral_fee_owed_b`, until the referrer collects them.
+impl ReferralFees {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 64;
+
+    /// Initializes the referral fees of a referrer on a pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `ai_dex_pool` - The pool the referral fees are accrued on.
+    /// * `referrer` - The referrer collecting the fees.
+    pub fn initialize(&mut self, ai_dex_pool: Pubkey, referrer: Pubkey) {
+        self.ai_dex_pool = ai_dex_pool;
+        self.referrer = referrer;
+    }
+
+    /// Adds the referral fee of a swap.
+    ///
+    /// # Arguments
+    ///
+    /// * `referral_fee` - The referral fee, in the input token of the swap.
+    /// * `is_token_fee_in_a` - Whether the fee is in token A.
+    pub fn accrue(&mut self, referral_fee: u64, is_token_fee_in_a: bool) {
+        if is_token_fee_in_a {
+            self.fee_owed_a += referral_fee;
+        } else {
+            self.fee_owed_b += referral_fee;
+        }
+    }
+
+    /// Resets the fees owed once they have been collected.
+    pub fn reset_fees_owed(&mut self) {
+        self.fee_owed_a = 0;
+        self.fee_owed_b = 0;
+    }
+}
@@ -0,0 +1,209 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+// Number of pools a session can be allowed to swap on
+pub const MAX_SESSION_POOLS: usize = 4;
+
+#[account]
+#[derive(Default)]
+pub struct Session {
+    pub owner: Pubkey,         // 32
+    pub session_key: Pubkey,   // 32
+    pub expiry_timestamp: i64, // 8
+
+    // The notional of a swap is its amount of the notional mint, input or output
+    pub notional_mint: Pubkey, // 32
+    pub max_notional: u64,     // 8
+    pub notional_used: u64,    // 8
+
+    // The default pubkey for the unused slots
+    pub allowed_pools: [Pubkey; MAX_SESSION_POOLS], // 128
+
+    pub bump: [u8; 1], // 1
+    // 64 RESERVE
+}
+
+/// Struct representing the swap rights an owner delegates to a session key.
+///
+/// The session key signs swaps as the token authority, on the token accounts of the owner, on
+/// the allowed pools only, until the session expires and within a cumulative notional. The
+/// owner approves the session account as the delegate of the token accounts the swaps are paid
+/// from, and the output of the swaps is always sent to token accounts of the owner.
+impl Session {
+    /// Length of the `Session` struct in bytes.
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 32 + 8 + 8 + 32 * MAX_SESSION_POOLS + 1 + 64;
+
+    /// Initializes the `Session` struct.
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - The owner delegating its swap rights.
+    /// * `session_key` - The key the swap rights are delegated to.
+    /// * `expiry_timestamp` - The unix timestamp after which the session key can no longer swap.
+    /// * `notional_mint` - The mint the notional of the swaps is measured in.
+    /// * `max_notional` - The maximum cumulative notional of the swaps.
+    /// * `allowed_pools` - The pools the session key can swap on.
+    /// * `bump` - The bump of the session PDA.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no pool or more than `MAX_SESSION_POOLS` pools are allowed.
+    pub fn initialize(
+        &mut self,
+        owner: Pubkey,
+        session_key: Pubkey,
+        expiry_timestamp: i64,
+        notional_mint: Pubkey,
+        max_notional: u64,
+        allowed_pools: &[Pubkey],
+        bump: u8,
+    ) -> Result<()> {
+        if allowed_pools.is_empty()
+            || allowed_pools.len() > MAX_SESSION_POOLS
+            || allowed_pools.contains(&Pubkey::default())
+        {
+            return Err(ErrorCode::InvalidSessionPoolsError.into());
+        }
+
+        self.owner = owner;
+        self.session_key = session_key;
+        self.expiry_timestamp = expiry_timestamp;
+        self.notional_mint = notional_mint;
+        self.max_notional = max_notional;
+        self.notional_used = 0;
+        self.allowed_pools = [Pubkey::default(); MAX_SESSION_POOLS];
+        self.allowed_pools[..allowed_pools.len()].copy_from_slice(allowed_pools);
+        self.bump = [bump];
+        Ok(())
+    }
+
+    /// Returns the seeds of the session PDA, to sign as the delegate of the owner token accounts.
+    pub fn seeds(&self) -> [&[u8]; 4] {
+        [
+            &b"session"[..],
+            self.owner.as_ref(),
+            self.session_key.as_ref(),
+            self.bump.as_ref(),
+        ]
+    }
+
+    /// Returns true if the session key can swap on the pool.
+    pub fn is_pool_allowed(&self, ai_dex_pool: &Pubkey) -> bool {
+        *ai_dex_pool != Pubkey::default() && self.allowed_pools.contains(ai_dex_pool)
+    }
+
+    /// Records a swap of the session key, checking it against the limits of the session.
+    ///
+    /// # Arguments
+    ///
+    /// * `ai_dex_pool` - The pool of the swap.
+    /// * `token_mint_a` - The mint of token A of the pool.
+    /// * `token_mint_b` - The mint of token B of the pool.
+    /// * `amount_a` - The amount of token A of the swap.
+    /// * `amount_b` - The amount of token B of the swap.
+    /// * `timestamp` - The current unix timestamp.
+    ///
+    /// # Errors
+    ///
+    /// * `ErrorCode::SessionExpiredError` - If the session has expired.
+    /// * `ErrorCode::SessionPoolNotAllowedError` - If the pool is not allowed or does not trade the notional mint.
+    /// * `ErrorCode::SessionNotionalLimitExceededError` - If the swap exceeds the remaining notional of the session.
+    pub fn record_swap(
+        &mut self,
+        ai_dex_pool: &Pubkey,
+        token_mint_a: &Pubkey,
+        token_mint_b: &Pubkey,
+        amount_a: u64,
+        amount_b: u64,
+        timestamp: i64,
+    ) -> Result<()> {
+        if timestamp > self.expiry_timestamp {
+            return Err(ErrorCode::SessionExpiredError.into());
+        }
+        if !self.is_pool_allowed(ai_dex_pool) {
+            return Err(ErrorCode::SessionPoolNotAllowedError.into());
+        }
+
+        let notional = if *token_mint_a == self.notional_mint {
+            amount_a
+        } else if *token_mint_b == self.notional_mint {
+            amount_b
+        } else {
+            return Err(ErrorCode::SessionPoolNotAllowedError.into());
+        };
+
+        let notional_used = self
+            .notional_used
+            .checked_add(notional)
+            .filter(|notional_used| *notional_used <= self.max_notional)
+            .ok_or(ErrorCode::SessionNotionalLimitExceededError)?;
+        self.notional_used = notional_used;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod session_tests {
+    use super::*;
+
+    fn new_session(notional_mint: Pubkey, ai_dex_pool: Pubkey) -> Session {
+        let mut session = Session::default();
+        session
+            .initialize(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                1_000,
+                notional_mint,
+                500,
+                &[ai_dex_pool],
+                255,
+            )
+            .unwrap();
+        session
+    }
+
+    #[test]
+    fn test_initialize_allowed_pools() {
+        let mut session = Session::default();
+        let pools = [Pubkey::new_unique(); MAX_SESSION_POOLS + 1];
+        let initialize = |session: &mut Session, pools: &[Pubkey]| {
+            session.initialize(Pubkey::new_unique(), Pubkey::new_unique(), 0, Pubkey::new_unique(), 0, pools, 255)
+        };
+
+        assert!(initialize(&mut session, &[]).is_err());
+        assert!(initialize(&mut session, &pools).is_err());
+        assert!(initialize(&mut session, &[Pubkey::default()]).is_err());
+        initialize(&mut session, &pools[..MAX_SESSION_POOLS]).unwrap();
+        assert!(session.is_pool_allowed(&pools[0]));
+        assert!(!session.is_pool_allowed(&Pubkey::new_unique()));
+        assert!(!session.is_pool_allowed(&Pubkey::default()));
+    }
+
+    #[test]
+    fn test_record_swap_notional() {
+        let notional_mint = Pubkey::new_unique();
+        let other_mint = Pubkey::new_unique();
+        let ai_dex_pool = Pubkey::new_unique();
+        let mut session = new_session(notional_mint, ai_dex_pool);
+
+        session.record_swap(&ai_dex_pool, &notional_mint, &other_mint, 200, 10, 0).unwrap();
+        session.record_swap(&ai_dex_pool, &other_mint, &notional_mint, 10, 300, 0).unwrap();
+        assert_eq!(session.notional_used, 500);
+        assert!(session.record_swap(&ai_dex_pool, &notional_mint, &other_mint, 1, 0, 0).is_err());
+        assert_eq!(session.notional_used, 500);
+    }
+
+    #[test]
+    fn test_record_swap_limits() {
+        let notional_mint = Pubkey::new_unique();
+        let other_mint = Pubkey::new_unique();
+        let ai_dex_pool = Pubkey::new_unique();
+        let mut session = new_session(notional_mint, ai_dex_pool);
+
+        assert!(session.record_swap(&ai_dex_pool, &notional_mint, &other_mint, 1, 1, 1_001).is_err());
+        assert!(session.record_swap(&Pubkey::new_unique(), &notional_mint, &other_mint, 1, 1, 0).is_err());
+        assert!(session.record_swap(&ai_dex_pool, &other_mint, &Pubkey::new_unique(), 1, 1, 0).is_err());
+        session.record_swap(&ai_dex_pool, &notional_mint, &other_mint, 1, 1, 1_000).unwrap();
+    }
+}
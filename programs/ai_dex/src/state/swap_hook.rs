@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+
+// Discriminator of the instruction the swap hook programs are called with, the Anchor
+// discriminator of an `after_swap` instruction
+pub const SWAP_HOOK_DISCRIMINATOR: [u8; 8] = [235, 215, 232, 183, 152, 109, 5, 35];
+
+#[account]
+#[derive(Default)]
+pub struct SwapHook {
+    pub ai_dex_config: Pubkey, // 32
+    pub hook_program: Pubkey,  // 32
+    // 64 RESERVE
+}
+
+/// Struct representing a program whitelisted by the config authority as a swap hook.
+///
+/// The pools of the config can register a whitelisted program, which is then called after each
+/// swap with a `SwapHookSummary`.
+impl SwapHook {
+    /// Length of the `SwapHook` struct in bytes.
+    pub const LEN: usize = 8 + 32 + 32 + 64;
+
+    /// Initializes the `SwapHook` struct.
+    ///
+    /// # Arguments
+    ///
+    /// * `ai_dex_config` - The config whitelisting the program.
+    /// * `hook_program` - The whitelisted program.
+    pub fn initialize(&mut self, ai_dex_config: Pubkey, hook_program: Pubkey) {
+        self.ai_dex_config = ai_dex_config;
+        self.hook_program = hook_program;
+    }
+}
+
+/// The summary of a swap passed to the swap hook program of the pool.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SwapHookSummary {
+    pub ai_dex_pool: Pubkey,
+    pub token_authority: Pubkey,
+    pub a_to_b: bool,
+    pub input_amount: u64,
+    pub output_amount: u64,
+    pub fee_rate: u16,
+    pub lp_fee: u64,
+    pub protocol_fee: u64,
+    pub pre_sqrt_price: u128,
+    pub post_sqrt_price: u128,
+    pub post_tick_index: i32,
+    pub timestamp: u64,
+}
+
+impl SwapHookSummary {
+    /// Returns the data of the instruction calling the swap hook program with this summary.
+    pub fn instruction_data(&self) -> Result<Vec<u8>> {
+        let mut data = SWAP_HOOK_DISCRIMINATOR.to_vec();
+        self.serialize(&mut data)?;
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+mod swap_hook_tests {
+    use super::*;
+
+    #[test]
+    fn test_swap_hook_discriminator() {
+        let hash = anchor_lang::solana_program::hash::hash(b"global:after_swap");
+        assert_eq!(hash.to_bytes()[..8], SWAP_HOOK_DISCRIMINATOR);
+    }
+
+    #[test]
+    fn test_swap_hook_summary_instruction_data() {
+        let summary = SwapHookSummary {
+            ai_dex_pool: Pubkey::new_unique(),
+            token_authority: Pubkey::new_unique(),
+            a_to_b: true,
+            input_amount: 1_000,
+            output_amount: 990,
+            post_tick_index: -10,
+            ..Default::default()
+        };
+
+        let data = summary.instruction_data().unwrap();
+        assert_eq!(data[..8], SWAP_HOOK_DISCRIMINATOR);
+        assert_eq!(SwapHookSummary::try_from_slice(&data[8..]).unwrap(), summary);
+    }
+}
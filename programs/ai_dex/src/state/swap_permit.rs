@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+// Prefix of the messages signed for swap permits, followed by the program id and the permit
+pub const SWAP_PERMIT_DOMAIN: &[u8] = b"ai_dex:swap_permit";
+
+#[account]
+#[derive(Default)]
+pub struct SwapPermitNonce {
+    pub owner: Pubkey, // 32
+    pub nonce: u64,    // 8
+    pub bump: [u8; 1], // 1
+    // 32 RESERVE
+}
+
+/// Struct representing the nonce of the swap permits of an owner.
+///
+/// Each permit carries the current nonce, which is incremented when the permit is used, so a
+/// permit can be used only once. The account is also the delegate the owner approves on the
+/// token accounts the permit swaps are paid from.
+impl SwapPermitNonce {
+    /// Length of the `SwapPermitNonce` struct in bytes.
+    pub const LEN: usize = 8 + 32 + 8 + 1 + 32;
+
+    /// Initializes the `SwapPermitNonce` struct.
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - The owner signing the permits.
+    /// * `bump` - The bump of the nonce PDA.
+    pub fn initialize(&mut self, owner: Pubkey, bump: u8) {
+        self.owner = owner;
+        self.nonce = 0;
+        self.bump = [bump];
+    }
+
+    /// Returns the seeds of the nonce PDA, to sign as the delegate of the owner token accounts.
+    pub fn seeds(&self) -> [&[u8]; 3] {
+        [&b"swap_permit_nonce"[..], self.owner.as_ref(), self.bump.as_ref()]
+    }
+
+    /// Uses the current nonce, invalidating the permits signed with it.
+    ///
+    /// # Arguments
+    ///
+    /// * `nonce` - The nonce of the permit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the nonce is not the current nonce.
+    pub fn use_nonce(&mut self, nonce: u64) -> Result<()> {
+        if nonce != self.nonce {
+            return Err(ErrorCode::InvalidSwapPermitNonceError.into());
+        }
+        self.nonce = self
+            .nonce
+            .checked_add(1)
+            .ok_or(ErrorCode::InvalidSwapPermitNonceError)?;
+        Ok(())
+    }
+}
+
+/// The parameters of a swap signed off-chain by the token authority.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SwapPermit {
+    pub ai_dex_pool: Pubkey,
+    pub amount: u64,
+    pub other_amount_threshold: u64,
+    pub sqrt_price_limit: u128,
+    pub amount_specified_is_input: bool,
+    pub a_to_b: bool,
+    pub nonce: u64,
+    pub deadline_timestamp: i64,
+}
+
+impl SwapPermit {
+    /// Returns the message the token authority signs to authorize the permit.
+    ///
+    /// The message is bound to the program id, so a permit cannot be replayed on another
+    /// deployment of the program.
+    pub fn message(&self) -> Result<Vec<u8>> {
+        let mut message = SWAP_PERMIT_DOMAIN.to_vec();
+        message.extend_from_slice(crate::ID.as_ref());
+        self.serialize(&mut message)?;
+        Ok(message)
+    }
+}
+
+#[cfg(test)]
+mod swap_permit_tests {
+    use super::*;
+
+    #[test]
+    fn test_use_nonce() {
+        let mut swap_permit_nonce = SwapPermitNonce::default();
+        swap_permit_nonce.initialize(Pubkey::new_unique(), 255);
+
+        assert!(swap_permit_nonce.use_nonce(1).is_err());
+        swap_permit_nonce.use_nonce(0).unwrap();
+        assert_eq!(swap_permit_nonce.nonce, 1);
+        assert!(swap_permit_nonce.use_nonce(0).is_err());
+        swap_permit_nonce.use_nonce(1).unwrap();
+        assert_eq!(swap_permit_nonce.nonce, 2);
+    }
+
+    #[test]
+    fn test_swap_permit_message() {
+        let permit = SwapPermit {
+            ai_dex_pool: Pubkey::new_unique(),
+            amount: 1_000,
+            a_to_b: true,
+            nonce: 7,
+            deadline_timestamp: 1_700_000_000,
+            ..Default::default()
+        };
+
+        let message = permit.message().unwrap();
+        let prefix_len = SWAP_PERMIT_DOMAIN.len() + 32;
+        assert_eq!(message[..SWAP_PERMIT_DOMAIN.len()], *SWAP_PERMIT_DOMAIN);
+        assert_eq!(message[SWAP_PERMIT_DOMAIN.len()..prefix_len], crate::ID.to_bytes());
+        assert_eq!(SwapPermit::try_from_slice(&message[prefix_len..]).unwrap(), permit);
+
+        let other_nonce = SwapPermit { nonce: 8, ..permit };
+        assert_ne!(other_nonce.message().unwrap(), message);
+    }
+}
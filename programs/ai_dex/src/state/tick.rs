@@ -4,9 +4,14 @@ use anchor_lang::prelude::*;
 
 use super::AiDexPool;
 
-// Max & min tick index based on sqrt(1.0001) & max.min price of 2^64
-pub const MAX_TICK_INDEX: i32 = 443636;
-pub const MIN_TICK_INDEX: i32 = -443636;
+// Max & min tick index based on sqrt(1.0001) & max.min price of 2^75
+pub const MAX_TICK_INDEX: i32 = 519886;
+pub const MIN_TICK_INDEX: i32 = -519886;
+
+// Max & min tick index of the pools initialized before the tick range was widened, based on a
+// max.min price of 2^64. Their positions and swaps stay within these bounds.
+pub const LEGACY_MAX_TICK_INDEX: i32 = 443636;
+pub const LEGACY_MIN_TICK_INDEX: i32 = -443636;
 
 // We have two consts because most of our code uses it as a i32. However,
 // for us to use it in tick array declarations, anchor requires it to be a usize.
@@ -17,7 +22,7 @@ pub const TICK_ARRAY_SIZE_USIZE: usize = 88;
 #[repr(packed)]
 #[derive(Default, Debug, PartialEq)]
 pub struct Tick {
-    // Total 137 bytes
+    // Total 153 bytes
     pub initialized: bool,     // 1
     pub liquidity_net: i128,   // 16
     pub liquidity_gross: u128, // 16
@@ -29,10 +34,13 @@ pub struct Tick {
 
     // Array of Q64.64
     pub reward_growths_outside: [u128; NUM_REWARDS], // 48 = 16 * 3
+
+    // Q64.64
+    pub seconds_per_liquidity_outside_x64: u128, // 16
 }
 
 impl Tick {
-    pub const LEN: usize = 113;
+    pub const LEN: usize = 129;
 
     /// Apply an update for this tick
     ///
@@ -45,6 +53,7 @@ impl Tick {
         self.fee_growth_outside_a = update.fee_growth_outside_a;
         self.fee_growth_outside_b = update.fee_growth_outside_b;
         self.reward_growths_outside = update.reward_growths_outside;
+        self.seconds_per_liquidity_outside_x64 = update.seconds_per_liquidity_outside_x64;
     }
 
     /// Check that the tick index is within the supported range of this contract
@@ -98,9 +107,25 @@ impl Tick {
         tick_index % tick_spacing as i32 == 0
     }
 
-    pub fn full_range_indexes(tick_spacing: u16) -> (i32, i32) {
-        let lower_index = MIN_TICK_INDEX / tick_spacing as i32 * tick_spacing as i32;
-        let upper_index = MAX_TICK_INDEX / tick_spacing as i32 * tick_spacing as i32;
+    /// Get the min & max tick index of a pool
+    ///
+    /// # Parameters
+    /// - `extended_tick_range` - Whether the pool was initialized after the tick range was widened
+    ///
+    /// # Returns
+    /// - `(i32, i32)` The min & max tick index, the legacy ones unless the tick range is extended.
+    pub fn tick_index_bounds(extended_tick_range: bool) -> (i32, i32) {
+        if extended_tick_range {
+            (MIN_TICK_INDEX, MAX_TICK_INDEX)
+        } else {
+            (LEGACY_MIN_TICK_INDEX, LEGACY_MAX_TICK_INDEX)
+        }
+    }
+
+    pub fn full_range_indexes(tick_spacing: u16, extended_tick_range: bool) -> (i32, i32) {
+        let (min_tick_index, max_tick_index) = Tick::tick_index_bounds(extended_tick_range);
+        let lower_index = min_tick_index / tick_spacing as i32 * tick_spacing as i32;
+        let upper_index = max_tick_index / tick_spacing as i32 * tick_spacing as i32;
         (lower_index, upper_index)
     }
 
@@ -124,6 +149,7 @@ pub struct TickUpdate {
     pub fee_growth_outside_a: u128,
     pub fee_growth_outside_b: u128,
     pub reward_growths_outside: [u128; NUM_REWARDS],
+    pub seconds_per_liquidity_outside_x64: u128,
 }
 
 impl TickUpdate {
@@ -135,6 +161,7 @@ impl TickUpdate {
             fee_growth_outside_a: tick.fee_growth_outside_a,
             fee_growth_outside_b: tick.fee_growth_outside_b,
             reward_growths_outside: tick.reward_growths_outside,
+            seconds_per_liquidity_outside_x64: tick.seconds_per_liquidity_outside_x64,
         }
     }
 }
@@ -223,10 +250,11 @@ impl TickArray {
     /// - `InvalidStartTickIndex`: - The provided start-tick-index is not an initializable tick index in this AiDex w/ this tick-spacing.
     pub fn initialize(
         &mut self,
-        ai_dex: &Account<AiDexPool>,
+        ai_dex: &AccountLoader<AiDexPool>,
         start_tick_index: i32,
     ) -> Result<()> {
-        if !Tick::check_is_valid_start_tick(start_tick_index, ai_dex.tick_spacing) {
+        let tick_spacing = ai_dex.load()?.tick_spacing;
+        if !Tick::check_is_valid_start_tick(start_tick_index, tick_spacing) {
             return Err(ErrorCode::InvalidStartTickIndex.into());
         }
 
@@ -284,6 +312,34 @@ impl TickArray {
         self.ticks.get_mut(offset as usize).unwrap().update(update);
         Ok(())
     }
+}
+
+/// Common interface of the fixed `TickArray` and the sparse `DynamicTickArray`,
+/// so the swap and liquidity logic can work with either kind of tick array.
+pub trait TickArrayType {
+    fn start_tick_index(&self) -> i32;
+
+    fn ai_dex_pool(&self) -> Pubkey;
+
+    /// Search for the next initialized tick in this array.
+    /// See `TickArray::get_next_init_tick_index` for the search semantics.
+    fn get_next_init_tick_index(
+        &self,
+        tick_index: i32,
+        tick_spacing: u16,
+        a_to_b: bool,
+    ) -> Result<Option<i32>>;
+
+    /// Get the Tick object at the given tick-index & tick-spacing
+    fn get_tick(&self, tick_index: i32, tick_spacing: u16) -> Result<&Tick>;
+
+    /// Updates the Tick object at the given tick-index & tick-spacing
+    fn update_tick(
+        &mut self,
+        tick_index: i32,
+        tick_spacing: u16,
+        update: &TickUpdate,
+    ) -> Result<()>;
 
     /// Checks that this array holds the next tick index for the current tick index, given the pool's tick spacing & search direction.
     ///
@@ -297,9 +353,9 @@ impl TickArray {
     ///
     /// For b_to_a swaps, this tick-array's left-most ticks can be the 'next' usable tick-index of the previous tick-array.
     /// The right-most ticks also points towards the next tick-array. The search range is therefore shifted by 1 tick-spacing.
-    pub fn in_search_range(&self, tick_index: i32, tick_spacing: u16, shifted: bool) -> bool {
-        let mut lower = self.start_tick_index;
-        let mut upper = self.start_tick_index + TICK_ARRAY_SIZE * tick_spacing as i32;
+    fn in_search_range(&self, tick_index: i32, tick_spacing: u16, shifted: bool) -> bool {
+        let mut lower = self.start_tick_index();
+        let mut upper = self.start_tick_index() + TICK_ARRAY_SIZE * tick_spacing as i32;
         if shifted {
             lower = lower - tick_spacing as i32;
             upper = upper - tick_spacing as i32;
@@ -307,25 +363,57 @@ impl TickArray {
         tick_index >= lower && tick_index < upper
     }
 
-    pub fn check_in_array_bounds(&self, tick_index: i32, tick_spacing: u16) -> bool {
+    fn check_in_array_bounds(&self, tick_index: i32, tick_spacing: u16) -> bool {
         self.in_search_range(tick_index, tick_spacing, false)
     }
 
-    pub fn is_min_tick_array(&self) -> bool {
-        self.start_tick_index <= MIN_TICK_INDEX
+    fn is_min_tick_array(&self, min_tick_index: i32) -> bool {
+        self.start_tick_index() <= min_tick_index
     }
 
-    pub fn is_max_tick_array(&self, tick_spacing: u16) -> bool {
-        self.start_tick_index + TICK_ARRAY_SIZE * (tick_spacing as i32) > MAX_TICK_INDEX
+    fn is_max_tick_array(&self, max_tick_index: i32, tick_spacing: u16) -> bool {
+        self.start_tick_index() + TICK_ARRAY_SIZE * (tick_spacing as i32) > max_tick_index
     }
 
     // Calculates an offset from a tick index that can be used to access the tick data
-    pub fn tick_offset(&self, tick_index: i32, tick_spacing: u16) -> Result<isize> {
+    fn tick_offset(&self, tick_index: i32, tick_spacing: u16) -> Result<isize> {
         if tick_spacing == 0 {
             return Err(ErrorCode::UnsupportedTickSpacing.into());
         }
 
-        Ok(get_offset(tick_index, self.start_tick_index, tick_spacing))
+        Ok(get_offset(tick_index, self.start_tick_index(), tick_spacing))
+    }
+}
+
+impl TickArrayType for TickArray {
+    fn start_tick_index(&self) -> i32 {
+        self.start_tick_index
+    }
+
+    fn ai_dex_pool(&self) -> Pubkey {
+        self.ai_dex_pool
+    }
+
+    fn get_next_init_tick_index(
+        &self,
+        tick_index: i32,
+        tick_spacing: u16,
+        a_to_b: bool,
+    ) -> Result<Option<i32>> {
+        TickArray::get_next_init_tick_index(self, tick_index, tick_spacing, a_to_b)
+    }
+
+    fn get_tick(&self, tick_index: i32, tick_spacing: u16) -> Result<&Tick> {
+        TickArray::get_tick(self, tick_index, tick_spacing)
+    }
+
+    fn update_tick(
+        &mut self,
+        tick_index: i32,
+        tick_spacing: u16,
+        update: &TickUpdate,
+    ) -> Result<()> {
+        TickArray::update_tick(self, tick_index, tick_spacing, update)
     }
 }
 
@@ -356,6 +444,7 @@ pub mod tick_builder {
         fee_growth_outside_a: u128,
         fee_growth_outside_b: u128,
         reward_growths_outside: [u128; NUM_REWARDS],
+        seconds_per_liquidity_outside_x64: u128,
     }
 
     impl TickBuilder {
@@ -392,6 +481,11 @@ pub mod tick_builder {
             self
         }
 
+        pub fn seconds_per_liquidity_outside_x64(mut self, seconds_per_liquidity_outside_x64: u128) -> Self {
+            self.seconds_per_liquidity_outside_x64 = seconds_per_liquidity_outside_x64;
+            self
+        }
+
         pub fn build(self) -> Tick {
             Tick {
                 initialized: self.initialized,
@@ -400,6 +494,7 @@ pub mod tick_builder {
                 fee_growth_outside_a: self.fee_growth_outside_a,
                 fee_growth_outside_b: self.fee_growth_outside_b,
                 reward_growths_outside: self.reward_growths_outside,
+                seconds_per_liquidity_outside_x64: self.seconds_per_liquidity_outside_x64,
             }
         }
     }
@@ -586,7 +681,7 @@ mod full_range_indexes_tests {
     #[test]
     fn test_min_tick_spacing() {
         assert_eq!(
-            Tick::full_range_indexes(1),
+            Tick::full_range_indexes(1, true),
             (MIN_TICK_INDEX, MAX_TICK_INDEX)
         );
     }
@@ -594,25 +689,39 @@ mod full_range_indexes_tests {
     #[test]
     fn test_standard_tick_spacing() {
         assert_eq!(
-            Tick::full_range_indexes(128),
-            (-443520, 443520)
+            Tick::full_range_indexes(128, true),
+            (-519808, 519808)
         );
     }
 
     #[test]
     fn test_full_range_only_tick_spacing() {
         assert_eq!(
-            Tick::full_range_indexes(FULL_RANGE_ONLY_TICK_SPACING_THRESHOLD),
-            (-425984, 425984)
+            Tick::full_range_indexes(FULL_RANGE_ONLY_TICK_SPACING_THRESHOLD, true),
+            (-491520, 491520)
         );
     }
 
     #[test]
     fn test_max_tick_spacing() {
         assert_eq!(
-            Tick::full_range_indexes(u16::MAX),
-            (-393210, 393210)
+            Tick::full_range_indexes(u16::MAX, true),
+            (-458745, 458745)
+        );
+    }
+
+    #[test]
+    fn test_legacy_tick_range() {
+        assert_eq!(
+            Tick::full_range_indexes(1, false),
+            (LEGACY_MIN_TICK_INDEX, LEGACY_MAX_TICK_INDEX)
+        );
+        assert_eq!(Tick::full_range_indexes(128, false), (-443520, 443520));
+        assert_eq!(
+            Tick::full_range_indexes(FULL_RANGE_ONLY_TICK_SPACING_THRESHOLD, false),
+            (-425984, 425984)
         );
+        assert_eq!(Tick::full_range_indexes(u16::MAX, false), (-393210, 393210));
     }
 }
 
@@ -631,6 +740,7 @@ mod array_update_tests {
             fee_growth_outside_a: 28728282u128,
             fee_growth_outside_b: 22528728282u128,
             reward_growths_outside: [124272242u128, 1271221u128, 966958u128],
+            seconds_per_liquidity_outside_x64: 83726u128,
         };
 
         array.ticks[1] = original;
@@ -642,6 +752,7 @@ mod array_update_tests {
             fee_growth_outside_a: 3928372892u128,
             fee_growth_outside_b: 12242u128,
             reward_growths_outside: [53264u128, 539282u128, 98744u128],
+            seconds_per_liquidity_outside_x64: 1923u128,
         };
 
         let tick_spacing = 8;
@@ -657,6 +768,7 @@ mod array_update_tests {
             fee_growth_outside_a: 3928372892u128,
             fee_growth_outside_b: 12242u128,
             reward_growths_outside: [53264u128, 539282u128, 98744u128],
+            seconds_per_liquidity_outside_x64: 1923u128,
         };
         let result = array.get_tick(tick_index, tick_spacing).unwrap();
         assert_eq!(*result, expected);
@@ -0,0 +1,194 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+use super::{Tick, MIN_TICK_INDEX, TICK_ARRAY_SIZE};
+
+// Number of u64 words in the bitmap. One bit per tick array, enough to cover
+// the full tick range at tick spacing 1 (11816 tick arrays).
+pub const TICK_ARRAY_BITMAP_WORDS: usize = 185;
+
+#[account(zero_copy(unsafe))]
+#[repr(packed)]
+pub struct TickArrayBitmap {
+    pub ai_dex_pool: Pubkey,
+    pub bitmap: [u64; TICK_ARRAY_BITMAP_WORDS],
+}
+
+impl Default for TickArrayBitmap {
+    #[inline]
+    fn default() -> TickArrayBitmap {
+        TickArrayBitmap {
+            ai_dex_pool: Pubkey::default(),
+            bitmap: [0; TICK_ARRAY_BITMAP_WORDS],
+        }
+    }
+}
+
+impl TickArrayBitmap {
+    pub const LEN: usize = 8 + 32 + 8 * TICK_ARRAY_BITMAP_WORDS;
+
+    /// Initialize the TickArrayBitmap object with no tick array marked as initialized
+    ///
+    /// # Parameters
+    /// - `ai_dex_pool` - The pool whose tick arrays are tracked
+    pub fn initialize(&mut self, ai_dex_pool: Pubkey) {
+        self.ai_dex_pool = ai_dex_pool;
+        self.bitmap = [0; TICK_ARRAY_BITMAP_WORDS];
+    }
+
+    /// Mark the tick array starting at `start_tick_index` as initialized
+    ///
+    /// # Errors
+    /// - `InvalidStartTickIndex` - The start tick index is not a valid tick array start for this tick spacing
+    pub fn set_initialized(&mut self, start_tick_index: i32, tick_spacing: u16) -> Result<()> {
+        let bit = bit_position(start_tick_index, tick_spacing)?;
+        self.bitmap[bit / 64] |= 1u64 << (bit % 64);
+        Ok(())
+    }
+
+    /// Mark the tick array starting at `start_tick_index` as not initialized
+    ///
+    /// # Errors
+    /// - `InvalidStartTickIndex` - The start tick index is not a valid tick array start for this tick spacing
+    pub fn set_uninitialized(&mut self, start_tick_index: i32, tick_spacing: u16) -> Result<()> {
+        let bit = bit_position(start_tick_index, tick_spacing)?;
+        self.bitmap[bit / 64] &= !(1u64 << (bit % 64));
+        Ok(())
+    }
+
+    /// Returns true if the tick array starting at `start_tick_index` is initialized
+    ///
+    /// # Errors
+    /// - `InvalidStartTickIndex` - The start tick index is not a valid tick array start for this tick spacing
+    pub fn is_initialized(&self, start_tick_index: i32, tick_spacing: u16) -> Result<bool> {
+        let bit = bit_position(start_tick_index, tick_spacing)?;
+        Ok(self.bitmap[bit / 64] & (1u64 << (bit % 64)) != 0)
+    }
+
+    /// Find the start tick index of the next initialized tick array, excluding the given one
+    ///
+    /// # Parameters
+    /// - `start_tick_index` - The start tick index of the tick array to search from
+    /// - `tick_spacing` - The tick spacing of the pool
+    /// - `a_to_b` - If true, the search moves to the left (lower ticks), otherwise to the right
+    ///
+    /// # Returns
+    /// - `Some(i32)` - The start tick index of the next initialized tick array
+    /// - `None` - No tick array is initialized in that direction
+    ///
+    /// # Errors
+    /// - `InvalidStartTickIndex` - The start tick index is not a valid tick array start for this tick spacing
+    pub fn next_initialized_start_tick_index(
+        &self,
+        start_tick_index: i32,
+        tick_spacing: u16,
+        a_to_b: bool,
+    ) -> Result<Option<i32>> {
+        let bit = bit_position(start_tick_index, tick_spacing)?;
+        let bitmap = self.bitmap;
+
+        let next_bit = if a_to_b {
+            if bit == 0 {
+                return Ok(None);
+            }
+            let last = bit - 1;
+            let mut word_index = last / 64;
+            let mut word = bitmap[word_index] & (u64::MAX >> (63 - last % 64));
+            loop {
+                if word != 0 {
+                    break Some(word_index * 64 + 63 - word.leading_zeros() as usize);
+                }
+                if word_index == 0 {
+                    break None;
+                }
+                word_index -= 1;
+                word = bitmap[word_index];
+            }
+        } else {
+            let first = bit + 1;
+            let mut word_index = first / 64;
+            if word_index >= TICK_ARRAY_BITMAP_WORDS {
+                return Ok(None);
+            }
+            let mut word = bitmap[word_index] & (u64::MAX << (first % 64));
+            loop {
+                if word != 0 {
+                    break Some(word_index * 64 + word.trailing_zeros() as usize);
+                }
+                word_index += 1;
+                if word_index == TICK_ARRAY_BITMAP_WORDS {
+                    break None;
+                }
+                word = bitmap[word_index];
+            }
+        };
+
+        let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+        Ok(next_bit.map(|bit| (bit as i32 + min_array_index(ticks_in_array)) * ticks_in_array))
+    }
+}
+
+/// Index of the left-most tick array for the given number of ticks per array.
+fn min_array_index(ticks_in_array: i32) -> i32 {
+    MIN_TICK_INDEX.div_euclid(ticks_in_array)
+}
+
+/// Maps a tick array start tick index to its bit in the bitmap.
+fn bit_position(start_tick_index: i32, tick_spacing: u16) -> Result<usize> {
+    if tick_spacing == 0 || !Tick::check_is_valid_start_tick(start_tick_index, tick_spacing) {
+        return Err(ErrorCode::InvalidStartTickIndex.into());
+    }
+
+    let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+    Ok((start_tick_index.div_euclid(ticks_in_array) - min_array_index(ticks_in_array)) as usize)
+}
+
+#[cfg(test)]
+mod tick_array_bitmap_tests {
+    use super::*;
+    use crate::state::MAX_TICK_INDEX;
+
+    const TS_1: u16 = 1;
+    const TS_64: u16 = 64;
+
+    #[test]
+    fn test_bit_position_covers_full_range() {
+        let min_start = MIN_TICK_INDEX.div_euclid(TICK_ARRAY_SIZE) * TICK_ARRAY_SIZE;
+        let max_start = MAX_TICK_INDEX.div_euclid(TICK_ARRAY_SIZE) * TICK_ARRAY_SIZE;
+
+        assert_eq!(bit_position(min_start, TS_1).unwrap(), 0);
+        assert!(bit_position(max_start, TS_1).unwrap() < TICK_ARRAY_BITMAP_WORDS * 64);
+        assert!(bit_position(max_start + TICK_ARRAY_SIZE, TS_1).is_err());
+        assert!(bit_position(1, TS_1).is_err());
+    }
+
+    #[test]
+    fn test_set_and_clear_initialized() {
+        let bitmap = &mut TickArrayBitmap::default();
+        let start = -2 * TICK_ARRAY_SIZE * TS_64 as i32;
+
+        assert!(!bitmap.is_initialized(start, TS_64).unwrap());
+        bitmap.set_initialized(start, TS_64).unwrap();
+        assert!(bitmap.is_initialized(start, TS_64).unwrap());
+        bitmap.set_uninitialized(start, TS_64).unwrap();
+        assert!(!bitmap.is_initialized(start, TS_64).unwrap());
+    }
+
+    #[test]
+    fn test_next_initialized_start_tick_index() {
+        let bitmap = &mut TickArrayBitmap::default();
+        let ticks_in_array = TICK_ARRAY_SIZE * TS_1 as i32;
+        let far_left = -5000 * ticks_in_array;
+        let far_right = 5000 * ticks_in_array;
+        bitmap.set_initialized(far_left, TS_1).unwrap();
+        bitmap.set_initialized(0, TS_1).unwrap();
+        bitmap.set_initialized(far_right, TS_1).unwrap();
+
+        assert_eq!(bitmap.next_initialized_start_tick_index(0, TS_1, true).unwrap(), Some(far_left));
+        assert_eq!(bitmap.next_initialized_start_tick_index(0, TS_1, false).unwrap(), Some(far_right));
+        assert_eq!(bitmap.next_initialized_start_tick_index(far_right, TS_1, true).unwrap(), Some(0));
+        assert_eq!(bitmap.next_initialized_start_tick_index(far_left, TS_1, true).unwrap(), None);
+        assert_eq!(bitmap.next_initialized_start_tick_index(far_right, TS_1, false).unwrap(), None);
+    }
+}
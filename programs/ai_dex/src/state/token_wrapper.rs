@@ -5,19 +5,28 @@ use anchor_lang::prelude::*;
 pub struct TokenWrapper {
     pub ai_dex_config: Pubkey, // 32
     pub token_mint: Pubkey, // 32
-    // 128 RESERVE
+    pub transfer_hook_program_id: Pubkey, // 32
+    pub allow_permanent_delegate: bool, // 1
+    pub allow_transfer_hook: bool, // 1
+    pub allow_mint_close_authority: bool, // 1
+    pub allow_freeze_authority: bool, // 1
+    pub allow_default_account_state: bool, // 1
+    // 91 RESERVE
 }
 
 /// Struct representing a token wrapper.
 ///
 /// The `TokenWrapper` struct holds information about the AI Dex configuration and the token mint.
 /// It also provides a method to initialize the struct with the given AI Dex configuration and token mint.
+/// Each `allow_*` flag enables the support of one mint extension or authority for the token mint.
 impl TokenWrapper {
     /// Length of the `TokenWrapper` struct in bytes.
-    pub const LEN: usize = 8 + 32 + 32 + 128;
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 1 + 1 + 1 + 1 + 1 + 91;
 
     /// Initializes the `TokenWrapper` struct with the given AI Dex configuration and token mint.
     ///
+    /// All the capability flags are enabled, they can be narrowed down with `set_flags`.
+    ///
     /// # Arguments
     ///
     /// * `ai_dex_config` - The AI Dex configuration pubkey.
@@ -33,8 +42,42 @@ impl TokenWrapper {
     ) -> Result<()> {
         self.ai_dex_config = ai_dex_config;
         self.token_mint = token_mint;
+        self.set_flags(true, true, true, true, true);
         Ok(())
     }
+
+    /// Sets the capability flags of the token wrapper.
+    ///
+    /// # Arguments
+    ///
+    /// * `allow_permanent_delegate` - Whether the mint may have a permanent delegate.
+    /// * `allow_transfer_hook` - Whether the mint may have a transfer hook.
+    /// * `allow_mint_close_authority` - Whether the mint may have a close authority.
+    /// * `allow_freeze_authority` - Whether the mint may have a freeze authority.
+    /// * `allow_default_account_state` - Whether the mint may have a default account state.
+    pub fn set_flags(
+        &mut self,
+        allow_permanent_delegate: bool,
+        allow_transfer_hook: bool,
+        allow_mint_close_authority: bool,
+        allow_freeze_authority: bool,
+        allow_default_account_state: bool,
+    ) {
+        self.allow_permanent_delegate = allow_permanent_delegate;
+        self.allow_transfer_hook = allow_transfer_hook;
+        self.allow_mint_close_authority = allow_mint_close_authority;
+        self.allow_freeze_authority = allow_freeze_authority;
+        self.allow_default_account_state = allow_default_account_state;
+    }
+
+    /// Sets the transfer hook program allowed for the token mint.
+    ///
+    /// # Arguments
+    ///
+    /// * `transfer_hook_program_id` - The allowlisted transfer hook program, or the default pubkey for none.
+    pub fn set_transfer_hook_program_id(&mut self, transfer_hook_program_id: Pubkey) {
+        self.transfer_hook_program_id = transfer_hook_program_id;
+    }
 }
 
 #[cfg(test)]
@@ -69,5 +112,36 @@ mod token_wrapper_initialize_tests {
 
         assert_eq!(ai_dex_config, token_wrapper.ai_dex_config);
         assert_eq!(token_mint, token_wrapper.token_mint);
+        assert!(token_wrapper.allow_permanent_delegate);
+        assert!(token_wrapper.allow_transfer_hook);
+        assert!(token_wrapper.allow_mint_close_authority);
+        assert!(token_wrapper.allow_freeze_authority);
+        assert!(token_wrapper.allow_default_account_state);
+    }
+
+    #[test]
+    fn test_set_flags() {
+        let mut token_wrapper = TokenWrapper {
+            ..Default::default()
+        };
+
+        token_wrapper.set_flags(true, false, true, false, true);
+        assert!(token_wrapper.allow_permanent_delegate);
+        assert!(!token_wrapper.allow_transfer_hook);
+        assert!(token_wrapper.allow_mint_close_authority);
+        assert!(!token_wrapper.allow_freeze_authority);
+        assert!(token_wrapper.allow_default_account_state);
+    }
+
+    #[test]
+    fn test_set_transfer_hook_program_id() {
+        let mut token_wrapper = TokenWrapper {
+            ..Default::default()
+        };
+        let transfer_hook_program_id =
+            Pubkey::from_str("HookPRGx1mDwyUWkGqFzTstsBXUqnS8ZADvUKsNk1TnV").unwrap();
+
+        token_wrapper.set_transfer_hook_program_id(transfer_hook_program_id);
+        assert_eq!(transfer_hook_program_id, token_wrapper.transfer_hook_program_id);
     }
 }
@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+use super::POSITION_TRADE_BATCH_SIZE;
+
+pub const TRADE_BATCH_EXTENSION_BITMAP_USIZE: usize = 480;
+pub const TRADE_BATCH_EXTENSION_SIZE: u16 = 8 * TRADE_BATCH_EXTENSION_BITMAP_USIZE as u16;
+/// The number of trade batch indexes a position trade batch can manage with its extension.
+pub const MAX_TRADE_BATCH_SIZE: u16 = POSITION_TRADE_BATCH_SIZE + TRADE_BATCH_EXTENSION_SIZE;
+
+/// Extends the occupancy bitmap of a position trade batch beyond `POSITION_TRADE_BATCH_SIZE`.
+///
+/// Bit `i` of the extension tracks the trade batch index `POSITION_TRADE_BATCH_SIZE + i`.
+#[account(zero_copy(unsafe))]
+#[repr(packed)]
+pub struct TradeBatchExtension {
+    pub position_trade_batch: Pubkey,
+    pub position_bitmap: [u8; TRADE_BATCH_EXTENSION_BITMAP_USIZE],
+}
+
+impl Default for TradeBatchExtension {
+    #[inline]
+    fn default() -> TradeBatchExtension {
+        TradeBatchExtension {
+            position_trade_batch: Pubkey::default(),
+            position_bitmap: [0; TRADE_BATCH_EXTENSION_BITMAP_USIZE],
+        }
+    }
+}
+
+impl TradeBatchExtension {
+    pub const LEN: usize = 8 + 32 + TRADE_BATCH_EXTENSION_BITMAP_USIZE;
+
+    /// Initialize the TradeBatchExtension object with no trade batch index opened
+    ///
+    /// # Parameters
+    /// - `position_trade_batch` - The position trade batch being extended
+    pub fn initialize(&mut self, position_trade_batch: Pubkey) {
+        self.position_trade_batch = position_trade_batch;
+        self.position_bitmap = [0; TRADE_BATCH_EXTENSION_BITMAP_USIZE];
+    }
+
+    /// Returns true if no trade batch index of the extension is opened
+    pub fn is_empty(&self) -> bool {
+        self.position_bitmap.iter().all(|&bitmap| bitmap == 0)
+    }
+
+    /// Mark the trade batch index as opened
+    ///
+    /// # Errors
+    /// - `InvalidTradeBatchIndexError` - The index is not covered by the extension
+    /// - `PositionAlreadyOpenedError` - The index is already opened
+    pub fn open_trade_batch_position(&mut self, trade_batch_index: u16) -> Result<()> {
+        self.update_bitmap(trade_batch_index, true)
+    }
+
+    /// Mark the trade batch index as closed
+    ///
+    /// # Errors
+    /// - `InvalidTradeBatchIndexError` - The index is not covered by the extension
+    /// - `PositionAlreadyClosedError` - The index is already closed
+    pub fn close_trade_batch_position(&mut self, trade_batch_index: u16) -> Result<()> {
+        self.update_bitmap(trade_batch_index, false)
+    }
+
+    fn update_bitmap(&mut self, trade_batch_index: u16, open: bool) -> Result<()> {
+        if !(POSITION_TRADE_BATCH_SIZE..MAX_TRADE_BATCH_SIZE).contains(&trade_batch_index) {
+            return Err(ErrorCode::InvalidTradeBatchIndexError.into());
+        }
+
+        let bit = trade_batch_index - POSITION_TRADE_BATCH_SIZE;
+        let bitmap = &mut self.position_bitmap[(bit / 8) as usize];
+        let mask = 1 << (bit % 8);
+
+        if open && *bitmap & mask != 0 {
+            return Err(ErrorCode::PositionAlreadyOpenedError.into());
+        }
+        if !open && *bitmap & mask == 0 {
+            return Err(ErrorCode::PositionAlreadyClosedError.into());
+        }
+
+        *bitmap ^= mask;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod trade_batch_extension_tests {
+    use super::*;
+
+    #[test]
+    fn test_default_is_empty() {
+        assert!(TradeBatchExtension::default().is_empty());
+    }
+
+    #[test]
+    fn test_open_and_close_each_index() {
+        let extension = &mut TradeBatchExtension::default();
+        for trade_batch_index in POSITION_TRADE_BATCH_SIZE..MAX_TRADE_BATCH_SIZE {
+            extension.open_trade_batch_position(trade_batch_index).unwrap();
+            assert!(!extension.is_empty());
+            extension.close_trade_batch_position(trade_batch_index).unwrap();
+            assert!(extension.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_index_out_of_extension_range() {
+        let extension = &mut TradeBatchExtension::default();
+        for trade_batch_index in [0, POSITION_TRADE_BATCH_SIZE - 1, MAX_TRADE_BATCH_SIZE, u16::MAX] {
+            assert!(extension.open_trade_batch_position(trade_batch_index).is_err());
+            assert!(extension.close_trade_batch_position(trade_batch_index).is_err());
+        }
+    }
+
+    #[test]
+    fn test_open_twice_and_close_twice() {
+        let extension = &mut TradeBatchExtension::default();
+        let trade_batch_index = MAX_TRADE_BATCH_SIZE - 1;
+
+        assert!(extension.close_trade_batch_position(trade_batch_index).is_err());
+        extension.open_trade_batch_position(trade_batch_index).unwrap();
+        assert!(extension.open_trade_batch_position(trade_batch_index).is_err());
+        extension.close_trade_batch_position(trade_batch_index).unwrap();
+        assert!(extension.close_trade_batch_position(trade_batch_index).is_err());
+    }
+}
@@ -0,0 +1,175 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::math::{MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64};
+
+pub const MAX_TRIGGER_ORDER_BOUNTY_BPS: u16 = 1_000;
+
+#[account]
+#[derive(Default)]
+pub struct TriggerOrder {
+    pub position: Pubkey, // 32
+    pub funder: Pubkey,   // 32
+
+    // Triggers when the pool sqrt price falls to or below it, 0 if unset
+    pub trigger_sqrt_price_lower: u128, // 16
+    // Triggers when the pool sqrt price rises to or above it, 0 if unset
+    pub trigger_sqrt_price_upper: u128, // 16
+
+    // The withdrawn token A is swapped into token B if true, token B into token A otherwise
+    pub a_to_b: bool,         // 1
+    pub min_amount_out: u64,  // 8
+    pub bounty_bps: u16,      // 2
+    // 64 RESERVE
+}
+
+/// Struct representing a stop-loss or take-profit order on a position.
+///
+/// Once the sqrt price of the pool crosses one of the triggers, any keeper can withdraw all the
+/// liquidity of the position and swap it into a single target token for the position owner.
+impl TriggerOrder {
+    /// Length of the `TriggerOrder` struct in bytes.
+    pub const LEN: usize = 8 + 32 + 32 + 16 + 16 + 1 + 8 + 2 + 64;
+
+    /// Initializes the `TriggerOrder` struct.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The position pubkey.
+    /// * `funder` - The account paying the rent, refunded when the order is closed.
+    /// * `sqrt_price` - The current sqrt price of the pool.
+    /// * `trigger_sqrt_price_lower` - The lower trigger, `0` if unset.
+    /// * `trigger_sqrt_price_upper` - The upper trigger, `0` if unset.
+    /// * `a_to_b` - The direction of the swap into the target token.
+    /// * `min_amount_out` - The minimum amount of the target token the owner receives.
+    /// * `bounty_bps` - The share of the target token paid to the keeper, in basis points.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no trigger is set, a trigger is out of bounds or already crossed, or
+    /// the bounty is too large.
+    pub fn initialize(
+        &mut self,
+        position: Pubkey,
+        funder: Pubkey,
+        sqrt_price: u128,
+        trigger_sqrt_price_lower: u128,
+        trigger_sqrt_price_upper: u128,
+        a_to_b: bool,
+        min_amount_out: u64,
+        bounty_bps: u16,
+    ) -> Result<()> {
+        if bounty_bps > MAX_TRIGGER_ORDER_BOUNTY_BPS {
+            return Err(ErrorCode::InvalidTriggerOrderError.into());
+        }
+        if trigger_sqrt_price_lower == 0 && trigger_sqrt_price_upper == 0 {
+            return Err(ErrorCode::InvalidTriggerOrderError.into());
+        }
+        if trigger_sqrt_price_lower != 0
+            && (trigger_sqrt_price_lower < MIN_SQRT_PRICE_X64 || trigger_sqrt_price_lower >= sqrt_price)
+        {
+            return Err(ErrorCode::InvalidTriggerOrderError.into());
+        }
+        if trigger_sqrt_price_upper != 0
+            && (trigger_sqrt_price_upper > MAX_SQRT_PRICE_X64 || trigger_sqrt_price_upper <= sqrt_price)
+        {
+            return Err(ErrorCode::InvalidTriggerOrderError.into());
+        }
+
+        self.position = position;
+        self.funder = funder;
+        self.trigger_sqrt_price_lower = trigger_sqrt_price_lower;
+        self.trigger_sqrt_price_upper = trigger_sqrt_price_upper;
+        self.a_to_b = a_to_b;
+        self.min_amount_out = min_amount_out;
+        self.bounty_bps = bounty_bps;
+        Ok(())
+    }
+
+    /// Checks if the sqrt price of the pool has crossed one of the triggers.
+    ///
+    /// # Arguments
+    ///
+    /// * `sqrt_price` - The current sqrt price of the pool.
+    pub fn is_triggered(&self, sqrt_price: u128) -> bool {
+        (self.trigger_sqrt_price_lower != 0 && sqrt_price <= self.trigger_sqrt_price_lower)
+            || (self.trigger_sqrt_price_upper != 0 && sqrt_price >= self.trigger_sqrt_price_upper)
+    }
+
+    /// Splits the target token between the keeper bounty and the owner.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The total amount of the target token.
+    ///
+    /// # Returns
+    ///
+    /// * `(u64, u64)` - The bounty of the keeper and the amount of the owner.
+    pub fn split_bounty(&self, amount: u64) -> (u64, u64) {
+        let bounty = ((amount as u128) * (self.bounty_bps as u128) / 10_000) as u64;
+        (bounty, amount - bounty)
+    }
+}
+
+#[cfg(test)]
+mod trigger_order_tests {
+    use super::*;
+
+    const SQRT_PRICE: u128 = 1 << 64;
+
+    #[test]
+    fn test_stop_loss() {
+        let mut order = TriggerOrder::default();
+        order
+            .initialize(Pubkey::new_unique(), Pubkey::new_unique(), SQRT_PRICE, SQRT_PRICE - 100, 0, true, 0, 10)
+            .unwrap();
+
+        assert!(!order.is_triggered(SQRT_PRICE));
+        assert!(!order.is_triggered(SQRT_PRICE + 1_000));
+        assert!(order.is_triggered(SQRT_PRICE - 100));
+    }
+
+    #[test]
+    fn test_take_profit() {
+        let mut order = TriggerOrder::default();
+        order
+            .initialize(Pubkey::new_unique(), Pubkey::new_unique(), SQRT_PRICE, 0, SQRT_PRICE + 100, false, 0, 10)
+            .unwrap();
+
+        assert!(!order.is_triggered(SQRT_PRICE));
+        assert!(!order.is_triggered(SQRT_PRICE - 1_000));
+        assert!(order.is_triggered(SQRT_PRICE + 100));
+    }
+
+    #[test]
+    fn test_initialize_invalid() {
+        let mut order = TriggerOrder::default();
+        let position = Pubkey::new_unique();
+        let funder = Pubkey::new_unique();
+
+        // No trigger
+        assert!(order.initialize(position, funder, SQRT_PRICE, 0, 0, true, 0, 10).is_err());
+        // Triggers already crossed
+        assert!(order.initialize(position, funder, SQRT_PRICE, SQRT_PRICE, 0, true, 0, 10).is_err());
+        assert!(order.initialize(position, funder, SQRT_PRICE, 0, SQRT_PRICE, true, 0, 10).is_err());
+        // Triggers out of bounds
+        assert!(order.initialize(position, funder, SQRT_PRICE, 1, 0, true, 0, 10).is_err());
+        assert!(order
+            .initialize(position, funder, SQRT_PRICE, 0, MAX_SQRT_PRICE_X64 + 1, true, 0, 10)
+            .is_err());
+        // Bounty too large
+        assert!(order
+            .initialize(position, funder, SQRT_PRICE, SQRT_PRICE - 100, 0, true, 0, 1_001)
+            .is_err());
+    }
+
+    #[test]
+    fn test_split_bounty() {
+        let order = TriggerOrder {
+            bounty_bps: 50,
+            ..Default::default()
+        };
+
+        assert_eq!(order.split_bounty(1_000_000), (5_000, 995_000));
+    }
+}
@@ -0,0 +1,177 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+#[account]
+#[derive(Default)]
+pub struct TwapOrder {
+    pub ai_dex_pool: Pubkey, // 32
+    pub owner: Pubkey,       // 32
+    // Sells token A for token B if true, token B for token A otherwise
+    pub a_to_b: bool,        // 1
+
+    // Input token not swapped yet
+    pub amount_in: u64,  // 8
+    // Output token received from tranches
+    pub amount_out: u64, // 8
+
+    pub tranche_amount: u64,    // 8
+    pub num_tranches: u16,      // 2
+    pub tranches_executed: u16, // 2
+    pub start_timestamp: u64,   // 8
+    pub tranche_interval: u64,  // 8
+    // Price guard of every tranche, 0 if unset
+    pub sqrt_price_limit: u128, // 16
+    // 64 RESERVE
+}
+
+/// Struct representing a large swap split into equal tranches over a time window.
+///
+/// The input and the output are held by the vaults of the pool until the order is closed. Tranche
+/// `i` becomes due `i * tranche_interval` seconds after the start of the order, and the last
+/// tranche swaps whatever is left of the input.
+impl TwapOrder {
+    /// Length of the `TwapOrder` struct in bytes.
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 8 + 8 + 8 + 2 + 2 + 8 + 8 + 16 + 64;
+
+    /// Initializes the `TwapOrder` struct.
+    ///
+    /// # Arguments
+    ///
+    /// * `ai_dex_pool` - The pool pubkey.
+    /// * `owner` - The owner of the order.
+    /// * `a_to_b` - The direction of the swaps.
+    /// * `amount_in` - The amount of input token committed to the order.
+    /// * `num_tranches` - The number of tranches the input is split into.
+    /// * `start_timestamp` - The timestamp the first tranche is due at.
+    /// * `duration` - The length of the window in seconds, split evenly between the tranches.
+    /// * `sqrt_price_limit` - The price guard of every tranche, `0` if unset.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a tranche would be empty or shorter than one second.
+    pub fn initialize(
+        &mut self,
+        ai_dex_pool: Pubkey,
+        owner: Pubkey,
+        a_to_b: bool,
+        amount_in: u64,
+        num_tranches: u16,
+        start_timestamp: u64,
+        duration: u64,
+        sqrt_price_limit: u128,
+    ) -> Result<()> {
+        if num_tranches == 0 {
+            return Err(ErrorCode::InvalidTwapOrderError.into());
+        }
+        let tranche_amount = amount_in / num_tranches as u64;
+        let tranche_interval = duration / num_tranches as u64;
+        if tranche_amount == 0 || tranche_interval == 0 {
+            return Err(ErrorCode::InvalidTwapOrderError.into());
+        }
+
+        self.ai_dex_pool = ai_dex_pool;
+        self.owner = owner;
+        self.a_to_b = a_to_b;
+        self.amount_in = amount_in;
+        self.amount_out = 0;
+        self.tranche_amount = tranche_amount;
+        self.num_tranches = num_tranches;
+        self.tranches_executed = 0;
+        self.start_timestamp = start_timestamp;
+        self.tranche_interval = tranche_interval;
+        self.sqrt_price_limit = sqrt_price_limit;
+        Ok(())
+    }
+
+    /// Returns the amount of input token to swap in the next tranche at `timestamp`.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp` - The current timestamp.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if all the tranches have been executed or the next one is not due yet.
+    pub fn next_tranche_amount(&self, timestamp: u64) -> Result<u64> {
+        if self.tranches_executed >= self.num_tranches {
+            return Err(ErrorCode::NoTradableAmountError.into());
+        }
+
+        let due_timestamp = self
+            .start_timestamp
+            .saturating_add(self.tranche_interval.saturating_mul(self.tranches_executed as u64));
+        if timestamp < due_timestamp {
+            return Err(ErrorCode::TwapTrancheNotDueError.into());
+        }
+
+        if self.tranches_executed + 1 == self.num_tranches {
+            Ok(self.amount_in)
+        } else {
+            Ok(self.tranche_amount)
+        }
+    }
+
+    /// Records the execution of a tranche.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount_in` - The amount of input token swapped.
+    /// * `amount_out` - The amount of output token received.
+    pub fn record_tranche(&mut self, amount_in: u64, amount_out: u64) -> Result<()> {
+        self.amount_in = self
+            .amount_in
+            .checked_sub(amount_in)
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        self.amount_out = self
+            .amount_out
+            .checked_add(amount_out)
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        self.tranches_executed += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod twap_order_tests {
+    use super::*;
+
+    #[test]
+    fn test_initialize_invalid() {
+        let mut order = TwapOrder::default();
+        let pool = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+
+        assert!(order.initialize(pool, owner, true, 1_000, 0, 0, 3_600, 0).is_err());
+        // Empty tranches
+        assert!(order.initialize(pool, owner, true, 3, 4, 0, 3_600, 0).is_err());
+        // Tranches shorter than one second
+        assert!(order.initialize(pool, owner, true, 1_000, 4, 0, 3, 0).is_err());
+    }
+
+    #[test]
+    fn test_tranches() {
+        let mut order = TwapOrder::default();
+        order
+            .initialize(Pubkey::new_unique(), Pubkey::new_unique(), false, 1_000, 3, 100, 300, 0)
+            .unwrap();
+        assert_eq!(order.tranche_amount, 333);
+        assert_eq!(order.tranche_interval, 100);
+
+        assert!(order.next_tranche_amount(99).is_err());
+        assert_eq!(order.next_tranche_amount(100).unwrap(), 333);
+        order.record_tranche(333, 300).unwrap();
+
+        assert!(order.next_tranche_amount(199).is_err());
+        assert_eq!(order.next_tranche_amount(200).unwrap(), 333);
+        order.record_tranche(333, 290).unwrap();
+
+        // The last tranche swaps the remainder of the input
+        assert_eq!(order.next_tranche_amount(1_000).unwrap(), 334);
+        order.record_tranche(334, 280).unwrap();
+
+        assert_eq!(order.amount_in, 0);
+        assert_eq!(order.amount_out, 870);
+        assert!(order.next_tranche_amount(2_000).is_err());
+    }
+}
@@ -0,0 +1,383 @@
+use anchor_lang::prelude::*;
+use std::convert::TryFrom;
+
+use crate::{errors::ErrorCode, math::checked_mul_div_round_up_if};
+
+use super::{AiDexPool, Position, NUM_REWARDS};
+
+// Number of positions a vault can hold
+pub const MAX_VAULT_POSITIONS: usize = 4;
+
+// Decimals of the vault shares
+pub const VAULT_SHARE_DECIMALS: u8 = 6;
+
+#[account]
+#[derive(Default)]
+pub struct Vault {
+    pub ai_dex_pool: Pubkey,        // 32
+    pub share_mint: Pubkey,         // 32
+    pub strategy_authority: Pubkey, // 32
+    // Allowed to rebalance the positions along with the strategy authority, the default pubkey for none
+    pub agent: Pubkey, // 32
+
+    // Hold the tokens of the vault that are not deployed in its positions
+    pub token_account_a: Pubkey, // 32
+    pub token_account_b: Pubkey, // 32
+
+    // The default pubkey for the unused slots
+    pub positions: [Pubkey; MAX_VAULT_POSITIONS], // 128
+
+    pub vault_bump: [u8; 1], // 1
+    // 64 RESERVE
+}
+
+/// Struct representing a vault turning positions managed by a strategy authority into shares.
+///
+/// Depositors pay in their pro-rata share of the tokens of the vault, in its token accounts and in
+/// its positions, and receive vault shares, which they burn to withdraw their share of the token
+/// accounts and of the liquidity, fees and rewards of each position. The strategy authority, or the agent it
+/// designates, deploys the tokens of the token accounts into the positions. The positions have no
+/// position token, their position mint being set to the vault, so they can only be modified through
+/// the vault instructions.
+impl Vault {
+    /// Length of the `Vault` struct in bytes.
+    pub const LEN: usize = 8 + 32 * 6 + 32 * MAX_VAULT_POSITIONS + 1 + 64;
+
+    /// Initializes the `Vault` struct.
+    ///
+    /// # Arguments
+    ///
+    /// * `ai_dex_pool` - The pool pubkey.
+    /// * `share_mint` - The mint of the vault shares.
+    /// * `strategy_authority` - The authority managing the positions of the vault.
+    /// * `agent` - The agent allowed to rebalance the positions, the default pubkey for none.
+    /// * `token_account_a` - The token account of the vault for token A.
+    /// * `token_account_b` - The token account of the vault for token B.
+    /// * `bump` - The bump of the vault PDA.
+    pub fn initialize(
+        &mut self,
+        ai_dex_pool: Pubkey,
+        share_mint: Pubkey,
+        strategy_authority: Pubkey,
+        agent: Pubkey,
+        token_account_a: Pubkey,
+        token_account_b: Pubkey,
+        bump: u8,
+    ) {
+        self.ai_dex_pool = ai_dex_pool;
+        self.share_mint = share_mint;
+        self.strategy_authority = strategy_authority;
+        self.agent = agent;
+        self.token_account_a = token_account_a;
+        self.token_account_b = token_account_b;
+        self.positions = [Pubkey::default(); MAX_VAULT_POSITIONS];
+        self.vault_bump = [bump];
+    }
+
+    /// Returns the seeds of the vault PDA, to sign for its token accounts.
+    pub fn seeds(&self) -> [&[u8]; 3] {
+        [&b"vault"[..], self.share_mint.as_ref(), self.vault_bump.as_ref()]
+    }
+
+    /// Returns the number of positions of the vault.
+    pub fn position_count(&self) -> usize {
+        self.positions
+            .iter()
+            .take_while(|position| **position != Pubkey::default())
+            .count()
+    }
+
+    /// Adds a position to the vault.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The position pubkey.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the vault already holds the maximum number of positions.
+    pub fn add_position(&mut self, position: Pubkey) -> Result<()> {
+        let index = self.position_count();
+        if index >= MAX_VAULT_POSITIONS {
+            return Err(ErrorCode::VaultPositionLimitError.into());
+        }
+        self.positions[index] = position;
+        Ok(())
+    }
+
+    /// Checks if the given key may rebalance the positions of the vault.
+    ///
+    /// # Arguments
+    ///
+    /// * `authority` - The signer of the rebalance.
+    pub fn is_rebalance_authority(&self, authority: &Pubkey) -> bool {
+        *authority == self.strategy_authority
+            || (self.agent != Pubkey::default() && *authority == self.agent)
+    }
+
+    /// Sets the agent allowed to rebalance the positions.
+    ///
+    /// # Arguments
+    ///
+    /// * `agent` - The agent pubkey, the default pubkey for none.
+    pub fn set_agent(&mut self, agent: Pubkey) {
+        self.agent = agent;
+    }
+}
+
+/// Calculates the share of an amount held by the vault matching an amount of vault shares.
+///
+/// # Arguments
+///
+/// * `share_amount` - The amount of vault shares.
+/// * `share_supply` - The supply of the vault shares.
+/// * `amount` - The amount held by the vault, such as a token balance or a liquidity.
+/// * `round_up` - Whether to round up, when vault shares are minted.
+///
+/// # Errors
+///
+/// Returns an error if the share supply is zero or the result overflows.
+pub fn get_vault_share_amount(
+    share_amount: u64,
+    share_supply: u64,
+    amount: u128,
+    round_up: bool,
+) -> Result<u128> {
+    Ok(checked_mul_div_round_up_if(
+        share_amount.into(),
+        amount,
+        share_supply.into(),
+        round_up,
+    )?)
+}
+
+/// Calculates the share of a token amount held by the vault matching an amount of vault shares.
+///
+/// # Errors
+///
+/// Returns an error if the share supply is zero or the share exceeds the maximum token amount.
+pub fn get_vault_share_token_amount(
+    share_amount: u64,
+    share_supply: u64,
+    amount: u64,
+    round_up: bool,
+) -> Result<u64> {
+    let share = get_vault_share_amount(share_amount, share_supply, amount.into(), round_up)?;
+    u64::try_from(share).map_err(|_| ErrorCode::TokenLimitExceededError.into())
+}
+
+/// Adds to the rewards owed by a position of the vault the share paid in by a depositor of
+/// `share_amount` vault shares, rounded up, so that the shares already minted keep their value.
+///
+/// # Arguments
+///
+/// * `ai_dex` - The pool of the vault.
+/// * `position` - The position of the vault, with its rewards owed up to date.
+/// * `share_amount` - The amount of vault shares minted.
+/// * `share_supply` - The supply of the vault shares before the deposit.
+///
+/// # Returns
+///
+/// The amount of each reward paid in, zero for the rewards not initialized.
+///
+/// # Errors
+///
+/// Returns an error if the share supply is zero or a reward owed overflows.
+pub fn deposit_vault_position_rewards(
+    ai_dex: &mut AiDexPool,
+    position: &mut Position,
+    share_amount: u64,
+    share_supply: u64,
+) -> Result<[u64; NUM_REWARDS]> {
+    let mut reward_shares = [0u64; NUM_REWARDS];
+    for (index, reward_share) in reward_shares.iter_mut().enumerate() {
+        if !ai_dex.reward_infos[index].initialized() {
+            continue;
+        }
+        let reward_owed = position.reward_infos[index].amount_owed;
+        *reward_share = get_vault_share_token_amount(share_amount, share_supply, reward_owed, true)?;
+        let updated_reward_owed = reward_owed
+            .checked_add(*reward_share)
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        position.update_reward_owed(index, updated_reward_owed);
+        ai_dex.record_reward_deposited(index, *reward_share);
+    }
+    Ok(reward_shares)
+}
+
+/// Removes from the rewards owed by a position of the vault the share paid out to a holder
+/// burning `share_amount` vault shares, rounded down and capped by the balances left in the reward
+/// vaults of the pool.
+///
+/// # Arguments
+///
+/// * `ai_dex` - The pool of the vault.
+/// * `position` - The position of the vault, with its rewards owed up to date.
+/// * `share_amount` - The amount of vault shares burned.
+/// * `share_supply` - The supply of the vault shares before the withdrawal.
+/// * `reward_vault_amounts` - The balances left in the reward vaults, reduced by the amounts paid out.
+///
+/// # Returns
+///
+/// The amount of each reward paid out, zero for the rewards not initialized.
+///
+/// # Errors
+///
+/// Returns an error if the share supply is zero or the share exceeds the rewards owed.
+pub fn withdraw_vault_position_rewards(
+    ai_dex: &mut AiDexPool,
+    position: &mut Position,
+    share_amount: u64,
+    share_supply: u64,
+    reward_vault_amounts: &mut [u64; NUM_REWARDS],
+) -> Result<[u64; NUM_REWARDS]> {
+    let mut reward_shares = [0u64; NUM_REWARDS];
+    for (index, reward_share) in reward_shares.iter_mut().enumerate() {
+        if !ai_dex.reward_infos[index].initialized() {
+            continue;
+        }
+        let reward_owed = position.reward_infos[index].amount_owed;
+        *reward_share = get_vault_share_token_amount(share_amount, share_supply, reward_owed, false)?
+            .min(reward_vault_amounts[index]);
+        let updated_reward_owed = reward_owed
+            .checked_sub(*reward_share)
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        position.update_reward_owed(index, updated_reward_owed);
+        ai_dex.record_reward_collected(index, *reward_share);
+        reward_vault_amounts[index] -= *reward_share;
+    }
+    Ok(reward_shares)
+}
+
+#[cfg(test)]
+mod vault_tests {
+    use super::*;
+    use crate::math::Q64_RESOLUTION;
+    use crate::orchestrator::ai_dex_orchestrator::next_ai_dex_reward_infos;
+    use crate::orchestrator::position_orchestrator::next_position_modify_liquidity_update;
+    use crate::state::{position_builder::PositionBuilder, AiDexRewardInfo, PositionRewardInfo};
+
+    #[test]
+    fn test_add_position() {
+        let mut vault = Vault::default();
+        assert_eq!(vault.position_count(), 0);
+
+        for count in 1..=MAX_VAULT_POSITIONS {
+            vault.add_position(Pubkey::new_unique()).unwrap();
+            assert_eq!(vault.position_count(), count);
+        }
+        assert!(vault.add_position(Pubkey::new_unique()).is_err());
+    }
+
+    #[test]
+    fn test_is_rebalance_authority() {
+        let strategy_authority = Pubkey::new_unique();
+        let agent = Pubkey::new_unique();
+        let mut vault = Vault {
+            strategy_authority,
+            ..Default::default()
+        };
+
+        assert!(vault.is_rebalance_authority(&strategy_authority));
+        assert!(!vault.is_rebalance_authority(&agent));
+        assert!(!vault.is_rebalance_authority(&Pubkey::default()));
+
+        vault.set_agent(agent);
+        assert!(vault.is_rebalance_authority(&agent));
+    }
+
+    #[test]
+    fn test_get_vault_share_token_amount() {
+        assert_eq!(get_vault_share_token_amount(1, 3, 100, false).unwrap(), 33);
+        assert_eq!(get_vault_share_token_amount(1, 3, 100, true).unwrap(), 34);
+        assert_eq!(get_vault_share_token_amount(3, 3, u64::MAX, false).unwrap(), u64::MAX);
+        assert!(get_vault_share_token_amount(4, 3, u64::MAX, true).is_err());
+        assert!(get_vault_share_token_amount(1, 0, 100, true).is_err());
+    }
+
+    // Accrues the emissions of the pool up to `timestamp` to a position holding all its liquidity
+    fn accrue_rewards(ai_dex: &mut AiDexPool, position: &mut Position, timestamp: u64) {
+        let reward_infos = next_ai_dex_reward_infos(ai_dex, timestamp).unwrap();
+        ai_dex.update_rewards(reward_infos, timestamp);
+        let reward_growths = AiDexRewardInfo::to_reward_growths(&reward_infos);
+        let update = next_position_modify_liquidity_update(position, 0, 0, 0, &reward_growths, 0).unwrap();
+        position.update(&update);
+    }
+
+    #[test]
+    fn test_vault_rewards_deposit_accrue_withdraw() {
+        let mut ai_dex = AiDexPool {
+            liquidity: 100,
+            reward_last_updated_timestamp: 1000,
+            ..Default::default()
+        };
+        ai_dex.initialize_reward(0, Pubkey::new_unique(), Pubkey::new_unique()).unwrap();
+        ai_dex.reward_infos[0].emissions_per_second_x64 = 1 << Q64_RESOLUTION;
+        ai_dex.reward_emissions[0].emissions_end_timestamp = 2000;
+        let mut position = PositionBuilder::new(-10, 10).liquidity(100).build();
+
+        // A deposit into a position without rewards owed pays nothing in
+        let reward_shares = deposit_vault_position_rewards(&mut ai_dex, &mut position, 100, 100).unwrap();
+        assert_eq!(reward_shares, [0; NUM_REWARDS]);
+
+        accrue_rewards(&mut ai_dex, &mut position, 1100);
+        assert_eq!(position.reward_infos[0].amount_owed, 100);
+        assert_eq!({ ai_dex.reward_emissions[0].emissions_outstanding }, 100);
+
+        // Burning half of the 200 shares pays out half of the rewards owed
+        let mut reward_vault_amounts = [1_000, 0, 0];
+        let reward_shares = withdraw_vault_position_rewards(
+            &mut ai_dex,
+            &mut position,
+            100,
+            200,
+            &mut reward_vault_amounts,
+        )
+        .unwrap();
+        assert_eq!(reward_shares, [50, 0, 0]);
+        assert_eq!(reward_vault_amounts, [950, 0, 0]);
+        assert_eq!(position.reward_infos[0].amount_owed, 50);
+        assert_eq!({ ai_dex.reward_emissions[0].emissions_outstanding }, 50);
+
+        // A later depositor pays in the rewards owed to the shares it receives
+        let reward_shares = deposit_vault_position_rewards(&mut ai_dex, &mut position, 100, 100).unwrap();
+        assert_eq!(reward_shares, [50, 0, 0]);
+        assert_eq!(position.reward_infos[0].amount_owed, 100);
+        assert_eq!({ ai_dex.reward_emissions[0].emissions_outstanding }, 100);
+
+        // Burning every share pays out every reward owed, leaving nothing outstanding
+        let mut reward_vault_amounts = [950, 0, 0];
+        let reward_shares = withdraw_vault_position_rewards(
+            &mut ai_dex,
+            &mut position,
+            200,
+            200,
+            &mut reward_vault_amounts,
+        )
+        .unwrap();
+        assert_eq!(reward_shares, [100, 0, 0]);
+        assert_eq!(position.reward_infos[0].amount_owed, 0);
+        assert_eq!({ ai_dex.reward_emissions[0].emissions_outstanding }, 0);
+    }
+
+    #[test]
+    fn test_withdraw_vault_position_rewards_capped_by_reward_vault() {
+        let mut ai_dex = AiDexPool::default();
+        ai_dex.initialize_reward(0, Pubkey::new_unique(), Pubkey::new_unique()).unwrap();
+        ai_dex.initialize_reward(1, Pubkey::new_unique(), Pubkey::new_unique()).unwrap();
+        let mut position = PositionBuilder::new(-10, 10)
+            .reward_info(0, PositionRewardInfo { amount_owed: 100, ..Default::default() })
+            .reward_info(1, PositionRewardInfo { amount_owed: 100, ..Default::default() })
+            .reward_info(2, PositionRewardInfo { amount_owed: 100, ..Default::default() })
+            .build();
+
+        let mut reward_vault_amounts = [30, 1_000, 1_000];
+        let reward_shares =
+            withdraw_vault_position_rewards(&mut ai_dex, &mut position, 1, 1, &mut reward_vault_amounts).unwrap();
+        assert_eq!(reward_shares, [30, 100, 0]);
+        assert_eq!(reward_vault_amounts, [0, 900, 1_000]);
+        assert_eq!(position.reward_infos[0].amount_owed, 70);
+        // The uninitialized reward is left untouched
+        assert_eq!(position.reward_infos[2].amount_owed, 100);
+    }
+}
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+
+use super::{
+    AiDexConfig, FeeTier, Position, PositionTradeBatch, AI_DEX_CONFIG_VERSION, FEE_TIER_VERSION,
+    POSITION_TRADE_BATCH_VERSION, POSITION_VERSION,
+};
+
+/// An account whose layout is versioned.
+///
+/// Fields are only ever appended to the layout of a versioned account, either at its end or in
+/// its reserved space, and its version is raised each time. An account of a past layout is grown
+/// to the current size by `migrate_program_account`, the appended fields reading as zero.
+pub trait VersionedAccount {
+    /// The version of the current layout.
+    const VERSION: u8;
+    /// The size of an account of the current layout in bytes, reserved space included.
+    const SPACE: usize;
+
+    /// Returns the version of the layout of the account, zero if it predates versioning.
+    fn version(&self) -> u8;
+
+    fn set_version(&mut self, version: u8);
+
+    /// Raises the account to the current layout.
+    ///
+    /// # Returns
+    /// The version of the account before the migration.
+    fn migrate(&mut self) -> u8 {
+        let old_version = self.version();
+        self.set_version(Self::VERSION);
+        old_version
+    }
+}
+
+impl VersionedAccount for Position {
+    const VERSION: u8 = POSITION_VERSION;
+    const SPACE: usize = Position::LEN;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
+
+impl VersionedAccount for PositionTradeBatch {
+    const VERSION: u8 = POSITION_TRADE_BATCH_VERSION;
+    const SPACE: usize = PositionTradeBatch::LEN;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
+
+impl VersionedAccount for FeeTier {
+    const VERSION: u8 = FEE_TIER_VERSION;
+    const SPACE: usize = FeeTier::LEN;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
+
+impl VersionedAccount for AiDexConfig {
+    const VERSION: u8 = AI_DEX_CONFIG_VERSION;
+    const SPACE: usize = AiDexConfig::LEN;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
+
+#[cfg(test)]
+mod versioned_tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_position() {
+        let mut position = Position::default();
+        assert_eq!(position.migrate(), 0);
+        assert_eq!(position.version, POSITION_VERSION);
+        assert_eq!(position.migrate(), POSITION_VERSION);
+    }
+
+    #[test]
+    fn test_versioned_accounts_keep_their_reserve() {
+        assert_eq!(Position::SPACE - 8 - Position::default().try_to_vec().unwrap().len(), 64);
+        assert_eq!(
+            PositionTradeBatch::SPACE - 8 - PositionTradeBatch::default().try_to_vec().unwrap().len(),
+            62
+        );
+        let fee_tier = FeeTier {
+            ai_dex_config: Pubkey::default(),
+            tick_spacing: 0,
+            default_fee_rate: 0,
+            version: FEE_TIER_VERSION,
+            full_range_only: false,
+        };
+        assert_eq!(FeeTier::SPACE - 8 - fee_tier.try_to_vec().unwrap().len(), 63);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::errors::ErrorCode;
 use crate::orchestrator::swap_orchestrator::*;
 use crate::math::*;
-use crate::state::{MAX_TICK_INDEX, MIN_TICK_INDEX, TICK_ARRAY_SIZE};
+use crate::state::{LEGACY_MAX_TICK_INDEX, LEGACY_MIN_TICK_INDEX, TICK_ARRAY_SIZE};
 use crate::util::test_utils::swap_test_fixture::*;
 use crate::util::{create_ai_dex_reward_infos, SwapTickSequence};
 use serde::Deserialize;
@@ -285,5 +285,5 @@ fn derive_last_tick_in_seq(start_tick: i32, tick_spacing: u16, a_to_b: bool) ->
     } else {
         start_tick + (3 * num_of_ticks_in_array) - 1
     };
-    max(min(potential_last, MAX_TICK_INDEX), MIN_TICK_INDEX)
+    max(min(potential_last, LEGACY_MAX_TICK_INDEX), LEGACY_MIN_TICK_INDEX)
 }
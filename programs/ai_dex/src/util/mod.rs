@@ -1,12 +1,22 @@
+pub mod native_sol;
+pub mod oracle_utils;
 pub mod remaining_accounts_utils;
+pub mod signature_utils;
 pub mod swap_tick_sequence;
 pub mod swap_utils;
+pub mod tick_array_bitmap_utils;
+pub mod tick_array_utils;
 pub mod token;
 pub mod util;
 
+pub use native_sol::*;
+pub use oracle_utils::*;
 pub use remaining_accounts_utils::*;
+pub use signature_utils::*;
 pub use swap_tick_sequence::*;
 pub use swap_utils::*;
+pub use tick_array_bitmap_utils::*;
+pub use tick_array_utils::*;
 pub use token::*;
 pub use util::*;
 
@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token_interface::{
+    self, CloseAccount, Mint, SyncNative, TokenAccount, TokenInterface,
+};
+
+use crate::errors::ErrorCode;
+
+/// Checks if a mint is the native mint, whose tokens are wrapped SOL (wSOL).
+pub fn is_native_mint(mint: &Pubkey) -> bool {
+    spl_token::native_mint::check_id(mint)
+}
+
+/// Returns the system program passed in the `NativeSol` slice of the remaining accounts, if any.
+///
+/// Passing the slice opts in to native SOL handling: the wSOL side of the instruction is paid
+/// in lamports and the wSOL token account of the user is closed back to SOL afterwards.
+///
+/// # Arguments
+///
+/// * `native_sol_accounts` - The accounts of the `NativeSol` slice.
+/// * `token_mint_a` - The mint of token A of the pool.
+/// * `token_mint_b` - The mint of token B of the pool.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidNativeSolAccountsError` - If the slice does not hold only the system program,
+///   or neither token of the pool is wSOL.
+pub fn parse_native_sol_system_program<'info>(
+    native_sol_accounts: &Option<Vec<AccountInfo<'info>>>,
+    token_mint_a: &InterfaceAccount<'info, Mint>,
+    token_mint_b: &InterfaceAccount<'info, Mint>,
+) -> Result<Option<AccountInfo<'info>>> {
+    let accounts = match native_sol_accounts {
+        Some(accounts) => accounts,
+        None => return Ok(None),
+    };
+
+    if accounts.len() != 1
+        || accounts[0].key() != system_program::ID
+        || (!is_native_mint(&token_mint_a.key()) && !is_native_mint(&token_mint_b.key()))
+    {
+        return Err(ErrorCode::InvalidNativeSolAccountsError.into());
+    }
+
+    Ok(Some(accounts[0].clone()))
+}
+
+/// Wraps lamports of the payer into its wSOL token account so the account holds at least `amount`.
+///
+/// Only the part of the amount not already held by the token account is transferred.
+///
+/// # Arguments
+///
+/// * `payer` - The owner of the wSOL token account, paying the lamports.
+/// * `native_token_account` - The wSOL token account.
+/// * `token_program` - The token program of the wSOL token account.
+/// * `system_program` - The system program.
+/// * `amount` - The amount of wSOL the token account must hold.
+///
+/// # Errors
+///
+/// Returns an error if the lamports transfer or the sync of the token account fails.
+pub fn wrap_native_sol<'info>(
+    payer: &Signer<'info>,
+    native_token_account: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    system_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let shortfall = amount.saturating_sub(native_token_account.amount);
+    if shortfall == 0 {
+        return Ok(());
+    }
+
+    system_program::transfer(
+        CpiContext::new(
+            system_program.clone(),
+            Transfer {
+                from: payer.to_account_info(),
+                to: native_token_account.to_account_info(),
+            },
+        ),
+        shortfall,
+    )?;
+
+    token_interface::sync_native(CpiContext::new(
+        token_program.to_account_info(),
+        SyncNative {
+            account: native_token_account.to_account_info(),
+        },
+    ))
+}
+
+/// Closes the wSOL token account of the owner, unwrapping its whole balance and rent to the owner.
+///
+/// # Arguments
+///
+/// * `owner` - The owner of the wSOL token account, receiving the lamports.
+/// * `native_token_account` - The wSOL token account.
+/// * `token_program` - The token program of the wSOL token account.
+///
+/// # Errors
+///
+/// Returns an error if the token account cannot be closed, e.g. if the signer is not its owner.
+pub fn unwrap_native_sol<'info>(
+    owner: &Signer<'info>,
+    native_token_account: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    token_interface::close_account(CpiContext::new(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: native_token_account.to_account_info(),
+            destination: owner.to_account_info(),
+            authority: owner.to_account_info(),
+        },
+    ))
+}
+
+/// Closes the wSOL token account of the owner for the wSOL side of a pool, see `unwrap_native_sol`.
+///
+/// # Arguments
+///
+/// * `owner` - The owner of the wSOL token account, receiving the lamports.
+/// * `token_mint_a` - The mint of token A of the pool.
+/// * `token_owner_account_a` - The token A account of the owner.
+/// * `token_program_a` - The token program of token A.
+/// * `token_mint_b` - The mint of token B of the pool.
+/// * `token_owner_account_b` - The token B account of the owner.
+/// * `token_program_b` - The token program of token B.
+///
+/// # Errors
+///
+/// Returns an error if the wSOL token account cannot be closed.
+pub fn unwrap_pool_native_sol<'info>(
+    owner: &Signer<'info>,
+    token_mint_a: &InterfaceAccount<'info, Mint>,
+    token_owner_account_a: &InterfaceAccount<'info, TokenAccount>,
+    token_program_a: &Interface<'info, TokenInterface>,
+    token_mint_b: &InterfaceAccount<'info, Mint>,
+    token_owner_account_b: &InterfaceAccount<'info, TokenAccount>,
+    token_program_b: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    if is_native_mint(&token_mint_a.key()) {
+        unwrap_native_sol(owner, token_owner_account_a, token_program_a)
+    } else if is_native_mint(&token_mint_b.key()) {
+        unwrap_native_sol(owner, token_owner_account_b, token_program_b)
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{AiDexPool, Oracle};
+
+use super::{load_zero_copy, load_zero_copy_mut};
+
+/// Records an oracle observation for the pool, if its oracle account has been initialized.
+///
+/// Oracles are opt-in, so swaps on pools whose oracle PDA was never initialized simply skip
+/// the observation. Must be called before the pool state is updated by the swap, so the
+/// accumulated tick reflects the price in effect since the previous observation.
+///
+/// # Arguments
+///
+/// * `oracle` - The oracle PDA of the pool.
+/// * `ai_dex` - The pool state before the swap is applied.
+/// * `timestamp` - The current timestamp.
+///
+/// # Errors
+///
+/// Returns an error if the oracle account is owned by this program but cannot be loaded as an `Oracle`.
+pub fn record_oracle_observation<'info>(
+    oracle: &UncheckedAccount<'info>,
+    ai_dex: &AiDexPool,
+    timestamp: u64,
+) -> Result<()> {
+    if oracle.owner != &crate::ID || oracle.data_is_empty() {
+        return Ok(());
+    }
+
+    let mut oracle = load_zero_copy_mut::<Oracle>(oracle)?;
+    oracle.record(timestamp, ai_dex.tick_current_index, ai_dex.liquidity);
+
+    Ok(())
+}
+
+/// Measures the volatility driving the adaptive fee of the pool, zero if the adaptive fee is disabled.
+///
+/// Unlike observations, the volatility cannot be skipped when the oracle is missing, or swaps
+/// could avoid the adaptive fee by passing an uninitialized oracle.
+///
+/// # Arguments
+///
+/// * `oracle` - The oracle PDA of the pool.
+/// * `ai_dex` - The pool state before the swap is applied.
+/// * `timestamp` - The current timestamp.
+///
+/// # Errors
+///
+/// * `AdaptiveFeeOracleError` - If the adaptive fee is enabled but the oracle has not been initialized.
+pub fn oracle_volatility_ticks<'info>(
+    oracle: &UncheckedAccount<'info>,
+    ai_dex: &AiDexPool,
+    timestamp: u64,
+) -> Result<u32> {
+    if ai_dex.adaptive_fee_window_seconds == 0 {
+        return Ok(0);
+    }
+    if oracle.owner != &crate::ID || oracle.data_is_empty() {
+        return Err(ErrorCode::AdaptiveFeeOracleError.into());
+    }
+
+    let oracle = load_zero_copy::<Oracle>(oracle)?;
+    Ok(oracle.tick_volatility(timestamp, ai_dex.adaptive_fee_window_seconds, ai_dex.tick_current_index))
+}
+
+/// Resolves a sqrt price limit expressed as a maximum deviation, in basis points, from the
+/// time-weighted average price of the pool over a window.
+///
+/// # Arguments
+///
+/// * `oracle` - The oracle PDA of the pool.
+/// * `ai_dex` - The pool state before the swap is applied.
+/// * `timestamp` - The current timestamp.
+/// * `window_seconds` - The length of the averaging window.
+/// * `max_deviation_bps` - The maximum deviation from the average price, in basis points.
+/// * `a_to_b` - The direction of the swap.
+///
+/// # Errors
+///
+/// * `TwapPriceLimitOracleError` - If the oracle of the pool has not been initialized.
+/// * `OracleObservationTooOldError` - If the oracle does not cover the whole window.
+pub fn oracle_twap_sqrt_price_limit<'info>(
+    oracle: &UncheckedAccount<'info>,
+    ai_dex: &AiDexPool,
+    timestamp: u64,
+    window_seconds: u32,
+    max_deviation_bps: u128,
+    a_to_b: bool,
+) -> Result<u128> {
+    if oracle.owner != &crate::ID || oracle.data_is_empty() {
+        return Err(ErrorCode::TwapPriceLimitOracleError.into());
+    }
+
+    let oracle = load_zero_copy::<Oracle>(oracle)?;
+    oracle.twap_sqrt_price_limit(
+        timestamp,
+        window_seconds,
+        ai_dex.tick_current_index,
+        max_deviation_bps,
+        a_to_b,
+    )
+}
@@ -9,9 +9,14 @@ pub enum AccountsType {
     TransferHookInput,
     TransferHookIntermediate,
     TransferHookOutput,
-    //TickArray,
-    //TickArrayOne,
-    //TickArrayTwo,
+    SupplementalTickArrays,
+    SupplementalTickArraysOne,
+    SupplementalTickArraysTwo,
+    RewardAccounts,
+    PositionAccounts,
+    NativeSol,
+    FeeRecipients,
+    SwapHookAccounts,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -33,6 +38,14 @@ pub struct ParsedRemainingAccounts<'info> {
     pub transfer_hook_input: Option<Vec<AccountInfo<'info>>>,
     pub transfer_hook_intermediate: Option<Vec<AccountInfo<'info>>>,
     pub transfer_hook_output: Option<Vec<AccountInfo<'info>>>,
+    pub supplemental_tick_arrays: Option<Vec<AccountInfo<'info>>>,
+    pub supplemental_tick_arrays_one: Option<Vec<AccountInfo<'info>>>,
+    pub supplemental_tick_arrays_two: Option<Vec<AccountInfo<'info>>>,
+    pub reward_accounts: Option<Vec<AccountInfo<'info>>>,
+    pub position_accounts: Option<Vec<AccountInfo<'info>>>,
+    pub native_sol: Option<Vec<AccountInfo<'info>>>,
+    pub fee_recipients: Option<Vec<AccountInfo<'info>>>,
+    pub swap_hook_accounts: Option<Vec<AccountInfo<'info>>>,
 }
 
 /// Parses the remaining accounts based on the provided information and valid account types.
@@ -126,6 +139,54 @@ pub fn parse_remaining_accounts<'info>(
           }
           parsed_remaining_accounts.transfer_hook_output = Some(accounts);
         }
+        AccountsType::SupplementalTickArrays => {
+          if parsed_remaining_accounts.supplemental_tick_arrays.is_some() {
+            return Err(ErrorCode::DuplicateAccountTypesError.into());
+          }
+          parsed_remaining_accounts.supplemental_tick_arrays = Some(accounts);
+        }
+        AccountsType::SupplementalTickArraysOne => {
+          if parsed_remaining_accounts.supplemental_tick_arrays_one.is_some() {
+            return Err(ErrorCode::DuplicateAccountTypesError.into());
+          }
+          parsed_remaining_accounts.supplemental_tick_arrays_one = Some(accounts);
+        }
+        AccountsType::SupplementalTickArraysTwo => {
+          if parsed_remaining_accounts.supplemental_tick_arrays_two.is_some() {
+            return Err(ErrorCode::DuplicateAccountTypesError.into());
+          }
+          parsed_remaining_accounts.supplemental_tick_arrays_two = Some(accounts);
+        }
+        AccountsType::RewardAccounts => {
+          if parsed_remaining_accounts.reward_accounts.is_some() {
+            return Err(ErrorCode::DuplicateAccountTypesError.into());
+          }
+          parsed_remaining_accounts.reward_accounts = Some(accounts);
+        }
+        AccountsType::PositionAccounts => {
+          if parsed_remaining_accounts.position_accounts.is_some() {
+            return Err(ErrorCode::DuplicateAccountTypesError.into());
+          }
+          parsed_remaining_accounts.position_accounts = Some(accounts);
+        }
+        AccountsType::NativeSol => {
+          if parsed_remaining_accounts.native_sol.is_some() {
+            return Err(ErrorCode::DuplicateAccountTypesError.into());
+          }
+          parsed_remaining_accounts.native_sol = Some(accounts);
+        }
+        AccountsType::FeeRecipients => {
+          if parsed_remaining_accounts.fee_recipients.is_some() {
+            return Err(ErrorCode::DuplicateAccountTypesError.into());
+          }
+          parsed_remaining_accounts.fee_recipients = Some(accounts);
+        }
+        AccountsType::SwapHookAccounts => {
+          if parsed_remaining_accounts.swap_hook_accounts.is_some() {
+            return Err(ErrorCode::DuplicateAccountTypesError.into());
+          }
+          parsed_remaining_accounts.swap_hook_accounts = Some(accounts);
+        }
       }
     }
   }
@@ -133,3 +194,33 @@ pub fn parse_remaining_accounts<'info>(
   // Return the parsed_remaining_accounts
   Ok(parsed_remaining_accounts)
 }
+
+/// Borrows the accounts of a slice of the remaining accounts, empty if the slice is not provided.
+///
+/// `ParsedRemainingAccounts` holds copies of the accounts, which cannot be deserialized into
+/// typed accounts living as long as the instruction. The remaining accounts must have been
+/// parsed by `parse_remaining_accounts` first, which validates the slices.
+///
+/// # Arguments
+///
+/// * `remaining_accounts` - The remaining accounts of the instruction.
+/// * `remaining_accounts_info` - The information about the slices of the remaining accounts.
+/// * `accounts_type` - The type of the slice to borrow.
+pub fn remaining_accounts_slice<'c, 'info>(
+  remaining_accounts: &'c [AccountInfo<'info>],
+  remaining_accounts_info: &Option<RemainingAccountsInfo>,
+  accounts_type: AccountsType,
+) -> &'c [AccountInfo<'info>] {
+  let mut start = 0;
+  if let Some(remaining_accounts_info) = remaining_accounts_info {
+    for slice in &remaining_accounts_info.slices {
+      let end = start + slice.length as usize;
+      if slice.accounts_type == accounts_type {
+        return &remaining_accounts[start..end];
+      }
+      start = end;
+    }
+  }
+
+  &[]
+}
@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+use solana_program::ed25519_program;
+use solana_program::sysvar::instructions::load_instruction_at_checked;
+
+use crate::errors::ErrorCode;
+
+// Layout of the data of an ed25519 program instruction: the number of signatures and a padding
+// byte, followed by the offsets of each signature
+const ED25519_SIGNATURE_OFFSETS_START: usize = 2;
+const ED25519_SIGNATURE_OFFSETS_LEN: usize = 14;
+const ED25519_PUBKEY_LEN: usize = 32;
+const ED25519_SIGNATURE_LEN: usize = 64;
+
+// Instruction index of the offsets pointing into the data of the ed25519 instruction itself
+const ED25519_CURRENT_INSTRUCTION_INDEX: u16 = u16::MAX;
+
+/// Checks that an ed25519 program instruction of the transaction verifies a signature of the
+/// signer over the message.
+///
+/// The ed25519 program fails the whole transaction if a signature it is given is invalid, so the
+/// signature itself is verified by the runtime. Only the signer and the message it verifies are
+/// checked here, and only signatures whose public key, message and signature are all held in the
+/// data of the ed25519 instruction itself are considered.
+///
+/// # Arguments
+///
+/// * `instructions_sysvar` - The instructions sysvar.
+/// * `signer` - The expected signer.
+/// * `message` - The expected message.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidSwapPermitSignatureError` - If no ed25519 instruction of the transaction verifies the signature.
+pub fn verify_ed25519_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let mut index = 0;
+    while let Ok(instruction) = load_instruction_at_checked(index, instructions_sysvar) {
+        if instruction.program_id == ed25519_program::ID
+            && ed25519_instruction_verifies(&instruction.data, signer, message)
+        {
+            return Ok(());
+        }
+        index += 1;
+    }
+
+    Err(ErrorCode::InvalidSwapPermitSignatureError.into())
+}
+
+/// Returns true if the data of an ed25519 program instruction verifies a signature of the signer
+/// over the message.
+///
+/// # Arguments
+///
+/// * `data` - The data of the ed25519 instruction.
+/// * `signer` - The expected signer.
+/// * `message` - The expected message.
+pub fn ed25519_instruction_verifies(data: &[u8], signer: &Pubkey, message: &[u8]) -> bool {
+    let num_signatures = match data.first() {
+        Some(num_signatures) => usize::from(*num_signatures),
+        None => return false,
+    };
+
+    (0..num_signatures).any(|i| {
+        let start = ED25519_SIGNATURE_OFFSETS_START + i * ED25519_SIGNATURE_OFFSETS_LEN;
+        let offsets = match data.get(start..start + ED25519_SIGNATURE_OFFSETS_LEN) {
+            Some(offsets) => offsets,
+            None => return false,
+        };
+        let read_u16 = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]);
+
+        let signature_offset = usize::from(read_u16(0));
+        let signature_instruction_index = read_u16(2);
+        let public_key_offset = usize::from(read_u16(4));
+        let public_key_instruction_index = read_u16(6);
+        let message_data_offset = usize::from(read_u16(8));
+        let message_data_size = usize::from(read_u16(10));
+        let message_instruction_index = read_u16(12);
+
+        if signature_instruction_index != ED25519_CURRENT_INSTRUCTION_INDEX
+            || public_key_instruction_index != ED25519_CURRENT_INSTRUCTION_INDEX
+            || message_instruction_index != ED25519_CURRENT_INSTRUCTION_INDEX
+            || data.get(signature_offset..signature_offset + ED25519_SIGNATURE_LEN).is_none()
+        {
+            return false;
+        }
+
+        data.get(public_key_offset..public_key_offset + ED25519_PUBKEY_LEN) == Some(signer.as_ref())
+            && data.get(message_data_offset..message_data_offset + message_data_size) == Some(message)
+    })
+}
+
+#[cfg(test)]
+mod signature_utils_tests {
+    use super::*;
+
+    // Builds the data of an ed25519 instruction verifying a single signature, laid out as the
+    // ed25519 program expects
+    fn build_ed25519_data(signer: &Pubkey, message: &[u8], instruction_index: u16) -> Vec<u8> {
+        let public_key_offset = ED25519_SIGNATURE_OFFSETS_START + ED25519_SIGNATURE_OFFSETS_LEN;
+        let signature_offset = public_key_offset + ED25519_PUBKEY_LEN;
+        let message_data_offset = signature_offset + ED25519_SIGNATURE_LEN;
+
+        let mut data = vec![1, 0];
+        for value in [
+            signature_offset as u16,
+            instruction_index,
+            public_key_offset as u16,
+            instruction_index,
+            message_data_offset as u16,
+            message.len() as u16,
+            instruction_index,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(signer.as_ref());
+        data.extend_from_slice(&[7; ED25519_SIGNATURE_LEN]);
+        data.extend_from_slice(message);
+        data
+    }
+
+    #[test]
+    fn test_ed25519_instruction_verifies() {
+        let signer = Pubkey::new_unique();
+        let data = build_ed25519_data(&signer, b"permit", ED25519_CURRENT_INSTRUCTION_INDEX);
+
+        assert!(ed25519_instruction_verifies(&data, &signer, b"permit"));
+        assert!(!ed25519_instruction_verifies(&data, &Pubkey::new_unique(), b"permit"));
+        assert!(!ed25519_instruction_verifies(&data, &signer, b"other"));
+        assert!(!ed25519_instruction_verifies(&data, &signer, b"permi"));
+    }
+
+    #[test]
+    fn test_ed25519_instruction_verifies_rejects_other_instructions() {
+        let signer = Pubkey::new_unique();
+        let data = build_ed25519_data(&signer, b"permit", 0);
+
+        assert!(!ed25519_instruction_verifies(&data, &signer, b"permit"));
+    }
+
+    #[test]
+    fn test_ed25519_instruction_verifies_malformed_data() {
+        let signer = Pubkey::new_unique();
+        let data = build_ed25519_data(&signer, b"permit", ED25519_CURRENT_INSTRUCTION_INDEX);
+
+        assert!(!ed25519_instruction_verifies(&[], &signer, b"permit"));
+        assert!(!ed25519_instruction_verifies(&data[..10], &signer, b"permit"));
+        assert!(!ed25519_instruction_verifies(&data[..data.len() - 1], &signer, b"permit"));
+
+        let mut no_signature = data.clone();
+        no_signature[0] = 0;
+        assert!(!ed25519_instruction_verifies(&no_signature, &signer, b"permit"));
+    }
+}
@@ -3,8 +3,13 @@ use crate::state::*;
 use anchor_lang::prelude::*;
 use std::cell::RefMut;
 
+use super::TickArrayRefMut;
+
 pub struct SwapTickSequence<'info> {
-    arrays: Vec<RefMut<'info, TickArray>>,
+    arrays: Vec<TickArrayRefMut<'info>>,
+    no_initialized_tick_arrays_beyond: bool,
+    skip_tick_updates: bool,
+    tick_index_bounds: (i32, i32),
 }
 
 impl<'info> SwapTickSequence<'info> {
@@ -12,6 +17,19 @@ impl<'info> SwapTickSequence<'info> {
         ta0: RefMut<'info, TickArray>,
         ta1: Option<RefMut<'info, TickArray>>,
         ta2: Option<RefMut<'info, TickArray>>,
+    ) -> Self {
+        Self::new_with_tick_arrays(
+            TickArrayRefMut::Fixed(ta0),
+            ta1.map(TickArrayRefMut::Fixed),
+            ta2.map(TickArrayRefMut::Fixed),
+        )
+    }
+
+    /// Creates a sequence from tick arrays of either kind, fixed or dynamic.
+    pub fn new_with_tick_arrays(
+        ta0: TickArrayRefMut<'info>,
+        ta1: Option<TickArrayRefMut<'info>>,
+        ta2: Option<TickArrayRefMut<'info>>,
     ) -> Self {
         let mut vec = Vec::with_capacity(3);
         vec.push(ta0);
@@ -21,7 +39,63 @@ impl<'info> SwapTickSequence<'info> {
         if ta2.is_some() {
             vec.push(ta2.unwrap());
         }
-        Self { arrays: vec }
+        Self {
+            arrays: vec,
+            no_initialized_tick_arrays_beyond: false,
+            skip_tick_updates: false,
+            tick_index_bounds: (MIN_TICK_INDEX, MAX_TICK_INDEX),
+        }
+    }
+
+    /// Leaves the ticks untouched by the swap, e.g. to quote it without changing any state.
+    ///
+    /// The ticks crossed by a swap only get their growths outside updated, which the rest of the
+    /// swap never reads since the price moves one way, so the result of the swap is the same.
+    pub fn skip_tick_updates(&mut self) {
+        self.skip_tick_updates = true;
+    }
+
+    /// Limits the sequence to the tick range of the pool, which is narrower for the pools created
+    /// before the tick range was widened.
+    ///
+    /// # Parameters
+    /// - `tick_index_bounds` - The min and max tick indexes of the pool
+    pub fn set_tick_index_bounds(&mut self, tick_index_bounds: (i32, i32)) {
+        self.tick_index_bounds = tick_index_bounds;
+    }
+
+    /// Appends a tick array to the end of the sequence, e.g. a supplemental tick array
+    /// passed through the remaining accounts.
+    ///
+    /// The tick array must continue the sequence in the swap direction.
+    pub fn push_tick_array(&mut self, tick_array: TickArrayRefMut<'info>) {
+        self.arrays.push(tick_array);
+    }
+
+    /// Uses the pool's tick array bitmap to check whether any tick array beyond the last one
+    /// in this sequence is initialized in the swap direction.
+    ///
+    /// If none is, there is no initialized tick past the last array, so the swap is allowed to
+    /// continue up to the price limit instead of stopping at the end of the last array.
+    ///
+    /// # Parameters
+    /// - `tick_array_bitmap` - The tick array bitmap of the pool
+    /// - `tick_spacing` - A u16 integer of the tick spacing for this ai_dex
+    /// - `a_to_b` - The direction of the swap
+    ///
+    /// # Errors
+    /// - `InvalidStartTickIndex` - The last tick array has an invalid start tick index
+    pub fn apply_tick_array_bitmap(
+        &mut self,
+        tick_array_bitmap: &TickArrayBitmap,
+        tick_spacing: u16,
+        a_to_b: bool,
+    ) -> Result<()> {
+        let last_start_tick_index = self.arrays[self.arrays.len() - 1].start_tick_index();
+        self.no_initialized_tick_arrays_beyond = tick_array_bitmap
+            .next_initialized_start_tick_index(last_start_tick_index, tick_spacing, a_to_b)?
+            .is_none();
+        Ok(())
     }
 
     /// Get the Tick object at the given tick-index & tick-spacing
@@ -66,7 +140,9 @@ impl<'info> SwapTickSequence<'info> {
         update: &TickUpdate,
     ) -> Result<()> {
         if let Some(array) = self.arrays.get_mut(array_index) {
-            array.update_tick(tick_index, tick_spacing, update)?;
+            if !self.skip_tick_updates {
+                array.update_tick(tick_index, tick_spacing, update)?;
+            }
             Ok(())
         } else {
             Err(ErrorCode::TickArrayIndexOutOfBounds.into())
@@ -117,6 +193,7 @@ impl<'info> SwapTickSequence<'info> {
         start_array_index: usize,
     ) -> Result<(usize, i32)> {
         let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+        let (min_tick_index, max_tick_index) = self.tick_index_bounds;
         let mut search_index = tick_index;
         let mut array_index = start_array_index;
 
@@ -128,32 +205,36 @@ impl<'info> SwapTickSequence<'info> {
             }
 
             // Check if the trade direction is from A to B and the current array is the minimum tick array
-            if a_to_b && next_array.is_min_tick_array() {
-            return Ok((array_index, MIN_TICK_INDEX));
+            if a_to_b && next_array.is_min_tick_array(min_tick_index) {
+            return Ok((array_index, min_tick_index));
             }
             // Check if the trade direction is from B to A and the current array is the maximum tick array
-            else if !a_to_b && next_array.is_max_tick_array(tick_spacing) {
-            return Ok((array_index, MAX_TICK_INDEX));
+            else if !a_to_b && next_array.is_max_tick_array(max_tick_index, tick_spacing) {
+            return Ok((array_index, max_tick_index));
             }
 
             // Check if we have reached the last array
             if array_index + 1 == self.arrays.len() {
+            // If no tick array is initialized beyond the last one, there is no initialized tick to stop at
+            if self.no_initialized_tick_arrays_beyond {
+                return Ok((array_index, if a_to_b { min_tick_index } else { max_tick_index }));
+            }
             // If the trade direction is from A to B, return the start tick index of the last array
             if a_to_b {
-                return Ok((array_index, next_array.start_tick_index));
+                return Ok((array_index, next_array.start_tick_index()));
             }
             // If the trade direction is from B to A, return the last tick index of the last array
             else {
-                let last_tick = next_array.start_tick_index + ticks_in_array - 1;
+                let last_tick = next_array.start_tick_index() + ticks_in_array - 1;
                 return Ok((array_index, last_tick));
             }
             }
 
             // Update the search index and array index for the next iteration
             search_index = if a_to_b {
-            next_array.start_tick_index - 1
+            next_array.start_tick_index() - 1
             } else {
-            next_array.start_tick_index + ticks_in_array - 1
+            next_array.start_tick_index() + ticks_in_array - 1
             };
 
             array_index += 1;
@@ -232,6 +313,32 @@ mod swap_tick_sequence_tests {
             }
         }
 
+        #[test]
+        fn skip_tick_updates_leaves_ticks_untouched() {
+            let ta0 = build_tick_array(0, vec![25]);
+            let mut swap_tick_sequence = SwapTickSequence::new(ta0.borrow_mut(), None, None);
+            swap_tick_sequence.skip_tick_updates();
+
+            let tick_index = 25 * TS_128 as i32;
+            let update_result = swap_tick_sequence.update_tick(
+                0,
+                tick_index,
+                TS_128,
+                &TickUpdate {
+                    initialized: true,
+                    liquidity_net: 1500,
+                    ..Default::default()
+                },
+            );
+            assert_eq!(update_result.is_ok(), true);
+
+            let liq_net = swap_tick_sequence.get_tick(0, tick_index, TS_128).unwrap().liquidity_net;
+            assert_eq!(liq_net, 0);
+            assert!(swap_tick_sequence
+                .update_tick(1, tick_index, TS_128, &TickUpdate::default())
+                .is_err());
+        }
+
         #[test]
         fn modify_tick_uninitializable_tick() {
             let ta0 = build_tick_array(9216, vec![50]);
@@ -507,7 +614,7 @@ mod swap_tick_sequence_tests {
         fn b_to_a_search_reaching_max_tick() {
             let ta0 = build_tick_array(0, vec![]);
             let ta1 = build_tick_array(0, vec![]);
-            let ta2 = build_tick_array(443520, vec![]); // Max(443636).div_floor(tick-spacing (8) * TA Size (72))* tick-spacing (8) *  TA Size (72)
+            let ta2 = build_tick_array(519552, vec![]); // Max(519886).div_floor(tick-spacing (8) * TA Size (88))* tick-spacing (8) *  TA Size (88)
             let swap_tick_sequence = SwapTickSequence::new(
                 ta0.borrow_mut(),
                 Some(ta1.borrow_mut()),
@@ -515,10 +622,10 @@ mod swap_tick_sequence_tests {
             );
 
             let (array_index, index) = swap_tick_sequence
-                .get_next_initialized_tick_index(443521, TS_8, false, 2)
+                .get_next_initialized_tick_index(519553, TS_8, false, 2)
                 .unwrap();
 
-            assert_eq!(index, 443636);
+            assert_eq!(index, 519886);
             assert_eq!(array_index, 2);
         }
 
@@ -526,13 +633,53 @@ mod swap_tick_sequence_tests {
         fn a_to_b_search_reaching_min_tick() {
             let ta0 = build_tick_array(0, vec![]);
             let ta1 = build_tick_array(0, vec![]);
-            let ta2 = build_tick_array(-444096, vec![]); // Min(-443636).div_ceil(tick-spacing (8) * TA Size (72)) * tick-spacing (8) * TA Size (72)
+            let ta2 = build_tick_array(-520256, vec![]); // Min(-519886).div_ceil(tick-spacing (8) * TA Size (88)) * tick-spacing (8) * TA Size (88)
             let swap_tick_sequence = SwapTickSequence::new(
                 ta2.borrow_mut(),
                 Some(ta1.borrow_mut()),
                 Some(ta0.borrow_mut()),
             );
 
+            let (array_index, index) = swap_tick_sequence
+                .get_next_initialized_tick_index(-519553, TS_8, true, 0)
+                .unwrap();
+
+            assert_eq!(index, -519886);
+            assert_eq!(array_index, 0);
+        }
+
+        #[test]
+        fn b_to_a_search_reaching_legacy_max_tick() {
+            let ta0 = build_tick_array(0, vec![]);
+            let ta1 = build_tick_array(0, vec![]);
+            let ta2 = build_tick_array(443520, vec![]); // Legacy max(443636).div_floor(tick-spacing (8) * TA Size (88)) * tick-spacing (8) * TA Size (88)
+            let mut swap_tick_sequence = SwapTickSequence::new(
+                ta0.borrow_mut(),
+                Some(ta1.borrow_mut()),
+                Some(ta2.borrow_mut()),
+            );
+            swap_tick_sequence.set_tick_index_bounds((LEGACY_MIN_TICK_INDEX, LEGACY_MAX_TICK_INDEX));
+
+            let (array_index, index) = swap_tick_sequence
+                .get_next_initialized_tick_index(443521, TS_8, false, 2)
+                .unwrap();
+
+            assert_eq!(index, 443636);
+            assert_eq!(array_index, 2);
+        }
+
+        #[test]
+        fn a_to_b_search_reaching_legacy_min_tick() {
+            let ta0 = build_tick_array(0, vec![]);
+            let ta1 = build_tick_array(0, vec![]);
+            let ta2 = build_tick_array(-444224, vec![]); // Legacy min(-443636).div_ceil(tick-spacing (8) * TA Size (88)) * tick-spacing (8) * TA Size (88)
+            let mut swap_tick_sequence = SwapTickSequence::new(
+                ta2.borrow_mut(),
+                Some(ta1.borrow_mut()),
+                Some(ta0.borrow_mut()),
+            );
+            swap_tick_sequence.set_tick_index_bounds((LEGACY_MIN_TICK_INDEX, LEGACY_MAX_TICK_INDEX));
+
             let (array_index, index) = swap_tick_sequence
                 .get_next_initialized_tick_index(-443521, TS_8, true, 0)
                 .unwrap();
@@ -540,6 +687,39 @@ mod swap_tick_sequence_tests {
             assert_eq!(index, -443636);
             assert_eq!(array_index, 0);
         }
+
+        #[test]
+        fn a_to_b_search_past_last_array_with_bitmap() {
+            let ticks_in_array = TICK_ARRAY_SIZE * TS_8 as i32;
+            let ta0 = build_tick_array(ticks_in_array, vec![]);
+            let ta1 = build_tick_array(0, vec![]);
+            let ta2 = build_tick_array(-ticks_in_array, vec![]);
+            let mut swap_tick_sequence = SwapTickSequence::new(
+                ta0.borrow_mut(),
+                Some(ta1.borrow_mut()),
+                Some(ta2.borrow_mut()),
+            );
+
+            let tick_array_bitmap = &mut TickArrayBitmap::default();
+            tick_array_bitmap.set_initialized(-ticks_in_array, TS_8).unwrap();
+
+            // No tick array is initialized past the last one, so the search is bounded by the min tick
+            swap_tick_sequence.apply_tick_array_bitmap(tick_array_bitmap, TS_8, true).unwrap();
+            let (array_index, index) = swap_tick_sequence
+                .get_next_initialized_tick_index(-1, TS_8, true, 2)
+                .unwrap();
+            assert_eq!(index, MIN_TICK_INDEX);
+            assert_eq!(array_index, 2);
+
+            // Another tick array is initialized past the last one, so the search stops at the last array
+            tick_array_bitmap.set_initialized(-2 * ticks_in_array, TS_8).unwrap();
+            swap_tick_sequence.apply_tick_array_bitmap(tick_array_bitmap, TS_8, true).unwrap();
+            let (array_index, index) = swap_tick_sequence
+                .get_next_initialized_tick_index(-1, TS_8, true, 2)
+                .unwrap();
+            assert_eq!(index, -ticks_in_array);
+            assert_eq!(array_index, 2);
+        }
     }
 
     #[test]
@@ -734,4 +914,87 @@ mod swap_tick_sequence_tests {
             curr_array_index = array_index;
         }
     }
+
+    #[test]
+    /// a-to-b search continuing into a supplemental tick array pushed after the first three
+    fn a_to_b_search_into_supplemental_array() {
+        let ta0 = build_tick_array(9216, vec![]);
+        let ta1 = build_tick_array(0, vec![]);
+        let ta2 = build_tick_array(-9216, vec![]);
+        let ta3 = build_tick_array(-18432, vec![40]);
+        let mut swap_tick_sequence = SwapTickSequence::new(
+            ta0.borrow_mut(),
+            Some(ta1.borrow_mut()),
+            Some(ta2.borrow_mut()),
+        );
+        swap_tick_sequence.push_tick_array(TickArrayRefMut::Fixed(ta3.borrow_mut()));
+
+        let (array_index, index) = swap_tick_sequence
+            .get_next_initialized_tick_index(-1, TS_128, true, 2)
+            .unwrap();
+        assert_eq!(array_index, 3);
+        assert_eq!(index, -18432 + 40 * TS_128 as i32);
+        assert!(swap_tick_sequence.get_tick(array_index, index, TS_128).unwrap().initialized);
+    }
+
+    #[test]
+    fn a_to_b_search_across_fixed_and_dynamic_arrays() {
+        let ta0 = build_tick_array(704, vec![5]);
+        let ta1 = RefCell::new(vec![0u8; DynamicTickArray::len_for_ticks(1) - 8]);
+        {
+            let mut data = ta1.borrow_mut();
+            data[..4].copy_from_slice(&0i32.to_le_bytes());
+            DynamicTickArrayLoader::load_mut(&mut data[..])
+                .insert_tick(
+                    80,
+                    TS_8,
+                    &TickUpdate {
+                        initialized: true,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+        }
+        let mut swap_tick_sequence = SwapTickSequence::new_with_tick_arrays(
+            TickArrayRefMut::Fixed(ta0.borrow_mut()),
+            Some(TickArrayRefMut::Dynamic(RefMut::map(ta1.borrow_mut(), |data| {
+                DynamicTickArrayLoader::load_mut(&mut data[..])
+            }))),
+            None,
+        );
+
+        let expectation = [(744, 0, true), (80, 1, true), (0, 1, false)];
+        let mut search_index = 780;
+        let mut curr_array_index = 0;
+        for i in 0..expectation.len() {
+            let (array_index, index) = swap_tick_sequence
+                .get_next_initialized_tick_index(search_index, TS_8, true, curr_array_index)
+                .unwrap();
+
+            assert_eq!(index, expectation[i].0);
+            assert_eq!(array_index, expectation[i].1);
+
+            let tick = swap_tick_sequence
+                .get_tick(array_index, index, TS_8)
+                .unwrap();
+            assert_eq!(tick.initialized, expectation[i].2);
+
+            search_index = index - 1;
+            curr_array_index = array_index;
+        }
+
+        swap_tick_sequence
+            .update_tick(
+                1,
+                80,
+                TS_8,
+                &TickUpdate {
+                    initialized: true,
+                    liquidity_net: 1500,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!({ swap_tick_sequence.get_tick(1, 80, TS_8).unwrap().liquidity_net }, 1500);
+    }
 }
@@ -2,16 +2,26 @@ use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use anchor_spl::memo::Memo;
 
-use crate::{orchestrator::swap_orchestrator::PostSwapUpdate, state::AiDexPool};
+use crate::{
+    errors::ErrorCode,
+    orchestrator::swap_orchestrator::{resolve_sqrt_price_limit, swap, PostSwapUpdate},
+    state::{AiDexPool, PoolStats, SwapHookSummary},
+};
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program::invoke;
 
-use super::{transfer_from_owner_to_vault, transfer_from_vault_to_owner};
+use super::{
+    apply_tick_array_bitmap, load_supplemental_tick_arrays, oracle_volatility_ticks,
+    record_oracle_observation, token_account_amount, transfer_from_owner_to_vault,
+    transfer_from_program_account, transfer_from_vault_to_owner, SwapTickSequence, TickArrayAccount,
+};
 
 
 /// Updates the AiDex state and performs a swap between two tokens in the AiDex program.
 /// 
 /// # Arguments
 /// 
-/// * `ai_dex` - The mutable reference to the AiDex account.
+/// * `ai_dex` - The loader of the AiDex account.
 /// * `token_authority` - The signer for the token authority account.
 /// * `token_mint_a` - The interface account for the first token mint.
 /// * `token_mint_b` - The interface account for the second token mint.
@@ -33,7 +43,7 @@ use super::{transfer_from_owner_to_vault, transfer_from_vault_to_owner};
 /// 
 /// Returns an error if the swap fails.
 pub fn update_and_swap_ai_dex<'info>(
-    ai_dex: &mut Account<'info, AiDexPool>,
+    ai_dex: &AccountLoader<'info, AiDexPool>,
     token_authority: &Signer<'info>,
     token_mint_a: &InterfaceAccount<'info, Mint>,
     token_mint_b: &InterfaceAccount<'info, Mint>,
@@ -51,16 +61,15 @@ pub fn update_and_swap_ai_dex<'info>(
     reward_last_updated_timestamp: u64,
     memo: &[u8],
 ) -> Result<()> {
-    ai_dex.update_after_swap(
-        swap_update.next_liquidity,
-        swap_update.next_tick_index,
-        swap_update.next_sqrt_price,
-        swap_update.next_fee_growth_global,
-        swap_update.next_reward_infos,
-        swap_update.next_protocol_fee,
+    update_ai_dex_after_swap(
+        ai_dex,
+        token_authority.key,
+        token_vault_a,
+        token_vault_b,
+        &swap_update,
         is_token_fee_in_a,
         reward_last_updated_timestamp,
-    );
+    )?;
 
     perform_swap(
         ai_dex,
@@ -83,11 +92,176 @@ pub fn update_and_swap_ai_dex<'info>(
     )
 }
 
+/// Updates the AiDex state and performs a swap whose input is paid through a delegation.
+///
+/// The owner of the token accounts approved a program account as the delegate of its input
+/// token account, which signs the input transfer with its seeds. The output is sent to the
+/// output token account of the owner. Input mints with a transfer hook are not supported.
+///
+/// # Arguments
+///
+/// * `ai_dex` - The loader of the AiDex account.
+/// * `token_authority` - The owner of the token accounts, on whose behalf the swap is made.
+/// * `delegate` - The program account delegated by the owner of the input token account.
+/// * `delegate_seeds` - The seeds of the delegate.
+/// * `token_mint_a` - The interface account for the first token mint.
+/// * `token_mint_b` - The interface account for the second token mint.
+/// * `token_owner_account_a` - The interface account for the first token owner account.
+/// * `token_owner_account_b` - The interface account for the second token owner account.
+/// * `token_vault_a` - The interface account for the first token vault account.
+/// * `token_vault_b` - The interface account for the second token vault account.
+/// * `transfer_hook_accounts_a` - The transfer hook accounts of the first token, used when it is the output.
+/// * `transfer_hook_accounts_b` - The transfer hook accounts of the second token, used when it is the output.
+/// * `token_program_a` - The interface for the first token program.
+/// * `token_program_b` - The interface for the second token program.
+/// * `memo_program` - The program for memo instructions.
+/// * `swap_update` - The post-swap update.
+/// * `is_token_fee_in_a` - A boolean indicating whether the token fee is in the first token.
+/// * `reward_last_updated_timestamp` - The timestamp when the reward was last updated.
+/// * `memo` - The memo bytes for the swap.
+///
+/// # Errors
+///
+/// Returns an error if the swap fails or the input mint has a transfer hook.
+pub fn update_and_swap_ai_dex_from_delegate<'info>(
+    ai_dex: &AccountLoader<'info, AiDexPool>,
+    token_authority: &Pubkey,
+    delegate: &AccountInfo<'info>,
+    delegate_seeds: &[&[u8]],
+    token_mint_a: &InterfaceAccount<'info, Mint>,
+    token_mint_b: &InterfaceAccount<'info, Mint>,
+    token_owner_account_a: &InterfaceAccount<'info, TokenAccount>,
+    token_owner_account_b: &InterfaceAccount<'info, TokenAccount>,
+    token_vault_a: &InterfaceAccount<'info, TokenAccount>,
+    token_vault_b: &InterfaceAccount<'info, TokenAccount>,
+    transfer_hook_accounts_a: &Option<Vec<AccountInfo<'info>>>,
+    transfer_hook_accounts_b: &Option<Vec<AccountInfo<'info>>>,
+    token_program_a: &Interface<'info, TokenInterface>,
+    token_program_b: &Interface<'info, TokenInterface>,
+    memo_program: &Program<'info, Memo>,
+    swap_update: &PostSwapUpdate,
+    is_token_fee_in_a: bool,
+    reward_last_updated_timestamp: u64,
+    memo: &[u8],
+) -> Result<()> {
+    update_ai_dex_after_swap(
+        ai_dex,
+        token_authority,
+        token_vault_a,
+        token_vault_b,
+        swap_update,
+        is_token_fee_in_a,
+        reward_last_updated_timestamp,
+    )?;
+
+    if is_token_fee_in_a {
+        transfer_from_program_account(
+            ai_dex,
+            delegate,
+            delegate_seeds,
+            token_mint_a,
+            token_owner_account_a,
+            token_vault_a,
+            token_program_a,
+            memo_program,
+            swap_update.amount_a,
+            memo,
+        )?;
+        transfer_from_vault_to_owner(
+            ai_dex,
+            token_mint_b,
+            token_vault_b,
+            token_owner_account_b,
+            token_program_b,
+            memo_program,
+            transfer_hook_accounts_b,
+            swap_update.amount_b,
+            memo,
+        )
+    } else {
+        transfer_from_program_account(
+            ai_dex,
+            delegate,
+            delegate_seeds,
+            token_mint_b,
+            token_owner_account_b,
+            token_vault_b,
+            token_program_b,
+            memo_program,
+            swap_update.amount_b,
+            memo,
+        )?;
+        transfer_from_vault_to_owner(
+            ai_dex,
+            token_mint_a,
+            token_vault_a,
+            token_owner_account_a,
+            token_program_a,
+            memo_program,
+            transfer_hook_accounts_a,
+            swap_update.amount_a,
+            memo,
+        )
+    }
+}
+
+/// Updates the AiDex state after a swap, before its tokens are transferred.
+///
+/// The slot volume, the authority of the swap and the window volume are recorded along with
+/// the new price, liquidity, fee growth, rewards and protocol fee.
+///
+/// # Arguments
+///
+/// * `ai_dex` - The loader of the AiDex account.
+/// * `token_authority` - The authority of the swap, checked by the anti-sandwich protection.
+/// * `token_vault_a` - The interface account for the first token vault account.
+/// * `token_vault_b` - The interface account for the second token vault account.
+/// * `swap_update` - The post-swap update.
+/// * `is_token_fee_in_a` - A boolean indicating whether the token fee is in the first token.
+/// * `reward_last_updated_timestamp` - The timestamp when the reward was last updated.
+///
+/// # Errors
+///
+/// Returns an error if the slot volume cap or the anti-sandwich check rejects the swap.
+pub fn update_ai_dex_after_swap<'info>(
+    ai_dex: &AccountLoader<'info, AiDexPool>,
+    token_authority: &Pubkey,
+    token_vault_a: &InterfaceAccount<'info, TokenAccount>,
+    token_vault_b: &InterfaceAccount<'info, TokenAccount>,
+    swap_update: &PostSwapUpdate,
+    is_token_fee_in_a: bool,
+    reward_last_updated_timestamp: u64,
+) -> Result<()> {
+    let (amount_out, vault_out_balance) = if is_token_fee_in_a {
+        (swap_update.amount_b, token_vault_b.amount)
+    } else {
+        (swap_update.amount_a, token_vault_a.amount)
+    };
+    let slot = Clock::get()?.slot;
+
+    let mut ai_dex_mut = ai_dex.load_mut()?;
+    ai_dex_mut.record_slot_volume(slot, amount_out, vault_out_balance, is_token_fee_in_a)?;
+    ai_dex_mut.record_swap_authority(slot, *token_authority, is_token_fee_in_a)?;
+    ai_dex_mut.record_window_volume(swap_update.amount_a, reward_last_updated_timestamp);
+    ai_dex_mut.update_after_swap(
+        swap_update.next_liquidity,
+        swap_update.next_tick_index,
+        swap_update.next_sqrt_price,
+        swap_update.next_fee_growth_global,
+        swap_update.next_reward_infos,
+        swap_update.next_protocol_fee,
+        is_token_fee_in_a,
+        reward_last_updated_timestamp,
+    );
+
+    Ok(())
+}
+
 /// Performs a swap between two tokens in the AiDex program.
 /// 
 /// # Arguments
 /// 
-/// * `ai_dex` - The reference to the AiDex account.
+/// * `ai_dex` - The loader of the AiDex account.
 /// * `token_authority` - The signer for the token authority account.
 /// * `token_mint_a` - The interface account for the first token mint.
 /// * `token_mint_b` - The interface account for the second token mint.
@@ -109,7 +283,7 @@ pub fn update_and_swap_ai_dex<'info>(
 /// 
 /// Returns an error if the swap fails.
 fn perform_swap<'info>(
-    ai_dex: &Account<'info, AiDexPool>,
+    ai_dex: &AccountLoader<'info, AiDexPool>,
     token_authority: &Signer<'info>,
     token_mint_a: &InterfaceAccount<'info, Mint>,
     token_mint_b: &InterfaceAccount<'info, Mint>,
@@ -174,6 +348,7 @@ fn perform_swap<'info>(
     }
 
     transfer_from_owner_to_vault(
+        ai_dex,
         token_authority,
         deposit_mint,
         deposit_account_user,
@@ -205,8 +380,8 @@ fn perform_swap<'info>(
 /// 
 /// * `swap_update_one` - The post-swap update for the first swap.
 /// * `swap_update_two` - The post-swap update for the second swap.
-/// * `ai_dex_one` - The mutable reference to the first AiDex account.
-/// * `ai_dex_two` - The mutable reference to the second AiDex account.
+/// * `ai_dex_one` - The loader of the first AiDex account.
+/// * `ai_dex_two` - The loader of the second AiDex account.
 /// * `is_token_fee_in_one_a` - A boolean indicating whether the token fee is in the first token for the first swap.
 /// * `is_token_fee_in_two_a` - A boolean indicating whether the token fee is in the first token for the second swap.
 /// * `token_mint_input` - The interface account for the input token mint.
@@ -237,8 +412,8 @@ pub fn update_and_two_hop_swap_ai_dex<'info>(
     swap_update_one: PostSwapUpdate,
     swap_update_two: PostSwapUpdate,
     // ai_dex
-    ai_dex_one: &mut Account<'info, AiDexPool>,
-    ai_dex_two: &mut Account<'info, AiDexPool>,
+    ai_dex_one: &AccountLoader<'info, AiDexPool>,
+    ai_dex_two: &AccountLoader<'info, AiDexPool>,
     // direction
     is_token_fee_in_one_a: bool,
     is_token_fee_in_two_a: bool,
@@ -267,7 +442,27 @@ pub fn update_and_two_hop_swap_ai_dex<'info>(
     reward_last_updated_timestamp: u64,
     memo: &[u8],
 ) -> Result<()> {
-    ai_dex_one.update_after_swap(
+    let slot = Clock::get()?.slot;
+    let intermediate_amount_out = if is_token_fee_in_one_a { swap_update_one.amount_b } else { swap_update_one.amount_a };
+    let output_amount_out = if is_token_fee_in_two_a { swap_update_two.amount_b } else { swap_update_two.amount_a };
+    ai_dex_one.load_mut()?.record_slot_volume(
+        slot,
+        intermediate_amount_out,
+        token_vault_one_intermediate.amount,
+        is_token_fee_in_one_a,
+    )?;
+    ai_dex_one.load_mut()?.record_swap_authority(slot, token_authority.key(), is_token_fee_in_one_a)?;
+    ai_dex_one.load_mut()?.record_window_volume(swap_update_one.amount_a, reward_last_updated_timestamp);
+    ai_dex_two.load_mut()?.record_slot_volume(
+        slot,
+        output_amount_out,
+        token_vault_two_output.amount,
+        is_token_fee_in_two_a,
+    )?;
+    ai_dex_two.load_mut()?.record_swap_authority(slot, token_authority.key(), is_token_fee_in_two_a)?;
+    ai_dex_two.load_mut()?.record_window_volume(swap_update_two.amount_a, reward_last_updated_timestamp);
+
+    ai_dex_one.load_mut()?.update_after_swap(
         swap_update_one.next_liquidity,
         swap_update_one.next_tick_index,
         swap_update_one.next_sqrt_price,
@@ -278,7 +473,7 @@ pub fn update_and_two_hop_swap_ai_dex<'info>(
         reward_last_updated_timestamp,
     );
 
-    ai_dex_two.update_after_swap(
+    ai_dex_two.load_mut()?.update_after_swap(
         swap_update_two.next_liquidity,
         swap_update_two.next_tick_index,
         swap_update_two.next_sqrt_price,
@@ -298,6 +493,7 @@ pub fn update_and_two_hop_swap_ai_dex<'info>(
     let output_amount = if is_token_fee_in_two_a { swap_update_two.amount_b } else { swap_update_two.amount_a };
 
     transfer_from_owner_to_vault(
+        ai_dex_one,
         token_authority,
         token_mint_input,
         token_owner_account_input,
@@ -309,6 +505,7 @@ pub fn update_and_two_hop_swap_ai_dex<'info>(
     )?;
 
     // Transfer from pool to pool
+    let vault_two_intermediate_amount = token_account_amount(token_vault_two_intermediate)?;
     transfer_from_vault_to_owner(
         ai_dex_one,
         token_mint_intermediate,
@@ -320,6 +517,11 @@ pub fn update_and_two_hop_swap_ai_dex<'info>(
         intermediate_amount,
         memo,
     )?;
+    ai_dex_two.load_mut()?.track_vault_balance(
+        &token_vault_two_intermediate.key(),
+        vault_two_intermediate_amount,
+        token_account_amount(token_vault_two_intermediate)?,
+    );
 
     transfer_from_vault_to_owner(
         ai_dex_two,
@@ -335,3 +537,259 @@ pub fn update_and_two_hop_swap_ai_dex<'info>(
 
     Ok(())
 }
+
+/// Swaps tokens already held by the vaults of a pool on behalf of an order, without any transfer.
+///
+/// The input stays in its vault and the output is left in the other vault, so the caller only has
+/// to account for the amounts. No transfer fee applies to either side. The output still counts
+/// toward the per-slot volume cap of the pool, and the owner of the order toward its anti-sandwich
+/// protection, like any other swap.
+///
+/// # Arguments
+///
+/// * `ai_dex` - The AiDex pool, updated in place.
+/// * `ai_dex_pool_key` - The pubkey of the AiDex pool.
+/// * `swap_authority` - The owner of the order, checked by the anti-sandwich protection.
+/// * `token_vault_a` - The token vault A of the pool.
+/// * `token_vault_b` - The token vault B of the pool.
+/// * `tick_array_0` - The first tick array of the swap.
+/// * `tick_array_1` - The second tick array of the swap.
+/// * `tick_array_2` - The third tick array of the swap.
+/// * `supplemental_tick_arrays` - The optional tick arrays continuing the sequence.
+/// * `tick_array_bitmap` - The tick array bitmap of the pool.
+/// * `oracle` - The oracle account of the pool.
+/// * `amount` - The input amount to swap.
+/// * `sqrt_price_limit` - The square root price limit for the swap. `0` means no limit in the swap direction.
+/// * `a_to_b` - The direction of the swap.
+/// * `timestamp` - The current timestamp.
+///
+/// # Returns
+///
+/// * `(u64, u64)` - The input and the output amounts of the swap.
+///
+/// # Errors
+///
+/// * `ErrorCode::SwapHookNotSupportedError` - If the pool has a swap hook, which order fills do not call.
+/// * `ErrorCode::PartialFillError` - If the price limit or the tick arrays stop the swap before the whole amount is swapped.
+/// * `ErrorCode::SlotVolumeExceededError` - If the volume swapped out within the slot exceeds the cap of the pool.
+/// * `ErrorCode::SandwichSwapError` - If the owner swapped in the opposite direction within the slot.
+pub fn swap_within_vaults<'info>(
+    ai_dex: &mut AiDexPool,
+    ai_dex_pool_key: &Pubkey,
+    swap_authority: &Pubkey,
+    token_vault_a: &InterfaceAccount<'info, TokenAccount>,
+    token_vault_b: &InterfaceAccount<'info, TokenAccount>,
+    tick_array_0: &UncheckedAccount<'info>,
+    tick_array_1: &UncheckedAccount<'info>,
+    tick_array_2: &UncheckedAccount<'info>,
+    supplemental_tick_arrays: &Option<Vec<AccountInfo<'info>>>,
+    tick_array_bitmap: &UncheckedAccount<'info>,
+    oracle: &UncheckedAccount<'info>,
+    amount: u64,
+    sqrt_price_limit: u128,
+    a_to_b: bool,
+    timestamp: u64,
+) -> Result<(u64, u64)> {
+    ai_dex.check_swap_hook_not_set()?;
+
+    let tick_array_0 = TickArrayAccount::try_from_with_pool(tick_array_0, ai_dex_pool_key)?;
+    let tick_array_1 = TickArrayAccount::try_from_with_pool(tick_array_1, ai_dex_pool_key)?;
+    let tick_array_2 = TickArrayAccount::try_from_with_pool(tick_array_2, ai_dex_pool_key)?;
+    let supplemental_tick_arrays = load_supplemental_tick_arrays(supplemental_tick_arrays, ai_dex_pool_key)?;
+
+    let mut swap_tick_sequence = SwapTickSequence::new_with_tick_arrays(
+        tick_array_0.load_mut()?,
+        tick_array_1.load_mut().ok(),
+        tick_array_2.load_mut().ok(),
+    );
+    for tick_array in supplemental_tick_arrays.iter() {
+        swap_tick_sequence.push_tick_array(tick_array.load_mut()?);
+    }
+    apply_tick_array_bitmap(tick_array_bitmap, &mut swap_tick_sequence, ai_dex.tick_spacing, a_to_b)?;
+
+    let volatility_ticks = oracle_volatility_ticks(oracle, ai_dex, timestamp)?;
+    let swap_update = swap(
+        ai_dex,
+        &mut swap_tick_sequence,
+        amount,
+        resolve_sqrt_price_limit(ai_dex, sqrt_price_limit, a_to_b),
+        true,
+        a_to_b,
+        timestamp,
+        0,
+        0,
+        volatility_ticks,
+    )?;
+    drop(swap_tick_sequence);
+
+    let (amount_in, amount_out) = if a_to_b {
+        (swap_update.amount_a, swap_update.amount_b)
+    } else {
+        (swap_update.amount_b, swap_update.amount_a)
+    };
+    if amount_in < amount {
+        return Err(ErrorCode::PartialFillError.into());
+    }
+
+    let vault_out_balance = if a_to_b { token_vault_b.amount } else { token_vault_a.amount };
+    let slot = Clock::get()?.slot;
+    ai_dex.record_slot_volume(slot, amount_out, vault_out_balance, a_to_b)?;
+    ai_dex.record_swap_authority(slot, *swap_authority, a_to_b)?;
+
+    record_oracle_observation(oracle, ai_dex, timestamp)?;
+    ai_dex.record_window_volume(swap_update.amount_a, timestamp);
+    ai_dex.update_after_swap(
+        swap_update.next_liquidity,
+        swap_update.next_tick_index,
+        swap_update.next_sqrt_price,
+        swap_update.next_fee_growth_global,
+        swap_update.next_reward_infos,
+        swap_update.next_protocol_fee,
+        a_to_b,
+        timestamp,
+    );
+
+    Ok((amount_in, amount_out))
+}
+
+/// Records a swap into the statistics account of the pool, if it has been initialized.
+///
+/// # Arguments
+///
+/// * `pool_stats` - The statistics PDA of the pool.
+/// * `swap_update` - The update computed for the swap.
+/// * `a_to_b` - The direction of the swap.
+/// * `timestamp` - The timestamp of the swap.
+///
+/// # Errors
+///
+/// Returns an error if the account is owned by this program but cannot be loaded as `PoolStats`.
+pub fn record_pool_stats<'info>(
+    pool_stats: &UncheckedAccount<'info>,
+    swap_update: &PostSwapUpdate,
+    a_to_b: bool,
+    timestamp: u64,
+) -> Result<()> {
+    if pool_stats.owner != &crate::ID || pool_stats.data_is_empty() {
+        return Ok(());
+    }
+
+    let mut stats = PoolStats::try_deserialize(&mut &pool_stats.try_borrow_data()?[..])?;
+    stats.record_swap(
+        swap_update.amount_a,
+        swap_update.amount_b,
+        swap_update.fee_amount,
+        swap_update.next_protocol_fee,
+        a_to_b,
+        timestamp,
+    );
+    stats.try_serialize(&mut &mut pool_stats.try_borrow_mut_data()?[..])
+}
+
+/// Calls the swap hook program of a pool with the summary of a swap.
+///
+/// The hook program receives the pool followed by the accounts it expects, none of them as a
+/// signer, so that it cannot act on behalf of the token authority.
+///
+/// # Arguments
+///
+/// * `swap_hook_program` - The swap hook program of the pool.
+/// * `ai_dex` - The pool of the swap.
+/// * `swap_hook_accounts` - The accounts expected by the swap hook program, if any.
+/// * `summary` - The summary of the swap.
+///
+/// # Errors
+///
+/// Returns an error if the swap hook program fails, which fails the swap.
+pub fn invoke_swap_hook<'info>(
+    swap_hook_program: &AccountInfo<'info>,
+    ai_dex: &AccountInfo<'info>,
+    swap_hook_accounts: &Option<Vec<AccountInfo<'info>>>,
+    summary: &SwapHookSummary,
+) -> Result<()> {
+    let mut account_metas = vec![AccountMeta::new_readonly(ai_dex.key(), false)];
+    let mut account_infos = vec![ai_dex.clone()];
+    for account in swap_hook_accounts.iter().flatten() {
+        account_metas.push(if account.is_writable {
+            AccountMeta::new(account.key(), false)
+        } else {
+            AccountMeta::new_readonly(account.key(), false)
+        });
+        account_infos.push(account.clone());
+    }
+    account_infos.push(swap_hook_program.clone());
+
+    let instruction = Instruction {
+        program_id: swap_hook_program.key(),
+        accounts: account_metas,
+        data: summary.instruction_data()?,
+    };
+    invoke(&instruction, &account_infos)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod swap_within_vaults_tests {
+    use super::*;
+    use anchor_spl::token::spl_token;
+    use solana_program::program_pack::Pack;
+
+    fn token_account_data() -> Vec<u8> {
+        let token_account = spl_token::state::Account {
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        };
+        let mut data = vec![0u8; spl_token::state::Account::LEN];
+        spl_token::state::Account::pack(token_account, &mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_swap_within_vaults_rejects_pool_with_swap_hook() {
+        let mut ai_dex = AiDexPool::default();
+        ai_dex.set_swap_hook_program(Pubkey::new_unique());
+
+        let keys: Vec<Pubkey> = (0..7).map(|_| Pubkey::new_unique()).collect();
+        let mut lamports = [0u64; 7];
+        let mut vault_data = [token_account_data(), token_account_data()];
+        let mut empty_data = [vec![], vec![], vec![], vec![], vec![]];
+        let (vault_lamports, empty_lamports) = lamports.split_at_mut(2);
+        let (vault_a_lamports, vault_b_lamports) = vault_lamports.split_at_mut(1);
+        let (vault_a_data, vault_b_data) = vault_data.split_at_mut(1);
+        let vault_a_info = AccountInfo::new(
+            &keys[0], false, false, &mut vault_a_lamports[0], &mut vault_a_data[0], &spl_token::ID, false, 0,
+        );
+        let vault_b_info = AccountInfo::new(
+            &keys[1], false, false, &mut vault_b_lamports[0], &mut vault_b_data[0], &spl_token::ID, false, 0,
+        );
+        let token_vault_a = InterfaceAccount::<TokenAccount>::try_from(&vault_a_info).unwrap();
+        let token_vault_b = InterfaceAccount::<TokenAccount>::try_from(&vault_b_info).unwrap();
+        let unchecked_infos: Vec<AccountInfo> = keys[2..]
+            .iter()
+            .zip(empty_lamports.iter_mut())
+            .zip(empty_data.iter_mut())
+            .map(|((key, lamports), data)| AccountInfo::new(key, false, true, lamports, data, &crate::ID, false, 0))
+            .collect();
+        let unchecked: Vec<UncheckedAccount> = unchecked_infos.iter().map(UncheckedAccount::try_from).collect();
+
+        let result = swap_within_vaults(
+            &mut ai_dex,
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &token_vault_a,
+            &token_vault_b,
+            &unchecked[0],
+            &unchecked[1],
+            &unchecked[2],
+            &None,
+            &unchecked[3],
+            &unchecked[4],
+            1_000,
+            0,
+            true,
+            0,
+        );
+        assert_eq!(result.unwrap_err(), ErrorCode::SwapHookNotSupportedError.into());
+    }
+}
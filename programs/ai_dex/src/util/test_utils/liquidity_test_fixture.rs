@@ -112,6 +112,8 @@ impl LiquidityTestFixture {
         let next_timestamp = self.ai_dex.reward_last_updated_timestamp + seconds;
         self.ai_dex.reward_infos =
             next_ai_dex_reward_infos(&self.ai_dex, next_timestamp).unwrap();
+        self.ai_dex.seconds_per_liquidity_global_x64 =
+            next_seconds_per_liquidity_global(&self.ai_dex, next_timestamp).unwrap();
         self.ai_dex.reward_last_updated_timestamp = next_timestamp;
     }
 
@@ -126,7 +128,8 @@ impl LiquidityTestFixture {
             tick,
             self.ai_dex.fee_growth_global_a,
             self.ai_dex.fee_growth_global_b,
-            &self.ai_dex.reward_infos,
+            &{ self.ai_dex.reward_infos },
+            self.ai_dex.seconds_per_liquidity_global_x64,
         )
         .unwrap();
     
@@ -152,6 +155,8 @@ impl LiquidityTestFixture {
         reward_last_updated_timestamp: u64,
     ) {
         assert!(reward_last_updated_timestamp >= self.ai_dex.reward_last_updated_timestamp);
+        self.ai_dex.seconds_per_liquidity_global_x64 =
+            next_seconds_per_liquidity_global(&self.ai_dex, reward_last_updated_timestamp).unwrap();
         self.ai_dex.reward_last_updated_timestamp = reward_last_updated_timestamp;
         self.ai_dex.liquidity = update.ai_dex_liquidity;
         self.ai_dex.reward_infos = update.reward_infos;
@@ -50,6 +50,7 @@ pub struct SwapTestFixtureInfo<'info> {
     pub array_3_ticks: Option<&'info Vec<TestTickInfo>>,
     pub fee_rate: u16,
     pub protocol_fee_rate: u16,
+    pub extended_tick_range: bool,
 }
 
 impl<'info> Default for SwapTestFixtureInfo<'info> {
@@ -76,6 +77,7 @@ impl<'info> Default for SwapTestFixtureInfo<'info> {
             array_3_ticks: None,
             fee_rate: 0,
             protocol_fee_rate: 0,
+            extended_tick_range: false,
         }
     }
 }
@@ -140,6 +142,7 @@ impl SwapTestFixture {
             .fee_growth_global_b(info.fee_growth_global_b)
             .fee_rate(info.fee_rate)
             .protocol_fee_rate(info.protocol_fee_rate)
+            .extended_tick_range(info.extended_tick_range)
             .build();
     
         let array_ticks: Vec<Option<&Vec<TestTickInfo>>> = vec![
@@ -201,6 +204,9 @@ impl SwapTestFixture {
             self.amount_specified_is_input,
             self.a_to_b,
             next_timestamp,
+            0,
+            0,
+            0,
         )
         .unwrap()
     }
@@ -218,6 +224,9 @@ impl SwapTestFixture {
             self.amount_specified_is_input,
             self.a_to_b,
             next_timestamp,
+            0,
+            0,
+            0,
         )
     }
 }
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::state::TickArrayBitmap;
+
+use super::{load_zero_copy, load_zero_copy_mut, SwapTickSequence};
+
+/// Checks whether the tick array bitmap of a pool has been initialized.
+///
+/// Bitmaps are created together with the pool, so pools created before bitmaps were
+/// introduced have none and are tracked without it. The same goes for the bitmaps created
+/// before the tick range was widened, which are shorter and map tick arrays to other bits.
+fn has_tick_array_bitmap(tick_array_bitmap: &AccountInfo) -> bool {
+    tick_array_bitmap.owner == &crate::ID && tick_array_bitmap.data_len() >= TickArrayBitmap::LEN
+}
+
+/// Marks a newly initialized tick array in the tick array bitmap of its pool.
+///
+/// # Arguments
+///
+/// * `tick_array_bitmap` - The tick array bitmap PDA of the pool.
+/// * `start_tick_index` - The start tick index of the tick array.
+/// * `tick_spacing` - The tick spacing of the pool.
+///
+/// # Errors
+///
+/// Returns an error if the bitmap cannot be loaded or the start tick index is invalid.
+pub fn mark_tick_array_initialized<'info>(
+    tick_array_bitmap: &UncheckedAccount<'info>,
+    start_tick_index: i32,
+    tick_spacing: u16,
+) -> Result<()> {
+    if has_tick_array_bitmap(tick_array_bitmap) {
+        load_zero_copy_mut::<TickArrayBitmap>(tick_array_bitmap)?
+            .set_initialized(start_tick_index, tick_spacing)?;
+    }
+
+    Ok(())
+}
+
+/// Lets the swap tick sequence use the tick array bitmap of the pool, if it has one.
+///
+/// # Arguments
+///
+/// * `tick_array_bitmap` - The tick array bitmap PDA of the pool.
+/// * `swap_tick_sequence` - The tick arrays used by the swap.
+/// * `tick_spacing` - The tick spacing of the pool.
+/// * `a_to_b` - The direction of the swap.
+///
+/// # Errors
+///
+/// Returns an error if the bitmap cannot be loaded.
+pub fn apply_tick_array_bitmap<'info>(
+    tick_array_bitmap: &UncheckedAccount<'info>,
+    swap_tick_sequence: &mut SwapTickSequence,
+    tick_spacing: u16,
+    a_to_b: bool,
+) -> Result<()> {
+    if has_tick_array_bitmap(tick_array_bitmap) {
+        let tick_array_bitmap = load_zero_copy::<TickArrayBitmap>(tick_array_bitmap)?;
+        swap_tick_sequence.apply_tick_array_bitmap(&tick_array_bitmap, tick_spacing, a_to_b)?;
+    }
+
+    Ok(())
+}
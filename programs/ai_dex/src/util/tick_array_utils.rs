@@ -0,0 +1,312 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
+use std::cell::{RefCell, RefMut};
+
+use crate::errors::ErrorCode;
+use crate::state::{
+    DynamicTickArray, DynamicTickArrayLoader, Tick, TickArray, TickArrayType, TickUpdate,
+};
+
+use super::{load_zero_copy, load_zero_copy_mut};
+
+/// Maximum number of tick arrays a swap accepts in addition to the three in its context.
+pub const MAX_SUPPLEMENTAL_TICK_ARRAYS: usize = 3;
+
+/// A mutably borrowed tick array of either kind.
+pub enum TickArrayRefMut<'a> {
+    Fixed(RefMut<'a, TickArray>),
+    Dynamic(RefMut<'a, DynamicTickArrayLoader>),
+}
+
+impl<'a> TickArrayType for TickArrayRefMut<'a> {
+    fn start_tick_index(&self) -> i32 {
+        match self {
+            TickArrayRefMut::Fixed(tick_array) => tick_array.start_tick_index(),
+            TickArrayRefMut::Dynamic(tick_array) => tick_array.start_tick_index(),
+        }
+    }
+
+    fn ai_dex_pool(&self) -> Pubkey {
+        match self {
+            TickArrayRefMut::Fixed(tick_array) => tick_array.ai_dex_pool(),
+            TickArrayRefMut::Dynamic(tick_array) => tick_array.ai_dex_pool(),
+        }
+    }
+
+    fn get_next_init_tick_index(
+        &self,
+        tick_index: i32,
+        tick_spacing: u16,
+        a_to_b: bool,
+    ) -> Result<Option<i32>> {
+        match self {
+            TickArrayRefMut::Fixed(tick_array) => {
+                tick_array.get_next_init_tick_index(tick_index, tick_spacing, a_to_b)
+            }
+            TickArrayRefMut::Dynamic(tick_array) => {
+                tick_array.get_next_init_tick_index(tick_index, tick_spacing, a_to_b)
+            }
+        }
+    }
+
+    fn get_tick(&self, tick_index: i32, tick_spacing: u16) -> Result<&Tick> {
+        match self {
+            TickArrayRefMut::Fixed(tick_array) => tick_array.get_tick(tick_index, tick_spacing),
+            TickArrayRefMut::Dynamic(tick_array) => tick_array.get_tick(tick_index, tick_spacing),
+        }
+    }
+
+    fn update_tick(
+        &mut self,
+        tick_index: i32,
+        tick_spacing: u16,
+        update: &TickUpdate,
+    ) -> Result<()> {
+        match self {
+            TickArrayRefMut::Fixed(tick_array) => {
+                tick_array.update_tick(tick_index, tick_spacing, update)
+            }
+            TickArrayRefMut::Dynamic(tick_array) => {
+                tick_array.update_tick(tick_index, tick_spacing, update)
+            }
+        }
+    }
+}
+
+/// A tick array account of either kind, loaded from an unchecked account.
+///
+/// Fixed and dynamic tick arrays share the same PDA, so instructions accept either one
+/// and tell them apart by their account discriminator.
+pub enum TickArrayAccount<'info> {
+    Fixed(AccountInfo<'info>),
+    Dynamic(AccountInfo<'info>),
+}
+
+impl<'info> TickArrayAccount<'info> {
+    /// Loads a tick array account of either kind.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the account is not owned by this program or is not a tick array.
+    pub fn try_from(account_info: &AccountInfo<'info>) -> Result<Self> {
+        if account_info.owner != &crate::ID {
+            return Err(anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram.into());
+        }
+
+        let discriminator: [u8; 8] = {
+            let data = account_info.try_borrow_data()?;
+            if data.len() < 8 {
+                return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorNotFound.into());
+            }
+            let mut discriminator = [0u8; 8];
+            discriminator.copy_from_slice(&data[..8]);
+            discriminator
+        };
+
+        if discriminator == TickArray::discriminator() {
+            Ok(TickArrayAccount::Fixed(account_info.clone()))
+        } else if discriminator == DynamicTickArray::discriminator() {
+            Ok(TickArrayAccount::Dynamic(account_info.clone()))
+        } else {
+            Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into())
+        }
+    }
+
+    /// Loads a tick array account of either kind and checks that it belongs to the given pool.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the account is not a tick array of this program, or `ConstraintHasOne`
+    /// if it belongs to another pool.
+    pub fn try_from_with_pool(account_info: &AccountInfo<'info>, ai_dex_pool: &Pubkey) -> Result<Self> {
+        let tick_array = Self::try_from(account_info)?;
+        if tick_array.ai_dex_pool()? != *ai_dex_pool {
+            return Err(anchor_lang::error::ErrorCode::ConstraintHasOne.into());
+        }
+        Ok(tick_array)
+    }
+
+    pub fn key(&self) -> Pubkey {
+        match self {
+            TickArrayAccount::Fixed(tick_array) => tick_array.key(),
+            TickArrayAccount::Dynamic(tick_array) => tick_array.key(),
+        }
+    }
+
+    pub fn ai_dex_pool(&self) -> Result<Pubkey> {
+        match self {
+            TickArrayAccount::Fixed(tick_array) => Ok(load_zero_copy::<TickArray>(tick_array)?.ai_dex_pool),
+            TickArrayAccount::Dynamic(tick_array) => {
+                let data = tick_array.try_borrow_data()?;
+                Ok(DynamicTickArrayLoader::load(&data[8..]).ai_dex_pool())
+            }
+        }
+    }
+
+    /// Returns a copy of the tick at the given tick-index & tick-spacing.
+    pub fn get_tick(&self, tick_index: i32, tick_spacing: u16) -> Result<Tick> {
+        match self {
+            TickArrayAccount::Fixed(tick_array) => {
+                Ok(*load_zero_copy::<TickArray>(tick_array)?.get_tick(tick_index, tick_spacing)?)
+            }
+            TickArrayAccount::Dynamic(tick_array) => {
+                let data = tick_array.try_borrow_data()?;
+                Ok(*DynamicTickArrayLoader::load(&data[8..]).get_tick(tick_index, tick_spacing)?)
+            }
+        }
+    }
+
+    /// Mutably borrows the tick array, e.g. to build a `SwapTickSequence`.
+    pub fn load_mut(&self) -> Result<TickArrayRefMut<'_>> {
+        match self {
+            TickArrayAccount::Fixed(tick_array) => Ok(TickArrayRefMut::Fixed(load_zero_copy_mut(tick_array)?)),
+            TickArrayAccount::Dynamic(tick_array) => {
+                if !tick_array.is_writable {
+                    return Err(anchor_lang::error::ErrorCode::AccountNotMutable.into());
+                }
+                let data = tick_array.try_borrow_mut_data()?;
+                Ok(TickArrayRefMut::Dynamic(RefMut::map(data, |data| {
+                    DynamicTickArrayLoader::load_mut(&mut data[8..])
+                })))
+            }
+        }
+    }
+
+    /// Copies the tick array, e.g. to build a `SwapTickSequence` from a read-only account.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the account data cannot be borrowed or is not a tick array.
+    pub fn load_copy(&self) -> Result<TickArrayCopy> {
+        match self {
+            TickArrayAccount::Fixed(tick_array) => Ok(TickArrayCopy::Fixed(RefCell::new(
+                bytemuck::bytes_of(&*load_zero_copy::<TickArray>(tick_array)?).to_vec(),
+            ))),
+            TickArrayAccount::Dynamic(tick_array) => {
+                let data = tick_array.try_borrow_data()?;
+                Ok(TickArrayCopy::Dynamic(RefCell::new(data[8..].to_vec())))
+            }
+        }
+    }
+
+    /// Updates the tick at the given tick-index & tick-spacing.
+    ///
+    /// A dynamic tick array grows when the tick gets initialized, with `funder` paying for the
+    /// additional rent, and shrinks when it gets uninitialized. The rent freed by shrinking is
+    /// kept in the account and covers the next growth.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tick cannot be updated or the account cannot be resized.
+    pub fn update_tick(
+        &self,
+        tick_index: i32,
+        tick_spacing: u16,
+        update: &TickUpdate,
+        funder: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let tick_array = match self {
+            TickArrayAccount::Fixed(_) => {
+                return self.load_mut()?.update_tick(tick_index, tick_spacing, update);
+            }
+            TickArrayAccount::Dynamic(tick_array) => tick_array,
+        };
+
+        let initialized = self.get_tick(tick_index, tick_spacing)?.initialized;
+        match (initialized, update.initialized) {
+            (false, true) => {
+                let new_len = tick_array.data_len() + Tick::LEN;
+                let missing_lamports = Rent::get()?
+                    .minimum_balance(new_len)
+                    .saturating_sub(tick_array.lamports());
+                if missing_lamports > 0 {
+                    transfer(
+                        CpiContext::new(
+                            system_program.clone(),
+                            Transfer {
+                                from: funder.clone(),
+                                to: tick_array.clone(),
+                            },
+                        ),
+                        missing_lamports,
+                    )?;
+                }
+                tick_array.realloc(new_len, false)?;
+
+                let mut data = tick_array.try_borrow_mut_data()?;
+                DynamicTickArrayLoader::load_mut(&mut data[8..]).insert_tick(
+                    tick_index,
+                    tick_spacing,
+                    update,
+                )
+            }
+            (true, false) => {
+                {
+                    let mut data = tick_array.try_borrow_mut_data()?;
+                    DynamicTickArrayLoader::load_mut(&mut data[8..])
+                        .remove_tick(tick_index, tick_spacing)?;
+                }
+                tick_array.realloc(tick_array.data_len() - Tick::LEN, false)?;
+                Ok(())
+            }
+            _ => self.load_mut()?.update_tick(tick_index, tick_spacing, update),
+        }
+    }
+}
+
+/// A copy of the data of a tick array of either kind, discriminator excluded.
+///
+/// Changes made through `load_mut` only affect the copy, never the account it was taken from.
+pub enum TickArrayCopy {
+    Fixed(RefCell<Vec<u8>>),
+    Dynamic(RefCell<Vec<u8>>),
+}
+
+impl TickArrayCopy {
+    /// Mutably borrows the copied tick array, e.g. to build a `SwapTickSequence`.
+    pub fn load_mut(&self) -> TickArrayRefMut<'_> {
+        match self {
+            TickArrayCopy::Fixed(data) => TickArrayRefMut::Fixed(RefMut::map(data.borrow_mut(), |data| {
+                bytemuck::from_bytes_mut(&mut data[..])
+            })),
+            TickArrayCopy::Dynamic(data) => TickArrayRefMut::Dynamic(RefMut::map(data.borrow_mut(), |data| {
+                DynamicTickArrayLoader::load_mut(&mut data[..])
+            })),
+        }
+    }
+}
+
+/// Loads the supplemental tick arrays passed through the remaining accounts of a swap.
+///
+/// # Arguments
+///
+/// * `supplemental_tick_arrays` - The parsed supplemental tick array accounts, if any.
+/// * `ai_dex_pool` - The pool the tick arrays must belong to.
+///
+/// # Returns
+///
+/// The tick arrays in the order they were passed.
+///
+/// # Errors
+///
+/// * `ErrorCode::TooManySupplementalTickArraysError` - If more than `MAX_SUPPLEMENTAL_TICK_ARRAYS` are passed.
+/// * Any error from `TickArrayAccount::try_from_with_pool` if an account is not a tick array of the pool.
+pub fn load_supplemental_tick_arrays<'info>(
+    supplemental_tick_arrays: &Option<Vec<AccountInfo<'info>>>,
+    ai_dex_pool: &Pubkey,
+) -> Result<Vec<TickArrayAccount<'info>>> {
+    let accounts = match supplemental_tick_arrays {
+        Some(accounts) => accounts,
+        None => return Ok(Vec::new()),
+    };
+    if accounts.len() > MAX_SUPPLEMENTAL_TICK_ARRAYS {
+        return Err(ErrorCode::TooManySupplementalTickArraysError.into());
+    }
+
+    accounts
+        .iter()
+        .map(|account_info| TickArrayAccount::try_from_with_pool(account_info, ai_dex_pool))
+        .collect()
+}
@@ -1,50 +1,91 @@
-use crate::state::{PositionTradeBatch, AiDexPool};
+use crate::state::{position_nft_metadata, AiDexConfigExtension, PositionTradeBatch, AiDexPool, Vault};
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{self, AssociatedToken};
 use anchor_spl::token::{Mint, Token, TokenAccount};
-use anchor_spl::metadata::{self, CreateMetadataAccountsV3, mpl_token_metadata::types::DataV2};
-use solana_program::program::invoke_signed;
-use spl_token::instruction::{burn_checked, close_account, mint_to, set_authority, AuthorityType};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{metadata_pointer, ExtensionType},
+};
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{
+    Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface,
+};
+use anchor_spl::metadata::{
+    self, CreateMasterEditionV3, CreateMetadataAccountsV3, VerifyCollection,
+    mpl_token_metadata::types::{Collection, DataV2},
+};
+use anchor_lang::system_program::{create_account, CreateAccount};
+use solana_program::program::{invoke, invoke_signed};
+use spl_token_metadata_interface::state::TokenMetadata;
+use spl_token::instruction::{mint_to, set_authority, AuthorityType};
 
 use crate::constants::nft::{
-    ADB_METADATA_SYMBOL, ADB_METADATA_URI, AD_METADATA_NAME,
-    AD_METADATA_SYMBOL, AD_METADATA_URI,
+    ADB_METADATA_SYMBOL, ADB_METADATA_URI, ADC_METADATA_NAME, ADC_METADATA_SYMBOL, ADC_METADATA_URI,
 };
 
+/// The accounts of the collection NFT grouping the positions of a pool.
+pub struct PositionCollectionAccounts<'a, 'info> {
+    pub mint: &'a Account<'info, Mint>,
+    pub metadata: &'a UncheckedAccount<'info>,
+    pub master_edition: &'a UncheckedAccount<'info>,
+}
+
 /// Burns a single token from the user's position token account and closes the account.
 ///
+/// The position token may be an SPL Token or a Token-2022 token.
+///
 /// # Arguments
 ///
 /// * `token_authority` - The signer authority for the token.
-/// * `receiver` - The account to receive the remaining funds.
+/// * `rent_receiver` - The account to receive the reclaimed rent lamports.
 /// * `position_mint` - The mint of the position token.
 /// * `position_token_account` - The user's position token account.
-/// * `token_program` - The token program.
+/// * `token_program` - The token program of the position mint.
 ///
 /// # Errors
 ///
 /// Returns an error if the burn or close account operations fail.
 pub fn burn_and_close_user_position_token<'info>(
     token_authority: &Signer<'info>,
-    receiver: &UncheckedAccount<'info>,
-    position_mint: &Account<'info, Mint>,
-    position_token_account: &Account<'info, TokenAccount>,
-    token_program: &Program<'info, Token>,
+    rent_receiver: &UncheckedAccount<'info>,
+    position_mint: &InterfaceAccount<'info, MintInterface>,
+    position_token_account: &InterfaceAccount<'info, TokenAccountInterface>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    burn_and_close_token(
+        token_authority,
+        rent_receiver,
+        &position_mint.to_account_info(),
+        position_mint.decimals,
+        &position_token_account.to_account_info(),
+        &token_program.to_account_info(),
+    )
+}
+
+/// Burns the single token of a token account and closes the account, for either token program.
+fn burn_and_close_token<'info>(
+    token_authority: &Signer<'info>,
+    rent_receiver: &UncheckedAccount<'info>,
+    mint: &AccountInfo<'info>,
+    decimals: u8,
+    token_account: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
 ) -> Result<()> {
     // Burn a single token in user account
     invoke_signed(
-        &burn_checked(
+        &spl_token_2022::instruction::burn_checked(
             token_program.key,
-            position_token_account.to_account_info().key,
-            position_mint.to_account_info().key,
+            token_account.key,
+            mint.key,
             token_authority.key,
             &[],
             1,
-            position_mint.decimals,
+            decimals,
         )?,
         &[
-            token_program.to_account_info(),
-            position_token_account.to_account_info(),
-            position_mint.to_account_info(),
+            token_program.clone(),
+            token_account.clone(),
+            mint.clone(),
             token_authority.to_account_info(),
         ],
         &[],
@@ -52,17 +93,17 @@ pub fn burn_and_close_user_position_token<'info>(
 
     // Close user account
     invoke_signed(
-        &close_account(
+        &spl_token_2022::instruction::close_account(
             token_program.key,
-            position_token_account.to_account_info().key,
-            receiver.key,
+            token_account.key,
+            rent_receiver.key,
             token_authority.key,
             &[],
         )?,
         &[
-            token_program.to_account_info(),
-            position_token_account.to_account_info(),
-            receiver.to_account_info(),
+            token_program.clone(),
+            token_account.clone(),
+            rent_receiver.to_account_info(),
             token_authority.to_account_info(),
         ],
         &[],
@@ -84,7 +125,7 @@ pub fn burn_and_close_user_position_token<'info>(
 ///
 /// Returns an error if the mint or authority removal fails.
 pub fn mint_position_token_and_remove_authority<'info>(
-    ai_dex: &Account<'info, AiDexPool>,
+    ai_dex: &AccountLoader<'info, AiDexPool>,
     position_mint: &Account<'info, Mint>,
     position_token_account: &Account<'info, TokenAccount>,
     token_program: &Program<'info, Token>,
@@ -112,12 +153,14 @@ pub fn mint_position_token_and_remove_authority<'info>(
 /// * `token_program` - The token program.
 /// * `system_program` - The system program.
 /// * `rent` - The rent sysvar.
+/// * `config_extension` - The config extension of the pool, providing the metadata if any.
+/// * `position_collection` - The collection NFT of the pool, if the position is to be part of it.
 ///
 /// # Errors
 ///
-/// Returns an error if the mint, metadata creation, or authority removal fails.
+/// Returns an error if the mint, metadata creation, collection verification, or authority removal fails.
 pub fn mint_position_token_with_metadata_and_remove_authority<'info>(
-    ai_dex: &Account<'info, AiDexPool>,
+    ai_dex: &AccountLoader<'info, AiDexPool>,
     position_mint: &Account<'info, Mint>,
     position_token_account: &Account<'info, TokenAccount>,
     position_metadata_account: &UncheckedAccount<'info>,
@@ -127,6 +170,8 @@ pub fn mint_position_token_with_metadata_and_remove_authority<'info>(
     token_program: &Program<'info, Token>,
     system_program: &Program<'info, System>,
     rent: &Sysvar<'info, Rent>,
+    config_extension: Option<&AiDexConfigExtension>,
+    position_collection: Option<PositionCollectionAccounts<'_, 'info>>,
 ) -> Result<()> {
     mint_position_token(
         ai_dex,
@@ -135,7 +180,9 @@ pub fn mint_position_token_with_metadata_and_remove_authority<'info>(
         token_program,
     )?;
 
+    let (name, symbol, uri) = position_nft_metadata(config_extension);
     let metadata_mint_auth_account = ai_dex;
+    let ai_dex_state = ai_dex.load()?;
     metadata::create_metadata_accounts_v3(
         CpiContext::new_with_signer(
             metadata_program.to_account_info(),
@@ -148,15 +195,18 @@ pub fn mint_position_token_with_metadata_and_remove_authority<'info>(
                 rent: rent.to_account_info(),
                 system_program: system_program.to_account_info(),
             },
-            &[&metadata_mint_auth_account.seeds()],
+            &[&ai_dex_state.seeds()],
         ),
         DataV2 {
-            name: AD_METADATA_NAME.to_string(),
-            symbol: AD_METADATA_SYMBOL.to_string(),
-            uri: AD_METADATA_URI.to_string(),
+            name,
+            symbol,
+            uri,
             creators: None,
             seller_fee_basis_points: 0,
-            collection: None,
+            collection: position_collection.as_ref().map(|collection| Collection {
+                verified: false,
+                key: collection.mint.key(),
+            }),
             uses: None,
         },
         true,
@@ -164,9 +214,268 @@ pub fn mint_position_token_with_metadata_and_remove_authority<'info>(
         None,
     )?;
 
+    // The pool is the update authority of its collection, so it can verify the position
+    if let Some(collection) = position_collection {
+        metadata::verify_collection(
+            CpiContext::new_with_signer(
+                metadata_program.to_account_info(),
+                VerifyCollection {
+                    payer: funder.to_account_info(),
+                    metadata: position_metadata_account.to_account_info(),
+                    collection_authority: ai_dex.to_account_info(),
+                    collection_mint: collection.mint.to_account_info(),
+                    collection_metadata: collection.metadata.to_account_info(),
+                    collection_master_edition: collection.master_edition.to_account_info(),
+                },
+                &[&ai_dex_state.seeds()],
+            ),
+            None,
+        )?;
+    }
+
     remove_position_token_mint_authority(ai_dex, position_mint, token_program)
 }
 
+/// Mints the collection NFT grouping the positions of a pool.
+///
+/// The collection token is held by the pool, which is also the update authority of the
+/// collection metadata. The master edition takes over the mint authority, so no other
+/// collection token can be minted.
+///
+/// # Arguments
+///
+/// * `ai_dex` - The AiDex account.
+/// * `collection_mint` - The mint of the collection token.
+/// * `collection_token_account` - The token account of the pool holding the collection token.
+/// * `collection_metadata_account` - The collection metadata account.
+/// * `collection_master_edition` - The collection master edition account.
+/// * `funder` - The funder of the metadata and master edition accounts.
+/// * `metadata_program` - The metadata program.
+/// * `token_program` - The token program.
+/// * `system_program` - The system program.
+/// * `rent` - The rent sysvar.
+///
+/// # Errors
+///
+/// Returns an error if the mint, metadata creation, or master edition creation fails.
+pub fn mint_position_collection_token<'info>(
+    ai_dex: &AccountLoader<'info, AiDexPool>,
+    collection_mint: &Account<'info, Mint>,
+    collection_token_account: &Account<'info, TokenAccount>,
+    collection_metadata_account: &UncheckedAccount<'info>,
+    collection_master_edition: &UncheckedAccount<'info>,
+    funder: &Signer<'info>,
+    metadata_program: &Program<'info, metadata::Metadata>,
+    token_program: &Program<'info, Token>,
+    system_program: &Program<'info, System>,
+    rent: &Sysvar<'info, Rent>,
+) -> Result<()> {
+    mint_position_token(
+        ai_dex,
+        collection_mint,
+        collection_token_account,
+        token_program,
+    )?;
+
+    let ai_dex_state = ai_dex.load()?;
+    metadata::create_metadata_accounts_v3(
+        CpiContext::new_with_signer(
+            metadata_program.to_account_info(),
+            CreateMetadataAccountsV3 {
+                metadata: collection_metadata_account.to_account_info(),
+                mint: collection_mint.to_account_info(),
+                mint_authority: ai_dex.to_account_info(),
+                update_authority: ai_dex.to_account_info(),
+                payer: funder.to_account_info(),
+                rent: rent.to_account_info(),
+                system_program: system_program.to_account_info(),
+            },
+            &[&ai_dex_state.seeds()],
+        ),
+        DataV2 {
+            name: ADC_METADATA_NAME.to_string(),
+            symbol: ADC_METADATA_SYMBOL.to_string(),
+            uri: ADC_METADATA_URI.to_string(),
+            creators: None,
+            seller_fee_basis_points: 0,
+            collection: None,
+            uses: None,
+        },
+        true,
+        true,
+        None,
+    )?;
+
+    metadata::create_master_edition_v3(
+        CpiContext::new_with_signer(
+            metadata_program.to_account_info(),
+            CreateMasterEditionV3 {
+                edition: collection_master_edition.to_account_info(),
+                mint: collection_mint.to_account_info(),
+                update_authority: ai_dex.to_account_info(),
+                mint_authority: ai_dex.to_account_info(),
+                payer: funder.to_account_info(),
+                metadata: collection_metadata_account.to_account_info(),
+                token_program: token_program.to_account_info(),
+                system_program: system_program.to_account_info(),
+                rent: rent.to_account_info(),
+            },
+            &[&ai_dex_state.seeds()],
+        ),
+        Some(0),
+    )
+}
+
+/// Mints a Token-2022 position token carrying its own metadata and removes the mint authority.
+///
+/// The position mint is created with the `MetadataPointer` extension pointing to itself and the
+/// `TokenMetadata` extension, so no Metaplex metadata account is needed. The associated token
+/// account of the owner is created for the position token.
+///
+/// # Arguments
+///
+/// * `ai_dex` - The AiDex account, mint authority of the position token.
+/// * `position_mint` - The position mint keypair, not created yet.
+/// * `position_token_account` - The associated token account of the owner, not created yet.
+/// * `owner` - The owner of the position.
+/// * `metadata_update_auth` - The metadata update authority.
+/// * `funder` - The funder of the mint and token account.
+/// * `token_2022_program` - The Token-2022 program.
+/// * `system_program` - The system program.
+/// * `associated_token_program` - The associated token program.
+/// * `config_extension` - The config extension of the pool, providing the metadata if any.
+///
+/// # Errors
+///
+/// Returns an error if the mint creation, metadata initialization, mint, or authority removal fails.
+pub fn mint_position_token_2022_with_metadata_and_remove_authority<'info>(
+    ai_dex: &AccountLoader<'info, AiDexPool>,
+    position_mint: &Signer<'info>,
+    position_token_account: &UncheckedAccount<'info>,
+    owner: &UncheckedAccount<'info>,
+    metadata_update_auth: &UncheckedAccount<'info>,
+    funder: &Signer<'info>,
+    token_2022_program: &Program<'info, Token2022>,
+    system_program: &Program<'info, System>,
+    associated_token_program: &Program<'info, AssociatedToken>,
+    config_extension: Option<&AiDexConfigExtension>,
+) -> Result<()> {
+    let (name, symbol, uri) = position_nft_metadata(config_extension);
+    let metadata = TokenMetadata {
+        name,
+        symbol,
+        uri,
+        ..Default::default()
+    };
+
+    // The mint is funded for the metadata, which Token-2022 reallocates when it is initialized
+    let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+        ExtensionType::MetadataPointer,
+    ])?;
+    let lamports = Rent::get()?.minimum_balance(space + metadata.tlv_size_of()?);
+    create_account(
+        CpiContext::new(
+            system_program.to_account_info(),
+            CreateAccount {
+                from: funder.to_account_info(),
+                to: position_mint.to_account_info(),
+            },
+        ),
+        lamports,
+        space as u64,
+        token_2022_program.key,
+    )?;
+
+    invoke(
+        &metadata_pointer::instruction::initialize(
+            token_2022_program.key,
+            position_mint.key,
+            None,
+            Some(position_mint.key()),
+        )?,
+        &[position_mint.to_account_info()],
+    )?;
+    invoke(
+        &spl_token_2022::instruction::initialize_mint2(
+            token_2022_program.key,
+            position_mint.key,
+            ai_dex.to_account_info().key,
+            None,
+            0,
+        )?,
+        &[position_mint.to_account_info()],
+    )?;
+
+    let ai_dex_state = ai_dex.load()?;
+    invoke_signed(
+        &spl_token_metadata_interface::instruction::initialize(
+            token_2022_program.key,
+            position_mint.key,
+            metadata_update_auth.key,
+            position_mint.key,
+            ai_dex.to_account_info().key,
+            metadata.name,
+            metadata.symbol,
+            metadata.uri,
+        ),
+        &[
+            position_mint.to_account_info(),
+            metadata_update_auth.to_account_info(),
+            ai_dex.to_account_info(),
+            token_2022_program.to_account_info(),
+        ],
+        &[&ai_dex_state.seeds()],
+    )?;
+
+    associated_token::create(CpiContext::new(
+        associated_token_program.to_account_info(),
+        associated_token::Create {
+            payer: funder.to_account_info(),
+            associated_token: position_token_account.to_account_info(),
+            authority: owner.to_account_info(),
+            mint: position_mint.to_account_info(),
+            system_program: system_program.to_account_info(),
+            token_program: token_2022_program.to_account_info(),
+        },
+    ))?;
+
+    invoke_signed(
+        &spl_token_2022::instruction::mint_to(
+            token_2022_program.key,
+            position_mint.key,
+            position_token_account.key,
+            ai_dex.to_account_info().key,
+            &[],
+            1,
+        )?,
+        &[
+            position_mint.to_account_info(),
+            position_token_account.to_account_info(),
+            ai_dex.to_account_info(),
+            token_2022_program.to_account_info(),
+        ],
+        &[&ai_dex_state.seeds()],
+    )?;
+
+    invoke_signed(
+        &spl_token_2022::instruction::set_authority(
+            token_2022_program.key,
+            position_mint.key,
+            Option::None,
+            spl_token_2022::instruction::AuthorityType::MintTokens,
+            ai_dex.to_account_info().key,
+            &[],
+        )?,
+        &[
+            position_mint.to_account_info(),
+            ai_dex.to_account_info(),
+            token_2022_program.to_account_info(),
+        ],
+        &[&ai_dex_state.seeds()],
+    )?;
+    Ok(())
+}
+
 /// Mints a single position token to the specified token account.
 ///
 /// # Arguments
@@ -180,11 +489,12 @@ pub fn mint_position_token_with_metadata_and_remove_authority<'info>(
 ///
 /// Returns an error if the mint operation fails.
 fn mint_position_token<'info>(
-    ai_dex: &Account<'info, AiDexPool>,
+    ai_dex: &AccountLoader<'info, AiDexPool>,
     position_mint: &Account<'info, Mint>,
     position_token_account: &Account<'info, TokenAccount>,
     token_program: &Program<'info, Token>,
 ) -> Result<()> {
+    let ai_dex_state = ai_dex.load()?;
     invoke_signed(
         &mint_to(
             token_program.key,
@@ -200,7 +510,7 @@ fn mint_position_token<'info>(
             ai_dex.to_account_info(),
             token_program.to_account_info(),
         ],
-        &[&ai_dex.seeds()],
+        &[&ai_dex_state.seeds()],
     )?;
     Ok(())
 }
@@ -217,10 +527,11 @@ fn mint_position_token<'info>(
 ///
 /// Returns an error if the authority removal fails.
 fn remove_position_token_mint_authority<'info>(
-    ai_dex: &Account<'info, AiDexPool>,
+    ai_dex: &AccountLoader<'info, AiDexPool>,
     position_mint: &Account<'info, Mint>,
     token_program: &Program<'info, Token>,
 ) -> Result<()> {
+    let ai_dex_state = ai_dex.load()?;
     invoke_signed(
         &set_authority(
             token_program.key,
@@ -235,7 +546,7 @@ fn remove_position_token_mint_authority<'info>(
             ai_dex.to_account_info(),
             token_program.to_account_info(),
         ],
-        &[&ai_dex.seeds()],
+        &[&ai_dex_state.seeds()],
     )?;
     Ok(())
 }
@@ -444,7 +755,7 @@ fn remove_position_trade_batch_token_mint_authority<'info>(
 /// # Arguments
 ///
 /// * `position_trade_batch_authority` - The signer authority for the position trade batch.
-/// * `receiver` - The account to receive the remaining funds.
+/// * `rent_receiver` - The account to receive the reclaimed rent lamports.
 /// * `position_trade_batch_mint` - The mint of the position trade batch token.
 /// * `position_trade_batch_token_account` - The position trade batch token account.
 /// * `token_program` - The token program.
@@ -454,17 +765,177 @@ fn remove_position_trade_batch_token_mint_authority<'info>(
 /// Returns an error if the burn or close account operations fail.
 pub fn burn_and_close_position_trade_batch_token<'info>(
     position_trade_batch_authority: &Signer<'info>,
-    receiver: &UncheckedAccount<'info>,
+    rent_receiver: &UncheckedAccount<'info>,
     position_trade_batch_mint: &Account<'info, Mint>,
     position_trade_batch_token_account: &Account<'info, TokenAccount>,
     token_program: &Program<'info, Token>,
 ) -> Result<()> {
     // use same logic
-    burn_and_close_user_position_token(
+    burn_and_close_token(
         position_trade_batch_authority,
-        receiver,
-        position_trade_batch_mint,
-        position_trade_batch_token_account,
-        token_program,
+        rent_receiver,
+        &position_trade_batch_mint.to_account_info(),
+        position_trade_batch_mint.decimals,
+        &position_trade_batch_token_account.to_account_info(),
+        &token_program.to_account_info(),
     )
 }
+
+/// Mints LP tokens of a full range only pool to a token account.
+///
+/// # Arguments
+///
+/// * `ai_dex` - The AiDex account, the mint authority of the LP token.
+/// * `lp_mint` - The mint of the LP token.
+/// * `lp_token_account` - The account to receive the minted LP tokens.
+/// * `token_program` - The token program.
+/// * `amount` - The amount of LP tokens to mint.
+///
+/// # Errors
+///
+/// Returns an error if the mint operation fails.
+pub fn mint_lp_token<'info>(
+    ai_dex: &AccountLoader<'info, AiDexPool>,
+    lp_mint: &Account<'info, Mint>,
+    lp_token_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let ai_dex_state = ai_dex.load()?;
+    invoke_signed(
+        &mint_to(
+            token_program.key,
+            lp_mint.to_account_info().key,
+            lp_token_account.to_account_info().key,
+            ai_dex.to_account_info().key,
+            &[],
+            amount,
+        )?,
+        &[
+            lp_mint.to_account_info(),
+            lp_token_account.to_account_info(),
+            ai_dex.to_account_info(),
+            token_program.to_account_info(),
+        ],
+        &[&ai_dex_state.seeds()],
+    )?;
+    Ok(())
+}
+
+/// Burns LP tokens of a full range only pool from a token account of the signer.
+///
+/// # Arguments
+///
+/// * `token_authority` - The owner of the LP token account.
+/// * `lp_mint` - The mint of the LP token.
+/// * `lp_token_account` - The account to burn the LP tokens from.
+/// * `token_program` - The token program.
+/// * `amount` - The amount of LP tokens to burn.
+///
+/// # Errors
+///
+/// Returns an error if the burn operation fails.
+pub fn burn_lp_token<'info>(
+    token_authority: &Signer<'info>,
+    lp_mint: &Account<'info, Mint>,
+    lp_token_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    invoke(
+        &spl_token::instruction::burn(
+            token_program.key,
+            lp_token_account.to_account_info().key,
+            lp_mint.to_account_info().key,
+            token_authority.key,
+            &[],
+            amount,
+        )?,
+        &[
+            lp_token_account.to_account_info(),
+            lp_mint.to_account_info(),
+            token_authority.to_account_info(),
+            token_program.to_account_info(),
+        ],
+    )?;
+    Ok(())
+}
+
+/// Mints shares of a vault to a token account.
+///
+/// # Arguments
+///
+/// * `vault` - The vault, the mint authority of its shares.
+/// * `share_mint` - The mint of the vault shares.
+/// * `share_token_account` - The account to receive the minted shares.
+/// * `token_program` - The token program.
+/// * `amount` - The amount of shares to mint.
+///
+/// # Errors
+///
+/// Returns an error if the mint operation fails.
+pub fn mint_vault_shares<'info>(
+    vault: &Account<'info, Vault>,
+    share_mint: &Account<'info, Mint>,
+    share_token_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    invoke_signed(
+        &mint_to(
+            token_program.key,
+            share_mint.to_account_info().key,
+            share_token_account.to_account_info().key,
+            vault.to_account_info().key,
+            &[],
+            amount,
+        )?,
+        &[
+            share_mint.to_account_info(),
+            share_token_account.to_account_info(),
+            vault.to_account_info(),
+            token_program.to_account_info(),
+        ],
+        &[&vault.seeds()],
+    )?;
+    Ok(())
+}
+
+/// Burns shares of a vault from a token account of the signer.
+///
+/// # Arguments
+///
+/// * `token_authority` - The owner of the share token account.
+/// * `share_mint` - The mint of the vault shares.
+/// * `share_token_account` - The account to burn the shares from.
+/// * `token_program` - The token program.
+/// * `amount` - The amount of shares to burn.
+///
+/// # Errors
+///
+/// Returns an error if the burn operation fails.
+pub fn burn_vault_shares<'info>(
+    token_authority: &Signer<'info>,
+    share_mint: &Account<'info, Mint>,
+    share_token_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    invoke(
+        &spl_token::instruction::burn(
+            token_program.key,
+            share_token_account.to_account_info().key,
+            share_mint.to_account_info().key,
+            token_authority.key,
+            &[],
+            amount,
+        )?,
+        &[
+            share_token_account.to_account_info(),
+            share_mint.to_account_info(),
+            token_authority.to_account_info(),
+            token_program.to_account_info(),
+        ],
+    )?;
+    Ok(())
+}
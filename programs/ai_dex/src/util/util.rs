@@ -1,12 +1,22 @@
 use anchor_lang::{
     prelude::{AccountInfo, Pubkey, Signer, *},
-    ToAccountInfo,
+    ToAccountInfo, ZeroCopy,
+};
+use anchor_lang::system_program::{
+    allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer,
 };
 use anchor_spl::token::TokenAccount;
+use anchor_spl::token_interface::TokenAccount as TokenAccountInterface;
 use solana_program::program_option::COption;
+use std::cell::{Ref, RefMut};
 use std::convert::TryFrom;
+use std::mem;
 
 use crate::errors::ErrorCode;
+use crate::state::{
+    AiDexPool, PoolAllowlist, PoolAllowlistKind, Position, PositionTradeBatch, TradeBatchExtension,
+    VersionedAccount, POSITION_TRADE_BATCH_SIZE,
+};
 
 /// Verifies the authority of a position trade batch token account.
 ///
@@ -28,14 +38,20 @@ pub fn verify_position_trade_batch_authority<'info>(
     position_trade_batch_authority: &Signer<'info>,
 ) -> Result<()> {
     // use same logic
-    verify_position_authority(position_trade_batch_token_account, position_trade_batch_authority)
+    verify_token_account_authority(
+        &position_trade_batch_token_account.owner,
+        &position_trade_batch_token_account.delegate,
+        position_trade_batch_token_account.delegated_amount,
+        position_trade_batch_authority,
+    )
 }
 
 /// Verifies the authority of a position token account.
 ///
 /// This function checks if the provided position token account has the correct authority.
 /// If the position token account has a delegate, it checks if the authority matches the delegate.
-/// Otherwise, it checks if the authority matches the owner.
+/// Otherwise, it checks if the authority matches the owner. The position token may be held in an
+/// SPL Token or a Token-2022 account.
 ///
 /// # Arguments
 ///
@@ -48,26 +64,75 @@ pub fn verify_position_trade_batch_authority<'info>(
 /// * The authority is missing or invalid.
 /// * The position token amount is invalid.
 pub fn verify_position_authority<'info>(
-    position_token_account: &TokenAccount,
+    position_token_account: &TokenAccountInterface,
     position_authority: &Signer<'info>,
 ) -> Result<()> {
-    if let COption::Some(ref delegate) = position_token_account.delegate {
-        if position_authority.key == delegate {
-            validate_owner(delegate, &position_authority.to_account_info())?;
-            if position_token_account.delegated_amount != 1 {
+    verify_token_account_authority(
+        &position_token_account.owner,
+        &position_token_account.delegate,
+        position_token_account.delegated_amount,
+        position_authority,
+    )
+}
+
+/// Verifies that the authority is the owner of a token account holding a position or trade batch
+/// token, or its delegate for that token.
+fn verify_token_account_authority<'info>(
+    owner: &Pubkey,
+    delegate: &COption<Pubkey>,
+    delegated_amount: u64,
+    authority: &Signer<'info>,
+) -> Result<()> {
+    if let COption::Some(ref delegate) = delegate {
+        if authority.key == delegate {
+            validate_owner(delegate, &authority.to_account_info())?;
+            if delegated_amount != 1 {
                 return Err(ErrorCode::InvalidPositionTokenAmountError.into());
             }
         } else {
-            validate_owner(
-                &position_token_account.owner,
-                &position_authority.to_account_info(),
-            )?;
+            validate_owner(owner, &authority.to_account_info())?;
         }
     } else {
-        validate_owner(
-            &position_token_account.owner,
-            &position_authority.to_account_info(),
-        )?;
+        validate_owner(owner, &authority.to_account_info())?;
+    }
+    Ok(())
+}
+
+/// Verifies the authority of a position, also accepting the operator of the position.
+///
+/// The holder of the position token or its delegate are verified as in `verify_position_authority`.
+/// If the signer is the operator of the position instead, every account receiving tokens must be
+/// owned by the owner of the position token account.
+///
+/// # Arguments
+///
+/// * `position_token_account` - The position token account to verify.
+/// * `position` - The position the token account holds.
+/// * `position_authority` - The holder, delegate or operator of the position.
+/// * `destination_owners` - The owners of the accounts receiving tokens from the instruction.
+///
+/// # Errors
+///
+/// This function returns an error if:
+/// * The authority is missing or invalid.
+/// * The position token amount is invalid.
+/// * The operator withdraws to an account not owned by the position owner.
+pub fn verify_position_authority_or_operator<'info>(
+    position_token_account: &TokenAccountInterface,
+    position: &Position,
+    position_authority: &Signer<'info>,
+    destination_owners: &[Pubkey],
+) -> Result<()> {
+    if !position.is_operator(position_authority.key) {
+        return verify_position_authority(position_token_account, position_authority);
+    }
+
+    validate_owner(&position.operator, &position_authority.to_account_info())?;
+    if destination_owners
+        .iter()
+        .any(|owner| *owner != position_token_account.owner)
+    {
+        return Err(ErrorCode::InvalidOperatorDestinationError.into());
     }
     Ok(())
 }
@@ -92,6 +157,71 @@ fn validate_owner(expected_owner: &Pubkey, owner_account_info: &AccountInfo) ->
     Ok(())
 }
 
+/// Verifies that a transaction is executed before its deadline.
+///
+/// # Arguments
+///
+/// * `deadline_timestamp` - The optional unix timestamp after which the transaction is rejected.
+/// * `unix_timestamp` - The current on-chain unix timestamp.
+///
+/// # Errors
+///
+/// This function returns an error if the current timestamp is past the deadline.
+pub fn check_deadline(deadline_timestamp: Option<i64>, unix_timestamp: i64) -> Result<()> {
+    if let Some(deadline_timestamp) = deadline_timestamp {
+        if unix_timestamp > deadline_timestamp {
+            return Err(ErrorCode::TransactionExpiredError.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Verifies that an authority may perform an operation restricted by an allowlist of a pool.
+///
+/// Nothing is checked while the allowlist is disabled. Otherwise the authority must be on the
+/// allowlist, or own a token account holding a token of its gating mint.
+///
+/// # Arguments
+///
+/// * `ai_dex` - The pool the operation is performed on.
+/// * `kind` - The kind of the allowlist restricting the operation.
+/// * `allowlist` - The allowlist of the pool, if provided.
+/// * `authority` - The authority performing the operation.
+/// * `gating_token_account` - The token account of the authority holding a gating token, if provided.
+///
+/// # Errors
+///
+/// This function returns an error if the allowlist is enabled and the authority is neither on it
+/// nor holds a gating token.
+pub fn verify_pool_allowlist(
+    ai_dex: &AiDexPool,
+    kind: PoolAllowlistKind,
+    allowlist: Option<&Account<'_, PoolAllowlist>>,
+    authority: &Pubkey,
+    gating_token_account: Option<&InterfaceAccount<'_, TokenAccountInterface>>,
+) -> Result<()> {
+    if !ai_dex.allowlist_enabled(kind) {
+        return Ok(());
+    }
+
+    let allowed = match allowlist {
+        Some(allowlist) => {
+            allowlist.contains(authority)
+                || gating_token_account.map_or(false, |token_account| {
+                    token_account.owner == *authority
+                        && allowlist.is_gating_token(&token_account.mint, token_account.amount)
+                })
+        }
+        None => false,
+    };
+    if !allowed {
+        return Err(ErrorCode::AuthorityNotAllowlistedError.into());
+    }
+
+    Ok(())
+}
+
 /// Converts a timestamp from `i64` to `u64`.
 ///
 /// This function converts a timestamp from `i64` to `u64`.
@@ -106,3 +236,250 @@ fn validate_owner(expected_owner: &Pubkey, owner_account_info: &AccountInfo) ->
 pub fn to_timestamp_u64(t: i64) -> Result<u64> {
     u64::try_from(t).or(Err(ErrorCode::TimestampConversionError.into()))
 }
+
+/// Creates a PDA owned by this program, funded by `funder`.
+///
+/// This is what `init` does for a single account, for instructions creating a variable number of
+/// accounts passed as remaining accounts. An address that already holds lamports cannot be created
+/// by the system program, so it is topped up to rent exemption, allocated and assigned instead.
+///
+/// # Arguments
+///
+/// * `funder` - The signer paying the rent.
+/// * `account` - The PDA to create.
+/// * `system_program` - The system program.
+/// * `signer_seeds` - The seeds of the PDA, bump included.
+/// * `space` - The size of the account in bytes.
+/// * `rent` - The rent sysvar.
+pub fn create_program_account<'info>(
+    funder: &Signer<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    signer_seeds: &[&[u8]],
+    space: usize,
+    rent: &Rent,
+) -> Result<()> {
+    let required_lamports = rent.minimum_balance(space);
+    let current_lamports = account.lamports();
+
+    if current_lamports == 0 {
+        return create_account(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                CreateAccount {
+                    from: funder.to_account_info(),
+                    to: account.clone(),
+                },
+                &[signer_seeds],
+            ),
+            required_lamports,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    let missing_lamports = required_lamports.saturating_sub(current_lamports);
+    if missing_lamports > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: funder.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            missing_lamports,
+        )?;
+    }
+    allocate(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            Allocate {
+                account_to_allocate: account.clone(),
+            },
+            &[signer_seeds],
+        ),
+        space as u64,
+    )?;
+    assign(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            Assign {
+                account_to_assign: account.clone(),
+            },
+            &[signer_seeds],
+        ),
+        &crate::ID,
+    )
+}
+
+/// Grows an account of this program to `space` bytes, funded by `funder`.
+///
+/// The added bytes are zeroed, so the fields appended to the layout of an account read as zero
+/// until the account is migrated. The account is topped up to rent exemption for its new size.
+/// An account already at least `space` bytes long is left as is.
+///
+/// # Arguments
+///
+/// * `funder` - The signer paying the rent.
+/// * `account` - The account to grow.
+/// * `system_program` - The system program.
+/// * `space` - The new size of the account in bytes.
+///
+/// # Returns
+///
+/// * `Result<bool>` - Whether the account was grown.
+pub fn realloc_program_account<'info>(
+    funder: &Signer<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    space: usize,
+) -> Result<bool> {
+    if account.data_len() >= space {
+        return Ok(false);
+    }
+
+    let missing_lamports = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(account.lamports());
+    if missing_lamports > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: funder.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            missing_lamports,
+        )?;
+    }
+    account.realloc(space, true)?;
+
+    Ok(true)
+}
+
+/// Upgrades a versioned account of this program to its current layout, funded by `funder`.
+///
+/// The account is grown to the current size of its type, the appended fields reading as zero,
+/// and its version is raised. Migrating an account of the current layout does nothing.
+///
+/// # Arguments
+///
+/// * `funder` - The signer paying the rent of the added bytes.
+/// * `account` - The account to migrate, of type `T`.
+/// * `system_program` - The system program.
+///
+/// # Returns
+///
+/// * `Result<(u8, u8)>` - The versions of the account before and after the migration.
+///
+/// # Errors
+///
+/// * `AccountDiscriminatorMismatch` - If the account is not of type `T`.
+pub fn migrate_program_account<'info, T>(
+    funder: &Signer<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<(u8, u8)>
+where
+    T: AccountSerialize + AccountDeserialize + Owner + Clone + VersionedAccount,
+{
+    realloc_program_account(funder, account, system_program, T::SPACE)?;
+
+    let mut versioned_account = T::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    let old_version = versioned_account.migrate();
+    let new_version = versioned_account.version();
+    versioned_account.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+
+    Ok((old_version, new_version))
+}
+
+/// Borrows the zero-copy account held by an account info, like `AccountLoader::load`.
+///
+/// `AccountLoader` needs the account info to be borrowed for the whole instruction, which
+/// accounts passed as `UncheckedAccount` cannot provide.
+///
+/// # Errors
+///
+/// Returns an error if the account is not owned by this program or is not of type `T`.
+pub fn load_zero_copy<'a, T: ZeroCopy + Owner>(account_info: &'a AccountInfo) -> Result<Ref<'a, T>> {
+    check_zero_copy_account::<T>(account_info)?;
+    let data = account_info.try_borrow_data()?;
+    if data.len() < 8 + mem::size_of::<T>() || data[..8] != T::discriminator() {
+        return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+    }
+
+    Ok(Ref::map(data, |data| bytemuck::from_bytes(&data[8..8 + mem::size_of::<T>()])))
+}
+
+/// Mutably borrows the zero-copy account held by an account info, like `AccountLoader::load_mut`.
+///
+/// # Errors
+///
+/// Returns an error if the account is not writable, not owned by this program or not of type `T`.
+pub fn load_zero_copy_mut<'a, T: ZeroCopy + Owner>(
+    account_info: &'a AccountInfo,
+) -> Result<RefMut<'a, T>> {
+    check_zero_copy_account::<T>(account_info)?;
+    if !account_info.is_writable {
+        return Err(anchor_lang::error::ErrorCode::AccountNotMutable.into());
+    }
+    let data = account_info.try_borrow_mut_data()?;
+    if data.len() < 8 + mem::size_of::<T>() || data[..8] != T::discriminator() {
+        return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+    }
+
+    Ok(RefMut::map(data, |data| {
+        bytemuck::from_bytes_mut(&mut data[8..8 + mem::size_of::<T>()])
+    }))
+}
+
+fn check_zero_copy_account<T: Owner>(account_info: &AccountInfo) -> Result<()> {
+    if account_info.owner != &T::owner() {
+        return Err(anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram.into());
+    }
+    Ok(())
+}
+
+/// Opens or closes a trade batch index of a position trade batch.
+///
+/// Indexes below `POSITION_TRADE_BATCH_SIZE` are tracked by the position trade batch itself, the
+/// following ones by its trade batch extension.
+///
+/// # Arguments
+///
+/// * `position_trade_batch` - The position trade batch owning the index.
+/// * `trade_batch_extension` - The extension of the position trade batch, if provided.
+/// * `trade_batch_index` - The index of the trade batch position.
+/// * `open` - A flag indicating whether to open or close the index.
+///
+/// # Errors
+///
+/// This function returns an error if:
+/// * The index is in the extension range and the extension is not provided.
+/// * The index is invalid or already opened/closed.
+pub fn update_trade_batch_index<'info>(
+    position_trade_batch: &mut PositionTradeBatch,
+    trade_batch_extension: &Option<AccountLoader<'info, TradeBatchExtension>>,
+    trade_batch_index: u16,
+    open: bool,
+) -> Result<()> {
+    if trade_batch_index < POSITION_TRADE_BATCH_SIZE {
+        return if open {
+            position_trade_batch.open_trade_batch_position(trade_batch_index)
+        } else {
+            position_trade_batch.close_trade_batch_position(trade_batch_index)
+        };
+    }
+
+    let mut trade_batch_extension = trade_batch_extension
+        .as_ref()
+        .ok_or(ErrorCode::TradeBatchExtensionRequiredError)?
+        .load_mut()?;
+    if open {
+        trade_batch_extension.open_trade_batch_position(trade_batch_index)
+    } else {
+        trade_batch_extension.close_trade_batch_position(trade_batch_index)
+    }
+}
@@ -3,6 +3,7 @@ use crate::errors::ErrorCode;
 use anchor_lang::prelude::*;
 use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::{TransferFee, MAX_FEE_BASIS_POINTS};
 use anchor_spl::token_interface::spl_token_2022::extension::BaseStateWithExtensions;
+use std::convert::{TryFrom, TryInto};
 
 use anchor_spl::token::Token;
 use anchor_spl::token_2022::spl_token_2022::{self, extension::{self, StateWithExtensions}, state::AccountState};
@@ -10,6 +11,19 @@ use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use anchor_spl::memo::{self, Memo, BuildMemo};
 use spl_transfer_hook_interface;
 
+// Start of the TLV data of a Token-2022 mint: 82 for Mint, 83 for padding and 1 for the account type
+const MINT_TLV_START: usize = 82 + 83 + 1;
+// 2 for the extension type and 2 for the length
+const TLV_HEADER_LEN: usize = 4;
+
+// Extension types newer than the Token-2022 crate the program is built with
+pub const SCALED_UI_AMOUNT_EXTENSION_TYPE: u16 = 25;
+pub const PAUSABLE_EXTENSION_TYPE: u16 = 26;
+// Offset of the paused flag in the PausableConfig data, after the pause authority
+const PAUSABLE_CONFIG_PAUSED_OFFSET: usize = 32;
+// Offset of the amount in a token account, after the mint and the owner
+const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 32 + 32;
+
 /// Transfers tokens from the owner's account to the vault.
 ///
 /// This function performs the following steps:
@@ -18,9 +32,11 @@ use spl_transfer_hook_interface;
 /// 3. Prepares the necessary account infos for the transfer instruction.
 /// 4. Handles any transfer hooks by adding extra accounts if required.
 /// 5. Invokes the transfer instruction.
+/// 6. Accounts for the tokens received by the vault in the reserves of the AiDex.
 ///
 /// # Arguments
 ///
+/// * `ai_dex` - A reference to the AiDex account owning the vault.
 /// * `authority` - A reference to the signer authority.
 /// * `token_mint` - A reference to the token mint account.
 /// * `token_owner_account` - A reference to the token owner's token account.
@@ -39,6 +55,7 @@ use spl_transfer_hook_interface;
 /// Returns an error if there is an issue with logging the transfer fee, creating the transfer instruction,
 /// preparing the account infos, handling the transfer hooks, or invoking the transfer instruction.
 pub fn transfer_from_owner_to_vault<'info>(
+    ai_dex: &AccountLoader<'info, AiDexPool>,
     authority: &Signer<'info>,
     token_mint: &InterfaceAccount<'info, Mint>,
     token_owner_account: &InterfaceAccount<'info, TokenAccount>,
@@ -48,6 +65,11 @@ pub fn transfer_from_owner_to_vault<'info>(
     transfer_hook_accounts: &Option<Vec<AccountInfo<'info>>>,
     amount: u64,
 ) -> Result<()> {
+    // Handle Pausable extension
+    if is_token_mint_paused(token_mint)? {
+        return Err(ErrorCode::TokenMintPausedError.into());
+    }
+
     // Handle TransferFee extension
     if let Some(epoch_transfer_fee) = get_epoch_transfer_fee(token_mint)? {
         // log applied transfer fee
@@ -91,6 +113,9 @@ pub fn transfer_from_owner_to_vault<'info>(
     // Handle TransferHook extension
     if let Some(hook_program_id) = get_transfer_hook_program_id(token_mint)? {
         if let Some(hook_accounts) = transfer_hook_accounts {
+            // Inbound transfers only risk the tokens of the signer, so the token wrapper
+            // of the mint may come from any config
+            verify_transfer_hook_program(token_mint, &hook_program_id, hook_accounts, None)?;
             spl_transfer_hook_interface::onchain::add_extra_accounts_for_execute_cpi(
                 &mut instruction,
                 &mut account_infos,
@@ -108,12 +133,15 @@ pub fn transfer_from_owner_to_vault<'info>(
     }
 
     // Invoke the instruction
+    let vault_amount_before = token_account_amount(token_vault)?;
     solana_program::program::invoke_signed(
         &instruction,
         &account_infos,
         &[],
     )?;
 
+    ai_dex.load_mut()?.track_vault_balance(&token_vault.key(), vault_amount_before, token_account_amount(token_vault)?);
+
     Ok(())
 }
 
@@ -156,6 +184,7 @@ fn build_and_log_memo<'info>(
 /// 4. Prepares the necessary account infos for the transfer instruction.
 /// 5. Handles any transfer hooks by adding extra accounts if required.
 /// 6. Invokes the transfer instruction.
+/// 7. Accounts for the tokens sent by the vault in the reserves of the AiDex.
 ///
 /// # Arguments
 ///
@@ -179,7 +208,7 @@ fn build_and_log_memo<'info>(
 /// creating the transfer instruction, preparing the account infos, handling the transfer hooks,
 /// or invoking the transfer instruction.
 pub fn transfer_from_vault_to_owner<'info>(
-    ai_dex: &Account<'info, AiDexPool>,
+    ai_dex: &AccountLoader<'info, AiDexPool>,
     token_mint: &InterfaceAccount<'info, Mint>,
     token_vault: &InterfaceAccount<'info, TokenAccount>,
     token_owner_account: &InterfaceAccount<'info, TokenAccount>,
@@ -189,6 +218,11 @@ pub fn transfer_from_vault_to_owner<'info>(
     amount: u64,
     memo: &[u8],
 ) -> Result<()> {
+    // Handle Pausable extension
+    if is_token_mint_paused(token_mint)? {
+        return Err(ErrorCode::TokenMintPausedError.into());
+    }
+
     // Handle TransferFee extension
     if let Some(epoch_transfer_fee) = get_epoch_transfer_fee(token_mint)? {
         let transfer_fee_memo = format!(
@@ -228,6 +262,12 @@ pub fn transfer_from_vault_to_owner<'info>(
     // Handle TransferHook extension
     if let Some(hook_program_id) = get_transfer_hook_program_id(token_mint)? {
         if let Some(hook_accounts) = transfer_hook_accounts {
+            verify_transfer_hook_program(
+                token_mint,
+                &hook_program_id,
+                hook_accounts,
+                Some(ai_dex.load()?.ai_dex_config),
+            )?;
             spl_transfer_hook_interface::onchain::add_extra_accounts_for_execute_cpi(
                 &mut instruction,
                 &mut account_infos,
@@ -245,15 +285,132 @@ pub fn transfer_from_vault_to_owner<'info>(
     }
 
     // Invoke the instruction
-    solana_program::program::invoke_signed(
-        &instruction,
-        &account_infos,
-        &[&ai_dex.seeds()],
+    let vault_amount_before = token_account_amount(token_vault)?;
+    {
+        let ai_dex_state = ai_dex.load()?;
+        solana_program::program::invoke_signed(
+            &instruction,
+            &account_infos,
+            &[&ai_dex_state.seeds()],
+        )?;
+    }
+
+    ai_dex.load_mut()?.track_vault_balance(&token_vault.key(), vault_amount_before, token_account_amount(token_vault)?);
+
+    Ok(())
+}
+
+/// Transfers tokens out of a token account owned by a program account, such as a strategy vault.
+///
+/// The transfer is signed with the seeds of the program account. The destination may be a token
+/// vault of the AiDex, whose reserves then account for the tokens received. Mints with a transfer
+/// hook are not supported, since the extra accounts of the hook are not passed along.
+///
+/// # Arguments
+///
+/// * `ai_dex` - A reference to the AiDex account.
+/// * `authority` - The program account owning the source token account.
+/// * `authority_seeds` - The seeds of the program account.
+/// * `token_mint` - A reference to the token mint account.
+/// * `token_source_account` - The token account the tokens are transferred from.
+/// * `token_destination_account` - The token account the tokens are transferred to.
+/// * `token_program` - A reference to the token program interface.
+/// * `memo_program` - A reference to the memo program.
+/// * `amount` - The amount of tokens to transfer.
+/// * `memo` - The memo to be logged if required by the destination.
+///
+/// # Errors
+///
+/// Returns an error if the mint is paused or has a transfer hook, or if the transfer fails.
+pub fn transfer_from_program_account<'info>(
+    ai_dex: &AccountLoader<'info, AiDexPool>,
+    authority: &AccountInfo<'info>,
+    authority_seeds: &[&[u8]],
+    token_mint: &InterfaceAccount<'info, Mint>,
+    token_source_account: &InterfaceAccount<'info, TokenAccount>,
+    token_destination_account: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    memo_program: &Program<'info, Memo>,
+    amount: u64,
+    memo: &[u8],
+) -> Result<()> {
+    // Handle Pausable extension
+    if is_token_mint_paused(token_mint)? {
+        return Err(ErrorCode::TokenMintPausedError.into());
+    }
+
+    // Handle TransferHook extension
+    if get_transfer_hook_program_id(token_mint)?.is_some() {
+        return Err(ErrorCode::MissingExtraAccountsForTransferHookError.into());
+    }
+
+    // Handle TransferFee extension
+    if let Some(epoch_transfer_fee) = get_epoch_transfer_fee(token_mint)? {
+        let transfer_fee_memo = format!(
+            "TFe: {}, {}",
+            u16::from(epoch_transfer_fee.transfer_fee_basis_points),
+            u64::from(epoch_transfer_fee.maximum_fee),
+        );
+        build_and_log_memo(memo_program, transfer_fee_memo.as_bytes())?;
+    }
+
+    // Handle MemoTransfer extension
+    if is_transfer_memo_required(token_destination_account)? {
+        build_and_log_memo(memo_program, memo)?;
+    }
+
+    let instruction = spl_token_2022::instruction::transfer_checked(
+        token_program.key,
+        &token_source_account.key(), // from
+        &token_mint.key(), // mint
+        &token_destination_account.key(), // to
+        authority.key, // authority
+        &[],
+        amount,
+        token_mint.decimals,
     )?;
 
+    let account_infos = [
+        token_program.to_account_info(),
+        token_source_account.to_account_info(),
+        token_mint.to_account_info(),
+        token_destination_account.to_account_info(),
+        authority.clone(),
+    ];
+
+    let destination_amount_before = token_account_amount(token_destination_account)?;
+    solana_program::program::invoke_signed(&instruction, &account_infos, &[authority_seeds])?;
+
+    ai_dex.load_mut()?.track_vault_balance(
+        &token_destination_account.key(),
+        destination_amount_before,
+        token_account_amount(token_destination_account)?,
+    );
+
     Ok(())
 }
 
+/// Returns the current amount of a token account.
+///
+/// The amount is read from the account data, so it reflects the transfers made earlier in the
+/// same instruction, unlike the amount deserialized when the account was loaded.
+///
+/// # Arguments
+///
+/// * `token_account` - A reference to the token account.
+///
+/// # Returns
+///
+/// * `Result<u64>` - The amount of the token account.
+pub fn token_account_amount<'info>(token_account: &InterfaceAccount<'info, TokenAccount>) -> Result<u64> {
+    let token_account_info = token_account.to_account_info();
+    let data = token_account_info.try_borrow_data()?;
+    let amount_bytes = data
+        .get(TOKEN_ACCOUNT_AMOUNT_OFFSET..TOKEN_ACCOUNT_AMOUNT_OFFSET + 8)
+        .ok_or(anchor_lang::error::ErrorCode::AccountDidNotDeserialize)?;
+    Ok(u64::from_le_bytes(amount_bytes.try_into().unwrap()))
+}
+
 /// Retrieves the transfer hook program ID for a given token mint.
 ///
 /// This function checks if the token mint is owned by the Token Program and, if not,
@@ -271,7 +428,7 @@ pub fn transfer_from_vault_to_owner<'info>(
 /// # Errors
 ///
 /// Returns an error if there is an issue with borrowing data or unpacking the mint data.
-fn get_transfer_hook_program_id<'info>(
+pub fn get_transfer_hook_program_id<'info>(
     token_mint: &InterfaceAccount<'info, Mint>,
 ) -> Result<Option<Pubkey>> {
     let token_mint_info = token_mint.to_account_info();
@@ -284,6 +441,53 @@ fn get_transfer_hook_program_id<'info>(
     Ok(extension::transfer_hook::get_program_id(&token_mint_unpacked))
 }
 
+/// Verifies that the transfer hook program of a mint is the one allowlisted by its token wrapper.
+///
+/// The token wrapper of the mint has to be passed along with the transfer hook accounts, so a
+/// hook program changed by the hook authority of the mint is never invoked by the program.
+///
+/// # Arguments
+///
+/// * `token_mint` - A reference to the token mint account.
+/// * `hook_program_id` - The current transfer hook program of the mint.
+/// * `hook_accounts` - The transfer hook accounts, including the token wrapper of the mint.
+/// * `ai_dex_config` - The config the token wrapper must belong to, if any.
+///
+/// # Errors
+///
+/// * `ErrorCode::TransferHookProgramNotAllowedError` - If no token wrapper of the mint allowlisting
+///   the hook program is passed.
+fn verify_transfer_hook_program<'info>(
+    token_mint: &InterfaceAccount<'info, Mint>,
+    hook_program_id: &Pubkey,
+    hook_accounts: &[AccountInfo<'info>],
+    ai_dex_config: Option<Pubkey>,
+) -> Result<()> {
+    // Token wrappers are only created by the program, at their PDA
+    let is_allowlisted = hook_accounts
+        .iter()
+        .filter(|account| *account.owner == crate::id())
+        .any(|account| {
+            let data = match account.try_borrow_data() {
+                Ok(data) => data,
+                Err(_) => return false,
+            };
+            match TokenWrapper::try_deserialize(&mut &data[..]) {
+                Ok(token_wrapper) => {
+                    token_wrapper.token_mint == token_mint.key()
+                        && token_wrapper.transfer_hook_program_id == *hook_program_id
+                        && ai_dex_config.map_or(true, |ai_dex_config| ai_dex_config == token_wrapper.ai_dex_config)
+                }
+                Err(_) => false,
+            }
+        });
+
+    if !is_allowlisted {
+        return Err(ErrorCode::TransferHookProgramNotAllowedError.into());
+    }
+    Ok(())
+}
+
 /// Checks if a transfer memo is required for a given token account.
 ///
 /// This function checks if the token account is owned by the Token Program and, if not,
@@ -323,13 +527,15 @@ fn is_transfer_memo_required<'info>(token_account: &InterfaceAccount<'info, Toke
 /// This function performs several checks to determine if a token mint is supported:
 /// 1. Checks if the mint is owned by the Token Program.
 /// 2. Checks if the mint is the native mint of the Token-2022 Program.
-/// 3. Checks if the mint has a freeze authority and if the token wrapper is initialized.
-/// 4. Unpacks the mint data and iterates over the extension types to handle each case accordingly.
+/// 3. Checks if the mint has a freeze authority and if the token wrapper allows it.
+/// 4. Unpacks the mint data and iterates over the extension types to handle each case accordingly,
+///    including the extension types newer than the Token-2022 crate the program is built with.
 ///
 /// # Arguments
 ///
 /// * `token_mint` - A reference to the token mint account.
-/// * `is_token_wrapper_initialized` - A boolean indicating if the token wrapper is initialized.
+/// * `token_wrapper` - The token wrapper of the mint, if it is initialized. Its capability flags
+///   select the extensions and authorities allowed for the mint.
 ///
 /// # Returns
 ///
@@ -340,9 +546,10 @@ fn is_transfer_memo_required<'info>(token_account: &InterfaceAccount<'info, Toke
 /// Returns an error if there is an issue with borrowing data or unpacking the mint data.
 pub fn is_supported_token_mint<'info>(
     token_mint: &InterfaceAccount<'info, Mint>,
-    is_token_wrapper_initialized: bool,
+    token_wrapper: Option<&TokenWrapper>,
 ) -> Result<bool> {
     let token_mint_info = token_mint.to_account_info();
+    let is_token_wrapper_initialized = token_wrapper.is_some();
 
     // Check if mint is owned by the Token Program
     if *token_mint_info.owner == Token::id() {
@@ -354,16 +561,50 @@ pub fn is_supported_token_mint<'info>(
         return Ok(false);
     }
 
-    // Check if mint has a freeze authority and if the token wrapper is initialized
-    if token_mint.freeze_authority.is_some() && !is_token_wrapper_initialized {
+    // Check if mint has a freeze authority and if the token wrapper allows it
+    if token_mint.freeze_authority.is_some()
+        && !token_wrapper.map_or(false, |token_wrapper| token_wrapper.allow_freeze_authority) {
         return Ok(false);
     }
 
     let token_mint_data = token_mint_info.try_borrow_data()?;
     let token_mint_unpacked = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&token_mint_data)?;
-    let extensions = token_mint_unpacked.get_extension_types()?;
+    let raw_extensions = get_raw_mint_extensions(&token_mint_data)?;
+
+    let mut has_newer_extension = false;
+    for (raw_extension_type, _) in raw_extensions {
+        let extension = match extension::ExtensionType::try_from(&raw_extension_type.to_le_bytes()[..]) {
+            Ok(extension) => extension,
+            // Extensions newer than the Token-2022 crate the program is built with
+            Err(_) => {
+                has_newer_extension = true;
+                match raw_extension_type {
+                    // The multiplier only affects the UI amount, the raw amounts
+                    // transferred and accounted by the pool are unchanged.
+                    SCALED_UI_AMOUNT_EXTENSION_TYPE => continue,
+                    // Transfers fail while the mint is paused, which can lock the liquidity
+                    PAUSABLE_EXTENSION_TYPE => {
+                        if !is_token_wrapper_initialized {
+                            return Ok(false);
+                        }
+                        continue;
+                    }
+                    _ => return Ok(false),
+                }
+            }
+        };
+
+        // The Token-2022 crate cannot look up an extension stored after one it does not know,
+        // so the extensions read by the program must come first.
+        if has_newer_extension && matches!(
+            extension,
+            extension::ExtensionType::TransferFeeConfig |
+            extension::ExtensionType::TransferHook |
+            extension::ExtensionType::DefaultAccountState
+        ) {
+            return Ok(false);
+        }
 
-    for extension in extensions {
         match extension {
             // supported
             extension::ExtensionType::TransferFeeConfig |
@@ -371,6 +612,11 @@ pub fn is_supported_token_mint<'info>(
             extension::ExtensionType::MetadataPointer => {
                 // Supported extensions
             }
+            // The interest only affects the UI amount, the raw amounts
+            // transferred and accounted by the pool are unchanged.
+            extension::ExtensionType::InterestBearingConfig => {
+                // Supported extension
+            }
             // Supported, but non-confidential transfer only
             //
             // AiDexProgram invokes TransferChecked instruction and it supports non-confidential transfer only.
@@ -385,15 +631,28 @@ pub fn is_supported_token_mint<'info>(
                 // When both TransferFeeConfig and ConfidentialTransferMint are initialized,
                 // ConfidentialTransferFeeConfig is also initialized to store encrypted transfer fee amount.
             }
+            // The hook program must be the one allowlisted by the token wrapper
+            extension::ExtensionType::TransferHook => {
+                let hook_program_id = extension::transfer_hook::get_program_id(&token_mint_unpacked);
+                match token_wrapper {
+                    Some(token_wrapper) if token_wrapper.allow_transfer_hook && hook_program_id
+                        .map_or(true, |hook_program_id| hook_program_id == token_wrapper.transfer_hook_program_id) => {}
+                    _ => return Ok(false),
+                }
+            }
             extension::ExtensionType::PermanentDelegate |
-            extension::ExtensionType::TransferHook |
             extension::ExtensionType::MintCloseAuthority |
             extension::ExtensionType::DefaultAccountState => {
-                if !is_token_wrapper_initialized {
+                let is_allowed = token_wrapper.map_or(false, |token_wrapper| match extension {
+                    extension::ExtensionType::PermanentDelegate => token_wrapper.allow_permanent_delegate,
+                    extension::ExtensionType::MintCloseAuthority => token_wrapper.allow_mint_close_authority,
+                    _ => token_wrapper.allow_default_account_state,
+                });
+                if !is_allowed {
                     return Ok(false);
                 }
 
-                // reject if default state is not Initialized even if it has token wrapper
+                // reject if default state is not Initialized even if the token wrapper allows it
                 if let extension::ExtensionType::DefaultAccountState = extension {
                     let default_state = token_mint_unpacked.get_extension::<extension::default_account_state::DefaultAccountState>()?;
                     let initialized: u8 = AccountState::Initialized.into();
@@ -416,6 +675,69 @@ pub fn is_supported_token_mint<'info>(
     return Ok(true);
 }
 
+/// Checks if the transfers of a token mint are paused by the Token-2022 Pausable extension.
+///
+/// # Arguments
+///
+/// * `token_mint` - A reference to the token mint account.
+///
+/// # Returns
+///
+/// * `Result<bool>` - Returns `Ok(true)` if the mint is paused, `Ok(false)` if it is not paused,
+///   has no Pausable extension or is owned by the Token Program.
+///
+/// # Errors
+///
+/// Returns an error if there is an issue with borrowing data or reading the mint extensions.
+pub fn is_token_mint_paused<'info>(token_mint: &InterfaceAccount<'info, Mint>) -> Result<bool> {
+    let token_mint_info = token_mint.to_account_info();
+    if *token_mint_info.owner == Token::id() {
+        return Ok(false);
+    }
+
+    let token_mint_data = token_mint_info.try_borrow_data()?;
+    let paused = get_raw_mint_extensions(&token_mint_data)?
+        .iter()
+        .any(|(raw_extension_type, data)| {
+            *raw_extension_type == PAUSABLE_EXTENSION_TYPE
+                && data.get(PAUSABLE_CONFIG_PAUSED_OFFSET).map_or(false, |paused| *paused != 0)
+        });
+    Ok(paused)
+}
+
+/// Returns the raw type and data of each extension of a Token-2022 mint.
+///
+/// Unlike `StateWithExtensions::get_extension_types`, it does not fail on the extension
+/// types newer than the Token-2022 crate the program is built with.
+///
+/// # Arguments
+///
+/// * `token_mint_data` - The data of the Token-2022 mint account.
+///
+/// # Errors
+///
+/// Returns an error if the TLV data of the mint is malformed.
+fn get_raw_mint_extensions(token_mint_data: &[u8]) -> Result<Vec<(u16, &[u8])>> {
+    let mut raw_extensions = Vec::new();
+    let mut offset = MINT_TLV_START;
+    while offset + TLV_HEADER_LEN <= token_mint_data.len() {
+        let raw_extension_type = u16::from_le_bytes([token_mint_data[offset], token_mint_data[offset + 1]]);
+        // Uninitialized, the rest of the data is unused
+        if raw_extension_type == 0 {
+            break;
+        }
+        let length = u16::from_le_bytes([token_mint_data[offset + 2], token_mint_data[offset + 3]]) as usize;
+        let data_start = offset + TLV_HEADER_LEN;
+        let data_end = data_start + length;
+        if data_end > token_mint_data.len() {
+            return Err(ProgramError::InvalidAccountData.into());
+        }
+        raw_extensions.push((raw_extension_type, &token_mint_data[data_start..data_end]));
+        offset = data_end;
+    }
+    Ok(raw_extensions)
+}
+
 /// Checks if the token wrapper is initialized with the given configuration and mint keys.
 ///
 /// # Arguments
@@ -432,9 +754,28 @@ pub fn is_token_wrapper_initialized<'info>(
     token_mint_key: Pubkey,
     token_wrapper: &UncheckedAccount<'info>,
 ) -> Result<bool> {
+    Ok(load_token_wrapper(ai_dex_config_key, token_mint_key, token_wrapper)?.is_some())
+}
+
+/// Loads the token wrapper if it is initialized with the given configuration and mint keys.
+///
+/// # Arguments
+///
+/// * `ai_dex_config_key` - The public key of the AI DEX configuration.
+/// * `token_mint_key` - The public key of the token mint.
+/// * `token_wrapper` - The unchecked account of the token wrapper.
+///
+/// # Returns
+///
+/// * `Result<Option<TokenWrapper>>` - Returns the token wrapper if it is initialized with the given keys, otherwise `None`.
+pub fn load_token_wrapper<'info>(
+    ai_dex_config_key: Pubkey,
+    token_mint_key: Pubkey,
+    token_wrapper: &UncheckedAccount<'info>,
+) -> Result<Option<TokenWrapper>> {
     // Check if the token wrapper account is owned by the expected program ID
     if *token_wrapper.owner != crate::id() {
-        return Ok(false);
+        return Ok(None);
     }
 
     // Borrow the data from the token wrapper account
@@ -443,10 +784,11 @@ pub fn is_token_wrapper_initialized<'info>(
     let token_wrapper = TokenWrapper::try_deserialize(&mut &token_wrapper_data[..])?;
 
     // Compare the ai_dex_config and token_mint fields with the provided keys
-    Ok(
-        token_wrapper.ai_dex_config == ai_dex_config_key &&
-        token_wrapper.token_mint == token_mint_key
-    )
+    if token_wrapper.ai_dex_config == ai_dex_config_key && token_wrapper.token_mint == token_mint_key {
+        Ok(Some(token_wrapper))
+    } else {
+        Ok(None)
+    }
 }
 
 #[derive(Debug)]
@@ -639,5 +981,208 @@ mod fuzz_tests {
 
             let _ = calculate_transfer_fee_included_amount(&interface_account_mint, amount)?;
         }
+
+        #[test]
+        fn test_scaled_ui_amount_mint_transfer_amounts_are_raw(amount in 0..MAX_AMOUNT) {
+            let mut data = is_supported_token_mint_tests::mint_data_with_extension(
+                SCALED_UI_AMOUNT_EXTENSION_TYPE,
+                &is_supported_token_mint_tests::scaled_ui_amount_config(),
+            );
+
+            let key = Pubkey::new_unique();
+            let mut lamports = 0u64;
+            let owner = anchor_spl::token_2022::ID;
+            let account_info = AccountInfo::new(
+                &key,
+                false,
+                false,
+                &mut lamports,
+                &mut data,
+                &owner,
+                false,
+                0
+            );
+
+            let interface_account_mint = InterfaceAccount::<Mint>::try_from(&account_info).unwrap();
+
+            // Swaps transfer the raw amounts computed by the pool, without any fee or scaling
+            assert!(get_epoch_transfer_fee(&interface_account_mint).unwrap().is_none());
+            assert_eq!(calculate_transfer_fee_included_amount(&interface_account_mint, amount).unwrap().amount, amount);
+            assert_eq!(calculate_transfer_fee_excluded_amount(&interface_account_mint, amount).unwrap().amount, amount);
+        }
+    }
+}
+
+#[cfg(test)]
+mod is_supported_token_mint_tests {
+    use super::*;
+
+    // Offset of the is_initialized flag in the base Mint data
+    const MINT_IS_INITIALIZED_OFFSET: usize = 45;
+    // 82 for Mint and 83 for padding, followed by the account type
+    const MINT_WITH_PADDING_LEN: usize = 82 + 83;
+
+    pub(super) fn mint_data_with_extension(extension_type: u16, extension_data: &[u8]) -> Vec<u8> {
+        let mut data = vec![0u8; MINT_WITH_PADDING_LEN];
+        data[MINT_IS_INITIALIZED_OFFSET] = 1;
+        data.push(1); // Mint
+        data.extend_from_slice(&extension_type.to_le_bytes());
+        data.extend_from_slice(&(extension_data.len() as u16).to_le_bytes());
+        data.extend_from_slice(extension_data);
+        data
+    }
+
+    pub(super) fn scaled_ui_amount_config() -> Vec<u8> {
+        // authority, multiplier, new_multiplier_effective_timestamp and new_multiplier
+        let mut scaled_ui_amount_config = Vec::new();
+        scaled_ui_amount_config.extend_from_slice(Pubkey::new_unique().as_ref());
+        scaled_ui_amount_config.extend_from_slice(&2.5f64.to_le_bytes());
+        scaled_ui_amount_config.extend_from_slice(&0i64.to_le_bytes());
+        scaled_ui_amount_config.extend_from_slice(&2.5f64.to_le_bytes());
+        scaled_ui_amount_config
+    }
+
+    fn is_supported(data: Vec<u8>) -> bool {
+        is_supported_with_wrapper(data, None)
+    }
+
+    fn is_supported_with_wrapper(mut data: Vec<u8>, token_wrapper: Option<&TokenWrapper>) -> bool {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0u64;
+        let owner = anchor_spl::token_2022::ID;
+        let account_info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        let interface_account_mint = InterfaceAccount::<Mint>::try_from(&account_info).unwrap();
+        is_supported_token_mint(&interface_account_mint, token_wrapper).unwrap()
+    }
+
+    fn is_paused(mut data: Vec<u8>) -> bool {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0u64;
+        let owner = anchor_spl::token_2022::ID;
+        let account_info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        let interface_account_mint = InterfaceAccount::<Mint>::try_from(&account_info).unwrap();
+        is_token_mint_paused(&interface_account_mint).unwrap()
+    }
+
+    fn pausable_config(paused: bool) -> Vec<u8> {
+        // authority and paused
+        let mut pausable_config = Pubkey::new_unique().to_bytes().to_vec();
+        pausable_config.push(paused as u8);
+        pausable_config
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_interest_bearing_mint_is_supported() {
+        // rate_authority, initialization_timestamp, pre_update_average_rate,
+        // last_update_timestamp and current_rate of InterestBearingConfig
+        let mut interest_bearing_config = Vec::new();
+        interest_bearing_config.extend_from_slice(Pubkey::new_unique().as_ref());
+        interest_bearing_config.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+        interest_bearing_config.extend_from_slice(&500i16.to_le_bytes());
+        interest_bearing_config.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+        interest_bearing_config.extend_from_slice(&500i16.to_le_bytes());
+
+        let extension_type = extension::ExtensionType::InterestBearingConfig as u16;
+        assert!(is_supported(mint_data_with_extension(extension_type, &interest_bearing_config)));
+    }
+
+    #[test]
+    fn test_scaled_ui_amount_mint_is_supported() {
+        let scaled_ui_amount_config = scaled_ui_amount_config();
+        assert!(is_supported(mint_data_with_extension(SCALED_UI_AMOUNT_EXTENSION_TYPE, &scaled_ui_amount_config)));
+    }
+
+    #[test]
+    fn test_unknown_extension_is_not_supported() {
+        assert!(!is_supported(mint_data_with_extension(SCALED_UI_AMOUNT_EXTENSION_TYPE + 100, &[0u8; 8])));
+    }
+
+    #[test]
+    fn test_transfer_fee_config_after_newer_extension_is_not_supported() {
+        let mut data = mint_data_with_extension(SCALED_UI_AMOUNT_EXTENSION_TYPE, &scaled_ui_amount_config());
+        data.extend_from_slice(&(extension::ExtensionType::TransferFeeConfig as u16).to_le_bytes());
+        data.extend_from_slice(&108u16.to_le_bytes());
+        data.extend_from_slice(&[0u8; 108]);
+        assert!(!is_supported(data));
+    }
+
+    #[test]
+    fn test_pausable_mint_requires_token_wrapper() {
+        let data = mint_data_with_extension(PAUSABLE_EXTENSION_TYPE, &pausable_config(false));
+        assert!(!is_supported_with_wrapper(data.clone(), None));
+        assert!(is_supported_with_wrapper(data, Some(&TokenWrapper::default())));
+    }
+
+    #[test]
+    fn test_transfer_hook_program_must_be_allowlisted() {
+        let hook_program_id = Pubkey::new_unique();
+        // authority and program_id of TransferHook
+        let mut transfer_hook = Pubkey::new_unique().to_bytes().to_vec();
+        transfer_hook.extend_from_slice(hook_program_id.as_ref());
+        let data = mint_data_with_extension(extension::ExtensionType::TransferHook as u16, &transfer_hook);
+
+        let mut token_wrapper = TokenWrapper::default();
+        token_wrapper.set_flags(false, true, false, false, false);
+        assert!(!is_supported_with_wrapper(data.clone(), None));
+        assert!(!is_supported_with_wrapper(data.clone(), Some(&token_wrapper)));
+
+        token_wrapper.set_transfer_hook_program_id(hook_program_id);
+        assert!(is_supported_with_wrapper(data.clone(), Some(&token_wrapper)));
+
+        token_wrapper.set_flags(true, false, true, true, true);
+        assert!(!is_supported_with_wrapper(data, Some(&token_wrapper)));
+    }
+
+    #[test]
+    fn test_token_wrapper_flags_are_checked_individually() {
+        // authority of PermanentDelegate
+        let delegate = Pubkey::new_unique().to_bytes();
+        let data = mint_data_with_extension(extension::ExtensionType::PermanentDelegate as u16, &delegate);
+
+        let mut token_wrapper = TokenWrapper::default();
+        token_wrapper.set_flags(false, true, true, true, true);
+        assert!(!is_supported_with_wrapper(data.clone(), Some(&token_wrapper)));
+
+        token_wrapper.set_flags(true, false, false, false, false);
+        assert!(is_supported_with_wrapper(data, Some(&token_wrapper)));
+
+        // close_authority of MintCloseAuthority
+        let close_authority = Pubkey::new_unique().to_bytes();
+        let data = mint_data_with_extension(extension::ExtensionType::MintCloseAuthority as u16, &close_authority);
+        assert!(!is_supported_with_wrapper(data.clone(), Some(&token_wrapper)));
+
+        token_wrapper.set_flags(false, false, true, false, false);
+        assert!(is_supported_with_wrapper(data, Some(&token_wrapper)));
+    }
+
+    #[test]
+    fn test_is_token_mint_paused() {
+        assert!(is_paused(mint_data_with_extension(PAUSABLE_EXTENSION_TYPE, &pausable_config(true))));
+        assert!(!is_paused(mint_data_with_extension(PAUSABLE_EXTENSION_TYPE, &pausable_config(false))));
+        assert!(!is_paused(mint_data_with_extension(SCALED_UI_AMOUNT_EXTENSION_TYPE, &scaled_ui_amount_config())));
+    }
+
+    #[test]
+    fn test_non_transferable_mint_is_not_supported() {
+        let extension_type = extension::ExtensionType::NonTransferable as u16;
+        assert!(!is_supported(mint_data_with_extension(extension_type, &[])));
+    }
+}